@@ -1,4 +1,6 @@
 //! Example of Macro Code Generation for SEDS
+// the deku derives in the generated code trip this lint
+#![allow(clippy::manual_div_ceil)]
 use deku::DekuContainerRead;
 use seds_macro::seds;
 
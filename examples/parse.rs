@@ -1,9 +1,9 @@
 //! This example shows how to parse an XML file into a `PackageFile` struct
-use serde_xml_rs::from_str;
 use std::env;
 use std::fs;
 
 use seds_rs::eds::raw::PackageFile;
+use seds_rs::parse::Parser;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -15,16 +15,13 @@ fn main() {
     let file_path = &args[1];
     let xml_string = fs::read_to_string(file_path).expect("Could not read the XML file");
 
-    match parse_xml::<serde_xml_rs::Error>(&xml_string) {
-        Ok(data) => println!("{:#?}", data),
-        Err(e) => eprintln!("Failed to parse XML: {:?}", e),
+    match Parser::new(file_path).parse::<PackageFile>(&xml_string) {
+        Ok(parsed) => {
+            for warning in parsed.warnings.iter() {
+                eprint!("{}", warning);
+            }
+            println!("{:#?}", parsed.value)
+        }
+        Err(e) => eprint!("{}", e),
     }
 }
-
-/// Parse an XML string into a `PackageFile` struct
-fn parse_xml<DeError: std::convert::From<serde_xml_rs::Error>>(
-    xml: &str,
-) -> Result<PackageFile, DeError> {
-    let package_file: PackageFile = from_str(xml)?;
-    Ok(package_file)
-}
@@ -2,78 +2,159 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, AttributeArgs, NestedMeta, Lit, Meta};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{parse_macro_input, AttributeArgs, NestedMeta, Lit, LitStr, Meta};
 
 use seds_rs::eds::raw;
 use seds_rs::eds::ast;
+use seds_rs::diagnostic::{Diagnostic, SourceMap};
 use seds_rs::expr::ExpressionContext;
-use seds_rs::eds::resolve::Resolve;
+use seds_rs::expr::sources::{Layer, ParameterLoader};
+use seds_rs::eds::resolve::resolve_all;
 use seds_rs::codegen::codegen_packagefiles;
+use seds_rs::parse::{Parsed, Parser};
+use seds_rs::validate::{Schema, Validator};
 
+/// Generate the data types of SEDS package files, e.g.
+/// `#[seds("eds/package.xml", parameters = "eds/mission_parameters.json")]`.
+/// With `validate = true`, the files are validated against the SEDS schema first, and
+/// `schema = "eds/dot.xsd"` adds the schema of a user-defined DoT to validate against.
+/// Errors are reported at the argument naming the file they come from.
 #[proc_macro_attribute]
 pub fn seds(attr: TokenStream, _item: TokenStream) -> TokenStream {
     let attr_args = parse_macro_input!(attr as AttributeArgs);
 
-    let mut xml_files: Vec<String> = Vec::new();
-    let mut parameters: Option<String> = None;
+    let mut xml_files: Vec<LitStr> = Vec::new();
+    let mut parameters: Option<LitStr> = None;
+    let mut validate = false;
+    let mut schemas: Vec<LitStr> = Vec::new();
 
     for arg in attr_args {
         match arg {
             NestedMeta::Lit(Lit::Str(s)) => {
-                xml_files.push(s.value().into());
+                xml_files.push(s);
             }
             NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("parameters") => {
                 if let Lit::Str(s) = nv.lit {
-                    parameters = Some(s.value().into());
+                    parameters = Some(s);
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("validate") => {
+                if let Lit::Bool(b) = nv.lit {
+                    validate = b.value;
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("schema") => {
+                if let Lit::Str(s) = nv.lit {
+                    schemas.push(s);
                 }
             }
             _ => {}
         }
     }
 
-    let mut package_files = Vec::<ast::PackageFile>::new();
+    let validation = match (validate, schemas.is_empty()) {
+        (false, true) => Ok(()),
+        _ => validate_files(&xml_files, &schemas),
+    };
+    if let Err(error) = validation {
+        return error.to_compile_error().into();
+    }
+
+    match expand(&xml_files, parameters.as_ref()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// a compile error with the rendered diagnostic; the compiler adds the severity itself
+fn error(span: Span, diagnostic: Diagnostic) -> syn::Error {
+    let text = diagnostic.to_string();
+    let text = text.strip_prefix("error: ").unwrap_or(&text);
+    syn::Error::new(span, text.trim_end())
+}
+
+/// validate the files against the SEDS schema extended by the DoT schemas, before they are parsed
+fn validate_files(xml_files: &[LitStr], schemas: &[LitStr]) -> Result<(), syn::Error> {
+    let mut schema = Schema::seds().map_err(|d| error(Span::call_site(), *d))?;
+    for path in schemas.iter() {
+        schema = schema
+            .extend(path.value())
+            .map_err(|d| error(path.span(), *d))?;
+    }
+    let mut errors: Option<syn::Error> = None;
     for xml_file in xml_files.iter() {
-        // Check if file exists
-        let path = std::path::Path::new(xml_file);
-        if !path.exists() {
-            panic!("File {} does not exist", xml_file);
+        let path = xml_file.value();
+        let file_content = std::fs::read_to_string(&path).map_err(|e| {
+            error(
+                xml_file.span(),
+                Diagnostic::error(format!("can not read {}: {}", path, e)),
+            )
+        })?;
+        // one compile error per violation
+        for d in Validator::new(&schema, &path).validate(&file_content).err().unwrap_or_default() {
+            let e = error(xml_file.span(), d);
+            match errors.as_mut() {
+                Some(all) => all.combine(e),
+                None => errors = Some(e),
+            }
         }
+    }
+    errors.map_or(Ok(()), Err)
+}
 
-        // Read the file content
-        let file_content = std::fs::read_to_string(xml_file)
-            .expect(&format!("Failed to read the file: {}", xml_file));
-
-        // Parse the XML content
-        let rpf: raw::PackageFile = serde_xml_rs::from_str(&file_content)
-            .expect(&format!("Failed to parse the file: {}", xml_file));
+fn expand(xml_files: &[LitStr], parameters: Option<&LitStr>) -> Result<TokenStream2, syn::Error> {
+    let ectx = match parameters {
+        Some(params_file) => ParameterLoader::new()
+            .file(Layer::Mission, std::path::Path::new(&params_file.value()))
+            .and_then(|loader| loader.load())
+            .map_err(|e| error(params_file.span(), Diagnostic::error(e.to_string())))?,
+        None => ExpressionContext::new(),
+    };
 
-        let ectx = if let Some(params_file) = &parameters {
-            let params_content = std::fs::read_to_string(params_file)
-                .expect(&format!("Failed to read the parameters file: {}", params_file));
-            
-            let json = serde_json::from_str(&params_content.as_str())
-                .expect(&format!("Failed to parse the parameters file: {}", params_file));
+    let mut sources = Vec::<(Span, SourceMap)>::new();
+    let mut package_files = Vec::<ast::PackageFile>::new();
+    for xml_file in xml_files.iter() {
+        let path = xml_file.value();
+        let file_content = std::fs::read_to_string(&path).map_err(|e| {
+            error(
+                xml_file.span(),
+                Diagnostic::error(format!("can not read {}: {}", path, e)),
+            )
+        })?;
 
-            ExpressionContext::from_json(&json)
-                .expect(format!("Failed to parse the parameters file {}", params_file).as_str())
-        } else {
-            let json = serde_json::json!({});
-            ExpressionContext::from_json(&json).expect("Failed to parse the default parameters")
-        };
+        let Parsed {
+            value: rpf,
+            source,
+            ..
+        } = Parser::new(&path)
+            .parse::<raw::PackageFile>(&file_content)
+            .map_err(|d| error(xml_file.span(), *d))?;
 
-        let pf_result = rpf.resolve(&ectx);
-        if let Err(e) = pf_result {
-            panic!("Failed to resolve the package file: {:?}", e);
-        }
-        let pf = pf_result.unwrap();
+        let pf = resolve_all(&rpf, &ectx).map_err(|errors| {
+            // one compile error per resolve error
+            errors
+                .iter()
+                .map(|e| error(xml_file.span(), source.locate(Diagnostic::from(e))))
+                .reduce(|mut all, e| {
+                    all.combine(e);
+                    all
+                })
+                .unwrap_or_else(|| syn::Error::new(xml_file.span(), "can not resolve"))
+        })?;
         package_files.push(pf);
-    } 
+        sources.push((xml_file.span(), source));
+    }
 
     // Generate Rust code
     let pfs: Vec<&ast::PackageFile> = package_files.iter().collect();
-    let gc_result = codegen_packagefiles(&pfs);
-    if let Err(e) = gc_result {
-        panic!("Codegen Error: {:?}", e);
-    }
-    gc_result.unwrap().into()
+    codegen_packagefiles(&pfs).map_err(|e| {
+        // errors are located by package, report them at the file that defines it
+        let diagnostic = Diagnostic::from(&e);
+        let package = e.path().split('/').next().unwrap_or_default().to_string();
+        match sources.iter().find(|(_, source)| source.contains(&package)) {
+            Some((span, source)) => error(*span, source.locate(diagnostic)),
+            None => error(Span::call_site(), diagnostic),
+        }
+    })
 }
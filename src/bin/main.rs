@@ -2,13 +2,29 @@
 //! TODO: add to this
 use clap::Parser;
 use glob::glob;
+use seds_rs::compat::{self, Compatibility};
+use seds_rs::diagnostic::{Diagnostic, SourceMap};
+use seds_rs::expr::report::{references, ParameterReport};
+use seds_rs::expr::sources::{Layer, ParameterLoader};
 use seds_rs::expr::ExpressionContext;
+use seds_rs::lint::{Level, Linter};
+use seds_rs::parse::{Element, FromXml, Parser as EdsParser, Strictness};
+use seds_rs::validate::{Schema, SemanticValidator, Validator};
+use seds_rs::write::{WriteError, Writer};
 use seds_rs::{
-    codegen::{codegen_packagefiles, rustfmt},
-    eds::{ast::PackageFile, raw, resolve::Resolve},
+    codegen::{
+        codegen_packagefiles, dependency::DependencyGraph, diagram::ContainerHierarchy, rustfmt,
+    },
+    eds::{
+        ast::{MetadataValue, NumericValue, PackageFile},
+        query::{self, EntryMatch, TypeMatch},
+        raw,
+        resolve::resolve_all,
+    },
 };
 use std::fs::{self, OpenOptions};
 use std::io::{self, Read, Write};
+use std::process::ExitCode;
 use std::{fs::File, path::Path};
 
 /// open a file with the correct io Result return
@@ -20,29 +36,37 @@ pub fn open_file(path: &str) -> io::Result<String> {
     Ok(contents)
 }
 
-/// load the necessary JSON parameters
-/// TODO: support EDS Design Parameters
-pub fn load_mission_params_from_file(filepath: &str) -> io::Result<ExpressionContext> {
-    let contents = std::fs::read_to_string(filepath)?;
-    let json: serde_json::Value = serde_json::from_str(&contents)?;
-    ExpressionContext::from_json(&json).ok_or_else(|| {
-        io::Error::new(
-            io::ErrorKind::Other,
-            "Failed to create ExpressionContext from JSON",
-        )
-    })
+/// load the mission and design parameters, merging the layers in order of precedence
+pub fn load_parameters(args: &Inputs) -> io::Result<ExpressionContext> {
+    let mut loader = ParameterLoader::new();
+    for (layer, path) in args
+        .mission_params
+        .iter()
+        .map(|p| (Layer::Mission, p))
+        .chain(args.target_params.iter().map(|p| (Layer::Target, p)))
+    {
+        loader = loader
+            .file(layer, Path::new(path))
+            .map_err(io::Error::other)?;
+    }
+    loader = loader
+        .environment(&args.env_prefix)
+        .map_err(io::Error::other)?;
+    for assignment in args.set.iter() {
+        loader = loader.set(assignment).map_err(io::Error::other)?;
+    }
+    loader.load().map_err(io::Error::other)
 }
 
 #[derive(Parser, Debug)]
 #[clap(version = "1.0", author = "Ethan Lew", about = "seds-rs CLI Tool")]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// XML paths pattern, e.g. eds/**/*xml
-    #[clap(required = true)]
-    paths: Vec<String>,
+    #[clap(subcommand)]
+    command: Option<Command>,
 
-    /// Path to JSON mission parameters file
-    #[clap(short, long)]
-    mission_params: Option<String>,
+    #[clap(flatten)]
+    inputs: Inputs,
 
     /// Output type: stdout, rs, project
     #[clap(short, long, default_value = "stdout")]
@@ -53,16 +77,242 @@ struct Args {
     project_name: Option<String>,
 }
 
-fn main() -> io::Result<()> {
-    let matches = Args::parse();
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Report where each parameter is used, and the missing and unused ones
+    ///
+    /// The datasheets are not resolved, so every missing parameter is reported at once.
+    /// Fails if any parameter is missing.
+    Params(Inputs),
+    /// Validate the datasheets against the SEDS schema, or the schemas of user-defined DoTs
+    /// extending it
+    Validate(Inputs),
+    /// Write the datasheets with their parameters substituted, e.g. to validate them
+    /// against the schema
+    Flatten(Flatten),
+    /// Check the datasheets for quality issues, like missing descriptions
+    ///
+    /// Fails if a lint set to deny reports an issue, e.g. with `--deny all` in CI.
+    Lint(Lint),
+    /// Compare two versions of the datasheets, and classify their changes by whether packets
+    /// of the old version still decode with the new one
+    ///
+    /// Fails if a change is wire-breaking.
+    Diff(Diff),
+    /// Look up data types, entries and metadata in the datasheets, and who references them
+    ///
+    /// Fails if the queried data type or metadata value does not exist.
+    Query(Query),
+    /// Print the dependencies between the data types of the datasheets, or between their
+    /// packages
+    ///
+    /// Fails if the dependencies are cyclic, unless they are printed as a graph.
+    Deps(Deps),
+    /// Draw the baseType hierarchies of the containers of the datasheets, with the constraints
+    /// of the derived containers and their fixed values
+    Hierarchy(Hierarchy),
+}
 
-    let patterns: Vec<_> = matches.paths;
-    let output_type = matches.output;
-    let project_name = matches.project_name;
+/// Arguments of the flatten command
+#[derive(clap::Args, Debug)]
+pub struct Flatten {
+    #[clap(flatten)]
+    inputs: Inputs,
 
-    // Collect all XML paths
+    /// Directory to write the flattened files to, under their file names
+    #[clap(long, value_name = "DIR", default_value = "flattened")]
+    out_dir: String,
+}
+
+/// Arguments of the lint command
+#[derive(clap::Args, Debug)]
+pub struct Lint {
+    #[clap(flatten)]
+    inputs: Inputs,
+
+    /// Do not run a lint, or any with `all`; may be repeated
+    #[clap(short = 'A', long, value_name = "LINT")]
+    allow: Vec<String>,
+
+    /// Report the issues of a lint as warnings; may be repeated
+    #[clap(short = 'W', long, value_name = "LINT")]
+    warn: Vec<String>,
+
+    /// Report the issues of a lint as errors; may be repeated, and applied last
+    #[clap(short = 'D', long, value_name = "LINT")]
+    deny: Vec<String>,
+
+    /// Output format of the issues: text or json
+    #[clap(long, default_value = "text")]
+    format: String,
+}
+
+/// Arguments of the diff command
+#[derive(clap::Args, Debug)]
+pub struct Diff {
+    /// XML paths pattern of the old version of the datasheets, may be repeated; the new
+    /// version is given by the paths of the inputs, and both are resolved with the same
+    /// parameters
+    #[clap(long, required = true, value_name = "PATHS")]
+    old: Vec<String>,
+
+    #[clap(flatten)]
+    inputs: Inputs,
+
+    /// Output format of the report: text or json
+    #[clap(long, default_value = "text")]
+    format: String,
+}
+
+/// the XML elements of data types, the kinds of the query command
+const DATA_TYPE_ELEMENTS: [&str; 8] = [
+    "ArrayDataType",
+    "BooleanDataType",
+    "ContainerDataType",
+    "EnumeratedDataType",
+    "FloatDataType",
+    "IntegerDataType",
+    "StringDataType",
+    "SubRangeDataType",
+];
+
+/// Arguments of the query command
+#[derive(clap::Args, Debug)]
+#[clap(group(clap::ArgGroup::new("query").required(true)))]
+pub struct Query {
+    #[clap(flatten)]
+    inputs: Inputs,
+
+    /// Print a data type by its qualified name, e.g. CCSDS/PriHdr
+    #[clap(long = "type", value_name = "NAME", group = "query")]
+    type_: Option<String>,
+
+    /// Print the data types of an XML element, e.g. ContainerDataType
+    #[clap(long, value_name = "ELEMENT", group = "query", value_parser = DATA_TYPE_ELEMENTS)]
+    kind: Option<String>,
+
+    /// Print the references to a data type; the json also lists the data types that depend
+    /// on it, directly or not
+    #[clap(long, value_name = "NAME", group = "query")]
+    references: Option<String>,
+
+    /// Print the containers derived from a container, directly or not
+    #[clap(long, value_name = "NAME", group = "query")]
+    derived: Option<String>,
+
+    /// Print the entries whose type is a data type
+    #[clap(long, value_name = "NAME", group = "query")]
+    entries_of_type: Option<String>,
+
+    /// Print a metadata value by the names of its categories, e.g. Mission/Version
+    #[clap(long, value_name = "PATH", group = "query")]
+    metadata: Option<String>,
+
+    /// Output format of the results: text or json
+    #[clap(long, default_value = "text")]
+    format: String,
+}
+
+/// Arguments of the deps command
+#[derive(clap::Args, Debug)]
+pub struct Deps {
+    #[clap(flatten)]
+    inputs: Inputs,
+
+    /// Print the dependencies between packages instead of data types
+    #[clap(long)]
+    packages: bool,
+
+    /// Output format: text for the names with their dependencies first, dot or mermaid for
+    /// the graph
+    #[clap(long, default_value = "text")]
+    format: String,
+}
+
+/// Arguments of the hierarchy command
+#[derive(clap::Args, Debug)]
+pub struct Hierarchy {
+    #[clap(flatten)]
+    inputs: Inputs,
+
+    /// Qualified name of the container to draw with the containers derived from it, e.g.
+    /// CCSDS/CommandPacket; all the hierarchies if not given
+    #[clap(long, value_name = "NAME")]
+    root: Option<String>,
+
+    /// Output format: svg or dot
+    #[clap(long, default_value = "svg")]
+    format: String,
+}
+
+/// A datasheet or a package file, depending on the root element of the file
+enum Sheet {
+    DataSheet(raw::DataSheet),
+    PackageFile(raw::PackageFile),
+}
+
+impl FromXml for Sheet {
+    fn from_xml(element: &mut Element<'_, '_>) -> seds_rs::parse::reader::Result<Self> {
+        match element.name() {
+            "DataSheet" => raw::DataSheet::from_xml(element).map(Sheet::DataSheet),
+            _ => raw::PackageFile::from_xml(element).map(Sheet::PackageFile),
+        }
+    }
+}
+
+/// Datasheets and the parameters to resolve them with
+#[derive(clap::Args, Clone, Debug)]
+pub struct Inputs {
+    /// XML paths pattern, e.g. eds/**/*xml
+    #[clap(required = true)]
+    paths: Vec<String>,
+
+    /// Mission parameters file (.json, .toml, .yaml), may be repeated
+    #[clap(short, long)]
+    mission_params: Vec<String>,
+
+    /// Target parameters file, overriding the mission parameters, may be repeated
+    #[clap(short, long)]
+    target_params: Vec<String>,
+
+    /// Override a parameter, e.g. --set CFE_MISSION/MAX_PATH_LEN=64
+    #[clap(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Prefix of environment variables overriding parameters (`__` separates namespaces)
+    #[clap(long, default_value = "SEDS_PARAM_")]
+    env_prefix: String,
+
+    /// Print the parameters and the layer that supplied each of them to stderr
+    #[clap(long)]
+    show_params: bool,
+
+    /// Fail on unknown SEDS elements and attributes instead of warning about them
+    #[clap(long, conflicts_with = "lenient")]
+    strict: bool,
+
+    /// Skip unknown elements, attributes and extensions without warnings
+    #[clap(long)]
+    lenient: bool,
+
+    /// Directory to search for files included by datasheets, may be repeated
+    #[clap(short = 'I', long, value_name = "DIR")]
+    include_path: Vec<String>,
+
+    /// Validate the datasheets against the SEDS schema before parsing them, and check the
+    /// rules of containers and constraints once they are resolved
+    #[clap(long)]
+    validate: bool,
+
+    /// Schema of a user-defined DoT extending the SEDS schema, may be repeated; implies --validate
+    #[clap(long, value_name = "XSD")]
+    schema: Vec<String>,
+}
+
+/// the datasheet files matching the path patterns
+fn find_files(inputs: &Inputs) -> Vec<String> {
     let mut paths = vec![];
-    for pattern in patterns {
+    for pattern in inputs.paths.iter() {
         for path in glob(pattern.as_str())
             .expect("Failed to read glob pattern")
             .flatten()
@@ -70,34 +320,358 @@ fn main() -> io::Result<()> {
             paths.push(path.display().to_string());
         }
     }
+    paths
+}
+
+/// validate the datasheet files against the SEDS schema, extended by the DoT schemas;
+/// the violations of all files are reported at once
+fn validate_files(inputs: &Inputs, paths: &[String]) -> io::Result<()> {
+    let schema = Schema::seds()
+        .and_then(|seds| {
+            inputs
+                .schema
+                .iter()
+                .try_fold(seds, |schema, path| schema.extend(path))
+        })
+        .map_err(|d| io::Error::other(d.to_string()))?;
+    let mut diagnostics = String::new();
+    for fp in paths.iter() {
+        let contents = open_file(fp)?;
+        let validator = inputs
+            .include_path
+            .iter()
+            .fold(Validator::new(&schema, fp), |validator, dir| {
+                validator.include_path(dir)
+            });
+        for d in validator.validate(&contents).err().unwrap_or_default() {
+            if !diagnostics.is_empty() {
+                diagnostics.push('\n');
+            }
+            diagnostics.push_str(&d.to_string());
+        }
+    }
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(io::Error::other(diagnostics))
+    }
+}
 
-    let rpackagefiles: Vec<raw::PackageFile> = paths
+/// parse the datasheet files, with source maps to locate errors in them;
+/// warnings about unknown content are printed to stderr
+fn parse_files<T: FromXml>(inputs: &Inputs, paths: &[String]) -> io::Result<Vec<(SourceMap, T)>> {
+    if inputs.validate || !inputs.schema.is_empty() {
+        validate_files(inputs, paths)?;
+    }
+    let strictness = match (inputs.strict, inputs.lenient) {
+        (true, _) => Strictness::Strict,
+        (_, true) => Strictness::Lenient,
+        _ => Strictness::Warn,
+    };
+    paths
         .iter()
-        .map(|fp| serde_xml_rs::from_str(&open_file(fp)?))
-        .collect::<Result<_, _>>()
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Parse error: {:?}", e)))?;
+        .map(|fp| {
+            let contents = open_file(fp)?;
+            let parser = inputs
+                .include_path
+                .iter()
+                .fold(EdsParser::new(fp).strictness(strictness), |parser, dir| {
+                    parser.include_path(dir)
+                });
+            let parsed = parser
+                .parse::<T>(&contents)
+                .map_err(|d| io::Error::other(d.to_string()))?;
+            for warning in parsed.warnings.iter() {
+                eprint!("{}", warning);
+            }
+            Ok((parsed.source, parsed.value))
+        })
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let matches = Args::parse();
+
+    let result = match matches.command {
+        Some(Command::Params(ref inputs)) => report_parameters(inputs),
+        Some(Command::Validate(ref inputs)) => validate_files(inputs, &find_files(inputs)),
+        Some(Command::Flatten(ref flatten)) => flatten_files(flatten),
+        Some(Command::Lint(ref lint)) => lint_files(lint),
+        Some(Command::Diff(ref diff)) => diff_files(diff),
+        Some(Command::Query(ref query)) => query_files(query),
+        Some(Command::Deps(ref deps)) => print_dependencies(deps),
+        Some(Command::Hierarchy(ref hierarchy)) => print_hierarchy(hierarchy),
+        None => generate(&matches),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprint!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// print where each parameter is used, and the missing and unused ones
+fn report_parameters(inputs: &Inputs) -> io::Result<()> {
+    let paths = find_files(inputs);
+    let files = parse_files::<raw::PackageFile>(inputs, &paths)?;
+    let ectx = load_parameters(inputs)?;
+
+    let mut found = Vec::new();
+    for (source, rpf) in files.iter() {
+        found.extend(references(source.file(), rpf).map_err(io::Error::other)?);
+    }
+    let report = ParameterReport::new(found, &ectx);
+
+    let mut parameters: Vec<_> = report.references.iter().map(|r| &r.parameter).collect();
+    parameters.sort();
+    parameters.dedup();
+    for parameter in parameters {
+        match ectx.source(parameter) {
+            Some(source) => println!("{}    [{}]", parameter, source),
+            None if report.missing.contains(parameter) => println!("{}    [missing]", parameter),
+            None => println!("{}", parameter),
+        }
+        for reference in report.references_to(parameter) {
+            println!("    {}: {}", reference.file, reference.location);
+        }
+    }
+
+    if !report.unused.is_empty() {
+        println!("\nunused parameters:");
+        for parameter in report.unused.iter() {
+            match ectx.source(parameter) {
+                Some(source) => println!("    {}    [{}]", parameter, source),
+                None => println!("    {}", parameter),
+            }
+        }
+    }
+
+    if report.is_complete() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "error: missing parameters: {}\n",
+            report.missing.join(", ")
+        )))
+    }
+}
+
+/// write the datasheets with their parameters substituted to the output directory
+fn flatten_files(flatten: &Flatten) -> io::Result<()> {
+    let inputs = &flatten.inputs;
+    let paths = find_files(inputs);
+    let files = parse_files::<Sheet>(inputs, &paths)?;
+    let ectx = load_parameters(inputs)?;
+    let writer = Writer::new().flatten(&ectx);
+    fs::create_dir_all(&flatten.out_dir)?;
+
+    // report the errors of all files before giving up
+    let mut diagnostics = String::new();
+    for (source, sheet) in files.iter() {
+        let written = match sheet {
+            Sheet::DataSheet(sheet) => writer.write_string(sheet),
+            Sheet::PackageFile(package_file) => writer.write_string(package_file),
+        };
+        match written {
+            Ok(xml) => {
+                let name = Path::new(source.file()).file_name().unwrap_or_default();
+                fs::write(Path::new(&flatten.out_dir).join(name), xml)?;
+            }
+            Err(WriteError::Unresolved(errors)) => {
+                for e in errors.iter() {
+                    if !diagnostics.is_empty() {
+                        diagnostics.push('\n');
+                    }
+                    diagnostics.push_str(&source.locate(Diagnostic::from(e)).to_string());
+                }
+            }
+            Err(err) => return Err(io::Error::other(format!("error: {}\n", err))),
+        }
+    }
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(io::Error::other(diagnostics))
+    }
+}
+
+/// resolve the datasheets; the errors of all files are reported at once
+fn resolve_files(
+    files: &[(SourceMap, raw::PackageFile)],
+    ectx: &ExpressionContext,
+) -> io::Result<Vec<PackageFile>> {
+    let mut packagefiles: Vec<PackageFile> = Vec::new();
+    let mut diagnostics = String::new();
+    for (source, rpf) in files.iter() {
+        match resolve_all(rpf, ectx) {
+            Ok(pf) => packagefiles.push(pf),
+            Err(errors) => {
+                for e in errors.iter() {
+                    if !diagnostics.is_empty() {
+                        diagnostics.push('\n');
+                    }
+                    diagnostics.push_str(&source.locate(Diagnostic::from(e)).to_string());
+                }
+            }
+        }
+    }
+    if diagnostics.is_empty() {
+        Ok(packagefiles)
+    } else {
+        Err(io::Error::other(diagnostics))
+    }
+}
+
+/// true if the output format is json rather than text
+fn is_json(format: &str) -> io::Result<bool> {
+    match format {
+        "text" => Ok(false),
+        "json" => Ok(true),
+        format => Err(io::Error::other(format!(
+            "error: invalid format `{}`, expected text or json\n",
+            format
+        ))),
+    }
+}
+
+/// run the lints over the datasheets and report their issues
+fn lint_files(lint: &Lint) -> io::Result<()> {
+    let mut linter = Linter::new();
+    for (ids, level) in [
+        (&lint.allow, Level::Allow),
+        (&lint.warn, Level::Warn),
+        (&lint.deny, Level::Deny),
+    ] {
+        for id in ids.iter() {
+            linter
+                .set_level(id, level)
+                .map_err(|e| io::Error::other(format!("error: {}\n", e)))?;
+        }
+    }
+    let json = is_json(&lint.format)?;
 
-    let ectx = if let Some(ref params_path) = matches.mission_params {
-        load_mission_params_from_file(params_path)?
+    let inputs = &lint.inputs;
+    let paths = find_files(inputs);
+    let files = parse_files::<raw::PackageFile>(inputs, &paths)?;
+    let ectx = load_parameters(inputs)?;
+    let packagefiles = resolve_files(&files, &ectx)?;
+    let pfs: Vec<&PackageFile> = packagefiles.iter().collect();
+
+    let mut findings = linter.check(&pfs);
+    for finding in findings.iter_mut() {
+        finding.diagnostic = files[finding.file].0.locate(finding.diagnostic.clone());
+    }
+    if json {
+        let findings: Vec<_> = findings.iter().map(|f| f.to_json()).collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&findings).map_err(io::Error::other)?
+        );
+    } else {
+        for finding in findings.iter() {
+            eprintln!("{}", finding.diagnostic);
+        }
+    }
+    let denied = findings.iter().filter(|f| f.level == Level::Deny).count();
+    if denied == 0 {
+        Ok(())
     } else {
-        ExpressionContext::new()
+        Err(io::Error::other(format!(
+            "error: {} issues of denied lints\n",
+            denied
+        )))
+    }
+}
+
+/// report the changes between two versions of the datasheets
+fn diff_files(diff: &Diff) -> io::Result<()> {
+    let json = is_json(&diff.format)?;
+    let new_inputs = &diff.inputs;
+    let old_inputs = Inputs {
+        paths: diff.old.clone(),
+        ..new_inputs.clone()
     };
+    let ectx = load_parameters(new_inputs)?;
+    let mut versions = Vec::new();
+    for inputs in [&old_inputs, new_inputs] {
+        let files = parse_files::<raw::PackageFile>(inputs, &find_files(inputs))?;
+        versions.push(resolve_files(&files, &ectx)?);
+    }
+    let old: Vec<&PackageFile> = versions[0].iter().collect();
+    let new: Vec<&PackageFile> = versions[1].iter().collect();
 
-    let packagefiles: Vec<PackageFile> = rpackagefiles
-        .iter()
-        .map(|rpf| rpf.resolve(&ectx))
-        .collect::<Result<_, _>>()
-        .map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::Other,
-                format!("Expression resolution error: {:?}", e),
-            )
-        })?;
+    let report = compat::diff(&old, &new);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report.to_json()).map_err(io::Error::other)?
+        );
+    } else {
+        print!("{}", report);
+    }
+    let breaking = report.changes(Compatibility::Breaking).count();
+    if breaking == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "error: {} wire-breaking changes\n",
+            breaking
+        )))
+    }
+}
+
+/// generate the code of the datasheets
+fn generate(matches: &Args) -> io::Result<()> {
+    let inputs = &matches.inputs;
+    let output_type = matches.output.clone();
+    let project_name = matches.project_name.clone();
+
+    // Collect all XML paths
+    let paths = find_files(inputs);
+    let files = parse_files::<raw::PackageFile>(inputs, &paths)?;
+
+    let ectx = load_parameters(inputs)?;
+    if inputs.show_params {
+        for (path, value) in ectx.parameters() {
+            match ectx.source(&path) {
+                Some(source) => eprintln!("{} = {}    [{}]", path, value, source),
+                None => eprintln!("{} = {}", path, value),
+            }
+        }
+    }
+
+    let packagefiles = resolve_files(&files, &ectx)?;
     let pfs: Vec<&PackageFile> = packagefiles.iter().collect();
-    let code_tokens = codegen_packagefiles(&pfs)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Codegen error: {:?}", e)))?;
+    if inputs.validate || !inputs.schema.is_empty() {
+        // every file was resolved, in the order of the files
+        let mut diagnostics = String::new();
+        let validator = SemanticValidator::new(&pfs);
+        for ((source, _), pf) in files.iter().zip(pfs.iter()) {
+            for d in validator.validate(pf).err().unwrap_or_default() {
+                if !diagnostics.is_empty() {
+                    diagnostics.push('\n');
+                }
+                diagnostics.push_str(&source.locate(d).to_string());
+            }
+        }
+        if !diagnostics.is_empty() {
+            return Err(io::Error::other(diagnostics));
+        }
+    }
+    let code_tokens = codegen_packagefiles(&pfs).map_err(|e| {
+        // errors are located by package, find the file that defines it
+        let diagnostic = Diagnostic::from(&e);
+        let package = e.path().split('/').next().unwrap_or_default().to_string();
+        let diagnostic = match files.iter().find(|(source, _)| source.contains(&package)) {
+            Some((source, _)) => source.locate(diagnostic),
+            None => diagnostic,
+        };
+        io::Error::other(diagnostic.to_string())
+    })?;
     let code = rustfmt(code_tokens)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Rustfmt error: {:?}", e)))?;
+        .map_err(|e| io::Error::other(format!("error: rustfmt failed: {}\n", e)))?;
 
     match output_type.as_str() {
         "stdout" => println!("{}", code),
@@ -148,3 +722,181 @@ fn create_cargo_project(name: &str, code: &str) {
         .write_all(updated_contents.as_bytes())
         .expect("Failed to write to Cargo.toml");
 }
+
+/// run a query over the datasheets and print its results, one per line
+fn query_files(args: &Query) -> io::Result<()> {
+    let json = is_json(&args.format)?;
+    let inputs = &args.inputs;
+    let files = parse_files::<raw::PackageFile>(inputs, &find_files(inputs))?;
+    let ectx = load_parameters(inputs)?;
+    let packagefiles = resolve_files(&files, &ectx)?;
+    let pfs: Vec<&PackageFile> = packagefiles.iter().collect();
+    let query = query::Query::new(&pfs);
+
+    let known = |name: &str| match query.data_type(name) {
+        Some(type_match) => Ok(type_match),
+        None => Err(io::Error::other(format!(
+            "error: unknown data type `{}`\n",
+            name
+        ))),
+    };
+    let type_json = |t: &TypeMatch| {
+        serde_json::json!({
+            "name": t.name(),
+            "element": t.element(),
+            "shortDescription": t.data_type.name_entity_type().and_then(|n| n.short_description.clone()),
+        })
+    };
+    let entry_json = |e: &EntryMatch| {
+        serde_json::json!({
+            "path": e.path(),
+            "element": e.entry.element(),
+            "type": e.type_name(),
+            "trailer": e.trailer,
+        })
+    };
+    let (results, lines): (serde_json::Value, Vec<String>) = if let Some(name) = &args.type_ {
+        let type_match = known(name)?;
+        let bases: Vec<String> = query.bases(name).iter().map(|t| t.name()).collect();
+        let mut result = type_json(&type_match);
+        result["bases"] = bases.clone().into();
+        let mut line = format!("{} {}", type_match.element(), type_match.name());
+        if !bases.is_empty() {
+            line = format!("{} : {}", line, bases.join(" : "));
+        }
+        (result, vec![line])
+    } else if let Some(kind) = &args.kind {
+        let types = query.data_types_where(|t| t.element() == kind);
+        (
+            types.iter().map(type_json).collect(),
+            types.iter().map(|t| t.name()).collect(),
+        )
+    } else if let Some(name) = &args.references {
+        let _ = known(name)?;
+        let references = query.references_to(name);
+        let dependents = query.dependents(name);
+        let result = serde_json::json!({
+            "references": references.iter().map(|r| serde_json::json!({
+                "path": r.path,
+                "element": r.element,
+                "dataType": r.data_type,
+            })).collect::<Vec<_>>(),
+            "dependents": dependents,
+        });
+        (
+            result,
+            references
+                .iter()
+                .map(|r| format!("{} {}", r.element, r.path))
+                .collect(),
+        )
+    } else if let Some(name) = &args.derived {
+        let _ = known(name)?;
+        let derived = query.derived(name);
+        (
+            derived.iter().map(type_json).collect(),
+            derived.iter().map(|t| t.name()).collect(),
+        )
+    } else if let Some(name) = &args.entries_of_type {
+        let _ = known(name)?;
+        let entries = query.entries_of_type(name);
+        (
+            entries.iter().map(entry_json).collect(),
+            entries.iter().map(|e| e.path()).collect(),
+        )
+    } else if let Some(path) = &args.metadata {
+        let value = query.metadata_value(path).ok_or_else(|| {
+            io::Error::other(format!("error: unknown metadata value `{}`\n", path))
+        })?;
+        let literal = metadata_literal(value);
+        (
+            serde_json::json!({ "path": path, "value": literal }),
+            vec![format!("{} = {}", path, literal.unwrap_or("<computed>"))],
+        )
+    } else {
+        unreachable!("clap requires one query")
+    };
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&results).map_err(io::Error::other)?
+        );
+    } else {
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// the literal of a metadata value, none if it is calculated
+fn metadata_literal(value: &MetadataValue) -> Option<&str> {
+    match value {
+        MetadataValue::DateValue(v) | MetadataValue::StringValue(v) => Some(&v.value.0),
+        MetadataValue::FloatValue(v) | MetadataValue::IntegerValue(v) => match &v.value {
+            NumericValue::Literal(literal) => Some(&literal.0),
+            _ => None,
+        },
+    }
+}
+
+/// print the dependency graph of the datasheets, or the order of its nodes
+fn print_dependencies(deps: &Deps) -> io::Result<()> {
+    let inputs = &deps.inputs;
+    let files = parse_files::<raw::PackageFile>(inputs, &find_files(inputs))?;
+    let ectx = load_parameters(inputs)?;
+    let packagefiles = resolve_files(&files, &ectx)?;
+    let pfs: Vec<&PackageFile> = packagefiles.iter().collect();
+    let graph = if deps.packages {
+        DependencyGraph::packages(&pfs)
+    } else {
+        DependencyGraph::data_types(&pfs)
+    };
+
+    match deps.format.as_str() {
+        "text" => {
+            let order = graph
+                .topological_order()
+                .map_err(|e| io::Error::other(format!("error: {}\n", e)))?;
+            for node in order {
+                println!("{}", node);
+            }
+        }
+        "dot" => print!("{}", graph.to_dot()),
+        "mermaid" => print!("{}", graph.to_mermaid()),
+        format => {
+            return Err(io::Error::other(format!(
+                "error: invalid format `{}`, expected text, dot or mermaid\n",
+                format
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// print the inheritance diagram of the containers of the datasheets
+fn print_hierarchy(args: &Hierarchy) -> io::Result<()> {
+    let inputs = &args.inputs;
+    let files = parse_files::<raw::PackageFile>(inputs, &find_files(inputs))?;
+    let ectx = load_parameters(inputs)?;
+    let packagefiles = resolve_files(&files, &ectx)?;
+    let pfs: Vec<&PackageFile> = packagefiles.iter().collect();
+    let hierarchy = match &args.root {
+        Some(root) => ContainerHierarchy::rooted(&pfs, root)
+            .ok_or_else(|| io::Error::other(format!("error: unknown container `{}`\n", root)))?,
+        None => ContainerHierarchy::new(&pfs),
+    };
+
+    match args.format.as_str() {
+        "svg" => println!("{}", hierarchy.to_svg()),
+        "dot" => print!("{}", hierarchy.to_dot()),
+        format => {
+            return Err(io::Error::other(format!(
+                "error: invalid format `{}`, expected svg or dot\n",
+                format
+            )))
+        }
+    }
+    Ok(())
+}
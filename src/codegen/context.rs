@@ -2,10 +2,12 @@
 use std::collections::HashMap;
 
 use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
+use quote::quote;
 
-use super::format::{format_pascal_case, format_snake_case};
+use super::format::{format_pascal_case, format_snake_case, to_ident};
 use crate::eds::ast::{DataType, Identifier, NamedEntityType, Package, PackageFile};
+use crate::eds::extension::ExtensionRegistry;
+use crate::eds::fingerprint::Fingerprints;
 
 use super::RustCodegenError;
 
@@ -18,15 +20,40 @@ pub struct CodegenContext<'a> {
     pub locals: &'a Namespace<'a>,
     /// Namespace for Global Type References
     pub namespace: &'a Namespace<'a>,
+    /// Handlers of the extensions of user DoTs
+    extensions: &'a ExtensionRegistry,
 }
 
 impl<'a> CodegenContext<'a> {
+    /// a context for the types of a package, with the namespace of all packages; the
+    /// extensions of user DoTs are not handled
+    pub fn new(locals: &'a Namespace<'a>, namespace: &'a Namespace<'a>) -> Self {
+        CodegenContext {
+            name: None,
+            locals,
+            namespace,
+            extensions: ExtensionRegistry::empty(),
+        }
+    }
+
+    /// handle the extensions of user DoTs with a registry
+    pub fn with_extensions(mut self, extensions: &'a ExtensionRegistry) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// the handlers of the extensions of user DoTs
+    pub fn extensions(&self) -> &'a ExtensionRegistry {
+        self.extensions
+    }
+
     /// rename a context and keep all other references the same
     pub fn change_name(&self, name: Option<&'a NamedEntityType>) -> Self {
         CodegenContext {
             name,
             locals: self.locals,
             namespace: self.namespace,
+            extensions: self.extensions,
         }
     }
 
@@ -44,6 +71,14 @@ impl<'a> CodegenContext<'a> {
         }
     }
 
+    /// fingerprint of a data type of the local package
+    pub fn fingerprint(&self, name: &str) -> Option<u64> {
+        let package = self.locals.name.as_ref()?;
+        self.namespace
+            .fingerprints
+            .get(&format!("{}/{}", package.0, name))
+    }
+
     /// Get an identifier from locals or another module in the global namespace
     pub fn get_qualified_ident(&self, path: &str) -> Result<TokenStream, RustCodegenError> {
         let segments = path.split('/').collect::<Vec<_>>();
@@ -51,15 +86,15 @@ impl<'a> CodegenContext<'a> {
         match segments.len() {
             1 => {
                 // Single identifier
-                let ident = format_ident!("{}", segments[0]); // Create an Ident from the string
+                let ident = to_ident(segments[0])?;
                 let pascal_ident = format_pascal_case(&ident)?;
                 Ok(quote! { #pascal_ident })
             }
             2 => {
                 // Module and identifier
-                let module_ident = format_ident!("{}", segments[0]);
+                let module_ident = to_ident(segments[0])?;
                 let snake_module = format_snake_case(&module_ident)?;
-                let ident = format_ident!("{}", segments[1]);
+                let ident = to_ident(segments[1])?;
                 let pascal_ident = format_pascal_case(&ident)?;
                 Ok(quote! { #snake_module::#pascal_ident })
             }
@@ -75,6 +110,8 @@ pub struct Namespace<'a> {
     pub name: Option<Identifier>,
     /// map from ast item strings to rust type items
     pub type_refs: HashMap<String, RustTypeItem<'a>>,
+    /// fingerprints of the data types of all packages, empty for the namespace of a package
+    pub fingerprints: Fingerprints,
     /// optional children namespace
     pub children: Option<Vec<Namespace<'a>>>,
 }
@@ -86,9 +123,15 @@ impl<'a> TryFrom<Vec<&'a PackageFile>> for Namespace<'a> {
     fn try_from(value: Vec<&'a PackageFile>) -> Result<Self, RustCodegenError> {
         let package_vecs: Vec<&Vec<Package>> = value.iter().map(|p| &p.package).collect();
         let packages: Vec<&Package> = package_vecs.iter().copied().flatten().collect();
+        let fingerprints = Fingerprints::try_new(&value).map_err(|e| {
+            RustCodegenError::InvalidLayout(e.error)
+                .within(format!("DataTypeSet/{}[{}]", e.element, e.name))
+                .within(format!("Package[{}]", e.package))
+        })?;
         Ok(Namespace {
             name: None,
             type_refs: HashMap::new(),
+            fingerprints,
             children: Some(
                 packages
                     .into_iter()
@@ -101,15 +144,15 @@ impl<'a> TryFrom<Vec<&'a PackageFile>> for Namespace<'a> {
 
 /// helper method to build a rust item from a name and datatype
 fn prepare_item<'a>(
-    sname: &String,
+    sname: &str,
     datatype: &'a DataType,
 ) -> Result<(String, RustTypeItem<'a>), RustCodegenError> {
     let item = RustTypeItem {
-        ident: format_pascal_case(&format_ident!("{}", sname))?,
+        ident: format_pascal_case(&to_ident(sname)?)?,
         data_type: datatype,
     };
 
-    Ok((sname.clone(), item))
+    Ok((sname.to_string(), item))
 }
 
 /// implement conversion from relevant ast concepts
@@ -165,6 +208,7 @@ impl<'a> TryFrom<&'a Package> for Namespace<'a> {
         Ok(Namespace {
             name: Some(value.name_entity_type.name.clone()),
             type_refs,
+            fingerprints: Fingerprints::default(),
             children: None,
         })
     }
@@ -1,20 +1,24 @@
 //! conversion from AST Items to TokenStreams
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, TokenStreamExt};
+use syn::LitInt;
 
 use crate::eds::ast::{
-    BooleanDataType, ContainerDataType, DataType, EntryElement, EnumeratedDataType, FloatDataType,
-    IntegerDataType, NamedEntityType, Package, PackageFile, QualifiedName, StringDataType,
+    BooleanDataType, Calibrator, ContainerDataType, DataType, EntryElement, EnumeratedDataType,
+    FloatDataType, FloatEncodingAndPrecision, IntegerDataType, IntegerEncoding, NamedEntityType,
+    Package, PackageFile, QualifiedName, Semantics, StringDataType,
 };
+use crate::eds::extension::Extensions;
+use crate::eds::semantics::SemanticTerm;
 
 use super::{
     context::CodegenContext,
     dependency::{AstNode, QualifiedNameIter},
-    format::format_snake_case,
+    format::{format_shouty_snake_case, format_snake_case, to_ident},
     RustCodegenError,
 };
 
-use super::doc::ToRustDoc;
+use super::doc::{get_enumeration_doc, ToRustDoc};
 
 /// Trait for DataTypes
 pub trait ToRustTokens {
@@ -33,8 +37,11 @@ pub trait ToRustMod {
 }
 
 /// Resolve name from an optional NamedEntityType and a NamedEntityType
-fn get_name(opt_name: Option<&NamedEntityType>, name: &NamedEntityType) -> Ident {
-    format_ident!("{}", opt_name.unwrap_or(name).name.0.to_string())
+fn get_name(
+    opt_name: Option<&NamedEntityType>,
+    name: &NamedEntityType,
+) -> Result<Ident, RustCodegenError> {
+    to_ident(&opt_name.unwrap_or(name).name.0)
 }
 
 /// get the closest, larger unsize type for a given size in bits
@@ -56,11 +63,349 @@ fn get_traits() -> TokenStream {
     }
 }
 
+/// Get associated constants for the semantic terms (unit, quantity kind, ...) of an item,
+/// each prefixed with `prefix` (e.g. the field name for container entries)
+fn get_semantic_consts(
+    prefix: Option<&Ident>,
+    semantics: &Semantics,
+) -> Result<TokenStream, RustCodegenError> {
+    fn term<T: SemanticTerm>(
+        name: &'static str,
+        value: &Option<T>,
+    ) -> Option<(&'static str, &'static str)> {
+        value.as_ref().map(|v| (name, v.as_term()))
+    }
+
+    let terms = [
+        term("UNIT", &semantics.unit),
+        term("QUANTITY_KIND", &semantics.quantity_kind),
+        term("CHIRALITY", &semantics.chirality),
+        term("COORDINATE_TYPE", &semantics.coordinate_type),
+        term("TO_COORDINATE_TYPE", &semantics.to_coordinate_type),
+        term("DIFFERENCE_CONVENTION", &semantics.difference_convention),
+        term("INTERPRETATION", &semantics.interpretation),
+        term("PURPOSE", &semantics.purpose),
+        term("REFERENCE_FRAME", &semantics.reference_frame),
+        term("TO_FRAME", &semantics.to_frame),
+        term("RELATION_TO_TIMESTAMP", &semantics.relation_to_timestamp),
+        term("STATUS_CONVENTION", &semantics.status_convention),
+        term("TRANSFORMATION_TYPE", &semantics.transformation_type),
+    ];
+
+    let mut consts = TokenStream::new();
+    for (name, value) in terms.into_iter().flatten() {
+        let cname = match prefix {
+            Some(prefix) => format_shouty_snake_case(&to_ident(&format!("{}_{}", prefix, name))?)?,
+            None => format_ident!("{}", name),
+        };
+        let description = format!("semantic term `{}` of the SEDS core semantics", value);
+        consts.extend(quote! {
+            #[doc = #description]
+            pub const #cname: &'static str = #value;
+        });
+    }
+    Ok(consts)
+}
+
+/// Get associated constants for the extensions of user DoTs that have a handler and a constant
+/// (e.g. `PRIORITY`), prefixed like the semantic terms
+fn get_extension_consts(
+    prefix: Option<&Ident>,
+    extensions: &Extensions,
+    ctx: &CodegenContext,
+) -> Result<TokenStream, RustCodegenError> {
+    let mut consts = TokenStream::new();
+    if extensions.is_empty() {
+        return Ok(consts);
+    }
+    let values = ctx
+        .extensions()
+        .values(extensions)
+        .map_err(RustCodegenError::InvalidExtension)?;
+    for extension in values.iter() {
+        let Some((ty, value)) = extension.value.to_rust_const() else {
+            continue;
+        };
+        let cname = match prefix {
+            Some(prefix) => format!("{}_{}", prefix, extension.name),
+            None => extension.name.clone(),
+        };
+        let cname = format_shouty_snake_case(&to_ident(&cname)?)?;
+        let description = format!("extension `{}` of a user DoT", extension.name);
+        consts.extend(quote! {
+            #[doc = #description]
+            pub const #cname: #ty = #value;
+        });
+    }
+    Ok(consts)
+}
+
+/// Get the associated constants of the semantic terms and extensions of an item
+fn get_named_consts(
+    prefix: Option<&Ident>,
+    named: &NamedEntityType,
+    ctx: &CodegenContext,
+) -> Result<TokenStream, RustCodegenError> {
+    let mut consts = get_semantic_consts(prefix, &named.semantics)?;
+    consts.extend(get_extension_consts(prefix, &named.extensions, ctx)?);
+    Ok(consts)
+}
+
+/// Get the `FINGERPRINT` associated constant of a data type, if its encoding is known
+fn get_fingerprint_const(named: &NamedEntityType, ctx: &CodegenContext) -> TokenStream {
+    match ctx.fingerprint(&named.name.0) {
+        Some(fingerprint) => {
+            let value = LitInt::new(&format!("{:#018x}", fingerprint), Span::call_site());
+            quote! {
+                #[doc = "structural fingerprint of the encoding of the type, see `seds_rs::eds::fingerprint`"]
+                pub const FINGERPRINT: u64 = #value;
+            }
+        }
+        None => TokenStream::new(),
+    }
+}
+
+/// expression converting the encoded `value` of a numeric data type to an f64, or `None` if the
+/// data type is not numeric; `value` holds the raw bits of the encoding
+fn get_value_as_f64(
+    dt: &DataType,
+    value: TokenStream,
+) -> Result<Option<TokenStream>, RustCodegenError> {
+    let value = match dt {
+        DataType::IntegerDataType(idt) => {
+            let size = idt.encoding.size_in_bits;
+            if size == 0 || size > 128 {
+                return Err(RustCodegenError::InvalidBitSize(size));
+            }
+            // the sign bit and the mask of the bits below it
+            let sign = (size - 1) as u32;
+            let magnitude: u128 = (1 << sign) - 1;
+            match idt.encoding.encoding {
+                IntegerEncoding::Unsigned => quote! { (#value as f64) },
+                IntegerEncoding::TwosComplement => {
+                    let shift = (128 - size) as u32;
+                    quote! { ((((#value as i128) << #shift) >> #shift) as f64) }
+                }
+                IntegerEncoding::SignMagnitude => quote! {
+                    ({
+                        let raw = #value as u128;
+                        let magnitude = (raw & #magnitude) as f64;
+                        if ((raw >> #sign) & 1) == 1 {
+                            -magnitude
+                        } else {
+                            magnitude
+                        }
+                    })
+                },
+                IntegerEncoding::OnesComplement => {
+                    let bits = magnitude | (1 << sign);
+                    quote! {
+                        ({
+                            let raw = #value as u128;
+                            if ((raw >> #sign) & 1) == 1 {
+                                -((!raw & #bits) as f64)
+                            } else {
+                                raw as f64
+                            }
+                        })
+                    }
+                }
+                IntegerEncoding::BinaryCodedDecimal => quote! {
+                    ({
+                        let mut raw = #value as u128;
+                        let mut decimal = 0.0;
+                        let mut scale = 1.0;
+                        while raw != 0 {
+                            decimal += (raw & 0xf) as f64 * scale;
+                            scale *= 10.0;
+                            raw >>= 4;
+                        }
+                        decimal
+                    })
+                },
+            }
+        }
+        DataType::FloatDataType(fdt) => match fdt.encoding.encoding_and_precision {
+            FloatEncodingAndPrecision::IEEE7542008Single => {
+                quote! { (f32::from_bits(#value) as f64) }
+            }
+            FloatEncodingAndPrecision::IEEE7542008Double => quote! { f64::from_bits(#value) },
+            ref encoding => {
+                return Err(RustCodegenError::UnsupportedEncoding(encoding.to_string()))
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// the calibrator of a numeric data type
+fn get_calibrator(dt: &DataType) -> Option<&Calibrator> {
+    match dt {
+        DataType::IntegerDataType(idt) => idt.calibrator.as_ref(),
+        DataType::FloatDataType(fdt) => fdt.calibrator.as_ref(),
+        _ => None,
+    }
+}
+
+/// expression applying a calibrator to the raw `value` of a numeric data type; a spline
+/// without extrapolation is NaN outside of its points
+fn get_calibrated(
+    calibrator: &Calibrator,
+    value: TokenStream,
+) -> Result<TokenStream, RustCodegenError> {
+    let invalid = |message: String| RustCodegenError::InvalidCalibrator(message);
+    match calibrator {
+        Calibrator::PolynomialCalibrator(pc) => {
+            let terms = pc
+                .term
+                .iter()
+                .enumerate()
+                .map(|(i, term)| {
+                    let coefficient: f64 = term.coefficient.0.parse().map_err(|_| {
+                        invalid(format!(
+                            "coefficient `{}` is not a number",
+                            term.coefficient.0
+                        ))
+                        .within(format!("Term[{}]", i))
+                    })?;
+                    let exponent: i32 = term.exponent.0.parse().map_err(|_| {
+                        invalid(format!("exponent `{}` is not an integer", term.exponent.0))
+                            .within(format!("Term[{}]", i))
+                    })?;
+                    Ok(quote! { #coefficient * raw.powi(#exponent) })
+                })
+                .collect::<Result<Vec<_>, RustCodegenError>>()
+                .map_err(|e| e.within("PolynomialCalibrator".to_string()))?;
+            Ok(quote! {
+                ({
+                    let raw = #value;
+                    0.0 #(+ #terms)*
+                })
+            })
+        }
+        Calibrator::SplineCalibrator(sc) => {
+            let points = &sc.spline_point;
+            if let Some(point) = points.iter().find(|p| p.order != 1) {
+                return Err(invalid(format!(
+                    "splines of order {} are not supported",
+                    point.order
+                ))
+                .within("SplineCalibrator".to_string()));
+            }
+            // the resolver checked the values are numbers
+            let raws: Vec<f64> = points
+                .iter()
+                .map(|p| p.raw.0.parse().unwrap_or(f64::NAN))
+                .collect();
+            let calibrated: Vec<f64> = points
+                .iter()
+                .map(|p| p.calibrated.0.parse().unwrap_or(f64::NAN))
+                .collect();
+            let finite = raws.iter().chain(calibrated.iter()).all(|v| v.is_finite());
+            if !finite || raws.len() < 2 || raws.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(invalid(
+                    "a spline needs at least 2 finite points with increasing raw values"
+                        .to_string(),
+                )
+                .within("SplineCalibrator".to_string()));
+            }
+            let n = raws.len();
+            let last = n - 1;
+            let interpolated = quote! {
+                CALIBRATED[i] + (raw - RAW[i]) * (CALIBRATED[i + 1] - CALIBRATED[i]) / (RAW[i + 1] - RAW[i])
+            };
+            let calibrated_value = if sc.extrapolate {
+                interpolated
+            } else {
+                quote! {
+                    if (RAW[0]..=RAW[#last]).contains(&raw) {
+                        #interpolated
+                    } else {
+                        f64::NAN
+                    }
+                }
+            };
+            Ok(quote! {
+                ({
+                    const RAW: [f64; #n] = [#(#raws),*];
+                    const CALIBRATED: [f64; #n] = [#(#calibrated),*];
+                    let raw = #value;
+                    // the segment of the raw value, the first or the last one outside of the points
+                    let i = RAW[1..#last].iter().take_while(|r| raw > **r).count();
+                    #calibrated_value
+                })
+            })
+        }
+    }
+}
+
+/// Get the SI unit constant and a `to_si` accessor for a numeric value with a unit,
+/// each prefixed with `prefix` (e.g. the field name for container entries); the raw value is
+/// calibrated first, if the value has a calibrator
+fn get_si_accessor(
+    prefix: Option<&Ident>,
+    semantics: &Semantics,
+    dt: &DataType,
+    calibrator: Option<&Calibrator>,
+    value: TokenStream,
+) -> Result<TokenStream, RustCodegenError> {
+    let conversion = match semantics.unit.as_ref().and_then(|u| u.si_conversion()) {
+        Some(conversion) => conversion,
+        None => return Ok(TokenStream::new()),
+    };
+    let value = match get_value_as_f64(dt, value)? {
+        Some(value) => value,
+        None => return Ok(TokenStream::new()),
+    };
+    let value = match calibrator {
+        Some(calibrator) => get_calibrated(calibrator, value)?,
+        None => value,
+    };
+    let (cname, fname) = match prefix {
+        Some(prefix) => (
+            format_shouty_snake_case(&format_ident!("{}_SI_UNIT", prefix))?,
+            format_snake_case(&format_ident!("{}_to_si", prefix))?,
+        ),
+        None => (format_ident!("SI_UNIT"), format_ident!("to_si")),
+    };
+    let si_unit = conversion.si_unit.as_term();
+    let scale = conversion.scale;
+    let offset = conversion.offset;
+    let const_description = format!("SI unit returned by `{}`", fname);
+    let fn_description = match calibrator {
+        Some(_) => format!("calibrated value converted to the SI unit `{}`", si_unit),
+        None => format!("value converted to the SI unit `{}`", si_unit),
+    };
+    Ok(quote! {
+        #[doc = #const_description]
+        pub const #cname: &'static str = #si_unit;
+
+        #[doc = #fn_description]
+        pub fn #fname(&self) -> f64 {
+            #value * #scale + #offset
+        }
+    })
+}
+
+/// wrap associated constants in an impl block (or nothing if there are none)
+fn impl_semantic_consts(sname: &Ident, consts: TokenStream) -> TokenStream {
+    if consts.is_empty() {
+        consts
+    } else {
+        quote! {
+            impl #sname {
+                #consts
+            }
+        }
+    }
+}
+
 impl ToRustMod for PackageFile {
     fn to_rust_mod(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         if self.package.is_empty() {
-            let sname = get_name(name, &NamedEntityType::new("Package"));
+            let sname = get_name(name, &NamedEntityType::new("Package"))?;
             Ok(quote!(
                 mod #sname {
                 }
@@ -78,9 +423,9 @@ impl ToRustMod for PackageFile {
 
 /// Get all depencies mentioned in a package as imports tokenstream
 fn get_package_imports(pkg: &Package) -> Result<TokenStream, RustCodegenError> {
-    // collect the necessary imports
+    // collect the necessary imports, of the data types only: components are not generated
     let mut imports = TokenStream::new();
-    let qni = QualifiedNameIter::new(AstNode::Package(pkg));
+    let qni = QualifiedNameIter::new(AstNode::DataTypeSet(&pkg.data_type_set));
     let mut qnames: Vec<&QualifiedName> = qni.into_iter().collect();
     qnames.dedup();
 
@@ -92,7 +437,7 @@ fn get_package_imports(pkg: &Package) -> Result<TokenStream, RustCodegenError> {
             1 => (),
             2 => {
                 // Module and identifier
-                let module_ident = format_ident!("{}", segments[0]);
+                let module_ident = to_ident(segments[0])?;
                 let snake_module = format_snake_case(&module_ident)?;
                 if !imported_modules.contains(&snake_module) {
                     imports.extend(quote!(
@@ -111,13 +456,17 @@ fn get_package_imports(pkg: &Package) -> Result<TokenStream, RustCodegenError> {
 impl ToRustMod for Package {
     fn to_rust_mod(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let _name = ctx.name;
         let mut structs = TokenStream::new();
         let description = self.to_description(ctx);
         for dt in self.data_type_set.data_types.iter() {
             let nctx = ctx.change_name(None);
-            structs.extend(dt.to_rust_struct(&nctx)?);
+            structs.extend(
+                dt.to_rust_struct(&nctx).map_err(|e| {
+                    e.within(format!("DataTypeSet/{}[{}]", dt.element(), dt.name()))
+                })?,
+            );
         }
 
         let imports = get_package_imports(self)?;
@@ -163,7 +512,7 @@ impl ToRustTokens for DataType {
 impl ToRustTokens for EnumeratedDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let ty = uint_nearest(&self.encoding.size_in_bits)?;
         let description = self.to_description(ctx);
         Ok(quote! {
@@ -175,7 +524,7 @@ impl ToRustTokens for EnumeratedDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let description = self.to_description(ctx);
 
@@ -183,11 +532,22 @@ impl ToRustTokens for EnumeratedDataType {
         fields.extend(quote!(
             #[default]
         ));
-        for enum_entry in self.enumeration_list.enumeration.iter() {
+        // semantic terms and extensions of the labels, e.g. `ON_PRIORITY`
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        consts.extend(get_named_consts(None, &self.name_entity_type, ctx)?);
+        for (i, enum_entry) in self.enumeration_list.enumeration.iter().enumerate() {
+            let segment = || format!("EnumerationList/Enumeration[{}]", i);
             let value_str = enum_entry.value.0.as_str();
-            let value = value_str.parse::<isize>().unwrap();
-            let fname = format_ident!("{}", enum_entry.label.0);
-            let field = match &enum_entry.short_description {
+            let value = value_str.parse::<isize>().map_err(|_| {
+                RustCodegenError::InvalidEnumerationValue(value_str.to_string()).within(segment())
+            })?;
+            let fname = to_ident(&enum_entry.label.0)?;
+            consts.extend(get_semantic_consts(Some(&fname), &enum_entry.semantics)?);
+            consts.extend(
+                get_extension_consts(Some(&fname), &enum_entry.extensions, ctx)
+                    .map_err(|e| e.within(segment()))?,
+            );
+            let field = match get_enumeration_doc(enum_entry, ctx) {
                 Some(descr) => {
                     let description = format!("(value: {:?}) {}", value, descr);
                     quote!(
@@ -209,6 +569,7 @@ impl ToRustTokens for EnumeratedDataType {
         };
         let ty = uint_nearest(&self.encoding.size_in_bits)?.to_string();
 
+        let consts = impl_semantic_consts(sname, consts);
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -217,6 +578,8 @@ impl ToRustTokens for EnumeratedDataType {
             pub enum #sname {
                 #fields
             }
+
+            #consts
         })
     }
 }
@@ -224,7 +587,7 @@ impl ToRustTokens for EnumeratedDataType {
 impl ToRustTokens for StringDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let description = self.to_description(ctx);
         let length_ident = format_ident!("{}_dlen", sname);
         let update_str = format!("self.{}.len()", sname);
@@ -241,12 +604,15 @@ impl ToRustTokens for StringDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let field_name = NamedEntityType::new("value");
         let nctx = ctx.change_name(Some(&field_name));
         let field = self.to_rust_field(&nctx)?;
         let description = self.to_description(ctx);
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        consts.extend(get_named_consts(None, &self.name_entity_type, ctx)?);
+        let consts = impl_semantic_consts(sname, consts);
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -255,6 +621,7 @@ impl ToRustTokens for StringDataType {
                 #field
             }
 
+            #consts
         })
     }
 }
@@ -262,7 +629,7 @@ impl ToRustTokens for StringDataType {
 impl ToRustTokens for FloatDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let ty = uint_nearest(&self.encoding.size_in_bits)?;
         let sib = format!("{}", self.encoding.size_in_bits);
         let endian = match self.encoding.byte_order {
@@ -280,12 +647,22 @@ impl ToRustTokens for FloatDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let field_name = NamedEntityType::new("value");
         let nctx = ctx.change_name(Some(&field_name));
         let field = self.to_rust_field(&nctx)?;
         let description = self.to_description(ctx);
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        consts.extend(get_named_consts(None, &self.name_entity_type, ctx)?);
+        consts.extend(get_si_accessor(
+            None,
+            &self.name_entity_type.semantics,
+            &DataType::FloatDataType(self.clone()),
+            self.calibrator.as_ref(),
+            quote!(self.value),
+        )?);
+        let consts = impl_semantic_consts(sname, consts);
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -294,6 +671,7 @@ impl ToRustTokens for FloatDataType {
                 #field
             }
 
+            #consts
         })
     }
 }
@@ -301,7 +679,7 @@ impl ToRustTokens for FloatDataType {
 impl ToRustTokens for IntegerDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let ty = uint_nearest(&self.encoding.size_in_bits)?;
         let sib = format!("{}", self.encoding.size_in_bits);
         let endian = match self.encoding.byte_order {
@@ -319,12 +697,22 @@ impl ToRustTokens for IntegerDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let field_name = NamedEntityType::new("value");
         let nctx = ctx.change_name(Some(&field_name));
         let field = self.to_rust_field(&nctx)?;
         let description = self.to_description(ctx);
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        consts.extend(get_named_consts(None, &self.name_entity_type, ctx)?);
+        consts.extend(get_si_accessor(
+            None,
+            &self.name_entity_type.semantics,
+            &DataType::IntegerDataType(self.clone()),
+            self.calibrator.as_ref(),
+            quote!(self.value),
+        )?);
+        let consts = impl_semantic_consts(sname, consts);
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -332,6 +720,8 @@ impl ToRustTokens for IntegerDataType {
             pub struct #sname {
                 #field
             }
+
+            #consts
         })
     }
 }
@@ -339,7 +729,7 @@ impl ToRustTokens for IntegerDataType {
 impl ToRustTokens for BooleanDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
-        let sname = format_snake_case(&get_name(name, &self.name_entity_type))?;
+        let sname = format_snake_case(&get_name(name, &self.name_entity_type)?)?;
         let ty = uint_nearest(&self.encoding.size_in_bits)?;
         let sib = format!("{}", self.encoding.size_in_bits);
         let description = self.to_description(ctx);
@@ -353,12 +743,15 @@ impl ToRustTokens for BooleanDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let field_name = NamedEntityType::new("value");
         let nctx = ctx.change_name(Some(&field_name));
         let field = self.to_rust_field(&nctx)?;
         let description = self.to_description(ctx);
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        consts.extend(get_named_consts(None, &self.name_entity_type, ctx)?);
+        let consts = impl_semantic_consts(sname, consts);
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -366,6 +759,8 @@ impl ToRustTokens for BooleanDataType {
             pub struct #sname {
                 #field
             }
+
+            #consts
         })
     }
 }
@@ -373,91 +768,77 @@ impl ToRustTokens for BooleanDataType {
 impl ToRustTokens for ContainerDataType {
     fn to_rust_field(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let mut fields = TokenStream::new();
-        match &self.base_type {
-            Some(bt) => {
-                // get type or return invalidtype
-                let tref = ctx.get_qualified_ident(&bt.0)?;
-                let base_field = quote!(
-                    pub base: #tref,
-                );
-                fields.extend(base_field);
-            }
-            None => (),
+        if let Some(bt) = &self.base_type {
+            // get type or return invalidtype
+            let tref = ctx.get_qualified_ident(&bt.0)?;
+            let base_field = quote!(
+                pub base: #tref,
+            );
+            fields.extend(base_field);
         }
-        match &self.entry_list {
-            Some(entries) => {
-                for entry in entries.entries.iter() {
-                    match entry {
-                        EntryElement::Entry(entry) => {
-                            // get type or return invalidtype
-                            let tref = ctx.get_qualified_ident(&entry.type_.0)?;
-                            let name = &format_snake_case(&format_ident!(
-                                "{}",
-                                entry.name_entity_type.name.0
-                            ))?;
-                            let description = entry.to_description(ctx);
-                            let field = quote! {
-                                #[doc = #description]
-                                pub #name: #tref,
-                            };
-                            fields.append_all(field);
-                        }
-                        // TODO: duplicate code
-                        EntryElement::LengthEntry(entry) => {
-                            // get type or return invalidtype
-                            let tref = ctx.get_qualified_ident(&entry.type_.0)?;
-                            let name = &format_snake_case(&format_ident!(
-                                "{}",
-                                entry.name_entity_type.name.0
-                            ))?;
-                            let description = entry.to_description(ctx);
-                            let field = quote! {
-                                #[doc = #description]
-                                pub #name: #tref,
-                            };
-                            fields.append_all(field);
-                        }
-                        // TODO: duplicate code
-                        EntryElement::FixedValueEntry(entry) => {
-                            // get type or return invalidtype
-                            let tref = ctx.get_qualified_ident(&entry.type_.0)?;
-                            let name = &format_snake_case(&format_ident!(
-                                "{}",
-                                entry.name_entity_type.name.0
-                            ))?;
-                            let description = entry.to_description(ctx);
-                            let field = quote! {
-                                #[doc = #description]
-                                pub #name: #tref,
-                            };
-                            fields.append_all(field);
-                        }
-                        EntryElement::ErrorControlEntry(_) => (),
-                        EntryElement::PaddingEntry(pe) => {
-                            let pad_size = pe.size_in_bits.to_string();
-                            let field = match &pe.short_description {
-                                Some(_descr) => {
-                                    quote!(
-                                        #[deku(pad_bits_before = #pad_size)]
-                                    )
-                                }
-                                None => {
-                                    quote!(
-                                        #[deku(pad_bits_before = #pad_size)]
-                                    )
-                                }
-                            };
-                            fields.append_all(field);
-                        }
-                        ee => {
-                            return Err(RustCodegenError::UnsupportedEntryElement(Box::new(
-                                ee.clone(),
-                            )))
-                        }
+        if let Some(entries) = &self.entry_list {
+            for entry in entries.entries.iter() {
+                match entry {
+                    EntryElement::Entry(entry) => {
+                        // get type or return invalidtype
+                        let tref = ctx.get_qualified_ident(&entry.type_.0)?;
+                        let name = &format_snake_case(&to_ident(&entry.name_entity_type.name.0)?)?;
+                        let description = entry.to_description(ctx);
+                        let field = quote! {
+                            #[doc = #description]
+                            pub #name: #tref,
+                        };
+                        fields.append_all(field);
+                    }
+                    // TODO: duplicate code
+                    EntryElement::LengthEntry(entry) => {
+                        // get type or return invalidtype
+                        let tref = ctx.get_qualified_ident(&entry.type_.0)?;
+                        let name = &format_snake_case(&to_ident(&entry.name_entity_type.name.0)?)?;
+                        let description = entry.to_description(ctx);
+                        let field = quote! {
+                            #[doc = #description]
+                            pub #name: #tref,
+                        };
+                        fields.append_all(field);
+                    }
+                    // TODO: duplicate code
+                    EntryElement::FixedValueEntry(entry) => {
+                        // get type or return invalidtype
+                        let tref = ctx.get_qualified_ident(&entry.type_.0)?;
+                        let name = &format_snake_case(&to_ident(&entry.name_entity_type.name.0)?)?;
+                        let description = entry.to_description(ctx);
+                        let field = quote! {
+                            #[doc = #description]
+                            pub #name: #tref,
+                        };
+                        fields.append_all(field);
+                    }
+                    EntryElement::ErrorControlEntry(_) => (),
+                    EntryElement::PaddingEntry(pe) => {
+                        let pad_size = pe.size_in_bits.to_string();
+                        let field = match &pe.short_description {
+                            Some(_descr) => {
+                                quote!(
+                                    #[deku(pad_bits_before = #pad_size)]
+                                )
+                            }
+                            None => {
+                                quote!(
+                                    #[deku(pad_bits_before = #pad_size)]
+                                )
+                            }
+                        };
+                        fields.append_all(field);
+                    }
+                    ee => {
+                        return Err(
+                            RustCodegenError::UnsupportedEntryElement(Box::new(ee.clone()))
+                                .within(format!("EntryList/{}[{}]", ee.element(), ee.name())),
+                        )
                     }
                 }
             }
-            None => {}
         }
         Ok(quote! {
             #fields
@@ -467,12 +848,56 @@ impl ToRustTokens for ContainerDataType {
     fn to_rust_struct(&self, ctx: &CodegenContext) -> Result<TokenStream, RustCodegenError> {
         let name = ctx.name;
         let sname = &ctx
-            .lookup_ident(&get_name(name, &self.name_entity_type).to_string())?
+            .lookup_ident(&get_name(name, &self.name_entity_type)?.to_string())?
             .ident;
         let nctx = ctx.change_name(name);
         let fields = self.to_rust_field(&nctx)?;
         let description = self.to_description(ctx);
 
+        // semantic terms of the entries, e.g. `VOLTAGE_UNIT` and `voltage_to_si()`
+        let mut consts = get_fingerprint_const(&self.name_entity_type, ctx);
+        if let Some(entries) = &self.entry_list {
+            for entry in entries.entries.iter() {
+                let segment = format!("EntryList/{}[{}]", entry.element(), entry.name());
+                // the calibrator of a length entry takes the place of the one of its type
+                let (name_entity_type, type_, calibration) = match entry {
+                    EntryElement::Entry(entry) => (&entry.name_entity_type, &entry.type_, None),
+                    EntryElement::LengthEntry(entry) => (
+                        &entry.name_entity_type,
+                        &entry.type_,
+                        entry
+                            .calibration
+                            .clone()
+                            .map(Calibrator::PolynomialCalibrator),
+                    ),
+                    EntryElement::FixedValueEntry(entry) => {
+                        (&entry.name_entity_type, &entry.type_, None)
+                    }
+                    _ => continue,
+                };
+                let prefix = to_ident(&name_entity_type.name.0)?;
+                consts.extend(
+                    get_named_consts(Some(&prefix), name_entity_type, ctx)
+                        .map_err(|e| e.within(segment.clone()))?,
+                );
+                let item = ctx
+                    .lookup_ident(&type_.0)
+                    .map_err(|e| e.within(segment.clone()))?;
+                let field = format_snake_case(&prefix)?;
+                consts.extend(
+                    get_si_accessor(
+                        Some(&prefix),
+                        &name_entity_type.semantics,
+                        item.data_type,
+                        calibration.as_ref().or(get_calibrator(item.data_type)),
+                        quote!(self.#field.value),
+                    )
+                    .map_err(|e| e.within(segment))?,
+                );
+            }
+        }
+        let consts = impl_semantic_consts(sname, consts);
+
         let traits = get_traits();
         Ok(quote! {
             #[doc = #description]
@@ -480,6 +905,8 @@ impl ToRustTokens for ContainerDataType {
             pub struct #sname {
                 #fields
             }
+
+            #consts
         })
     }
 }
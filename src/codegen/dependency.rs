@@ -1,5 +1,17 @@
 //! Collect Dependencies for Codegen
+//!
+//! [`QualifiedNameIter`](crate::codegen::dependency::QualifiedNameIter) collects the qualified
+//! names a package refers to, to import their modules. A
+//! [`DependencyGraph`](crate::codegen::dependency::DependencyGraph) links the data types, or
+//! the packages, to the ones they refer to: it finds the cycles between them, orders them
+//! with their dependencies first, like the modules of the generated code, and exports them to
+//! Graphviz DOT and Mermaid.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
 use crate::eds::ast::*;
+use crate::eds::query::Query;
+use crate::eds::visit::{Path, Visitor};
 
 /// put all relevant ast items in an enum so we iterate over them
 #[derive(Debug)]
@@ -54,15 +66,48 @@ pub enum AstNode<'a> {
     ListEntry(&'a ListEntry),
 }
 
-/// iterate over qualified names in the ast
+/// iterate over the qualified names of the references to data types and interface types in
+/// a node of the ast, in document order
+///
+/// see [`crate::eds::visit`] to walk every node of the ast
 pub struct QualifiedNameIter<'a> {
-    stack: Vec<AstNode<'a>>,
+    names: std::vec::IntoIter<&'a QualifiedName>,
 }
 
 impl<'a> QualifiedNameIter<'a> {
     /// constructor
     pub fn new(root: AstNode<'a>) -> Self {
-        Self { stack: vec![root] }
+        let mut references = TypeRefs::default();
+        let path = Path::default();
+        match root {
+            AstNode::PackageFile(node) => references.visit_package_file(&path, node),
+            AstNode::Package(node) => references.visit_package(&path, node),
+            AstNode::NamedEntityType(node) => references.visit_named_entity_type(&path, node),
+            AstNode::EntryList(node) => references.visit_entry_list(&path, node),
+            AstNode::EntryElement(node) => references.visit_entry_element(&path, node),
+            AstNode::FixedValueEntry(node) => references.visit_fixed_value_entry(&path, node),
+            AstNode::ErrorControlEntry(node) => references.visit_error_control_entry(&path, node),
+            AstNode::Entry(node) => references.visit_entry(&path, node),
+            AstNode::LengthEntry(node) => references.visit_length_entry(&path, node),
+            AstNode::DataTypeSet(node) => references.visit_data_type_set(&path, node),
+            AstNode::DataType(node) => references.visit_data_type(&path, node),
+            AstNode::NoneDataType => (),
+            AstNode::BooleanDataType(node) => references.visit_boolean_data_type(&path, node),
+            AstNode::IntegerDataType(node) => references.visit_integer_data_type(&path, node),
+            AstNode::ArrayDataType(node) => references.visit_array_data_type(&path, node),
+            AstNode::EnumeratedDataType(node) => references.visit_enumerated_data_type(&path, node),
+            AstNode::ContainerDataType(node) => references.visit_container_data_type(&path, node),
+            AstNode::FloatDataType(node) => references.visit_float_data_type(&path, node),
+            AstNode::StringDataType(node) => references.visit_string_data_type(&path, node),
+            AstNode::SubRangeDataType(node) => references.visit_sub_range_data_type(&path, node),
+            AstNode::DimensionList(node) => references.visit_dimension_list(&path, node),
+            AstNode::Range(node) => references.visit_range(&path, node),
+            AstNode::PaddingEntry(node) => references.visit_padding_entry(&path, node),
+            AstNode::ListEntry(node) => references.visit_list_entry(&path, node),
+        }
+        Self {
+            names: references.names.into_iter(),
+        }
     }
 }
 
@@ -70,114 +115,332 @@ impl<'a> Iterator for QualifiedNameIter<'a> {
     type Item = &'a QualifiedName;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.stack.pop() {
-            match node {
-                AstNode::PackageFile(pkg_file) => {
-                    for pkg in pkg_file.package.iter().rev() {
-                        self.stack.push(AstNode::Package(pkg));
-                    }
-                }
-                AstNode::Package(pkg) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&pkg.name_entity_type));
-                    self.stack.push(AstNode::DataTypeSet(&pkg.data_type_set));
-                }
-                AstNode::DataTypeSet(dts) => {
-                    for dt in dts.data_types.iter().rev() {
-                        self.stack.push(AstNode::DataType(dt));
-                    }
-                }
-                AstNode::DataType(datatype) => match datatype {
-                    DataType::NoneDataType => self.stack.push(AstNode::NoneDataType),
-                    DataType::BooleanDataType(dt) => self.stack.push(AstNode::BooleanDataType(dt)),
-                    DataType::IntegerDataType(dt) => self.stack.push(AstNode::IntegerDataType(dt)),
-                    DataType::ArrayDataType(dt) => self.stack.push(AstNode::ArrayDataType(dt)),
-                    DataType::EnumeratedDataType(dt) => {
-                        self.stack.push(AstNode::EnumeratedDataType(dt))
-                    }
-                    DataType::ContainerDataType(dt) => {
-                        self.stack.push(AstNode::ContainerDataType(dt))
-                    }
-                    DataType::FloatDataType(dt) => self.stack.push(AstNode::FloatDataType(dt)),
-                    DataType::StringDataType(dt) => self.stack.push(AstNode::StringDataType(dt)),
-                    DataType::SubRangeDataType(dt) => {
-                        self.stack.push(AstNode::SubRangeDataType(dt))
-                    }
-                },
-                AstNode::ContainerDataType(cdt) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&cdt.name_entity_type));
-                    match &cdt.entry_list {
-                        Some(el) => self.stack.push(AstNode::EntryList(el)),
-                        None => (),
-                    }
-                    match &cdt.base_type {
-                        Some(bt) => return Some(bt),
-                        None => (),
-                    }
+        self.names.next()
+    }
+}
+
+/// Collects the references of the nodes it visits
+#[derive(Default)]
+struct TypeRefs<'a> {
+    names: Vec<&'a QualifiedName>,
+}
+
+impl<'a> Visitor<'a> for TypeRefs<'a> {
+    fn visit_type_ref(&mut self, _path: &Path, type_ref: &'a QualifiedName) {
+        self.names.push(type_ref);
+    }
+}
+
+/// Errors of the order of a dependency graph
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyError {
+    /// the nodes of a cycle, in document order
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyError::Cycle(nodes) => {
+                let nodes: Vec<_> = nodes.iter().map(|n| format!("`{}`", n)).collect();
+                write!(f, "cyclic dependencies between {}", nodes.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+/// The dependencies between the data types, or the packages, of a set of package files
+///
+/// Nodes are the qualified names `Package/Type` of data types, or the names of packages, in
+/// document order. A data type or a package that is referenced but not defined, e.g. of a
+/// package file that is not loaded, is a node without dependencies.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    nodes: Vec<String>,
+    index: BTreeMap<String, usize>,
+    dependencies: Vec<BTreeSet<usize>>,
+    /// true if the nodes are data types, grouped by package in the exports
+    data_types: bool,
+}
+
+impl DependencyGraph {
+    /// the graph of the data types, linked to the types of their entries, their base types
+    /// and the types of their constraints
+    pub fn data_types(package_files: &[&PackageFile]) -> Self {
+        let query = Query::new(package_files);
+        let mut graph = DependencyGraph {
+            data_types: true,
+            ..Default::default()
+        };
+        for type_match in query.data_types() {
+            let _ = graph.node(&type_match.name());
+        }
+        for reference in query.references() {
+            if let Some(data_type) = &reference.data_type {
+                graph.edge(data_type, &reference.to);
+            }
+        }
+        graph
+    }
+
+    /// the graph of the packages, linked to the packages their data types and components
+    /// refer to
+    pub fn packages(package_files: &[&PackageFile]) -> Self {
+        let query = Query::new(package_files);
+        let mut graph = DependencyGraph::default();
+        for package in package_files.iter().flat_map(|pf| pf.package.iter()) {
+            let _ = graph.node(&package.name_entity_type.name.0);
+        }
+        for reference in query.references() {
+            match reference.to.rsplit_once('/') {
+                Some((package, _)) if package != reference.package => {
+                    graph.edge(&reference.package, package)
                 }
-                AstNode::EntryList(el) => {
-                    for entry in el.entries.iter().rev() {
-                        self.stack.push(AstNode::EntryElement(entry));
-                    }
+                _ => (),
+            }
+        }
+        graph
+    }
+
+    fn node(&mut self, name: &str) -> usize {
+        if let Some(index) = self.index.get(name) {
+            return *index;
+        }
+        let index = self.nodes.len();
+        self.nodes.push(name.to_string());
+        self.dependencies.push(BTreeSet::new());
+        let _ = self.index.insert(name.to_string(), index);
+        index
+    }
+
+    fn edge(&mut self, from: &str, to: &str) {
+        let (from, to) = (self.node(from), self.node(to));
+        let _ = self.dependencies[from].insert(to);
+    }
+
+    /// the nodes, in document order
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(String::as_str)
+    }
+
+    /// the nodes a node depends on, in document order
+    pub fn dependencies(&self, node: &str) -> impl Iterator<Item = &str> {
+        self.index
+            .get(node)
+            .into_iter()
+            .flat_map(|index| self.dependencies[*index].iter())
+            .map(|index| self.nodes[*index].as_str())
+    }
+
+    /// the nodes that depend on a node directly, in document order
+    pub fn dependents<'a>(&'a self, node: &'a str) -> impl Iterator<Item = &'a str> {
+        let index = self.index.get(node).copied();
+        self.dependencies
+            .iter()
+            .enumerate()
+            .filter(move |(_, dependencies)| index.is_some_and(|i| dependencies.contains(&i)))
+            .map(|(from, _)| self.nodes[from].as_str())
+    }
+
+    /// the cycles of the graph, as the nodes of each of them in document order; a node that
+    /// depends on itself is a cycle
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            next: 0,
+            indices: vec![None; self.nodes.len()],
+            lowlinks: vec![0; self.nodes.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.nodes.len()],
+            components: Vec::new(),
+        };
+        for node in 0..self.nodes.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+        let mut cycles: Vec<Vec<usize>> = tarjan
+            .components
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.dependencies[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+        cycles.sort();
+        cycles
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.nodes[i].as_str()).collect())
+            .collect()
+    }
+
+    /// the nodes with their dependencies first, otherwise in document order
+    pub fn topological_order(&self) -> Result<Vec<&str>, DependencyError> {
+        let mut remaining: Vec<usize> = self.dependencies.iter().map(BTreeSet::len).collect();
+        let mut dependents = vec![Vec::new(); self.nodes.len()];
+        for (from, dependencies) in self.dependencies.iter().enumerate() {
+            for to in dependencies.iter() {
+                dependents[*to].push(from);
+            }
+        }
+        let mut ready: BTreeSet<usize> = (0..self.nodes.len())
+            .filter(|node| remaining[*node] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = ready.pop_first() {
+            order.push(self.nodes[node].as_str());
+            for dependent in dependents[node].iter() {
+                remaining[*dependent] -= 1;
+                if remaining[*dependent] == 0 {
+                    let _ = ready.insert(*dependent);
                 }
-                AstNode::EntryElement(ee) => match ee {
-                    EntryElement::Entry(e) => self.stack.push(AstNode::Entry(e)),
-                    EntryElement::LengthEntry(e) => self.stack.push(AstNode::LengthEntry(e)),
-                    EntryElement::ErrorControlEntry(e) => {
-                        self.stack.push(AstNode::ErrorControlEntry(e))
-                    }
-                    EntryElement::FixedValueEntry(e) => {
-                        self.stack.push(AstNode::FixedValueEntry(e))
-                    }
-                    EntryElement::PaddingEntry(e) => self.stack.push(AstNode::PaddingEntry(e)),
-                    EntryElement::ListEntry(e) => self.stack.push(AstNode::ListEntry(e)),
+            }
+        }
+        match self.cycles().first() {
+            Some(cycle) if order.len() < self.nodes.len() => Err(DependencyError::Cycle(
+                cycle.iter().map(|n| n.to_string()).collect(),
+            )),
+            _ => Ok(order),
+        }
+    }
+
+    /// the package of a node and its name within it, none if the nodes are packages
+    fn package_of<'a>(&self, node: &'a str) -> Option<(&'a str, &'a str)> {
+        if self.data_types {
+            node.rsplit_once('/')
+        } else {
+            None
+        }
+    }
+
+    /// the nodes grouped by package in order of appearance, and the nodes without one
+    fn clusters(&self) -> (Vec<(&str, Vec<usize>)>, Vec<usize>) {
+        let mut clusters: Vec<(&str, Vec<usize>)> = Vec::new();
+        let mut loose = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            match self.package_of(node) {
+                Some((package, _)) => match clusters.iter_mut().find(|(p, _)| *p == package) {
+                    Some((_, nodes)) => nodes.push(index),
+                    None => clusters.push((package, vec![index])),
                 },
-                AstNode::Entry(e) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&e.name_entity_type));
-                    return Some(&e.type_);
-                }
-                AstNode::LengthEntry(le) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&le.name_entity_type));
-                    return Some(&le.type_);
-                }
-                AstNode::ErrorControlEntry(ece) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&ece.name_entity_type));
-                    return Some(&ece.type_);
-                }
-                AstNode::FixedValueEntry(fve) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&fve.name_entity_type));
-                    return Some(&fve.type_);
+                None => loose.push(index),
+            }
+        }
+        (clusters, loose)
+    }
+
+    /// the graph in the Graphviz DOT language, with the data types grouped by package
+    pub fn to_dot(&self) -> String {
+        fn quote(name: &str) -> String {
+            format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+        let mut dot = String::from("digraph dependencies {\n    rankdir=LR;\n");
+        let (clusters, loose) = self.clusters();
+        for (package, nodes) in clusters.iter() {
+            let _ = writeln!(
+                dot,
+                "    subgraph {} {{",
+                quote(&format!("cluster_{}", package))
+            );
+            let _ = writeln!(dot, "        label={};", quote(package));
+            for node in nodes.iter() {
+                let name = &self.nodes[*node];
+                let label = self.package_of(name).map_or(name.as_str(), |(_, n)| n);
+                let _ = writeln!(dot, "        {} [label={}];", quote(name), quote(label));
+            }
+            dot.push_str("    }\n");
+        }
+        for node in loose.iter() {
+            let _ = writeln!(dot, "    {};", quote(&self.nodes[*node]));
+        }
+        for (from, dependencies) in self.dependencies.iter().enumerate() {
+            for to in dependencies.iter() {
+                let _ = writeln!(
+                    dot,
+                    "    {} -> {};",
+                    quote(&self.nodes[from]),
+                    quote(&self.nodes[*to])
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// the graph as a Mermaid flowchart, with the data types grouped by package
+    pub fn to_mermaid(&self) -> String {
+        fn label(name: &str) -> String {
+            format!("[\"{}\"]", name.replace('"', "#quot;"))
+        }
+        let mut mermaid = String::from("graph LR\n");
+        let (clusters, loose) = self.clusters();
+        for (index, (package, nodes)) in clusters.iter().enumerate() {
+            let _ = writeln!(mermaid, "    subgraph p{} {}", index, label(package));
+            for node in nodes.iter() {
+                let name = &self.nodes[*node];
+                let short = self.package_of(name).map_or(name.as_str(), |(_, n)| n);
+                let _ = writeln!(mermaid, "    n{}{}", node, label(short));
+            }
+            mermaid.push_str("    end\n");
+        }
+        for node in loose.iter() {
+            let _ = writeln!(mermaid, "    n{}{}", node, label(&self.nodes[*node]));
+        }
+        for (from, dependencies) in self.dependencies.iter().enumerate() {
+            for to in dependencies.iter() {
+                let _ = writeln!(mermaid, "    n{} --> n{}", from, to);
+            }
+        }
+        mermaid
+    }
+}
+
+/// state of Tarjan's algorithm for the strongly connected components of a graph
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    next: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn connect(&mut self, node: usize) {
+        self.indices[node] = Some(self.next);
+        self.lowlinks[node] = self.next;
+        self.next += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for dependency in self.graph.dependencies[node].iter() {
+            match self.indices[*dependency] {
+                None => {
+                    self.connect(*dependency);
+                    self.lowlinks[node] = self.lowlinks[node].min(self.lowlinks[*dependency]);
                 }
-                AstNode::ArrayDataType(adt) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&adt.name_entity_type));
-                    self.stack.push(AstNode::DimensionList(&adt.dimension_list));
-                    return Some(&adt.data_type_ref);
+                Some(index) if self.on_stack[*dependency] => {
+                    self.lowlinks[node] = self.lowlinks[node].min(index);
                 }
-                AstNode::SubRangeDataType(srdt) => {
-                    self.stack
-                        .push(AstNode::NamedEntityType(&srdt.name_entity_type));
-                    self.stack.push(AstNode::Range(&srdt.range));
-                    return Some(&srdt.base_type);
+                Some(_) => (),
+            }
+        }
+
+        if Some(self.lowlinks[node]) == self.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
                 }
-                AstNode::IntegerDataType(_idt) => (),
-                AstNode::BooleanDataType(_bdt) => (),
-                AstNode::NamedEntityType(_net) => (),
-                AstNode::NoneDataType => (),
-                AstNode::FloatDataType(_net) => (),
-                AstNode::EnumeratedDataType(_edt) => {}
-                AstNode::StringDataType(_sdt) => (),
-                AstNode::DimensionList(_dl) => (),
-                AstNode::PaddingEntry(_pe) => (),
-                AstNode::Range(_r) => (),
-                AstNode::ListEntry(_el) => (),
             }
+            self.components.push(component);
         }
-        None
     }
 }
@@ -1,9 +1,21 @@
 //! Methods to make diagrams from the seds ast
+use std::fmt::Write;
 use std::vec;
 
-use crate::codegen::frame_diagram::{format::ToSvg, frame::PacketFrame, minify::minify_svg};
+use nalgebra::{Matrix3, Point2, Rotation2, Vector2};
 
-use crate::eds::ast::{DataType, EntryElement};
+use crate::codegen::frame_diagram::{
+    drawable::{CompositeDrawable, Drawable, Line, Rectangle},
+    format::ToSvg,
+    frame::PacketFrame,
+    minify::minify_svg,
+};
+
+use crate::eds::ast::{
+    Constraint, ContainerDataType, DataType, EntryElement, MinMaxRange, MinMaxRangeType,
+    PackageFile,
+};
+use crate::eds::query::Query;
 
 use super::{context::CodegenContext, RustCodegenError};
 
@@ -13,9 +25,6 @@ fn get_frame_model(
     ctx: &CodegenContext,
 ) -> Result<PacketFrame, RustCodegenError> {
     match &datatype {
-        DataType::NoneDataType => Err(RustCodegenError::UnsupportedDataType(Box::new(
-            datatype.clone(),
-        ))),
         DataType::IntegerDataType(dt) => Ok(PacketFrame {
             name: dt.name_entity_type.name.0.clone(),
             bits: dt.encoding.size_in_bits,
@@ -59,7 +68,11 @@ fn get_frame_model(
                                 ctx.lookup_ident(&e.type_.0)?.data_type
                             }
                             EntryElement::LengthEntry(e) => ctx.lookup_ident(&e.type_.0)?.data_type,
-                            e => panic!("{:?} is not supported", e),
+                            e => {
+                                return Err(RustCodegenError::UnsupportedEntryElement(Box::new(
+                                    (*e).clone(),
+                                )))
+                            }
                         };
                         let pf = get_frame_model(dt, ctx)?;
                         children.push(pf);
@@ -80,7 +93,9 @@ fn get_frame_model(
                 children,
             })
         }
-        d => panic!("{:?} is not supported", d),
+        d => Err(RustCodegenError::UnsupportedDataType(Box::new(
+            (*d).clone(),
+        ))),
     }
 }
 
@@ -95,3 +110,340 @@ pub fn get_datatype_packet_svg(
         .map_err(|_| RustCodegenError::SVGConversion)?
         .replace('\n', ""))
 }
+
+/// A container of an inheritance hierarchy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HierarchyNode {
+    /// qualified name of the container
+    pub name: String,
+    /// true if the container is abstract
+    pub is_abstract: bool,
+    /// the fixed value entries of the container, as `Entry = value`
+    pub fixed_values: Vec<String>,
+}
+
+/// A container derived from a base container
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HierarchyEdge {
+    /// index of the base container in the nodes
+    pub base: usize,
+    /// index of the derived container in the nodes
+    pub derived: usize,
+    /// the constraints of the derived container on the entries of its base, like
+    /// `FunctionCode = 3`
+    pub constraints: Vec<String>,
+}
+
+/// The baseType hierarchies of the containers of a set of package files
+///
+/// A base type that is referenced but not defined, e.g. of a package file that is not loaded,
+/// is a node without fixed values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerHierarchy {
+    /// the containers that derive from another or are derived from, in document order
+    pub nodes: Vec<HierarchyNode>,
+    /// the base types of the containers, in document order of the derived containers
+    pub edges: Vec<HierarchyEdge>,
+}
+
+/// height of the name of a container
+const NODE_HEIGHT: f32 = 24.0;
+/// height of a fixed value of a container
+const ROW_HEIGHT: f32 = 16.0;
+/// width of a character of a name, in the monospace font of the diagrams
+const CHAR_WIDTH: f32 = 7.5;
+/// width of a character of a fixed value or a constraint
+const SMALL_CHAR_WIDTH: f32 = 6.5;
+/// space between the containers of a row
+const H_GAP: f32 = 40.0;
+/// space between the rows, for the constraints on the edges
+const V_GAP: f32 = 60.0;
+/// length of the sides of the arrow heads
+const ARROW_SIZE: f32 = 8.0;
+
+impl ContainerHierarchy {
+    /// the hierarchies of all the containers
+    pub fn new(package_files: &[&PackageFile]) -> Self {
+        let query = Query::new(package_files);
+        let mut hierarchy = ContainerHierarchy::default();
+        for type_match in query.data_types() {
+            if let DataType::ContainerDataType(container) = type_match.data_type {
+                let name = type_match.name();
+                let base = query.base_type(&name);
+                if base.is_some() || !query.derived(&name).is_empty() {
+                    hierarchy.container(&name, container, base);
+                }
+            }
+        }
+        hierarchy
+    }
+
+    /// the hierarchy of a container and of the containers derived from it, directly or not;
+    /// none if there is no container of the name
+    pub fn rooted(package_files: &[&PackageFile], name: &str) -> Option<Self> {
+        let query = Query::new(package_files);
+        let root = query.data_type(name)?;
+        let DataType::ContainerDataType(container) = root.data_type else {
+            return None;
+        };
+        let mut hierarchy = ContainerHierarchy::default();
+        hierarchy.container(name, container, None);
+        for derived in query.derived(name) {
+            if let DataType::ContainerDataType(container) = derived.data_type {
+                let name = derived.name();
+                let base = query.base_type(&name);
+                hierarchy.container(&name, container, base);
+            }
+        }
+        Some(hierarchy)
+    }
+
+    fn node(&mut self, name: &str) -> usize {
+        match self.nodes.iter().position(|node| node.name == name) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(HierarchyNode {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// add a container and the edge to its base type
+    fn container(&mut self, name: &str, container: &ContainerDataType, base: Option<String>) {
+        let derived = self.node(name);
+        let fixed_values = container
+            .entry_list
+            .iter()
+            .chain(container.trailer_entry_list.iter())
+            .flat_map(|list| list.entries.iter())
+            .filter_map(|entry| match entry {
+                EntryElement::FixedValueEntry(e) => Some(format!(
+                    "{} = {}",
+                    e.name_entity_type.name.0, e.fixed_value.0
+                )),
+                _ => None,
+            })
+            .collect();
+        self.nodes[derived].is_abstract = container._abstract;
+        self.nodes[derived].fixed_values = fixed_values;
+        if let Some(base) = base {
+            let base = self.node(&base);
+            let constraints = container
+                .constraint_set
+                .iter()
+                .flat_map(|set| set.constraints.iter())
+                .map(format_constraint)
+                .collect();
+            self.edges.push(HierarchyEdge {
+                base,
+                derived,
+                constraints,
+            });
+        }
+    }
+
+    /// the graph in the Graphviz DOT language, with the base containers on top
+    pub fn to_dot(&self) -> String {
+        /// a quoted string of lines, separated by the `\n` escape of DOT
+        fn quote<S: AsRef<str>>(lines: &[S]) -> String {
+            let lines: Vec<_> = lines
+                .iter()
+                .map(|line| line.as_ref().replace('\\', "\\\\").replace('"', "\\\""))
+                .collect();
+            format!("\"{}\"", lines.join("\\n"))
+        }
+        let mut dot =
+            String::from("digraph containers {\n    rankdir=BT;\n    node [shape=box];\n");
+        for node in self.nodes.iter() {
+            let mut label = vec![node_title(node)];
+            label.extend(node.fixed_values.iter().cloned());
+            let style = if node.is_abstract {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    {} [label={}{}];",
+                quote(&[&node.name]),
+                quote(&label),
+                style
+            );
+        }
+        for edge in self.edges.iter() {
+            let _ = write!(
+                dot,
+                "    {} -> {} [arrowhead=empty",
+                quote(&[&self.nodes[edge.derived].name]),
+                quote(&[&self.nodes[edge.base].name])
+            );
+            if !edge.constraints.is_empty() {
+                let _ = write!(dot, ", label={}", quote(&edge.constraints));
+            }
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// the column and the row of each node: the base containers above the derived ones, and
+    /// the derived containers of a base side by side below it
+    fn layout(&self) -> Vec<(f32, usize)> {
+        fn place(
+            hierarchy: &ContainerHierarchy,
+            node: usize,
+            row: usize,
+            next_column: &mut f32,
+            positions: &mut Vec<Option<(f32, usize)>>,
+        ) {
+            // a cycle of base types is placed once
+            positions[node] = Some((*next_column, row));
+            let children: Vec<usize> = hierarchy
+                .edges
+                .iter()
+                .filter(|edge| edge.base == node && positions[edge.derived].is_none())
+                .map(|edge| edge.derived)
+                .collect();
+            if children.is_empty() {
+                *next_column += 1.0;
+                return;
+            }
+            for child in children.iter() {
+                place(hierarchy, *child, row + 1, next_column, positions);
+            }
+            let first = positions[children[0]].map_or(0.0, |(column, _)| column);
+            let last = positions[*children.last().unwrap_or(&children[0])]
+                .map_or(0.0, |(column, _)| column);
+            positions[node] = Some(((first + last) / 2.0, row));
+        }
+
+        let mut positions = vec![None; self.nodes.len()];
+        let mut next_column = 0.0;
+        let roots = (0..self.nodes.len())
+            .filter(|node| !self.edges.iter().any(|edge| edge.derived == *node))
+            .collect::<Vec<_>>();
+        for node in roots.into_iter().chain(0..self.nodes.len()) {
+            if positions[node].is_none() {
+                place(self, node, 0, &mut next_column, &mut positions);
+            }
+        }
+        positions
+            .into_iter()
+            .map(|p| p.unwrap_or_default())
+            .collect()
+    }
+
+    /// the hierarchy as an SVG diagram: the containers as boxes with their fixed values, and
+    /// arrows to their base containers labeled with their constraints
+    pub fn to_svg(&self) -> String {
+        let widths: Vec<f32> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let name = node_title(node).len() as f32 * CHAR_WIDTH;
+                node.fixed_values
+                    .iter()
+                    .map(|value| value.len() as f32 * SMALL_CHAR_WIDTH)
+                    .fold(name, f32::max)
+                    + 16.0
+            })
+            .collect();
+        let heights: Vec<f32> = self
+            .nodes
+            .iter()
+            .map(|node| NODE_HEIGHT + node.fixed_values.len() as f32 * ROW_HEIGHT)
+            .collect();
+        let column_width = widths.iter().copied().fold(0.0, f32::max) + H_GAP;
+        let row_height = heights.iter().copied().fold(0.0, f32::max) + V_GAP;
+        let positions = self.layout();
+        let top_center = |node: usize| {
+            let (column, row) = positions[node];
+            Point2::new(column * column_width, row as f32 * row_height)
+        };
+
+        let mut composite = CompositeDrawable::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let top = top_center(index);
+            let mut title = Rectangle::new(widths[index], NODE_HEIGHT, node_title(node));
+            title.text_style.font_size = Some(12.0);
+            title.transform(&Matrix3::new_translation(&Vector2::new(
+                top.x,
+                top.y + NODE_HEIGHT / 2.0,
+            )));
+            composite.add(title);
+            for (row, fixed_value) in node.fixed_values.iter().enumerate() {
+                let mut value = Rectangle::new(widths[index], ROW_HEIGHT, fixed_value.clone());
+                value.text_style.font_size = Some(10.0);
+                value.transform(&Matrix3::new_translation(&Vector2::new(
+                    top.x,
+                    top.y + NODE_HEIGHT + (row as f32 + 0.5) * ROW_HEIGHT,
+                )));
+                composite.add(value);
+            }
+        }
+        for edge in self.edges.iter() {
+            let start = top_center(edge.derived);
+            let base = top_center(edge.base);
+            let end = Point2::new(base.x, base.y + heights[edge.base]);
+            composite.add(Line::new(start, end));
+
+            // the arrow head points at the base container
+            let direction = (start - end).normalize() * ARROW_SIZE;
+            for angle in [0.5f32, -0.5] {
+                let rotation = Rotation2::new(angle);
+                composite.add(Line::new(end, end + rotation * direction));
+            }
+
+            if !edge.constraints.is_empty() {
+                let text = edge.constraints.join(", ");
+                let mut label =
+                    Rectangle::new(text.len() as f32 * SMALL_CHAR_WIDTH, ROW_HEIGHT, text);
+                label.style.stroke_width = None;
+                label.text_style.font_size = Some(10.0);
+                // beside the middle of the edge, so that the edge does not cross the text
+                let middle = nalgebra::center(&start, &end);
+                label.transform(&Matrix3::new_translation(&Vector2::new(
+                    middle.x + label.width / 2.0 + 4.0,
+                    middle.y,
+                )));
+                composite.add(label);
+            }
+        }
+        composite.to_svg().to_string()
+    }
+}
+
+/// the name of a container as drawn in its box
+fn node_title(node: &HierarchyNode) -> String {
+    if node.is_abstract {
+        format!("{} (abstract)", node.name)
+    } else {
+        node.name.clone()
+    }
+}
+
+/// a constraint as an expression on its entry, like `FunctionCode = 3`
+fn format_constraint(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::ValueConstraint(c) => format!("{} = {}", c.entry.0, c.value.0),
+        Constraint::TypeConstraint(c) => format!("{} : {}", c.entry.0, c.type_.0),
+        Constraint::RangeConstraint(c) => format_range(&c.entry.0, &c.range.min_max_range),
+    }
+}
+
+fn format_range(entry: &str, range: &MinMaxRange) -> String {
+    let (min, max) = (&range.min.0, &range.max.0);
+    match range.range_type {
+        MinMaxRangeType::ExclusiveMinExclusiveMax => format!("{} < {} < {}", min, entry, max),
+        MinMaxRangeType::InclusiveMinInclusiveMax => format!("{} <= {} <= {}", min, entry, max),
+        MinMaxRangeType::InclusiveMinExclusiveMax => format!("{} <= {} < {}", min, entry, max),
+        MinMaxRangeType::ExclusiveMinInclusiveMax => format!("{} < {} <= {}", min, entry, max),
+        MinMaxRangeType::GreaterThan => format!("{} > {}", entry, min),
+        MinMaxRangeType::AtLeast => format!("{} >= {}", entry, min),
+        MinMaxRangeType::LessThan => format!("{} < {}", entry, max),
+        MinMaxRangeType::AtMost => format!("{} <= {}", entry, max),
+    }
+}
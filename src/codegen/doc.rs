@@ -4,13 +4,14 @@ use std::io::Error;
 
 use crate::eds::ast::{
     BooleanDataType, Constraint, ConstraintSet, ContainerDataType, DataType, DataTypeSet, Entry,
-    EnumeratedDataType, FixedValueEntry, FloatDataType, IntegerDataType, LengthEntry, MinMaxRange,
-    MinMaxRangeType, NamedEntityType, Package, StringDataType,
+    EnumeratedDataType, Enumeration, FixedValueEntry, FloatDataType, IntegerDataType, LengthEntry,
+    MinMaxRange, MinMaxRangeType, NamedEntityType, Package, Semantics, StringDataType,
 };
+use crate::eds::extension::Extensions;
+use crate::eds::semantics::SemanticTerm;
 
 use super::{context::CodegenContext, diagram::get_datatype_packet_svg};
 
-
 use prettytable::{format, Cell, Row, Table};
 
 /// formatter so that the item can be represented in rustdoc
@@ -44,6 +45,14 @@ fn get_doc_string(
         description.push_str(&format!(" - {}", short_description));
     }
 
+    if !relevant_name.semantics.is_empty() {
+        description.push_str(&format!("\n\n{}", relevant_name.semantics.to_docstring()));
+    }
+
+    if let Some(extensions) = get_extension_docs(&relevant_name.extensions, ctx) {
+        description.push_str(&format!("\n\n{}", extensions));
+    }
+
     let svg_res = get_datatype_packet_svg(dt, ctx);
 
     match svg_res {
@@ -52,6 +61,41 @@ fn get_doc_string(
     }
 }
 
+/// the docs of the extensions of user DoTs that have a handler, one per line; extensions the
+/// handler can not read are left out, the code generator reports them
+fn get_extension_docs(extensions: &Extensions, ctx: &CodegenContext) -> Option<String> {
+    if extensions.is_empty() {
+        return None;
+    }
+    let values = ctx.extensions().values(extensions).ok()?;
+    let docs: Vec<String> = values.iter().map(|e| e.value.to_docstring()).collect();
+    match docs.is_empty() {
+        true => None,
+        false => Some(docs.join("\n")),
+    }
+}
+
+/// the doc of a label of an enumerated data type: its short description, semantic terms and
+/// extensions
+pub(crate) fn get_enumeration_doc(
+    enumeration: &Enumeration,
+    ctx: &CodegenContext,
+) -> Option<String> {
+    let semantics = Some(enumeration.semantics.to_docstring()).filter(|s| !s.is_empty());
+    let parts: Vec<String> = [
+        enumeration.short_description.clone(),
+        semantics,
+        get_extension_docs(&enumeration.extensions, ctx),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join("\n\n")),
+    }
+}
+
 /// generate markdown table that summarized the constraint docs
 fn get_constraint_docs(constraint_set: &ConstraintSet) -> Result<String, Error> {
     let mut table = Table::new();
@@ -101,7 +145,7 @@ fn get_constraint_docs(constraint_set: &ConstraintSet) -> Result<String, Error>
     let mut output = Vec::new();
 
     let _ = table.print(&mut output)?;
-    String::from_utf8(output).map_err(|e| Error::new(std::io::ErrorKind::Other, format!("{:?}", e)))
+    String::from_utf8(output).map_err(|e| Error::other(format!("{:?}", e)))
 }
 
 /// generate summary tables for the package docs
@@ -129,8 +173,7 @@ fn get_data_type_docs(data_type_set: &DataTypeSet) -> Result<String, Error> {
 
         let mut output = Vec::new();
         let _ = table.print(&mut output)?;
-        String::from_utf8(output)
-            .map_err(|e| Error::new(std::io::ErrorKind::Other, format!("{:?}", e)))
+        String::from_utf8(output).map_err(|e| Error::other(format!("{:?}", e)))
     }
 
     // Separate data by type
@@ -202,6 +245,36 @@ impl FormatRustDoc for MinMaxRange {
     }
 }
 
+/// format Semantics in RustDoc as a list of the attached semantic terms
+impl FormatRustDoc for Semantics {
+    fn to_docstring(&self) -> String {
+        fn item<T: SemanticTerm>(label: &str, term: &Option<T>) -> Option<String> {
+            term.as_ref()
+                .map(|t| format!("- {}: `{}`", label, t.as_term()))
+        }
+
+        [
+            item("Unit", &self.unit),
+            item("Quantity Kind", &self.quantity_kind),
+            item("Chirality", &self.chirality),
+            item("Coordinate Type", &self.coordinate_type),
+            item("To Coordinate Type", &self.to_coordinate_type),
+            item("Difference Convention", &self.difference_convention),
+            item("Interpretation", &self.interpretation),
+            item("Purpose", &self.purpose),
+            item("Reference Frame", &self.reference_frame),
+            item("To Frame", &self.to_frame),
+            item("Relation To Timestamp", &self.relation_to_timestamp),
+            item("Status Convention", &self.status_convention),
+            item("Transformation Type", &self.transformation_type),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join("\n")
+    }
+}
+
 impl ToRustDoc for Package {
     fn to_description(&self, ctx: &CodegenContext) -> String {
         let name = ctx.name;
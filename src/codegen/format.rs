@@ -1,14 +1,28 @@
 //! Code Formatting
 use anyhow::Context;
-use heck::{ToPascalCase, ToSnakeCase};
-use proc_macro2::Ident;
+use heck::{ToPascalCase, ToShoutySnakeCase, ToSnakeCase};
+use proc_macro2::{Ident, Span};
 use std::io::Write;
 use std::process::{Command, Output, Stdio};
+use syn::ext::IdentExt;
+use syn::parse::Error as SynError;
 
 use quote::ToTokens;
 
 use super::RustCodegenError;
 
+/// an identifier from a name in a datasheet, without panicking on names that are not one
+pub fn to_ident(name: &str) -> Result<Ident, RustCodegenError> {
+    syn::parse::Parser::parse_str(Ident::parse_any, name)
+        .map_err(|_| {
+            SynError::new(
+                Span::call_site(),
+                format!("`{}` is not a valid identifier", name),
+            )
+        })
+        .map_err(RustCodegenError::InvalidIdentifier)
+}
+
 /// format an identifier to snake_case
 pub fn format_snake_case(ident: &Ident) -> Result<Ident, RustCodegenError> {
     let ident_str = ident.to_string();
@@ -23,6 +37,13 @@ pub fn format_pascal_case(ident: &Ident) -> Result<Ident, RustCodegenError> {
     syn::parse_str(&pascal_case).map_err(RustCodegenError::InvalidIdentifier)
 }
 
+/// format an identifier to SHOUTY_SNAKE_CASE
+pub fn format_shouty_snake_case(ident: &Ident) -> Result<Ident, RustCodegenError> {
+    let ident_str = ident.to_string();
+    let shouty_snake_case = ident_str.to_shouty_snake_case();
+    syn::parse_str(&shouty_snake_case).map_err(RustCodegenError::InvalidIdentifier)
+}
+
 /// run the rust formatter rustfmt on a token stream
 pub fn rustfmt(tokens: impl ToTokens) -> Result<String, anyhow::Error> {
     let tokens = tokens.into_token_stream();
@@ -39,7 +60,7 @@ pub fn rustfmt(tokens: impl ToTokens) -> Result<String, anyhow::Error> {
         .spawn()
         .context("Failed to spawn rustfmt")?;
 
-    let mut stdin = child.stdin.take().unwrap();
+    let mut stdin = child.stdin.take().context("Failed to open rustfmt stdin")?;
     write!(stdin, "{}", s)?;
     stdin.flush()?;
     drop(stdin);
@@ -162,6 +162,52 @@ impl Drawable for Rectangle {
     }
 }
 
+/// Define the Line, e.g. the edge between two shapes
+pub struct Line {
+    /// Start of the Line
+    pub start: Point2<f32>,
+    /// End of the Line
+    pub end: Point2<f32>,
+    /// Transformation to Apply
+    pub transform: Matrix3<f32>,
+    /// Style Attributes for the Line (the fill is ignored)
+    pub style: ShapeStyle,
+}
+
+impl Line {
+    /// Constructor
+    pub fn new(start: Point2<f32>, end: Point2<f32>) -> Self {
+        Self {
+            start,
+            end,
+            transform: Matrix3::<f32>::identity(),
+            style: ShapeStyle::default(),
+        }
+    }
+
+    /// the transformed end points
+    pub fn get_transformed_points(&self) -> (Point2<f32>, Point2<f32>) {
+        (
+            self.transform.transform_point(&self.start),
+            self.transform.transform_point(&self.end),
+        )
+    }
+}
+
+impl Drawable for Line {
+    fn transform(&mut self, matrix: &Matrix3<f32>) {
+        self.transform *= matrix;
+    }
+
+    fn get_bounding_box(&self) -> BoundingBox {
+        let (start, end) = self.get_transformed_points();
+        BoundingBox {
+            top_left: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+            bottom_right: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+        }
+    }
+}
+
 /// Canvas is like a CompositeShape with Linear Repositioning
 /// i.e., it uses the bounding boxes to place the shapes so that
 /// they do not overlap
@@ -5,7 +5,7 @@ use svg::{node::element, Document};
 use crate::codegen::frame_diagram::drawable::Drawable;
 use crate::codegen::frame_diagram::style::{ShapeStyle, TextStyle};
 use crate::codegen::frame_diagram::{
-    drawable::{Canvas, CompositeDrawable, Rectangle},
+    drawable::{Canvas, CompositeDrawable, Line, Rectangle},
     frame::PacketFrame,
 };
 
@@ -115,6 +115,22 @@ impl ToSvg for Rectangle {
     }
 }
 
+impl ToSvg for Line {
+    fn to_svg(&self) -> Document {
+        let doc = Document::new().set("viewBox", self.get_viewbox_str());
+        let (start, end) = self.get_transformed_points();
+        let line = element::Line::new()
+            .set("x1", start.x.to_string())
+            .set("y1", start.y.to_string())
+            .set("x2", end.x.to_string())
+            .set("y2", end.y.to_string())
+            .set("stroke", self.style.stroke.svg_rgb())
+            .set("stroke-opacity", self.style.stroke_opacity)
+            .set("stroke-width", self.style.stroke_width.unwrap_or(0.0));
+        doc.add(line)
+    }
+}
+
 impl ToSvg for Canvas {
     fn to_svg(&self) -> Document {
         // we pull the children of each to flattent the document
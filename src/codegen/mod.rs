@@ -5,16 +5,22 @@ pub mod dependency;
 pub mod diagram;
 pub mod doc;
 pub mod format;
+// drawing library: not every primitive is used by the generators
+#[allow(dead_code)]
 mod frame_diagram;
 
 pub use convert::*;
 pub use format::rustfmt;
 use proc_macro2::TokenStream;
+use std::fmt;
 
 use crate::eds::ast::{DataType, EntryElement, PackageFile};
+use crate::eds::extension::{ExtensionError, ExtensionRegistry};
+use crate::eds::layout::LayoutError;
 use syn::parse::Error as SynError;
 
 use self::context::{CodegenContext, Namespace};
+use self::dependency::DependencyGraph;
 
 /// RustCodegenError is the error type for the Rust code generator
 #[derive(Debug)]
@@ -31,23 +37,139 @@ pub enum RustCodegenError {
     UnsupportedEntryElement(Box<EntryElement>),
     /// DataType conflicts with another one
     ConflictingDataType(Box<DataType>),
+    /// Enumeration value is not an integer
+    InvalidEnumerationValue(String),
     /// Error generating SVG files
     SVGConversion,
+    /// an extension of a user DoT that its handler could not read
+    InvalidExtension(ExtensionError),
+    /// the layout of a data type is unknown, so it has no fingerprint
+    InvalidLayout(LayoutError),
+    /// a calibrator that can not be applied to the raw value of a data type
+    InvalidCalibrator(String),
+    /// an encoding whose values can not be converted to numbers
+    UnsupportedEncoding(String),
+    /// an error within an element, `path` is relative to the enclosing element
+    InElement {
+        /// element path, see [`crate::diagnostic`]
+        path: String,
+        /// the error
+        error: Box<RustCodegenError>,
+    },
 }
 
+impl RustCodegenError {
+    /// path of the element or attribute the error occurred in, see [`crate::diagnostic`]
+    pub fn path(&self) -> String {
+        fn join(path: &mut String, segment: &str) {
+            if !path.is_empty() && !segment.starts_with('@') {
+                path.push('/');
+            }
+            path.push_str(segment);
+        }
+
+        let mut path = String::new();
+        let mut error = self;
+        loop {
+            match error {
+                RustCodegenError::InElement {
+                    path: segment,
+                    error: inner,
+                } => {
+                    join(&mut path, segment);
+                    error = inner;
+                }
+                RustCodegenError::InvalidExtension(e) => {
+                    join(&mut path, &e.path);
+                    return path;
+                }
+                _ => return path,
+            }
+        }
+    }
+
+    /// the error without the elements it occurred in
+    pub fn cause(&self) -> &RustCodegenError {
+        match self {
+            RustCodegenError::InElement { error, .. } => error.cause(),
+            error => error,
+        }
+    }
+
+    /// record the element an error occurred in
+    pub(crate) fn within(self, segment: String) -> Self {
+        RustCodegenError::InElement {
+            path: segment,
+            error: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for RustCodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RustCodegenError::InvalidIdentifier(err) => write!(f, "invalid identifier: {}", err),
+            RustCodegenError::InvalidType(t) => write!(f, "unknown type `{}`", t),
+            RustCodegenError::InvalidBitSize(size) => {
+                write!(f, "no integer type has {} bits", size)
+            }
+            RustCodegenError::UnsupportedDataType(dt) => {
+                write!(f, "{} `{}` is not supported", dt.element(), dt.name())
+            }
+            RustCodegenError::UnsupportedEntryElement(ee) => {
+                write!(f, "{} `{}` is not supported", ee.element(), ee.name())
+            }
+            RustCodegenError::ConflictingDataType(dt) => write!(
+                f,
+                "{} `{}` conflicts with another data type of the same name",
+                dt.element(),
+                dt.name()
+            ),
+            RustCodegenError::InvalidEnumerationValue(v) => {
+                write!(f, "enumeration value `{}` is not an integer", v)
+            }
+            RustCodegenError::SVGConversion => f.write_str("could not generate an SVG diagram"),
+            RustCodegenError::InvalidExtension(e) => {
+                write!(f, "invalid extension: {}", e.message)
+            }
+            RustCodegenError::InvalidLayout(e) => write!(f, "layout unknown: {}", e),
+            RustCodegenError::InvalidCalibrator(s) => write!(f, "invalid calibrator: {}", s),
+            RustCodegenError::UnsupportedEncoding(e) => {
+                write!(f, "values encoded as `{}` are not supported", e)
+            }
+            RustCodegenError::InElement { .. } => write!(f, "{}: {}", self.path(), self.cause()),
+        }
+    }
+}
+
+impl std::error::Error for RustCodegenError {}
+
 /// CodeGen function to convert packagefiles to a tokenstream
 pub fn codegen_packagefiles(pfs: &[&PackageFile]) -> Result<TokenStream, RustCodegenError> {
-    let mut generated_code = proc_macro2::TokenStream::new();
+    codegen_packagefiles_with_extensions(pfs, ExtensionRegistry::empty())
+}
+
+/// convert packagefiles to a tokenstream, with the extensions of user DoTs read by the
+/// handlers of a registry; the module of a package comes after the modules of the packages
+/// it depends on, unless they depend on each other
+pub fn codegen_packagefiles_with_extensions(
+    pfs: &[&PackageFile],
+    extensions: &ExtensionRegistry,
+) -> Result<TokenStream, RustCodegenError> {
+    let mut generated_code = TokenStream::new();
     let namespace = Namespace::try_from(pfs.to_owned())?;
-    for pf in pfs.iter() {
-        for pkg in pf.package.iter() {
+    let graph = DependencyGraph::packages(pfs);
+    let order = graph
+        .topological_order()
+        .unwrap_or_else(|_| graph.nodes().collect());
+    for name in order {
+        let packages = pfs.iter().flat_map(|pf| pf.package.iter());
+        for pkg in packages.filter(|pkg| pkg.name_entity_type.name.0 == name) {
             let locals = Namespace::try_from(pkg)?;
-            let ctx = CodegenContext {
-                name: None,
-                locals: &locals,
-                namespace: &namespace,
-            };
-            let code = pkg.to_rust_mod(&ctx)?;
+            let ctx = CodegenContext::new(&locals, &namespace).with_extensions(extensions);
+            let code = pkg
+                .to_rust_mod(&ctx)
+                .map_err(|e| e.within(format!("Package[{}]", pkg.name_entity_type.name.0)))?;
             generated_code.extend(code);
         }
     }
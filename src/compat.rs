@@ -0,0 +1,584 @@
+//! Wire Compatibility of Datasheet Versions
+//!
+//! [`diff`] compares two versions of a set of resolved package files, data type by data
+//! type, and classifies each change by whether packets encoded with the old version still
+//! decode with the new one:
+//!
+//! | Compatibility     | Changes |
+//! |-------------------|---------|
+//! | `wire-breaking`   | a data type or a field removed; a field moved, resized or inserted; an encoding, byte order or fixed value changed; an enumeration label removed or its value changed |
+//! | `wire-compatible` | a data type added; a field added at the end of a container; a field or label renamed in place; an enumeration label added; a description changed |
+//! | `cosmetic`        | the order of the DataTypeSet; the type of a field renamed with the same encoding; whitespace of a description |
+//!
+//! Containers are compared by their [layouts](crate::eds::layout), so that a change to a
+//! type is reported in every container that encodes it.
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::eds::ast::{DataType, NamedEntityType, Package, PackageFile};
+use crate::eds::layout::{Encoding, Field, Layout, Layouts};
+use crate::eds::value::Value;
+
+/// Compatibility of a change, from the least to the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// the change does not affect the encoding or the meaning of packets
+    Cosmetic,
+    /// packets of the old version decode with the new one
+    Compatible,
+    /// packets of the old version may not decode with the new one
+    Breaking,
+}
+
+impl fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compatibility::Cosmetic => "cosmetic",
+            Compatibility::Compatible => "wire-compatible",
+            Compatibility::Breaking => "wire-breaking",
+        })
+    }
+}
+
+/// A change between two versions of a data type
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// compatibility of the change
+    pub compatibility: Compatibility,
+    /// qualified name of the data type, like `Pump/Status`
+    pub data_type: String,
+    /// dotted path of the field of a container that changed
+    pub field: Option<String>,
+    /// what changed, like `resized from 8 to 16 bits`
+    pub message: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(
+                f,
+                "{}: field `{}` {}",
+                self.compatibility, field, self.message
+            ),
+            None => write!(f, "{}: {}", self.compatibility, self.message),
+        }
+    }
+}
+
+/// The changes between two versions of package files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    /// the changes, by data type in the order of the old version, then the added data types
+    pub changes: Vec<Change>,
+}
+
+impl Report {
+    /// the most severe compatibility of the changes, none if nothing changed
+    pub fn compatibility(&self) -> Option<Compatibility> {
+        self.changes.iter().map(|c| c.compatibility).max()
+    }
+
+    /// true if packets of the old version may not decode with the new one
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Some(Compatibility::Breaking)
+    }
+
+    /// the changes of a compatibility
+    pub fn changes(&self, compatibility: Compatibility) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |c| c.compatibility == compatibility)
+    }
+
+    /// the report as a JSON object, with camelCase keys like the attributes of datasheets
+    pub fn to_json(&self) -> serde_json::Value {
+        let changes: Vec<_> = self
+            .changes
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "compatibility": c.compatibility.to_string(),
+                    "dataType": c.data_type,
+                    "field": c.field,
+                    "message": c.message,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "compatibility": self.compatibility().map(|c| c.to_string()),
+            "changes": changes,
+        })
+    }
+
+    fn push(&mut self, compatibility: Compatibility, data_type: &str, message: String) {
+        self.changes.push(Change {
+            compatibility,
+            data_type: data_type.to_string(),
+            field: None,
+            message,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        let mut data_type = None;
+        for change in self.changes.iter() {
+            if data_type != Some(&change.data_type) {
+                writeln!(f, "{}", change.data_type)?;
+                data_type = Some(&change.data_type);
+            }
+            writeln!(f, "    {}", change)?;
+        }
+        let counts: Vec<_> = [
+            Compatibility::Breaking,
+            Compatibility::Compatible,
+            Compatibility::Cosmetic,
+        ]
+        .into_iter()
+        .map(|c| format!("{} {}", self.changes(c).count(), c))
+        .collect();
+        writeln!(f, "{} changes: {}", self.changes.len(), counts.join(", "))
+    }
+}
+
+/// the changes from an old version of package files to a new one
+pub fn diff(old: &[&PackageFile], new: &[&PackageFile]) -> Report {
+    let (old, new) = (Layouts::new(old), Layouts::new(new));
+    let mut report = Report::default();
+
+    for old_package in old.packages() {
+        let package_name = &old_package.name_entity_type.name.0;
+        let new_package = package(&new, package_name);
+        for data_type in old_package.data_type_set.data_types.iter() {
+            let name = format!("{}/{}", package_name, data_type.name());
+            match new_package.and_then(|p| p.data_type(data_type.name()).map(|dt| (p, dt))) {
+                Some((new_package, new_type)) => compare_types(
+                    &mut report,
+                    &name,
+                    (&old, old_package, data_type),
+                    (&new, new_package, new_type),
+                ),
+                None => report.push(Compatibility::Breaking, &name, "removed".to_string()),
+            }
+        }
+        if let Some(new_package) = new_package {
+            let order = |from: &Package, to: &Package| -> Vec<String> {
+                from.data_type_set
+                    .data_types
+                    .iter()
+                    .filter(|dt| to.data_type(dt.name()).is_some())
+                    .map(|dt| dt.name().to_string())
+                    .collect()
+            };
+            if order(old_package, new_package) != order(new_package, old_package) {
+                report.push(
+                    Compatibility::Cosmetic,
+                    package_name,
+                    "data types of the DataTypeSet reordered".to_string(),
+                );
+            }
+        }
+    }
+
+    for new_package in new.packages() {
+        let package_name = &new_package.name_entity_type.name.0;
+        let old_package = package(&old, package_name);
+        for data_type in new_package.data_type_set.data_types.iter() {
+            if old_package
+                .and_then(|p| p.data_type(data_type.name()))
+                .is_none()
+            {
+                let name = format!("{}/{}", package_name, data_type.name());
+                report.push(Compatibility::Compatible, &name, "added".to_string());
+            }
+        }
+    }
+    report
+}
+
+/// a package by its name
+fn package<'a>(layouts: &Layouts<'a>, name: &str) -> Option<&'a Package> {
+    layouts
+        .packages()
+        .iter()
+        .find(|p| p.name_entity_type.name.0 == name)
+        .copied()
+}
+
+/// a data type with the layouts of its version
+type Versioned<'l, 'a> = (&'l Layouts<'a>, &'a Package, &'a DataType);
+
+fn compare_types(report: &mut Report, name: &str, old: Versioned<'_, '_>, new: Versioned<'_, '_>) {
+    let ((old_layouts, old_package, old_type), (new_layouts, new_package, new_type)) = (old, new);
+    if old_type.element() != new_type.element() {
+        let message = format!(
+            "changed from {} to {}",
+            old_type.element(),
+            new_type.element()
+        );
+        report.push(Compatibility::Breaking, name, message);
+        return;
+    }
+    if let (Some(old_entity), Some(new_entity)) =
+        (old_type.name_entity_type(), new_type.name_entity_type())
+    {
+        for (compatibility, message) in compare_descriptions(old_entity, new_entity) {
+            report.push(compatibility, name, message);
+        }
+    }
+
+    if let (DataType::ContainerDataType(old_type), DataType::ContainerDataType(new_type)) =
+        (old_type, new_type)
+    {
+        match (
+            old_layouts.container(old_package, old_type),
+            new_layouts.container(new_package, new_type),
+        ) {
+            (Ok(old), Ok(new)) => compare_layouts(report, name, &old, &new),
+            (Ok(_), Err(e)) => report.push(
+                Compatibility::Breaking,
+                name,
+                format!("layout unknown: {}", e),
+            ),
+            // packets of the old version cannot be checked against the new one
+            (Err(e), _) => report.push(
+                Compatibility::Breaking,
+                name,
+                format!("old layout unknown: {}", e),
+            ),
+        }
+        return;
+    }
+    match (
+        old_layouts.encoding(old_package, old_type),
+        new_layouts.encoding(new_package, new_type),
+    ) {
+        (Ok((old, old_size)), Ok((new, new_size))) => {
+            for (compatibility, message) in compare_encodings(&old, old_size, &new, new_size) {
+                report.push(compatibility, name, message);
+            }
+        }
+        (Ok(_), Err(e)) => report.push(
+            Compatibility::Breaking,
+            name,
+            format!("encoding unknown: {}", e),
+        ),
+        (Err(e), _) => report.push(
+            Compatibility::Breaking,
+            name,
+            format!("old encoding unknown: {}", e),
+        ),
+    }
+}
+
+/// changes of the short and long descriptions
+fn compare_descriptions(
+    old: &NamedEntityType,
+    new: &NamedEntityType,
+) -> Vec<(Compatibility, String)> {
+    let short = (
+        "shortDescription",
+        old.short_description.as_deref(),
+        new.short_description.as_deref(),
+    );
+    let long = (
+        "LongDescription",
+        old.long_description.as_ref().map(|d| d.text.as_str()),
+        new.long_description.as_ref().map(|d| d.text.as_str()),
+    );
+    [short, long]
+        .into_iter()
+        .filter_map(|(what, old, new)| compare_description(what, old, new))
+        .collect()
+}
+
+fn compare_description(
+    what: &str,
+    old: Option<&str>,
+    new: Option<&str>,
+) -> Option<(Compatibility, String)> {
+    let words = |d: Option<&str>| d.map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "));
+    if old == new {
+        None
+    } else if words(old) == words(new) {
+        Some((
+            Compatibility::Cosmetic,
+            format!("{} changed only in whitespace", what),
+        ))
+    } else {
+        Some((Compatibility::Compatible, format!("{} changed", what)))
+    }
+}
+
+/// a size for messages
+fn bits(size: Option<usize>) -> String {
+    match size {
+        Some(size) => format!("{} bits", size),
+        None => "a variable size".to_string(),
+    }
+}
+
+/// changes of the encoding of a data type or a field
+fn compare_encodings(
+    old: &Encoding,
+    old_size: Option<usize>,
+    new: &Encoding,
+    new_size: Option<usize>,
+) -> Vec<(Compatibility, String)> {
+    let mut changes = Vec::new();
+    if old_size != new_size {
+        let message = format!("resized from {} to {}", bits(old_size), bits(new_size));
+        changes.push((Compatibility::Breaking, message));
+    }
+    match (old, new) {
+        (
+            Encoding::Enumerated {
+                labels: old_labels, ..
+            },
+            Encoding::Enumerated {
+                labels: new_labels, ..
+            },
+        ) => {
+            if old.to_string() != new.to_string() {
+                let message = format!("encoding changed from {} to {}", old, new);
+                changes.push((Compatibility::Breaking, message));
+            }
+            changes.extend(compare_labels(old_labels, new_labels));
+        }
+        (
+            Encoding::Array {
+                dimensions: old_dimensions,
+                element: old_element,
+            },
+            Encoding::Array {
+                dimensions: new_dimensions,
+                element: new_element,
+            },
+        ) => {
+            if old_dimensions != new_dimensions {
+                let message = format!(
+                    "dimensions changed from {:?} to {:?}",
+                    old_dimensions, new_dimensions
+                );
+                changes.push((Compatibility::Breaking, message));
+            }
+            for (compatibility, message) in compare_encodings(old_element, None, new_element, None)
+            {
+                changes.push((compatibility, format!("elements: {}", message)));
+            }
+        }
+        (Encoding::Container(old_fields), Encoding::Container(new_fields)) => {
+            let layout = |fields: &[_]| Layout {
+                fields: Vec::from(fields),
+                size_in_bits: None,
+            };
+            let mut report = Report::default();
+            compare_layouts(&mut report, "", &layout(old_fields), &layout(new_fields));
+            for change in report.changes {
+                let message = match change.field {
+                    Some(field) => format!("elements: field `{}` {}", field, change.message),
+                    None => format!("elements: {}", change.message),
+                };
+                changes.push((change.compatibility, message));
+            }
+        }
+        (old, new) if old != new => {
+            let message = format!("encoding changed from {} to {}", old, new);
+            changes.push((Compatibility::Breaking, message));
+        }
+        _ => (),
+    }
+    changes
+}
+
+/// changes of the labels of an enumeration, whose values are compared as numbers
+fn compare_labels(
+    old: &[(String, String)],
+    new: &[(String, String)],
+) -> Vec<(Compatibility, String)> {
+    let value = |labels: &[(String, String)], label: &str| {
+        labels
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, v)| Value::parse(v))
+    };
+    let mut changes = Vec::new();
+    let mut renamed = BTreeSet::new();
+    for (label, old_value) in old.iter() {
+        let old_value = Value::parse(old_value);
+        match value(new, label) {
+            Some(new_value) if new_value != old_value => changes.push((
+                Compatibility::Breaking,
+                format!(
+                    "value of label `{}` changed from {} to {}",
+                    label, old_value, new_value
+                ),
+            )),
+            Some(_) => (),
+            None => {
+                // a new label with the value of the old one, that was not in the old version
+                let renaming = new.iter().find(|(l, v)| {
+                    value(old, l).is_none() && Value::parse(v) == old_value && !renamed.contains(l)
+                });
+                match renaming {
+                    Some((new_label, _)) => {
+                        let _ = renamed.insert(new_label);
+                        changes.push((
+                            Compatibility::Compatible,
+                            format!("label `{}` renamed to `{}`", label, new_label),
+                        ));
+                    }
+                    None => changes.push((
+                        Compatibility::Breaking,
+                        format!("label `{}` removed", label),
+                    )),
+                }
+            }
+        }
+    }
+    for (label, _) in new.iter() {
+        if value(old, label).is_none() && !renamed.contains(label) {
+            changes.push((
+                Compatibility::Compatible,
+                format!("label `{}` added", label),
+            ));
+        }
+    }
+    changes
+}
+
+/// an offset for messages
+fn offset(offset: Option<usize>) -> String {
+    match offset {
+        Some(offset) => format!("bit {}", offset),
+        None => "a variable offset".to_string(),
+    }
+}
+
+/// changes of the fields of a container
+fn compare_layouts(report: &mut Report, name: &str, old: &Layout, new: &Layout) {
+    let start = report.changes.len();
+    let mut push = |compatibility, field: &str, message| {
+        report.changes.push(Change {
+            compatibility,
+            data_type: name.to_string(),
+            field: Some(field.to_string()),
+            message,
+        })
+    };
+    let mut renamed = BTreeSet::new();
+    let is_named = |f: &&Field| f.encoding != Encoding::Padding;
+    let old_fields: Vec<_> = old.fields.iter().filter(is_named).collect();
+    let new_fields: Vec<_> = new.fields.iter().filter(is_named).collect();
+
+    for old_field in old_fields.iter() {
+        let Some(new_field) = new.field(&old_field.name) else {
+            // a new field in the same place, with the same encoding
+            let renaming = new_fields.iter().find(|f| {
+                old.field(&f.name).is_none()
+                    && !renamed.contains(&f.name)
+                    && f.offset_in_bits == old_field.offset_in_bits
+                    && f.size_in_bits == old_field.size_in_bits
+                    && f.encoding == old_field.encoding
+                    && f.fixed_value == old_field.fixed_value
+            });
+            match renaming {
+                Some(new_field) => {
+                    let _ = renamed.insert(&new_field.name);
+                    let message = format!("renamed to `{}`", new_field.name);
+                    push(Compatibility::Compatible, &old_field.name, message);
+                }
+                None => push(
+                    Compatibility::Breaking,
+                    &old_field.name,
+                    "removed".to_string(),
+                ),
+            }
+            continue;
+        };
+        if old_field.offset_in_bits != new_field.offset_in_bits {
+            let message = format!(
+                "moved from {} to {}",
+                offset(old_field.offset_in_bits),
+                offset(new_field.offset_in_bits)
+            );
+            push(Compatibility::Breaking, &old_field.name, message);
+        }
+        let changes = compare_encodings(
+            &old_field.encoding,
+            old_field.size_in_bits,
+            &new_field.encoding,
+            new_field.size_in_bits,
+        );
+        if changes.is_empty() && old_field.type_ != new_field.type_ {
+            let message = format!(
+                "type changed from `{}` to `{}`, with the same encoding",
+                old_field.type_.as_deref().unwrap_or_default(),
+                new_field.type_.as_deref().unwrap_or_default()
+            );
+            push(Compatibility::Cosmetic, &old_field.name, message);
+        }
+        for (compatibility, message) in changes {
+            push(compatibility, &old_field.name, message);
+        }
+        if old_field.fixed_value != new_field.fixed_value {
+            let value = |v: &Option<String>| match v {
+                Some(v) => format!("`{}`", v),
+                None => "none".to_string(),
+            };
+            let message = format!(
+                "fixed value changed from {} to {}",
+                value(&old_field.fixed_value),
+                value(&new_field.fixed_value)
+            );
+            push(Compatibility::Breaking, &old_field.name, message);
+        }
+        let description = compare_description(
+            "shortDescription",
+            old_field.short_description.as_deref(),
+            new_field.short_description.as_deref(),
+        );
+        if let Some((compatibility, message)) = description {
+            push(compatibility, &old_field.name, message);
+        }
+    }
+
+    let mut added = false;
+    for new_field in new_fields.iter() {
+        if old.field(&new_field.name).is_some() || renamed.contains(&new_field.name) {
+            continue;
+        }
+        added = true;
+        match (new_field.offset_in_bits, old.size_in_bits) {
+            (Some(o), Some(size)) if o >= size => push(
+                Compatibility::Compatible,
+                &new_field.name,
+                "added at the end".to_string(),
+            ),
+            (o, _) => push(
+                Compatibility::Breaking,
+                &new_field.name,
+                format!("inserted at {}", offset(o)),
+            ),
+        }
+    }
+
+    // padding changed, without changes to the fields
+    if !added && report.changes.len() == start && old.size_in_bits != new.size_in_bits {
+        let compatibility = match (old.size_in_bits, new.size_in_bits) {
+            (Some(old), Some(new)) if new > old => Compatibility::Compatible,
+            _ => Compatibility::Breaking,
+        };
+        let message = format!(
+            "resized from {} to {}",
+            bits(old.size_in_bits),
+            bits(new.size_in_bits)
+        );
+        report.push(compatibility, name, message);
+    }
+}
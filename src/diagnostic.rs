@@ -0,0 +1,428 @@
+//! Diagnostics
+//!
+//! Errors of the parser, the resolver and the code generator are rendered as compiler-style
+//! diagnostics, pointing into the datasheet they come from:
+//!
+//! ```text
+//! error: invalid byte order `middleEndian`, expected littleEndian or bigEndian
+//!   --> eds/heater.xml:6:62
+//!    |
+//!  6 |             <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="middleEndian" />
+//!    |                                                              ^^^^^^^^^^^^^^^^^^^^^^^^
+//!    = in Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@byteOrder
+//! ```
+//!
+//! The models do not keep source positions. Errors carry the path of the element (or
+//! attribute) they occurred in instead, like `Package[P]/DataTypeSet/IntegerDataType[T]@name`,
+//! where elements with a `name` attribute are qualified by it, unnamed elements by their position
+//! among the siblings of the same element starting at 0, like `PaddingEntry[1]`, and the root
+//! element is left out.
+//! A [`SourceMap`] of the file turns the path into a line and column.
+use std::collections::HashMap;
+use std::fmt;
+
+use xml::attribute::OwnedAttribute;
+use xml::common::{Position, TextPosition};
+use xml::name::OwnedName;
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::codegen::RustCodegenError;
+use crate::eds::resolve::ResolveError;
+
+/// Severity of a diagnostic
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A position in a file, with the text of its line for snippets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// line, starting at 1
+    pub line: usize,
+    /// column in characters, starting at 1
+    pub column: usize,
+    /// number of characters to underline
+    pub length: usize,
+    /// text of the line
+    pub text: String,
+}
+
+/// A message about a datasheet, optionally located in a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// severity
+    pub severity: Severity,
+    /// what went wrong
+    pub message: String,
+    /// file the diagnostic refers to
+    pub file: Option<String>,
+    /// path of the element or attribute within the file
+    pub path: Option<String>,
+    /// position within the file
+    pub span: Option<Span>,
+    /// additional information
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// an error
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message.into())
+    }
+
+    /// a warning
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message.into())
+    }
+
+    fn new(severity: Severity, message: String) -> Self {
+        Diagnostic {
+            severity,
+            message,
+            file: None,
+            path: None,
+            span: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// set the file of the diagnostic
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// set the element path of the diagnostic, see the [module documentation](self)
+    pub fn at(mut self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.path = if path.is_empty() { None } else { Some(path) };
+        self
+    }
+
+    /// set the position of the diagnostic
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// add a note
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.severity, self.message)?;
+        let gutter = match &self.span {
+            Some(span) => span.line.to_string().len(),
+            None => 1,
+        };
+        let pad = " ".repeat(gutter);
+        match (&self.file, &self.span) {
+            (Some(file), Some(span)) => {
+                writeln!(f, "{}--> {}:{}:{}", pad, file, span.line, span.column)?
+            }
+            (Some(file), None) => writeln!(f, "{}--> {}", pad, file)?,
+            _ => (),
+        }
+        if let Some(span) = &self.span {
+            // keep tabs so the marker lines up with the snippet
+            let indent: String = span
+                .text
+                .chars()
+                .take(span.column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            writeln!(f, "{} |", pad)?;
+            writeln!(f, "{} | {}", span.line, span.text)?;
+            writeln!(f, "{} | {}{}", pad, indent, "^".repeat(span.length.max(1)))?;
+        }
+        if let Some(path) = &self.path {
+            writeln!(f, "{} = in {}", pad, path)?;
+        }
+        for note in self.notes.iter() {
+            writeln!(f, "{} = note: {}", pad, note)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&ResolveError> for Diagnostic {
+    fn from(error: &ResolveError) -> Self {
+        Diagnostic::error(error.cause().to_string()).at(error.path())
+    }
+}
+
+impl From<&RustCodegenError> for Diagnostic {
+    fn from(error: &RustCodegenError) -> Self {
+        Diagnostic::error(error.cause().to_string()).at(error.path())
+    }
+}
+
+impl From<&xml::reader::Error> for Diagnostic {
+    fn from(error: &xml::reader::Error) -> Self {
+        Diagnostic::error(error.msg())
+    }
+}
+
+/// Positions of the elements and attributes of an XML file, by path
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    file: String,
+    text: String,
+    /// byte offset and length in characters of each element name and attribute
+    positions: HashMap<String, (usize, usize)>,
+}
+
+impl SourceMap {
+    /// index the elements of an XML document
+    pub fn new(file: &str, text: &str) -> Self {
+        let mut index = Index::new(text);
+        let mut events = EventReader::new(text.as_bytes());
+        loop {
+            match events.next() {
+                Ok(XmlEvent::StartElement {
+                    name, attributes, ..
+                }) => {
+                    let _ = index.start(text, events.position(), &name, &attributes);
+                }
+                Ok(XmlEvent::EndElement { .. }) => index.end(),
+                Ok(XmlEvent::EndDocument) | Err(_) => break,
+                Ok(_) => (),
+            }
+        }
+        Self::from_index(file, text, index)
+    }
+
+    pub(crate) fn from_index(file: &str, text: &str, index: Index) -> Self {
+        SourceMap {
+            file: file.to_string(),
+            text: text.to_string(),
+            positions: index.positions,
+        }
+    }
+
+    /// name of the file
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// true if the file has an element or attribute with this path
+    pub fn contains(&self, path: &str) -> bool {
+        self.positions.contains_key(path)
+    }
+
+    /// position of an element or attribute; if the file has no such path, the position of its
+    /// closest enclosing element
+    pub fn span(&self, path: &str) -> Option<Span> {
+        let mut path = path;
+        loop {
+            if let Some((offset, length)) = self.positions.get(path) {
+                return Some(self.span_at_offset(*offset, *length));
+            }
+            path = &path[..path.rfind(['/', '@'])?];
+        }
+    }
+
+    /// position of a line and column, both starting at 1
+    pub fn span_at(&self, line: usize, column: usize) -> Option<Span> {
+        let text = self.text.lines().nth(line.checked_sub(1)?)?;
+        Some(Span {
+            line,
+            column,
+            length: 1,
+            text: text.to_string(),
+        })
+    }
+
+    fn span_at_offset(&self, offset: usize, length: usize) -> Span {
+        let before = &self.text[..offset];
+        let start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let end = self.text[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(self.text.len());
+        Span {
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            length,
+            text: self.text[start..end].trim_end_matches('\r').to_string(),
+        }
+    }
+
+    /// locate a diagnostic in this file, by its path
+    pub fn locate(&self, diagnostic: Diagnostic) -> Diagnostic {
+        let span = diagnostic.path.as_deref().and_then(|path| self.span(path));
+        let diagnostic = diagnostic.in_file(self.file.clone());
+        match span {
+            Some(span) => diagnostic.with_span(span),
+            None => diagnostic,
+        }
+    }
+}
+
+/// locate an XML syntax error in the file it comes from
+pub fn parse_error(source: &SourceMap, error: &xml::reader::Error) -> Diagnostic {
+    let diagnostic = Diagnostic::from(error).in_file(source.file());
+    let position = error.position();
+    match source.span_at(position.row as usize + 1, position.column as usize + 1) {
+        Some(span) => diagnostic.with_span(span),
+        None => diagnostic,
+    }
+}
+
+/// An open element while indexing
+#[derive(Debug)]
+struct Open {
+    path: String,
+    /// number of children with each element name, to index unnamed elements
+    children: HashMap<String, usize>,
+}
+
+/// Paths and positions of the elements of an XML document, indexed from the start and end
+/// events of an XML parser
+#[derive(Debug, Default)]
+pub(crate) struct Index {
+    positions: HashMap<String, (usize, usize)>,
+    stack: Vec<Open>,
+    /// byte offsets of the starts of the lines
+    lines: Vec<usize>,
+}
+
+impl Index {
+    /// an index of the elements of a text
+    pub(crate) fn new(text: &str) -> Self {
+        Index {
+            lines: std::iter::once(0)
+                .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+            ..Index::default()
+        }
+    }
+
+    /// index a start tag the parser read at a position, and return the path of its element;
+    /// unnamed elements after the first of their name are returned by position, e.g.
+    /// `Dimension[1]`
+    pub(crate) fn start(
+        &mut self,
+        text: &str,
+        position: TextPosition,
+        name: &OwnedName,
+        attributes: &[OwnedAttribute],
+    ) -> String {
+        let local = name.local_name.as_str();
+        let segment = match attributes.iter().find(|a| a.name.local_name == "name") {
+            Some(attribute) => format!("{}[{}]", local, attribute.value),
+            None => local.to_string(),
+        };
+        // unnamed elements are also registered by their position among their siblings
+        let (path, indexed, index) = match self.stack.last_mut() {
+            None => (String::new(), None, 0),
+            Some(parent) => {
+                let count = parent.children.entry(local.to_string()).or_insert(0);
+                let index = *count;
+                *count += 1;
+                let prefix = if parent.path.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}/", parent.path)
+                };
+                let indexed = if segment.contains('[') {
+                    None
+                } else {
+                    Some(format!("{}{}[{}]", prefix, segment, index))
+                };
+                (format!("{}{}", prefix, segment), indexed, index)
+            }
+        };
+        self.stack.push(Open {
+            path: path.clone(),
+            children: HashMap::new(),
+        });
+
+        // the parser gives the position of the `<` of the tag; the positions of the
+        // attributes are found in the text of the tag
+        if let Some(tag) = self.offset(text, position) {
+            let name_start = tag + 1 + name.prefix.as_ref().map_or(0, |p| p.len() + 1);
+            let attributes = self::attributes(text, name_start + local.len());
+            for element in std::iter::once(&path).chain(indexed.as_ref()) {
+                let _ = self
+                    .positions
+                    .entry(element.clone())
+                    .or_insert((name_start, local.chars().count()));
+                for (name, offset, length) in attributes.iter() {
+                    let _ = self
+                        .positions
+                        .entry(format!("{}@{}", element, name))
+                        .or_insert((*offset, *length));
+                }
+            }
+        }
+        match indexed {
+            Some(indexed) if index > 0 => indexed,
+            _ => path,
+        }
+    }
+
+    /// close the innermost open element
+    pub(crate) fn end(&mut self) {
+        let _ = self.stack.pop();
+    }
+
+    /// byte offset of a position of the parser, whose columns count characters
+    fn offset(&self, text: &str, position: TextPosition) -> Option<usize> {
+        let start = *self.lines.get(position.row as usize)?;
+        text[start..]
+            .char_indices()
+            .nth(position.column as usize)
+            .map(|(i, _)| start + i)
+    }
+}
+
+/// local name, offset and length in characters of an attribute
+type Attribute<'a> = (&'a str, usize, usize);
+
+/// the attributes of the start tag whose name ends at an offset
+fn attributes(text: &str, from: usize) -> Vec<Attribute<'_>> {
+    let mut attributes = Vec::new();
+    let mut i = from;
+    let bytes = text.as_bytes();
+    while i < text.len() {
+        match bytes[i] {
+            b'>' => break,
+            c if c.is_ascii_whitespace() || c == b'/' => i += 1,
+            _ => {
+                let start = i;
+                let Some(eq) = text[i..].find('=').map(|e| i + e) else {
+                    break;
+                };
+                let name = text[start..eq].trim();
+                let Some(quote_at) = text[eq + 1..].find(['"', '\'']).map(|q| eq + 1 + q) else {
+                    break;
+                };
+                let quote = bytes[quote_at] as char;
+                let Some(close) = text[quote_at + 1..].find(quote).map(|c| quote_at + 1 + c) else {
+                    break;
+                };
+                let name = name.rsplit(':').next().unwrap_or(name);
+                attributes.push((name, start, text[start..=close].chars().count()));
+                i = close + 1;
+            }
+        }
+    }
+    attributes
+}
@@ -1,5 +1,14 @@
 //! EDS PackageFile Model
 
+use std::fmt;
+
+pub use super::extension::Extensions;
+pub use super::semantics::{
+    Chirality, CoordinateType, DifferenceConvention, Interpretation, Purpose, QuantityKind,
+    ReferenceFrame, RelationToTimestamp, StatusConvention, ToCoordinateType, ToFrame,
+    TransformationType, Unit,
+};
+
 /// Identifier
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Identifier(pub String);
@@ -124,6 +133,9 @@ pub struct Package {
 
     /// A Package element may contain a DataTypeSet element
     pub data_type_set: DataTypeSet,
+
+    /// A Package element may contain a ComponentSet element
+    pub component_set: ComponentSet,
 }
 
 /// MetaData provides additional information about the Device or PackageFile
@@ -133,6 +145,60 @@ pub struct MetaData {
     pub creation_date: Option<String>,
 
     pub creator: Option<String>,
+
+    pub values: Vec<MetadataValue>,
+
+    pub categories: Vec<MetadataCategory>,
+}
+
+/// Category groups metadata values and nested categories - Req 3.4.2
+#[derive(Debug, Default, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct MetadataCategory {
+    pub name_entity_type: NamedEntityType,
+    pub values: Vec<MetadataValue>,
+    pub categories: Vec<MetadataCategory>,
+}
+
+/// MetadataValue is one of DateValue, FloatValue, IntegerValue or StringValue - Req 3.4.5
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum MetadataValue {
+    DateValue(StringMetadataValue),
+    FloatValue(NumericMetadataValue),
+    IntegerValue(NumericMetadataValue),
+    StringValue(StringMetadataValue),
+}
+
+/// DateValue or StringValue with a literal value - Req 3.4.7
+#[derive(Debug, Default, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct StringMetadataValue {
+    pub name_entity_type: NamedEntityType,
+    pub value: Literal,
+}
+
+/// FloatValue or IntegerValue - Req 3.4.8
+#[derive(Debug, Default, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct NumericMetadataValue {
+    pub name_entity_type: NamedEntityType,
+    pub value: NumericValue,
+}
+
+/// NumericValue is a literal or describes how the value is calculated - Req 3.4.9
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum NumericValue {
+    Literal(Literal),
+    MathOperation(MathOperation),
+    Conditional(Conditional),
+}
+
+impl Default for NumericValue {
+    fn default() -> Self {
+        NumericValue::Literal(Literal::default())
+    }
 }
 
 /// DataTypeSet element contains one or more DataType elements
@@ -178,6 +244,28 @@ pub struct NamedEntityType {
     pub name: Identifier,
     pub short_description: Option<String>,
     pub long_description: Option<LongDescription>,
+    pub semantics: Semantics,
+    /// attributes and elements of user DoTs, with their parameters substituted
+    pub extensions: Extensions,
+}
+
+/// Semantics holds the resolved semantic terms of a NamedEntityType
+#[derive(Debug, Default, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub struct Semantics {
+    pub quantity_kind: Option<QuantityKind>,
+    pub unit: Option<Unit>,
+    pub chirality: Option<Chirality>,
+    pub coordinate_type: Option<CoordinateType>,
+    pub to_coordinate_type: Option<ToCoordinateType>,
+    pub difference_convention: Option<DifferenceConvention>,
+    pub interpretation: Option<Interpretation>,
+    pub purpose: Option<Purpose>,
+    pub reference_frame: Option<ReferenceFrame>,
+    pub to_frame: Option<ToFrame>,
+    pub relation_to_timestamp: Option<RelationToTimestamp>,
+    pub status_convention: Option<StatusConvention>,
+    pub transformation_type: Option<TransformationType>,
 }
 
 /// LongDescription element contains text representing a long description
@@ -202,6 +290,9 @@ pub struct Enumeration {
     pub label: Identifier,
     pub value: Literal,
     pub short_description: Option<String>,
+    pub semantics: Semantics,
+    /// attributes and elements of user DoTs, with their parameters substituted
+    pub extensions: Extensions,
 }
 
 /// ContainerDataType defines a container data type
@@ -345,6 +436,7 @@ pub enum BooleanFalseValue {
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct IntegerDataType {
     pub name_entity_type: NamedEntityType,
+    pub calibrator: Option<Calibrator>,
     pub encoding: IntegerDataEncoding,
     pub range: Range,
 }
@@ -380,6 +472,7 @@ pub struct MinMaxRange {
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct FloatDataType {
     pub name_entity_type: NamedEntityType,
+    pub calibrator: Option<Calibrator>,
     pub encoding: FloatDataEncoding,
     pub range: Option<Range>,
 }
@@ -449,6 +542,8 @@ pub struct GenericTypeMap {
 pub struct Implementation {
     pub variable_set: VariableSet,
     pub parameter_map_set: ParameterMapSet,
+    pub activity_set: ActivitySet,
+    pub state_machine_set: StateMachineSet,
 }
 
 #[allow(missing_docs)]
@@ -463,7 +558,362 @@ pub struct Variable {
     pub type_: QualifiedName,
     pub read_only: bool,
     pub name: Identifier,
-    pub initial_value: Literal,
+    pub initial_value: Option<Literal>,
+}
+
+/// StateMachineSet contains the state machines used to implement a component
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StateMachineSet {
+    pub state_machines: Vec<StateMachine>,
+}
+
+/// StateMachine responds to events and schedules the execution of activities - Req 3.16
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StateMachine {
+    pub name_entity_type: NamedEntityType,
+    pub elements: Vec<StateMachineElement>,
+}
+
+/// StateMachineElement is one of EntryState, ExitState, State or Transition - Req 3.16.2
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateMachineElement {
+    EntryState(EntryState),
+    ExitState(ExitState),
+    State(State),
+    Transition(Transition),
+}
+
+/// EntryState is the pseudo-state a state machine starts in
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntryState {
+    pub name_entity_type: NamedEntityType,
+}
+
+/// ExitState is a pseudo-state that terminates a state machine
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExitState {
+    pub name_entity_type: NamedEntityType,
+}
+
+/// State may invoke an activity on entry and immediately before exit - Req 3.16.5
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct State {
+    pub name_entity_type: NamedEntityType,
+    pub on_entry: Option<ActivityInvocation>,
+    pub on_exit: Option<ActivityInvocation>,
+}
+
+/// Transition between two states of a state machine
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Transition {
+    pub name_entity_type: NamedEntityType,
+    pub from_state: Identifier,
+    pub to_state: Identifier,
+    pub trigger: TransitionTrigger,
+    pub guard: Option<BooleanExpression>,
+    pub do_: Option<ActivityInvocation>,
+}
+
+/// TransitionTrigger is the event that fires a transition
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionTrigger {
+    OnCommandPrimitive(OnCommandPrimitive),
+    OnParameterPrimitive(OnParameterPrimitive),
+    OnTimer(OnTimer),
+}
+
+impl Default for TransitionTrigger {
+    fn default() -> Self {
+        TransitionTrigger::OnTimer(OnTimer::default())
+    }
+}
+
+/// ActivityInvocation names an activity and its arguments - Req 3.16.6, 3.16.7
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ActivityInvocation {
+    pub activity: Identifier,
+    pub argument_values: Vec<ArgumentValue>,
+}
+
+/// ArgumentValue associates a value with a named activity argument - Req 3.16.7
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ArgumentValue {
+    pub name: Identifier,
+    pub value: Operand,
+}
+
+/// Operand is either a literal Value or a reference to a component variable
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    VariableRef(QualifiedName),
+    Value(Literal),
+}
+
+impl Default for Operand {
+    fn default() -> Self {
+        Operand::Value(Literal::default())
+    }
+}
+
+/// OnCommandPrimitive triggers a transition when a command arrives on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OnCommandPrimitive {
+    pub interface: Identifier,
+    pub command: Identifier,
+    pub transaction: Option<Identifier>,
+    pub argument_values: Vec<SinkArgumentValue>,
+}
+
+/// SinkArgumentValue stores an incoming command argument in a component variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SinkArgumentValue {
+    pub name: Identifier,
+    pub output_variable_ref: QualifiedName,
+}
+
+/// OnParameterPrimitive triggers a transition when a parameter is read or written on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OnParameterPrimitive {
+    pub interface: Identifier,
+    pub parameter: Identifier,
+    pub operation: ParameterOperation,
+    pub transaction: Option<Identifier>,
+    pub variable_ref: Option<QualifiedName>,
+}
+
+/// ParameterOperation - the operations possible on an interface parameter
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[allow(missing_docs)]
+pub enum ParameterOperation {
+    #[default]
+    Get,
+    Set,
+}
+
+/// OnTimer triggers a transition a specified duration after entering its source state
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OnTimer {
+    pub nanoseconds_after_entry: u64,
+}
+
+/// ActivitySet contains the activities used to implement a component - Req 3.15
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ActivitySet {
+    pub activities: Vec<Activity>,
+}
+
+/// Activity is a named sequence of statements with arguments
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Activity {
+    pub name_entity_type: NamedEntityType,
+    pub arguments: Vec<ActivityArgument>,
+    pub body: Body,
+}
+
+/// ActivityArgument is a typed argument of an activity
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ActivityArgument {
+    pub name_entity_type: NamedEntityType,
+    pub type_: QualifiedName,
+}
+
+/// Body is the implementation of an activity, executed statement by statement
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Body {
+    pub statements: Vec<Statement>,
+}
+
+/// Statement is a single step within an activity body
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Assignment(Assignment),
+    Conditional(Conditional),
+    Iteration(Iteration),
+    Calibration(Calibration),
+    SendParameterPrimitive(SendParameterPrimitive),
+    SendCommandPrimitive(SendCommandPrimitive),
+    MathOperation(MathOperation),
+    Call(ActivityInvocation),
+}
+
+/// Assignment of a variable or a value to a variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Assignment {
+    pub output_variable_ref: QualifiedName,
+    pub value: Operand,
+}
+
+/// Conditional executes one of two bodies depending on a boolean expression - Req 3.15.37
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional {
+    pub condition: BooleanExpression,
+    pub on_condition_true: Body,
+    pub on_condition_false: Body,
+}
+
+/// BooleanExpression is a comparison, a type check or a group of ANDed or ORed expressions
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum BooleanExpression {
+    Condition(Comparison),
+    ANDedConditions(Vec<BooleanExpression>),
+    ORedConditions(Vec<BooleanExpression>),
+    TypeCondition(TypeCheck),
+}
+
+/// Comparison of a variable with a value or another variable
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub first_operand: QualifiedName,
+    pub comparison_operator: ComparisonOperator,
+    pub second_operand: Operand,
+}
+
+/// ComparisonOperator - the operators of a Comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum ComparisonOperator {
+    Equals,
+    NotEquals,
+    LessThan,
+    LessThanEquals,
+    GreaterThan,
+    GreaterThanEquals,
+}
+
+/// TypeCheck tests a variable for compatibility with a type
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypeCheck {
+    pub first_operand: QualifiedName,
+    pub type_operand: QualifiedName,
+}
+
+/// Iteration repeats a body for each element of an array or each step of a range
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iteration {
+    pub iterator_variable_ref: QualifiedName,
+    pub range: IterationRange,
+    pub do_: Body,
+}
+
+/// IterationRange is an array variable or a numeric range
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum IterationRange {
+    OverArray(QualifiedName),
+    Numeric {
+        start_at: Operand,
+        step: Operand,
+        end_at: Operand,
+    },
+}
+
+/// Calibration of an input variable into an output variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Calibration {
+    pub output_variable_ref: QualifiedName,
+    pub input_variable_ref: QualifiedName,
+    pub calibrator: PolynomialCalibrator,
+}
+
+/// SendCommandPrimitive sends a command on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SendCommandPrimitive {
+    pub interface: Identifier,
+    pub command: Identifier,
+    pub transaction: Option<Identifier>,
+    pub argument_values: Vec<ArgumentValue>,
+}
+
+/// SendParameterPrimitive reads or writes a parameter on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SendParameterPrimitive {
+    pub interface: Identifier,
+    pub parameter: Identifier,
+    pub operation: ParameterOperation,
+    pub transaction: Option<Identifier>,
+    pub argument_value: Option<Operand>,
+}
+
+/// MathOperation is a stack calculation in reverse polish notation - Req 3.15.32
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MathOperation {
+    pub output_variable_ref: QualifiedName,
+    pub elements: Vec<MathElement>,
+}
+
+/// MathElement pushes an operand on the stack or applies an operator to it
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathElement {
+    Operand(Operand),
+    Operator(MathOperator),
+}
+
+/// MathOperator - the operators of a MathOperation (trigonometric operators use degrees)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum MathOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulus,
+    Pow,
+    Ln,
+    Log,
+    Exp,
+    Inverse,
+    Tan,
+    Cos,
+    Sin,
+    Atan,
+    Atan2,
+    Acos,
+    Asin,
+    Tanh,
+    Cosh,
+    Sinh,
+    Atanh,
+    Acosh,
+    Asinh,
+    Swap,
+    Abs,
+    Ceil,
+    Floor,
+    Round,
+    Sqrt,
+    Min,
+    Max,
 }
 
 #[allow(missing_docs)]
@@ -503,6 +953,34 @@ pub struct Term {
     pub exponent: Literal,
 }
 
+/// Calibrator that converts the raw value of a numeric data type into the units of its
+/// semantics
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Calibrator {
+    PolynomialCalibrator(PolynomialCalibrator),
+    SplineCalibrator(SplineCalibrator),
+}
+
+/// SplineCalibrator converts a raw value into a calibrated value along a line through points,
+/// ordered by their raw values; outside of the points the line is extended if `extrapolate`
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SplineCalibrator {
+    pub extrapolate: bool,
+    pub spline_point: Vec<SplinePoint>,
+}
+
+/// SplinePoint is a point of a SplineCalibrator, the order of the line up to the point is 1 for
+/// a straight line
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SplinePoint {
+    pub order: usize,
+    pub raw: Literal,
+    pub calibrated: Literal,
+}
+
 /// ErrorControlEntry specifies an entry whose value is constrained, or derived,
 /// based on the contents of the container in which it is present.
 #[allow(missing_docs)]
@@ -540,7 +1018,6 @@ pub struct ListEntry {
 pub struct SubRangeDataType {
     pub base_type: QualifiedName,
     pub name_entity_type: NamedEntityType,
-    pub unit: String,
     pub range: Range,
 }
 
@@ -552,6 +1029,195 @@ impl NamedEntityType {
             name: Identifier(name.to_string()),
             short_description: None,
             long_description: None,
+            semantics: Semantics::default(),
+            extensions: Extensions::default(),
+        }
+    }
+}
+
+impl Package {
+    /// the data type of a name in the DataTypeSet of the package
+    pub fn data_type(&self, name: &str) -> Option<&DataType> {
+        self.data_type_set
+            .data_types
+            .iter()
+            .find(|data_type| data_type.name() == name)
+    }
+}
+
+impl DataType {
+    /// XML element of the data type
+    pub fn element(&self) -> &'static str {
+        match self {
+            DataType::NoneDataType => "DataType",
+            DataType::BooleanDataType(_) => "BooleanDataType",
+            DataType::IntegerDataType(_) => "IntegerDataType",
+            DataType::ArrayDataType(_) => "ArrayDataType",
+            DataType::EnumeratedDataType(_) => "EnumeratedDataType",
+            DataType::ContainerDataType(_) => "ContainerDataType",
+            DataType::FloatDataType(_) => "FloatDataType",
+            DataType::StringDataType(_) => "StringDataType",
+            DataType::SubRangeDataType(_) => "SubRangeDataType",
+        }
+    }
+
+    /// names and descriptions of the data type, none for a NoneDataType
+    pub fn name_entity_type(&self) -> Option<&NamedEntityType> {
+        match self {
+            DataType::NoneDataType => None,
+            DataType::BooleanDataType(dt) => Some(&dt.name_entity_type),
+            DataType::IntegerDataType(dt) => Some(&dt.name_entity_type),
+            DataType::ArrayDataType(dt) => Some(&dt.name_entity_type),
+            DataType::EnumeratedDataType(dt) => Some(&dt.name_entity_type),
+            DataType::ContainerDataType(dt) => Some(&dt.name_entity_type),
+            DataType::FloatDataType(dt) => Some(&dt.name_entity_type),
+            DataType::StringDataType(dt) => Some(&dt.name_entity_type),
+            DataType::SubRangeDataType(dt) => Some(&dt.name_entity_type),
+        }
+    }
+
+    /// name of the data type, empty for a NoneDataType
+    pub fn name(&self) -> &str {
+        match self {
+            DataType::NoneDataType => "",
+            DataType::BooleanDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::IntegerDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::ArrayDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::EnumeratedDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::ContainerDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::FloatDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::StringDataType(dt) => &dt.name_entity_type.name.0,
+            DataType::SubRangeDataType(dt) => &dt.name_entity_type.name.0,
+        }
+    }
+}
+
+impl EntryElement {
+    /// XML element of the entry
+    pub fn element(&self) -> &'static str {
+        match self {
+            EntryElement::Entry(_) => "Entry",
+            EntryElement::FixedValueEntry(_) => "FixedValueEntry",
+            EntryElement::PaddingEntry(_) => "PaddingEntry",
+            EntryElement::ListEntry(_) => "ListEntry",
+            EntryElement::LengthEntry(_) => "LengthEntry",
+            EntryElement::ErrorControlEntry(_) => "ErrorControlEntry",
+        }
+    }
+
+    /// name of the entry, empty for a PaddingEntry
+    pub fn name(&self) -> &str {
+        match self {
+            EntryElement::Entry(e) => &e.name_entity_type.name.0,
+            EntryElement::FixedValueEntry(e) => &e.name_entity_type.name.0,
+            EntryElement::PaddingEntry(_) => "",
+            EntryElement::ListEntry(e) => &e.name_entity_type.name.0,
+            EntryElement::LengthEntry(e) => &e.name_entity_type.name.0,
+            EntryElement::ErrorControlEntry(e) => &e.name_entity_type.name.0,
+        }
+    }
+
+    /// type of the entry, none for a PaddingEntry or a ListEntry
+    pub fn type_(&self) -> Option<&QualifiedName> {
+        match self {
+            EntryElement::Entry(e) => Some(&e.type_),
+            EntryElement::FixedValueEntry(e) => Some(&e.type_),
+            EntryElement::LengthEntry(e) => Some(&e.type_),
+            EntryElement::ErrorControlEntry(e) => Some(&e.type_),
+            EntryElement::PaddingEntry(_) | EntryElement::ListEntry(_) => None,
+        }
+    }
+}
+
+impl StateMachineElement {
+    /// name of the state machine element - Req 3.16.3
+    pub fn name_entity_type(&self) -> &NamedEntityType {
+        match self {
+            StateMachineElement::EntryState(es) => &es.name_entity_type,
+            StateMachineElement::ExitState(es) => &es.name_entity_type,
+            StateMachineElement::State(s) => &s.name_entity_type,
+            StateMachineElement::Transition(t) => &t.name_entity_type,
+        }
+    }
+}
+
+impl Semantics {
+    /// true if no semantic term is set
+    pub fn is_empty(&self) -> bool {
+        *self == Semantics::default()
+    }
+}
+
+impl MetadataValue {
+    /// name of the metadata value
+    pub fn name_entity_type(&self) -> &NamedEntityType {
+        match self {
+            MetadataValue::DateValue(v) | MetadataValue::StringValue(v) => &v.name_entity_type,
+            MetadataValue::FloatValue(v) | MetadataValue::IntegerValue(v) => &v.name_entity_type,
+        }
+    }
+}
+
+impl MathOperator {
+    /// number of stack values the operator consumes
+    pub fn arity(&self) -> usize {
+        use MathOperator::*;
+        match self {
+            Add | Subtract | Multiply | Divide | Modulus | Pow | Atan2 | Swap | Min | Max => 2,
+            _ => 1,
         }
     }
 }
+
+impl fmt::Display for IntegerEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IntegerEncoding::Unsigned => "unsigned",
+            IntegerEncoding::SignMagnitude => "signMagnitude",
+            IntegerEncoding::TwosComplement => "twosComplement",
+            IntegerEncoding::OnesComplement => "onesComplement",
+            IntegerEncoding::BinaryCodedDecimal => "binaryCodedDecimal",
+        })
+    }
+}
+
+impl fmt::Display for StringEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StringEncoding::ASCII => "ASCII",
+            StringEncoding::UTF8 => "UTF-8",
+        })
+    }
+}
+
+impl fmt::Display for FloatEncodingAndPrecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FloatEncodingAndPrecision::IEEE7542008Single => "IEEE754_2008_single",
+            FloatEncodingAndPrecision::IEEE7542008Double => "IEEE754_2008_double",
+            FloatEncodingAndPrecision::IEEE7542008Quadruple => "IEEE754_2008_quadruple",
+            FloatEncodingAndPrecision::MILSTD1770ASimple => "MILSTD_1750A_simple",
+            FloatEncodingAndPrecision::MILSTD1770AExtended => "MILSTD_1750A_extended",
+        })
+    }
+}
+
+impl fmt::Display for ByteOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ByteOrder::BigEndian => "bigEndian",
+            ByteOrder::LittleEndian => "littleEndian",
+        })
+    }
+}
+
+impl fmt::Display for ErrorControlType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorControlType::CRC16CCITT => "CRC16_CCITT",
+            ErrorControlType::CRC8 => "CRC8",
+            ErrorControlType::CHECKSUM => "CHECKSUM",
+            ErrorControlType::CHECKSUMLONGITUDINAL => "CHECKSUM_LONGITUDINAL",
+        })
+    }
+}
@@ -0,0 +1,1031 @@
+//! Builders of EDS Models
+//!
+//! A [`PackageFileBuilder`] assembles a package file from packages, data types and entries in
+//! Rust, e.g. to generate datasheets for test fixtures or from ICDs kept in spreadsheets.
+//! `build` checks the invariants of the model and returns the raw model, which
+//! [`crate::write::to_xml`] writes as a datasheet; `resolve` also resolves it to the ast.
+//!
+//! The checks are the ones a datasheet author gets wrong most often:
+//! - names are not empty, and unique among the packages of the file, the data types of a
+//!   package, the entries and trailer entries of a container and the labels of an enumeration
+//! - sizes, lengths and dimensions are not 0, arrays have dimensions, enumerations have labels
+//!   and sub ranges have a range
+//! - a setting of a builder applies to its kind of data type or entry, e.g. no labels on an
+//!   integer
+//! - a container with constraints has a base type
+//! - references to data types of the packages being built name existing data types; other
+//!   references are left to the resolver
+//!
+//! Values, like the bounds of ranges and the values of labels, are written as given, so they
+//! can be literals or `${...}` parameters of the mission.
+use std::collections::HashSet;
+use std::fmt;
+
+use super::ast::{
+    self, ByteOrder, ErrorControlType, FloatEncodingAndPrecision, IntegerEncoding, StringEncoding,
+};
+use super::raw;
+use super::resolve::{resolve_all, ResolveError};
+use crate::expr::ExpressionContext;
+
+/// What is wrong with a model being built
+#[allow(missing_docs)]
+#[derive(Debug)]
+pub enum BuildErrorKind {
+    EmptyName,
+    DuplicateName(String),
+    /// a setting of a builder that does not apply to the kind of its data type or entry
+    NotApplicable {
+        setting: &'static str,
+        element: &'static str,
+    },
+    /// an attribute that must not be 0
+    Zero(&'static str),
+    /// an element the model needs at least one of
+    Missing(&'static str),
+    UnknownType(String),
+    /// constraints of a container without a base type to constrain
+    NoBaseType,
+    Resolve(ResolveError),
+}
+
+/// An invariant that does not hold for an element of the model being built
+#[derive(Debug)]
+pub struct BuildError {
+    /// path of the element, like `Package[Pump]/DataTypeSet/ContainerDataType[Status]`
+    pub path: String,
+    /// what is wrong with it
+    pub kind: BuildErrorKind,
+}
+
+impl fmt::Display for BuildErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildErrorKind::EmptyName => write!(f, "empty name"),
+            BuildErrorKind::DuplicateName(name) => write!(f, "duplicate name `{}`", name),
+            BuildErrorKind::NotApplicable { setting, element } => {
+                write!(f, "`{}` does not apply to the {}", setting, element)
+            }
+            BuildErrorKind::Zero(attribute) => write!(f, "{} of 0", attribute),
+            BuildErrorKind::Missing(element) => write!(f, "no {}", element),
+            BuildErrorKind::UnknownType(type_) => write!(f, "unknown type `{}`", type_),
+            BuildErrorKind::NoBaseType => write!(f, "constraints without a base type"),
+            BuildErrorKind::Resolve(err) => err.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// what a setter of a builder found wrong, reported with the path of the element on `build`
+#[derive(Debug, Clone, Copy)]
+enum Problem {
+    NotApplicable {
+        setting: &'static str,
+        element: &'static str,
+    },
+    Zero(&'static str),
+}
+
+impl From<Problem> for BuildErrorKind {
+    fn from(problem: Problem) -> Self {
+        match problem {
+            Problem::NotApplicable { setting, element } => {
+                BuildErrorKind::NotApplicable { setting, element }
+            }
+            Problem::Zero(attribute) => BuildErrorKind::Zero(attribute),
+        }
+    }
+}
+
+fn named(element: &str, name: &str) -> String {
+    format!("{}[{}]", element, name)
+}
+
+fn named_entity_type(name: &str) -> raw::NamedEntityType {
+    raw::NamedEntityType {
+        name: name.to_string(),
+        ..raw::NamedEntityType::default()
+    }
+}
+
+/// the range of the values of an integer encoding, for integers without an explicit range
+fn full_range(encoding: &IntegerEncoding, size_in_bits: usize) -> (String, String) {
+    let bits = size_in_bits.clamp(1, 127) as u32;
+    let max = |bits: u32| 2i128.checked_pow(bits).map_or(i128::MAX, |p| p - 1);
+    let (min, max) = match encoding {
+        IntegerEncoding::Unsigned => (0, max(bits)),
+        IntegerEncoding::TwosComplement => (-max(bits - 1) - 1, max(bits - 1)),
+        IntegerEncoding::SignMagnitude | IntegerEncoding::OnesComplement => {
+            (-max(bits - 1), max(bits - 1))
+        }
+        IntegerEncoding::BinaryCodedDecimal => {
+            (0, 10i128.checked_pow(bits / 4).map_or(i128::MAX, |p| p - 1))
+        }
+    };
+    (min.to_string(), max.to_string())
+}
+
+fn inclusive_range(min: impl fmt::Display, max: impl fmt::Display) -> raw::Range {
+    raw::Range {
+        min_max_range: raw::MinMaxRange {
+            min: min.to_string(),
+            max: max.to_string(),
+            range_type: "inclusiveMinInclusiveMax".to_string(),
+        },
+        precision_range: None,
+    }
+}
+
+fn non_zero(size: usize, attribute: &'static str, problems: &mut Vec<Problem>) {
+    if size == 0 {
+        problems.push(Problem::Zero(attribute));
+    }
+}
+
+/// Builder of a package file
+#[derive(Debug, Clone, Default)]
+pub struct PackageFileBuilder {
+    packages: Vec<PackageBuilder>,
+}
+
+impl PackageFileBuilder {
+    /// a package file without packages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a package
+    pub fn package(mut self, package: PackageBuilder) -> Self {
+        self.packages.push(package);
+        self
+    }
+
+    /// the raw package file, or every invariant that does not hold
+    pub fn build(&self) -> Result<raw::PackageFile, Vec<BuildError>> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        let mut data_types = HashSet::new();
+        for package in self.packages.iter() {
+            for data_type in package.data_types.iter() {
+                let _ = data_types.insert(format!("{}/{}", package.name(), data_type.name()));
+            }
+        }
+        let checks = Checks {
+            packages: self.packages.iter().map(|p| p.name()).collect(),
+            data_types,
+        };
+        for package in self.packages.iter() {
+            if !names.insert(package.name()) {
+                errors.push(BuildError {
+                    path: named("Package", package.name()),
+                    kind: BuildErrorKind::DuplicateName(package.name().to_string()),
+                });
+            }
+            package.check(&checks, &mut errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(raw::PackageFile {
+            package: self.packages.iter().map(PackageBuilder::build).collect(),
+            metadata: None,
+        })
+    }
+
+    /// the package file resolved to the ast, or every invariant that does not hold and every
+    /// error of the resolver
+    pub fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::PackageFile, Vec<BuildError>> {
+        resolve_all(&self.build()?, ectx).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|err| BuildError {
+                    path: err.path(),
+                    kind: BuildErrorKind::Resolve(err.into_cause()),
+                })
+                .collect()
+        })
+    }
+}
+
+/// what the checks of the elements of a package file need to know about the others
+struct Checks<'a> {
+    packages: HashSet<&'a str>,
+    /// the data types being built, as `Package/Type`
+    data_types: HashSet<String>,
+}
+
+impl Checks<'_> {
+    /// check a reference to a data type, from a package
+    fn type_ref(&self, package: &str, type_: &str, path: &str, errors: &mut Vec<BuildError>) {
+        if type_.contains("${") {
+            return;
+        }
+        let qualified = match type_.rsplit_once('/') {
+            Some((other, _)) if !self.packages.contains(other) => return,
+            Some(_) => type_.to_string(),
+            None => format!("{}/{}", package, type_),
+        };
+        if !self.data_types.contains(&qualified) {
+            errors.push(BuildError {
+                path: path.to_string(),
+                kind: BuildErrorKind::UnknownType(type_.to_string()),
+            });
+        }
+    }
+}
+
+/// Builder of a package
+#[derive(Debug, Clone)]
+pub struct PackageBuilder {
+    name_entity_type: raw::NamedEntityType,
+    data_types: Vec<DataTypeBuilder>,
+}
+
+impl PackageBuilder {
+    /// a package without data types
+    pub fn new(name: &str) -> Self {
+        PackageBuilder {
+            name_entity_type: named_entity_type(name),
+            data_types: Vec::new(),
+        }
+    }
+
+    /// set the short description
+    pub fn short_description(mut self, description: &str) -> Self {
+        self.name_entity_type.short_description = Some(description.to_string());
+        self
+    }
+
+    /// set the long description
+    pub fn long_description(mut self, description: &str) -> Self {
+        self.name_entity_type.long_description = Some(raw::LongDescription {
+            text: description.to_string(),
+        });
+        self
+    }
+
+    /// add a data type
+    pub fn data_type(mut self, data_type: DataTypeBuilder) -> Self {
+        self.data_types.push(data_type);
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name_entity_type.name
+    }
+
+    fn check(&self, checks: &Checks, errors: &mut Vec<BuildError>) {
+        let path = named("Package", self.name());
+        if self.name().is_empty() {
+            errors.push(BuildError {
+                path: path.clone(),
+                kind: BuildErrorKind::EmptyName,
+            });
+        }
+        let mut names = HashSet::new();
+        for data_type in self.data_types.iter() {
+            let path = format!(
+                "{}/DataTypeSet/{}",
+                path,
+                named(data_type.element(), data_type.name())
+            );
+            if !data_type.name().is_empty() && !names.insert(data_type.name()) {
+                errors.push(BuildError {
+                    path: path.clone(),
+                    kind: BuildErrorKind::DuplicateName(data_type.name().to_string()),
+                });
+            }
+            data_type.check(self.name(), &path, checks, errors);
+        }
+    }
+
+    fn build(&self) -> raw::Package {
+        raw::Package {
+            name_entity_type: self.name_entity_type.clone(),
+            data_type_set: (!self.data_types.is_empty()).then(|| raw::DataTypeSet {
+                data_types: self.data_types.iter().map(DataTypeBuilder::build).collect(),
+            }),
+            component_set: None,
+        }
+    }
+}
+
+/// Builder of a data type of any kind
+///
+/// The settings that do not apply to the kind of the data type, like a label of an integer,
+/// are reported by [`PackageFileBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct DataTypeBuilder {
+    data_type: raw::DataType,
+    /// size of an integer or enumeration, for its default range
+    size_in_bits: usize,
+    range: bool,
+    entries: Vec<EntryBuilder>,
+    trailer_entries: Vec<EntryBuilder>,
+    problems: Vec<Problem>,
+}
+
+impl DataTypeBuilder {
+    fn new(data_type: raw::DataType, size_in_bits: usize) -> Self {
+        DataTypeBuilder {
+            data_type,
+            size_in_bits,
+            range: false,
+            entries: Vec::new(),
+            trailer_entries: Vec::new(),
+            problems: Vec::new(),
+        }
+    }
+
+    fn integer_encoding(size_in_bits: usize) -> raw::IntegerDataEncoding {
+        raw::IntegerDataEncoding {
+            size_in_bits: size_in_bits.to_string(),
+            encoding: IntegerEncoding::Unsigned.to_string(),
+            byte_order: Some(ByteOrder::BigEndian.to_string()),
+        }
+    }
+
+    /// an unsigned big endian integer, with the range of its encoding
+    pub fn integer(name: &str, size_in_bits: usize) -> Self {
+        let (min, max) = full_range(&IntegerEncoding::Unsigned, size_in_bits);
+        let mut builder = Self::new(
+            raw::DataType::IntegerDataType(raw::IntegerDataType {
+                name_entity_type: named_entity_type(name),
+                polynomial_calibrator: None,
+                spline_calibrator: None,
+                encoding: Some(Self::integer_encoding(size_in_bits)),
+                range: inclusive_range(min, max),
+            }),
+            size_in_bits,
+        );
+        non_zero(size_in_bits, "sizeInBits", &mut builder.problems);
+        builder
+    }
+
+    /// an enumeration encoded as an unsigned big endian integer, without labels
+    pub fn enumerated(name: &str, size_in_bits: usize) -> Self {
+        let mut builder = Self::new(
+            raw::DataType::EnumeratedDataType(raw::EnumeratedDataType {
+                name_entity_type: named_entity_type(name),
+                encoding: Some(Self::integer_encoding(size_in_bits)),
+                enumeration_list: raw::EnumerationList::default(),
+            }),
+            size_in_bits,
+        );
+        non_zero(size_in_bits, "sizeInBits", &mut builder.problems);
+        builder
+    }
+
+    /// a big endian IEEE 754 float of 32, 64 or 128 bits
+    pub fn float(name: &str, size_in_bits: usize) -> Self {
+        let encoding = match size_in_bits {
+            64 => FloatEncodingAndPrecision::IEEE7542008Double,
+            128 => FloatEncodingAndPrecision::IEEE7542008Quadruple,
+            _ => FloatEncodingAndPrecision::IEEE7542008Single,
+        };
+        let mut builder = Self::new(
+            raw::DataType::FloatDataType(raw::FloatDataType {
+                name_entity_type: named_entity_type(name),
+                polynomial_calibrator: None,
+                spline_calibrator: None,
+                encoding: Some(raw::FloatDataEncoding {
+                    size_in_bits: size_in_bits.to_string(),
+                    byte_order: Some(ByteOrder::BigEndian.to_string()),
+                    encoding_and_precision: encoding.to_string(),
+                }),
+                range: None,
+            }),
+            size_in_bits,
+        );
+        non_zero(size_in_bits, "sizeInBits", &mut builder.problems);
+        builder
+    }
+
+    /// a boolean, false when all its bits are 0
+    pub fn boolean(name: &str, size_in_bits: usize) -> Self {
+        let mut builder = Self::new(
+            raw::DataType::BooleanDataType(raw::BooleanDataType {
+                name_entity_type: named_entity_type(name),
+                encoding: Some(raw::BooleanDataEncoding {
+                    size_in_bits: size_in_bits.to_string(),
+                    false_value: None,
+                }),
+            }),
+            size_in_bits,
+        );
+        non_zero(size_in_bits, "sizeInBits", &mut builder.problems);
+        builder
+    }
+
+    /// an ASCII string of a fixed length in bytes
+    pub fn string(name: &str, length: usize) -> Self {
+        let mut builder = Self::new(
+            raw::DataType::StringDataType(raw::StringDataType {
+                name_entity_type: named_entity_type(name),
+                length: length.to_string(),
+                encoding: Some(raw::StringDataEncoding {
+                    termination_character: None,
+                    encoding: Some(StringEncoding::ASCII.to_string()),
+                }),
+                fixed_length: Some("true".to_string()),
+            }),
+            0,
+        );
+        non_zero(length, "length", &mut builder.problems);
+        builder
+    }
+
+    /// an array of a data type, without dimensions
+    pub fn array(name: &str, element_type: &str) -> Self {
+        Self::new(
+            raw::DataType::ArrayDataType(raw::ArrayDataType {
+                name_entity_type: named_entity_type(name),
+                data_type_ref: element_type.to_string(),
+                dimension_list: raw::DimensionList::default(),
+            }),
+            0,
+        )
+    }
+
+    /// a sub range of a data type, whose range must be set
+    pub fn sub_range(name: &str, base_type: &str) -> Self {
+        Self::new(
+            raw::DataType::SubRangeDataType(raw::SubRangeDataType {
+                base_type: base_type.to_string(),
+                name_entity_type: named_entity_type(name),
+                range: raw::Range::default(),
+                encoding: None,
+            }),
+            0,
+        )
+    }
+
+    /// a container without entries
+    pub fn container(name: &str) -> Self {
+        Self::new(
+            raw::DataType::ContainerDataType(raw::ContainerDataType {
+                name_entity_type: named_entity_type(name),
+                ..raw::ContainerDataType::default()
+            }),
+            0,
+        )
+    }
+
+    /// set the short description
+    pub fn short_description(mut self, description: &str) -> Self {
+        if let Some(named) = self.name_entity_type_mut() {
+            named.short_description = Some(description.to_string());
+        }
+        self
+    }
+
+    /// set the long description
+    pub fn long_description(mut self, description: &str) -> Self {
+        if let Some(named) = self.name_entity_type_mut() {
+            named.long_description = Some(raw::LongDescription {
+                text: description.to_string(),
+            });
+        }
+        self
+    }
+
+    /// set the unit, a term of the core semantics like `meter`
+    pub fn unit(mut self, unit: &str) -> Self {
+        if let Some(named) = self.name_entity_type_mut() {
+            named.semantics.unit = Some(unit.to_string());
+        }
+        self
+    }
+
+    /// set the encoding of an integer or an enumeration; the range of an integer without an
+    /// explicit range follows
+    pub fn encoding(mut self, encoding: IntegerEncoding) -> Self {
+        let (min, max) = full_range(&encoding, self.size_in_bits);
+        match &mut self.data_type {
+            raw::DataType::IntegerDataType(dt) => {
+                if let Some(e) = dt.encoding.as_mut() {
+                    e.encoding = encoding.to_string();
+                }
+                if !self.range {
+                    dt.range = inclusive_range(min, max);
+                }
+            }
+            raw::DataType::EnumeratedDataType(dt) => {
+                if let Some(e) = dt.encoding.as_mut() {
+                    e.encoding = encoding.to_string();
+                }
+            }
+            _ => self.not_applicable("encoding"),
+        }
+        self
+    }
+
+    /// set the byte order of an integer, an enumeration or a float
+    pub fn byte_order(mut self, byte_order: ByteOrder) -> Self {
+        let byte_order = Some(byte_order.to_string());
+        match &mut self.data_type {
+            raw::DataType::IntegerDataType(raw::IntegerDataType {
+                encoding: Some(e), ..
+            })
+            | raw::DataType::EnumeratedDataType(raw::EnumeratedDataType {
+                encoding: Some(e),
+                ..
+            }) => e.byte_order = byte_order,
+            raw::DataType::FloatDataType(raw::FloatDataType {
+                encoding: Some(e), ..
+            }) => e.byte_order = byte_order,
+            _ => self.not_applicable("byte_order"),
+        }
+        self
+    }
+
+    /// set the encoding and precision of a float
+    pub fn float_encoding(mut self, encoding: FloatEncodingAndPrecision) -> Self {
+        match &mut self.data_type {
+            raw::DataType::FloatDataType(raw::FloatDataType {
+                encoding: Some(e), ..
+            }) => e.encoding_and_precision = encoding.to_string(),
+            _ => self.not_applicable("float_encoding"),
+        }
+        self
+    }
+
+    /// set the character encoding of a string
+    pub fn string_encoding(mut self, encoding: StringEncoding) -> Self {
+        match &mut self.data_type {
+            raw::DataType::StringDataType(raw::StringDataType {
+                encoding: Some(e), ..
+            }) => e.encoding = Some(encoding.to_string()),
+            _ => self.not_applicable("string_encoding"),
+        }
+        self
+    }
+
+    /// set whether a string always has its length, or is shorter when terminated
+    pub fn fixed_length(mut self, fixed_length: bool) -> Self {
+        match &mut self.data_type {
+            raw::DataType::StringDataType(dt) => dt.fixed_length = Some(fixed_length.to_string()),
+            _ => self.not_applicable("fixed_length"),
+        }
+        self
+    }
+
+    /// set the inclusive range of an integer, a float or a sub range
+    pub fn range(mut self, min: impl fmt::Display, max: impl fmt::Display) -> Self {
+        let range = inclusive_range(min, max);
+        match &mut self.data_type {
+            raw::DataType::IntegerDataType(dt) => dt.range = range,
+            raw::DataType::FloatDataType(dt) => dt.range = Some(range),
+            raw::DataType::SubRangeDataType(dt) => dt.range = range,
+            _ => self.not_applicable("range"),
+        }
+        self.range = true;
+        self
+    }
+
+    /// add a label of an enumeration
+    pub fn label(mut self, label: &str, value: impl fmt::Display) -> Self {
+        match &mut self.data_type {
+            raw::DataType::EnumeratedDataType(dt) => {
+                dt.enumeration_list.enumeration.push(raw::Enumeration {
+                    label: label.to_string(),
+                    value: value.to_string(),
+                    ..raw::Enumeration::default()
+                })
+            }
+            _ => self.not_applicable("label"),
+        }
+        self
+    }
+
+    /// add a dimension of an array
+    pub fn dimension(mut self, size: usize) -> Self {
+        match &mut self.data_type {
+            raw::DataType::ArrayDataType(dt) => {
+                dt.dimension_list.dimension.push(raw::Dimension {
+                    size: size.to_string(),
+                });
+                non_zero(size, "Dimension size", &mut self.problems);
+            }
+            _ => self.not_applicable("dimension"),
+        }
+        self
+    }
+
+    /// make a container abstract, to be used only as a base type
+    pub fn abstract_(mut self) -> Self {
+        match &mut self.data_type {
+            raw::DataType::ContainerDataType(dt) => dt._abstract = Some("true".to_string()),
+            _ => self.not_applicable("abstract_"),
+        }
+        self
+    }
+
+    /// set the base type of a container
+    pub fn base_type(mut self, base_type: &str) -> Self {
+        match &mut self.data_type {
+            raw::DataType::ContainerDataType(dt) => dt.base_type = Some(base_type.to_string()),
+            _ => self.not_applicable("base_type"),
+        }
+        self
+    }
+
+    /// add an entry of a container
+    pub fn entry(mut self, entry: EntryBuilder) -> Self {
+        match self.data_type {
+            raw::DataType::ContainerDataType(_) => self.entries.push(entry),
+            _ => self.not_applicable("entry"),
+        }
+        self
+    }
+
+    /// add a trailer entry of a container
+    pub fn trailer_entry(mut self, entry: EntryBuilder) -> Self {
+        match self.data_type {
+            raw::DataType::ContainerDataType(_) => self.trailer_entries.push(entry),
+            _ => self.not_applicable("trailer_entry"),
+        }
+        self
+    }
+
+    /// constrain an entry of the base type of a container to an inclusive range
+    pub fn range_constraint(
+        self,
+        entry: &str,
+        min: impl fmt::Display,
+        max: impl fmt::Display,
+    ) -> Self {
+        self.constraint(
+            "range_constraint",
+            raw::Constraint::RangeConstraint(raw::RangeConstraint {
+                range: inclusive_range(min, max),
+                entry: entry.to_string(),
+            }),
+        )
+    }
+
+    /// constrain an entry of the base type of a container to a data type
+    pub fn type_constraint(self, entry: &str, type_: &str) -> Self {
+        self.constraint(
+            "type_constraint",
+            raw::Constraint::TypeConstraint(raw::TypeConstraint {
+                type_: type_.to_string(),
+                entry: entry.to_string(),
+            }),
+        )
+    }
+
+    /// constrain an entry of the base type of a container to a value
+    pub fn value_constraint(self, entry: &str, value: impl fmt::Display) -> Self {
+        self.constraint(
+            "value_constraint",
+            raw::Constraint::ValueConstraint(raw::ValueConstraint {
+                value: value.to_string(),
+                entry: entry.to_string(),
+            }),
+        )
+    }
+
+    fn constraint(mut self, setting: &'static str, constraint: raw::Constraint) -> Self {
+        match &mut self.data_type {
+            raw::DataType::ContainerDataType(dt) => dt
+                .constraint_set
+                .get_or_insert_with(raw::ConstraintSet::default)
+                .constraints
+                .push(constraint),
+            _ => self.not_applicable(setting),
+        }
+        self
+    }
+
+    fn not_applicable(&mut self, setting: &'static str) {
+        self.problems.push(Problem::NotApplicable {
+            setting,
+            element: self.element(),
+        });
+    }
+
+    fn element(&self) -> &'static str {
+        match &self.data_type {
+            raw::DataType::NoneDataType => "DataType",
+            raw::DataType::BooleanDataType(_) => "BooleanDataType",
+            raw::DataType::IntegerDataType(_) => "IntegerDataType",
+            raw::DataType::ArrayDataType(_) => "ArrayDataType",
+            raw::DataType::EnumeratedDataType(_) => "EnumeratedDataType",
+            raw::DataType::ContainerDataType(_) => "ContainerDataType",
+            raw::DataType::FloatDataType(_) => "FloatDataType",
+            raw::DataType::StringDataType(_) => "StringDataType",
+            raw::DataType::SubRangeDataType(_) => "SubRangeDataType",
+        }
+    }
+
+    fn name_entity_type(&self) -> Option<&raw::NamedEntityType> {
+        match &self.data_type {
+            raw::DataType::NoneDataType => None,
+            raw::DataType::BooleanDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::IntegerDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::ArrayDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::EnumeratedDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::ContainerDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::FloatDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::StringDataType(dt) => Some(&dt.name_entity_type),
+            raw::DataType::SubRangeDataType(dt) => Some(&dt.name_entity_type),
+        }
+    }
+
+    fn name_entity_type_mut(&mut self) -> Option<&mut raw::NamedEntityType> {
+        match &mut self.data_type {
+            raw::DataType::NoneDataType => None,
+            raw::DataType::BooleanDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::IntegerDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::ArrayDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::EnumeratedDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::ContainerDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::FloatDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::StringDataType(dt) => Some(&mut dt.name_entity_type),
+            raw::DataType::SubRangeDataType(dt) => Some(&mut dt.name_entity_type),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name_entity_type().map_or("", |named| &named.name)
+    }
+
+    fn check(&self, package: &str, path: &str, checks: &Checks, errors: &mut Vec<BuildError>) {
+        let error = |kind| BuildError {
+            path: path.to_string(),
+            kind,
+        };
+        if self.name().is_empty() {
+            errors.push(error(BuildErrorKind::EmptyName));
+        }
+        for problem in self.problems.iter() {
+            errors.push(error((*problem).into()));
+        }
+        match &self.data_type {
+            raw::DataType::EnumeratedDataType(dt) => {
+                let labels = &dt.enumeration_list.enumeration;
+                if labels.is_empty() {
+                    errors.push(error(BuildErrorKind::Missing("Enumeration")));
+                }
+                let mut names = HashSet::new();
+                for label in labels.iter().filter(|l| !names.insert(l.label.as_str())) {
+                    errors.push(error(BuildErrorKind::DuplicateName(label.label.clone())));
+                }
+            }
+            raw::DataType::ArrayDataType(dt) => {
+                if dt.dimension_list.dimension.is_empty() {
+                    errors.push(error(BuildErrorKind::Missing("Dimension")));
+                }
+                checks.type_ref(package, &dt.data_type_ref, path, errors);
+            }
+            raw::DataType::SubRangeDataType(dt) => {
+                if !self.range {
+                    errors.push(error(BuildErrorKind::Missing("Range")));
+                }
+                checks.type_ref(package, &dt.base_type, path, errors);
+            }
+            raw::DataType::ContainerDataType(dt) => {
+                if let Some(base_type) = &dt.base_type {
+                    checks.type_ref(package, base_type, path, errors);
+                }
+                if dt.constraint_set.is_some() && dt.base_type.is_none() {
+                    errors.push(error(BuildErrorKind::NoBaseType));
+                }
+                for constraint in dt.constraint_set.iter().flat_map(|cs| &cs.constraints) {
+                    if let raw::Constraint::TypeConstraint(c) = constraint {
+                        let path = format!(
+                            "{}/ConstraintSet/{}",
+                            path,
+                            named("TypeConstraint", &c.entry)
+                        );
+                        checks.type_ref(package, &c.type_, &path, errors);
+                    }
+                }
+                let mut names = HashSet::new();
+                let lists = [
+                    ("EntryList", &self.entries),
+                    ("TrailerEntryList", &self.trailer_entries),
+                ];
+                for (list, entries) in lists {
+                    let mut paddings = 0;
+                    for entry in entries.iter() {
+                        let path = format!("{}/{}/{}", path, list, entry.segment(paddings));
+                        if let raw::EntryElement::PaddingEntry(_) = entry.entry {
+                            paddings += 1;
+                        }
+                        if !entry.name().is_empty() && !names.insert(entry.name()) {
+                            errors.push(BuildError {
+                                path: path.clone(),
+                                kind: BuildErrorKind::DuplicateName(entry.name().to_string()),
+                            });
+                        }
+                        entry.check(package, &path, checks, errors);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn build(&self) -> raw::DataType {
+        let mut data_type = self.data_type.clone();
+        if let raw::DataType::ContainerDataType(dt) = &mut data_type {
+            let list = |entries: &[EntryBuilder]| {
+                (!entries.is_empty()).then(|| raw::EntryList {
+                    entries: entries.iter().map(|e| e.entry.clone()).collect(),
+                })
+            };
+            dt.entry_list = list(&self.entries);
+            dt.trailer_entry_list = list(&self.trailer_entries);
+        }
+        data_type
+    }
+}
+
+/// Builder of an entry of a container
+#[derive(Debug, Clone)]
+pub struct EntryBuilder {
+    entry: raw::EntryElement,
+    problems: Vec<Problem>,
+}
+
+impl EntryBuilder {
+    fn new(entry: raw::EntryElement) -> Self {
+        EntryBuilder {
+            entry,
+            problems: Vec::new(),
+        }
+    }
+
+    /// an entry of a data type
+    pub fn entry(name: &str, type_: &str) -> Self {
+        Self::new(raw::EntryElement::Entry(raw::Entry {
+            name_entity_type: named_entity_type(name),
+            type_: type_.to_string(),
+        }))
+    }
+
+    /// an entry whose value is fixed, like the identifier of a packet
+    pub fn fixed_value(name: &str, type_: &str, value: impl fmt::Display) -> Self {
+        Self::new(raw::EntryElement::FixedValueEntry(raw::FixedValueEntry {
+            name_entity_type: named_entity_type(name),
+            type_: type_.to_string(),
+            fixed_value: value.to_string(),
+        }))
+    }
+
+    /// unused bits
+    pub fn padding(size_in_bits: usize) -> Self {
+        let mut builder = Self::new(raw::EntryElement::PaddingEntry(raw::PaddingEntry {
+            size_in_bits: size_in_bits.to_string(),
+            short_description: None,
+        }));
+        non_zero(size_in_bits, "sizeInBits", &mut builder.problems);
+        builder
+    }
+
+    /// an entry holding the length of the container
+    pub fn length(name: &str, type_: &str) -> Self {
+        Self::new(raw::EntryElement::LengthEntry(raw::LengthEntry {
+            name_entity_type: named_entity_type(name),
+            type_: type_.to_string(),
+            calibration: None,
+        }))
+    }
+
+    /// an entry holding a checksum or a CRC of the container
+    pub fn error_control(name: &str, type_: &str, error_control_type: ErrorControlType) -> Self {
+        Self::new(raw::EntryElement::ErrorControlEntry(
+            raw::ErrorControlEntry {
+                name_entity_type: named_entity_type(name),
+                type_: type_.to_string(),
+                error_control_type: error_control_type.to_string(),
+            },
+        ))
+    }
+
+    /// a list of elements of a data type, as many as the value of another entry
+    pub fn list(name: &str, type_: &str, length_field: &str) -> Self {
+        Self::new(raw::EntryElement::ListEntry(raw::ListEntry {
+            name_entity_type: named_entity_type(name),
+            type_: type_.to_string(),
+            list_length_field: length_field.to_string(),
+        }))
+    }
+
+    /// set the short description
+    pub fn short_description(mut self, description: &str) -> Self {
+        match self.name_entity_type_mut() {
+            Some(named) => named.short_description = Some(description.to_string()),
+            None => {
+                if let raw::EntryElement::PaddingEntry(e) = &mut self.entry {
+                    e.short_description = Some(description.to_string());
+                }
+            }
+        }
+        self
+    }
+
+    /// set the long description
+    pub fn long_description(mut self, description: &str) -> Self {
+        match self.name_entity_type_mut() {
+            Some(named) => {
+                named.long_description = Some(raw::LongDescription {
+                    text: description.to_string(),
+                })
+            }
+            None => self.not_applicable("long_description"),
+        }
+        self
+    }
+
+    /// set the unit, a term of the core semantics like `meter`
+    pub fn unit(mut self, unit: &str) -> Self {
+        match self.name_entity_type_mut() {
+            Some(named) => named.semantics.unit = Some(unit.to_string()),
+            None => self.not_applicable("unit"),
+        }
+        self
+    }
+
+    fn not_applicable(&mut self, setting: &'static str) {
+        self.problems.push(Problem::NotApplicable {
+            setting,
+            element: self.element(),
+        });
+    }
+
+    fn element(&self) -> &'static str {
+        match &self.entry {
+            raw::EntryElement::Entry(_) => "Entry",
+            raw::EntryElement::FixedValueEntry(_) => "FixedValueEntry",
+            raw::EntryElement::PaddingEntry(_) => "PaddingEntry",
+            raw::EntryElement::ListEntry(_) => "ListEntry",
+            raw::EntryElement::LengthEntry(_) => "LengthEntry",
+            raw::EntryElement::ErrorControlEntry(_) => "ErrorControlEntry",
+        }
+    }
+
+    fn name_entity_type_mut(&mut self) -> Option<&mut raw::NamedEntityType> {
+        match &mut self.entry {
+            raw::EntryElement::Entry(e) => Some(&mut e.name_entity_type),
+            raw::EntryElement::FixedValueEntry(e) => Some(&mut e.name_entity_type),
+            raw::EntryElement::PaddingEntry(_) => None,
+            raw::EntryElement::ListEntry(e) => Some(&mut e.name_entity_type),
+            raw::EntryElement::LengthEntry(e) => Some(&mut e.name_entity_type),
+            raw::EntryElement::ErrorControlEntry(e) => Some(&mut e.name_entity_type),
+        }
+    }
+
+    /// name of the entry, empty for a padding entry
+    fn name(&self) -> &str {
+        match &self.entry {
+            raw::EntryElement::Entry(e) => &e.name_entity_type.name,
+            raw::EntryElement::FixedValueEntry(e) => &e.name_entity_type.name,
+            raw::EntryElement::PaddingEntry(_) => "",
+            raw::EntryElement::ListEntry(e) => &e.name_entity_type.name,
+            raw::EntryElement::LengthEntry(e) => &e.name_entity_type.name,
+            raw::EntryElement::ErrorControlEntry(e) => &e.name_entity_type.name,
+        }
+    }
+
+    /// segment of the entry, by its position among the padding entries of its list if it is one
+    fn segment(&self, padding_index: usize) -> String {
+        match self.entry {
+            raw::EntryElement::PaddingEntry(_) => format!("PaddingEntry[{}]", padding_index),
+            _ => named(self.element(), self.name()),
+        }
+    }
+
+    fn check(&self, package: &str, path: &str, checks: &Checks, errors: &mut Vec<BuildError>) {
+        let error = |kind| BuildError {
+            path: path.to_string(),
+            kind,
+        };
+        let type_ = match &self.entry {
+            raw::EntryElement::Entry(e) => &e.type_,
+            raw::EntryElement::FixedValueEntry(e) => &e.type_,
+            raw::EntryElement::PaddingEntry(_) => "",
+            raw::EntryElement::ListEntry(e) => &e.type_,
+            raw::EntryElement::LengthEntry(e) => &e.type_,
+            raw::EntryElement::ErrorControlEntry(e) => &e.type_,
+        };
+        if !matches!(self.entry, raw::EntryElement::PaddingEntry(_)) {
+            if self.name().is_empty() {
+                errors.push(error(BuildErrorKind::EmptyName));
+            }
+            checks.type_ref(package, type_, path, errors);
+        }
+        for problem in self.problems.iter() {
+            errors.push(error((*problem).into()));
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Extensions of User-Defined DoTs
+//!
+//! A user DoT (Req 3.2.6) adds attributes and elements in its own namespace to SEDS elements,
+//! e.g. the telemetry priority of a data type:
+//!
+//! ```xml
+//! <IntegerDataType name="Temperature" tlm:priority="2" xmlns:tlm="urn:example:tlm">
+//! ```
+//!
+//! The models keep them as text, in document order. Their meaning is given by the handlers of
+//! an [`ExtensionRegistry`], which read them into typed [`ExtensionValue`]s for the code
+//! generator and the docs. Extensions without a handler are kept in the models and written
+//! back, but are not part of the generated code.
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use proc_macro2::TokenStream;
+
+/// An attribute in the namespace of a user DoT
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExtensionAttribute {
+    /// namespace of the attribute, empty for the attributes of extension elements that have
+    /// none
+    pub namespace: String,
+    /// local name of the attribute
+    pub name: String,
+    /// value of the attribute, as written
+    pub value: String,
+}
+
+/// An element in the namespace of a user DoT, with its content
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExtensionElement {
+    /// namespace of the element
+    pub namespace: String,
+    /// local name of the element
+    pub name: String,
+    /// attributes of the element, in any namespace
+    pub attributes: Vec<ExtensionAttribute>,
+    /// text content, without surrounding whitespace
+    pub text: String,
+    /// child elements, in any namespace
+    pub children: Vec<ExtensionElement>,
+}
+
+impl ExtensionElement {
+    /// value of an attribute
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+/// The extension attributes and elements of an element
+#[allow(missing_docs)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Extensions {
+    pub attributes: Vec<ExtensionAttribute>,
+    pub elements: Vec<ExtensionElement>,
+}
+
+impl Extensions {
+    /// true if the element has no extensions
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.elements.is_empty()
+    }
+
+    /// value of an extension attribute
+    pub fn attribute(&self, namespace: &str, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.namespace == namespace && attribute.name == name)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    /// the first extension element of a name
+    pub fn element(&self, namespace: &str, name: &str) -> Option<&ExtensionElement> {
+        self.elements
+            .iter()
+            .find(|element| element.namespace == namespace && element.name == name)
+    }
+}
+
+/// A typed value of an extension, read by the handler registered for it
+pub trait ExtensionValue: Any + fmt::Debug + Send + Sync {
+    /// the value in the rustdoc of the generated code, like `- Telemetry Priority: 2`
+    fn to_docstring(&self) -> String;
+
+    /// type and value of an associated constant of the generated type, named after the
+    /// extension (e.g. `PRIORITY`), if the value has one
+    fn to_rust_const(&self) -> Option<(TokenStream, TokenStream)> {
+        None
+    }
+}
+
+/// reads the text of an extension into a typed value
+type Handler<T> = Arc<dyn Fn(&T) -> Result<Box<dyn ExtensionValue>, String> + Send + Sync>;
+
+/// A typed extension value, with the extension it was read from
+#[derive(Debug)]
+pub struct TypedExtension {
+    /// local name of the attribute or element
+    pub name: String,
+    /// path of the attribute or element relative to the extended element, see
+    /// [`crate::diagnostic`]
+    pub path: String,
+    /// the value the handler read
+    pub value: Box<dyn ExtensionValue>,
+}
+
+/// An extension that its handler could not read
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionError {
+    /// path of the attribute or element relative to the extended element, see
+    /// [`crate::diagnostic`]
+    pub path: String,
+    /// what the handler reported
+    pub message: String,
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid extension {}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ExtensionError {}
+
+/// Handlers of the extensions of user DoTs, by namespace and name
+///
+/// ```
+/// use seds_rs::eds::extension::{ExtensionRegistry, ExtensionValue};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Priority(u8);
+///
+/// impl ExtensionValue for Priority {
+///     fn to_docstring(&self) -> String {
+///         format!("- Telemetry Priority: {}", self.0)
+///     }
+/// }
+///
+/// let registry = ExtensionRegistry::new().attribute("urn:example:tlm", "priority", |value| {
+///     value.parse().map(Priority).map_err(|e| e.to_string())
+/// });
+/// assert!(registry.handles_attribute("urn:example:tlm", "priority"));
+/// ```
+#[derive(Clone, Default)]
+pub struct ExtensionRegistry {
+    attributes: BTreeMap<(String, String), Handler<str>>,
+    elements: BTreeMap<(String, String), Handler<ExtensionElement>>,
+}
+
+impl fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtensionRegistry")
+            .field("attributes", &self.attributes.keys().collect::<Vec<_>>())
+            .field("elements", &self.elements.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// the registry of code generated without extension handlers
+static EMPTY: ExtensionRegistry = ExtensionRegistry {
+    attributes: BTreeMap::new(),
+    elements: BTreeMap::new(),
+};
+
+impl ExtensionRegistry {
+    /// a registry without handlers
+    pub fn new() -> Self {
+        ExtensionRegistry::default()
+    }
+
+    /// a shared registry without handlers
+    pub fn empty() -> &'static Self {
+        &EMPTY
+    }
+
+    /// register the handler of an extension attribute, it reads the value of the attribute
+    pub fn attribute<T, F>(mut self, namespace: &str, name: &str, handler: F) -> Self
+    where
+        T: ExtensionValue,
+        F: Fn(&str) -> Result<T, String> + Send + Sync + 'static,
+    {
+        let handler: Handler<str> = Arc::new(move |value| {
+            handler(value).map(|value| Box::new(value) as Box<dyn ExtensionValue>)
+        });
+        let _ = self
+            .attributes
+            .insert((namespace.to_string(), name.to_string()), handler);
+        self
+    }
+
+    /// register the handler of an extension element, it reads the element with its content
+    pub fn element<T, F>(mut self, namespace: &str, name: &str, handler: F) -> Self
+    where
+        T: ExtensionValue,
+        F: Fn(&ExtensionElement) -> Result<T, String> + Send + Sync + 'static,
+    {
+        let handler: Handler<ExtensionElement> = Arc::new(move |element| {
+            handler(element).map(|value| Box::new(value) as Box<dyn ExtensionValue>)
+        });
+        let _ = self
+            .elements
+            .insert((namespace.to_string(), name.to_string()), handler);
+        self
+    }
+
+    /// true if an extension attribute has a handler
+    pub fn handles_attribute(&self, namespace: &str, name: &str) -> bool {
+        self.attributes
+            .contains_key(&(namespace.to_string(), name.to_string()))
+    }
+
+    /// true if an extension element has a handler
+    pub fn handles_element(&self, namespace: &str, name: &str) -> bool {
+        self.elements
+            .contains_key(&(namespace.to_string(), name.to_string()))
+    }
+
+    /// the typed values of the extensions that have a handler, attributes first, in document
+    /// order; the others are skipped
+    pub fn values(&self, extensions: &Extensions) -> Result<Vec<TypedExtension>, ExtensionError> {
+        let mut values = Vec::new();
+        for attribute in extensions.attributes.iter() {
+            let key = (attribute.namespace.clone(), attribute.name.clone());
+            if let Some(handler) = self.attributes.get(&key) {
+                let path = format!("@{}", attribute.name);
+                let value = handler(&attribute.value).map_err(|message| ExtensionError {
+                    path: path.clone(),
+                    message,
+                })?;
+                values.push(TypedExtension {
+                    name: attribute.name.clone(),
+                    path,
+                    value,
+                });
+            }
+        }
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for element in extensions.elements.iter() {
+            // elements are named like the source map of the parser does
+            let count = counts.entry(element.name.as_str()).or_insert(0);
+            let path = match *count {
+                0 => element.name.clone(),
+                index => format!("{}[{}]", element.name, index),
+            };
+            *count += 1;
+            let key = (element.namespace.clone(), element.name.clone());
+            if let Some(handler) = self.elements.get(&key) {
+                let value = handler(element).map_err(|message| ExtensionError {
+                    path: path.clone(),
+                    message,
+                })?;
+                values.push(TypedExtension {
+                    name: element.name.clone(),
+                    path,
+                    value,
+                });
+            }
+        }
+        Ok(values)
+    }
+
+    /// the typed value of an extension attribute, if it is present and has a handler that
+    /// reads it as a `T`
+    pub fn attribute_value<T: ExtensionValue>(
+        &self,
+        extensions: &Extensions,
+        namespace: &str,
+        name: &str,
+    ) -> Option<Result<T, String>> {
+        let value = extensions.attribute(namespace, name)?;
+        let handler = self
+            .attributes
+            .get(&(namespace.to_string(), name.to_string()))?;
+        downcast(handler(value))
+    }
+
+    /// the typed value of the first extension element of a name, if it is present and has a
+    /// handler that reads it as a `T`
+    pub fn element_value<T: ExtensionValue>(
+        &self,
+        extensions: &Extensions,
+        namespace: &str,
+        name: &str,
+    ) -> Option<Result<T, String>> {
+        let element = extensions.element(namespace, name)?;
+        let handler = self
+            .elements
+            .get(&(namespace.to_string(), name.to_string()))?;
+        downcast(handler(element))
+    }
+}
+
+/// the value of a handler as a `T`, if it reads values of that type
+fn downcast<T: ExtensionValue>(
+    value: Result<Box<dyn ExtensionValue>, String>,
+) -> Option<Result<T, String>> {
+    match value {
+        Ok(value) => (value as Box<dyn Any>)
+            .downcast()
+            .ok()
+            .map(|value| Ok(*value)),
+        Err(message) => Some(Err(message)),
+    }
+}
@@ -0,0 +1,213 @@
+//! Structural Fingerprints of Data Types
+//!
+//! The fingerprint of a data type is a 64-bit FNV-1a hash of what its bits mean: the
+//! [layout](super::layout) of a container with the names, sizes, encodings and fixed values
+//! of its fields, or the encoding of any other type, with the labels of enumerations. The
+//! types an entry refers to are part of the layout, so a change to a dependency changes the
+//! fingerprint of every type that encodes it. Names of data types, descriptions and semantic
+//! terms are not hashed, so that a fingerprint identifies the encoding of packets, e.g. in a
+//! telemetry archive, across datasheet versions that only document it better.
+//!
+//! Ranges are not hashed either: a range restricts which values a sender may put in a field,
+//! but not how the bits of a packet are read. Narrowing the range of a speed from 0..1000 to
+//! 0..500 leaves every archived packet decodable as before, so it keeps the fingerprint;
+//! ValueConstraints of derived containers do change how packets are told apart, and they are
+//! hashed as the fixed values of the fields.
+//!
+//! Fingerprints are stable across runs, platforms and versions of this library unless the
+//! encoding of a type changes. Generated code exposes them as the `FINGERPRINT` associated
+//! constant of each type.
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::{self, Write};
+
+use super::ast::{DataType, Package, PackageFile};
+use super::layout::{Encoding, Field, LayoutError, Layouts};
+
+/// offset basis of the 64-bit FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// prime of the 64-bit FNV-1a hash
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A data type whose fingerprint is unknown, because its layout is
+#[derive(Debug, Clone, PartialEq)]
+pub struct FingerprintError {
+    /// name of the package of the data type
+    pub package: String,
+    /// XML element of the data type, like `ContainerDataType`
+    pub element: &'static str,
+    /// name of the data type
+    pub name: String,
+    /// why the layout of the data type is unknown
+    pub error: LayoutError,
+}
+
+impl fmt::Display for FingerprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no fingerprint of `{}/{}`: {}",
+            self.package, self.name, self.error
+        )
+    }
+}
+
+impl std::error::Error for FingerprintError {}
+
+/// The fingerprints of the data types of a set of package files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Fingerprints {
+    fingerprints: BTreeMap<String, u64>,
+}
+
+impl Fingerprints {
+    /// the fingerprints of every data type whose encoding is known, e.g. whose entries refer
+    /// to types of the package files
+    pub fn new(package_files: &[&PackageFile]) -> Self {
+        Self::collect(package_files, |_| Ok::<(), Infallible>(()))
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// the fingerprints of every data type, or an error for the first data type whose
+    /// encoding is unknown
+    pub fn try_new(package_files: &[&PackageFile]) -> Result<Self, FingerprintError> {
+        Self::collect(package_files, Err)
+    }
+
+    fn collect<E>(
+        package_files: &[&PackageFile],
+        on_error: impl Fn(FingerprintError) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        let layouts = Layouts::new(package_files);
+        let mut fingerprints = BTreeMap::new();
+        for package in layouts.packages() {
+            for data_type in package.data_type_set.data_types.iter() {
+                match fingerprint(&layouts, package, data_type) {
+                    Ok(fingerprint) => {
+                        let name =
+                            format!("{}/{}", package.name_entity_type.name.0, data_type.name());
+                        let _ = fingerprints.insert(name, fingerprint);
+                    }
+                    Err(error) => on_error(FingerprintError {
+                        package: package.name_entity_type.name.0.clone(),
+                        element: data_type.element(),
+                        name: data_type.name().to_string(),
+                        error,
+                    })?,
+                }
+            }
+        }
+        Ok(Fingerprints { fingerprints })
+    }
+
+    /// the fingerprint of a data type by its qualified name, like `CCSDS/PriHdr`
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.fingerprints.get(name).copied()
+    }
+
+    /// the qualified names of the data types and their fingerprints, by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.fingerprints
+            .iter()
+            .map(|(name, fingerprint)| (name.as_str(), *fingerprint))
+    }
+}
+
+/// the fingerprint of a data type of a package
+pub fn fingerprint<'a>(
+    layouts: &Layouts<'a>,
+    package: &'a Package,
+    data_type: &'a DataType,
+) -> Result<u64, LayoutError> {
+    let mut canonical = String::new();
+    match data_type {
+        DataType::ContainerDataType(container) => {
+            let layout = layouts.container(package, container)?;
+            canonical.push_str("container");
+            write_fields(&layout.fields, &mut canonical);
+        }
+        data_type => {
+            let (encoding, size) = layouts.encoding(package, data_type)?;
+            write_size(size, &mut canonical);
+            write_encoding(&encoding, &mut canonical);
+        }
+    }
+    Ok(fnv1a(canonical.as_bytes()))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn write_size(size: Option<usize>, out: &mut String) {
+    match size {
+        Some(size) => {
+            let _ = write!(out, "{}:", size);
+        }
+        None => out.push_str("?:"),
+    }
+}
+
+/// fields as `{name:size:encoding=value;...}`; offsets follow from the sizes
+fn write_fields(fields: &[Field], out: &mut String) {
+    out.push('{');
+    for field in fields {
+        let _ = write!(out, "{}:", field.name);
+        write_size(field.size_in_bits, out);
+        write_encoding(&field.encoding, out);
+        if let Some(value) = &field.fixed_value {
+            let _ = write!(out, "={}", value);
+        }
+        out.push(';');
+    }
+    out.push('}');
+}
+
+/// an encoding with the labels of enumerations and the fields of containers, spelled as
+/// in a datasheet so that the text does not depend on messages
+fn write_encoding(encoding: &Encoding, out: &mut String) {
+    let _ = match encoding {
+        Encoding::Integer {
+            encoding,
+            byte_order,
+        } => write!(out, "integer:{}:{}", encoding, byte_order),
+        Encoding::Enumerated {
+            encoding,
+            byte_order,
+            labels,
+        } => {
+            let _ = write!(out, "enumeration:{}:{}[", encoding, byte_order);
+            for (label, value) in labels {
+                let _ = write!(out, "{}={};", label, value);
+            }
+            write!(out, "]")
+        }
+        Encoding::Float {
+            encoding,
+            byte_order,
+        } => write!(out, "float:{}:{}", encoding, byte_order),
+        Encoding::Boolean { false_value } => write!(out, "boolean:{}", false_value),
+        Encoding::String {
+            encoding,
+            fixed_length,
+        } => write!(out, "string:{}:{}", encoding, fixed_length),
+        Encoding::Array {
+            dimensions,
+            element,
+        } => {
+            let _ = write!(out, "array{:?}:", dimensions);
+            write_encoding(element, out);
+            Ok(())
+        }
+        Encoding::Container(fields) => {
+            out.push_str("container");
+            write_fields(fields, out);
+            Ok(())
+        }
+        Encoding::List { length_field } => write!(out, "list:{}", length_field),
+        Encoding::Padding => write!(out, "padding"),
+    };
+}
@@ -0,0 +1,497 @@
+//! Bit Layout of Containers
+//!
+//! The layout of a container lists the fields of its encoding in order: the entries of its
+//! base containers, root first, then its own entries, then its trailer entries and those of
+//! its base containers. Entries of container types are flattened into the fields of their
+//! entries, named by dotted paths like `Hdr.Mode`, and the type and value constraints of
+//! derived containers apply to the entries they name. Offsets are known up to the first
+//! field of variable size, like a list or a string that is not of fixed length.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::ast::{
+    ByteOrder, Constraint, ContainerDataType, DataType, EntryElement, FloatEncodingAndPrecision,
+    IntegerEncoding, Package, PackageFile, StringEncoding,
+};
+
+/// depth of base types and nested types beyond which they are considered cyclic
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// Errors of the layout of a container
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutError {
+    UnknownType(String),
+    NotAContainer(String),
+    TooDeep(String),
+    Overflow(String),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::UnknownType(name) => write!(f, "unknown type `{}`", name),
+            LayoutError::NotAContainer(name) => {
+                write!(f, "base type `{}` is not a container", name)
+            }
+            LayoutError::TooDeep(name) => write!(
+                f,
+                "types nested too deeply in `{}`, they may be cyclic",
+                name
+            ),
+            LayoutError::Overflow(name) => write!(f, "size of `{}` overflows", name),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Encoding of a field
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Encoding {
+    /// an integer
+    Integer {
+        encoding: IntegerEncoding,
+        byte_order: ByteOrder,
+    },
+    /// an integer, with the labels of its values
+    Enumerated {
+        encoding: IntegerEncoding,
+        byte_order: ByteOrder,
+        labels: Vec<(String, String)>,
+    },
+    /// a floating point number
+    Float {
+        encoding: FloatEncodingAndPrecision,
+        byte_order: ByteOrder,
+    },
+    /// a boolean
+    Boolean { false_value: bool },
+    /// a string
+    String {
+        encoding: StringEncoding,
+        fixed_length: bool,
+    },
+    /// an array of elements
+    Array {
+        dimensions: Vec<usize>,
+        element: Box<Encoding>,
+    },
+    /// the fields of a container, as the elements of an array
+    Container(Vec<Field>),
+    /// the elements of a ListEntry, as many as the value of its length field
+    List { length_field: String },
+    /// bits that are not used
+    Padding,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Integer {
+                encoding,
+                byte_order,
+            } => write!(f, "{} {} integer", encoding, byte_order),
+            Encoding::Enumerated {
+                encoding,
+                byte_order,
+                ..
+            } => write!(f, "{} {} enumeration", encoding, byte_order),
+            Encoding::Float {
+                encoding,
+                byte_order,
+            } => write!(f, "{} {} float", encoding, byte_order),
+            Encoding::Boolean { false_value: true } => f.write_str("boolean, false when zero"),
+            Encoding::Boolean { false_value: false } => f.write_str("boolean, false when not zero"),
+            Encoding::String {
+                encoding,
+                fixed_length: true,
+            } => write!(f, "fixed length {} string", encoding),
+            Encoding::String { encoding, .. } => write!(f, "{} string", encoding),
+            Encoding::Array {
+                dimensions,
+                element,
+            } => {
+                let dimensions: Vec<_> = dimensions.iter().map(|d| d.to_string()).collect();
+                write!(f, "array [{}] of {}", dimensions.join(", "), element)
+            }
+            Encoding::Container(_) => f.write_str("container"),
+            Encoding::List { length_field } => write!(f, "list of length `{}`", length_field),
+            Encoding::Padding => f.write_str("padding"),
+        }
+    }
+}
+
+/// A field of a container layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// dotted path of the entry in the container, empty for padding
+    pub name: String,
+    /// qualified name of the type of the entry, none for padding and lists
+    pub type_: Option<String>,
+    /// offset from the start of the container, unknown after a field of variable size
+    pub offset_in_bits: Option<usize>,
+    /// size of the field, unknown if it is variable
+    pub size_in_bits: Option<usize>,
+    /// encoding of the field
+    pub encoding: Encoding,
+    /// value of a FixedValueEntry, or set by a ValueConstraint of a derived container
+    pub fixed_value: Option<String>,
+    /// shortDescription of the entry
+    pub short_description: Option<String>,
+}
+
+/// Layout of a container
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    /// the fields of the container, in the order they are encoded
+    pub fields: Vec<Field>,
+    /// size of the container, unknown if a field has a variable size
+    pub size_in_bits: Option<usize>,
+}
+
+impl Layout {
+    /// a field by its dotted path
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A constraint of a derived container on an entry of its base containers
+#[derive(Debug, Clone)]
+struct Override<'a> {
+    package: &'a Package,
+    type_: Option<&'a str>,
+    value: Option<&'a str>,
+}
+
+/// The packages to compute layouts with, to look up the types of entries
+#[derive(Debug, Clone)]
+pub struct Layouts<'a> {
+    packages: Vec<&'a Package>,
+}
+
+impl<'a> Layouts<'a> {
+    /// the layouts of the types of a set of package files
+    pub fn new(package_files: &[&'a PackageFile]) -> Self {
+        Layouts {
+            packages: package_files
+                .iter()
+                .flat_map(|package_file| package_file.package.iter())
+                .collect(),
+        }
+    }
+
+    /// the packages of all the files
+    pub fn packages(&self) -> &[&'a Package] {
+        &self.packages
+    }
+
+    /// a data type by its qualified name, relative to the package it is referenced from
+    pub fn lookup(&self, package: &'a Package, name: &str) -> Option<(&'a Package, &'a DataType)> {
+        let (package, name) = match name.rsplit_once('/') {
+            Some((package_name, name)) => (
+                *self
+                    .packages
+                    .iter()
+                    .find(|p| p.name_entity_type.name.0 == package_name)?,
+                name,
+            ),
+            None => (package, name),
+        };
+        package
+            .data_type(name)
+            .map(|data_type| (package, data_type))
+    }
+
+    /// the base containers of a container, nearest first
+    pub fn bases(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+    ) -> Result<Vec<(&'a Package, &'a ContainerDataType)>, LayoutError> {
+        let mut bases = Vec::new();
+        let (mut package, mut current) = (package, container);
+        while let Some(base_type) = &current.base_type {
+            if bases.len() >= MAX_DEPTH {
+                return Err(LayoutError::TooDeep(
+                    container.name_entity_type.name.0.clone(),
+                ));
+            }
+            match self.lookup(package, &base_type.0) {
+                Some((p, DataType::ContainerDataType(base))) => {
+                    bases.push((p, base));
+                    (package, current) = (p, base);
+                }
+                Some(_) => return Err(LayoutError::NotAContainer(base_type.0.clone())),
+                None => return Err(LayoutError::UnknownType(base_type.0.clone())),
+            }
+        }
+        Ok(bases)
+    }
+
+    /// the layout of a container, with the entries of its base containers
+    pub fn container(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+    ) -> Result<Layout, LayoutError> {
+        self.layout(package, container, 0)
+    }
+
+    /// the encoding and the size of a data type
+    pub fn encoding(
+        &self,
+        package: &'a Package,
+        data_type: &'a DataType,
+    ) -> Result<(Encoding, Option<usize>), LayoutError> {
+        self.encoding_at(package, data_type, 0)
+    }
+
+    fn layout(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+        depth: usize,
+    ) -> Result<Layout, LayoutError> {
+        let mut fields = Vec::new();
+        self.fields(package, container, "", &BTreeMap::new(), depth, &mut fields)?;
+        let mut offset = Some(0);
+        for field in fields.iter_mut() {
+            field.offset_in_bits = offset;
+            offset = match offset.zip(field.size_in_bits) {
+                Some((o, s)) => Some(o.checked_add(s).ok_or_else(|| {
+                    LayoutError::Overflow(container.name_entity_type.name.0.clone())
+                })?),
+                None => None,
+            };
+        }
+        Ok(Layout {
+            fields,
+            size_in_bits: offset,
+        })
+    }
+
+    /// append the fields of a container, with their paths under a prefix
+    fn fields(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+        prefix: &str,
+        overrides: &BTreeMap<String, Override<'a>>,
+        depth: usize,
+        fields: &mut Vec<Field>,
+    ) -> Result<(), LayoutError> {
+        if depth > MAX_DEPTH {
+            return Err(LayoutError::TooDeep(
+                container.name_entity_type.name.0.clone(),
+            ));
+        }
+        let chain: Vec<_> = std::iter::once((package, container))
+            .chain(self.bases(package, container)?)
+            .collect();
+
+        // the constraints of the outer containers and of nearer derived containers apply first
+        let mut overrides = overrides.clone();
+        for (package, container) in chain.iter() {
+            let constraints = container.constraint_set.iter().flat_map(|s| &s.constraints);
+            for constraint in constraints {
+                let (entry, type_, value) = match constraint {
+                    Constraint::TypeConstraint(c) => (&c.entry, Some(c.type_.0.as_str()), None),
+                    Constraint::ValueConstraint(c) => (&c.entry, None, Some(c.value.0.as_str())),
+                    _ => continue,
+                };
+                let o = overrides
+                    .entry(format!("{}{}", prefix, entry.0))
+                    .or_insert(Override {
+                        package,
+                        type_: None,
+                        value: None,
+                    });
+                o.type_ = o.type_.or(type_);
+                o.value = o.value.or(value);
+            }
+        }
+
+        let entry_lists = chain.iter().rev().map(|(p, c)| (p, &c.entry_list));
+        let trailer_lists = chain.iter().map(|(p, c)| (p, &c.trailer_entry_list));
+        for (package, list) in entry_lists.chain(trailer_lists) {
+            for entry in list.iter().flat_map(|list| list.entries.iter()) {
+                let name = format!("{}{}", prefix, entry.name());
+                let (type_package, type_name) = match (overrides.get(&name), entry) {
+                    (
+                        Some(Override {
+                            package,
+                            type_: Some(type_),
+                            ..
+                        }),
+                        _,
+                    ) => (*package, *type_),
+                    (_, EntryElement::PaddingEntry(padding)) => {
+                        fields.push(Field {
+                            name: String::new(),
+                            type_: None,
+                            offset_in_bits: None,
+                            size_in_bits: Some(padding.size_in_bits),
+                            encoding: Encoding::Padding,
+                            fixed_value: None,
+                            short_description: padding.short_description.clone(),
+                        });
+                        continue;
+                    }
+                    (_, EntryElement::ListEntry(list)) => {
+                        fields.push(Field {
+                            name,
+                            type_: None,
+                            offset_in_bits: None,
+                            size_in_bits: None,
+                            encoding: Encoding::List {
+                                length_field: list.list_length_field.0.clone(),
+                            },
+                            fixed_value: None,
+                            short_description: list.name_entity_type.short_description.clone(),
+                        });
+                        continue;
+                    }
+                    (_, entry) => (*package, entry.type_().map(|t| t.0.as_str()).unwrap_or("")),
+                };
+                let (p, data_type) = self
+                    .lookup(type_package, type_name)
+                    .ok_or_else(|| LayoutError::UnknownType(type_name.to_string()))?;
+                if let DataType::ContainerDataType(inner) = data_type {
+                    let prefix = format!("{}.", name);
+                    self.fields(p, inner, &prefix, &overrides, depth + 1, fields)?;
+                    continue;
+                }
+                let (encoding, size_in_bits) = self.encoding_at(p, data_type, depth + 1)?;
+                let fixed_value = match (overrides.get(&name), entry) {
+                    (Some(Override { value: Some(v), .. }), _) => Some(v.to_string()),
+                    (_, EntryElement::FixedValueEntry(e)) => Some(e.fixed_value.0.clone()),
+                    _ => None,
+                };
+                fields.push(Field {
+                    name,
+                    type_: Some(format!(
+                        "{}/{}",
+                        p.name_entity_type.name.0,
+                        data_type.name()
+                    )),
+                    offset_in_bits: None,
+                    size_in_bits,
+                    encoding,
+                    fixed_value,
+                    short_description: short_description(entry),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn encoding_at(
+        &self,
+        package: &'a Package,
+        data_type: &'a DataType,
+        depth: usize,
+    ) -> Result<(Encoding, Option<usize>), LayoutError> {
+        if depth > MAX_DEPTH {
+            return Err(LayoutError::TooDeep(data_type.name().to_string()));
+        }
+        Ok(match data_type {
+            DataType::NoneDataType => return Err(LayoutError::UnknownType(String::new())),
+            DataType::BooleanDataType(dt) => (
+                Encoding::Boolean {
+                    false_value: dt.encoding.false_value,
+                },
+                Some(dt.encoding.size_in_bits),
+            ),
+            DataType::IntegerDataType(dt) => (
+                Encoding::Integer {
+                    encoding: dt.encoding.encoding.clone(),
+                    byte_order: dt.encoding.byte_order.clone(),
+                },
+                Some(dt.encoding.size_in_bits),
+            ),
+            DataType::EnumeratedDataType(dt) => (
+                Encoding::Enumerated {
+                    encoding: dt.encoding.encoding.clone(),
+                    byte_order: dt.encoding.byte_order.clone(),
+                    labels: dt
+                        .enumeration_list
+                        .enumeration
+                        .iter()
+                        .map(|e| (e.label.0.clone(), e.value.0.clone()))
+                        .collect(),
+                },
+                Some(dt.encoding.size_in_bits),
+            ),
+            DataType::FloatDataType(dt) => (
+                Encoding::Float {
+                    encoding: dt.encoding.encoding_and_precision.clone(),
+                    byte_order: dt.encoding.byte_order.clone(),
+                },
+                Some(dt.encoding.size_in_bits),
+            ),
+            // the length of strings is in bytes
+            DataType::StringDataType(dt) => {
+                let size = dt
+                    .length
+                    .checked_mul(8)
+                    .ok_or_else(|| LayoutError::Overflow(dt.name_entity_type.name.0.clone()))?;
+                (
+                    Encoding::String {
+                        encoding: dt.encoding.encoding.clone(),
+                        fixed_length: dt.fixed_length,
+                    },
+                    Some(size).filter(|_| dt.fixed_length),
+                )
+            }
+            DataType::ArrayDataType(dt) => {
+                let (p, element) = self
+                    .lookup(package, &dt.data_type_ref.0)
+                    .ok_or_else(|| LayoutError::UnknownType(dt.data_type_ref.0.clone()))?;
+                let (element, size) = self.encoding_at(p, element, depth + 1)?;
+                let dimensions: Vec<usize> =
+                    dt.dimension_list.dimension.iter().map(|d| d.size).collect();
+                let overflow = || LayoutError::Overflow(dt.name_entity_type.name.0.clone());
+                let count = dimensions
+                    .iter()
+                    .try_fold(1usize, |count, d| count.checked_mul(*d))
+                    .ok_or_else(overflow)?;
+                let size = match size {
+                    Some(size) => Some(size.checked_mul(count).ok_or_else(overflow)?),
+                    None => None,
+                };
+                (
+                    Encoding::Array {
+                        dimensions,
+                        element: Box::new(element),
+                    },
+                    size,
+                )
+            }
+            DataType::ContainerDataType(dt) => {
+                let layout = self.layout(package, dt, depth + 1)?;
+                (Encoding::Container(layout.fields), layout.size_in_bits)
+            }
+            DataType::SubRangeDataType(dt) => {
+                let (p, base) = self
+                    .lookup(package, &dt.base_type.0)
+                    .ok_or_else(|| LayoutError::UnknownType(dt.base_type.0.clone()))?;
+                self.encoding_at(p, base, depth + 1)?
+            }
+        })
+    }
+}
+
+/// shortDescription of an entry
+fn short_description(entry: &EntryElement) -> Option<String> {
+    match entry {
+        EntryElement::Entry(e) => e.name_entity_type.short_description.clone(),
+        EntryElement::FixedValueEntry(e) => e.name_entity_type.short_description.clone(),
+        EntryElement::PaddingEntry(e) => e.short_description.clone(),
+        EntryElement::ListEntry(e) => e.name_entity_type.short_description.clone(),
+        EntryElement::LengthEntry(e) => e.name_entity_type.short_description.clone(),
+        EntryElement::ErrorControlEntry(e) => e.name_entity_type.short_description.clone(),
+    }
+}
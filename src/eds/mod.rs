@@ -6,5 +6,32 @@ pub mod raw;
 /// EDS Model: what we use to represent the CCSDS Blue Book EDS Specification (expression resolved and namespace qualified)
 pub mod ast;
 
+/// Semantic terms of the core semantics DoT (units, quantity kinds, frames, ...)
+pub mod semantics;
+
+/// Unit conversions between the units of the core semantics DoT
+pub mod units;
+
+/// Attributes and elements of user-defined DoTs, and the handlers that read them
+pub mod extension;
+
+/// Typed values of literals, variables and expressions
+pub mod value;
+
+/// Bit layout of containers: the offsets, sizes and encodings of their fields
+pub mod layout;
+
+/// Structural fingerprints of data types, to identify the encoding of packets
+pub mod fingerprint;
+
+/// Builders of packages, data types and entries that check the invariants of the models
+pub mod builder;
+
+/// Visitors that walk every node of the ast, with the paths of their parents
+pub mod visit;
+
+/// Queries that look up data types, entries and metadata by path, and who references them
+pub mod query;
+
 /// Resolver that converts raw ast to ast
 pub mod resolve;
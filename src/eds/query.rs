@@ -0,0 +1,348 @@
+//! Queries over Resolved Packages
+//!
+//! A [`Query`](crate::eds::query::Query) indexes a set of package files once, and then looks
+//! up data types, entries and metadata values by their paths, finds who references a data
+//! type, and walks the hierarchies of derived containers, e.g. every container that derives
+//! from `CCSDS/CommandPacket`.
+//!
+//! Data types are named by their qualified names `Package/Type`, entries by `Package/Type/Entry`
+//! and metadata values by the names of their categories, like `Mission/Version`. References in
+//! the datasheets may name a type of their own package without its package; queries qualify
+//! them, so that `Speed` in the package `Pump` is found as `Pump/Speed`.
+use std::collections::BTreeMap;
+
+use super::ast::{
+    ContainerDataType, DataType, EntryElement, MetadataValue, Package, PackageFile, QualifiedName,
+};
+use super::layout::Layouts;
+use super::visit::{Path, Visitor};
+
+/// A data type found by a query, with its package
+#[derive(Debug, Clone, Copy)]
+pub struct TypeMatch<'a> {
+    /// the package of the data type
+    pub package: &'a Package,
+    /// the data type
+    pub data_type: &'a DataType,
+}
+
+impl TypeMatch<'_> {
+    /// qualified name `Package/Type` of the data type
+    pub fn name(&self) -> String {
+        format!(
+            "{}/{}",
+            self.package.name_entity_type.name.0,
+            self.data_type.name()
+        )
+    }
+
+    /// XML element of the data type, like `ContainerDataType`
+    pub fn element(&self) -> &'static str {
+        self.data_type.element()
+    }
+}
+
+/// An entry found by a query, with its container
+#[derive(Debug, Clone, Copy)]
+pub struct EntryMatch<'a> {
+    /// the package of the container
+    pub package: &'a Package,
+    /// the container of the entry
+    pub container: &'a ContainerDataType,
+    /// the entry
+    pub entry: &'a EntryElement,
+    /// true if the entry is in the TrailerEntryList of the container
+    pub trailer: bool,
+}
+
+impl EntryMatch<'_> {
+    /// path `Package/Type/Entry` of the entry
+    pub fn path(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.package.name_entity_type.name.0,
+            self.container.name_entity_type.name.0,
+            self.entry.name()
+        )
+    }
+
+    /// qualified name of the type of the entry, none for a PaddingEntry or a ListEntry
+    pub fn type_name(&self) -> Option<String> {
+        self.entry
+            .type_()
+            .map(|type_| qualify(&self.package.name_entity_type.name.0, &type_.0))
+    }
+}
+
+/// A reference to a data type or an interface type
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// qualified name of the referenced type
+    pub to: String,
+    /// name of the package the reference is in
+    pub package: String,
+    /// path of the node that holds the reference, like `Pump/Status/Speed`
+    pub path: String,
+    /// XML element of the node that holds the reference, like `Entry`
+    pub element: &'static str,
+    /// qualified name of the data type the reference is part of, none in a component
+    pub data_type: Option<String>,
+}
+
+/// An index of the data types, the references and the metadata of a set of package files
+#[derive(Debug, Clone)]
+pub struct Query<'a> {
+    layouts: Layouts<'a>,
+    references: Vec<Reference>,
+    metadata: Vec<(String, &'a MetadataValue)>,
+    derived: BTreeMap<String, Vec<TypeMatch<'a>>>,
+}
+
+impl<'a> Query<'a> {
+    /// index a set of package files
+    pub fn new(package_files: &[&'a PackageFile]) -> Self {
+        let mut index = Index::default();
+        for package_file in package_files {
+            index.visit_package_file(&Path::default(), package_file);
+        }
+        let query = Query {
+            layouts: Layouts::new(package_files),
+            references: index.references,
+            metadata: index.metadata,
+            derived: BTreeMap::new(),
+        };
+        let mut derived: BTreeMap<String, Vec<TypeMatch<'a>>> = BTreeMap::new();
+        for type_match in query.data_types() {
+            if let DataType::ContainerDataType(container) = type_match.data_type {
+                if let Some(base_type) = &container.base_type {
+                    let base = qualify(&type_match.package.name_entity_type.name.0, &base_type.0);
+                    derived.entry(base).or_default().push(type_match);
+                }
+            }
+        }
+        Query { derived, ..query }
+    }
+
+    /// every data type, in document order
+    pub fn data_types(&self) -> impl Iterator<Item = TypeMatch<'a>> + '_ {
+        self.layouts.packages().iter().flat_map(|package| {
+            package
+                .data_type_set
+                .data_types
+                .iter()
+                .map(|data_type| TypeMatch { package, data_type })
+        })
+    }
+
+    /// a data type by its qualified name, like `CCSDS/PriHdr`
+    pub fn data_type(&self, name: &str) -> Option<TypeMatch<'a>> {
+        let (package_name, name) = name.rsplit_once('/')?;
+        let package = self
+            .layouts
+            .packages()
+            .iter()
+            .find(|p| p.name_entity_type.name.0 == package_name)?;
+        package
+            .data_type(name)
+            .map(|data_type| TypeMatch { package, data_type })
+    }
+
+    /// the data types that satisfy a predicate, in document order
+    pub fn data_types_where<P>(&self, predicate: P) -> Vec<TypeMatch<'a>>
+    where
+        P: Fn(&TypeMatch<'a>) -> bool,
+    {
+        self.data_types().filter(predicate).collect()
+    }
+
+    /// every entry of the containers, without the entries they inherit, in document order
+    pub fn entries(&self) -> impl Iterator<Item = EntryMatch<'a>> + '_ {
+        self.data_types()
+            .flat_map(|TypeMatch { package, data_type }| {
+                let container = match data_type {
+                    DataType::ContainerDataType(container) => Some(container),
+                    _ => None,
+                };
+                container.into_iter().flat_map(move |container| {
+                    [
+                        (&container.entry_list, false),
+                        (&container.trailer_entry_list, true),
+                    ]
+                    .into_iter()
+                    .flat_map(move |(list, trailer)| {
+                        list.iter()
+                            .flat_map(|list| list.entries.iter())
+                            .map(move |entry| EntryMatch {
+                                package,
+                                container,
+                                entry,
+                                trailer,
+                            })
+                    })
+                })
+            })
+    }
+
+    /// an entry by its path, like `CCSDS/PriHdr/Length`
+    pub fn entry(&self, path: &str) -> Option<EntryMatch<'a>> {
+        self.entries().find(|entry| entry.path() == path)
+    }
+
+    /// the entries that satisfy a predicate, in document order
+    pub fn entries_where<P>(&self, predicate: P) -> Vec<EntryMatch<'a>>
+    where
+        P: Fn(&EntryMatch<'a>) -> bool,
+    {
+        self.entries().filter(predicate).collect()
+    }
+
+    /// the entries whose type is a data type, by its qualified name
+    pub fn entries_of_type(&self, name: &str) -> Vec<EntryMatch<'a>> {
+        self.entries_where(|entry| entry.type_name().as_deref() == Some(name))
+    }
+
+    /// every reference to a data type or an interface type, in document order
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// the references to a data type or an interface type, by its qualified name, in
+    /// document order
+    pub fn references_to(&self, name: &str) -> Vec<&Reference> {
+        self.references.iter().filter(|r| r.to == name).collect()
+    }
+
+    /// the qualified names of the data types that refer to a data type, directly or through
+    /// other data types, nearest first
+    pub fn dependents(&self, name: &str) -> Vec<String> {
+        let mut names = vec![name.to_string()];
+        let mut next = 0;
+        while let Some(current) = names.get(next).cloned() {
+            for reference in self.references_to(&current) {
+                if let Some(data_type) = &reference.data_type {
+                    if !names.contains(data_type) {
+                        names.push(data_type.clone());
+                    }
+                }
+            }
+            next += 1;
+        }
+        names.split_off(1)
+    }
+
+    /// qualified name of the base type of a container, by its qualified name, whether the
+    /// base type is known or not
+    pub fn base_type(&self, name: &str) -> Option<String> {
+        match self.data_type(name)? {
+            TypeMatch {
+                package,
+                data_type: DataType::ContainerDataType(container),
+            } => container
+                .base_type
+                .as_ref()
+                .map(|base_type| qualify(&package.name_entity_type.name.0, &base_type.0)),
+            _ => None,
+        }
+    }
+
+    /// the base containers of a container, by its qualified name, nearest first; none if a
+    /// base is unknown or the hierarchy is a cycle
+    pub fn bases(&self, name: &str) -> Vec<TypeMatch<'a>> {
+        match self.data_type(name) {
+            Some(TypeMatch {
+                package,
+                data_type: DataType::ContainerDataType(container),
+            }) => self
+                .layouts
+                .bases(package, container)
+                .map(|bases| {
+                    bases
+                        .into_iter()
+                        .filter_map(|(package, base)| {
+                            self.data_type(&qualify(
+                                &package.name_entity_type.name.0,
+                                &base.name_entity_type.name.0,
+                            ))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// the containers derived from a container, by its qualified name, directly or through
+    /// other containers, nearest first
+    pub fn derived(&self, name: &str) -> Vec<TypeMatch<'a>> {
+        let mut derived: Vec<TypeMatch<'a>> = Vec::new();
+        let mut names = vec![name.to_string()];
+        let mut next = 0;
+        while let Some(current) = names.get(next).cloned() {
+            for child in self.derived.get(&current).into_iter().flatten() {
+                let child_name = child.name();
+                if !names.contains(&child_name) {
+                    derived.push(*child);
+                    names.push(child_name);
+                }
+            }
+            next += 1;
+        }
+        derived
+    }
+
+    /// the metadata values of all the files, with their paths, in document order
+    pub fn metadata(&self) -> impl Iterator<Item = (&str, &'a MetadataValue)> + '_ {
+        self.metadata
+            .iter()
+            .map(|(path, value)| (path.as_str(), *value))
+    }
+
+    /// a metadata value by the names of its categories and its name, like `Mission/Version`
+    pub fn metadata_value(&self, path: &str) -> Option<&'a MetadataValue> {
+        self.metadata()
+            .find(|(value_path, _)| *value_path == path)
+            .map(|(_, value)| value)
+    }
+}
+
+/// qualified name of a reference from a package
+fn qualify(package: &str, name: &str) -> String {
+    if name.contains('/') {
+        name.to_string()
+    } else {
+        format!("{}/{}", package, name)
+    }
+}
+
+/// the references and the metadata values of package files
+#[derive(Default)]
+struct Index<'a> {
+    references: Vec<Reference>,
+    metadata: Vec<(String, &'a MetadataValue)>,
+}
+
+impl<'a> Visitor<'a> for Index<'a> {
+    fn visit_type_ref(&mut self, path: &Path, type_ref: &'a QualifiedName) {
+        let Some(package) = path.package() else {
+            return;
+        };
+        self.references.push(Reference {
+            to: qualify(package, &type_ref.0),
+            package: package.to_string(),
+            path: path.to_string(),
+            element: path.parent().map_or("", |parent| parent.element),
+            data_type: path.data_type(),
+        });
+    }
+
+    fn visit_metadata_value(&mut self, path: &Path, value: &'a MetadataValue) {
+        let name = &value.name_entity_type().name.0;
+        let path = path.to_string();
+        let path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{}", path, name)
+        };
+        self.metadata.push((path, value));
+    }
+}
@@ -1,11 +1,13 @@
 //! Raw EDS PackageFile Model
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
+
+pub use super::extension::Extensions;
 
 type Expression = String;
 
 /// DataSheet contains one Device element and one or more Package elements
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct DataSheet {
     #[serde(rename = "Device", default)]
     pub devices: Vec<Device>,
@@ -16,42 +18,102 @@ pub struct DataSheet {
 
 /// Device defines a device and is based on the NamedEntityType
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Device {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
 
-    #[serde(rename = "MetaData", default)]
+    #[serde(rename = "Metadata", alias = "MetaData", default)]
     pub metadata: Option<MetaData>,
 }
 
 /// MetaData provides additional information about the Device or PackageFile
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct MetaData {
     #[serde(rename = "CreationDate", default)]
     pub creation_date: Option<String>, // assuming creation date is string, change type accordingly
 
     #[serde(rename = "Creator", default)]
     pub creator: Option<String>,
-    // Add other fields as required...
+
+    #[serde(rename = "MetadataValueSet", default)]
+    pub value_set: Option<MetadataValueSet>,
+
+    #[serde(rename = "Category", default)]
+    pub categories: Vec<MetadataCategory>,
+}
+
+/// Category groups metadata values and nested categories - Req 3.4.2
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct MetadataCategory {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+
+    #[serde(rename = "MetadataValueSet", default)]
+    pub value_set: Option<MetadataValueSet>,
+
+    #[serde(rename = "Category", default)]
+    pub categories: Vec<MetadataCategory>,
+}
+
+/// MetadataValueSet contains Date, Float, Integer and String values - Req 3.4.5
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct MetadataValueSet {
+    pub values: Vec<MetadataValue>,
+}
+
+/// MetadataValue is one of DateValue, FloatValue, IntegerValue or StringValue
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum MetadataValue {
+    DateValue(StringMetadataValue),
+    FloatValue(NumericMetadataValue),
+    IntegerValue(NumericMetadataValue),
+    StringValue(StringMetadataValue),
+}
+
+/// DateValue or StringValue with a literal value - Req 3.4.7
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct StringMetadataValue {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "value")]
+    pub value: Expression,
+}
+
+/// FloatValue or IntegerValue, given as a literal or calculated - Req 3.4.8, 3.4.9
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct NumericMetadataValue {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "value", default)]
+    pub value: Option<Expression>,
+    #[serde(rename = "MathOperation", default)]
+    pub math_operation: Option<MathOperation>,
+    #[serde(rename = "Conditional", default)]
+    pub conditional: Option<Box<Conditional>>,
 }
 
 /// Package File describes a composable unit of software or hardware
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct PackageFile {
     /// PackageFile includes a Package element  
     #[serde(rename = "Package", default)]
     pub package: Vec<Package>,
 
-    #[serde(rename = "MetaData", default)]
+    #[serde(rename = "Metadata", alias = "MetaData", default)]
     pub metadata: Option<MetaData>,
 }
 
 /// Package describes a related set of components, data types, and interfaces
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Package {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -59,6 +121,10 @@ pub struct Package {
     /// A Package element may contain a DataTypeSet element
     #[serde(rename = "DataTypeSet", default)]
     pub data_type_set: Option<DataTypeSet>,
+
+    /// A Package element may contain a ComponentSet element
+    #[serde(rename = "ComponentSet", default)]
+    pub component_set: Option<ComponentSet>,
 }
 
 /// DataTypeSet element contains one or more DataType elements
@@ -74,7 +140,7 @@ pub struct DataTypeSet {
 /// EnumeratedDataType, FloatDataType, IntegerDataType, StringDataType,
 /// and SubRangeDataType.
 #[allow(missing_docs)]
-#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub enum DataType {
     #[default]
     NoneDataType,
@@ -90,7 +156,7 @@ pub enum DataType {
 
 /// EnumeratedDataType defines an enumerated data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct EnumeratedDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -103,18 +169,56 @@ pub struct EnumeratedDataType {
 /// NamedEntityType stores the name attribute and may have the optional
 /// shortDescription attribute and LongDescription child element.
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct NamedEntityType {
     pub name: String,
     #[serde(rename = "shortDescription", default)]
     pub short_description: Option<String>,
     #[serde(rename = "LongDescription", default)]
     pub long_description: Option<LongDescription>,
+    #[serde(flatten)]
+    pub semantics: Semantics,
+    /// attributes and elements of user DoTs
+    #[serde(skip)]
+    pub extensions: Extensions,
+}
+
+/// Semantics stores the quantityKind, unit and CoreSemanticsAttributeGroup
+/// attributes that may be attached to any NamedEntityType
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Semantics {
+    #[serde(rename = "quantityKind", default)]
+    pub quantity_kind: Option<Expression>,
+    #[serde(rename = "unit", default)]
+    pub unit: Option<Expression>,
+    #[serde(rename = "chirality", default)]
+    pub chirality: Option<Expression>,
+    #[serde(rename = "coordinateType", default)]
+    pub coordinate_type: Option<Expression>,
+    #[serde(rename = "toCoordinateType", default)]
+    pub to_coordinate_type: Option<Expression>,
+    #[serde(rename = "differenceConvention", default)]
+    pub difference_convention: Option<Expression>,
+    #[serde(rename = "interpretation", default)]
+    pub interpretation: Option<Expression>,
+    #[serde(rename = "purpose", default)]
+    pub purpose: Option<Expression>,
+    #[serde(rename = "referenceFrame", default)]
+    pub reference_frame: Option<Expression>,
+    #[serde(rename = "toFrame", default)]
+    pub to_frame: Option<Expression>,
+    #[serde(rename = "relationToTimestamp", default)]
+    pub relation_to_timestamp: Option<Expression>,
+    #[serde(rename = "statusConvention", default)]
+    pub status_convention: Option<Expression>,
+    #[serde(rename = "transformationType", default)]
+    pub transformation_type: Option<Expression>,
 }
 
 /// LongDescription element contains text representing a long description
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct LongDescription {
     #[serde(rename = "$value", default)]
     pub text: String,
@@ -122,7 +226,7 @@ pub struct LongDescription {
 
 ///EnumerationList consists of a list of one or more Enumeration elements
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct EnumerationList {
     #[serde(rename = "Enumeration", default)]
     pub enumeration: Vec<Enumeration>,
@@ -131,7 +235,7 @@ pub struct EnumerationList {
 /// Enumeration element has required label and value attributes,
 /// indicating the integer value corresponding to a given label string
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Enumeration {
     #[serde(rename = "label", default)]
     pub label: String,
@@ -139,11 +243,16 @@ pub struct Enumeration {
     pub value: Expression,
     #[serde(rename = "shortDescription", default)]
     pub short_description: Option<String>,
+    #[serde(flatten)]
+    pub semantics: Semantics,
+    /// attributes and elements of user DoTs
+    #[serde(skip)]
+    pub extensions: Extensions,
 }
 
 /// ContainerDataType defines a container data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ContainerDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -168,7 +277,7 @@ pub struct ConstraintSet {
 
 /// Constraint specifies the criteria that apply to the entries of the container type
 #[allow(missing_docs)]
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub enum Constraint {
     RangeConstraint(RangeConstraint),
     TypeConstraint(TypeConstraint),
@@ -177,7 +286,7 @@ pub enum Constraint {
 
 /// RangeConstraint specifies the range of valid values for a container entry
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct RangeConstraint {
     #[serde(rename = "Range", default)]
     pub range: Range,
@@ -186,7 +295,7 @@ pub struct RangeConstraint {
 
 /// TypeConstraint specifies the data type of a container entry
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct TypeConstraint {
     #[serde(rename = "type")]
     pub type_: String,
@@ -195,7 +304,7 @@ pub struct TypeConstraint {
 
 /// ValueConstraint specifies the value of a container entry
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ValueConstraint {
     #[serde(rename = "value")]
     pub value: Expression,
@@ -211,7 +320,7 @@ pub struct EntryList {
 
 /// EntryElement is either an Entry or a PaddingEntry
 #[allow(missing_docs)]
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub enum EntryElement {
     Entry(Entry),
     FixedValueEntry(FixedValueEntry),
@@ -223,7 +332,7 @@ pub enum EntryElement {
 
 /// Entry element defines a field within a container
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Entry {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -234,7 +343,7 @@ pub struct Entry {
 /// PaddingEntry within a container has an attribute sizeInBits that specifies
 /// the position of successive fields
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct PaddingEntry {
     #[serde(rename = "sizeInBits")]
     pub size_in_bits: Expression,
@@ -244,7 +353,7 @@ pub struct PaddingEntry {
 
 /// ArrayDataType defines an array data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ArrayDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -256,7 +365,7 @@ pub struct ArrayDataType {
 
 /// DimensionList consists of a list of one or more Dimension elements
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct DimensionList {
     #[serde(rename = "Dimension", default)]
     pub dimension: Vec<Dimension>,
@@ -264,7 +373,7 @@ pub struct DimensionList {
 
 /// Dimension determines the length of the array dimension
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Dimension {
     #[serde(rename = "size", default)]
     pub size: Expression,
@@ -272,7 +381,7 @@ pub struct Dimension {
 
 /// BooleanDataType defines a boolean data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct BooleanDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -282,7 +391,7 @@ pub struct BooleanDataType {
 
 /// BooleanDataEncoding defines the size in bits of a boolean data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct BooleanDataEncoding {
     #[serde(rename = "sizeInBits", default)]
     pub size_in_bits: Expression,
@@ -292,7 +401,7 @@ pub struct BooleanDataEncoding {
 
 /// BooleanFalseValue - Req 3.7.4
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub enum BooleanFalseValue {
     #[default]
     #[serde(rename = "zeroIsFalse")]
@@ -303,10 +412,14 @@ pub enum BooleanFalseValue {
 
 /// IntegerDataType defines an integer data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct IntegerDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
+    #[serde(rename = "PolynomialCalibrator", default)]
+    pub polynomial_calibrator: Option<PolynomialCalibrator>,
+    #[serde(rename = "SplineCalibrator", default)]
+    pub spline_calibrator: Option<SplineCalibrator>,
     #[serde(rename = "IntegerDataEncoding")]
     pub encoding: Option<IntegerDataEncoding>,
     #[serde(rename = "Range", default)]
@@ -316,7 +429,7 @@ pub struct IntegerDataType {
 /// IntegerDataEncoding defines the encoding of an integer data type,
 /// including the size in bits, encoding, and byte order
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct IntegerDataEncoding {
     #[serde(rename = "sizeInBits", default)]
     pub size_in_bits: Expression,
@@ -326,17 +439,20 @@ pub struct IntegerDataEncoding {
     pub byte_order: Option<Expression>,
 }
 
-/// Range defines an interval of inclusive or exclusive minimum and maximum values
+/// Range defines an interval of inclusive or exclusive minimum and maximum values,
+/// or the precision of a float
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Range {
     #[serde(rename = "MinMaxRange", default)]
     pub min_max_range: MinMaxRange,
+    #[serde(rename = "PrecisionRange", default)]
+    pub precision_range: Option<Expression>,
 }
 
 /// MinMaxRange defines the minimum and maximum values of a data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct MinMaxRange {
     #[serde(rename = "max", default)]
     pub max: Expression,
@@ -348,18 +464,23 @@ pub struct MinMaxRange {
 
 /// FloatDataType defines a floating point data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct FloatDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
+    #[serde(rename = "PolynomialCalibrator", default)]
+    pub polynomial_calibrator: Option<PolynomialCalibrator>,
+    #[serde(rename = "SplineCalibrator", default)]
+    pub spline_calibrator: Option<SplineCalibrator>,
     #[serde(rename = "FloatDataEncoding")]
     pub encoding: Option<FloatDataEncoding>,
+    #[serde(rename = "Range", default)]
     pub range: Option<Range>,
 }
 
 /// FloatDataEncoding defines the encoding of a floating point data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct FloatDataEncoding {
     #[serde(rename = "sizeInBits", default)]
     pub size_in_bits: Expression,
@@ -371,7 +492,7 @@ pub struct FloatDataEncoding {
 
 /// StringDataType defines a string data type of either fixed or variable length
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct StringDataType {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -383,7 +504,7 @@ pub struct StringDataType {
 
 /// StringDataEncoding defines the encoding of a string data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct StringDataEncoding {
     #[serde(rename = "terminationCharacter")]
     pub termination_character: Option<Expression>,
@@ -391,32 +512,32 @@ pub struct StringDataEncoding {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ComponentSet {
     #[serde(rename = "Component", default)]
     pub components: Vec<Component>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Component {
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "RequiredInterfaceSet", default)]
     pub required_interface_set: RequiredInterfaceSet,
-    #[serde(rename = "Implementation")]
+    #[serde(rename = "Implementation", default)]
     pub implementation: Implementation,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct RequiredInterfaceSet {
     #[serde(rename = "Interface", default)]
     pub interfaces: Vec<Interface>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Interface {
     #[serde(rename = "name")]
     pub name: String,
@@ -429,14 +550,14 @@ pub struct Interface {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct GenericTypeMapSet {
     #[serde(rename = "GenericTypeMap", default)]
     pub generic_type_maps: Vec<GenericTypeMap>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct GenericTypeMap {
     #[serde(rename = "name")]
     pub name: String,
@@ -445,43 +566,418 @@ pub struct GenericTypeMap {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Implementation {
     #[serde(rename = "VariableSet", default)]
     pub variable_set: VariableSet,
     #[serde(rename = "ParameterMapSet", default)]
     pub parameter_map_set: ParameterMapSet,
+    #[serde(rename = "ActivitySet", default)]
+    pub activity_set: ActivitySet,
+    #[serde(rename = "StateMachineSet", default)]
+    pub state_machine_set: StateMachineSet,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct VariableSet {
     #[serde(rename = "Variable", default)]
     pub variables: Vec<Variable>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Variable {
     #[serde(rename = "type")]
     pub type_: String,
-    #[serde(rename = "readOnly")]
+    #[serde(rename = "readOnly", default)]
     pub read_only: bool,
     #[serde(rename = "name")]
     pub name: String,
-    #[serde(rename = "initialValue")]
-    pub initial_value: Expression,
+    #[serde(rename = "initialValue", default)]
+    pub initial_value: Option<Expression>,
+}
+
+/// StateMachineSet contains the state machines used to implement a component
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct StateMachineSet {
+    #[serde(rename = "StateMachine", default)]
+    pub state_machines: Vec<StateMachine>,
+}
+
+/// StateMachine responds to events and schedules the execution of activities - Req 3.16
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct StateMachine {
+    pub name_entity_type: NamedEntityType,
+    pub elements: Vec<StateMachineElement>,
+}
+
+/// StateMachineElement is one of EntryState, ExitState, State or Transition
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum StateMachineElement {
+    EntryState(EntryState),
+    ExitState(ExitState),
+    State(State),
+    Transition(Box<Transition>),
+}
+
+/// EntryState is the pseudo-state a state machine starts in
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct EntryState {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+}
+
+/// ExitState is a pseudo-state that terminates a state machine
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ExitState {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+}
+
+/// State may invoke an activity on entry and immediately before exit - Req 3.16.5
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct State {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "OnEntry", default)]
+    pub on_entry: Option<ActivityInvocation>,
+    #[serde(rename = "OnExit", default)]
+    pub on_exit: Option<ActivityInvocation>,
+}
+
+/// Transition between two states, triggered by a command, a parameter or a timer
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Transition {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "fromState")]
+    pub from_state: String,
+    #[serde(rename = "toState")]
+    pub to_state: String,
+    #[serde(rename = "OnCommandPrimitive", default)]
+    pub on_command_primitive: Option<OnCommandPrimitive>,
+    #[serde(rename = "OnParameterPrimitive", default)]
+    pub on_parameter_primitive: Option<OnParameterPrimitive>,
+    #[serde(rename = "OnTimer", default)]
+    pub on_timer: Option<OnTimer>,
+    #[serde(rename = "Guard", default)]
+    pub guard: Option<BooleanExpression>,
+    #[serde(rename = "Do", default)]
+    pub do_: Option<ActivityInvocation>,
+}
+
+/// ActivityInvocation names an activity and its arguments - Req 3.16.6, 3.16.7
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ActivityInvocation {
+    #[serde(rename = "activity")]
+    pub activity: String,
+    #[serde(rename = "ArgumentValue", default)]
+    pub argument_values: Vec<ArgumentValue>,
+}
+
+/// ArgumentValue associates a literal Value or a VariableRef with a named argument
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ArgumentValue {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "VariableRef", default)]
+    pub variable_ref: Option<VariableRefOperand>,
+    #[serde(rename = "Value", default)]
+    pub value: Option<ValueOperand>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct VariableRefOperand {
+    #[serde(rename = "variableRef")]
+    pub variable_ref: String,
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ValueOperand {
+    #[serde(rename = "value")]
+    pub value: Expression,
+}
+
+/// OnCommandPrimitive triggers a transition when a command arrives on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct OnCommandPrimitive {
+    #[serde(rename = "interface")]
+    pub interface: String,
+    #[serde(rename = "command")]
+    pub command: String,
+    #[serde(rename = "transaction", default)]
+    pub transaction: Option<String>,
+    #[serde(rename = "ArgumentValue", default)]
+    pub argument_values: Vec<SinkArgumentValue>,
+}
+
+/// SinkArgumentValue stores an incoming command argument in a component variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct SinkArgumentValue {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "outputVariableRef")]
+    pub output_variable_ref: String,
+}
+
+/// OnParameterPrimitive triggers a transition when a parameter is read or written on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct OnParameterPrimitive {
+    #[serde(rename = "interface")]
+    pub interface: String,
+    #[serde(rename = "parameter")]
+    pub parameter: String,
+    #[serde(rename = "operation")]
+    pub operation: Expression,
+    #[serde(rename = "transaction", default)]
+    pub transaction: Option<String>,
+    #[serde(rename = "VariableRef", default)]
+    pub variable_ref: Option<String>,
+}
+
+/// OnTimer triggers a transition a specified duration after entering its source state
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct OnTimer {
+    #[serde(rename = "nanosecondsAfterEntry", default)]
+    pub nanoseconds_after_entry: Option<Expression>,
+}
+
+/// ActivitySet contains the activities used to implement a component - Req 3.15
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ActivitySet {
+    #[serde(rename = "Activity", default)]
+    pub activities: Vec<Activity>,
+}
+
+/// Activity is a named sequence of statements with arguments
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Activity {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "Argument", default)]
+    pub arguments: Vec<ActivityArgument>,
+    #[serde(rename = "Body")]
+    pub body: Body,
+}
+
+/// ActivityArgument is a typed argument of an activity
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct ActivityArgument {
+    #[serde(flatten)]
+    pub name_entity_type: NamedEntityType,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Body is the implementation of an activity, a list of statements
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Body {
+    pub statements: Vec<Statement>,
+}
+
+/// Statement is a single step within an activity body
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum Statement {
+    Assignment(Assignment),
+    Conditional(Box<Conditional>),
+    Iteration(Box<Iteration>),
+    Calibration(Calibration),
+    SendParameterPrimitive(SendParameterPrimitive),
+    SendCommandPrimitive(SendCommandPrimitive),
+    MathOperation(MathOperation),
+    Call(ActivityInvocation),
+}
+
+/// Operand is either a VariableRef or a Value element
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Operand {
+    #[serde(rename = "VariableRef", default)]
+    pub variable_ref: Option<VariableRefOperand>,
+    #[serde(rename = "Value", default)]
+    pub value: Option<ValueOperand>,
+}
+
+/// Assignment of a variable or a value to a variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Assignment {
+    #[serde(rename = "outputVariableRef")]
+    pub output_variable_ref: String,
+    #[serde(rename = "VariableRef", default)]
+    pub variable_ref: Option<VariableRefOperand>,
+    #[serde(rename = "Value", default)]
+    pub value: Option<ValueOperand>,
+}
+
+/// Conditional executes one of two bodies depending on a boolean expression - Req 3.15.37
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Conditional {
+    #[serde(rename = "Condition")]
+    pub condition: BooleanExpression,
+    #[serde(rename = "OnConditionTrue", default)]
+    pub on_condition_true: Option<Body>,
+    #[serde(rename = "OnConditionFalse", default)]
+    pub on_condition_false: Option<Body>,
+}
+
+/// BooleanExpression holds one Condition, ANDedConditions, ORedConditions or TypeCondition
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum BooleanExpression {
+    Condition(Comparison),
+    ANDedConditions(Vec<BooleanExpression>),
+    ORedConditions(Vec<BooleanExpression>),
+    TypeCondition(TypeCheck),
+}
+
+impl Default for BooleanExpression {
+    fn default() -> Self {
+        BooleanExpression::ANDedConditions(Vec::new())
+    }
+}
+
+/// Comparison of a variable with a value or another variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Comparison {
+    #[serde(rename = "FirstOperand")]
+    pub first_operand: VariableRefOperand,
+    #[serde(rename = "ComparisonOperator")]
+    pub comparison_operator: Expression,
+    #[serde(rename = "SecondOperand", default)]
+    pub second_operand: Option<VariableRefOperand>,
+    #[serde(rename = "Value", default)]
+    pub value: Option<ValueOperand>,
+}
+
+/// TypeCheck tests a variable for compatibility with a type
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct TypeCheck {
+    #[serde(rename = "FirstOperand")]
+    pub first_operand: VariableRefOperand,
+    #[serde(rename = "TypeOperand")]
+    pub type_operand: String,
+}
+
+/// Iteration over the elements of an array or a numeric range
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Iteration {
+    #[serde(rename = "iteratorVariableRef")]
+    pub iterator_variable_ref: String,
+    #[serde(rename = "OverArray", default)]
+    pub over_array: Option<String>,
+    #[serde(rename = "StartAt", default)]
+    pub start_at: Option<Operand>,
+    #[serde(rename = "Step", default)]
+    pub step: Option<Operand>,
+    #[serde(rename = "EndAt", default)]
+    pub end_at: Option<Operand>,
+    #[serde(rename = "Do")]
+    pub do_: Body,
+}
+
+/// Calibration of an input variable into an output variable
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct Calibration {
+    #[serde(rename = "outputVariableRef")]
+    pub output_variable_ref: String,
+    #[serde(rename = "inputVariableRef")]
+    pub input_variable_ref: String,
+    #[serde(rename = "PolynomialCalibrator", default)]
+    pub polynomial_calibrator: Option<PolynomialCalibrator>,
+    #[serde(rename = "SplineCalibrator", default)]
+    pub spline_calibrator: Option<SplineCalibrator>,
+}
+
+/// SendCommandPrimitive sends a command on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct SendCommandPrimitive {
+    #[serde(rename = "interface")]
+    pub interface: String,
+    #[serde(rename = "command")]
+    pub command: String,
+    #[serde(rename = "transaction", default)]
+    pub transaction: Option<String>,
+    #[serde(rename = "ArgumentValue", default)]
+    pub argument_values: Vec<ArgumentValue>,
+}
+
+/// SendParameterPrimitive reads or writes a parameter on an interface
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct SendParameterPrimitive {
+    #[serde(rename = "interface")]
+    pub interface: String,
+    #[serde(rename = "parameter")]
+    pub parameter: String,
+    #[serde(rename = "operation")]
+    pub operation: Expression,
+    #[serde(rename = "transaction", default)]
+    pub transaction: Option<String>,
+    #[serde(rename = "ArgumentValue", default)]
+    pub argument_value: Option<Operand>,
+}
+
+/// MathOperation is a stack calculation in reverse polish notation - Req 3.15.32
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct MathOperation {
+    pub output_variable_ref: String,
+    pub elements: Vec<MathElement>,
+}
+
+/// MathElement pushes a Value or VariableRef on the stack, or applies an Operator
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum MathElement {
+    Value(ValueOperand),
+    VariableRef(VariableRefOperand),
+    Operator(MathOperator),
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct MathOperator {
+    #[serde(rename = "operator")]
+    pub operator: Expression,
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ParameterMapSet {
     #[serde(rename = "ParameterMap", default)]
     pub parameter_maps: Vec<ParameterMap>,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ParameterMap {
     #[serde(rename = "interface")]
     pub interface: String,
@@ -492,7 +988,7 @@ pub struct ParameterMap {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct LengthEntry {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -505,22 +1001,35 @@ pub struct LengthEntry {
 /// PolynomialCalibrator calibration that would be required to take the raw value represented by the data
 /// type and convert it into the units and other semantic terms associated with the field
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct PolynomialCalibrator {
     #[serde(rename = "Term")]
     pub term: Vec<Term>,
 }
 
-/// TODO
+/// SplineCalibrator converts a raw value into a calibrated value along a line through points
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct SplineCalibrator {
-    #[serde(rename = "Term")]
-    pub term: Vec<Term>,
+    #[serde(rename = "extrapolate", default)]
+    pub extrapolate: Option<Expression>,
+    #[serde(rename = "SplinePoint", default)]
+    pub spline_point: Vec<SplinePoint>,
+}
+
+/// SplinePoint is a point of a SplineCalibrator, the order of the line up to the point is 1 for
+/// a straight line
+#[allow(missing_docs)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct SplinePoint {
+    #[serde(rename = "order", default)]
+    pub order: Option<Expression>,
+    pub raw: Expression,
+    pub calibrated: Expression,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct Term {
     pub coefficient: Expression,
     pub exponent: Expression,
@@ -529,7 +1038,7 @@ pub struct Term {
 /// ErrorControlEntry specifies an entry whose value is constrained, or derived,
 /// based on the contents of the container in which it is present.
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ErrorControlEntry {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -541,7 +1050,7 @@ pub struct ErrorControlEntry {
 
 /// FixedValueEntry within a container contains a fixed value
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct FixedValueEntry {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
@@ -557,24 +1066,25 @@ pub struct FixedValueEntry {
 
 /// TODO: ListEntry
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct ListEntry {
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
+    /// type of the elements of the list
+    #[serde(rename = "type", default)]
+    pub type_: String,
     #[serde(rename = "listLengthField")]
     pub list_length_field: Expression,
 }
 
 /// SubRangeDataType defines a sub range data type
 #[allow(missing_docs)]
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
 pub struct SubRangeDataType {
     #[serde(rename = "baseType")]
     pub base_type: String,
     #[serde(flatten)]
     pub name_entity_type: NamedEntityType,
-    #[serde(rename = "unit")]
-    pub unit: String,
     #[serde(rename = "Range", default)]
     pub range: Range,
     pub encoding: Option<Expression>,
@@ -1,19 +1,23 @@
-use evalexpr::EvalexprError;
+use std::collections::HashMap;
+use std::fmt;
 
 use crate::eds::ast;
 use crate::eds::raw;
 use crate::expr::ExpressionContext;
-use crate::expr::NamespaceError;
+use crate::expr::{ExpressionError, NamespaceError};
 
 use super::ast::Identifier;
 use super::ast::Literal;
+use super::extension::{ExtensionAttribute, ExtensionElement, Extensions};
 use super::raw::IntegerDataEncoding;
+use super::semantics::SemanticTerm;
+use super::value::Value;
 
 /// Errors that can occur during resolution
 #[allow(missing_docs)]
 #[derive(Debug)]
 pub enum ResolveError {
-    ExpressionError(EvalexprError),
+    ExpressionError(ExpressionError),
     ExpressionContextError(NamespaceError),
     InvalidEncoding(String),
     InvalidByteOrder(String),
@@ -24,27 +28,272 @@ pub enum ResolveError {
     InvalidCharacter(String),
     InvalidErrorCorrectionType(String),
     InvalidExpressionString(String),
+    InvalidSemanticTerm(String),
+    InvalidParameterOperation(String),
+    InvalidOperand(String),
+    InvalidTransition(String),
+    InvalidStateReference(String),
+    DuplicateName(String),
+    EmptyStateMachine(String),
+    InvalidMathOperator(String),
+    InvalidComparisonOperator(String),
+    InvalidMathOperation(String),
+    InvalidStatement(String),
+    InvalidMetadataValue(String),
+    UnknownVariable(String),
+    InvalidCalibrator(String),
+    /// an error within an element or attribute, `path` is relative to the enclosing element
+    InElement {
+        path: String,
+        error: Box<ResolveError>,
+    },
+    /// errors of several children of an element, in document order
+    Multiple(Vec<ResolveError>),
 }
 
-fn eval_to_string(s: &str, ectx: &ExpressionContext) -> Result<String, ResolveError> {
-    let encoding_eval = ectx
-        .eval_expression(s)
-        .map_err(ResolveError::ExpressionContextError)?;
-    // convert Value to string
-    match encoding_eval.as_string() {
-        Ok(s) => Ok(s),
-        Err(_) => Ok(encoding_eval.to_string()),
+impl ResolveError {
+    /// path of the element or attribute the error occurred in, see [`crate::diagnostic`]
+    ///
+    /// of the first error, if there are several
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        let mut error = self;
+        loop {
+            match error {
+                ResolveError::InElement {
+                    path: segment,
+                    error: inner,
+                } => {
+                    if !path.is_empty() && !segment.starts_with('@') {
+                        path.push('/');
+                    }
+                    path.push_str(segment);
+                    error = inner;
+                }
+                ResolveError::Multiple(errors) if !errors.is_empty() => error = &errors[0],
+                _ => return path,
+            }
+        }
+    }
+
+    /// the error without the elements it occurred in, the first one if there are several
+    pub fn cause(&self) -> &ResolveError {
+        match self {
+            ResolveError::InElement { error, .. } => error.cause(),
+            ResolveError::Multiple(errors) if !errors.is_empty() => errors[0].cause(),
+            error => error,
+        }
+    }
+
+    /// the error without the elements it occurred in, the first one if there are several
+    pub fn into_cause(self) -> ResolveError {
+        match self {
+            ResolveError::InElement { error, .. } => error.into_cause(),
+            ResolveError::Multiple(mut errors) if !errors.is_empty() => {
+                errors.swap_remove(0).into_cause()
+            }
+            error => error,
+        }
+    }
+
+    /// every single error, each within the elements it occurred in
+    pub fn into_errors(self) -> Vec<ResolveError> {
+        match self {
+            ResolveError::InElement { path, error } => error
+                .into_errors()
+                .into_iter()
+                .map(|error| ResolveError::InElement {
+                    path: path.clone(),
+                    error: Box::new(error),
+                })
+                .collect(),
+            ResolveError::Multiple(errors) => errors
+                .into_iter()
+                .flat_map(ResolveError::into_errors)
+                .collect(),
+            error => vec![error],
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ResolveError::*;
+        match self {
+            ExpressionError(err) => err.fmt(f),
+            ExpressionContextError(err) => err.fmt(f),
+            InvalidEncoding(s) => write!(f, "invalid encoding `{}`", s),
+            InvalidByteOrder(s) => write!(
+                f,
+                "invalid byte order `{}`, expected littleEndian or bigEndian",
+                s
+            ),
+            InvalidSizeInBits(s) => write!(f, "invalid size in bits `{}`", s),
+            InvalidFalseValue(s) => write!(f, "invalid boolean `{}`", s),
+            InvalidEncodingAndPrecision(s) => {
+                write!(f, "invalid float encoding and precision `{}`", s)
+            }
+            InvalidRangeType(s) => write!(f, "invalid range type `{}`", s),
+            InvalidCharacter(s) => write!(f, "invalid character `{}`", s),
+            InvalidErrorCorrectionType(s) => write!(f, "invalid error control type `{}`", s),
+            InvalidExpressionString(s) => write!(f, "invalid expression `{}`", s),
+            InvalidSemanticTerm(s) => write!(f, "unknown semantic term `{}`", s),
+            InvalidParameterOperation(s) => {
+                write!(
+                    f,
+                    "invalid parameter operation `{}`, expected get or set",
+                    s
+                )
+            }
+            InvalidOperand(s) => write!(f, "invalid operand: {}", s),
+            InvalidTransition(s) => write!(f, "invalid transition `{}`", s),
+            InvalidStateReference(s) => write!(f, "transition refers to an unknown state `{}`", s),
+            DuplicateName(s) => write!(f, "duplicate name `{}`", s),
+            EmptyStateMachine(s) => write!(f, "state machine `{}` has no states", s),
+            InvalidMathOperator(s) => write!(f, "unknown math operator `{}`", s),
+            InvalidComparisonOperator(s) => write!(f, "unknown comparison operator `{}`", s),
+            InvalidMathOperation(s) => write!(f, "invalid math operation: {}", s),
+            InvalidStatement(s) => write!(f, "invalid statement: {}", s),
+            InvalidMetadataValue(s) => write!(f, "invalid metadata value: {}", s),
+            UnknownVariable(s) => write!(f, "unknown variable `{}`", s),
+            InvalidCalibrator(s) => write!(f, "invalid calibrator: {}", s),
+            InElement { .. } => write!(f, "{}: {}", self.path(), self.cause()),
+            Multiple(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    error.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// record the element or attribute an error occurred in
+trait Within<T> {
+    /// `segment` is an element like `IntegerDataType[Count]` or an attribute like `@sizeInBits`
+    fn within(self, segment: impl FnOnce() -> String) -> Result<T, ResolveError>;
+}
+
+impl<T> Within<T> for Result<T, ResolveError> {
+    fn within(self, segment: impl FnOnce() -> String) -> Result<T, ResolveError> {
+        self.map_err(|error| ResolveError::InElement {
+            path: segment(),
+            error: Box::new(error),
+        })
+    }
+}
+
+/// Errors of the children of an element, so that resolution keeps going after the first one
+#[derive(Default)]
+struct Errors(Vec<ResolveError>);
+
+impl Errors {
+    /// the value of a result, or a placeholder after recording its error
+    fn or_default<T: Default>(&mut self, result: Result<T, ResolveError>) -> T {
+        result.unwrap_or_else(|error| {
+            self.0.push(error);
+            T::default()
+        })
+    }
+
+    /// record the error of a check
+    fn check(&mut self, result: Result<(), ResolveError>) {
+        if let Err(error) = result {
+            self.0.push(error);
+        }
+    }
+
+    /// the value if no error was recorded
+    fn finish<T>(mut self, value: T) -> Result<T, ResolveError> {
+        match self.0.len() {
+            0 => Ok(value),
+            1 => Err(self.0.remove(0)),
+            _ => Err(ResolveError::Multiple(self.0)),
+        }
+    }
+}
+
+/// collect resolved items, with the errors of all items that fail
+trait CollectAll<T> {
+    fn collect_all(self) -> Result<Vec<T>, ResolveError>;
+}
+
+impl<T, I: Iterator<Item = Result<T, ResolveError>>> CollectAll<T> for I {
+    fn collect_all(self) -> Result<Vec<T>, ResolveError> {
+        let mut errors = Errors::default();
+        let items = self
+            .filter_map(|result| result.map_err(|error| errors.0.push(error)).ok())
+            .collect();
+        errors.finish(items)
+    }
+}
+
+/// path segment of a named element
+fn named(element: &str, name: &str) -> String {
+    format!("{}[{}]", element, name)
+}
+
+fn data_type_segment(dt: &raw::DataType) -> String {
+    let (element, name) = match dt {
+        raw::DataType::NoneDataType => return String::new(),
+        raw::DataType::BooleanDataType(dt) => ("BooleanDataType", &dt.name_entity_type.name),
+        raw::DataType::IntegerDataType(dt) => ("IntegerDataType", &dt.name_entity_type.name),
+        raw::DataType::ArrayDataType(dt) => ("ArrayDataType", &dt.name_entity_type.name),
+        raw::DataType::EnumeratedDataType(dt) => ("EnumeratedDataType", &dt.name_entity_type.name),
+        raw::DataType::ContainerDataType(dt) => ("ContainerDataType", &dt.name_entity_type.name),
+        raw::DataType::FloatDataType(dt) => ("FloatDataType", &dt.name_entity_type.name),
+        raw::DataType::StringDataType(dt) => ("StringDataType", &dt.name_entity_type.name),
+        raw::DataType::SubRangeDataType(dt) => ("SubRangeDataType", &dt.name_entity_type.name),
+    };
+    named(element, name)
+}
+
+/// segment of an entry; padding entries have no name and are indexed by their position among
+/// the padding entries of their list, starting at 0
+fn entry_segment(entry: &raw::EntryElement, padding_index: usize) -> String {
+    let (element, name) = match entry {
+        raw::EntryElement::Entry(e) => ("Entry", &e.name_entity_type.name),
+        raw::EntryElement::FixedValueEntry(e) => ("FixedValueEntry", &e.name_entity_type.name),
+        raw::EntryElement::PaddingEntry(_) => return format!("PaddingEntry[{}]", padding_index),
+        raw::EntryElement::ListEntry(e) => ("ListEntry", &e.name_entity_type.name),
+        raw::EntryElement::LengthEntry(e) => ("LengthEntry", &e.name_entity_type.name),
+        raw::EntryElement::ErrorControlEntry(e) => ("ErrorControlEntry", &e.name_entity_type.name),
+    };
+    named(element, name)
+}
+
+fn metadata_segment(value: &raw::MetadataValue) -> String {
+    match value {
+        raw::MetadataValue::DateValue(v) => named("DateValue", &v.name_entity_type.name),
+        raw::MetadataValue::FloatValue(v) => named("FloatValue", &v.name_entity_type.name),
+        raw::MetadataValue::IntegerValue(v) => named("IntegerValue", &v.name_entity_type.name),
+        raw::MetadataValue::StringValue(v) => named("StringValue", &v.name_entity_type.name),
     }
 }
 
+fn state_machine_segment(element: &raw::StateMachineElement) -> String {
+    match element {
+        raw::StateMachineElement::EntryState(e) => named("EntryState", &e.name_entity_type.name),
+        raw::StateMachineElement::ExitState(e) => named("ExitState", &e.name_entity_type.name),
+        raw::StateMachineElement::State(e) => named("State", &e.name_entity_type.name),
+        raw::StateMachineElement::Transition(e) => named("Transition", &e.name_entity_type.name),
+    }
+}
+
+fn eval_to_string(s: &str, ectx: &ExpressionContext) -> Result<String, ResolveError> {
+    ectx.eval_expression(s)
+        .map(|value| value.to_string())
+        .map_err(ResolveError::ExpressionContextError)
+}
+
 fn eval_to_i64(s: &str, ectx: &ExpressionContext) -> Result<i64, ResolveError> {
-    let encoding_eval = ectx
-        .eval_expression(s)
-        .map_err(ResolveError::ExpressionContextError)?;
-    // convert Value to string
-    encoding_eval
-        .as_int()
-        .map_err(ResolveError::ExpressionError)
+    ectx.eval_integer(s)
+        .map_err(ResolveError::ExpressionContextError)
 }
 
 fn string_to_int_encoding(
@@ -74,6 +323,21 @@ fn string_to_str_encoding(
     }
 }
 
+fn string_to_term<T: SemanticTerm>(s: &str, ectx: &ExpressionContext) -> Result<T, ResolveError> {
+    let term_string = eval_to_string(s, ectx)?;
+    T::from_term(&term_string).ok_or(ResolveError::InvalidSemanticTerm(term_string))
+}
+
+fn option_to_term<T: SemanticTerm>(
+    s: &Option<String>,
+    ectx: &ExpressionContext,
+) -> Result<Option<T>, ResolveError> {
+    match s {
+        Some(s) => Ok(Some(string_to_term(s, ectx)?)),
+        None => Ok(None),
+    }
+}
+
 fn string_to_byte_order(s: &str, ectx: &ExpressionContext) -> Result<ast::ByteOrder, ResolveError> {
     let bo_string = eval_to_string(s, ectx)?;
     match bo_string.as_str() {
@@ -87,6 +351,94 @@ fn string_to_usize(s: &str, ectx: &ExpressionContext) -> Result<usize, ResolveEr
     Ok(eval_to_i64(s, ectx)? as usize)
 }
 
+fn string_to_parameter_operation(
+    s: &str,
+    ectx: &ExpressionContext,
+) -> Result<ast::ParameterOperation, ResolveError> {
+    let op_string = eval_to_string(s, ectx)?;
+    match op_string.as_str() {
+        "get" => Ok(ast::ParameterOperation::Get),
+        "set" => Ok(ast::ParameterOperation::Set),
+        _ => Err(ResolveError::InvalidParameterOperation(op_string)),
+    }
+}
+
+fn string_to_math_operator(
+    s: &str,
+    ectx: &ExpressionContext,
+) -> Result<ast::MathOperator, ResolveError> {
+    use ast::MathOperator::*;
+    let op_string = eval_to_string(s, ectx)?;
+    Ok(match op_string.as_str() {
+        "add" => Add,
+        "subtract" => Subtract,
+        "multiply" => Multiply,
+        "divide" => Divide,
+        "modulus" => Modulus,
+        "pow" => Pow,
+        "ln" => Ln,
+        "log" => Log,
+        "exp" => Exp,
+        "inverse" => Inverse,
+        "tan" => Tan,
+        "cos" => Cos,
+        "sin" => Sin,
+        "atan" => Atan,
+        "atan2" => Atan2,
+        "acos" => Acos,
+        "asin" => Asin,
+        "tanh" => Tanh,
+        "cosh" => Cosh,
+        "sinh" => Sinh,
+        "atanh" => Atanh,
+        "acosh" => Acosh,
+        "asinh" => Asinh,
+        "swap" => Swap,
+        "abs" => Abs,
+        "ceil" => Ceil,
+        "floor" => Floor,
+        "round" => Round,
+        "sqrt" => Sqrt,
+        "min" => Min,
+        "max" => Max,
+        _ => return Err(ResolveError::InvalidMathOperator(op_string)),
+    })
+}
+
+fn string_to_comparison_operator(
+    s: &str,
+    ectx: &ExpressionContext,
+) -> Result<ast::ComparisonOperator, ResolveError> {
+    let op_string = eval_to_string(s.trim(), ectx)?;
+    match op_string.as_str() {
+        "equals" => Ok(ast::ComparisonOperator::Equals),
+        "notEquals" => Ok(ast::ComparisonOperator::NotEquals),
+        "lessThan" => Ok(ast::ComparisonOperator::LessThan),
+        "lessThanEquals" => Ok(ast::ComparisonOperator::LessThanEquals),
+        "greaterThan" => Ok(ast::ComparisonOperator::GreaterThan),
+        "greaterThanEquals" => Ok(ast::ComparisonOperator::GreaterThanEquals),
+        _ => Err(ResolveError::InvalidComparisonOperator(op_string)),
+    }
+}
+
+/// resolve a choice of a VariableRef and a Value element, `owner` names the element in errors
+fn resolve_operand(
+    variable_ref: &Option<raw::VariableRefOperand>,
+    value: &Option<raw::ValueOperand>,
+    owner: &str,
+    ectx: &ExpressionContext,
+) -> Result<ast::Operand, ResolveError> {
+    match (value, variable_ref) {
+        (Some(v), None) => Ok(ast::Operand::Value(Literal(eval_to_string(
+            &v.value, ectx,
+        )?))),
+        (None, Some(vr)) => Ok(ast::Operand::VariableRef(ast::QualifiedName(
+            vr.variable_ref.clone(),
+        ))),
+        _ => Err(ResolveError::InvalidOperand(owner.to_string())),
+    }
+}
+
 fn string_to_false_value(s: &str, ectx: &ExpressionContext) -> Result<bool, ResolveError> {
     let s_string = eval_to_string(s, ectx)?;
     match s_string.as_str() {
@@ -170,53 +522,187 @@ pub trait Resolve<T> {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<T, ResolveError>;
 }
 
+/// resolve a package file, keeping going after errors
+///
+/// Returns the errors of all elements that could not be resolved, in document order and each
+/// within the elements it occurred in, instead of the first one only.
+pub fn resolve_all(
+    package_file: &raw::PackageFile,
+    ectx: &ExpressionContext,
+) -> Result<ast::PackageFile, Vec<ResolveError>> {
+    package_file
+        .resolve(ectx)
+        .map_err(ResolveError::into_errors)
+}
+
 impl Resolve<ast::PackageFile> for raw::PackageFile {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::PackageFile, ResolveError> {
-        let package = self
-            .package
-            .iter()
-            .map(|p| p.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(ast::PackageFile {
-            package,
-            metadata: match self.metadata {
-                Some(ref m) => Some(m.resolve(ectx)?),
-                None => None,
-            },
-        })
+        let mut errors = Errors::default();
+        let metadata = self
+            .metadata
+            .as_ref()
+            .map(|m| errors.or_default(m.resolve(ectx).within(|| "Metadata".to_string())));
+        let package = errors.or_default(
+            self.package
+                .iter()
+                .map(|p| {
+                    p.resolve(ectx)
+                        .within(|| named("Package", &p.name_entity_type.name))
+                })
+                .collect_all(),
+        );
+        errors.finish(ast::PackageFile { package, metadata })
     }
 }
 
 impl Resolve<ast::Package> for raw::Package {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Package, ResolveError> {
-        Ok(ast::Package {
-            name_entity_type: self.name_entity_type.resolve(ectx)?,
+        let mut errors = Errors::default();
+        let package = ast::Package {
+            name_entity_type: errors.or_default(self.name_entity_type.resolve(ectx)),
             data_type_set: match self.data_type_set {
-                Some(ref dts) => dts.resolve(ectx)?,
-                None => ast::DataTypeSet {
-                    data_types: Vec::new(),
-                },
+                Some(ref dts) => {
+                    errors.or_default(dts.resolve(ectx).within(|| "DataTypeSet".to_string()))
+                }
+                None => ast::DataTypeSet::default(),
             },
-        })
+            component_set: match self.component_set {
+                Some(ref cs) => {
+                    errors.or_default(cs.resolve(ectx).within(|| "ComponentSet".to_string()))
+                }
+                None => ast::ComponentSet::default(),
+            },
+        };
+        // components are checked against the data types that could be resolved
+        for component in package.component_set.components.iter() {
+            errors.check(
+                OperandScope::for_component(&package, component, ectx)
+                    .check_component(component)
+                    .within(|| format!("ComponentSet/{}", named("Component", &component.name.0))),
+            );
+        }
+        errors.finish(package)
     }
 }
 
 impl Resolve<ast::MetaData> for raw::MetaData {
-    fn resolve(&self, _: &ExpressionContext) -> Result<ast::MetaData, ResolveError> {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::MetaData, ResolveError> {
         Ok(ast::MetaData {
             creation_date: self.creation_date.clone(),
             creator: self.creator.clone(),
+            values: match self.value_set {
+                Some(ref vs) => vs.resolve(ectx).within(|| "MetadataValueSet".to_string())?,
+                None => Vec::new(),
+            },
+            categories: self
+                .categories
+                .iter()
+                .map(|c| {
+                    c.resolve(ectx)
+                        .within(|| named("Category", &c.name_entity_type.name))
+                })
+                .collect_all()?,
         })
     }
 }
 
+impl Resolve<ast::MetadataCategory> for raw::MetadataCategory {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::MetadataCategory, ResolveError> {
+        Ok(ast::MetadataCategory {
+            name_entity_type: self.name_entity_type.resolve(ectx)?,
+            values: match self.value_set {
+                Some(ref vs) => vs.resolve(ectx).within(|| "MetadataValueSet".to_string())?,
+                None => Vec::new(),
+            },
+            categories: self
+                .categories
+                .iter()
+                .map(|c| {
+                    c.resolve(ectx)
+                        .within(|| named("Category", &c.name_entity_type.name))
+                })
+                .collect_all()?,
+        })
+    }
+}
+
+impl Resolve<Vec<ast::MetadataValue>> for raw::MetadataValueSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<Vec<ast::MetadataValue>, ResolveError> {
+        self.values
+            .iter()
+            .map(|v| v.resolve(ectx).within(|| metadata_segment(v)))
+            .collect_all()
+    }
+}
+
+impl Resolve<ast::MetadataValue> for raw::MetadataValue {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::MetadataValue, ResolveError> {
+        // dates like 2024-05-01 must not be evaluated as arithmetic
+        let string_value = |v: &raw::StringMetadataValue| -> Result<_, ResolveError> {
+            Ok(ast::StringMetadataValue {
+                name_entity_type: v.name_entity_type.resolve(ectx)?,
+                value: Literal(
+                    ectx.substitute(&v.value)
+                        .map_err(ResolveError::ExpressionContextError)?,
+                ),
+            })
+        };
+        match self {
+            raw::MetadataValue::DateValue(v) => Ok(ast::MetadataValue::DateValue(string_value(v)?)),
+            raw::MetadataValue::StringValue(v) => {
+                Ok(ast::MetadataValue::StringValue(string_value(v)?))
+            }
+            raw::MetadataValue::FloatValue(v) => Ok(ast::MetadataValue::FloatValue(
+                resolve_numeric_metadata(v, Value::is_numeric, ectx)?,
+            )),
+            raw::MetadataValue::IntegerValue(v) => Ok(ast::MetadataValue::IntegerValue(
+                resolve_numeric_metadata(v, |value| matches!(value, Value::Integer(_)), ectx)?,
+            )),
+        }
+    }
+}
+
+/// Req 3.4.8, 3.4.9: a literal value of the right type, or a MathOperation or Conditional
+fn resolve_numeric_metadata(
+    v: &raw::NumericMetadataValue,
+    is_valid: fn(&Value) -> bool,
+    ectx: &ExpressionContext,
+) -> Result<ast::NumericMetadataValue, ResolveError> {
+    let name_entity_type = v.name_entity_type.resolve(ectx)?;
+    let value = match (&v.value, &v.math_operation, &v.conditional) {
+        (Some(literal), None, None) => {
+            let literal = eval_to_string(literal, ectx)?;
+            if !is_valid(&Value::parse(&literal)) {
+                return Err(ResolveError::InvalidMetadataValue(format!(
+                    "{}: {}",
+                    name_entity_type.name.0, literal
+                )));
+            }
+            ast::NumericValue::Literal(Literal(literal))
+        }
+        (None, Some(math), None) => ast::NumericValue::MathOperation(math.resolve(ectx)?),
+        (None, None, Some(conditional)) => {
+            ast::NumericValue::Conditional(conditional.resolve(ectx)?)
+        }
+        _ => {
+            return Err(ResolveError::InvalidMetadataValue(
+                name_entity_type.name.0.clone(),
+            ))
+        }
+    };
+    Ok(ast::NumericMetadataValue {
+        name_entity_type,
+        value,
+    })
+}
+
 impl Resolve<ast::DataTypeSet> for raw::DataTypeSet {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::DataTypeSet, ResolveError> {
         let data_types = self
             .data_types
             .iter()
-            .map(|p| p.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .map(|dt| dt.resolve(ectx).within(|| data_type_segment(dt)))
+            .collect_all()?;
         Ok(ast::DataTypeSet { data_types })
     }
 }
@@ -244,7 +730,7 @@ impl Resolve<ast::DataType> for raw::DataType {
             raw::DataType::SubRangeDataType(dt) => {
                 Ok(ast::DataType::SubRangeDataType(dt.resolve(ectx)?))
             }
-            r => panic!("{:?} not implemented", r),
+            raw::DataType::NoneDataType => Ok(ast::DataType::NoneDataType),
         }
     }
 }
@@ -253,9 +739,10 @@ impl Resolve<ast::SubRangeDataType> for raw::SubRangeDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::SubRangeDataType, ResolveError> {
         Ok(ast::SubRangeDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
-            base_type: ast::QualifiedName(eval_to_string(&self.base_type, ectx)?),
-            unit: eval_to_string(&self.unit, ectx)?,
-            range: self.range.resolve(ectx)?,
+            base_type: ast::QualifiedName(
+                eval_to_string(&self.base_type, ectx).within(|| "@baseType".to_string())?,
+            ),
+            range: self.range.resolve(ectx).within(|| "Range".to_string())?,
         })
     }
 }
@@ -264,8 +751,13 @@ impl Resolve<ast::ArrayDataType> for raw::ArrayDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ArrayDataType, ResolveError> {
         Ok(ast::ArrayDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
-            data_type_ref: ast::QualifiedName(eval_to_string(&self.data_type_ref, ectx)?),
-            dimension_list: self.dimension_list.resolve(ectx)?,
+            data_type_ref: ast::QualifiedName(
+                eval_to_string(&self.data_type_ref, ectx).within(|| "@dataTypeRef".to_string())?,
+            ),
+            dimension_list: self
+                .dimension_list
+                .resolve(ectx)
+                .within(|| "DimensionList".to_string())?,
         })
     }
 }
@@ -275,8 +767,9 @@ impl Resolve<ast::DimensionList> for raw::DimensionList {
         let dimension = self
             .dimension
             .iter()
-            .map(|d| d.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .enumerate()
+            .map(|(i, d)| d.resolve(ectx).within(|| format!("Dimension[{}]", i)))
+            .collect_all()?;
         Ok(ast::DimensionList { dimension })
     }
 }
@@ -284,7 +777,7 @@ impl Resolve<ast::DimensionList> for raw::DimensionList {
 impl Resolve<ast::Dimension> for raw::Dimension {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Dimension, ResolveError> {
         Ok(ast::Dimension {
-            size: string_to_usize(&self.size, ectx)?,
+            size: string_to_usize(&self.size, ectx).within(|| "@size".to_string())?,
         })
     }
 }
@@ -294,14 +787,19 @@ impl Resolve<ast::EnumeratedDataType> for raw::EnumeratedDataType {
         Ok(ast::EnumeratedDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
             encoding: match self.encoding {
-                Some(ref e) => e.resolve(ectx)?,
+                Some(ref e) => e
+                    .resolve(ectx)
+                    .within(|| "IntegerDataEncoding".to_string())?,
                 None => ast::IntegerDataEncoding {
                     encoding: ast::IntegerEncoding::Unsigned,
                     size_in_bits: 8,
                     byte_order: ast::ByteOrder::LittleEndian,
                 },
             },
-            enumeration_list: self.enumeration_list.resolve(ectx)?,
+            enumeration_list: self
+                .enumeration_list
+                .resolve(ectx)
+                .within(|| "EnumerationList".to_string())?,
         })
     }
 }
@@ -311,57 +809,78 @@ impl Resolve<ast::EnumerationList> for raw::EnumerationList {
         let enumeration = self
             .enumeration
             .iter()
-            .map(|e| e.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .enumerate()
+            .map(|(i, e)| e.resolve(ectx).within(|| format!("Enumeration[{}]", i)))
+            .collect_all()?;
         Ok(ast::EnumerationList { enumeration })
     }
 }
 
 impl Resolve<ast::Enumeration> for raw::Enumeration {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Enumeration, ResolveError> {
-        Ok(ast::Enumeration {
-            label: Identifier(eval_to_string(&self.label, ectx)?),
-            value: Literal(eval_to_string(&self.value, ectx)?),
+        let mut errors = Errors::default();
+        let enumeration = ast::Enumeration {
+            label: Identifier(
+                errors
+                    .or_default(eval_to_string(&self.label, ectx).within(|| "@label".to_string())),
+            ),
+            value: Literal(
+                errors
+                    .or_default(eval_to_string(&self.value, ectx).within(|| "@value".to_string())),
+            ),
             short_description: self.short_description.clone(),
-        })
+            semantics: errors.or_default(self.semantics.resolve(ectx)),
+            extensions: errors.or_default(self.extensions.resolve(ectx)),
+        };
+        errors.finish(enumeration)
     }
 }
 
 impl Resolve<ast::ContainerDataType> for raw::ContainerDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ContainerDataType, ResolveError> {
-        Ok(ast::ContainerDataType {
-            name_entity_type: self.name_entity_type.resolve(ectx)?,
+        let mut errors = Errors::default();
+        let container = ast::ContainerDataType {
+            name_entity_type: errors.or_default(self.name_entity_type.resolve(ectx)),
             _abstract: match self._abstract {
-                Some(ref a) => string_to_boolean(a, ectx)?,
+                Some(ref a) => {
+                    errors.or_default(string_to_boolean(a, ectx).within(|| "@abstract".to_string()))
+                }
                 None => false,
             },
-            base_type: match self.base_type {
-                Some(ref bt) => Some(ast::QualifiedName(eval_to_string(bt, ectx)?)),
-                None => None,
-            },
-            entry_list: match self.entry_list {
-                Some(ref el) => Some(el.resolve(ectx)?),
-                None => None,
-            },
-            constraint_set: match self.constraint_set {
-                Some(ref cs) => Some(cs.resolve(ectx)?),
-                None => None,
-            },
-            trailer_entry_list: match self.trailer_entry_list {
-                Some(ref tel) => Some(tel.resolve(ectx)?),
-                None => None,
-            },
-        })
+            base_type: self.base_type.as_ref().map(|bt| {
+                ast::QualifiedName(
+                    errors.or_default(eval_to_string(bt, ectx).within(|| "@baseType".to_string())),
+                )
+            }),
+            entry_list: self
+                .entry_list
+                .as_ref()
+                .map(|el| errors.or_default(el.resolve(ectx).within(|| "EntryList".to_string()))),
+            constraint_set: self.constraint_set.as_ref().map(|cs| {
+                errors.or_default(cs.resolve(ectx).within(|| "ConstraintSet".to_string()))
+            }),
+            trailer_entry_list: self.trailer_entry_list.as_ref().map(|tel| {
+                errors.or_default(tel.resolve(ectx).within(|| "TrailerEntryList".to_string()))
+            }),
+        };
+        errors.finish(container)
     }
 }
 
 impl Resolve<ast::EntryList> for raw::EntryList {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::EntryList, ResolveError> {
+        let mut paddings = 0;
         let entries = self
             .entries
             .iter()
-            .map(|e| e.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .map(|e| {
+                let padding_index = paddings;
+                if let raw::EntryElement::PaddingEntry(_) = e {
+                    paddings += 1;
+                }
+                e.resolve(ectx).within(|| entry_segment(e, padding_index))
+            })
+            .collect_all()?;
         Ok(ast::EntryList { entries })
     }
 }
@@ -372,7 +891,7 @@ impl Resolve<ast::ConstraintSet> for raw::ConstraintSet {
             .constraints
             .iter()
             .map(|c| c.resolve(ectx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect_all()?;
         Ok(ast::ConstraintSet { constraints })
     }
 }
@@ -453,7 +972,8 @@ impl Resolve<ast::Entry> for raw::Entry {
 impl Resolve<ast::PaddingEntry> for raw::PaddingEntry {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::PaddingEntry, ResolveError> {
         Ok(ast::PaddingEntry {
-            size_in_bits: string_to_usize(&self.size_in_bits, ectx)?,
+            size_in_bits: string_to_usize(&self.size_in_bits, ectx)
+                .within(|| "@sizeInBits".to_string())?,
             short_description: self.short_description.clone(),
         })
     }
@@ -475,11 +995,81 @@ impl Resolve<ast::LengthEntry> for raw::LengthEntry {
 impl Resolve<ast::PolynomialCalibrator> for raw::PolynomialCalibrator {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::PolynomialCalibrator, ResolveError> {
         Ok(ast::PolynomialCalibrator {
-            term: self
-                .term
-                .iter()
-                .map(|t| t.resolve(ectx))
-                .collect::<Result<Vec<_>, _>>()?,
+            term: self.term.iter().map(|t| t.resolve(ectx)).collect_all()?,
+        })
+    }
+}
+
+/// the calibrator of a numeric data type, which has a polynomial or a spline calibrator
+fn calibrator(
+    polynomial_calibrator: &Option<raw::PolynomialCalibrator>,
+    spline_calibrator: &Option<raw::SplineCalibrator>,
+    ectx: &ExpressionContext,
+) -> Result<Option<ast::Calibrator>, ResolveError> {
+    match (polynomial_calibrator, spline_calibrator) {
+        (None, None) => Ok(None),
+        (Some(pc), None) => Ok(Some(ast::Calibrator::PolynomialCalibrator(
+            pc.resolve(ectx)
+                .within(|| "PolynomialCalibrator".to_string())?,
+        ))),
+        (None, Some(sc)) => Ok(Some(ast::Calibrator::SplineCalibrator(
+            sc.resolve(ectx).within(|| "SplineCalibrator".to_string())?,
+        ))),
+        (Some(_), Some(_)) => Err(ResolveError::InvalidCalibrator(
+            "both a polynomial and a spline calibrator".to_string(),
+        )),
+    }
+}
+
+impl Resolve<ast::SplineCalibrator> for raw::SplineCalibrator {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::SplineCalibrator, ResolveError> {
+        let spline_point = self
+            .spline_point
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p.resolve(ectx).within(|| format!("SplinePoint[{}]", i)))
+            .collect_all()?;
+        Ok(ast::SplineCalibrator {
+            extrapolate: match self.extrapolate {
+                Some(ref e) => string_to_boolean(e, ectx).within(|| "@extrapolate".to_string())?,
+                None => false,
+            },
+            spline_point,
+        })
+    }
+}
+
+impl Resolve<ast::SplinePoint> for raw::SplinePoint {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::SplinePoint, ResolveError> {
+        let order = match self.order {
+            Some(ref order) => {
+                let order = eval_to_string(order, ectx).within(|| "@order".to_string())?;
+                order
+                    .parse()
+                    .ok()
+                    .filter(|order| (1..=3).contains(order))
+                    .ok_or_else(|| {
+                        ResolveError::InvalidCalibrator(format!("invalid order `{}`", order))
+                    })
+                    .within(|| "@order".to_string())?
+            }
+            None => 1,
+        };
+        let number = |value: &str, attribute: &str| {
+            let value = eval_to_string(value, ectx)?;
+            match value.parse::<f64>() {
+                Ok(_) => Ok(Literal(value)),
+                Err(_) => Err(ResolveError::InvalidCalibrator(format!(
+                    "`{}` is not a number",
+                    value
+                ))),
+            }
+            .within(|| format!("@{}", attribute))
+        };
+        Ok(ast::SplinePoint {
+            order,
+            raw: number(&self.raw, "raw")?,
+            calibrated: number(&self.calibrated, "calibrated")?,
         })
     }
 }
@@ -507,7 +1097,8 @@ impl Resolve<ast::ErrorControlEntry> for raw::ErrorControlEntry {
         Ok(ast::ErrorControlEntry {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
             type_: ast::QualifiedName(eval_to_string(&self.type_, ectx)?),
-            error_control_type: string_to_ect(&self.error_control_type, ectx)?,
+            error_control_type: string_to_ect(&self.error_control_type, ectx)
+                .within(|| "@errorControlType".to_string())?,
         })
     }
 }
@@ -526,17 +1117,23 @@ impl Resolve<ast::FloatDataType> for raw::FloatDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::FloatDataType, ResolveError> {
         Ok(ast::FloatDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
+            calibrator: calibrator(&self.polynomial_calibrator, &self.spline_calibrator, ectx)?,
             encoding: match self.encoding {
-                Some(ref fde) => fde.resolve(ectx)?,
+                Some(ref fde) => fde
+                    .resolve(ectx)
+                    .within(|| "FloatDataEncoding".to_string())?,
                 None => ast::FloatDataEncoding {
                     size_in_bits: 0,
                     encoding_and_precision: ast::FloatEncodingAndPrecision::IEEE7542008Single,
                     byte_order: ast::ByteOrder::LittleEndian,
                 },
             },
+            // the precision of a float is given by its encoding already
             range: match self.range {
-                Some(ref r) => Some(r.resolve(ectx)?),
-                None => None,
+                Some(ref r) if r.precision_range.is_none() => {
+                    Some(r.resolve(ectx).within(|| "Range".to_string())?)
+                }
+                _ => None,
             },
         })
     }
@@ -545,13 +1142,17 @@ impl Resolve<ast::FloatDataType> for raw::FloatDataType {
 impl Resolve<ast::FloatDataEncoding> for raw::FloatDataEncoding {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::FloatDataEncoding, ResolveError> {
         Ok(ast::FloatDataEncoding {
-            size_in_bits: string_to_usize(&self.size_in_bits, ectx)?,
+            size_in_bits: string_to_usize(&self.size_in_bits, ectx)
+                .within(|| "@sizeInBits".to_string())?,
             encoding_and_precision: string_to_encoding_and_precision(
                 &self.encoding_and_precision,
                 ectx,
-            )?,
+            )
+            .within(|| "@encodingAndPrecision".to_string())?,
             byte_order: match self.byte_order {
-                Some(ref bo) => string_to_byte_order(bo, ectx)?,
+                Some(ref bo) => {
+                    string_to_byte_order(bo, ectx).within(|| "@byteOrder".to_string())?
+                }
                 None => ast::ByteOrder::LittleEndian,
             },
         })
@@ -561,7 +1162,10 @@ impl Resolve<ast::FloatDataEncoding> for raw::FloatDataEncoding {
 impl Resolve<ast::Range> for raw::Range {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Range, ResolveError> {
         Ok(ast::Range {
-            min_max_range: self.min_max_range.resolve(ectx)?,
+            min_max_range: self
+                .min_max_range
+                .resolve(ectx)
+                .within(|| "MinMaxRange".to_string())?,
         })
     }
 }
@@ -571,7 +1175,8 @@ impl Resolve<ast::MinMaxRange> for raw::MinMaxRange {
         Ok(ast::MinMaxRange {
             min: Literal(self.min.clone()),
             max: Literal(self.max.clone()),
-            range_type: string_to_range_type(&self.range_type, ectx)?,
+            range_type: string_to_range_type(&self.range_type, ectx)
+                .within(|| "@rangeType".to_string())?,
         })
     }
 }
@@ -580,16 +1185,20 @@ impl Resolve<ast::StringDataType> for raw::StringDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::StringDataType, ResolveError> {
         Ok(ast::StringDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
-            length: string_to_usize(&self.length, ectx)?,
+            length: string_to_usize(&self.length, ectx).within(|| "@length".to_string())?,
             encoding: match self.encoding {
-                Some(ref sde) => sde.resolve(ectx)?,
+                Some(ref sde) => sde
+                    .resolve(ectx)
+                    .within(|| "StringDataEncoding".to_string())?,
                 None => ast::StringDataEncoding {
                     encoding: ast::StringEncoding::ASCII,
                     termination_character: None,
                 },
             },
             fixed_length: match self.fixed_length {
-                Some(ref fl) => string_to_boolean(fl, ectx)?,
+                Some(ref fl) => {
+                    string_to_boolean(fl, ectx).within(|| "@fixedLength".to_string())?
+                }
                 None => false,
             },
         })
@@ -600,11 +1209,15 @@ impl Resolve<ast::StringDataEncoding> for raw::StringDataEncoding {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::StringDataEncoding, ResolveError> {
         Ok(ast::StringDataEncoding {
             encoding: match self.encoding {
-                Some(ref se) => string_to_str_encoding(se, ectx)?,
+                Some(ref se) => {
+                    string_to_str_encoding(se, ectx).within(|| "@encoding".to_string())?
+                }
                 None => ast::StringEncoding::ASCII,
             },
             termination_character: match &self.termination_character {
-                Some(tc) => Some(string_to_tc(tc, ectx)?),
+                Some(tc) => {
+                    Some(string_to_tc(tc, ectx).within(|| "@terminationCharacter".to_string())?)
+                }
                 None => None,
             },
         })
@@ -616,7 +1229,9 @@ impl Resolve<ast::BooleanDataType> for raw::BooleanDataType {
         Ok(ast::BooleanDataType {
             name_entity_type: self.name_entity_type.resolve(ectx)?,
             encoding: match self.encoding {
-                Some(ref bde) => bde.resolve(ectx)?,
+                Some(ref bde) => bde
+                    .resolve(ectx)
+                    .within(|| "BooleanDataEncoding".to_string())?,
                 None => ast::BooleanDataEncoding {
                     size_in_bits: 1,
                     false_value: true,
@@ -629,9 +1244,12 @@ impl Resolve<ast::BooleanDataType> for raw::BooleanDataType {
 impl Resolve<ast::BooleanDataEncoding> for raw::BooleanDataEncoding {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::BooleanDataEncoding, ResolveError> {
         Ok(ast::BooleanDataEncoding {
-            size_in_bits: string_to_usize(&self.size_in_bits, ectx)?,
+            size_in_bits: string_to_usize(&self.size_in_bits, ectx)
+                .within(|| "@sizeInBits".to_string())?,
             false_value: match self.false_value {
-                Some(ref fv) => string_to_false_value(fv, ectx)?,
+                Some(ref fv) => {
+                    string_to_false_value(fv, ectx).within(|| "@falseValue".to_string())?
+                }
                 None => true,
             },
         })
@@ -640,31 +1258,48 @@ impl Resolve<ast::BooleanDataEncoding> for raw::BooleanDataEncoding {
 
 impl Resolve<ast::IntegerDataType> for raw::IntegerDataType {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::IntegerDataType, ResolveError> {
-        Ok(ast::IntegerDataType {
-            name_entity_type: self.name_entity_type.resolve(ectx)?,
+        let mut errors = Errors::default();
+        let integer = ast::IntegerDataType {
+            name_entity_type: errors.or_default(self.name_entity_type.resolve(ectx)),
+            calibrator: errors.or_default(calibrator(
+                &self.polynomial_calibrator,
+                &self.spline_calibrator,
+                ectx,
+            )),
             encoding: match self.encoding {
-                Some(ref ide) => ide.resolve(ectx)?,
+                Some(ref ide) => errors.or_default(
+                    ide.resolve(ectx)
+                        .within(|| "IntegerDataEncoding".to_string()),
+                ),
                 None => ast::IntegerDataEncoding {
                     size_in_bits: 0,
                     encoding: ast::IntegerEncoding::Unsigned,
                     byte_order: ast::ByteOrder::LittleEndian,
                 },
             },
-            range: self.range.resolve(ectx)?,
-        })
+            range: errors.or_default(self.range.resolve(ectx).within(|| "Range".to_string())),
+        };
+        errors.finish(integer)
     }
 }
 
 impl Resolve<ast::IntegerDataEncoding> for IntegerDataEncoding {
     fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::IntegerDataEncoding, ResolveError> {
-        Ok(ast::IntegerDataEncoding {
-            size_in_bits: string_to_usize(&self.size_in_bits, ectx)?,
-            encoding: string_to_int_encoding(&self.encoding, ectx)?,
+        let mut errors = Errors::default();
+        let encoding = ast::IntegerDataEncoding {
+            size_in_bits: errors.or_default(
+                string_to_usize(&self.size_in_bits, ectx).within(|| "@sizeInBits".to_string()),
+            ),
+            encoding: errors.or_default(
+                string_to_int_encoding(&self.encoding, ectx).within(|| "@encoding".to_string()),
+            ),
             byte_order: match self.byte_order {
-                Some(ref bo) => string_to_byte_order(bo, ectx)?,
+                Some(ref bo) => errors
+                    .or_default(string_to_byte_order(bo, ectx).within(|| "@byteOrder".to_string())),
                 None => ast::ByteOrder::LittleEndian,
             },
-        })
+        };
+        errors.finish(encoding)
     }
 }
 
@@ -677,10 +1312,134 @@ impl Resolve<ast::NamedEntityType> for raw::NamedEntityType {
                 Some(ld) => Some(ld.resolve(ectx)?),
                 None => None,
             },
+            semantics: self.semantics.resolve(ectx)?,
+            extensions: self.extensions.resolve(ectx)?,
+        })
+    }
+}
+
+/// the text of extensions is not evaluated, only its parameters are substituted
+fn substitute(s: &str, ectx: &ExpressionContext) -> Result<String, ResolveError> {
+    ectx.substitute(s)
+        .map_err(ResolveError::ExpressionContextError)
+}
+
+impl Resolve<ExtensionAttribute> for ExtensionAttribute {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ExtensionAttribute, ResolveError> {
+        Ok(ExtensionAttribute {
+            value: substitute(&self.value, ectx).within(|| format!("@{}", self.name))?,
+            ..self.clone()
         })
     }
 }
 
+impl Resolve<ExtensionElement> for ExtensionElement {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ExtensionElement, ResolveError> {
+        let mut errors = Errors::default();
+        let element = ExtensionElement {
+            namespace: self.namespace.clone(),
+            name: self.name.clone(),
+            attributes: errors.or_default(
+                self.attributes
+                    .iter()
+                    .map(|a| a.resolve(ectx))
+                    .collect_all(),
+            ),
+            text: errors.or_default(substitute(&self.text, ectx)),
+            children: errors.or_default(extension_elements(&self.children, ectx)),
+        };
+        errors.finish(element)
+    }
+}
+
+/// resolve extension elements, named like the source map of the parser does
+fn extension_elements(
+    elements: &[ExtensionElement],
+    ectx: &ExpressionContext,
+) -> Result<Vec<ExtensionElement>, ResolveError> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    elements
+        .iter()
+        .map(|element| {
+            let count = counts.entry(element.name.as_str()).or_insert(0);
+            let index = *count;
+            *count += 1;
+            element.resolve(ectx).within(|| match index {
+                0 => element.name.clone(),
+                index => format!("{}[{}]", element.name, index),
+            })
+        })
+        .collect_all()
+}
+
+impl Resolve<Extensions> for Extensions {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<Extensions, ResolveError> {
+        let mut errors = Errors::default();
+        let extensions = Extensions {
+            attributes: errors.or_default(
+                self.attributes
+                    .iter()
+                    .map(|a| a.resolve(ectx))
+                    .collect_all(),
+            ),
+            elements: errors.or_default(extension_elements(&self.elements, ectx)),
+        };
+        errors.finish(extensions)
+    }
+}
+
+impl Resolve<ast::Semantics> for raw::Semantics {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Semantics, ResolveError> {
+        let mut errors = Errors::default();
+        let semantics = ast::Semantics {
+            quantity_kind: errors.or_default(
+                option_to_term(&self.quantity_kind, ectx).within(|| "@quantityKind".to_string()),
+            ),
+            unit: errors
+                .or_default(option_to_term(&self.unit, ectx).within(|| "@unit".to_string())),
+            chirality: errors.or_default(
+                option_to_term(&self.chirality, ectx).within(|| "@chirality".to_string()),
+            ),
+            coordinate_type: errors.or_default(
+                option_to_term(&self.coordinate_type, ectx)
+                    .within(|| "@coordinateType".to_string()),
+            ),
+            to_coordinate_type: errors.or_default(
+                option_to_term(&self.to_coordinate_type, ectx)
+                    .within(|| "@toCoordinateType".to_string()),
+            ),
+            difference_convention: errors.or_default(
+                option_to_term(&self.difference_convention, ectx)
+                    .within(|| "@differenceConvention".to_string()),
+            ),
+            interpretation: errors.or_default(
+                option_to_term(&self.interpretation, ectx).within(|| "@interpretation".to_string()),
+            ),
+            purpose: errors
+                .or_default(option_to_term(&self.purpose, ectx).within(|| "@purpose".to_string())),
+            reference_frame: errors.or_default(
+                option_to_term(&self.reference_frame, ectx)
+                    .within(|| "@referenceFrame".to_string()),
+            ),
+            to_frame: errors
+                .or_default(option_to_term(&self.to_frame, ectx).within(|| "@toFrame".to_string())),
+            relation_to_timestamp: errors.or_default(
+                option_to_term(&self.relation_to_timestamp, ectx)
+                    .within(|| "@relationToTimestamp".to_string()),
+            ),
+            status_convention: errors.or_default(
+                option_to_term(&self.status_convention, ectx)
+                    .within(|| "@statusConvention".to_string()),
+            ),
+            transformation_type: errors.or_default(
+                option_to_term(&self.transformation_type, ectx)
+                    .within(|| "@transformationType".to_string()),
+            ),
+        };
+        errors.finish(semantics)
+    }
+}
+
 impl Resolve<ast::LongDescription> for raw::LongDescription {
     fn resolve(&self, _: &ExpressionContext) -> Result<ast::LongDescription, ResolveError> {
         Ok(ast::LongDescription {
@@ -688,3 +1447,846 @@ impl Resolve<ast::LongDescription> for raw::LongDescription {
         })
     }
 }
+
+impl Resolve<ast::ComponentSet> for raw::ComponentSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ComponentSet, ResolveError> {
+        let components = self
+            .components
+            .iter()
+            .map(|c| c.resolve(ectx).within(|| named("Component", &c.name)))
+            .collect_all()?;
+        Ok(ast::ComponentSet { components })
+    }
+}
+
+impl Resolve<ast::Component> for raw::Component {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Component, ResolveError> {
+        let mut errors = Errors::default();
+        let component = ast::Component {
+            name: Identifier(self.name.clone()),
+            required_interface_set: errors.or_default(
+                self.required_interface_set
+                    .resolve(ectx)
+                    .within(|| "RequiredInterfaceSet".to_string()),
+            ),
+            implementation: errors.or_default(
+                self.implementation
+                    .resolve(ectx)
+                    .within(|| "Implementation".to_string()),
+            ),
+        };
+        errors.finish(component)
+    }
+}
+
+impl Resolve<ast::RequiredInterfaceSet> for raw::RequiredInterfaceSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::RequiredInterfaceSet, ResolveError> {
+        let interfaces = self
+            .interfaces
+            .iter()
+            .map(|i| i.resolve(ectx).within(|| named("Interface", &i.name)))
+            .collect_all()?;
+        Ok(ast::RequiredInterfaceSet { interfaces })
+    }
+}
+
+impl Resolve<ast::Interface> for raw::Interface {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Interface, ResolveError> {
+        Ok(ast::Interface {
+            name: Identifier(self.name.clone()),
+            type_: ast::QualifiedName(eval_to_string(&self.type_, ectx)?),
+            short_description: self.short_description.clone().unwrap_or_default(),
+            generic_type_map_set: self.generic_type_map_set.resolve(ectx)?,
+        })
+    }
+}
+
+impl Resolve<ast::GenericTypeMapSet> for raw::GenericTypeMapSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::GenericTypeMapSet, ResolveError> {
+        let generic_type_maps = self
+            .generic_type_maps
+            .iter()
+            .map(|m| {
+                Ok(ast::GenericTypeMap {
+                    name: Identifier(m.name.clone()),
+                    type_: ast::QualifiedName(eval_to_string(&m.type_, ectx)?),
+                })
+            })
+            .collect_all()?;
+        Ok(ast::GenericTypeMapSet { generic_type_maps })
+    }
+}
+
+impl Resolve<ast::Implementation> for raw::Implementation {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Implementation, ResolveError> {
+        let mut errors = Errors::default();
+        let implementation = ast::Implementation {
+            variable_set: errors.or_default(
+                self.variable_set
+                    .resolve(ectx)
+                    .within(|| "VariableSet".to_string()),
+            ),
+            parameter_map_set: errors.or_default(
+                self.parameter_map_set
+                    .resolve(ectx)
+                    .within(|| "ParameterMapSet".to_string()),
+            ),
+            activity_set: errors.or_default(
+                self.activity_set
+                    .resolve(ectx)
+                    .within(|| "ActivitySet".to_string()),
+            ),
+            state_machine_set: errors.or_default(
+                self.state_machine_set
+                    .resolve(ectx)
+                    .within(|| "StateMachineSet".to_string()),
+            ),
+        };
+        errors.finish(implementation)
+    }
+}
+
+impl Resolve<ast::VariableSet> for raw::VariableSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::VariableSet, ResolveError> {
+        let variables = self
+            .variables
+            .iter()
+            .map(|v| {
+                Ok(ast::Variable {
+                    type_: ast::QualifiedName(eval_to_string(&v.type_, ectx)?),
+                    read_only: v.read_only,
+                    name: Identifier(v.name.clone()),
+                    initial_value: match v.initial_value {
+                        Some(ref iv) => Some(Literal(eval_to_string(iv, ectx)?)),
+                        None => None,
+                    },
+                })
+            })
+            .collect_all()?;
+        Ok(ast::VariableSet { variables })
+    }
+}
+
+impl Resolve<ast::ParameterMapSet> for raw::ParameterMapSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ParameterMapSet, ResolveError> {
+        let parameter_maps = self
+            .parameter_maps
+            .iter()
+            .map(|m| {
+                Ok(ast::ParameterMap {
+                    interface: m.interface.clone(),
+                    parameter: m.parameter.clone(),
+                    variable_ref: ast::QualifiedName(eval_to_string(&m.variable_ref, ectx)?),
+                })
+            })
+            .collect_all()?;
+        Ok(ast::ParameterMapSet { parameter_maps })
+    }
+}
+
+impl Resolve<ast::StateMachineSet> for raw::StateMachineSet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::StateMachineSet, ResolveError> {
+        let state_machines = self
+            .state_machines
+            .iter()
+            .map(|sm| {
+                sm.resolve(ectx)
+                    .within(|| named("StateMachine", &sm.name_entity_type.name))
+            })
+            .collect_all()?;
+
+        // state machine names are unique within a component
+        let mut names = std::collections::HashSet::new();
+        for sm in state_machines.iter() {
+            if !names.insert(&sm.name_entity_type.name.0) {
+                return Err(ResolveError::DuplicateName(
+                    sm.name_entity_type.name.0.clone(),
+                ));
+            }
+        }
+
+        Ok(ast::StateMachineSet { state_machines })
+    }
+}
+
+impl Resolve<ast::StateMachine> for raw::StateMachine {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::StateMachine, ResolveError> {
+        let name_entity_type = self.name_entity_type.resolve(ectx)?;
+        let elements = self
+            .elements
+            .iter()
+            .map(|e| e.resolve(ectx).within(|| state_machine_segment(e)))
+            .collect_all()?;
+
+        // Req 3.16.2: one or more EntryState, ExitState, State and Transition elements
+        if elements.is_empty() {
+            return Err(ResolveError::EmptyStateMachine(
+                name_entity_type.name.0.clone(),
+            ));
+        }
+
+        // Req 3.16.4: child element names are unique within the state machine
+        let mut names = std::collections::HashSet::new();
+        for element in elements.iter() {
+            let name = &element.name_entity_type().name.0;
+            if !names.insert(name) {
+                return Err(ResolveError::DuplicateName(format!(
+                    "{}/{}",
+                    name_entity_type.name.0, name
+                )));
+            }
+        }
+
+        // transitions may only refer to states of this state machine
+        for element in elements.iter() {
+            if let ast::StateMachineElement::Transition(t) = element {
+                for state in [&t.from_state, &t.to_state] {
+                    let found = elements.iter().any(|e| {
+                        !matches!(e, ast::StateMachineElement::Transition(_))
+                            && e.name_entity_type().name == *state
+                    });
+                    if !found {
+                        return Err(ResolveError::InvalidStateReference(format!(
+                            "{}/{}",
+                            name_entity_type.name.0, state.0
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(ast::StateMachine {
+            name_entity_type,
+            elements,
+        })
+    }
+}
+
+impl Resolve<ast::StateMachineElement> for raw::StateMachineElement {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::StateMachineElement, ResolveError> {
+        match self {
+            raw::StateMachineElement::EntryState(es) => {
+                Ok(ast::StateMachineElement::EntryState(ast::EntryState {
+                    name_entity_type: es.name_entity_type.resolve(ectx)?,
+                }))
+            }
+            raw::StateMachineElement::ExitState(es) => {
+                Ok(ast::StateMachineElement::ExitState(ast::ExitState {
+                    name_entity_type: es.name_entity_type.resolve(ectx)?,
+                }))
+            }
+            raw::StateMachineElement::State(s) => Ok(ast::StateMachineElement::State(ast::State {
+                name_entity_type: s.name_entity_type.resolve(ectx)?,
+                on_entry: match s.on_entry {
+                    Some(ref ai) => Some(ai.resolve(ectx)?),
+                    None => None,
+                },
+                on_exit: match s.on_exit {
+                    Some(ref ai) => Some(ai.resolve(ectx)?),
+                    None => None,
+                },
+            })),
+            raw::StateMachineElement::Transition(t) => {
+                Ok(ast::StateMachineElement::Transition(t.resolve(ectx)?))
+            }
+        }
+    }
+}
+
+impl Resolve<ast::Transition> for raw::Transition {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Transition, ResolveError> {
+        let name_entity_type = self.name_entity_type.resolve(ectx)?;
+        let trigger = match (
+            &self.on_command_primitive,
+            &self.on_parameter_primitive,
+            &self.on_timer,
+        ) {
+            (Some(cmd), None, None) => {
+                ast::TransitionTrigger::OnCommandPrimitive(cmd.resolve(ectx)?)
+            }
+            (None, Some(param), None) => {
+                ast::TransitionTrigger::OnParameterPrimitive(param.resolve(ectx)?)
+            }
+            (None, None, Some(timer)) => ast::TransitionTrigger::OnTimer(ast::OnTimer {
+                nanoseconds_after_entry: match timer.nanoseconds_after_entry {
+                    Some(ref ns) => eval_to_i64(ns, ectx)? as u64,
+                    None => 0,
+                },
+            }),
+            _ => {
+                return Err(ResolveError::InvalidTransition(
+                    name_entity_type.name.0.clone(),
+                ))
+            }
+        };
+        Ok(ast::Transition {
+            name_entity_type,
+            from_state: Identifier(self.from_state.clone()),
+            to_state: Identifier(self.to_state.clone()),
+            trigger,
+            guard: match self.guard {
+                Some(ref g) => Some(g.resolve(ectx)?),
+                None => None,
+            },
+            do_: match self.do_ {
+                Some(ref ai) => Some(ai.resolve(ectx)?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Resolve<ast::OnCommandPrimitive> for raw::OnCommandPrimitive {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::OnCommandPrimitive, ResolveError> {
+        let argument_values = self
+            .argument_values
+            .iter()
+            .map(|av| {
+                Ok(ast::SinkArgumentValue {
+                    name: Identifier(av.name.clone()),
+                    output_variable_ref: ast::QualifiedName(eval_to_string(
+                        &av.output_variable_ref,
+                        ectx,
+                    )?),
+                })
+            })
+            .collect_all()?;
+        Ok(ast::OnCommandPrimitive {
+            interface: Identifier(self.interface.clone()),
+            command: Identifier(self.command.clone()),
+            transaction: self.transaction.clone().map(Identifier),
+            argument_values,
+        })
+    }
+}
+
+impl Resolve<ast::OnParameterPrimitive> for raw::OnParameterPrimitive {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::OnParameterPrimitive, ResolveError> {
+        Ok(ast::OnParameterPrimitive {
+            interface: Identifier(self.interface.clone()),
+            parameter: Identifier(self.parameter.clone()),
+            operation: string_to_parameter_operation(&self.operation, ectx)?,
+            transaction: self.transaction.clone().map(Identifier),
+            variable_ref: match self.variable_ref {
+                Some(ref vr) => Some(ast::QualifiedName(eval_to_string(vr, ectx)?)),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Resolve<ast::ActivityInvocation> for raw::ActivityInvocation {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ActivityInvocation, ResolveError> {
+        let argument_values = self
+            .argument_values
+            .iter()
+            .map(|av| av.resolve(ectx))
+            .collect_all()?;
+        Ok(ast::ActivityInvocation {
+            activity: Identifier(self.activity.clone()),
+            argument_values,
+        })
+    }
+}
+
+impl Resolve<ast::ArgumentValue> for raw::ArgumentValue {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ArgumentValue, ResolveError> {
+        // Req 3.16.7: either a Value or a VariableRef element
+        Ok(ast::ArgumentValue {
+            name: Identifier(self.name.clone()),
+            value: resolve_operand(&self.variable_ref, &self.value, &self.name, ectx)?,
+        })
+    }
+}
+
+impl Resolve<ast::ActivitySet> for raw::ActivitySet {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::ActivitySet, ResolveError> {
+        let activities = self
+            .activities
+            .iter()
+            .map(|a| {
+                a.resolve(ectx)
+                    .within(|| named("Activity", &a.name_entity_type.name))
+            })
+            .collect_all()?;
+
+        // activity names are unique within a component
+        let mut names = std::collections::HashSet::new();
+        for activity in activities.iter() {
+            if !names.insert(&activity.name_entity_type.name.0) {
+                return Err(ResolveError::DuplicateName(
+                    activity.name_entity_type.name.0.clone(),
+                ));
+            }
+        }
+
+        Ok(ast::ActivitySet { activities })
+    }
+}
+
+impl Resolve<ast::Activity> for raw::Activity {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Activity, ResolveError> {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|a| {
+                Ok(ast::ActivityArgument {
+                    name_entity_type: a.name_entity_type.resolve(ectx)?,
+                    type_: ast::QualifiedName(eval_to_string(&a.type_, ectx)?),
+                })
+            })
+            .collect_all()?;
+        Ok(ast::Activity {
+            name_entity_type: self.name_entity_type.resolve(ectx)?,
+            arguments,
+            body: self.body.resolve(ectx)?,
+        })
+    }
+}
+
+impl Resolve<ast::Body> for raw::Body {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Body, ResolveError> {
+        let statements = self
+            .statements
+            .iter()
+            .map(|s| s.resolve(ectx))
+            .collect_all()?;
+        Ok(ast::Body { statements })
+    }
+}
+
+impl Resolve<ast::Statement> for raw::Statement {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Statement, ResolveError> {
+        match self {
+            raw::Statement::Assignment(a) => Ok(ast::Statement::Assignment(ast::Assignment {
+                output_variable_ref: ast::QualifiedName(a.output_variable_ref.clone()),
+                value: resolve_operand(&a.variable_ref, &a.value, &a.output_variable_ref, ectx)?,
+            })),
+            raw::Statement::Conditional(c) => Ok(ast::Statement::Conditional(c.resolve(ectx)?)),
+            raw::Statement::Iteration(i) => Ok(ast::Statement::Iteration(i.resolve(ectx)?)),
+            raw::Statement::Calibration(c) => Ok(ast::Statement::Calibration(c.resolve(ectx)?)),
+            raw::Statement::SendParameterPrimitive(p) => Ok(
+                ast::Statement::SendParameterPrimitive(ast::SendParameterPrimitive {
+                    interface: Identifier(p.interface.clone()),
+                    parameter: Identifier(p.parameter.clone()),
+                    operation: string_to_parameter_operation(&p.operation, ectx)?,
+                    transaction: p.transaction.clone().map(Identifier),
+                    argument_value: match p.argument_value {
+                        Some(ref av) => Some(resolve_operand(
+                            &av.variable_ref,
+                            &av.value,
+                            &p.parameter,
+                            ectx,
+                        )?),
+                        None => None,
+                    },
+                }),
+            ),
+            raw::Statement::SendCommandPrimitive(c) => Ok(ast::Statement::SendCommandPrimitive(
+                ast::SendCommandPrimitive {
+                    interface: Identifier(c.interface.clone()),
+                    command: Identifier(c.command.clone()),
+                    transaction: c.transaction.clone().map(Identifier),
+                    argument_values: c
+                        .argument_values
+                        .iter()
+                        .map(|av| av.resolve(ectx))
+                        .collect_all()?,
+                },
+            )),
+            raw::Statement::MathOperation(m) => Ok(ast::Statement::MathOperation(m.resolve(ectx)?)),
+            raw::Statement::Call(c) => Ok(ast::Statement::Call(c.resolve(ectx)?)),
+        }
+    }
+}
+
+impl Resolve<ast::Conditional> for raw::Conditional {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Conditional, ResolveError> {
+        Ok(ast::Conditional {
+            condition: self.condition.resolve(ectx)?,
+            on_condition_true: match self.on_condition_true {
+                Some(ref b) => b.resolve(ectx)?,
+                None => ast::Body::default(),
+            },
+            on_condition_false: match self.on_condition_false {
+                Some(ref b) => b.resolve(ectx)?,
+                None => ast::Body::default(),
+            },
+        })
+    }
+}
+
+impl Resolve<ast::BooleanExpression> for raw::BooleanExpression {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::BooleanExpression, ResolveError> {
+        let resolve_list =
+            |list: &Vec<raw::BooleanExpression>| list.iter().map(|e| e.resolve(ectx)).collect_all();
+        match self {
+            raw::BooleanExpression::Condition(c) => {
+                Ok(ast::BooleanExpression::Condition(c.resolve(ectx)?))
+            }
+            raw::BooleanExpression::ANDedConditions(list) => {
+                Ok(ast::BooleanExpression::ANDedConditions(resolve_list(list)?))
+            }
+            raw::BooleanExpression::ORedConditions(list) => {
+                Ok(ast::BooleanExpression::ORedConditions(resolve_list(list)?))
+            }
+            raw::BooleanExpression::TypeCondition(t) => {
+                Ok(ast::BooleanExpression::TypeCondition(ast::TypeCheck {
+                    first_operand: ast::QualifiedName(t.first_operand.variable_ref.clone()),
+                    type_operand: ast::QualifiedName(eval_to_string(t.type_operand.trim(), ectx)?),
+                }))
+            }
+        }
+    }
+}
+
+impl Resolve<ast::Comparison> for raw::Comparison {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Comparison, ResolveError> {
+        Ok(ast::Comparison {
+            first_operand: ast::QualifiedName(self.first_operand.variable_ref.clone()),
+            comparison_operator: string_to_comparison_operator(&self.comparison_operator, ectx)?,
+            second_operand: resolve_operand(
+                &self.second_operand,
+                &self.value,
+                &self.first_operand.variable_ref,
+                ectx,
+            )?,
+        })
+    }
+}
+
+impl Resolve<ast::Iteration> for raw::Iteration {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Iteration, ResolveError> {
+        let iterator = &self.iterator_variable_ref;
+        let numeric_operand = |operand: &raw::Operand| {
+            let operand = resolve_operand(&operand.variable_ref, &operand.value, iterator, ectx)?;
+            check_numeric_literal(&operand, iterator)?;
+            Ok(operand)
+        };
+        let range = match (&self.over_array, &self.start_at, &self.step, &self.end_at) {
+            (Some(array), None, None, None) => {
+                ast::IterationRange::OverArray(ast::QualifiedName(array.trim().to_string()))
+            }
+            (None, Some(start_at), Some(step), Some(end_at)) => ast::IterationRange::Numeric {
+                start_at: numeric_operand(start_at)?,
+                step: numeric_operand(step)?,
+                end_at: numeric_operand(end_at)?,
+            },
+            _ => return Err(ResolveError::InvalidStatement(iterator.clone())),
+        };
+        Ok(ast::Iteration {
+            iterator_variable_ref: ast::QualifiedName(iterator.clone()),
+            range,
+            do_: self.do_.resolve(ectx)?,
+        })
+    }
+}
+
+impl Resolve<ast::Calibration> for raw::Calibration {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::Calibration, ResolveError> {
+        // TODO: spline calibrators
+        let calibrator = match (&self.polynomial_calibrator, &self.spline_calibrator) {
+            (Some(pc), None) => pc.resolve(ectx)?,
+            _ => {
+                return Err(ResolveError::InvalidStatement(
+                    self.output_variable_ref.clone(),
+                ))
+            }
+        };
+        Ok(ast::Calibration {
+            output_variable_ref: ast::QualifiedName(self.output_variable_ref.clone()),
+            input_variable_ref: ast::QualifiedName(self.input_variable_ref.clone()),
+            calibrator,
+        })
+    }
+}
+
+impl Resolve<ast::MathOperation> for raw::MathOperation {
+    fn resolve(&self, ectx: &ExpressionContext) -> Result<ast::MathOperation, ResolveError> {
+        let output = &self.output_variable_ref;
+        let elements = self
+            .elements
+            .iter()
+            .map(|e| match e {
+                raw::MathElement::Value(v) => {
+                    let operand = ast::Operand::Value(Literal(eval_to_string(&v.value, ectx)?));
+                    check_numeric_literal(&operand, output)?;
+                    Ok(ast::MathElement::Operand(operand))
+                }
+                raw::MathElement::VariableRef(vr) => Ok(ast::MathElement::Operand(
+                    ast::Operand::VariableRef(ast::QualifiedName(vr.variable_ref.clone())),
+                )),
+                raw::MathElement::Operator(op) => Ok(ast::MathElement::Operator(
+                    string_to_math_operator(&op.operator, ectx)?,
+                )),
+            })
+            .collect_all()?;
+
+        // the operators must find their operands on the stack, leaving a single result
+        let mut depth = 0;
+        for element in elements.iter() {
+            match element {
+                ast::MathElement::Operand(_) => depth += 1,
+                ast::MathElement::Operator(op) if depth < op.arity() => {
+                    return Err(ResolveError::InvalidMathOperation(format!(
+                        "{}: {:?} needs {} operands",
+                        output,
+                        op,
+                        op.arity()
+                    )));
+                }
+                ast::MathElement::Operator(ast::MathOperator::Swap) => (),
+                ast::MathElement::Operator(op) => depth -= op.arity() - 1,
+            }
+        }
+        if depth != 1 {
+            return Err(ResolveError::InvalidMathOperation(format!(
+                "{}: leaves {} values on the stack",
+                output, depth
+            )));
+        }
+
+        Ok(ast::MathOperation {
+            output_variable_ref: ast::QualifiedName(output.clone()),
+            elements,
+        })
+    }
+}
+
+/// literal operands of calculations must be numbers
+fn check_numeric_literal(operand: &ast::Operand, owner: &str) -> Result<(), ResolveError> {
+    match operand {
+        ast::Operand::Value(literal) if !Value::parse(&literal.0).is_numeric() => Err(
+            ResolveError::InvalidOperand(format!("{}: {} is not a number", owner, literal.0)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Variables visible to the activities and state machines of a component, used to check
+/// that operands refer to declared variables (or mission parameters) of a suitable type
+struct OperandScope<'a> {
+    package: &'a ast::Package,
+    ectx: &'a ExpressionContext,
+    owner: String,
+    types: HashMap<String, ast::QualifiedName>,
+}
+
+impl<'a> OperandScope<'a> {
+    fn for_component(
+        package: &'a ast::Package,
+        component: &ast::Component,
+        ectx: &'a ExpressionContext,
+    ) -> Self {
+        let types = component
+            .implementation
+            .variable_set
+            .variables
+            .iter()
+            .map(|v| (v.name.0.clone(), v.type_.clone()))
+            .collect();
+        OperandScope {
+            package,
+            ectx,
+            owner: component.name.0.clone(),
+            types,
+        }
+    }
+
+    fn check_component(&self, component: &ast::Component) -> Result<(), ResolveError> {
+        let implementation = &component.implementation;
+        for activity in implementation.activity_set.activities.iter() {
+            let mut scope = OperandScope {
+                package: self.package,
+                ectx: self.ectx,
+                owner: format!("{}/{}", self.owner, activity.name_entity_type.name.0),
+                types: self.types.clone(),
+            };
+            for argument in activity.arguments.iter() {
+                let _ = scope.types.insert(
+                    argument.name_entity_type.name.0.clone(),
+                    argument.type_.clone(),
+                );
+            }
+            scope.check_body(&activity.body)?;
+        }
+
+        for sm in implementation.state_machine_set.state_machines.iter() {
+            for element in sm.elements.iter() {
+                match element {
+                    ast::StateMachineElement::State(s) => {
+                        for invocation in s.on_entry.iter().chain(s.on_exit.iter()) {
+                            self.check_invocation(invocation)?;
+                        }
+                    }
+                    ast::StateMachineElement::Transition(t) => {
+                        match &t.trigger {
+                            ast::TransitionTrigger::OnCommandPrimitive(cmd) => {
+                                for sink in cmd.argument_values.iter() {
+                                    self.check_declared(&sink.output_variable_ref)?;
+                                }
+                            }
+                            ast::TransitionTrigger::OnParameterPrimitive(param) => {
+                                if let Some(variable_ref) = &param.variable_ref {
+                                    self.check_declared(variable_ref)?;
+                                }
+                            }
+                            ast::TransitionTrigger::OnTimer(_) => (),
+                        }
+                        if let Some(guard) = &t.guard {
+                            self.check_condition(guard)?;
+                        }
+                        if let Some(do_) = &t.do_ {
+                            self.check_invocation(do_)?;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_body(&self, body: &ast::Body) -> Result<(), ResolveError> {
+        for statement in body.statements.iter() {
+            match statement {
+                ast::Statement::Assignment(a) => {
+                    self.check_declared(&a.output_variable_ref)?;
+                    self.check_operand(&a.value)?;
+                }
+                ast::Statement::Conditional(c) => {
+                    self.check_condition(&c.condition)?;
+                    self.check_body(&c.on_condition_true)?;
+                    self.check_body(&c.on_condition_false)?;
+                }
+                ast::Statement::Iteration(i) => {
+                    self.check_declared(&i.iterator_variable_ref)?;
+                    match &i.range {
+                        ast::IterationRange::OverArray(array) => self.check_declared(array)?,
+                        ast::IterationRange::Numeric {
+                            start_at,
+                            step,
+                            end_at,
+                        } => {
+                            for operand in [start_at, step, end_at] {
+                                self.check_numeric(operand)?;
+                            }
+                        }
+                    }
+                    self.check_body(&i.do_)?;
+                }
+                ast::Statement::Calibration(c) => {
+                    self.check_declared(&c.output_variable_ref)?;
+                    self.check_numeric(&ast::Operand::VariableRef(c.input_variable_ref.clone()))?;
+                }
+                ast::Statement::SendParameterPrimitive(p) => {
+                    if let Some(operand) = &p.argument_value {
+                        self.check_operand(operand)?;
+                    }
+                }
+                ast::Statement::SendCommandPrimitive(c) => {
+                    for av in c.argument_values.iter() {
+                        self.check_operand(&av.value)?;
+                    }
+                }
+                ast::Statement::MathOperation(m) => {
+                    self.check_declared(&m.output_variable_ref)?;
+                    for element in m.elements.iter() {
+                        if let ast::MathElement::Operand(operand) = element {
+                            self.check_numeric(operand)?;
+                        }
+                    }
+                }
+                ast::Statement::Call(c) => self.check_invocation(c)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_invocation(&self, invocation: &ast::ActivityInvocation) -> Result<(), ResolveError> {
+        for av in invocation.argument_values.iter() {
+            self.check_operand(&av.value)?;
+        }
+        Ok(())
+    }
+
+    fn check_condition(&self, condition: &ast::BooleanExpression) -> Result<(), ResolveError> {
+        match condition {
+            ast::BooleanExpression::Condition(c) => {
+                self.check_declared(&c.first_operand)?;
+                self.check_operand(&c.second_operand)?;
+                // a number can only be compared with a number
+                if self.is_numeric(&c.first_operand) == Some(true) {
+                    self.check_numeric(&c.second_operand)?;
+                }
+                Ok(())
+            }
+            ast::BooleanExpression::ANDedConditions(list)
+            | ast::BooleanExpression::ORedConditions(list) => {
+                list.iter().try_for_each(|c| self.check_condition(c))
+            }
+            ast::BooleanExpression::TypeCondition(t) => self.check_declared(&t.first_operand),
+        }
+    }
+
+    fn check_operand(&self, operand: &ast::Operand) -> Result<(), ResolveError> {
+        match operand {
+            ast::Operand::VariableRef(name) => self.check_declared(name),
+            ast::Operand::Value(_) => Ok(()),
+        }
+    }
+
+    fn check_numeric(&self, operand: &ast::Operand) -> Result<(), ResolveError> {
+        match operand {
+            ast::Operand::VariableRef(name) => {
+                self.check_declared(name)?;
+                if self.is_numeric(name) == Some(false) {
+                    return Err(ResolveError::InvalidOperand(format!(
+                        "{}: {} is not a number",
+                        self.owner, name.0
+                    )));
+                }
+                Ok(())
+            }
+            ast::Operand::Value(_) => check_numeric_literal(operand, &self.owner),
+        }
+    }
+
+    /// variables must be declared by the component, the activity, or be a mission parameter
+    fn check_declared(&self, name: &ast::QualifiedName) -> Result<(), ResolveError> {
+        let path = name.0.split('/').collect::<Vec<_>>();
+        if self.types.contains_key(&name.0) || self.ectx.get(&path).is_ok() {
+            return Ok(());
+        }
+        Err(ResolveError::UnknownVariable(format!(
+            "{}/{}",
+            self.owner, name.0
+        )))
+    }
+
+    /// whether a variable has a numeric type, if its type is declared in this package
+    fn is_numeric(&self, name: &ast::QualifiedName) -> Option<bool> {
+        let type_ = &self.types.get(&name.0)?.0;
+        let package_name = &self.package.name_entity_type.name.0;
+        let type_name = match type_.split_once('/') {
+            Some((package, type_name)) if package == package_name => type_name,
+            Some(_) => return None,
+            None => type_,
+        };
+        self.package.data_type_set.data_types.iter().find_map(|dt| {
+            match dt {
+                ast::DataType::IntegerDataType(dt) => Some((&dt.name_entity_type.name, true)),
+                ast::DataType::FloatDataType(dt) => Some((&dt.name_entity_type.name, true)),
+                ast::DataType::EnumeratedDataType(dt) => Some((&dt.name_entity_type.name, true)),
+                ast::DataType::SubRangeDataType(dt) => Some((&dt.name_entity_type.name, true)),
+                ast::DataType::StringDataType(dt) => Some((&dt.name_entity_type.name, false)),
+                ast::DataType::BooleanDataType(dt) => Some((&dt.name_entity_type.name, false)),
+                ast::DataType::ContainerDataType(dt) => Some((&dt.name_entity_type.name, false)),
+                ast::DataType::ArrayDataType(dt) => Some((&dt.name_entity_type.name, false)),
+                ast::DataType::NoneDataType => None,
+            }
+            .filter(|(name, _)| name.0 == type_name)
+            .map(|(_, numeric)| numeric)
+        })
+    }
+}
@@ -0,0 +1,336 @@
+//! Semantic Terms of the SEDS Core Semantics DoT
+//!
+//! Typed vocabularies for the `quantityKind`, `unit` and `CoreSemanticsAttributeGroup`
+//! attributes defined in `seds-core-semantics.xsd` (plus the single `local*` entries
+//! reserved by `seds-extension-semantics.xsd`).
+use std::fmt;
+
+/// A term of a semantics DoT vocabulary (an xs:enumeration in the schema)
+pub trait SemanticTerm: Sized {
+    /// look up a term by its spelling in the schema
+    fn from_term(term: &str) -> Option<Self>;
+
+    /// spelling of the term in the schema
+    fn as_term(&self) -> &'static str;
+}
+
+/// declare a vocabulary enum whose variants map one-to-one to schema terms
+macro_rules! semantic_terms {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $term:literal,)+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(missing_docs)]
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl SemanticTerm for $name {
+            fn from_term(term: &str) -> Option<Self> {
+                match term {
+                    $($term => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+
+            fn as_term(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $term,)+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_term())
+            }
+        }
+    };
+}
+
+semantic_terms! {
+    /// QuantityKind - DoTQuantityKind and ExtQuantityKind
+    QuantityKind {
+        CelsiusTemperature => "celsiusTemperatureQK",
+        Energy => "energyQK",
+        Length => "lengthQK",
+        Mass => "massQK",
+        Power => "powerQK",
+        ThermodynamicTemperature => "thermodynamicTemperatureQK",
+        Time => "timeQK",
+        Velocity => "velocityQK",
+        AcousticPower => "acousticPowerQK",
+        AmountOfMolecularSpecies => "amountOfMolecularSpeciesQK",
+        Angle => "angleQK",
+        AngularRate => "angularRateQK",
+        Area => "areaQK",
+        AstronomicalMagnitude => "astronomicalMagnitudeQK",
+        Capacitance => "capacitanceQK",
+        DataRate => "dataRateQK",
+        DynamicViscosity => "dynamicViscosityQK",
+        ElectricChargeDensity => "electricChargeDensityQK",
+        ElectricCharge => "electricChargeQK",
+        ElectricConductancePerDistance => "electricConductancePerDistanceQK",
+        ElectricConductance => "electricConductanceQK",
+        ElectricCurrentDensity => "electricCurrentDensityQK",
+        ElectricCurrent => "electricCurrentQK",
+        ElectricDipoleMoment => "electricDipoleMomentQK",
+        ElectricDisplacement => "electricDisplacementQK",
+        ElectricPotentialGradient => "electricPotentialGradientQK",
+        ElectricPotential => "electricPotentialQK",
+        ElectricResistance => "electricResistanceQK",
+        Entropy => "entropyQK",
+        Force => "forceQK",
+        Frequency => "frequencyQK",
+        Inductance => "inductanceQK",
+        Information => "informationQK",
+        Luminance => "luminanceQK",
+        Luminosity => "luminosityQK",
+        MagneticDipoleMoment => "magneticDipoleMomentQK",
+        MagneticFieldStrength => "magneticFieldStrengthQK",
+        MagneticFluxDensity => "magneticFluxDensityQK",
+        MagneticFlux => "magneticFluxQK",
+        MagneticPoleStrength => "magneticPoleStrengthQK",
+        Percent => "percentQK",
+        Permeability => "permeabilityQK",
+        Pixel => "pixelQK",
+        Pressure => "pressureQK",
+        Quantity => "quantityQK",
+        SolidAngle => "solidAngleQK",
+        SpecificCapacitance => "specificCapacitanceQK",
+        SurfaceTension => "surfaceTensionQK",
+        Torque => "torqueQK",
+        Volume => "volumeQK",
+        VolumetricFlow => "volumetricFlowQK",
+        Local => "localQK",
+    }
+}
+
+semantic_terms! {
+    /// Unit - DoTUnit and ExtUnit
+    Unit {
+        DegreeCelsius => "degreeCelsius",
+        Gram => "gram",
+        Inch => "inch",
+        Joule => "joule",
+        Kelvin => "kelvin",
+        Kilogram => "kilogram",
+        Metre => "metre",
+        MetrePerSecond => "metrePerSecond",
+        Second => "second",
+        Watt => "watt",
+        AToDCount => "aToDCount",
+        Ampere => "ampere",
+        AmpereHour => "ampere-hour",
+        AmpereMetre => "ampere-metre",
+        AmpereSquareMetre => "ampere-squareMetre",
+        AmperePerMetre => "amperePerMetre",
+        AmperePerSquareMetre => "amperePerSquareMetre",
+        Arcsecond => "arcsecond",
+        ArcsecondPerSecond => "arcsecondPerSecond",
+        AstronomicalUnit => "astronomicalUnit",
+        Bar => "bar",
+        Bit => "bit",
+        BitsPerSecond => "bitsPerSecond",
+        Byte => "byte",
+        Candela => "candela",
+        Coulomb => "coulomb",
+        CoulombPerCubicMetre => "coulombPerCubicMetre",
+        CoulombPerSquareMetre => "coulombPerSquareMetre",
+        Count => "count",
+        CubicMetre => "cubicMetre",
+        CubicMetrePerSecond => "cubicMetrePerSecond",
+        Day => "day",
+        Debye => "debye",
+        Decibel => "decibel",
+        DecibelWatt => "decibelWatt",
+        DegreeAngle => "degreeAngle",
+        Electron => "electron",
+        ElectronsPerPixelPerSecond => "electronsPerPixelPerSecond",
+        Farad => "farad",
+        FaradPerMetre => "faradPerMetre",
+        Henry => "henry",
+        HenryPerMetre => "henryPerMetre",
+        Hertz => "hertz",
+        Hour => "hour",
+        InverseHenry => "inverseHenry",
+        InverseKelvin => "inverseKelvin",
+        InverseSecond => "inverseSecond",
+        JoulePerKelvin => "joulePerKelvin",
+        Kibibyte => "kibibyte",
+        KilogramMetreSquared => "kilogramMetreSquared",
+        KilogramPerMetre => "kilogramPerMetre",
+        Litre => "litre",
+        MegabitsPerSecond => "megabitsPerSecond",
+        MetrePerSecondPerSecond => "metrePerSecondPerSecond",
+        Microsecond => "microsecond",
+        Millisecond => "millisecond",
+        Minute => "minute",
+        Mole => "mole",
+        Newton => "newton",
+        NewtonMetre => "newtonMetre",
+        NewtonPerMetre => "newtonPerMetre",
+        Nit => "nit",
+        Ohm => "ohm",
+        Pascal => "pascal",
+        PascalSecond => "pascalSecond",
+        Percent => "percent",
+        Pixel => "pixel",
+        Radian => "radian",
+        RadianPerSecond => "radianPerSecond",
+        Siemens => "siemens",
+        SiemensPerMetre => "siemensPerMetre",
+        SquareMetre => "squareMetre",
+        StellarMagnitude => "stellarMagnitude",
+        Steradian => "steradian",
+        Tesla => "tesla",
+        Volt => "volt",
+        VoltPerMetre => "voltPerMetre",
+        Weber => "weber",
+        WeberMetre => "weberMetre",
+        WeberPerMetre => "weberPerMetre",
+        WeberPerSquareMetre => "weberPerSquareMetre",
+        WeberPerSteradian => "weberPerSteradian",
+        FractionOfMaximum => "fractionOfMaximum",
+        Local => "localUnit",
+    }
+}
+
+semantic_terms! {
+    /// Chirality - handedness of a coordinate system
+    Chirality {
+        LeftHanded => "leftHanded",
+        RightHanded => "rightHanded",
+    }
+}
+
+semantic_terms! {
+    /// CoordinateType - coordinate or time system of a value
+    CoordinateType {
+        J2000 => "J2000",
+        LLA => "LLA",
+        MOD => "MOD",
+        TOD => "TOD",
+        UPS => "UPS",
+        UTC => "UTC",
+        UTM => "UTM",
+        LatLon => "latLon",
+        DtnTime => "DtnTime",
+        ICRS => "ICRS",
+    }
+}
+
+semantic_terms! {
+    /// ToCoordinateType - target coordinate system of a transformation
+    ToCoordinateType {
+        J2000 => "J2000",
+        LLA => "LLA",
+        MOD => "MOD",
+        TOD => "TOD",
+        UPS => "UPS",
+        UTC => "UTC",
+        UTM => "UTM",
+        LatLon => "latLon",
+        ICRS => "ICRS",
+    }
+}
+
+semantic_terms! {
+    /// DifferenceConvention - how a difference value is to be interpreted
+    DifferenceConvention {
+        Comparison => "comparison",
+        Delta => "delta",
+        Gradient => "gradient",
+        Path => "path",
+        Ratio => "ratio",
+        SinceEpoch => "sinceEpoch",
+        SinceSync => "sinceSync",
+    }
+}
+
+semantic_terms! {
+    /// Interpretation - what kind of information a value carries
+    Interpretation {
+        Command => "command",
+        Difference => "difference",
+        Mode => "mode",
+        Name => "name",
+        Orbit => "orbit",
+        Position => "position",
+        Scope => "scope",
+        Status => "status",
+        Transformation => "transformation",
+        Checksum => "checksum",
+        ValidityPeriod => "validityPeriod",
+    }
+}
+
+semantic_terms! {
+    /// Purpose - why a value is produced or consumed
+    Purpose {
+        Action => "action",
+        Calibration => "calibration",
+        Measurement => "measurement",
+        Nominal => "nominal",
+        SetPoint => "setPoint",
+    }
+}
+
+semantic_terms! {
+    /// ReferenceFrame - frame in which a value is expressed
+    ReferenceFrame {
+        ECEF => "ECEF",
+        ECI => "ECI",
+        LVLH => "LVLH",
+        Device => "device",
+        ExternalObject => "externalObject",
+        Mount => "mount",
+        Transducer => "transducer",
+        Vehicle => "vehicle",
+        ICRF => "ICRF",
+    }
+}
+
+semantic_terms! {
+    /// ToFrame - target frame of a transformation
+    ToFrame {
+        ECEF => "ECEF",
+        ECI => "ECI",
+        LVLH => "LVLH",
+        Device => "device",
+        ExternalObject => "externalObject",
+        Mount => "mount",
+        Transducer => "transducer",
+        Vehicle => "vehicle",
+        ICRF => "ICRF",
+    }
+}
+
+semantic_terms! {
+    /// RelationToTimestamp - event that an associated timestamp refers to
+    RelationToTimestamp {
+        Acquiring => "acquiring",
+        Generation => "generation",
+        Sending => "sending",
+    }
+}
+
+semantic_terms! {
+    /// StatusConvention - encoding convention of a status value
+    StatusConvention {
+        BinaryStatus => "binaryStatus",
+        QualityFraction => "qualityFraction",
+        TernaryStatus => "ternaryStatus",
+    }
+}
+
+semantic_terms! {
+    /// TransformationType - representation of a transformation
+    TransformationType {
+        DCM => "DCM",
+        Quaternion => "quaternion",
+        RollPitchYaw => "rollPitchYaw",
+        RotationRate => "rotationRate",
+        Translation => "translation",
+        Velocity => "velocity",
+    }
+}
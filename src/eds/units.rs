@@ -0,0 +1,229 @@
+//! Unit Conversions for the SEDS Core Semantics DoT
+//!
+//! Every [`Unit`] of the DoT vocabulary is related to a coherent SI unit (itself a member of
+//! the vocabulary) by `si = value * scale + offset`. Two units are compatible if they share
+//! the same SI unit, e.g. `degreeAngle` and `arcsecond` are both expressed in `radian`.
+//!
+//! NOTE: the DoT has no SI prefixes, so a vendor value in milliampere is described as an
+//! `ampere` entry with a calibrator rather than as a unit of its own.
+use std::fmt;
+
+use super::semantics::{QuantityKind, Unit};
+
+/// Errors that can occur converting between units
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitConversionError {
+    /// units measure different things (e.g. metre to second)
+    IncompatibleUnits(Unit, Unit),
+    /// unit has no known relation to SI (e.g. localUnit)
+    UnknownConversion(Unit),
+}
+
+impl fmt::Display for UnitConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitConversionError::IncompatibleUnits(from, to) => {
+                write!(f, "cannot convert {} to {}", from, to)
+            }
+            UnitConversionError::UnknownConversion(unit) => {
+                write!(f, "no known SI conversion for {}", unit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnitConversionError {}
+
+/// relation of a unit to its coherent SI unit: `si = value * scale + offset`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SiConversion {
+    /// coherent SI unit the value is converted to
+    pub si_unit: Unit,
+    /// multiplicative factor
+    pub scale: f64,
+    /// additive offset, applied after scaling (only non-zero for temperatures)
+    pub offset: f64,
+}
+
+impl SiConversion {
+    const fn scaled(si_unit: Unit, scale: f64) -> Self {
+        SiConversion {
+            si_unit,
+            scale,
+            offset: 0.0,
+        }
+    }
+
+    /// convert a value in the original unit to the SI unit
+    pub fn to_si(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+
+    /// convert a value in the SI unit back to the original unit
+    pub fn from_si(&self, value: f64) -> f64 {
+        (value - self.offset) / self.scale
+    }
+}
+
+const ARCSECOND: f64 = std::f64::consts::PI / 648_000.0;
+
+impl Unit {
+    /// conversion of this unit to its coherent SI unit, if the relation is known
+    pub fn si_conversion(&self) -> Option<SiConversion> {
+        use Unit::*;
+        let conversion = match self {
+            DegreeCelsius => SiConversion {
+                si_unit: Kelvin,
+                scale: 1.0,
+                offset: 273.15,
+            },
+            Gram => SiConversion::scaled(Kilogram, 1e-3),
+            Inch => SiConversion::scaled(Metre, 0.0254),
+            AstronomicalUnit => SiConversion::scaled(Metre, 149_597_870_700.0),
+            AmpereHour => SiConversion::scaled(Coulomb, 3600.0),
+            Electron => SiConversion::scaled(Coulomb, 1.602_176_634e-19),
+            Arcsecond => SiConversion::scaled(Radian, ARCSECOND),
+            DegreeAngle => SiConversion::scaled(Radian, std::f64::consts::PI / 180.0),
+            ArcsecondPerSecond => SiConversion::scaled(RadianPerSecond, ARCSECOND),
+            Bar => SiConversion::scaled(Pascal, 1e5),
+            Byte => SiConversion::scaled(Bit, 8.0),
+            Kibibyte => SiConversion::scaled(Bit, 8192.0),
+            MegabitsPerSecond => SiConversion::scaled(BitsPerSecond, 1e6),
+            Microsecond => SiConversion::scaled(Second, 1e-6),
+            Millisecond => SiConversion::scaled(Second, 1e-3),
+            Minute => SiConversion::scaled(Second, 60.0),
+            Hour => SiConversion::scaled(Second, 3600.0),
+            Day => SiConversion::scaled(Second, 86_400.0),
+            InverseSecond => SiConversion::scaled(Hertz, 1.0),
+            Litre => SiConversion::scaled(CubicMetre, 1e-3),
+            WeberPerSquareMetre => SiConversion::scaled(Tesla, 1.0),
+            Local => return None,
+            // every other unit of the vocabulary is its own coherent unit
+            unit => SiConversion::scaled(*unit, 1.0),
+        };
+        Some(conversion)
+    }
+
+    /// coherent SI unit of this unit, if the relation is known
+    pub fn si_unit(&self) -> Option<Unit> {
+        self.si_conversion().map(|c| c.si_unit)
+    }
+
+    /// true if values in this unit can be converted to `other`
+    pub fn is_compatible(&self, other: &Unit) -> bool {
+        match (self.si_unit(), other.si_unit()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// quantity kind measured by this unit, if it is unambiguous
+    pub fn quantity_kind(&self) -> Option<QuantityKind> {
+        use QuantityKind as QK;
+        use Unit::*;
+        let qk = match self {
+            DegreeCelsius => QK::CelsiusTemperature,
+            Kelvin => QK::ThermodynamicTemperature,
+            Gram | Kilogram => QK::Mass,
+            Inch | Metre | AstronomicalUnit => QK::Length,
+            Joule => QK::Energy,
+            MetrePerSecond => QK::Velocity,
+            Second | Microsecond | Millisecond | Minute | Hour | Day => QK::Time,
+            Watt => QK::Power,
+            Ampere => QK::ElectricCurrent,
+            AmpereHour | Coulomb | Electron => QK::ElectricCharge,
+            AmpereMetre => QK::MagneticPoleStrength,
+            AmpereSquareMetre => QK::MagneticDipoleMoment,
+            AmperePerMetre => QK::MagneticFieldStrength,
+            AmperePerSquareMetre => QK::ElectricCurrentDensity,
+            Arcsecond | DegreeAngle | Radian => QK::Angle,
+            ArcsecondPerSecond | RadianPerSecond => QK::AngularRate,
+            Bar | Pascal => QK::Pressure,
+            Bit | Byte | Kibibyte => QK::Information,
+            BitsPerSecond | MegabitsPerSecond => QK::DataRate,
+            Candela => QK::Luminosity,
+            CoulombPerCubicMetre => QK::ElectricChargeDensity,
+            CoulombPerSquareMetre => QK::ElectricDisplacement,
+            Count => QK::Quantity,
+            CubicMetre | Litre => QK::Volume,
+            CubicMetrePerSecond => QK::VolumetricFlow,
+            Debye => QK::ElectricDipoleMoment,
+            Farad => QK::Capacitance,
+            FaradPerMetre => QK::SpecificCapacitance,
+            Henry => QK::Inductance,
+            HenryPerMetre => QK::Permeability,
+            Hertz | InverseSecond => QK::Frequency,
+            JoulePerKelvin => QK::Entropy,
+            Mole => QK::AmountOfMolecularSpecies,
+            Newton => QK::Force,
+            NewtonMetre => QK::Torque,
+            NewtonPerMetre => QK::SurfaceTension,
+            Nit => QK::Luminance,
+            Ohm => QK::ElectricResistance,
+            PascalSecond => QK::DynamicViscosity,
+            Percent => QK::Percent,
+            Pixel => QK::Pixel,
+            Siemens => QK::ElectricConductance,
+            SiemensPerMetre => QK::ElectricConductancePerDistance,
+            SquareMetre => QK::Area,
+            StellarMagnitude => QK::AstronomicalMagnitude,
+            Steradian => QK::SolidAngle,
+            Tesla | WeberPerSquareMetre => QK::MagneticFluxDensity,
+            Volt => QK::ElectricPotential,
+            VoltPerMetre => QK::ElectricPotentialGradient,
+            Weber => QK::MagneticFlux,
+            _ => return None,
+        };
+        Some(qk)
+    }
+}
+
+/// A value together with the unit it is expressed in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    /// numeric value
+    pub value: f64,
+    /// unit of the value
+    pub unit: Unit,
+}
+
+impl Quantity {
+    /// create a new quantity
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Quantity { value, unit }
+    }
+
+    /// convert the quantity to `unit`, failing if the units are not compatible
+    pub fn convert_to(&self, unit: Unit) -> Result<Quantity, UnitConversionError> {
+        let from = self
+            .unit
+            .si_conversion()
+            .ok_or(UnitConversionError::UnknownConversion(self.unit))?;
+        let to = unit
+            .si_conversion()
+            .ok_or(UnitConversionError::UnknownConversion(unit))?;
+        if from.si_unit != to.si_unit {
+            return Err(UnitConversionError::IncompatibleUnits(self.unit, unit));
+        }
+        Ok(Quantity::new(to.from_si(from.to_si(self.value)), unit))
+    }
+
+    /// convert the quantity to its coherent SI unit
+    pub fn to_si(&self) -> Result<Quantity, UnitConversionError> {
+        let conversion = self
+            .unit
+            .si_conversion()
+            .ok_or(UnitConversionError::UnknownConversion(self.unit))?;
+        Ok(Quantity::new(
+            conversion.to_si(self.value),
+            conversion.si_unit,
+        ))
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
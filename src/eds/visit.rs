@@ -0,0 +1,1635 @@
+//! Visitors of the AST
+//!
+//! [`Visitor`](crate::eds::visit::Visitor) walks a package file by reference and
+//! [`VisitorMut`](crate::eds::visit::VisitorMut) by mutable reference. Every `visit_*` method
+//! defaults to the `walk_*` function of its node, which visits the children of the node in
+//! document order. An implementation overrides the methods of the nodes it is interested in,
+//! and calls the `walk_*` function to keep descending, or returns without it to skip the
+//! children. A `VisitorMut` can prune a node from the `Vec` of its parent before it walks it,
+//! e.g. from the `data_types` of a `DataTypeSet`.
+//!
+//! Each method gets the [`Path`](crate::eds::visit::Path) of the parents of its node.
+//! References to data types and to interface types, like the type of an entry or the base type
+//! of a container, are visited with `visit_type_ref`; references to variables are not.
+use std::fmt;
+
+use super::ast::*;
+
+/// A node on the way from the package file to a visited node
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// XML element of the node, like `ContainerDataType`
+    pub element: &'static str,
+    /// name of the node, if it has one
+    pub name: Option<String>,
+}
+
+/// The parents of a visited node, from the package file down
+///
+/// Displays as the names of the named parents, e.g. `Pump/Status` for the entries of the
+/// container `Status` of the package `Pump`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    /// the path of the children of a node below this path
+    pub fn join(&self, element: &'static str, name: Option<&str>) -> Path {
+        let mut segments = self.segments.clone();
+        segments.push(Segment {
+            element,
+            name: name.map(str::to_string),
+        });
+        Path { segments }
+    }
+
+    /// the parents, from the package file down
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// the direct parent
+    pub fn parent(&self) -> Option<&Segment> {
+        self.segments.last()
+    }
+
+    /// name of the nearest parent of an element, like `Component`
+    pub fn named(&self, element: &str) -> Option<&str> {
+        self.segments
+            .iter()
+            .rev()
+            .find(|s| s.element == element)
+            .and_then(|s| s.name.as_deref())
+    }
+
+    /// name of the package of the node
+    pub fn package(&self) -> Option<&str> {
+        self.named("Package")
+    }
+
+    /// qualified name `Package/Type` of the data type of the node
+    pub fn data_type(&self) -> Option<String> {
+        let data_type = self
+            .segments
+            .iter()
+            .rev()
+            .find(|s| s.element.ends_with("DataType"))?;
+        Some(format!(
+            "{}/{}",
+            self.package()?,
+            data_type.name.as_deref()?
+        ))
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self
+            .segments
+            .iter()
+            .filter_map(|s| s.name.as_deref())
+            .collect();
+        f.write_str(&names.join("/"))
+    }
+}
+
+/// Walks the nodes of a package file by reference
+///
+/// Every method defaults to the `walk_*` function of its node, which visits the children.
+#[allow(unused_variables)]
+pub trait Visitor<'ast> {
+    /// visit a package file, the root of the ast
+    fn visit_package_file(&mut self, path: &Path, package_file: &'ast PackageFile) {
+        walk_package_file(self, path, package_file)
+    }
+
+    /// visit the metadata of a package file
+    fn visit_metadata(&mut self, path: &Path, metadata: &'ast MetaData) {
+        walk_metadata(self, path, metadata)
+    }
+
+    /// visit a category of metadata
+    fn visit_metadata_category(&mut self, path: &Path, category: &'ast MetadataCategory) {
+        walk_metadata_category(self, path, category)
+    }
+
+    /// visit a metadata value
+    fn visit_metadata_value(&mut self, path: &Path, value: &'ast MetadataValue) {
+        walk_metadata_value(self, path, value)
+    }
+
+    /// visit a package
+    fn visit_package(&mut self, path: &Path, package: &'ast Package) {
+        walk_package(self, path, package)
+    }
+
+    /// visit the name, descriptions, semantics and extensions of the last node of the path
+    fn visit_named_entity_type(&mut self, path: &Path, named: &'ast NamedEntityType) {}
+
+    /// visit a reference to a data type or an interface type
+    fn visit_type_ref(&mut self, path: &Path, type_ref: &'ast QualifiedName) {}
+
+    /// visit the data types of a package
+    fn visit_data_type_set(&mut self, path: &Path, data_type_set: &'ast DataTypeSet) {
+        walk_data_type_set(self, path, data_type_set)
+    }
+
+    /// visit a data type of any kind
+    fn visit_data_type(&mut self, path: &Path, data_type: &'ast DataType) {
+        walk_data_type(self, path, data_type)
+    }
+
+    /// visit a boolean data type
+    fn visit_boolean_data_type(&mut self, path: &Path, data_type: &'ast BooleanDataType) {
+        walk_boolean_data_type(self, path, data_type)
+    }
+
+    /// visit an integer data type
+    fn visit_integer_data_type(&mut self, path: &Path, data_type: &'ast IntegerDataType) {
+        walk_integer_data_type(self, path, data_type)
+    }
+
+    /// visit an array data type
+    fn visit_array_data_type(&mut self, path: &Path, data_type: &'ast ArrayDataType) {
+        walk_array_data_type(self, path, data_type)
+    }
+
+    /// visit an enumerated data type
+    fn visit_enumerated_data_type(&mut self, path: &Path, data_type: &'ast EnumeratedDataType) {
+        walk_enumerated_data_type(self, path, data_type)
+    }
+
+    /// visit a container data type
+    fn visit_container_data_type(&mut self, path: &Path, data_type: &'ast ContainerDataType) {
+        walk_container_data_type(self, path, data_type)
+    }
+
+    /// visit a float data type
+    fn visit_float_data_type(&mut self, path: &Path, data_type: &'ast FloatDataType) {
+        walk_float_data_type(self, path, data_type)
+    }
+
+    /// visit a string data type
+    fn visit_string_data_type(&mut self, path: &Path, data_type: &'ast StringDataType) {
+        walk_string_data_type(self, path, data_type)
+    }
+
+    /// visit a sub range data type
+    fn visit_sub_range_data_type(&mut self, path: &Path, data_type: &'ast SubRangeDataType) {
+        walk_sub_range_data_type(self, path, data_type)
+    }
+
+    /// visit the labels of an enumerated data type
+    fn visit_enumeration_list(&mut self, path: &Path, list: &'ast EnumerationList) {
+        walk_enumeration_list(self, path, list)
+    }
+
+    /// visit a label of an enumerated data type
+    fn visit_enumeration(&mut self, path: &Path, enumeration: &'ast Enumeration) {}
+
+    /// visit the range of an integer, float or sub range data type, or of a range constraint
+    fn visit_range(&mut self, path: &Path, range: &'ast Range) {}
+
+    /// visit the dimensions of an array data type
+    fn visit_dimension_list(&mut self, path: &Path, dimensions: &'ast DimensionList) {}
+
+    /// visit the constraints of a container
+    fn visit_constraint_set(&mut self, path: &Path, constraint_set: &'ast ConstraintSet) {
+        walk_constraint_set(self, path, constraint_set)
+    }
+
+    /// visit a constraint of a container
+    fn visit_constraint(&mut self, path: &Path, constraint: &'ast Constraint) {
+        walk_constraint(self, path, constraint)
+    }
+
+    /// visit the entries of a container
+    fn visit_entry_list(&mut self, path: &Path, list: &'ast EntryList) {
+        walk_entry_list(self, path, list)
+    }
+
+    /// visit the trailer entries of a container
+    fn visit_trailer_entry_list(&mut self, path: &Path, list: &'ast EntryList) {
+        walk_trailer_entry_list(self, path, list)
+    }
+
+    /// visit an entry of any kind
+    fn visit_entry_element(&mut self, path: &Path, entry: &'ast EntryElement) {
+        walk_entry_element(self, path, entry)
+    }
+
+    /// visit an entry
+    fn visit_entry(&mut self, path: &Path, entry: &'ast Entry) {
+        walk_entry(self, path, entry)
+    }
+
+    /// visit a fixed value entry
+    fn visit_fixed_value_entry(&mut self, path: &Path, entry: &'ast FixedValueEntry) {
+        walk_fixed_value_entry(self, path, entry)
+    }
+
+    /// visit a padding entry
+    fn visit_padding_entry(&mut self, path: &Path, entry: &'ast PaddingEntry) {}
+
+    /// visit a list entry
+    fn visit_list_entry(&mut self, path: &Path, entry: &'ast ListEntry) {
+        walk_list_entry(self, path, entry)
+    }
+
+    /// visit a length entry
+    fn visit_length_entry(&mut self, path: &Path, entry: &'ast LengthEntry) {
+        walk_length_entry(self, path, entry)
+    }
+
+    /// visit an error control entry
+    fn visit_error_control_entry(&mut self, path: &Path, entry: &'ast ErrorControlEntry) {
+        walk_error_control_entry(self, path, entry)
+    }
+
+    /// visit the components of a package
+    fn visit_component_set(&mut self, path: &Path, component_set: &'ast ComponentSet) {
+        walk_component_set(self, path, component_set)
+    }
+
+    /// visit a component
+    fn visit_component(&mut self, path: &Path, component: &'ast Component) {
+        walk_component(self, path, component)
+    }
+
+    /// visit a required interface of a component
+    fn visit_interface(&mut self, path: &Path, interface: &'ast Interface) {
+        walk_interface(self, path, interface)
+    }
+
+    /// visit a generic type map of an interface
+    fn visit_generic_type_map(&mut self, path: &Path, map: &'ast GenericTypeMap) {
+        walk_generic_type_map(self, path, map)
+    }
+
+    /// visit the implementation of a component
+    fn visit_implementation(&mut self, path: &Path, implementation: &'ast Implementation) {
+        walk_implementation(self, path, implementation)
+    }
+
+    /// visit a variable of an implementation
+    fn visit_variable(&mut self, path: &Path, variable: &'ast Variable) {
+        walk_variable(self, path, variable)
+    }
+
+    /// visit a parameter map of an implementation
+    fn visit_parameter_map(&mut self, path: &Path, map: &'ast ParameterMap) {}
+
+    /// visit an activity of an implementation
+    fn visit_activity(&mut self, path: &Path, activity: &'ast Activity) {
+        walk_activity(self, path, activity)
+    }
+
+    /// visit an argument of an activity
+    fn visit_activity_argument(&mut self, path: &Path, argument: &'ast ActivityArgument) {
+        walk_activity_argument(self, path, argument)
+    }
+
+    /// visit the body of an activity, a conditional or an iteration
+    fn visit_body(&mut self, path: &Path, body: &'ast Body) {
+        walk_body(self, path, body)
+    }
+
+    /// visit a statement of a body
+    fn visit_statement(&mut self, path: &Path, statement: &'ast Statement) {
+        walk_statement(self, path, statement)
+    }
+
+    /// visit the condition of a conditional or the guard of a transition
+    fn visit_boolean_expression(&mut self, path: &Path, expression: &'ast BooleanExpression) {
+        walk_boolean_expression(self, path, expression)
+    }
+
+    /// visit a state machine of an implementation
+    fn visit_state_machine(&mut self, path: &Path, state_machine: &'ast StateMachine) {
+        walk_state_machine(self, path, state_machine)
+    }
+
+    /// visit a state or a transition of a state machine
+    fn visit_state_machine_element(&mut self, path: &Path, element: &'ast StateMachineElement) {
+        walk_state_machine_element(self, path, element)
+    }
+}
+
+/// visit the metadata and the packages of a package file
+pub fn walk_package_file<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    package_file: &'ast PackageFile,
+) {
+    let path = path.join("PackageFile", None);
+    if let Some(metadata) = &package_file.metadata {
+        visitor.visit_metadata(&path, metadata);
+    }
+    for package in package_file.package.iter() {
+        visitor.visit_package(&path, package);
+    }
+}
+
+/// visit the values and the categories of metadata
+pub fn walk_metadata<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    metadata: &'ast MetaData,
+) {
+    let path = path.join("Metadata", None);
+    for value in metadata.values.iter() {
+        visitor.visit_metadata_value(&path, value);
+    }
+    for category in metadata.categories.iter() {
+        visitor.visit_metadata_category(&path, category);
+    }
+}
+
+/// visit the name, the values and the categories of a category of metadata
+pub fn walk_metadata_category<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    category: &'ast MetadataCategory,
+) {
+    let path = path.join("Category", Some(&category.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &category.name_entity_type);
+    for value in category.values.iter() {
+        visitor.visit_metadata_value(&path, value);
+    }
+    for category in category.categories.iter() {
+        visitor.visit_metadata_category(&path, category);
+    }
+}
+
+/// visit the name of a metadata value
+pub fn walk_metadata_value<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    value: &'ast MetadataValue,
+) {
+    let (element, named) = match value {
+        MetadataValue::DateValue(v) => ("DateValue", &v.name_entity_type),
+        MetadataValue::FloatValue(v) => ("FloatValue", &v.name_entity_type),
+        MetadataValue::IntegerValue(v) => ("IntegerValue", &v.name_entity_type),
+        MetadataValue::StringValue(v) => ("StringValue", &v.name_entity_type),
+    };
+    let path = path.join(element, Some(&named.name.0));
+    visitor.visit_named_entity_type(&path, named);
+}
+
+/// visit the name, the data types and the components of a package
+pub fn walk_package<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    package: &'ast Package,
+) {
+    let path = path.join("Package", Some(&package.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &package.name_entity_type);
+    visitor.visit_data_type_set(&path, &package.data_type_set);
+    visitor.visit_component_set(&path, &package.component_set);
+}
+
+/// visit the data types of a package
+pub fn walk_data_type_set<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type_set: &'ast DataTypeSet,
+) {
+    let path = path.join("DataTypeSet", None);
+    for data_type in data_type_set.data_types.iter() {
+        visitor.visit_data_type(&path, data_type);
+    }
+}
+
+/// visit the data type of its kind
+pub fn walk_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast DataType,
+) {
+    match data_type {
+        DataType::NoneDataType => (),
+        DataType::BooleanDataType(dt) => visitor.visit_boolean_data_type(path, dt),
+        DataType::IntegerDataType(dt) => visitor.visit_integer_data_type(path, dt),
+        DataType::ArrayDataType(dt) => visitor.visit_array_data_type(path, dt),
+        DataType::EnumeratedDataType(dt) => visitor.visit_enumerated_data_type(path, dt),
+        DataType::ContainerDataType(dt) => visitor.visit_container_data_type(path, dt),
+        DataType::FloatDataType(dt) => visitor.visit_float_data_type(path, dt),
+        DataType::StringDataType(dt) => visitor.visit_string_data_type(path, dt),
+        DataType::SubRangeDataType(dt) => visitor.visit_sub_range_data_type(path, dt),
+    }
+}
+
+/// visit the name of a boolean data type
+pub fn walk_boolean_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast BooleanDataType,
+) {
+    let path = path.join("BooleanDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+}
+
+/// visit the name and the range of an integer data type
+pub fn walk_integer_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast IntegerDataType,
+) {
+    let path = path.join("IntegerDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    visitor.visit_range(&path, &data_type.range);
+}
+
+/// visit the name, the element type and the dimensions of an array data type
+pub fn walk_array_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast ArrayDataType,
+) {
+    let path = path.join("ArrayDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    visitor.visit_type_ref(&path, &data_type.data_type_ref);
+    visitor.visit_dimension_list(&path, &data_type.dimension_list);
+}
+
+/// visit the name and the labels of an enumerated data type
+pub fn walk_enumerated_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast EnumeratedDataType,
+) {
+    let path = path.join(
+        "EnumeratedDataType",
+        Some(&data_type.name_entity_type.name.0),
+    );
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    visitor.visit_enumeration_list(&path, &data_type.enumeration_list);
+}
+
+/// visit the name, the base type, the constraints, the entries and the trailer entries of a
+/// container data type
+pub fn walk_container_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast ContainerDataType,
+) {
+    let path = path.join(
+        "ContainerDataType",
+        Some(&data_type.name_entity_type.name.0),
+    );
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    if let Some(base_type) = &data_type.base_type {
+        visitor.visit_type_ref(&path, base_type);
+    }
+    if let Some(constraint_set) = &data_type.constraint_set {
+        visitor.visit_constraint_set(&path, constraint_set);
+    }
+    if let Some(list) = &data_type.entry_list {
+        visitor.visit_entry_list(&path, list);
+    }
+    if let Some(list) = &data_type.trailer_entry_list {
+        visitor.visit_trailer_entry_list(&path, list);
+    }
+}
+
+/// visit the name and the range of a float data type
+pub fn walk_float_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast FloatDataType,
+) {
+    let path = path.join("FloatDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    if let Some(range) = &data_type.range {
+        visitor.visit_range(&path, range);
+    }
+}
+
+/// visit the name of a string data type
+pub fn walk_string_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast StringDataType,
+) {
+    let path = path.join("StringDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+}
+
+/// visit the name, the base type and the range of a sub range data type
+pub fn walk_sub_range_data_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &'ast SubRangeDataType,
+) {
+    let path = path.join("SubRangeDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &data_type.name_entity_type);
+    visitor.visit_type_ref(&path, &data_type.base_type);
+    visitor.visit_range(&path, &data_type.range);
+}
+
+/// visit the labels of an enumerated data type
+pub fn walk_enumeration_list<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &'ast EnumerationList,
+) {
+    let path = path.join("EnumerationList", None);
+    for enumeration in list.enumeration.iter() {
+        visitor.visit_enumeration(&path, enumeration);
+    }
+}
+
+/// visit the constraints of a container
+pub fn walk_constraint_set<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    constraint_set: &'ast ConstraintSet,
+) {
+    let path = path.join("ConstraintSet", None);
+    for constraint in constraint_set.constraints.iter() {
+        visitor.visit_constraint(&path, constraint);
+    }
+}
+
+/// visit the range or the type of a constraint, named after the entry it constrains
+pub fn walk_constraint<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    constraint: &'ast Constraint,
+) {
+    match constraint {
+        Constraint::RangeConstraint(c) => {
+            let path = path.join("RangeConstraint", Some(&c.entry.0));
+            visitor.visit_range(&path, &c.range);
+        }
+        Constraint::TypeConstraint(c) => {
+            let path = path.join("TypeConstraint", Some(&c.entry.0));
+            visitor.visit_type_ref(&path, &c.type_);
+        }
+        Constraint::ValueConstraint(_) => (),
+    }
+}
+
+/// visit the entries of a container
+pub fn walk_entry_list<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &'ast EntryList,
+) {
+    let path = path.join("EntryList", None);
+    for entry in list.entries.iter() {
+        visitor.visit_entry_element(&path, entry);
+    }
+}
+
+/// visit the trailer entries of a container
+pub fn walk_trailer_entry_list<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &'ast EntryList,
+) {
+    let path = path.join("TrailerEntryList", None);
+    for entry in list.entries.iter() {
+        visitor.visit_entry_element(&path, entry);
+    }
+}
+
+/// visit the entry of its kind
+pub fn walk_entry_element<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast EntryElement,
+) {
+    match entry {
+        EntryElement::Entry(e) => visitor.visit_entry(path, e),
+        EntryElement::FixedValueEntry(e) => visitor.visit_fixed_value_entry(path, e),
+        EntryElement::PaddingEntry(e) => visitor.visit_padding_entry(path, e),
+        EntryElement::ListEntry(e) => visitor.visit_list_entry(path, e),
+        EntryElement::LengthEntry(e) => visitor.visit_length_entry(path, e),
+        EntryElement::ErrorControlEntry(e) => visitor.visit_error_control_entry(path, e),
+    }
+}
+
+/// visit the name and the type of an entry
+pub fn walk_entry<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast Entry,
+) {
+    let path = path.join("Entry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &entry.name_entity_type);
+    visitor.visit_type_ref(&path, &entry.type_);
+}
+
+/// visit the name and the type of a fixed value entry
+pub fn walk_fixed_value_entry<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast FixedValueEntry,
+) {
+    let path = path.join("FixedValueEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &entry.name_entity_type);
+    visitor.visit_type_ref(&path, &entry.type_);
+}
+
+/// visit the name of a list entry
+pub fn walk_list_entry<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast ListEntry,
+) {
+    let path = path.join("ListEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &entry.name_entity_type);
+}
+
+/// visit the name and the type of a length entry
+pub fn walk_length_entry<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast LengthEntry,
+) {
+    let path = path.join("LengthEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &entry.name_entity_type);
+    visitor.visit_type_ref(&path, &entry.type_);
+}
+
+/// visit the name and the type of an error control entry
+pub fn walk_error_control_entry<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &'ast ErrorControlEntry,
+) {
+    let path = path.join("ErrorControlEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &entry.name_entity_type);
+    visitor.visit_type_ref(&path, &entry.type_);
+}
+
+/// visit the components of a package
+pub fn walk_component_set<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    component_set: &'ast ComponentSet,
+) {
+    let path = path.join("ComponentSet", None);
+    for component in component_set.components.iter() {
+        visitor.visit_component(&path, component);
+    }
+}
+
+/// visit the required interfaces and the implementation of a component
+pub fn walk_component<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    component: &'ast Component,
+) {
+    let path = path.join("Component", Some(&component.name.0));
+    for interface in component.required_interface_set.interfaces.iter() {
+        visitor.visit_interface(&path, interface);
+    }
+    visitor.visit_implementation(&path, &component.implementation);
+}
+
+/// visit the type and the generic type maps of an interface
+pub fn walk_interface<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    interface: &'ast Interface,
+) {
+    let path = path.join("Interface", Some(&interface.name.0));
+    visitor.visit_type_ref(&path, &interface.type_);
+    for map in interface.generic_type_map_set.generic_type_maps.iter() {
+        visitor.visit_generic_type_map(&path, map);
+    }
+}
+
+/// visit the type of a generic type map
+pub fn walk_generic_type_map<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    map: &'ast GenericTypeMap,
+) {
+    let path = path.join("GenericTypeMap", Some(&map.name.0));
+    visitor.visit_type_ref(&path, &map.type_);
+}
+
+/// visit the variables, parameter maps, activities and state machines of an implementation
+pub fn walk_implementation<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    implementation: &'ast Implementation,
+) {
+    let path = path.join("Implementation", None);
+    for variable in implementation.variable_set.variables.iter() {
+        visitor.visit_variable(&path, variable);
+    }
+    for map in implementation.parameter_map_set.parameter_maps.iter() {
+        visitor.visit_parameter_map(&path, map);
+    }
+    for activity in implementation.activity_set.activities.iter() {
+        visitor.visit_activity(&path, activity);
+    }
+    for state_machine in implementation.state_machine_set.state_machines.iter() {
+        visitor.visit_state_machine(&path, state_machine);
+    }
+}
+
+/// visit the type of a variable
+pub fn walk_variable<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    variable: &'ast Variable,
+) {
+    let path = path.join("Variable", Some(&variable.name.0));
+    visitor.visit_type_ref(&path, &variable.type_);
+}
+
+/// visit the name, the arguments and the body of an activity
+pub fn walk_activity<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    activity: &'ast Activity,
+) {
+    let path = path.join("Activity", Some(&activity.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &activity.name_entity_type);
+    for argument in activity.arguments.iter() {
+        visitor.visit_activity_argument(&path, argument);
+    }
+    visitor.visit_body(&path, &activity.body);
+}
+
+/// visit the name and the type of an argument of an activity
+pub fn walk_activity_argument<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    argument: &'ast ActivityArgument,
+) {
+    let path = path.join("Argument", Some(&argument.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &argument.name_entity_type);
+    visitor.visit_type_ref(&path, &argument.type_);
+}
+
+/// visit the statements of a body
+pub fn walk_body<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, path: &Path, body: &'ast Body) {
+    let path = path.join("Body", None);
+    for statement in body.statements.iter() {
+        visitor.visit_statement(&path, statement);
+    }
+}
+
+/// visit the conditions and the bodies of a statement
+pub fn walk_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    statement: &'ast Statement,
+) {
+    match statement {
+        Statement::Conditional(c) => {
+            let path = path.join("Conditional", None);
+            visitor.visit_boolean_expression(&path, &c.condition);
+            visitor.visit_body(&path, &c.on_condition_true);
+            visitor.visit_body(&path, &c.on_condition_false);
+        }
+        Statement::Iteration(i) => {
+            let path = path.join("Iteration", None);
+            visitor.visit_body(&path, &i.do_);
+        }
+        Statement::Assignment(_)
+        | Statement::Calibration(_)
+        | Statement::SendParameterPrimitive(_)
+        | Statement::SendCommandPrimitive(_)
+        | Statement::MathOperation(_)
+        | Statement::Call(_) => (),
+    }
+}
+
+/// visit the conditions of a group of conditions, and the type of a type check
+pub fn walk_boolean_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    expression: &'ast BooleanExpression,
+) {
+    match expression {
+        BooleanExpression::Condition(_) => (),
+        BooleanExpression::ANDedConditions(conditions) => {
+            let path = path.join("ANDedConditions", None);
+            for condition in conditions.iter() {
+                visitor.visit_boolean_expression(&path, condition);
+            }
+        }
+        BooleanExpression::ORedConditions(conditions) => {
+            let path = path.join("ORedConditions", None);
+            for condition in conditions.iter() {
+                visitor.visit_boolean_expression(&path, condition);
+            }
+        }
+        BooleanExpression::TypeCondition(check) => {
+            let path = path.join("TypeCondition", None);
+            visitor.visit_type_ref(&path, &check.type_operand);
+        }
+    }
+}
+
+/// visit the name and the elements of a state machine
+pub fn walk_state_machine<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    state_machine: &'ast StateMachine,
+) {
+    let path = path.join("StateMachine", Some(&state_machine.name_entity_type.name.0));
+    visitor.visit_named_entity_type(&path, &state_machine.name_entity_type);
+    for element in state_machine.elements.iter() {
+        visitor.visit_state_machine_element(&path, element);
+    }
+}
+
+/// visit the name of a state or a transition, and the guard of a transition
+pub fn walk_state_machine_element<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    element: &'ast StateMachineElement,
+) {
+    let (name, named) = match element {
+        StateMachineElement::EntryState(s) => ("EntryState", &s.name_entity_type),
+        StateMachineElement::ExitState(s) => ("ExitState", &s.name_entity_type),
+        StateMachineElement::State(s) => ("State", &s.name_entity_type),
+        StateMachineElement::Transition(t) => ("Transition", &t.name_entity_type),
+    };
+    let path = path.join(name, Some(&named.name.0));
+    visitor.visit_named_entity_type(&path, named);
+    if let StateMachineElement::Transition(Transition {
+        guard: Some(guard), ..
+    }) = element
+    {
+        visitor.visit_boolean_expression(&path, guard);
+    }
+}
+
+/// Walks the nodes of a package file by mutable reference
+///
+/// Every method defaults to the `walk_*_mut` function of its node, which visits the children.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// visit a package file, the root of the ast
+    fn visit_package_file_mut(&mut self, path: &Path, package_file: &mut PackageFile) {
+        walk_package_file_mut(self, path, package_file)
+    }
+
+    /// visit the metadata of a package file
+    fn visit_metadata_mut(&mut self, path: &Path, metadata: &mut MetaData) {
+        walk_metadata_mut(self, path, metadata)
+    }
+
+    /// visit a category of metadata
+    fn visit_metadata_category_mut(&mut self, path: &Path, category: &mut MetadataCategory) {
+        walk_metadata_category_mut(self, path, category)
+    }
+
+    /// visit a metadata value
+    fn visit_metadata_value_mut(&mut self, path: &Path, value: &mut MetadataValue) {
+        walk_metadata_value_mut(self, path, value)
+    }
+
+    /// visit a package
+    fn visit_package_mut(&mut self, path: &Path, package: &mut Package) {
+        walk_package_mut(self, path, package)
+    }
+
+    /// visit the name, descriptions, semantics and extensions of the last node of the path
+    fn visit_named_entity_type_mut(&mut self, path: &Path, named: &mut NamedEntityType) {}
+
+    /// visit a reference to a data type or an interface type
+    fn visit_type_ref_mut(&mut self, path: &Path, type_ref: &mut QualifiedName) {}
+
+    /// visit the data types of a package
+    fn visit_data_type_set_mut(&mut self, path: &Path, data_type_set: &mut DataTypeSet) {
+        walk_data_type_set_mut(self, path, data_type_set)
+    }
+
+    /// visit a data type of any kind
+    fn visit_data_type_mut(&mut self, path: &Path, data_type: &mut DataType) {
+        walk_data_type_mut(self, path, data_type)
+    }
+
+    /// visit a boolean data type
+    fn visit_boolean_data_type_mut(&mut self, path: &Path, data_type: &mut BooleanDataType) {
+        walk_boolean_data_type_mut(self, path, data_type)
+    }
+
+    /// visit an integer data type
+    fn visit_integer_data_type_mut(&mut self, path: &Path, data_type: &mut IntegerDataType) {
+        walk_integer_data_type_mut(self, path, data_type)
+    }
+
+    /// visit an array data type
+    fn visit_array_data_type_mut(&mut self, path: &Path, data_type: &mut ArrayDataType) {
+        walk_array_data_type_mut(self, path, data_type)
+    }
+
+    /// visit an enumerated data type
+    fn visit_enumerated_data_type_mut(&mut self, path: &Path, data_type: &mut EnumeratedDataType) {
+        walk_enumerated_data_type_mut(self, path, data_type)
+    }
+
+    /// visit a container data type
+    fn visit_container_data_type_mut(&mut self, path: &Path, data_type: &mut ContainerDataType) {
+        walk_container_data_type_mut(self, path, data_type)
+    }
+
+    /// visit a float data type
+    fn visit_float_data_type_mut(&mut self, path: &Path, data_type: &mut FloatDataType) {
+        walk_float_data_type_mut(self, path, data_type)
+    }
+
+    /// visit a string data type
+    fn visit_string_data_type_mut(&mut self, path: &Path, data_type: &mut StringDataType) {
+        walk_string_data_type_mut(self, path, data_type)
+    }
+
+    /// visit a sub range data type
+    fn visit_sub_range_data_type_mut(&mut self, path: &Path, data_type: &mut SubRangeDataType) {
+        walk_sub_range_data_type_mut(self, path, data_type)
+    }
+
+    /// visit the labels of an enumerated data type
+    fn visit_enumeration_list_mut(&mut self, path: &Path, list: &mut EnumerationList) {
+        walk_enumeration_list_mut(self, path, list)
+    }
+
+    /// visit a label of an enumerated data type
+    fn visit_enumeration_mut(&mut self, path: &Path, enumeration: &mut Enumeration) {}
+
+    /// visit the range of an integer, float or sub range data type, or of a range constraint
+    fn visit_range_mut(&mut self, path: &Path, range: &mut Range) {}
+
+    /// visit the dimensions of an array data type
+    fn visit_dimension_list_mut(&mut self, path: &Path, dimensions: &mut DimensionList) {}
+
+    /// visit the constraints of a container
+    fn visit_constraint_set_mut(&mut self, path: &Path, constraint_set: &mut ConstraintSet) {
+        walk_constraint_set_mut(self, path, constraint_set)
+    }
+
+    /// visit a constraint of a container
+    fn visit_constraint_mut(&mut self, path: &Path, constraint: &mut Constraint) {
+        walk_constraint_mut(self, path, constraint)
+    }
+
+    /// visit the entries of a container
+    fn visit_entry_list_mut(&mut self, path: &Path, list: &mut EntryList) {
+        walk_entry_list_mut(self, path, list)
+    }
+
+    /// visit the trailer entries of a container
+    fn visit_trailer_entry_list_mut(&mut self, path: &Path, list: &mut EntryList) {
+        walk_trailer_entry_list_mut(self, path, list)
+    }
+
+    /// visit an entry of any kind
+    fn visit_entry_element_mut(&mut self, path: &Path, entry: &mut EntryElement) {
+        walk_entry_element_mut(self, path, entry)
+    }
+
+    /// visit an entry
+    fn visit_entry_mut(&mut self, path: &Path, entry: &mut Entry) {
+        walk_entry_mut(self, path, entry)
+    }
+
+    /// visit a fixed value entry
+    fn visit_fixed_value_entry_mut(&mut self, path: &Path, entry: &mut FixedValueEntry) {
+        walk_fixed_value_entry_mut(self, path, entry)
+    }
+
+    /// visit a padding entry
+    fn visit_padding_entry_mut(&mut self, path: &Path, entry: &mut PaddingEntry) {}
+
+    /// visit a list entry
+    fn visit_list_entry_mut(&mut self, path: &Path, entry: &mut ListEntry) {
+        walk_list_entry_mut(self, path, entry)
+    }
+
+    /// visit a length entry
+    fn visit_length_entry_mut(&mut self, path: &Path, entry: &mut LengthEntry) {
+        walk_length_entry_mut(self, path, entry)
+    }
+
+    /// visit an error control entry
+    fn visit_error_control_entry_mut(&mut self, path: &Path, entry: &mut ErrorControlEntry) {
+        walk_error_control_entry_mut(self, path, entry)
+    }
+
+    /// visit the components of a package
+    fn visit_component_set_mut(&mut self, path: &Path, component_set: &mut ComponentSet) {
+        walk_component_set_mut(self, path, component_set)
+    }
+
+    /// visit a component
+    fn visit_component_mut(&mut self, path: &Path, component: &mut Component) {
+        walk_component_mut(self, path, component)
+    }
+
+    /// visit a required interface of a component
+    fn visit_interface_mut(&mut self, path: &Path, interface: &mut Interface) {
+        walk_interface_mut(self, path, interface)
+    }
+
+    /// visit a generic type map of an interface
+    fn visit_generic_type_map_mut(&mut self, path: &Path, map: &mut GenericTypeMap) {
+        walk_generic_type_map_mut(self, path, map)
+    }
+
+    /// visit the implementation of a component
+    fn visit_implementation_mut(&mut self, path: &Path, implementation: &mut Implementation) {
+        walk_implementation_mut(self, path, implementation)
+    }
+
+    /// visit a variable of an implementation
+    fn visit_variable_mut(&mut self, path: &Path, variable: &mut Variable) {
+        walk_variable_mut(self, path, variable)
+    }
+
+    /// visit a parameter map of an implementation
+    fn visit_parameter_map_mut(&mut self, path: &Path, map: &mut ParameterMap) {}
+
+    /// visit an activity of an implementation
+    fn visit_activity_mut(&mut self, path: &Path, activity: &mut Activity) {
+        walk_activity_mut(self, path, activity)
+    }
+
+    /// visit an argument of an activity
+    fn visit_activity_argument_mut(&mut self, path: &Path, argument: &mut ActivityArgument) {
+        walk_activity_argument_mut(self, path, argument)
+    }
+
+    /// visit the body of an activity, a conditional or an iteration
+    fn visit_body_mut(&mut self, path: &Path, body: &mut Body) {
+        walk_body_mut(self, path, body)
+    }
+
+    /// visit a statement of a body
+    fn visit_statement_mut(&mut self, path: &Path, statement: &mut Statement) {
+        walk_statement_mut(self, path, statement)
+    }
+
+    /// visit the condition of a conditional or the guard of a transition
+    fn visit_boolean_expression_mut(&mut self, path: &Path, expression: &mut BooleanExpression) {
+        walk_boolean_expression_mut(self, path, expression)
+    }
+
+    /// visit a state machine of an implementation
+    fn visit_state_machine_mut(&mut self, path: &Path, state_machine: &mut StateMachine) {
+        walk_state_machine_mut(self, path, state_machine)
+    }
+
+    /// visit a state or a transition of a state machine
+    fn visit_state_machine_element_mut(&mut self, path: &Path, element: &mut StateMachineElement) {
+        walk_state_machine_element_mut(self, path, element)
+    }
+}
+
+/// visit the metadata and the packages of a package file
+pub fn walk_package_file_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    package_file: &mut PackageFile,
+) {
+    let path = path.join("PackageFile", None);
+    if let Some(metadata) = &mut package_file.metadata {
+        visitor.visit_metadata_mut(&path, metadata);
+    }
+    for package in package_file.package.iter_mut() {
+        visitor.visit_package_mut(&path, package);
+    }
+}
+
+/// visit the values and the categories of metadata
+pub fn walk_metadata_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    metadata: &mut MetaData,
+) {
+    let path = path.join("Metadata", None);
+    for value in metadata.values.iter_mut() {
+        visitor.visit_metadata_value_mut(&path, value);
+    }
+    for category in metadata.categories.iter_mut() {
+        visitor.visit_metadata_category_mut(&path, category);
+    }
+}
+
+/// visit the name, the values and the categories of a category of metadata
+pub fn walk_metadata_category_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    category: &mut MetadataCategory,
+) {
+    let path = path.join("Category", Some(&category.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut category.name_entity_type);
+    for value in category.values.iter_mut() {
+        visitor.visit_metadata_value_mut(&path, value);
+    }
+    for category in category.categories.iter_mut() {
+        visitor.visit_metadata_category_mut(&path, category);
+    }
+}
+
+/// visit the name of a metadata value
+pub fn walk_metadata_value_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    value: &mut MetadataValue,
+) {
+    let (element, named) = match value {
+        MetadataValue::DateValue(v) => ("DateValue", &mut v.name_entity_type),
+        MetadataValue::FloatValue(v) => ("FloatValue", &mut v.name_entity_type),
+        MetadataValue::IntegerValue(v) => ("IntegerValue", &mut v.name_entity_type),
+        MetadataValue::StringValue(v) => ("StringValue", &mut v.name_entity_type),
+    };
+    let path = path.join(element, Some(&named.name.0));
+    visitor.visit_named_entity_type_mut(&path, named);
+}
+
+/// visit the name, the data types and the components of a package
+pub fn walk_package_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    package: &mut Package,
+) {
+    let path = path.join("Package", Some(&package.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut package.name_entity_type);
+    visitor.visit_data_type_set_mut(&path, &mut package.data_type_set);
+    visitor.visit_component_set_mut(&path, &mut package.component_set);
+}
+
+/// visit the data types of a package
+pub fn walk_data_type_set_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type_set: &mut DataTypeSet,
+) {
+    let path = path.join("DataTypeSet", None);
+    for data_type in data_type_set.data_types.iter_mut() {
+        visitor.visit_data_type_mut(&path, data_type);
+    }
+}
+
+/// visit the data type of its kind
+pub fn walk_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut DataType,
+) {
+    match data_type {
+        DataType::NoneDataType => (),
+        DataType::BooleanDataType(dt) => visitor.visit_boolean_data_type_mut(path, dt),
+        DataType::IntegerDataType(dt) => visitor.visit_integer_data_type_mut(path, dt),
+        DataType::ArrayDataType(dt) => visitor.visit_array_data_type_mut(path, dt),
+        DataType::EnumeratedDataType(dt) => visitor.visit_enumerated_data_type_mut(path, dt),
+        DataType::ContainerDataType(dt) => visitor.visit_container_data_type_mut(path, dt),
+        DataType::FloatDataType(dt) => visitor.visit_float_data_type_mut(path, dt),
+        DataType::StringDataType(dt) => visitor.visit_string_data_type_mut(path, dt),
+        DataType::SubRangeDataType(dt) => visitor.visit_sub_range_data_type_mut(path, dt),
+    }
+}
+
+/// visit the name of a boolean data type
+pub fn walk_boolean_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut BooleanDataType,
+) {
+    let path = path.join("BooleanDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+}
+
+/// visit the name and the range of an integer data type
+pub fn walk_integer_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut IntegerDataType,
+) {
+    let path = path.join("IntegerDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    visitor.visit_range_mut(&path, &mut data_type.range);
+}
+
+/// visit the name, the element type and the dimensions of an array data type
+pub fn walk_array_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut ArrayDataType,
+) {
+    let path = path.join("ArrayDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut data_type.data_type_ref);
+    visitor.visit_dimension_list_mut(&path, &mut data_type.dimension_list);
+}
+
+/// visit the name and the labels of an enumerated data type
+pub fn walk_enumerated_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut EnumeratedDataType,
+) {
+    let path = path.join(
+        "EnumeratedDataType",
+        Some(&data_type.name_entity_type.name.0),
+    );
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    visitor.visit_enumeration_list_mut(&path, &mut data_type.enumeration_list);
+}
+
+/// visit the name, the base type, the constraints, the entries and the trailer entries of a
+/// container data type
+pub fn walk_container_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut ContainerDataType,
+) {
+    let path = path.join(
+        "ContainerDataType",
+        Some(&data_type.name_entity_type.name.0),
+    );
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    if let Some(base_type) = &mut data_type.base_type {
+        visitor.visit_type_ref_mut(&path, base_type);
+    }
+    if let Some(constraint_set) = &mut data_type.constraint_set {
+        visitor.visit_constraint_set_mut(&path, constraint_set);
+    }
+    if let Some(list) = &mut data_type.entry_list {
+        visitor.visit_entry_list_mut(&path, list);
+    }
+    if let Some(list) = &mut data_type.trailer_entry_list {
+        visitor.visit_trailer_entry_list_mut(&path, list);
+    }
+}
+
+/// visit the name and the range of a float data type
+pub fn walk_float_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut FloatDataType,
+) {
+    let path = path.join("FloatDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    if let Some(range) = &mut data_type.range {
+        visitor.visit_range_mut(&path, range);
+    }
+}
+
+/// visit the name of a string data type
+pub fn walk_string_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut StringDataType,
+) {
+    let path = path.join("StringDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+}
+
+/// visit the name, the base type and the range of a sub range data type
+pub fn walk_sub_range_data_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    data_type: &mut SubRangeDataType,
+) {
+    let path = path.join("SubRangeDataType", Some(&data_type.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut data_type.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut data_type.base_type);
+    visitor.visit_range_mut(&path, &mut data_type.range);
+}
+
+/// visit the labels of an enumerated data type
+pub fn walk_enumeration_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &mut EnumerationList,
+) {
+    let path = path.join("EnumerationList", None);
+    for enumeration in list.enumeration.iter_mut() {
+        visitor.visit_enumeration_mut(&path, enumeration);
+    }
+}
+
+/// visit the constraints of a container
+pub fn walk_constraint_set_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    constraint_set: &mut ConstraintSet,
+) {
+    let path = path.join("ConstraintSet", None);
+    for constraint in constraint_set.constraints.iter_mut() {
+        visitor.visit_constraint_mut(&path, constraint);
+    }
+}
+
+/// visit the range or the type of a constraint, named after the entry it constrains
+pub fn walk_constraint_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    constraint: &mut Constraint,
+) {
+    match constraint {
+        Constraint::RangeConstraint(c) => {
+            let path = path.join("RangeConstraint", Some(&c.entry.0));
+            visitor.visit_range_mut(&path, &mut c.range);
+        }
+        Constraint::TypeConstraint(c) => {
+            let path = path.join("TypeConstraint", Some(&c.entry.0));
+            visitor.visit_type_ref_mut(&path, &mut c.type_);
+        }
+        Constraint::ValueConstraint(_) => (),
+    }
+}
+
+/// visit the entries of a container
+pub fn walk_entry_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &mut EntryList,
+) {
+    let path = path.join("EntryList", None);
+    for entry in list.entries.iter_mut() {
+        visitor.visit_entry_element_mut(&path, entry);
+    }
+}
+
+/// visit the trailer entries of a container
+pub fn walk_trailer_entry_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    list: &mut EntryList,
+) {
+    let path = path.join("TrailerEntryList", None);
+    for entry in list.entries.iter_mut() {
+        visitor.visit_entry_element_mut(&path, entry);
+    }
+}
+
+/// visit the entry of its kind
+pub fn walk_entry_element_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &mut EntryElement,
+) {
+    match entry {
+        EntryElement::Entry(e) => visitor.visit_entry_mut(path, e),
+        EntryElement::FixedValueEntry(e) => visitor.visit_fixed_value_entry_mut(path, e),
+        EntryElement::PaddingEntry(e) => visitor.visit_padding_entry_mut(path, e),
+        EntryElement::ListEntry(e) => visitor.visit_list_entry_mut(path, e),
+        EntryElement::LengthEntry(e) => visitor.visit_length_entry_mut(path, e),
+        EntryElement::ErrorControlEntry(e) => visitor.visit_error_control_entry_mut(path, e),
+    }
+}
+
+/// visit the name and the type of an entry
+pub fn walk_entry_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &Path, entry: &mut Entry) {
+    let path = path.join("Entry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut entry.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut entry.type_);
+}
+
+/// visit the name and the type of a fixed value entry
+pub fn walk_fixed_value_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &mut FixedValueEntry,
+) {
+    let path = path.join("FixedValueEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut entry.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut entry.type_);
+}
+
+/// visit the name of a list entry
+pub fn walk_list_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &mut ListEntry,
+) {
+    let path = path.join("ListEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut entry.name_entity_type);
+}
+
+/// visit the name and the type of a length entry
+pub fn walk_length_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &mut LengthEntry,
+) {
+    let path = path.join("LengthEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut entry.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut entry.type_);
+}
+
+/// visit the name and the type of an error control entry
+pub fn walk_error_control_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    entry: &mut ErrorControlEntry,
+) {
+    let path = path.join("ErrorControlEntry", Some(&entry.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut entry.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut entry.type_);
+}
+
+/// visit the components of a package
+pub fn walk_component_set_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    component_set: &mut ComponentSet,
+) {
+    let path = path.join("ComponentSet", None);
+    for component in component_set.components.iter_mut() {
+        visitor.visit_component_mut(&path, component);
+    }
+}
+
+/// visit the required interfaces and the implementation of a component
+pub fn walk_component_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    component: &mut Component,
+) {
+    let path = path.join("Component", Some(&component.name.0));
+    for interface in component.required_interface_set.interfaces.iter_mut() {
+        visitor.visit_interface_mut(&path, interface);
+    }
+    visitor.visit_implementation_mut(&path, &mut component.implementation);
+}
+
+/// visit the type and the generic type maps of an interface
+pub fn walk_interface_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    interface: &mut Interface,
+) {
+    let path = path.join("Interface", Some(&interface.name.0));
+    visitor.visit_type_ref_mut(&path, &mut interface.type_);
+    for map in interface.generic_type_map_set.generic_type_maps.iter_mut() {
+        visitor.visit_generic_type_map_mut(&path, map);
+    }
+}
+
+/// visit the type of a generic type map
+pub fn walk_generic_type_map_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    map: &mut GenericTypeMap,
+) {
+    let path = path.join("GenericTypeMap", Some(&map.name.0));
+    visitor.visit_type_ref_mut(&path, &mut map.type_);
+}
+
+/// visit the variables, parameter maps, activities and state machines of an implementation
+pub fn walk_implementation_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    implementation: &mut Implementation,
+) {
+    let path = path.join("Implementation", None);
+    for variable in implementation.variable_set.variables.iter_mut() {
+        visitor.visit_variable_mut(&path, variable);
+    }
+    for map in implementation.parameter_map_set.parameter_maps.iter_mut() {
+        visitor.visit_parameter_map_mut(&path, map);
+    }
+    for activity in implementation.activity_set.activities.iter_mut() {
+        visitor.visit_activity_mut(&path, activity);
+    }
+    for state_machine in implementation.state_machine_set.state_machines.iter_mut() {
+        visitor.visit_state_machine_mut(&path, state_machine);
+    }
+}
+
+/// visit the type of a variable
+pub fn walk_variable_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    variable: &mut Variable,
+) {
+    let path = path.join("Variable", Some(&variable.name.0));
+    visitor.visit_type_ref_mut(&path, &mut variable.type_);
+}
+
+/// visit the name, the arguments and the body of an activity
+pub fn walk_activity_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    activity: &mut Activity,
+) {
+    let path = path.join("Activity", Some(&activity.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut activity.name_entity_type);
+    for argument in activity.arguments.iter_mut() {
+        visitor.visit_activity_argument_mut(&path, argument);
+    }
+    visitor.visit_body_mut(&path, &mut activity.body);
+}
+
+/// visit the name and the type of an argument of an activity
+pub fn walk_activity_argument_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    argument: &mut ActivityArgument,
+) {
+    let path = path.join("Argument", Some(&argument.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut argument.name_entity_type);
+    visitor.visit_type_ref_mut(&path, &mut argument.type_);
+}
+
+/// visit the statements of a body
+pub fn walk_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &Path, body: &mut Body) {
+    let path = path.join("Body", None);
+    for statement in body.statements.iter_mut() {
+        visitor.visit_statement_mut(&path, statement);
+    }
+}
+
+/// visit the conditions and the bodies of a statement
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    statement: &mut Statement,
+) {
+    match statement {
+        Statement::Conditional(c) => {
+            let path = path.join("Conditional", None);
+            visitor.visit_boolean_expression_mut(&path, &mut c.condition);
+            visitor.visit_body_mut(&path, &mut c.on_condition_true);
+            visitor.visit_body_mut(&path, &mut c.on_condition_false);
+        }
+        Statement::Iteration(i) => {
+            let path = path.join("Iteration", None);
+            visitor.visit_body_mut(&path, &mut i.do_);
+        }
+        Statement::Assignment(_)
+        | Statement::Calibration(_)
+        | Statement::SendParameterPrimitive(_)
+        | Statement::SendCommandPrimitive(_)
+        | Statement::MathOperation(_)
+        | Statement::Call(_) => (),
+    }
+}
+
+/// visit the conditions of a group of conditions, and the type of a type check
+pub fn walk_boolean_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    expression: &mut BooleanExpression,
+) {
+    match expression {
+        BooleanExpression::Condition(_) => (),
+        BooleanExpression::ANDedConditions(conditions) => {
+            let path = path.join("ANDedConditions", None);
+            for condition in conditions.iter_mut() {
+                visitor.visit_boolean_expression_mut(&path, condition);
+            }
+        }
+        BooleanExpression::ORedConditions(conditions) => {
+            let path = path.join("ORedConditions", None);
+            for condition in conditions.iter_mut() {
+                visitor.visit_boolean_expression_mut(&path, condition);
+            }
+        }
+        BooleanExpression::TypeCondition(check) => {
+            let path = path.join("TypeCondition", None);
+            visitor.visit_type_ref_mut(&path, &mut check.type_operand);
+        }
+    }
+}
+
+/// visit the name and the elements of a state machine
+pub fn walk_state_machine_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    state_machine: &mut StateMachine,
+) {
+    let path = path.join("StateMachine", Some(&state_machine.name_entity_type.name.0));
+    visitor.visit_named_entity_type_mut(&path, &mut state_machine.name_entity_type);
+    for element in state_machine.elements.iter_mut() {
+        visitor.visit_state_machine_element_mut(&path, element);
+    }
+}
+
+/// visit the name of a state or a transition, and the guard of a transition
+pub fn walk_state_machine_element_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &Path,
+    element: &mut StateMachineElement,
+) {
+    let (name, named) = match element {
+        StateMachineElement::EntryState(s) => ("EntryState", &mut s.name_entity_type),
+        StateMachineElement::ExitState(s) => ("ExitState", &mut s.name_entity_type),
+        StateMachineElement::State(s) => ("State", &mut s.name_entity_type),
+        StateMachineElement::Transition(t) => ("Transition", &mut t.name_entity_type),
+    };
+    let path = path.join(name, Some(&named.name.0));
+    visitor.visit_named_entity_type_mut(&path, named);
+    if let StateMachineElement::Transition(Transition {
+        guard: Some(guard), ..
+    }) = element
+    {
+        visitor.visit_boolean_expression_mut(&path, guard);
+    }
+}
@@ -1,8 +1,38 @@
 //! Expression Evaluation
-use evalexpr::{eval, EvalexprError, Value};
-use regex::Regex;
+//!
+//! Attribute values of a datasheet are literals (table 3-1) that may refer to mission
+//! parameters as `${A/B}`. How a value is read depends on its first character:
+//!
+//! - a value starting with a letter or `_` (other than `true` and `false`) is a string, such
+//!   as a name, a type reference or a keyword; parameters in it are substituted as text. A
+//!   name, like `BASE_TYPES/uint${SIZE}`, must still be a valid qualified name afterwards.
+//! - anything else is an expression (see [`parse`]), evaluated to a typed [`Value`]. Parameter
+//!   values are read as literals. A value that does not parse is an error, never a string.
+//!
+//! Parameters are loaded from JSON: strings are read as literals (so `"16"` and `16` are the
+//! same integer), numbers, booleans and arrays keep their type. Arrays are indexed with
+//! `${A/B}[i]`. The objects of an array of objects are namespaces named by their index, so
+//! `{"PIPES": [{"DEPTH": 4}]}` defines `${PIPES/0/DEPTH}`; arrays nested in arrays can not
+//! contain objects.
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::eds::value::Value;
+
+/// Parser for SEDS literal expressions
+pub mod parse;
+
+/// Layered parameter sources: files, environment and command line
+pub mod sources;
+
+/// Missing and unused parameters of a set of datasheets
+pub mod report;
+
+use parse::{BinaryOperator, Expr, UnaryOperator};
+pub use parse::{ExpressionError, ExpressionErrorKind};
+use sources::ParameterSource;
 
 /// Error Types for Expression Evaluator
 #[derive(Clone, Debug)]
@@ -10,16 +40,39 @@ pub enum NamespaceError {
     /// Variable Identifier isn't found
     VariableIdentifierNotFound(String),
     /// String failed expression evaluation
-    ExpressionEvaluationError(EvalexprError),
+    ExpressionEvaluationError(ExpressionError),
+    /// a parameter can not be represented as a value
+    InvalidParameterValue {
+        /// path of the parameter, e.g. `CFE_MISSION/PIPE_DEPTHS[2]`
+        key: String,
+        /// why the value is invalid
+        reason: String,
+    },
+}
+
+impl fmt::Display for NamespaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamespaceError::VariableIdentifierNotFound(path) => {
+                write!(f, "unknown parameter `{}`", path)
+            }
+            NamespaceError::ExpressionEvaluationError(err) => err.fmt(f),
+            NamespaceError::InvalidParameterValue { key, reason } => {
+                write!(f, "invalid value for parameter `{}`: {}", key, reason)
+            }
+        }
+    }
 }
 
+impl std::error::Error for NamespaceError {}
+
 /// A namespace is a nested mapping of variable names to values
 #[derive(Clone, Debug)]
 pub enum NamespaceValue {
     /// NamespaceValue HashMap
     Namespace(HashMap<String, NamespaceValue>),
-    /// NamespaceValue Value
-    Value(String),
+    /// NamespaceValue Value, a scalar or an array
+    Value(Value),
 }
 
 type Namespace = HashMap<String, NamespaceValue>;
@@ -28,44 +81,85 @@ type Namespace = HashMap<String, NamespaceValue>;
 #[derive(Clone, Debug)]
 pub struct ExpressionContext {
     namespace: NamespaceValue,
+    sources: BTreeMap<String, ParameterSource>,
 }
 
-/// build a namespacevalue from a json value
-fn json_to_namespace(json: &JsonValue) -> Option<NamespaceValue> {
+/// build a namespacevalue from a json value; `key` is the path of the value, for errors
+fn json_to_namespace(json: &JsonValue, key: &str) -> Result<NamespaceValue, NamespaceError> {
     match json {
         JsonValue::Object(map) => {
             let mut namespace = Namespace::new();
-            for (key, value) in map {
-                let r = json_to_namespace(value)?;
-                let _ = namespace.insert(key.clone(), r);
+            for (name, value) in map {
+                let path = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", key, name)
+                };
+                let r = json_to_namespace(value, &path)?;
+                let _ = namespace.insert(name.clone(), r);
+            }
+            Ok(NamespaceValue::Namespace(namespace))
+        }
+        // the objects of an array are namespaces, named by their index
+        JsonValue::Array(items) if items.iter().any(JsonValue::is_object) => {
+            let mut namespace = Namespace::new();
+            for (i, item) in items.iter().enumerate() {
+                let r = json_to_namespace(item, &format!("{}/{}", key, i))?;
+                let _ = namespace.insert(i.to_string(), r);
             }
-            Some(NamespaceValue::Namespace(namespace))
+            Ok(NamespaceValue::Namespace(namespace))
         }
-        JsonValue::String(s) => Some(NamespaceValue::Value(s.clone())),
-        _ => None,
+        json => json_to_value(json, key).map(NamespaceValue::Value),
+    }
+}
+
+/// convert a json scalar or array to a value
+fn json_to_value(json: &JsonValue, key: &str) -> Result<Value, NamespaceError> {
+    let invalid = |reason: &str| NamespaceError::InvalidParameterValue {
+        key: key.to_string(),
+        reason: reason.to_string(),
+    };
+    match json {
+        JsonValue::Bool(b) => Ok(Value::Boolean(*b)),
+        JsonValue::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => Ok(Value::Integer(i)),
+            (None, _) if n.is_u64() => Err(invalid("integer does not fit in 64 bits")),
+            (None, Some(f)) => Ok(Value::Float(f)),
+            (None, None) => Err(invalid("not a finite number")),
+        },
+        JsonValue::String(s) => Ok(Value::String(s.clone())),
+        JsonValue::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| json_to_value(item, &format!("{}[{}]", key, i)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        JsonValue::Object(_) => Err(invalid("nested arrays can not contain objects")),
+        JsonValue::Null => Err(invalid("null is not a value")),
+    }
+}
+
+/// read the strings of a parameter value as literals, so that `"16"` is a number
+fn literal(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::parse(s),
+        Value::Array(values) => Value::Array(values.iter().map(literal).collect()),
+        value => value.clone(),
     }
 }
 
 /// fetch a variable from a namespace
-fn fetch_variable(namespace: &NamespaceValue, path: &[&str]) -> Result<String, EvalexprError> {
-    let (head, tail) = path.split_at(1);
+fn fetch_variable<'a>(namespace: &'a NamespaceValue, path: &[&str]) -> Option<&'a Value> {
+    let (head, tail) = path.split_first()?;
     match namespace {
-        NamespaceValue::Namespace(inner) if !tail.is_empty() => inner
-            .get(head[0])
-            .and_then(|next| fetch_variable(next, tail).ok())
-            .ok_or_else(|| EvalexprError::VariableIdentifierNotFound(format!("{:?}", path))),
-        NamespaceValue::Namespace(inner) if tail.is_empty() => match inner.get(head[0]) {
-            Some(NamespaceValue::Value(value)) => Ok(value.clone()),
-            _ => Err(EvalexprError::VariableIdentifierNotFound(format!(
-                "{:?}",
-                path
-            ))),
+        NamespaceValue::Namespace(inner) if !tail.is_empty() => {
+            fetch_variable(inner.get(*head)?, tail)
+        }
+        NamespaceValue::Namespace(inner) => match inner.get(*head)? {
+            NamespaceValue::Value(value) => Some(value),
+            NamespaceValue::Namespace(_) => None,
         },
-        NamespaceValue::Value(value) if tail.is_empty() => Ok(value.clone()),
-        _ => Err(EvalexprError::VariableIdentifierNotFound(format!(
-            "{:?}",
-            path
-        ))),
+        NamespaceValue::Value(_) => None,
     }
 }
 
@@ -80,43 +174,327 @@ impl ExpressionContext {
     pub fn new() -> Self {
         ExpressionContext {
             namespace: NamespaceValue::Namespace(HashMap::new()),
+            sources: BTreeMap::new(),
         }
     }
 
-    /// create a namespace from a nested JSON dictionary
-    pub fn from_json(json: &JsonValue) -> Option<ExpressionContext> {
-        let namespace = json_to_namespace(json);
-        namespace.map(|namespace| ExpressionContext { namespace })
+    /// create a namespace from a nested JSON dictionary; values are strings (read as literals),
+    /// numbers, booleans, arrays of those or arrays of dictionaries
+    pub fn from_json(json: &JsonValue) -> Result<ExpressionContext, NamespaceError> {
+        match json {
+            JsonValue::Object(_) => Ok(ExpressionContext {
+                namespace: json_to_namespace(json, "")?,
+                sources: BTreeMap::new(),
+            }),
+            _ => Err(NamespaceError::InvalidParameterValue {
+                key: String::new(),
+                reason: "parameters must be a JSON object".to_string(),
+            }),
+        }
     }
 
-    /// get a variable from the namespace
+    /// get a variable from the namespace, as text
     pub fn get(&self, path: &[&str]) -> Result<String, NamespaceError> {
-        match fetch_variable(&self.namespace, path) {
-            Ok(value) => Ok(value),
-            Err(err) => Err(NamespaceError::ExpressionEvaluationError(err)),
+        fetch_variable(&self.namespace, path)
+            .map(|value| value.to_string())
+            .ok_or_else(|| NamespaceError::VariableIdentifierNotFound(path.join("/")))
+    }
+
+    /// get a variable from the namespace as a typed value
+    pub fn value(&self, path: &[&str]) -> Result<Value, NamespaceError> {
+        fetch_variable(&self.namespace, path)
+            .map(literal)
+            .ok_or_else(|| NamespaceError::VariableIdentifierNotFound(path.join("/")))
+    }
+
+    /// paths and values of all parameters, sorted by path
+    pub fn parameters(&self) -> Vec<(String, Value)> {
+        fn collect(namespace: &NamespaceValue, prefix: &str, out: &mut Vec<(String, Value)>) {
+            match namespace {
+                NamespaceValue::Namespace(inner) => {
+                    for (name, value) in inner {
+                        let path = if prefix.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{}/{}", prefix, name)
+                        };
+                        collect(value, &path, out);
+                    }
+                }
+                NamespaceValue::Value(value) => out.push((prefix.to_string(), literal(value))),
+            }
+        }
+        let mut parameters = Vec::new();
+        collect(&self.namespace, "", &mut parameters);
+        parameters.sort_by(|(a, _), (b, _)| a.cmp(b));
+        parameters
+    }
+
+    /// the layer that supplied a parameter, if it was loaded by a [`sources::ParameterLoader`];
+    /// the parameters of an array of objects come from the layer that supplied the array
+    pub fn source(&self, path: &str) -> Option<&ParameterSource> {
+        let mut path = path;
+        loop {
+            if let Some(source) = self.sources.get(path) {
+                return Some(source);
+            }
+            path = path.rsplit_once('/')?.0;
+        }
+    }
+
+    /// replace the `${...}` placeholders of an expression with their values, without evaluating it
+    pub fn substitute(&self, expression: &str) -> Result<String, NamespaceError> {
+        let mut result = String::new();
+        let mut rest = expression;
+        while let Some(start) = rest.find("${") {
+            let offset = expression.len() - rest.len() + start;
+            let error = |kind| {
+                NamespaceError::ExpressionEvaluationError(ExpressionError {
+                    expression: expression.to_string(),
+                    offset,
+                    kind,
+                })
+            };
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| error(ExpressionErrorKind::UnterminatedParameter))?;
+            let path = &rest[start + 2..start + end];
+            let value = fetch_variable(&self.namespace, &path.split('/').collect::<Vec<_>>())
+                .ok_or_else(|| error(ExpressionErrorKind::UnknownParameter(path.to_string())))?;
+            result.push_str(&rest[..start]);
+            result.push_str(&value.to_string());
+            rest = &rest[start + end + 1..];
         }
+        result.push_str(rest);
+        Ok(result)
     }
 
-    /// evaluate an expression in the namespace
+    /// evaluate a literal in the namespace; strings are returned as they are, with their
+    /// parameters substituted, everything else must be a valid expression
     pub fn eval_expression(&self, expression: &str) -> Result<Value, NamespaceError> {
-        let re = Regex::new(r"\$\{([A-Za-z0-9_/]+)\}").unwrap();
-
-        // Check and replace all placeholders first
-        let mut final_expression = String::from(expression);
-        for caps in re.captures_iter(expression) {
-            let path = &caps[1].split('/').collect::<Vec<_>>();
-            match self.get(path) {
-                Ok(value) => {
-                    final_expression = final_expression.replace(&caps[0], &value);
+        self.eval(expression).map(|(value, _)| value)
+    }
+
+    /// evaluate a literal that must be an integer, like a size
+    pub fn eval_integer(&self, expression: &str) -> Result<i64, NamespaceError> {
+        match self.eval(expression)? {
+            (Value::Integer(i), _) => Ok(i),
+            (value, offset) => Err(NamespaceError::ExpressionEvaluationError(ExpressionError {
+                expression: expression.to_string(),
+                offset,
+                kind: type_mismatch(format!(
+                    "expected an integer, found {} {}",
+                    value.type_name(),
+                    value
+                )),
+            })),
+        }
+    }
+
+    /// the value of a literal, with the offset of the expression or string it is
+    fn eval(&self, expression: &str) -> Result<(Value, usize), NamespaceError> {
+        let error = |offset, kind| {
+            NamespaceError::ExpressionEvaluationError(ExpressionError {
+                expression: expression.to_string(),
+                offset,
+                kind,
+            })
+        };
+        let trimmed = expression.trim_start();
+        let start = expression.len() - trimmed.len();
+        let is_string = match trimmed.chars().next() {
+            Some(c) => {
+                (c.is_alphabetic() || c == '_') && !matches!(trimmed.trim_end(), "true" | "false")
+            }
+            None => true,
+        };
+        if is_string {
+            let substituted = self.substitute(expression)?;
+            // a name must still be a name once its parameters are substituted
+            if let Some(offset) = expression.find("${") {
+                if is_name_template(expression) && !is_qualified_name(substituted.trim()) {
+                    return Err(error(offset, ExpressionErrorKind::InvalidName(substituted)));
                 }
-                Err(err) => return Err(err), // Return early if a variable could not be fetched
             }
+            return Ok((Value::String(substituted), start));
         }
 
-        // If all variables were successfully fetched, evaluate the expression
-        match eval(&final_expression) {
-            Ok(value) => Ok(value),
-            Err(_) => Ok(final_expression.into()), // ugh, this is not so good
+        let expr = parse::parse(expression).map_err(NamespaceError::ExpressionEvaluationError)?;
+        let value = self
+            .evaluate(&expr)
+            .map_err(|(offset, kind)| error(offset, kind))?;
+        Ok((value, expr.offset()))
+    }
+
+    /// evaluate a parsed expression
+    fn evaluate(&self, expr: &Expr) -> Result<Value, (usize, ExpressionErrorKind)> {
+        match expr {
+            Expr::Literal(value, _) => Ok(value.clone()),
+            Expr::Parameter(path, offset) => {
+                fetch_variable(&self.namespace, &path.split('/').collect::<Vec<_>>())
+                    .map(literal)
+                    .ok_or((*offset, ExpressionErrorKind::UnknownParameter(path.clone())))
+            }
+            Expr::Index(array, index, offset) => {
+                match (self.evaluate(array)?, self.evaluate(index)?) {
+                    (Value::Array(values), Value::Integer(i)) => usize::try_from(i)
+                        .ok()
+                        .and_then(|i| values.get(i).cloned())
+                        .ok_or((
+                            *offset,
+                            ExpressionErrorKind::IndexOutOfBounds(i, values.len()),
+                        )),
+                    (Value::Array(_), i) => Err((
+                        *offset,
+                        type_mismatch(format!(
+                            "index must be an integer, found {} {}",
+                            i.type_name(),
+                            i
+                        )),
+                    )),
+                    (v, _) => Err((
+                        *offset,
+                        type_mismatch(format!("{} {} can not be indexed", v.type_name(), v)),
+                    )),
+                }
+            }
+            Expr::Unary(op, operand, offset) => {
+                unary(*op, self.evaluate(operand)?).map_err(|kind| (*offset, kind))
+            }
+            Expr::Binary(op, lhs, rhs, offset) => {
+                binary(*op, self.evaluate(lhs)?, self.evaluate(rhs)?)
+                    .map_err(|kind| (*offset, kind))
+            }
         }
     }
 }
+
+/// true if a string is a name or a qualified name around its parameters, like
+/// `BASE_TYPES/uint${SIZE}`, rather than text like a description
+fn is_name_template(string: &str) -> bool {
+    let mut rest = string.trim();
+    let mut text = String::new();
+    while let Some(start) = rest.find("${") {
+        text.push_str(&rest[..start]);
+        match rest[start..].find('}') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => return false,
+        }
+    }
+    text.push_str(rest);
+    text.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/')
+}
+
+/// true if a string is a name qualified by packages, like `BASE_TYPES/uint32`
+fn is_qualified_name(string: &str) -> bool {
+    string.split('/').all(|name| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn type_mismatch(message: String) -> ExpressionErrorKind {
+    ExpressionErrorKind::TypeMismatch(message)
+}
+
+fn mismatch(op: BinaryOperator, a: &Value, b: &Value) -> ExpressionErrorKind {
+    type_mismatch(format!(
+        "{:?} of {} {} and {} {}",
+        op,
+        a.type_name(),
+        a,
+        b.type_name(),
+        b
+    ))
+}
+
+fn finite(value: f64) -> Result<Value, ExpressionErrorKind> {
+    if value.is_finite() {
+        Ok(Value::Float(value))
+    } else {
+        Err(ExpressionErrorKind::NonFiniteResult)
+    }
+}
+
+fn unary(op: UnaryOperator, value: Value) -> Result<Value, ExpressionErrorKind> {
+    match (op, value) {
+        (UnaryOperator::Negate, Value::Integer(i)) => i
+            .checked_neg()
+            .map(Value::Integer)
+            .ok_or(ExpressionErrorKind::Overflow),
+        (UnaryOperator::Negate, Value::Float(f)) => Ok(Value::Float(-f)),
+        (UnaryOperator::Plus, v @ (Value::Integer(_) | Value::Float(_))) => Ok(v),
+        (UnaryOperator::Not, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        (op, v) => Err(type_mismatch(format!(
+            "{:?} of {} {}",
+            op,
+            v.type_name(),
+            v
+        ))),
+    }
+}
+
+fn binary(op: BinaryOperator, a: Value, b: Value) -> Result<Value, ExpressionErrorKind> {
+    use BinaryOperator::*;
+
+    if let (And | Or, Value::Boolean(x), Value::Boolean(y)) = (op, &a, &b) {
+        return Ok(Value::Boolean(if op == And { *x && *y } else { *x || *y }));
+    }
+    if matches!(
+        op,
+        Equal | NotEqual | Less | LessEqual | Greater | GreaterEqual
+    ) {
+        let ordering = a.compare(&b).ok_or_else(|| {
+            type_mismatch(format!(
+                "can not compare {} and {}",
+                a.type_name(),
+                b.type_name()
+            ))
+        })?;
+        return Ok(Value::Boolean(match op {
+            Equal => ordering == Ordering::Equal,
+            NotEqual => ordering != Ordering::Equal,
+            Less => ordering == Ordering::Less,
+            LessEqual => ordering != Ordering::Greater,
+            Greater => ordering == Ordering::Greater,
+            _ => ordering != Ordering::Less,
+        }));
+    }
+
+    // integers stay exact; overflow is an error rather than a silent loss of precision
+    if let (Value::Integer(x), Value::Integer(y)) = (&a, &b) {
+        let (x, y) = (*x, *y);
+        let result = match op {
+            Add => x.checked_add(y),
+            Subtract => x.checked_sub(y),
+            Multiply => x.checked_mul(y),
+            Divide | Modulus if y == 0 => return Err(ExpressionErrorKind::DivisionByZero),
+            Divide if x % y != 0 => return finite(x as f64 / y as f64),
+            Divide => x.checked_div(y),
+            Modulus => x.checked_rem(y),
+            Power if y < 0 => return finite((x as f64).powf(y as f64)),
+            Power => u32::try_from(y).ok().and_then(|y| x.checked_pow(y)),
+            _ => return Err(mismatch(op, &a, &b)),
+        };
+        return result
+            .map(Value::Integer)
+            .ok_or(ExpressionErrorKind::Overflow);
+    }
+
+    let (x, y) = match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Err(mismatch(op, &a, &b)),
+    };
+    finite(match op {
+        Add => x + y,
+        Subtract => x - y,
+        Multiply => x * y,
+        Divide if y == 0.0 => return Err(ExpressionErrorKind::DivisionByZero),
+        Divide => x / y,
+        Modulus if y == 0.0 => return Err(ExpressionErrorKind::DivisionByZero),
+        Modulus => x % y,
+        Power => x.powf(y),
+        _ => return Err(mismatch(op, &a, &b)),
+    })
+}
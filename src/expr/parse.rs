@@ -0,0 +1,498 @@
+//! Parser for SEDS Literal Expressions
+//!
+//! Expressions combine literals and `${A/B}` mission parameters with the operators below,
+//! listed from lowest to highest precedence:
+//!
+//! | operators                  | meaning                     |
+//! |----------------------------|-----------------------------|
+//! | `\|\|`                     | logical or                  |
+//! | `&&`                       | logical and                 |
+//! | `==` `!=` `<` `<=` `>` `>=` | comparison (not chained)    |
+//! | `+` `-`                    | addition, subtraction       |
+//! | `*` `/` `%`                | multiplication, division, modulus |
+//! | `-` `+` `!` (prefix)       | negation, identity, not     |
+//! | `^`                        | power (right associative)   |
+//! | `[ ]` (suffix)             | array index                 |
+//!
+//! Literals are integers (decimal, or prefixed by `0x`, `0o` or `0b`), floats (`1.5`, `.5`,
+//! `2e-3`), `true`, `false` and double quoted strings. Parentheses group sub-expressions, and
+//! array parameters are indexed from zero with `${A/B}[i]`.
+use std::fmt;
+
+use crate::eds::value::Value;
+
+/// Kinds of errors found while parsing or evaluating an expression
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionErrorKind {
+    UnexpectedCharacter(char),
+    InvalidNumber(String),
+    UnterminatedString,
+    UnterminatedParameter,
+    InvalidParameter(String),
+    /// a token that can not appear at this position
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    UnknownParameter(String),
+    /// a name that is not valid once its parameters are substituted
+    InvalidName(String),
+    TypeMismatch(String),
+    IndexOutOfBounds(i64, usize),
+    DivisionByZero,
+    Overflow,
+    /// the result is infinite or not a number
+    NonFiniteResult,
+}
+
+impl fmt::Display for ExpressionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ExpressionErrorKind::*;
+        match self {
+            UnexpectedCharacter(c) => write!(f, "unexpected character `{}`", c),
+            InvalidNumber(n) => write!(f, "invalid number `{}`", n),
+            UnterminatedString => f.write_str("unterminated string"),
+            UnterminatedParameter => f.write_str("unterminated parameter, expected `}`"),
+            InvalidParameter(p) => write!(f, "invalid parameter name `{}`", p),
+            UnexpectedToken(t) => write!(f, "unexpected `{}`", t),
+            UnexpectedEnd => f.write_str("unexpected end of expression"),
+            UnknownParameter(p) => write!(f, "unknown parameter `{}`", p),
+            InvalidName(n) => write!(f, "`{}` is not a valid name", n),
+            TypeMismatch(msg) => write!(f, "type mismatch: {}", msg),
+            IndexOutOfBounds(index, len) => {
+                write!(
+                    f,
+                    "index {} is out of bounds for an array of {}",
+                    index, len
+                )
+            }
+            DivisionByZero => f.write_str("division by zero"),
+            Overflow => f.write_str("integer overflow"),
+            NonFiniteResult => f.write_str("result is not a finite number"),
+        }
+    }
+}
+
+/// An error in an expression, located by its byte offset
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionError {
+    /// the expression as written in the datasheet
+    pub expression: String,
+    /// byte offset of the offending token
+    pub offset: usize,
+    /// what went wrong
+    pub kind: ExpressionErrorKind,
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at offset {} in `{}`",
+            self.kind, self.offset, self.expression
+        )
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/// Prefix operators
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Negate,
+    Plus,
+    Not,
+}
+
+/// Infix operators
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulus,
+    Power,
+}
+
+/// A parsed expression; every node records the byte offset it was parsed from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// number, boolean or string literal
+    Literal(Value, usize),
+    /// `${A/B}` mission parameter path
+    Parameter(String, usize),
+    /// prefix operation
+    Unary(UnaryOperator, Box<Expr>, usize),
+    /// infix operation, located at the operator
+    Binary(BinaryOperator, Box<Expr>, Box<Expr>, usize),
+    /// `array[index]`, located at the bracket
+    Index(Box<Expr>, Box<Expr>, usize),
+}
+
+impl Expr {
+    /// byte offset of the node in the expression
+    pub fn offset(&self) -> usize {
+        match self {
+            Expr::Literal(_, offset)
+            | Expr::Parameter(_, offset)
+            | Expr::Unary(_, _, offset)
+            | Expr::Binary(_, _, _, offset)
+            | Expr::Index(_, _, offset) => *offset,
+        }
+    }
+
+    /// paths of the mission parameters the expression refers to, in order of appearance
+    pub fn parameters(&self) -> Vec<&str> {
+        match self {
+            Expr::Literal(..) => Vec::new(),
+            Expr::Parameter(path, _) => vec![path.as_str()],
+            Expr::Unary(_, operand, _) => operand.parameters(),
+            Expr::Binary(_, lhs, rhs, _) | Expr::Index(lhs, rhs, _) => {
+                let mut parameters = lhs.parameters();
+                parameters.extend(rhs.parameters());
+                parameters
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(Value),
+    Parameter(String),
+    Operator(&'static str),
+    Open,
+    Close,
+    OpenBracket,
+    CloseBracket,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Literal(Value::String(s)) => write!(f, "\"{}\"", s),
+            Token::Literal(v) => write!(f, "{}", v),
+            Token::Parameter(p) => write!(f, "${{{}}}", p),
+            Token::Operator(op) => f.write_str(op),
+            Token::Open => f.write_str("("),
+            Token::Close => f.write_str(")"),
+            Token::OpenBracket => f.write_str("["),
+            Token::CloseBracket => f.write_str("]"),
+        }
+    }
+}
+
+/// operators, longest first so that `<=` is not read as `<`
+const OPERATORS: [&str; 16] = [
+    "||", "&&", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "^", "!", "=",
+];
+
+type ParseResult<T> = Result<T, (usize, ExpressionErrorKind)>;
+
+fn tokenize(source: &str) -> ParseResult<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let offset = source.len() - rest.len();
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let (token, len) = if c.is_ascii_digit() || c == '.' {
+            let len = number_length(rest);
+            let text = &rest[..len];
+            match Value::parse(text) {
+                v @ (Value::Integer(_) | Value::Float(_)) => (Token::Literal(v), len),
+                _ => return Err((offset, ExpressionErrorKind::InvalidNumber(text.to_string()))),
+            }
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            match &rest[..len] {
+                "true" => (Token::Literal(Value::Boolean(true)), len),
+                "false" => (Token::Literal(Value::Boolean(false)), len),
+                word => {
+                    return Err((
+                        offset,
+                        ExpressionErrorKind::UnexpectedToken(word.to_string()),
+                    ))
+                }
+            }
+        } else if c == '"' {
+            match rest[1..].find('"') {
+                Some(end) => (Token::Literal(Value::from(&rest[1..end + 1])), end + 2),
+                None => return Err((offset, ExpressionErrorKind::UnterminatedString)),
+            }
+        } else if let Some(inner) = rest.strip_prefix("${") {
+            let end = inner
+                .find('}')
+                .ok_or((offset, ExpressionErrorKind::UnterminatedParameter))?;
+            let path = &inner[..end];
+            let valid = !path.is_empty()
+                && path.split('/').all(|part| {
+                    !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                });
+            if !valid {
+                return Err((
+                    offset,
+                    ExpressionErrorKind::InvalidParameter(path.to_string()),
+                ));
+            }
+            (Token::Parameter(path.to_string()), end + 3)
+        } else if c == '(' {
+            (Token::Open, 1)
+        } else if c == ')' {
+            (Token::Close, 1)
+        } else if c == '[' {
+            (Token::OpenBracket, 1)
+        } else if c == ']' {
+            (Token::CloseBracket, 1)
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            if *op == "=" {
+                // a single `=` is most likely a mistyped `==`
+                return Err((offset, ExpressionErrorKind::UnexpectedToken(op.to_string())));
+            }
+            (Token::Operator(op), op.len())
+        } else {
+            return Err((offset, ExpressionErrorKind::UnexpectedCharacter(c)));
+        };
+        tokens.push((token, offset));
+        rest = &rest[len..];
+    }
+    Ok(tokens)
+}
+
+/// length of the number at the start of `s`, including any trailing garbage like `16O`
+fn number_length(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let radix_prefix = bytes.len() > 1
+        && bytes[0] == b'0'
+        && matches!(bytes[1], b'x' | b'X' | b'o' | b'O' | b'b' | b'B');
+    let mut len = 0;
+    while len < bytes.len() {
+        let c = bytes[len];
+        let exponent_sign = !radix_prefix
+            && (c == b'+' || c == b'-')
+            && len > 0
+            && matches!(bytes[len - 1], b'e' | b'E');
+        if c.is_ascii_alphanumeric() || c == b'.' || c == b'_' || exponent_sign {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(t, _)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|(_, offset)| *offset)
+            .unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> ParseResult<(Token, usize)> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or((self.end, ExpressionErrorKind::UnexpectedEnd))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// consume one of the operators, if it is next
+    fn operator(
+        &mut self,
+        operators: &[(&str, BinaryOperator)],
+    ) -> Option<(BinaryOperator, usize)> {
+        let offset = self.offset();
+        match self.peek() {
+            Some(Token::Operator(op)) => {
+                let (_, operator) = operators.iter().find(|(o, _)| o == op)?;
+                self.position += 1;
+                Some((*operator, offset))
+            }
+            _ => None,
+        }
+    }
+
+    /// a left associative chain of operands at one precedence level
+    fn chain(
+        &mut self,
+        operators: &[(&str, BinaryOperator)],
+        operand: fn(&mut Self) -> ParseResult<Expr>,
+    ) -> ParseResult<Expr> {
+        let mut lhs = operand(self)?;
+        while let Some((op, offset)) = self.operator(operators) {
+            let rhs = operand(self)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs), offset);
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> ParseResult<Expr> {
+        self.chain(&[("||", BinaryOperator::Or)], Self::and)
+    }
+
+    fn and(&mut self) -> ParseResult<Expr> {
+        self.chain(&[("&&", BinaryOperator::And)], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> ParseResult<Expr> {
+        use BinaryOperator::*;
+        let operators = [
+            ("==", Equal),
+            ("!=", NotEqual),
+            ("<", Less),
+            ("<=", LessEqual),
+            (">", Greater),
+            (">=", GreaterEqual),
+        ];
+        let lhs = self.sum()?;
+        match self.operator(&operators) {
+            Some((op, offset)) => {
+                let rhs = self.sum()?;
+                if let Some(Token::Operator(next)) = self.peek() {
+                    if operators.iter().any(|(o, _)| o == next) {
+                        // `a < b < c` does not mean what it seems to
+                        return Err((
+                            self.offset(),
+                            ExpressionErrorKind::UnexpectedToken(next.to_string()),
+                        ));
+                    }
+                }
+                Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs), offset))
+            }
+            None => Ok(lhs),
+        }
+    }
+
+    fn sum(&mut self) -> ParseResult<Expr> {
+        self.chain(
+            &[("+", BinaryOperator::Add), ("-", BinaryOperator::Subtract)],
+            Self::product,
+        )
+    }
+
+    fn product(&mut self) -> ParseResult<Expr> {
+        self.chain(
+            &[
+                ("*", BinaryOperator::Multiply),
+                ("/", BinaryOperator::Divide),
+                ("%", BinaryOperator::Modulus),
+            ],
+            Self::unary,
+        )
+    }
+
+    fn unary(&mut self) -> ParseResult<Expr> {
+        let offset = self.offset();
+        let op = match self.peek() {
+            Some(Token::Operator("-")) => UnaryOperator::Negate,
+            Some(Token::Operator("+")) => UnaryOperator::Plus,
+            Some(Token::Operator("!")) => UnaryOperator::Not,
+            _ => return self.power(),
+        };
+        self.position += 1;
+        Ok(Expr::Unary(op, Box::new(self.unary()?), offset))
+    }
+
+    fn power(&mut self) -> ParseResult<Expr> {
+        let base = self.indexed()?;
+        match self.operator(&[("^", BinaryOperator::Power)]) {
+            // right associative, and binds tighter than a prefix operator on its left
+            Some((op, offset)) => Ok(Expr::Binary(
+                op,
+                Box::new(base),
+                Box::new(self.unary()?),
+                offset,
+            )),
+            None => Ok(base),
+        }
+    }
+
+    /// a primary followed by any number of `[index]` suffixes
+    fn indexed(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.primary()?;
+        while self.peek() == Some(&Token::OpenBracket) {
+            let offset = self.offset();
+            self.position += 1;
+            let index = self.or()?;
+            self.expect(Token::CloseBracket)?;
+            expr = Expr::Index(Box::new(expr), Box::new(index), offset);
+        }
+        Ok(expr)
+    }
+
+    fn expect(&mut self, expected: Token) -> ParseResult<()> {
+        match self.next()? {
+            (token, _) if token == expected => Ok(()),
+            (token, offset) => Err((
+                offset,
+                ExpressionErrorKind::UnexpectedToken(token.to_string()),
+            )),
+        }
+    }
+
+    fn primary(&mut self) -> ParseResult<Expr> {
+        let (token, offset) = self.next()?;
+        match token {
+            Token::Literal(value) => Ok(Expr::Literal(value, offset)),
+            Token::Parameter(path) => Ok(Expr::Parameter(path, offset)),
+            Token::Open => {
+                let inner = self.or()?;
+                self.expect(Token::Close)?;
+                Ok(inner)
+            }
+            token => Err((
+                offset,
+                ExpressionErrorKind::UnexpectedToken(token.to_string()),
+            )),
+        }
+    }
+}
+
+/// parse an expression
+pub fn parse(expression: &str) -> Result<Expr, ExpressionError> {
+    let error = |(offset, kind)| ExpressionError {
+        expression: expression.to_string(),
+        offset,
+        kind,
+    };
+    let mut parser = Parser {
+        tokens: tokenize(expression).map_err(error)?,
+        position: 0,
+        end: expression.trim_end().len(),
+    };
+    let expr = parser.or().map_err(error)?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(error((
+            parser.offset(),
+            ExpressionErrorKind::UnexpectedToken(token.to_string()),
+        ))),
+    }
+}
@@ -0,0 +1,108 @@
+//! Parameter Reports
+//!
+//! Lists the `${...}` parameters a set of datasheets refers to, without resolving them, and
+//! compares them with an [`ExpressionContext`]: parameters that are referenced but not defined
+//! are missing, parameters that are defined but never referenced are unused.
+//!
+//! Raw models are scanned through the elements they are written as, so every attribute and
+//! text is covered, including ones that are not expressions. Locations are the paths the
+//! flattening [`Writer`] reports, with element names qualified by their `name` attribute, or
+//! by their index among unnamed siblings after the first, e.g.
+//! `Package[SimpleDemo]/DataTypeSet/ContainerDataType[WriteMapInfoCmd]/EntryList/FixedValueEntry[SpacecraftID]@fixedValue`.
+use std::collections::BTreeSet;
+
+use super::ExpressionContext;
+use crate::write::{writer, Document, Writer};
+
+/// A reference to a parameter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterReference {
+    /// path of the parameter, e.g. `CFE_MISSION/MAX_PATH_LEN`
+    pub parameter: String,
+    /// file the reference was found in
+    pub file: String,
+    /// element and attribute of the reference within the file
+    pub location: String,
+}
+
+/// all parameter references of a raw model, e.g. a [`crate::eds::raw::PackageFile`]
+pub fn references<T: Document>(file: &str, model: &T) -> writer::Result<Vec<ParameterReference>> {
+    let mut references = Vec::new();
+    Writer::new()
+        .node(model)?
+        .values("", &mut |location, value| {
+            for parameter in placeholders(value) {
+                references.push(ParameterReference {
+                    parameter: parameter.to_string(),
+                    file: file.to_string(),
+                    location: location.clone(),
+                })
+            }
+        });
+    Ok(references)
+}
+
+/// Parameters referenced by datasheets, checked against a context
+#[derive(Debug, Clone, Default)]
+pub struct ParameterReport {
+    /// every reference, in the order they were given
+    pub references: Vec<ParameterReference>,
+    /// referenced parameters the context does not define, sorted
+    pub missing: Vec<String>,
+    /// parameters the context defines that are never referenced, sorted
+    pub unused: Vec<String>,
+}
+
+impl ParameterReport {
+    /// check the references against the parameters of a context
+    pub fn new(references: Vec<ParameterReference>, context: &ExpressionContext) -> Self {
+        let referenced: BTreeSet<&str> = references.iter().map(|r| r.parameter.as_str()).collect();
+        let missing = referenced
+            .iter()
+            .filter(|p| context.value(&p.split('/').collect::<Vec<_>>()).is_err())
+            .map(|p| p.to_string())
+            .collect();
+        let unused = context
+            .parameters()
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| !referenced.contains(path.as_str()))
+            .collect();
+        ParameterReport {
+            references,
+            missing,
+            unused,
+        }
+    }
+
+    /// the references to a parameter
+    pub fn references_to<'a>(
+        &'a self,
+        parameter: &'a str,
+    ) -> impl Iterator<Item = &'a ParameterReference> + 'a {
+        self.references
+            .iter()
+            .filter(move |r| r.parameter == parameter)
+    }
+
+    /// true when every referenced parameter is defined
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// the paths of the `${...}` placeholders in a text; unterminated ones are left to the resolver
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut paths = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        match rest[start..].find('}') {
+            Some(end) => {
+                paths.push(&rest[start + 2..start + end]);
+                rest = &rest[start + end + 1..];
+            }
+            None => break,
+        }
+    }
+    paths
+}
@@ -0,0 +1,308 @@
+//! Layered Parameter Sources
+//!
+//! Mission and design parameters are merged from several layers. Later layers override
+//! earlier ones, in the order of [`Layer`]:
+//!
+//! 1. mission files, shared by every build of the mission
+//! 2. target files, e.g. for the flight, flatsat or simulator build
+//! 3. environment variables, `<PREFIX>CFE_MISSION__MAX_PATH_LEN=64` sets `CFE_MISSION/MAX_PATH_LEN`
+//! 4. command line assignments, `--set CFE_MISSION/MAX_PATH_LEN=64`
+//!
+//! Files are JSON, TOML or YAML, chosen by their extension. Namespaces are merged key by key,
+//! values (including arrays) are replaced. Values given as text, in the environment or on the
+//! command line, are read as literals; a value starting with `[` must be a JSON array.
+//!
+//! The merged [`ExpressionContext`] records the source of every value, see
+//! [`ExpressionContext::source`].
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use super::{ExpressionContext, NamespaceError};
+
+/// A layer of parameters, from lowest to highest precedence
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Mission,
+    Target,
+    Environment,
+    CommandLine,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Layer::Mission => "mission",
+            Layer::Target => "target",
+            Layer::Environment => "environment",
+            Layer::CommandLine => "command line",
+        })
+    }
+}
+
+/// Where a parameter value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterSource {
+    /// layer of the source
+    pub layer: Layer,
+    /// file path, environment variable or command line assignment
+    pub origin: String,
+}
+
+impl fmt::Display for ParameterSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.layer, self.origin)
+    }
+}
+
+/// Format of a parameter file
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    /// format of a file, from its extension
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Format::Json),
+            "toml" => Some(Format::Toml),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur while loading parameters
+#[derive(Debug)]
+pub enum ParameterError {
+    /// a file could not be read
+    Io(String, std::io::Error),
+    /// the extension of a file is not .json, .toml, .yaml or .yml
+    UnknownFormat(String),
+    /// a file does not parse
+    Syntax {
+        /// file path
+        origin: String,
+        /// parser message
+        message: String,
+    },
+    /// an assignment is not of the form `KEY=VALUE`
+    InvalidAssignment(String),
+    /// a value would replace a namespace, or a namespace a value
+    Conflict {
+        /// path of the parameter
+        key: String,
+        /// the source that conflicts with an earlier one
+        source: ParameterSource,
+    },
+    /// a value is not a valid parameter
+    Value(NamespaceError),
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::Io(path, err) => write!(f, "can not read {}: {}", path, err),
+            ParameterError::UnknownFormat(path) => write!(
+                f,
+                "unknown parameter file format {}, expected .json, .toml, .yaml or .yml",
+                path
+            ),
+            ParameterError::Syntax { origin, message } => write!(f, "{}: {}", origin, message),
+            ParameterError::InvalidAssignment(a) => {
+                write!(f, "invalid assignment `{}`, expected KEY=VALUE", a)
+            }
+            ParameterError::Conflict { key, source } => write!(
+                f,
+                "`{}` from {} conflicts with an earlier layer: a namespace and a value can not replace each other",
+                key, source
+            ),
+            ParameterError::Value(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+/// Collects parameter layers and merges them into an [`ExpressionContext`]
+#[derive(Debug, Clone, Default)]
+pub struct ParameterLoader {
+    sources: Vec<(ParameterSource, JsonValue)>,
+}
+
+impl ParameterLoader {
+    /// create a loader without parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a parameter file to a layer
+    pub fn file(self, layer: Layer, path: &Path) -> Result<Self, ParameterError> {
+        let origin = path.display().to_string();
+        let format =
+            Format::from_path(path).ok_or_else(|| ParameterError::UnknownFormat(origin.clone()))?;
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| ParameterError::Io(origin.clone(), err))?;
+        self.document(layer, &origin, format, &contents)
+    }
+
+    /// add the contents of a parameter file to a layer; `origin` names it in errors and sources
+    pub fn document(
+        mut self,
+        layer: Layer,
+        origin: &str,
+        format: Format,
+        contents: &str,
+    ) -> Result<Self, ParameterError> {
+        let syntax = |message: String| ParameterError::Syntax {
+            origin: origin.to_string(),
+            message,
+        };
+        let json: JsonValue = match format {
+            Format::Json => serde_json::from_str(contents).map_err(|e| syntax(e.to_string()))?,
+            Format::Toml => toml::from_str(contents).map_err(|e| syntax(e.to_string()))?,
+            Format::Yaml => serde_yaml::from_str(contents).map_err(|e| syntax(e.to_string()))?,
+        };
+        if !json.is_object() {
+            return Err(syntax("parameters must be a mapping".to_string()));
+        }
+        self.sources.push((
+            ParameterSource {
+                layer,
+                origin: origin.to_string(),
+            },
+            json,
+        ));
+        Ok(self)
+    }
+
+    /// add the environment variables starting with `prefix`; `__` separates namespaces
+    pub fn environment(self, prefix: &str) -> Result<Self, ParameterError> {
+        self.variables(prefix, std::env::vars())
+    }
+
+    /// add variables as if they were set in the environment
+    pub fn variables(
+        mut self,
+        prefix: &str,
+        variables: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ParameterError> {
+        let mut variables: Vec<_> = variables
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(prefix) && name.len() > prefix.len())
+            .collect();
+        variables.sort();
+        for (name, value) in variables {
+            let key = name[prefix.len()..].replace("__", "/");
+            let json = assignment(&name, &key, &value)?;
+            self.sources.push((
+                ParameterSource {
+                    layer: Layer::Environment,
+                    origin: name,
+                },
+                json,
+            ));
+        }
+        Ok(self)
+    }
+
+    /// add a command line assignment `KEY=VALUE`, where KEY is a parameter path like `A/B`
+    pub fn set(mut self, assignment_text: &str) -> Result<Self, ParameterError> {
+        let (key, value) = assignment_text
+            .split_once('=')
+            .filter(|(key, _)| is_path(key.trim()))
+            .ok_or_else(|| ParameterError::InvalidAssignment(assignment_text.to_string()))?;
+        let json = assignment(assignment_text, key.trim(), value)?;
+        self.sources.push((
+            ParameterSource {
+                layer: Layer::CommandLine,
+                origin: assignment_text.to_string(),
+            },
+            json,
+        ));
+        Ok(self)
+    }
+
+    /// merge the layers, later sources of a layer overriding earlier ones
+    pub fn load(mut self) -> Result<ExpressionContext, ParameterError> {
+        // stable, so sources of the same layer keep their order
+        self.sources.sort_by_key(|(source, _)| source.layer);
+
+        let mut merged = Map::new();
+        let mut provenance = BTreeMap::new();
+        for (source, json) in self.sources.iter() {
+            if let JsonValue::Object(map) = json {
+                merge(&mut merged, map, "", source, &mut provenance)?;
+            }
+        }
+
+        let mut context = ExpressionContext::from_json(&JsonValue::Object(merged))
+            .map_err(ParameterError::Value)?;
+        context.sources = provenance;
+        Ok(context)
+    }
+}
+
+fn is_path(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// a nested object assigning a textual value to a path; a value starting with `[` must be a
+/// JSON array
+fn assignment(origin: &str, key: &str, value: &str) -> Result<JsonValue, ParameterError> {
+    let value = if value.trim_start().starts_with('[') {
+        serde_json::from_str(value).map_err(|e| ParameterError::Syntax {
+            origin: origin.to_string(),
+            message: e.to_string(),
+        })?
+    } else {
+        JsonValue::String(value.to_string())
+    };
+    Ok(key.rsplit('/').fold(value, |inner, name| {
+        let mut map = Map::new();
+        let _ = map.insert(name.to_string(), inner);
+        JsonValue::Object(map)
+    }))
+}
+
+fn merge(
+    target: &mut Map<String, JsonValue>,
+    layer: &Map<String, JsonValue>,
+    prefix: &str,
+    source: &ParameterSource,
+    provenance: &mut BTreeMap<String, ParameterSource>,
+) -> Result<(), ParameterError> {
+    for (name, value) in layer {
+        let key = format!("{}{}", prefix, name);
+        match (target.get_mut(name), value) {
+            (Some(JsonValue::Object(inner)), JsonValue::Object(map)) => {
+                merge(inner, map, &format!("{}/", key), source, provenance)?
+            }
+            (Some(JsonValue::Object(_)), _) | (Some(_), JsonValue::Object(_)) => {
+                return Err(ParameterError::Conflict {
+                    key,
+                    source: source.clone(),
+                })
+            }
+            (_, JsonValue::Object(map)) => {
+                let mut inner = Map::new();
+                merge(&mut inner, map, &format!("{}/", key), source, provenance)?;
+                let _ = target.insert(name.clone(), JsonValue::Object(inner));
+            }
+            (_, value) => {
+                let _ = target.insert(name.clone(), value.clone());
+                let _ = provenance.insert(key, source.clone());
+            }
+        }
+    }
+    Ok(())
+}
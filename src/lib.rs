@@ -10,10 +10,14 @@
 #![warn(unused_imports)]
 #![allow(missing_copy_implementations)]
 #![deny(missing_docs)]
-#[macro_use]
-extern crate prettytable;
 
 pub mod codegen;
+pub mod compat;
+pub mod diagnostic;
 pub mod eds;
 pub mod expr;
+pub mod lint;
 pub mod parse;
+pub mod sim;
+pub mod validate;
+pub mod write;
@@ -0,0 +1,400 @@
+//! Built-in Lints
+//!
+//! | id | level | reports |
+//! |----|-------|---------|
+//! | `missing-short-description` | warn | data types and entries without a `shortDescription` |
+//! | `misaligned-container` | warn | containers whose size is not a whole number of bytes |
+//! | `duplicate-enumeration-value` | warn | labels of an enumeration with the same value |
+//! | `non-contiguous-enumeration` | warn | enumerations whose values have gaps |
+//! | `range-exceeds-encoding` | warn | integer ranges that do not fit the encoding |
+//! | `unused-data-type` | warn | data types that no other type, component or activity uses |
+//!
+//! Unused types are only meaningful for a complete set of datasheets, and the models do not
+//! keep declared interfaces, so the types only they use are reported too.
+use std::collections::{BTreeSet, HashSet};
+
+use crate::diagnostic::Diagnostic;
+use crate::eds::ast::{
+    DataType, EntryElement, EnumeratedDataType, IntegerDataType, IntegerEncoding, MinMaxRangeType,
+    Package, PackageFile, QualifiedName,
+};
+use crate::eds::value::Value;
+use crate::eds::visit::{Path, Visitor};
+
+use super::{Lint, LintContext};
+
+/// the built-in lints, in the order they run
+pub fn builtin() -> Vec<Box<dyn Lint>> {
+    vec![
+        Box::new(MissingShortDescription),
+        Box::new(MisalignedContainer),
+        Box::new(DuplicateEnumerationValue),
+        Box::new(NonContiguousEnumeration),
+        Box::new(RangeExceedsEncoding),
+        Box::new(UnusedDataType),
+    ]
+}
+
+/// path of a data type
+fn data_type_path(package: &Package, data_type: &DataType) -> String {
+    format!(
+        "Package[{}]/DataTypeSet/{}[{}]",
+        package.name_entity_type.name.0,
+        data_type.element(),
+        data_type.name()
+    )
+}
+
+/// the data types of a package file, with their packages
+fn data_types(package_file: &PackageFile) -> impl Iterator<Item = (&Package, &DataType)> {
+    package_file.package.iter().flat_map(|package| {
+        package
+            .data_type_set
+            .data_types
+            .iter()
+            .map(move |data_type| (package, data_type))
+    })
+}
+
+/// the enumerated data types of a package file, with their paths
+fn enumerations(package_file: &PackageFile) -> impl Iterator<Item = (String, &EnumeratedDataType)> {
+    data_types(package_file).filter_map(|(package, data_type)| match data_type {
+        DataType::EnumeratedDataType(dt) => Some((data_type_path(package, data_type), dt)),
+        _ => None,
+    })
+}
+
+/// true if a description is missing or blank
+fn is_blank(description: &Option<String>) -> bool {
+    description.as_deref().is_none_or(|d| d.trim().is_empty())
+}
+
+/// Data types and entries without a `shortDescription`
+#[derive(Debug, Clone, Copy)]
+pub struct MissingShortDescription;
+
+impl Lint for MissingShortDescription {
+    fn id(&self) -> &'static str {
+        "missing-short-description"
+    }
+
+    fn description(&self) -> &'static str {
+        "data types and entries without a shortDescription"
+    }
+
+    fn check<'a>(&self, _cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (package, data_type) in data_types(package_file) {
+            let path = data_type_path(package, data_type);
+            let named = match data_type {
+                DataType::NoneDataType => continue,
+                DataType::BooleanDataType(dt) => &dt.name_entity_type,
+                DataType::IntegerDataType(dt) => &dt.name_entity_type,
+                DataType::ArrayDataType(dt) => &dt.name_entity_type,
+                DataType::EnumeratedDataType(dt) => &dt.name_entity_type,
+                DataType::ContainerDataType(dt) => &dt.name_entity_type,
+                DataType::FloatDataType(dt) => &dt.name_entity_type,
+                DataType::StringDataType(dt) => &dt.name_entity_type,
+                DataType::SubRangeDataType(dt) => &dt.name_entity_type,
+            };
+            if is_blank(&named.short_description) {
+                let message = format!("data type `{}` has no shortDescription", data_type.name());
+                diagnostics.push(Diagnostic::error(message).at(&path));
+            }
+            let DataType::ContainerDataType(container) = data_type else {
+                continue;
+            };
+            let lists = [
+                ("EntryList", &container.entry_list),
+                ("TrailerEntryList", &container.trailer_entry_list),
+            ];
+            for (list, entries) in lists {
+                for entry in entries.iter().flat_map(|l| l.entries.iter()) {
+                    let description = match entry {
+                        EntryElement::Entry(e) => &e.name_entity_type,
+                        EntryElement::FixedValueEntry(e) => &e.name_entity_type,
+                        EntryElement::ListEntry(e) => &e.name_entity_type,
+                        EntryElement::LengthEntry(e) => &e.name_entity_type,
+                        EntryElement::ErrorControlEntry(e) => &e.name_entity_type,
+                        EntryElement::PaddingEntry(_) => continue,
+                    };
+                    if is_blank(&description.short_description) {
+                        let message = format!(
+                            "entry `{}` of `{}` has no shortDescription",
+                            entry.name(),
+                            data_type.name()
+                        );
+                        let path =
+                            format!("{}/{}/{}[{}]", path, list, entry.element(), entry.name());
+                        diagnostics.push(Diagnostic::error(message).at(path));
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Containers whose size, with their base containers, is not a whole number of bytes
+#[derive(Debug, Clone, Copy)]
+pub struct MisalignedContainer;
+
+impl Lint for MisalignedContainer {
+    fn id(&self) -> &'static str {
+        "misaligned-container"
+    }
+
+    fn description(&self) -> &'static str {
+        "containers whose size is not a whole number of bytes"
+    }
+
+    fn check<'a>(&self, cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (package, data_type) in data_types(package_file) {
+            let container = match data_type {
+                // abstract containers are completed by the containers derived from them
+                DataType::ContainerDataType(dt) if !dt._abstract => dt,
+                _ => continue,
+            };
+            // containers of a variable size are not checked
+            let Some(size) = cx.container_size_in_bits(package, container) else {
+                continue;
+            };
+            if size % 8 != 0 {
+                let message = format!(
+                    "container `{}` is {} bits long, {} bits short of a whole byte",
+                    data_type.name(),
+                    size,
+                    8 - size % 8
+                );
+                diagnostics.push(
+                    Diagnostic::error(message)
+                        .at(data_type_path(package, data_type))
+                        .with_note("add a PaddingEntry to align the container"),
+                );
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Labels of an enumeration with the same value
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateEnumerationValue;
+
+impl Lint for DuplicateEnumerationValue {
+    fn id(&self) -> &'static str {
+        "duplicate-enumeration-value"
+    }
+
+    fn description(&self) -> &'static str {
+        "labels of an enumeration with the same value"
+    }
+
+    fn check<'a>(&self, _cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (path, dt) in enumerations(package_file) {
+            let labels = &dt.enumeration_list.enumeration;
+            for (i, enumeration) in labels.iter().enumerate() {
+                let value = Value::parse(&enumeration.value.0);
+                let first = labels[..i].iter().find(|other| {
+                    Value::parse(&other.value.0).compare(&value) == Some(std::cmp::Ordering::Equal)
+                });
+                if let Some(first) = first {
+                    let message = format!(
+                        "labels `{}` and `{}` of `{}` have the same value {}",
+                        first.label.0, enumeration.label.0, dt.name_entity_type.name.0, value
+                    );
+                    let path = format!("{}/EnumerationList/Enumeration[{}]@value", path, i);
+                    diagnostics.push(Diagnostic::error(message).at(path));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Enumerations whose values have gaps
+#[derive(Debug, Clone, Copy)]
+pub struct NonContiguousEnumeration;
+
+impl Lint for NonContiguousEnumeration {
+    fn id(&self) -> &'static str {
+        "non-contiguous-enumeration"
+    }
+
+    fn description(&self) -> &'static str {
+        "enumerations whose values have gaps"
+    }
+
+    fn check<'a>(&self, _cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (path, dt) in enumerations(package_file) {
+            let values: BTreeSet<i64> = dt
+                .enumeration_list
+                .enumeration
+                .iter()
+                .filter_map(|e| Value::parse(&e.value.0).as_i64())
+                .collect();
+            let values: Vec<i64> = values.into_iter().collect();
+            let gaps: Vec<String> = values
+                .windows(2)
+                .filter(|pair| pair[1] - pair[0] > 1)
+                .map(|pair| match (pair[0] + 1, pair[1] - 1) {
+                    (from, to) if from == to => from.to_string(),
+                    (from, to) => format!("{} to {}", from, to),
+                })
+                .collect();
+            if !gaps.is_empty() {
+                let message = format!(
+                    "values of `{}` are not contiguous, {} missing",
+                    dt.name_entity_type.name.0,
+                    gaps.join(", ")
+                );
+                diagnostics.push(Diagnostic::error(message).at(path));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Integer ranges with bounds that the encoding of the type can not represent
+#[derive(Debug, Clone, Copy)]
+pub struct RangeExceedsEncoding;
+
+impl Lint for RangeExceedsEncoding {
+    fn id(&self) -> &'static str {
+        "range-exceeds-encoding"
+    }
+
+    fn description(&self) -> &'static str {
+        "integer ranges that do not fit the encoding of the type"
+    }
+
+    fn check<'a>(&self, _cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (package, data_type) in data_types(package_file) {
+            let DataType::IntegerDataType(dt) = data_type else {
+                continue;
+            };
+            let Some((low, high)) = encodable(dt) else {
+                continue;
+            };
+            let (min, max) = bounds(dt);
+            let path = format!("{}/Range/MinMaxRange", data_type_path(package, data_type));
+            for (bound, attribute) in [(min, "min"), (max, "max")] {
+                match bound {
+                    Some(bound) if bound < low as f64 || bound > high as f64 => {
+                        let message = format!(
+                            "{} {} of `{}` does not fit {} {} bits, which hold {} to {}",
+                            attribute,
+                            bound,
+                            data_type.name(),
+                            dt.encoding.size_in_bits,
+                            dt.encoding.encoding,
+                            low,
+                            high
+                        );
+                        let path = format!("{}@{}", path, attribute);
+                        diagnostics.push(Diagnostic::error(message).at(path));
+                    }
+                    _ => (),
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// the smallest and largest values of the encoding of an integer type
+fn encodable(dt: &IntegerDataType) -> Option<(i128, i128)> {
+    let bits = dt.encoding.size_in_bits as u32;
+    if bits == 0 || bits > 64 {
+        return None;
+    }
+    let half = 1i128 << (bits - 1);
+    Some(match dt.encoding.encoding {
+        IntegerEncoding::Unsigned => (0, (1i128 << bits) - 1),
+        IntegerEncoding::TwosComplement => (-half, half - 1),
+        IntegerEncoding::SignMagnitude | IntegerEncoding::OnesComplement => (-(half - 1), half - 1),
+        IntegerEncoding::BinaryCodedDecimal => (0, 10i128.pow(bits / 4) - 1),
+    })
+}
+
+/// the smallest and largest integers of the range of a type, if it is bounded
+fn bounds(dt: &IntegerDataType) -> (Option<f64>, Option<f64>) {
+    let range = &dt.range.min_max_range;
+    let min = Value::parse(&range.min.0).as_f64();
+    let max = Value::parse(&range.max.0).as_f64();
+    let (min_inclusive, max_inclusive) = match range.range_type {
+        MinMaxRangeType::ExclusiveMinExclusiveMax => (Some(false), Some(false)),
+        MinMaxRangeType::InclusiveMinInclusiveMax => (Some(true), Some(true)),
+        MinMaxRangeType::InclusiveMinExclusiveMax => (Some(true), Some(false)),
+        MinMaxRangeType::ExclusiveMinInclusiveMax => (Some(false), Some(true)),
+        MinMaxRangeType::GreaterThan => (Some(false), None),
+        MinMaxRangeType::AtLeast => (Some(true), None),
+        MinMaxRangeType::LessThan => (None, Some(false)),
+        MinMaxRangeType::AtMost => (None, Some(true)),
+    };
+    let min = match (min, min_inclusive) {
+        (Some(min), Some(true)) => Some(min.ceil()),
+        (Some(min), Some(false)) => Some(min.floor() + 1.0),
+        _ => None,
+    };
+    let max = match (max, max_inclusive) {
+        (Some(max), Some(true)) => Some(max.floor()),
+        (Some(max), Some(false)) => Some(max.ceil() - 1.0),
+        _ => None,
+    };
+    (min, max)
+}
+
+/// Data types that no other type, component or activity of the linted files uses
+#[derive(Debug, Clone, Copy)]
+pub struct UnusedDataType;
+
+impl Lint for UnusedDataType {
+    fn id(&self) -> &'static str {
+        "unused-data-type"
+    }
+
+    fn description(&self) -> &'static str {
+        "data types that no other type, component or activity uses"
+    }
+
+    fn check<'a>(&self, cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic> {
+        // types by package and name
+        let mut used: HashSet<(&str, &str)> = HashSet::new();
+        for package in cx.packages() {
+            for name in references(package) {
+                if let Some((p, data_type)) = cx.lookup(package, &name.0) {
+                    let _ = used.insert((&p.name_entity_type.name.0, data_type.name()));
+                }
+            }
+        }
+        data_types(package_file)
+            .filter(|(package, data_type)| {
+                !used.contains(&(&package.name_entity_type.name.0, data_type.name()))
+            })
+            .map(|(package, data_type)| {
+                let message = format!("data type `{}` is never used", data_type.name());
+                Diagnostic::error(message).at(data_type_path(package, data_type))
+            })
+            .collect()
+    }
+}
+
+/// the names of the types a package refers to
+fn references(package: &Package) -> Vec<&QualifiedName> {
+    struct References<'a>(Vec<&'a QualifiedName>);
+
+    impl<'a> Visitor<'a> for References<'a> {
+        fn visit_type_ref(&mut self, _: &Path, type_ref: &'a QualifiedName) {
+            self.0.push(type_ref);
+        }
+    }
+
+    let mut references = References(Vec::new());
+    references.visit_package(&Path::default(), package);
+    references.0
+}
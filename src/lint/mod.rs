@@ -0,0 +1,294 @@
+//! Lints of Datasheets
+//!
+//! Lints report quality issues of resolved package files that are not violations of the
+//! standard, like data types without a `shortDescription` or containers that do not end on a
+//! byte boundary. Each lint has an id and a [`Level`], which can be changed per lint, or for
+//! all of them with the id `all`:
+//!
+//! ```
+//! use seds_rs::lint::{Level, Linter};
+//!
+//! let mut linter = Linter::new();
+//! linter.set_level("missing-short-description", Level::Allow).unwrap();
+//! linter.set_level("unused-data-type", Level::Deny).unwrap();
+//! assert_eq!(linter.level("misaligned-container"), Some(Level::Warn));
+//! ```
+//!
+//! The built-in lints are in [`lints`]; others implement [`Lint`] and are added with
+//! [`Linter::with_lint`]. Findings are diagnostics located by path, like the errors of the
+//! resolver, and can be written as JSON for tools.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::eds::ast::{ContainerDataType, DataType, Package, PackageFile};
+use crate::eds::layout::Layouts;
+
+/// the built-in lints
+pub mod lints;
+
+/// the id that sets the level of every lint
+const ALL: &str = "all";
+
+/// Level of a lint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// the lint is not run
+    Allow,
+    /// findings are warnings
+    Warn,
+    /// findings are errors
+    Deny,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Allow => "allow",
+            Level::Warn => "warn",
+            Level::Deny => "deny",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = LintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(LintError::InvalidLevel(s.to_string())),
+        }
+    }
+}
+
+/// Errors of the configuration of a [`Linter`]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintError {
+    UnknownLint(String),
+    InvalidLevel(String),
+}
+
+impl fmt::Display for LintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintError::UnknownLint(id) => write!(f, "unknown lint `{}`", id),
+            LintError::InvalidLevel(level) => write!(
+                f,
+                "invalid lint level `{}`, expected allow, warn or deny",
+                level
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LintError {}
+
+/// A check of package files
+pub trait Lint: Send + Sync {
+    /// the id of the lint, in kebab case like `missing-short-description`
+    fn id(&self) -> &'static str;
+
+    /// what the lint reports
+    fn description(&self) -> &'static str;
+
+    /// the level of the lint unless it is configured
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    /// the issues of a package file, as diagnostics with a path within the file; the
+    /// severity is set from the level of the lint
+    fn check<'a>(&self, cx: &LintContext<'a>, package_file: &'a PackageFile) -> Vec<Diagnostic>;
+}
+
+/// The package files being linted, to look up the types that lints refer to
+#[derive(Debug, Clone)]
+pub struct LintContext<'a> {
+    layouts: Layouts<'a>,
+}
+
+impl<'a> LintContext<'a> {
+    /// the context of a set of package files
+    pub fn new(package_files: &[&'a PackageFile]) -> Self {
+        LintContext {
+            layouts: Layouts::new(package_files),
+        }
+    }
+
+    /// the packages of all the files
+    pub fn packages(&self) -> &[&'a Package] {
+        self.layouts.packages()
+    }
+
+    /// the layouts of the containers of all the files
+    pub fn layouts(&self) -> &Layouts<'a> {
+        &self.layouts
+    }
+
+    /// a data type by its qualified name, relative to the package it is referenced from
+    pub fn lookup(&self, package: &'a Package, name: &str) -> Option<(&'a Package, &'a DataType)> {
+        self.layouts.lookup(package, name)
+    }
+
+    /// the encoded size of a data type, if it is fixed
+    pub fn size_in_bits(&self, package: &'a Package, data_type: &'a DataType) -> Option<usize> {
+        self.layouts.encoding(package, data_type).ok()?.1
+    }
+
+    /// the encoded size of a container with the entries of its base containers and trailers,
+    /// if it is fixed
+    pub fn container_size_in_bits(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+    ) -> Option<usize> {
+        self.layouts
+            .container(package, container)
+            .ok()?
+            .size_in_bits
+    }
+}
+
+/// An issue reported by a lint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// id of the lint
+    pub lint: &'static str,
+    /// level of the lint, warn or deny
+    pub level: Level,
+    /// index of the package file the issue is in, among the linted files
+    pub file: usize,
+    /// the issue, located by path in its file
+    pub diagnostic: Diagnostic,
+}
+
+impl Finding {
+    /// the finding as a JSON object, with the position of the diagnostic if it was located
+    pub fn to_json(&self) -> serde_json::Value {
+        let span = self.diagnostic.span.as_ref();
+        serde_json::json!({
+            "lint": self.lint,
+            "level": self.level.to_string(),
+            "message": self.diagnostic.message,
+            "file": self.diagnostic.file,
+            "line": span.map(|span| span.line),
+            "column": span.map(|span| span.column),
+            "path": self.diagnostic.path,
+        })
+    }
+}
+
+/// The lints to run, with their levels
+pub struct Linter {
+    lints: Vec<Box<dyn Lint>>,
+    levels: BTreeMap<&'static str, Level>,
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Linter")
+            .field("levels", &self.levels)
+            .finish()
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// a linter with the built-in lints at their default levels
+    pub fn new() -> Self {
+        lints::builtin()
+            .into_iter()
+            .fold(Self::empty(), |linter, lint| linter.with_boxed(lint))
+    }
+
+    /// a linter without lints
+    pub fn empty() -> Self {
+        Linter {
+            lints: Vec::new(),
+            levels: BTreeMap::new(),
+        }
+    }
+
+    /// add a lint at its default level, it replaces a lint with the same id
+    pub fn with_lint(self, lint: impl Lint + 'static) -> Self {
+        self.with_boxed(Box::new(lint))
+    }
+
+    fn with_boxed(mut self, lint: Box<dyn Lint>) -> Self {
+        let _ = self.levels.insert(lint.id(), lint.default_level());
+        self.lints.retain(|l| l.id() != lint.id());
+        self.lints.push(lint);
+        self
+    }
+
+    /// set the level of a lint, or of all of them with `all`
+    pub fn set_level(&mut self, id: &str, level: Level) -> Result<(), LintError> {
+        if id == ALL {
+            self.levels.values_mut().for_each(|l| *l = level);
+            return Ok(());
+        }
+        match self.levels.get_mut(id) {
+            Some(l) => {
+                *l = level;
+                Ok(())
+            }
+            None => Err(LintError::UnknownLint(id.to_string())),
+        }
+    }
+
+    /// the level of a lint
+    pub fn level(&self, id: &str) -> Option<Level> {
+        self.levels.get(id).copied()
+    }
+
+    /// the lints with their levels, in the order they run
+    pub fn lints(&self) -> impl Iterator<Item = (&dyn Lint, Level)> {
+        self.lints
+            .iter()
+            .map(|lint| (lint.as_ref(), self.levels[lint.id()]))
+    }
+
+    /// run the lints that are not allowed over package files, the findings are returned by
+    /// file, then by lint
+    pub fn check(&self, package_files: &[&PackageFile]) -> Vec<Finding> {
+        let cx = LintContext::new(package_files);
+        let mut findings = Vec::new();
+        for (file, package_file) in package_files.iter().enumerate() {
+            for (lint, level) in self.lints() {
+                if level == Level::Allow {
+                    continue;
+                }
+                for diagnostic in lint.check(&cx, package_file) {
+                    let diagnostic = Diagnostic {
+                        severity: match level {
+                            Level::Deny => Severity::Error,
+                            _ => Severity::Warning,
+                        },
+                        ..diagnostic
+                    };
+                    findings.push(Finding {
+                        lint: lint.id(),
+                        level,
+                        file,
+                        diagnostic: diagnostic.with_note(format!(
+                            "lint `{}` is set to {}",
+                            lint.id(),
+                            level
+                        )),
+                    });
+                }
+            }
+        }
+        findings
+    }
+}
@@ -0,0 +1,1386 @@
+//! [`FromXml`] implementations of the raw EDS models
+//!
+//! Attributes are read first, then the child elements in document order. A child that may
+//! appear once is read with [`Element::single`], repeated children with [`Element::push`].
+use crate::eds::raw::{
+    Activity, ActivityArgument, ActivityInvocation, ActivitySet, ArgumentValue, ArrayDataType,
+    Assignment, Body, BooleanDataEncoding, BooleanDataType, BooleanExpression, Calibration,
+    Comparison, Component, ComponentSet, Conditional, Constraint, ConstraintSet, ContainerDataType,
+    DataSheet, DataType, DataTypeSet, Device, Dimension, DimensionList, Entry, EntryElement,
+    EntryList, EntryState, EnumeratedDataType, Enumeration, EnumerationList, ErrorControlEntry,
+    ExitState, Extensions, FixedValueEntry, FloatDataEncoding, FloatDataType, GenericTypeMap,
+    GenericTypeMapSet, Implementation, IntegerDataEncoding, IntegerDataType, Interface, Iteration,
+    LengthEntry, ListEntry, LongDescription, MathElement, MathOperation, MathOperator, MetaData,
+    MetadataCategory, MetadataValue, MetadataValueSet, MinMaxRange, NamedEntityType,
+    NumericMetadataValue, OnCommandPrimitive, OnParameterPrimitive, OnTimer, Operand, Package,
+    PackageFile, PaddingEntry, ParameterMap, ParameterMapSet, PolynomialCalibrator, Range,
+    RangeConstraint, RequiredInterfaceSet, Semantics, SendCommandPrimitive, SendParameterPrimitive,
+    SinkArgumentValue, SplineCalibrator, SplinePoint, State, StateMachine, StateMachineElement,
+    StateMachineSet, Statement, StringDataEncoding, StringDataType, StringMetadataValue,
+    SubRangeDataType, Term, Transition, TypeCheck, TypeConstraint, ValueConstraint, ValueOperand,
+    Variable, VariableRefOperand, VariableSet,
+};
+
+use super::reader::{Element, FromXml, Result};
+
+/// the attributes of a NamedEntityType; its LongDescription is read by [`describe`], its
+/// extension elements are added by [`extended`]
+fn named_entity(element: &mut Element) -> Result<NamedEntityType> {
+    Ok(NamedEntityType {
+        name: element.required("name")?,
+        short_description: element.attribute("shortDescription"),
+        long_description: None,
+        semantics: semantics(element),
+        extensions: Extensions {
+            attributes: element.extension_attributes(),
+            elements: Vec::new(),
+        },
+    })
+}
+
+/// add the extension elements of a NamedEntityType, once its children are read
+fn extended(element: &mut Element, mut named: NamedEntityType) -> NamedEntityType {
+    named.extensions.elements = element.extension_elements();
+    named
+}
+
+/// read the LongDescription of a NamedEntityType
+fn describe(child: &mut Element, named: &mut NamedEntityType) -> Result<bool> {
+    match child.name() {
+        "LongDescription" => child.single(&mut named.long_description),
+        _ => Ok(false),
+    }
+}
+
+fn semantics(element: &mut Element) -> Semantics {
+    Semantics {
+        quantity_kind: element.attribute("quantityKind"),
+        unit: element.attribute("unit"),
+        chirality: element.attribute("chirality"),
+        coordinate_type: element.attribute("coordinateType"),
+        to_coordinate_type: element.attribute("toCoordinateType"),
+        difference_convention: element.attribute("differenceConvention"),
+        interpretation: element.attribute("interpretation"),
+        purpose: element.attribute("purpose"),
+        reference_frame: element.attribute("referenceFrame"),
+        to_frame: element.attribute("toFrame"),
+        relation_to_timestamp: element.attribute("relationToTimestamp"),
+        status_convention: element.attribute("statusConvention"),
+        transformation_type: element.attribute("transformationType"),
+    }
+}
+
+/// read the LongDescription of an element without other children
+fn described(element: &mut Element, mut named: NamedEntityType) -> Result<NamedEntityType> {
+    element.children(|child| describe(child, &mut named))?;
+    Ok(extended(element, named))
+}
+
+/// a NamedEntityType without other children
+fn described_only(element: &mut Element) -> Result<NamedEntityType> {
+    let named = named_entity(element)?;
+    described(element, named)
+}
+
+impl FromXml for String {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        element.text()
+    }
+}
+
+impl<T: FromXml> FromXml for Box<T> {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        element.read().map(Box::new)
+    }
+}
+
+impl FromXml for LongDescription {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(LongDescription {
+            text: element.text()?,
+        })
+    }
+}
+
+impl FromXml for DataSheet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut sheet = DataSheet::default();
+        element.children(|child| match child.name() {
+            "Device" => child.push(&mut sheet.devices),
+            "Package" => child.push(&mut sheet.packages),
+            _ => Ok(false),
+        })?;
+        Ok(sheet)
+    }
+}
+
+impl FromXml for Device {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut metadata = None;
+        element.children(|child| match child.name() {
+            "Metadata" | "MetaData" => child.single(&mut metadata),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(Device {
+            name_entity_type: extended(element, named),
+            metadata,
+        })
+    }
+}
+
+impl FromXml for MetaData {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut metadata = MetaData::default();
+        element.children(|child| match child.name() {
+            "CreationDate" => child.single(&mut metadata.creation_date),
+            "Creator" => child.single(&mut metadata.creator),
+            "MetadataValueSet" => child.single(&mut metadata.value_set),
+            "Category" => child.push(&mut metadata.categories),
+            _ => Ok(false),
+        })?;
+        Ok(metadata)
+    }
+}
+
+impl FromXml for MetadataCategory {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut value_set = None;
+        let mut categories = Vec::new();
+        element.children(|child| match child.name() {
+            "MetadataValueSet" => child.single(&mut value_set),
+            "Category" => child.push(&mut categories),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(MetadataCategory {
+            name_entity_type: extended(element, named),
+            value_set,
+            categories,
+        })
+    }
+}
+
+impl FromXml for MetadataValueSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut values = Vec::new();
+        element.children(|child| {
+            let value = match child.name() {
+                "DateValue" => MetadataValue::DateValue(child.read()?),
+                "FloatValue" => MetadataValue::FloatValue(child.read()?),
+                "IntegerValue" => MetadataValue::IntegerValue(child.read()?),
+                "StringValue" => MetadataValue::StringValue(child.read()?),
+                _ => return Ok(false),
+            };
+            values.push(value);
+            Ok(true)
+        })?;
+        Ok(MetadataValueSet { values })
+    }
+}
+
+impl FromXml for StringMetadataValue {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let value = element.required("value")?;
+        element.children(|child| describe(child, &mut named))?;
+        Ok(StringMetadataValue {
+            name_entity_type: extended(element, named),
+            value,
+        })
+    }
+}
+
+impl FromXml for NumericMetadataValue {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let value = element.attribute("value");
+        let mut math_operation = None;
+        let mut conditional = None;
+        element.children(|child| match child.name() {
+            "MathOperation" => child.single(&mut math_operation),
+            "Conditional" => child.single(&mut conditional),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(NumericMetadataValue {
+            name_entity_type: extended(element, named),
+            value,
+            math_operation,
+            conditional,
+        })
+    }
+}
+
+impl FromXml for PackageFile {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut package_file = PackageFile::default();
+        element.children(|child| match child.name() {
+            "Package" => child.push(&mut package_file.package),
+            "Metadata" | "MetaData" => child.single(&mut package_file.metadata),
+            // a DataSheet read as a package file, its device is not part of the model
+            "Device" => child.read::<Device>().map(|_| true),
+            _ => Ok(false),
+        })?;
+        Ok(package_file)
+    }
+}
+
+impl FromXml for Package {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut data_type_set = None;
+        let mut component_set = None;
+        element.children(|child| match child.name() {
+            "DataTypeSet" => child.single(&mut data_type_set),
+            "ComponentSet" => child.single(&mut component_set),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(Package {
+            name_entity_type: extended(element, named),
+            data_type_set,
+            component_set,
+        })
+    }
+}
+
+impl FromXml for DataTypeSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut data_types = Vec::new();
+        element.children(|child| {
+            let data_type = match child.name() {
+                "IntegerDataType" => DataType::IntegerDataType(child.read()?),
+                "BooleanDataType" => DataType::BooleanDataType(child.read()?),
+                "ContainerDataType" => DataType::ContainerDataType(child.read()?),
+                "EnumeratedDataType" => DataType::EnumeratedDataType(child.read()?),
+                "ArrayDataType" => DataType::ArrayDataType(child.read()?),
+                "FloatDataType" => DataType::FloatDataType(child.read()?),
+                "StringDataType" => DataType::StringDataType(child.read()?),
+                "SubRangeDataType" => DataType::SubRangeDataType(child.read()?),
+                _ => return Ok(false),
+            };
+            data_types.push(data_type);
+            Ok(true)
+        })?;
+        Ok(DataTypeSet { data_types })
+    }
+}
+
+impl FromXml for EnumeratedDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut encoding = None;
+        let mut enumeration_list = None;
+        element.children(|child| match child.name() {
+            "IntegerDataEncoding" => child.single(&mut encoding),
+            "EnumerationList" => child.single(&mut enumeration_list),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(EnumeratedDataType {
+            name_entity_type: extended(element, named),
+            encoding,
+            enumeration_list: enumeration_list.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for EnumerationList {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut enumeration = Vec::new();
+        element.children(|child| match child.name() {
+            "Enumeration" => child.push(&mut enumeration),
+            _ => Ok(false),
+        })?;
+        Ok(EnumerationList { enumeration })
+    }
+}
+
+impl FromXml for Enumeration {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut enumeration = Enumeration {
+            label: element.attribute("label").unwrap_or_default(),
+            value: element.attribute("value").unwrap_or_default(),
+            short_description: element.attribute("shortDescription"),
+            semantics: semantics(element),
+            extensions: Extensions {
+                attributes: element.extension_attributes(),
+                elements: Vec::new(),
+            },
+        };
+        // the label is the name of an enumeration, its description is not part of the model
+        element.children(|child| match child.name() {
+            "LongDescription" => child.ignore(),
+            _ => Ok(false),
+        })?;
+        enumeration.extensions.elements = element.extension_elements();
+        Ok(enumeration)
+    }
+}
+
+impl FromXml for ContainerDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let _abstract = element.attribute("abstract");
+        let base_type = element.attribute("baseType");
+        let mut entry_list = None;
+        let mut constraint_set = None;
+        let mut trailer_entry_list = None;
+        element.children(|child| match child.name() {
+            "EntryList" => child.single(&mut entry_list),
+            "ConstraintSet" => child.single(&mut constraint_set),
+            "TrailerEntryList" => child.single(&mut trailer_entry_list),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(ContainerDataType {
+            name_entity_type: extended(element, named),
+            entry_list,
+            _abstract,
+            base_type,
+            constraint_set,
+            trailer_entry_list,
+        })
+    }
+}
+
+impl FromXml for ConstraintSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut constraints = Vec::new();
+        element.children(|child| {
+            let constraint = match child.name() {
+                "RangeConstraint" => Constraint::RangeConstraint(child.read()?),
+                "TypeConstraint" => Constraint::TypeConstraint(child.read()?),
+                "ValueConstraint" => Constraint::ValueConstraint(child.read()?),
+                _ => return Ok(false),
+            };
+            constraints.push(constraint);
+            Ok(true)
+        })?;
+        Ok(ConstraintSet { constraints })
+    }
+}
+
+impl FromXml for RangeConstraint {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let entry = element.required("entry")?;
+        let mut range = None;
+        let mut min_max_range = None;
+        element.children(|child| match child.name() {
+            "Range" => child.single(&mut range),
+            // the schema has the range of a constraint without a Range element
+            "MinMaxRange" => child.single(&mut min_max_range),
+            _ => Ok(false),
+        })?;
+        let range = range.or(min_max_range.map(|min_max_range| Range {
+            min_max_range,
+            precision_range: None,
+        }));
+        Ok(RangeConstraint {
+            range: range.unwrap_or_default(),
+            entry,
+        })
+    }
+}
+
+impl FromXml for TypeConstraint {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(TypeConstraint {
+            type_: element.required("type")?,
+            entry: element.required("entry")?,
+        })
+    }
+}
+
+impl FromXml for ValueConstraint {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(ValueConstraint {
+            value: element.required("value")?,
+            entry: element.required("entry")?,
+        })
+    }
+}
+
+impl FromXml for EntryList {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut entries = Vec::new();
+        element.children(|child| {
+            let entry = match child.name() {
+                "Entry" => EntryElement::Entry(child.read()?),
+                "PaddingEntry" => EntryElement::PaddingEntry(child.read()?),
+                "LengthEntry" => EntryElement::LengthEntry(child.read()?),
+                "ErrorControlEntry" => EntryElement::ErrorControlEntry(child.read()?),
+                "FixedValueEntry" => EntryElement::FixedValueEntry(child.read()?),
+                "ListEntry" => EntryElement::ListEntry(child.read()?),
+                _ => return Ok(false),
+            };
+            entries.push(entry);
+            Ok(true)
+        })?;
+        Ok(EntryList { entries })
+    }
+}
+
+impl FromXml for Entry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let named = named_entity(element)?;
+        let type_ = element.required("type")?;
+        let name_entity_type = described(element, named)?;
+        Ok(Entry {
+            name_entity_type,
+            type_,
+        })
+    }
+}
+
+impl FromXml for PaddingEntry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(PaddingEntry {
+            size_in_bits: element.required("sizeInBits")?,
+            short_description: element.attribute("shortDescription"),
+        })
+    }
+}
+
+impl FromXml for LengthEntry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let type_ = element.required("type")?;
+        let mut calibration = None;
+        element.children(|child| match child.name() {
+            "PolynomialCalibrator" => child.single(&mut calibration),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(LengthEntry {
+            name_entity_type: extended(element, named),
+            type_,
+            calibration,
+        })
+    }
+}
+
+impl FromXml for ErrorControlEntry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let named = named_entity(element)?;
+        let type_ = element.required("type")?;
+        let error_control_type = element.required("errorControlType")?;
+        Ok(ErrorControlEntry {
+            name_entity_type: described(element, named)?,
+            type_,
+            error_control_type,
+        })
+    }
+}
+
+impl FromXml for FixedValueEntry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let named = named_entity(element)?;
+        let type_ = element.required("type")?;
+        let fixed_value = element.required("fixedValue")?;
+        Ok(FixedValueEntry {
+            name_entity_type: described(element, named)?,
+            type_,
+            fixed_value,
+        })
+    }
+}
+
+impl FromXml for ListEntry {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let named = named_entity(element)?;
+        let type_ = element.attribute("type").unwrap_or_default();
+        let list_length_field = element.required("listLengthField")?;
+        Ok(ListEntry {
+            name_entity_type: described(element, named)?,
+            type_,
+            list_length_field,
+        })
+    }
+}
+
+impl FromXml for ArrayDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let data_type_ref = element.attribute("dataTypeRef").unwrap_or_default();
+        let mut dimension_list = None;
+        element.children(|child| match child.name() {
+            "DimensionList" => child.single(&mut dimension_list),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(ArrayDataType {
+            name_entity_type: extended(element, named),
+            data_type_ref,
+            dimension_list: dimension_list.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for DimensionList {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut dimension = Vec::new();
+        element.children(|child| match child.name() {
+            "Dimension" => child.push(&mut dimension),
+            _ => Ok(false),
+        })?;
+        Ok(DimensionList { dimension })
+    }
+}
+
+impl FromXml for Dimension {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(Dimension {
+            size: element.attribute("size").unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for BooleanDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut encoding = None;
+        element.children(|child| match child.name() {
+            "BooleanDataEncoding" => child.single(&mut encoding),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(BooleanDataType {
+            name_entity_type: extended(element, named),
+            encoding,
+        })
+    }
+}
+
+impl FromXml for BooleanDataEncoding {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(BooleanDataEncoding {
+            size_in_bits: element.attribute("sizeInBits").unwrap_or_default(),
+            false_value: element.attribute("falseValue"),
+        })
+    }
+}
+
+impl FromXml for IntegerDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut polynomial_calibrator = None;
+        let mut spline_calibrator = None;
+        let mut encoding = None;
+        let mut range = None;
+        element.children(|child| match child.name() {
+            "PolynomialCalibrator" => child.single(&mut polynomial_calibrator),
+            "SplineCalibrator" => child.single(&mut spline_calibrator),
+            "IntegerDataEncoding" => child.single(&mut encoding),
+            "Range" => child.single(&mut range),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(IntegerDataType {
+            name_entity_type: extended(element, named),
+            polynomial_calibrator,
+            spline_calibrator,
+            encoding,
+            range: range.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for IntegerDataEncoding {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(IntegerDataEncoding {
+            size_in_bits: element.attribute("sizeInBits").unwrap_or_default(),
+            encoding: element.attribute("encoding").unwrap_or_default(),
+            byte_order: element.attribute("byteOrder"),
+        })
+    }
+}
+
+impl FromXml for Range {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut min_max_range = None;
+        let mut precision_range = None;
+        element.children(|child| match child.name() {
+            "MinMaxRange" => child.single(&mut min_max_range),
+            "PrecisionRange" => child.single(&mut precision_range),
+            _ => Ok(false),
+        })?;
+        Ok(Range {
+            min_max_range: min_max_range.unwrap_or_default(),
+            precision_range,
+        })
+    }
+}
+
+impl FromXml for MinMaxRange {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(MinMaxRange {
+            max: element.attribute("max").unwrap_or_default(),
+            min: element.attribute("min").unwrap_or_default(),
+            range_type: element.attribute("rangeType").unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for FloatDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut polynomial_calibrator = None;
+        let mut spline_calibrator = None;
+        let mut encoding = None;
+        let mut range = None;
+        element.children(|child| match child.name() {
+            "PolynomialCalibrator" => child.single(&mut polynomial_calibrator),
+            "SplineCalibrator" => child.single(&mut spline_calibrator),
+            "FloatDataEncoding" => child.single(&mut encoding),
+            "Range" => child.single(&mut range),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(FloatDataType {
+            name_entity_type: extended(element, named),
+            polynomial_calibrator,
+            spline_calibrator,
+            encoding,
+            range,
+        })
+    }
+}
+
+impl FromXml for FloatDataEncoding {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(FloatDataEncoding {
+            size_in_bits: element.attribute("sizeInBits").unwrap_or_default(),
+            byte_order: element.attribute("byteOrder"),
+            encoding_and_precision: element
+                .attribute("encodingAndPrecision")
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for StringDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let length = element.required("length")?;
+        let fixed_length = element.attribute("fixedLength");
+        let mut encoding = None;
+        element.children(|child| match child.name() {
+            "StringDataEncoding" => child.single(&mut encoding),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(StringDataType {
+            name_entity_type: extended(element, named),
+            length,
+            encoding,
+            fixed_length,
+        })
+    }
+}
+
+impl FromXml for StringDataEncoding {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(StringDataEncoding {
+            termination_character: element.attribute("terminationCharacter"),
+            encoding: element.attribute("encoding"),
+        })
+    }
+}
+
+impl FromXml for SubRangeDataType {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let base_type = element.required("baseType")?;
+        let encoding = element.attribute("encoding");
+        let mut range = None;
+        element.children(|child| match child.name() {
+            "Range" => child.single(&mut range),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(SubRangeDataType {
+            base_type,
+            name_entity_type: extended(element, named),
+            range: range.unwrap_or_default(),
+            encoding,
+        })
+    }
+}
+
+impl FromXml for ComponentSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut components = Vec::new();
+        element.children(|child| match child.name() {
+            "Component" => child.push(&mut components),
+            _ => Ok(false),
+        })?;
+        Ok(ComponentSet { components })
+    }
+}
+
+impl FromXml for Component {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let name = element.required("name")?;
+        let _ = element.attribute("shortDescription");
+        let mut required_interface_set = None;
+        let mut implementation = None;
+        element.children(|child| match child.name() {
+            "RequiredInterfaceSet" => child.single(&mut required_interface_set),
+            "Implementation" => child.single(&mut implementation),
+            "LongDescription" => child.ignore(),
+            _ => Ok(false),
+        })?;
+        Ok(Component {
+            name,
+            required_interface_set: required_interface_set.unwrap_or_default(),
+            implementation: implementation.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for RequiredInterfaceSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut interfaces = Vec::new();
+        element.children(|child| match child.name() {
+            "Interface" => child.push(&mut interfaces),
+            _ => Ok(false),
+        })?;
+        Ok(RequiredInterfaceSet { interfaces })
+    }
+}
+
+impl FromXml for Interface {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let name = element.required("name")?;
+        let type_ = element.required("type")?;
+        let short_description = element.attribute("shortDescription");
+        let mut generic_type_map_set = None;
+        element.children(|child| match child.name() {
+            "GenericTypeMapSet" => child.single(&mut generic_type_map_set),
+            "LongDescription" => child.ignore(),
+            _ => Ok(false),
+        })?;
+        Ok(Interface {
+            name,
+            type_,
+            short_description,
+            generic_type_map_set: generic_type_map_set.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for GenericTypeMapSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut generic_type_maps = Vec::new();
+        element.children(|child| match child.name() {
+            "GenericTypeMap" => child.push(&mut generic_type_maps),
+            _ => Ok(false),
+        })?;
+        Ok(GenericTypeMapSet { generic_type_maps })
+    }
+}
+
+impl FromXml for GenericTypeMap {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(GenericTypeMap {
+            name: element.required("name")?,
+            type_: element.required("type")?,
+        })
+    }
+}
+
+impl FromXml for Implementation {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut variable_set = None;
+        let mut parameter_map_set = None;
+        let mut activity_set = None;
+        let mut state_machine_set = None;
+        element.children(|child| match child.name() {
+            "VariableSet" => child.single(&mut variable_set),
+            "ParameterMapSet" => child.single(&mut parameter_map_set),
+            "ActivitySet" => child.single(&mut activity_set),
+            "StateMachineSet" => child.single(&mut state_machine_set),
+            _ => Ok(false),
+        })?;
+        Ok(Implementation {
+            variable_set: variable_set.unwrap_or_default(),
+            parameter_map_set: parameter_map_set.unwrap_or_default(),
+            activity_set: activity_set.unwrap_or_default(),
+            state_machine_set: state_machine_set.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromXml for VariableSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut variables = Vec::new();
+        element.children(|child| match child.name() {
+            "Variable" => child.push(&mut variables),
+            _ => Ok(false),
+        })?;
+        Ok(VariableSet { variables })
+    }
+}
+
+impl FromXml for Variable {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let variable = Variable {
+            type_: element.required("type")?,
+            read_only: element.flag("readOnly")?,
+            name: element.required("name")?,
+            initial_value: element.attribute("initialValue"),
+        };
+        let _ = element.attribute("shortDescription");
+        element.children(|child| match child.name() {
+            "LongDescription" => child.ignore(),
+            _ => Ok(false),
+        })?;
+        Ok(variable)
+    }
+}
+
+impl FromXml for ParameterMapSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut parameter_maps = Vec::new();
+        element.children(|child| match child.name() {
+            "ParameterMap" => child.push(&mut parameter_maps),
+            _ => Ok(false),
+        })?;
+        Ok(ParameterMapSet { parameter_maps })
+    }
+}
+
+impl FromXml for ParameterMap {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(ParameterMap {
+            interface: element.required("interface")?,
+            parameter: element.required("parameter")?,
+            variable_ref: element.required("variableRef")?,
+        })
+    }
+}
+
+impl FromXml for StateMachineSet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut state_machines = Vec::new();
+        element.children(|child| match child.name() {
+            "StateMachine" => child.push(&mut state_machines),
+            _ => Ok(false),
+        })?;
+        Ok(StateMachineSet { state_machines })
+    }
+}
+
+impl FromXml for StateMachine {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut elements = Vec::new();
+        element.children(|child| {
+            let element = match child.name() {
+                "EntryState" => StateMachineElement::EntryState(child.read()?),
+                "ExitState" => StateMachineElement::ExitState(child.read()?),
+                "State" => StateMachineElement::State(child.read()?),
+                "Transition" => StateMachineElement::Transition(child.read()?),
+                _ => return describe(child, &mut named),
+            };
+            elements.push(element);
+            Ok(true)
+        })?;
+        Ok(StateMachine {
+            name_entity_type: extended(element, named),
+            elements,
+        })
+    }
+}
+
+impl FromXml for EntryState {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(EntryState {
+            name_entity_type: described_only(element)?,
+        })
+    }
+}
+
+impl FromXml for ExitState {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(ExitState {
+            name_entity_type: described_only(element)?,
+        })
+    }
+}
+
+impl FromXml for State {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut on_entry = None;
+        let mut on_exit = None;
+        element.children(|child| match child.name() {
+            "OnEntry" => child.single(&mut on_entry),
+            "OnExit" => child.single(&mut on_exit),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(State {
+            name_entity_type: extended(element, named),
+            on_entry,
+            on_exit,
+        })
+    }
+}
+
+impl FromXml for Transition {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut transition = Transition {
+            from_state: element.required("fromState")?,
+            to_state: element.required("toState")?,
+            ..Transition::default()
+        };
+        element.children(|child| match child.name() {
+            "OnCommandPrimitive" => child.single(&mut transition.on_command_primitive),
+            "OnParameterPrimitive" => child.single(&mut transition.on_parameter_primitive),
+            "OnTimer" => child.single(&mut transition.on_timer),
+            "Guard" => child.single(&mut transition.guard),
+            "Do" => child.single(&mut transition.do_),
+            _ => describe(child, &mut named),
+        })?;
+        transition.name_entity_type = extended(element, named);
+        Ok(transition)
+    }
+}
+
+impl FromXml for ActivityInvocation {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let activity = element.required("activity")?;
+        let mut argument_values = Vec::new();
+        element.children(|child| match child.name() {
+            "ArgumentValue" => child.push(&mut argument_values),
+            _ => Ok(false),
+        })?;
+        Ok(ActivityInvocation {
+            activity,
+            argument_values,
+        })
+    }
+}
+
+impl FromXml for ArgumentValue {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let name = element.required("name")?;
+        let mut variable_ref = None;
+        let mut value = None;
+        element.children(|child| match child.name() {
+            "VariableRef" => child.single(&mut variable_ref),
+            "Value" => child.single(&mut value),
+            _ => Ok(false),
+        })?;
+        Ok(ArgumentValue {
+            name,
+            variable_ref,
+            value,
+        })
+    }
+}
+
+impl FromXml for VariableRefOperand {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(VariableRefOperand {
+            variable_ref: element.required("variableRef")?,
+        })
+    }
+}
+
+impl FromXml for ValueOperand {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(ValueOperand {
+            value: element.required("value")?,
+        })
+    }
+}
+
+impl FromXml for OnCommandPrimitive {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let interface = element.required("interface")?;
+        let command = element.required("command")?;
+        let transaction = element.attribute("transaction");
+        let mut argument_values = Vec::new();
+        element.children(|child| match child.name() {
+            "ArgumentValue" => child.push(&mut argument_values),
+            _ => Ok(false),
+        })?;
+        Ok(OnCommandPrimitive {
+            interface,
+            command,
+            transaction,
+            argument_values,
+        })
+    }
+}
+
+impl FromXml for SinkArgumentValue {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(SinkArgumentValue {
+            name: element.required("name")?,
+            output_variable_ref: element.required("outputVariableRef")?,
+        })
+    }
+}
+
+impl FromXml for OnParameterPrimitive {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let interface = element.required("interface")?;
+        let parameter = element.required("parameter")?;
+        let operation = element.required("operation")?;
+        let transaction = element.attribute("transaction");
+        let mut variable_ref: Option<VariableRefOperand> = None;
+        element.children(|child| match child.name() {
+            "VariableRef" => child.single(&mut variable_ref),
+            _ => Ok(false),
+        })?;
+        Ok(OnParameterPrimitive {
+            interface,
+            parameter,
+            operation,
+            transaction,
+            variable_ref: variable_ref.map(|operand| operand.variable_ref),
+        })
+    }
+}
+
+impl FromXml for OnTimer {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(OnTimer {
+            nanoseconds_after_entry: element.attribute("nanosecondsAfterEntry"),
+        })
+    }
+}
+
+impl FromXml for ActivitySet {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut activities = Vec::new();
+        element.children(|child| match child.name() {
+            "Activity" => child.push(&mut activities),
+            _ => Ok(false),
+        })?;
+        Ok(ActivitySet { activities })
+    }
+}
+
+impl FromXml for Activity {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut named = named_entity(element)?;
+        let mut arguments = Vec::new();
+        let mut body = None;
+        element.children(|child| match child.name() {
+            "Argument" => child.push(&mut arguments),
+            "Body" => child.single(&mut body),
+            _ => describe(child, &mut named),
+        })?;
+        Ok(Activity {
+            name_entity_type: extended(element, named),
+            arguments,
+            body: element.expect(body, "Body")?,
+        })
+    }
+}
+
+impl FromXml for ActivityArgument {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let named = named_entity(element)?;
+        let type_ = element.required("type")?;
+        Ok(ActivityArgument {
+            name_entity_type: described(element, named)?,
+            type_,
+        })
+    }
+}
+
+impl FromXml for Body {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut statements = Vec::new();
+        element.children(|child| {
+            let statement = match child.name() {
+                "Assignment" => Statement::Assignment(child.read()?),
+                "Conditional" => Statement::Conditional(child.read()?),
+                "Iteration" => Statement::Iteration(child.read()?),
+                "Calibration" => Statement::Calibration(child.read()?),
+                "SendParameterPrimitive" => Statement::SendParameterPrimitive(child.read()?),
+                "SendCommandPrimitive" => Statement::SendCommandPrimitive(child.read()?),
+                "MathOperation" => Statement::MathOperation(child.read()?),
+                "Call" => Statement::Call(child.read()?),
+                _ => return Ok(false),
+            };
+            statements.push(statement);
+            Ok(true)
+        })?;
+        Ok(Body { statements })
+    }
+}
+
+impl FromXml for Operand {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut operand = Operand::default();
+        element.children(|child| match child.name() {
+            "VariableRef" => child.single(&mut operand.variable_ref),
+            "Value" => child.single(&mut operand.value),
+            _ => Ok(false),
+        })?;
+        Ok(operand)
+    }
+}
+
+impl FromXml for Assignment {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let output_variable_ref = element.required("outputVariableRef")?;
+        let mut variable_ref = None;
+        let mut value = None;
+        element.children(|child| match child.name() {
+            "VariableRef" => child.single(&mut variable_ref),
+            "Value" => child.single(&mut value),
+            _ => Ok(false),
+        })?;
+        Ok(Assignment {
+            output_variable_ref,
+            variable_ref,
+            value,
+        })
+    }
+}
+
+impl FromXml for Conditional {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut condition = None;
+        let mut on_condition_true = None;
+        let mut on_condition_false = None;
+        element.children(|child| match child.name() {
+            "Condition" => child.single(&mut condition),
+            "OnConditionTrue" => child.single(&mut on_condition_true),
+            "OnConditionFalse" => child.single(&mut on_condition_false),
+            _ => Ok(false),
+        })?;
+        Ok(Conditional {
+            condition: element.expect(condition, "Condition")?,
+            on_condition_true,
+            on_condition_false,
+        })
+    }
+}
+
+/// the conditions within a boolean expression or a list of ANDed or ORed conditions
+fn conditions(element: &mut Element) -> Result<Vec<BooleanExpression>> {
+    let mut expressions = Vec::new();
+    element.children(|child| {
+        let expression = match child.name() {
+            "Condition" => BooleanExpression::Condition(child.read()?),
+            "ANDedConditions" => BooleanExpression::ANDedConditions(conditions(child)?),
+            "ORedConditions" => BooleanExpression::ORedConditions(conditions(child)?),
+            "TypeCondition" => BooleanExpression::TypeCondition(child.read()?),
+            _ => return Ok(false),
+        };
+        expressions.push(expression);
+        Ok(true)
+    })?;
+    Ok(expressions)
+}
+
+impl FromXml for BooleanExpression {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut expressions = conditions(element)?;
+        match expressions.len() {
+            1 => Ok(expressions.remove(0)),
+            0 => Err(element.error(format!("missing condition in `{}`", element.name()))),
+            _ => Err(element.error("a boolean expression contains exactly one condition")),
+        }
+    }
+}
+
+impl FromXml for Comparison {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut first_operand = None;
+        let mut comparison_operator = None;
+        let mut second_operand = None;
+        let mut value = None;
+        element.children(|child| match child.name() {
+            "FirstOperand" => child.single(&mut first_operand),
+            "ComparisonOperator" => child.single(&mut comparison_operator),
+            "SecondOperand" => child.single(&mut second_operand),
+            "Value" => child.single(&mut value),
+            _ => Ok(false),
+        })?;
+        Ok(Comparison {
+            first_operand: element.expect(first_operand, "FirstOperand")?,
+            comparison_operator: element.expect(comparison_operator, "ComparisonOperator")?,
+            second_operand,
+            value,
+        })
+    }
+}
+
+impl FromXml for TypeCheck {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut first_operand = None;
+        let mut type_operand = None;
+        element.children(|child| match child.name() {
+            "FirstOperand" => child.single(&mut first_operand),
+            "TypeOperand" => child.single(&mut type_operand),
+            _ => Ok(false),
+        })?;
+        Ok(TypeCheck {
+            first_operand: element.expect(first_operand, "FirstOperand")?,
+            type_operand: element.expect(type_operand, "TypeOperand")?,
+        })
+    }
+}
+
+impl FromXml for Iteration {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let iterator_variable_ref = element.required("iteratorVariableRef")?;
+        let mut over_array = None;
+        let mut start_at = None;
+        let mut step = None;
+        let mut end_at = None;
+        let mut do_ = None;
+        element.children(|child| match child.name() {
+            "OverArray" => child.single(&mut over_array),
+            "StartAt" => child.single(&mut start_at),
+            "Step" => child.single(&mut step),
+            "EndAt" => child.single(&mut end_at),
+            "Do" => child.single(&mut do_),
+            _ => Ok(false),
+        })?;
+        Ok(Iteration {
+            iterator_variable_ref,
+            over_array,
+            start_at,
+            step,
+            end_at,
+            do_: element.expect(do_, "Do")?,
+        })
+    }
+}
+
+impl FromXml for Calibration {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let output_variable_ref = element.required("outputVariableRef")?;
+        let input_variable_ref = element.required("inputVariableRef")?;
+        let mut polynomial_calibrator = None;
+        let mut spline_calibrator = None;
+        element.children(|child| match child.name() {
+            "PolynomialCalibrator" => child.single(&mut polynomial_calibrator),
+            "SplineCalibrator" => child.single(&mut spline_calibrator),
+            _ => Ok(false),
+        })?;
+        Ok(Calibration {
+            output_variable_ref,
+            input_variable_ref,
+            polynomial_calibrator,
+            spline_calibrator,
+        })
+    }
+}
+
+impl FromXml for SendCommandPrimitive {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let interface = element.required("interface")?;
+        let command = element.required("command")?;
+        let transaction = element.attribute("transaction");
+        let mut argument_values = Vec::new();
+        element.children(|child| match child.name() {
+            "ArgumentValue" => child.push(&mut argument_values),
+            _ => Ok(false),
+        })?;
+        Ok(SendCommandPrimitive {
+            interface,
+            command,
+            transaction,
+            argument_values,
+        })
+    }
+}
+
+impl FromXml for SendParameterPrimitive {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let interface = element.required("interface")?;
+        let parameter = element.required("parameter")?;
+        let operation = element.required("operation")?;
+        let transaction = element.attribute("transaction");
+        let mut argument_value = None;
+        element.children(|child| match child.name() {
+            "ArgumentValue" => child.single(&mut argument_value),
+            _ => Ok(false),
+        })?;
+        Ok(SendParameterPrimitive {
+            interface,
+            parameter,
+            operation,
+            transaction,
+            argument_value,
+        })
+    }
+}
+
+impl FromXml for MathOperation {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let output_variable_ref = element.required("outputVariableRef")?;
+        let _ = element.attribute("shortDescription");
+        let mut elements = Vec::new();
+        element.children(|child| {
+            let element = match child.name() {
+                "Value" => MathElement::Value(child.read()?),
+                "VariableRef" => MathElement::VariableRef(child.read()?),
+                "Operator" => MathElement::Operator(child.read()?),
+                "LongDescription" => return child.ignore(),
+                _ => return Ok(false),
+            };
+            elements.push(element);
+            Ok(true)
+        })?;
+        Ok(MathOperation {
+            output_variable_ref,
+            elements,
+        })
+    }
+}
+
+impl FromXml for MathOperator {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(MathOperator {
+            operator: element.required("operator")?,
+        })
+    }
+}
+
+impl FromXml for PolynomialCalibrator {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let mut term = Vec::new();
+        element.children(|child| match child.name() {
+            "Term" => child.push(&mut term),
+            _ => Ok(false),
+        })?;
+        Ok(PolynomialCalibrator { term })
+    }
+}
+
+impl FromXml for SplineCalibrator {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        let extrapolate = element.attribute("extrapolate");
+        let mut spline_point = Vec::new();
+        element.children(|child| match child.name() {
+            "SplinePoint" => child.push(&mut spline_point),
+            _ => Ok(false),
+        })?;
+        Ok(SplineCalibrator {
+            extrapolate,
+            spline_point,
+        })
+    }
+}
+
+impl FromXml for SplinePoint {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(SplinePoint {
+            order: element.attribute("order"),
+            raw: element.required("raw")?,
+            calibrated: element.required("calibrated")?,
+        })
+    }
+}
+
+impl FromXml for Term {
+    fn from_xml(element: &mut Element) -> Result<Self> {
+        Ok(Term {
+            coefficient: element.required("coefficient")?,
+            exponent: element.required("exponent")?,
+        })
+    }
+}
@@ -1,3 +1,9 @@
-//! XML parsing trait implementations for SEDS-rs
-//! This is mainly manual implementations of serde visitors and deserialization
-pub mod deserialize;
+//! XML parsing for SEDS-rs
+//!
+//! Datasheets are read by a streaming, namespace-aware [`Parser`].
+/// [`FromXml`] implementations of the raw models
+pub mod elements;
+/// the streaming reader
+pub mod reader;
+
+pub use reader::{Element, FromXml, Parsed, Parser, Strictness, SEDS_NAMESPACE};
@@ -0,0 +1,696 @@
+//! Streaming, namespace-aware reader of datasheets
+//!
+//! The models are read straight from the events of an XML pull parser, in document order and
+//! without building intermediate maps. Each model implements [`FromXml`]: it takes the
+//! attributes it knows from its [`Element`] and then walks the child elements. Whatever is left
+//! over is either unknown SEDS content or an extension in a foreign namespace, and is reported
+//! according to the [`Strictness`] of the [`Parser`]. Models that carry the extensions of user
+//! DoTs (see [`crate::eds::extension`]) take them with [`Element::extension_attributes`] and
+//! [`Element::extension_elements`] instead.
+//!
+//! Elements without a namespace are read as SEDS elements, so both
+//! `<seds:Package xmlns:seds="http://www.ccsds.org/schema/sois/seds">` and a plain `<Package>`
+//! work.
+//!
+//! `xi:include` elements are replaced by the element they include (Req 3.2.4), e.g. the Package
+//! of a package file in a DataSheet:
+//!
+//! ```xml
+//! <xi:include href="heater.xml" xpointer="element(/1/1)" />
+//! ```
+//!
+//! The `href` is relative to the including file, or to a directory of the include path of the
+//! [`Parser`]. Only the child sequences of the `element()` scheme are supported as XPointers;
+//! without one, the root element of the file is included.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use xml::attribute::OwnedAttribute;
+use xml::common::{Position, TextPosition};
+use xml::name::OwnedName;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+use crate::diagnostic::{parse_error, Diagnostic, Index, Severity, SourceMap, Span};
+use crate::eds::extension::{ExtensionAttribute, ExtensionElement};
+
+/// namespace of SEDS elements and attributes
+pub const SEDS_NAMESPACE: &str = "http://www.ccsds.org/schema/sois/seds";
+
+/// namespace of XInclude elements
+pub const XINCLUDE_NAMESPACE: &str = "http://www.w3.org/2001/XInclude";
+
+/// namespaces of attributes that are about the document rather than the model,
+/// like xsi:schemaLocation
+const DOCUMENT_NAMESPACES: [&str; 2] = [
+    "http://www.w3.org/2001/XMLSchema-instance",
+    "http://www.w3.org/XML/1998/namespace",
+];
+
+/// How to treat elements and attributes the models do not know
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// unknown SEDS elements and attributes are errors, extensions are warnings
+    Strict,
+    /// unknown SEDS elements and attributes and extensions are warnings
+    #[default]
+    Warn,
+    /// unknown elements, attributes and extensions are skipped silently
+    Lenient,
+}
+
+/// Result of reading a model, errors are located by the [`Parser`]
+pub type Result<T> = std::result::Result<T, Box<Diagnostic>>;
+
+/// reads a child element, see [`Element::children`]
+type Read<'f> = dyn FnMut(&mut Element<'_, '_>) -> Result<bool> + 'f;
+
+/// A model that is read from an XML element
+pub trait FromXml: Sized {
+    /// read the model from its element
+    fn from_xml(element: &mut Element<'_, '_>) -> Result<Self>;
+}
+
+/// Parser of datasheets and package files
+#[derive(Debug, Clone)]
+pub struct Parser {
+    file: String,
+    strictness: Strictness,
+    include_path: Vec<PathBuf>,
+}
+
+/// A parsed model with the warnings of the parser and the positions of its elements
+#[derive(Debug, Clone)]
+pub struct Parsed<T> {
+    /// the model
+    pub value: T,
+    /// unknown content that was skipped
+    pub warnings: Vec<Diagnostic>,
+    /// positions of the elements and attributes of the file
+    pub source: SourceMap,
+}
+
+impl Parser {
+    /// a parser of the given file, the name is used to locate diagnostics
+    pub fn new(file: &str) -> Self {
+        Parser {
+            file: file.to_string(),
+            strictness: Strictness::default(),
+            include_path: Vec::new(),
+        }
+    }
+
+    /// set how to treat unknown content
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// add a directory to search for included files that are not next to the including file
+    pub fn include_path(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.include_path.push(directory.into());
+        self
+    }
+
+    /// parse the contents of the file; the name of the root element is not checked, so a
+    /// DataSheet can be read as a [`crate::eds::raw::PackageFile`]
+    pub fn parse<T: FromXml>(&self, contents: &str) -> Result<Parsed<T>> {
+        let chain = fs::canonicalize(&self.file)
+            .map(|canonical| (canonical, self.file.clone()))
+            .into_iter()
+            .collect();
+        let mut reader = Reader::new(
+            &self.file,
+            contents,
+            self.strictness,
+            &self.include_path,
+            chain,
+        );
+        let value = reader.document::<T>();
+        let source = SourceMap::from_index(&self.file, contents, reader.index);
+        match value {
+            Ok(value) => Ok(Parsed {
+                value,
+                warnings: reader
+                    .warnings
+                    .into_iter()
+                    .map(|warning| locate(&source, warning))
+                    .collect(),
+                source,
+            }),
+            Err(error) => Err(Box::new(locate(&source, *error))),
+        }
+    }
+}
+
+/// locate a diagnostic in the file, unless it comes from an included file
+fn locate(source: &SourceMap, diagnostic: Diagnostic) -> Diagnostic {
+    match diagnostic.file {
+        Some(_) => diagnostic,
+        None => source.locate(diagnostic),
+    }
+}
+
+/// The state of a parse, shared by the elements being read
+pub(crate) struct Reader<'a> {
+    events: EventReader<&'a [u8]>,
+    file: &'a str,
+    text: &'a str,
+    /// paths and positions of the elements read so far
+    index: Index,
+    /// path of the element of the last start tag
+    path: String,
+    strictness: Strictness,
+    warnings: Vec<Diagnostic>,
+    include_path: &'a [PathBuf],
+    /// canonical and displayed paths of the files including this one, and of this one
+    chain: Vec<(PathBuf, String)>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(
+        file: &'a str,
+        text: &'a str,
+        strictness: Strictness,
+        include_path: &'a [PathBuf],
+        chain: Vec<(PathBuf, String)>,
+    ) -> Self {
+        let config = ParserConfig::new()
+            .trim_whitespace(true)
+            .whitespace_to_characters(true)
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .coalesce_characters(true);
+        Reader {
+            events: EventReader::new_with_config(text.as_bytes(), config),
+            file,
+            text,
+            index: Index::new(text),
+            path: String::new(),
+            strictness,
+            warnings: Vec::new(),
+            include_path,
+            chain,
+        }
+    }
+
+    /// the next event, indexing the elements it starts and ends
+    fn next(&mut self) -> Result<XmlEvent> {
+        let event = self.events.next().map_err(|error| {
+            let source = SourceMap::from_index(self.file, self.text, Index::default());
+            Box::new(parse_error(&source, &error))
+        })?;
+        match &event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let position = self.events.position();
+                self.path = self.index.start(self.text, position, name, attributes);
+            }
+            XmlEvent::EndElement { .. } => self.index.end(),
+            _ => (),
+        }
+        Ok(event)
+    }
+
+    fn document<T: FromXml>(&mut self) -> Result<T> {
+        let mut value = None;
+        loop {
+            match self.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let mut root = Element::new(self, name, attributes);
+                    value = Some(T::from_xml(&mut root)?);
+                    root.finish()?;
+                }
+                XmlEvent::EndDocument => {
+                    return value.ok_or_else(|| Box::new(Diagnostic::error("no root element")));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// read the element selected by the child sequence of an XPointer, the root element is `[1]`
+    fn included(&mut self, sequence: &[usize], parent: &str, read: &mut Read<'_>) -> Result<()> {
+        let mut matched = 0;
+        let mut seen = 0;
+        loop {
+            match self.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let mut element = Element::new(self, name, attributes);
+                    seen += 1;
+                    if seen != sequence[matched] {
+                        element.skip()?;
+                        continue;
+                    }
+                    matched += 1;
+                    seen = 0;
+                    if matched == sequence.len() {
+                        element.visit(parent, read)?;
+                        break;
+                    }
+                }
+                XmlEvent::EndElement { .. } | XmlEvent::EndDocument => {
+                    return Err(Box::new(Diagnostic::error(
+                        "the XPointer selects no element",
+                    )));
+                }
+                _ => (),
+            }
+        }
+        // the rest of the file must still be well-formed
+        while !matches!(self.next()?, XmlEvent::EndDocument) {}
+        Ok(())
+    }
+
+    /// the file an href refers to, relative to this file or a directory of the include path
+    fn find_include(&self, href: &str) -> Option<(PathBuf, PathBuf)> {
+        let base = Path::new(self.file).parent();
+        base.into_iter()
+            .chain(self.include_path.iter().map(PathBuf::as_path))
+            .map(|directory| directory.join(href))
+            .find(|path| path.is_file())
+            .and_then(|path| Some((fs::canonicalize(&path).ok()?, path)))
+    }
+
+    /// report unknown content, `extension` if it is in a foreign namespace
+    fn report(&mut self, extension: bool, warning: Diagnostic) -> Result<()> {
+        match (self.strictness, extension) {
+            (Strictness::Lenient, _) => Ok(()),
+            (Strictness::Strict, false) => Err(Box::new(Diagnostic {
+                severity: Severity::Error,
+                ..warning
+            })),
+            _ => {
+                self.warnings.push(warning);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An element being read, positioned after its start tag
+pub struct Element<'r, 'a> {
+    reader: &'r mut Reader<'a>,
+    name: OwnedName,
+    path: String,
+    /// position of the start tag
+    position: TextPosition,
+    /// attributes that have not been read yet
+    attributes: Vec<OwnedAttribute>,
+    attributes_checked: bool,
+    /// the extension child elements, if the model keeps them
+    extensions: Option<Vec<ExtensionElement>>,
+    closed: bool,
+}
+
+impl<'r, 'a> Element<'r, 'a> {
+    fn new(reader: &'r mut Reader<'a>, name: OwnedName, attributes: Vec<OwnedAttribute>) -> Self {
+        let path = std::mem::take(&mut reader.path);
+        let position = reader.events.position();
+        Element {
+            reader,
+            name,
+            path,
+            position,
+            attributes,
+            attributes_checked: false,
+            extensions: None,
+            closed: false,
+        }
+    }
+
+    /// local name of the element
+    pub fn name(&self) -> &str {
+        &self.name.local_name
+    }
+
+    /// path of the element, see [`crate::diagnostic`]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn is_seds(&self) -> bool {
+        matches!(self.name.namespace.as_deref(), None | Some(SEDS_NAMESPACE))
+    }
+
+    fn qualified_name(&self) -> String {
+        match &self.name.prefix {
+            Some(prefix) => format!("{}:{}", prefix, self.name.local_name),
+            None => self.name.local_name.clone(),
+        }
+    }
+
+    /// locate a diagnostic at this element; the root element has no path, so it is located by
+    /// its position
+    fn at(&self, diagnostic: Diagnostic) -> Diagnostic {
+        if !self.path.is_empty() {
+            return diagnostic.at(self.path.clone());
+        }
+        let line = self.position.row as usize;
+        match self.reader.text.lines().nth(line) {
+            Some(text) => diagnostic.with_span(Span {
+                line: line + 1,
+                column: self.position.column as usize + 2,
+                length: self.name.local_name.chars().count(),
+                text: text.trim_end_matches('\r').to_string(),
+            }),
+            None => diagnostic,
+        }
+    }
+
+    /// an error in this element
+    pub fn error(&self, message: impl Into<String>) -> Box<Diagnostic> {
+        Box::new(self.at(Diagnostic::error(message)))
+    }
+
+    /// take an attribute
+    pub fn attribute(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|attribute| {
+            attribute.name.local_name == name
+                && matches!(
+                    attribute.name.namespace.as_deref(),
+                    None | Some(SEDS_NAMESPACE)
+                )
+        })?;
+        Some(self.attributes.remove(index).value)
+    }
+
+    /// take an attribute the element must have
+    pub fn required(&mut self, name: &str) -> Result<String> {
+        self.attribute(name).ok_or_else(|| {
+            self.error(format!(
+                "missing attribute `{}` in `{}`",
+                name, self.name.local_name
+            ))
+        })
+    }
+
+    /// take the attributes in the namespaces of user DoTs, and keep the extension child
+    /// elements for [`Element::extension_elements`] rather than skipping them
+    pub fn extension_attributes(&mut self) -> Vec<ExtensionAttribute> {
+        let _ = self.extensions.get_or_insert_with(Vec::new);
+        let (extensions, attributes) = std::mem::take(&mut self.attributes)
+            .into_iter()
+            .partition(|attribute| is_extension(&attribute.name));
+        self.attributes = attributes;
+        extensions.into_iter().map(extension_attribute).collect()
+    }
+
+    /// take the extension child elements read so far, see [`Element::extension_attributes`]
+    pub fn extension_elements(&mut self) -> Vec<ExtensionElement> {
+        self.extensions.take().unwrap_or_default()
+    }
+
+    /// take a boolean attribute, false if it is missing
+    pub fn flag(&mut self, name: &str) -> Result<bool> {
+        match self.attribute(name).as_deref() {
+            None | Some("false") | Some("0") => Ok(false),
+            Some("true") | Some("1") => Ok(true),
+            Some(value) => Err(Box::new(
+                Diagnostic::error(format!("invalid boolean `{}`", value))
+                    .at(format!("{}@{}", self.path, name)),
+            )),
+        }
+    }
+
+    /// a child element the element must have
+    pub fn expect<T>(&self, value: Option<T>, child: &str) -> Result<T> {
+        value.ok_or_else(|| {
+            self.error(format!(
+                "missing element `{}` in `{}`",
+                child, self.name.local_name
+            ))
+        })
+    }
+
+    /// read this element as a model
+    pub fn read<T: FromXml>(&mut self) -> Result<T> {
+        T::from_xml(self)
+    }
+
+    /// read this element into a slot for a child that may appear once
+    pub fn single<T: FromXml>(&mut self, slot: &mut Option<T>) -> Result<bool> {
+        if slot.is_some() {
+            return Err(self.error(format!("duplicate element `{}`", self.name.local_name)));
+        }
+        *slot = Some(self.read()?);
+        Ok(true)
+    }
+
+    /// read this element into a list of children
+    pub fn push<T: FromXml>(&mut self, list: &mut Vec<T>) -> Result<bool> {
+        list.push(self.read()?);
+        Ok(true)
+    }
+
+    /// skip this element, for SEDS content the models do not keep
+    pub fn ignore(&mut self) -> Result<bool> {
+        self.skip()?;
+        Ok(true)
+    }
+
+    /// walk the child SEDS elements in document order; `read` returns false for elements it
+    /// does not know, which are then skipped. Extension elements are skipped without calling it.
+    pub fn children(
+        &mut self,
+        read: impl FnMut(&mut Element<'_, '_>) -> Result<bool>,
+    ) -> Result<()> {
+        self.walk(read, None)
+    }
+
+    /// the text content of the element, without surrounding whitespace
+    pub fn text(&mut self) -> Result<String> {
+        let mut text = String::new();
+        self.walk(|_| Ok(false), Some(&mut text))?;
+        Ok(text)
+    }
+
+    fn walk(
+        &mut self,
+        mut read: impl FnMut(&mut Element<'_, '_>) -> Result<bool>,
+        mut text: Option<&mut String>,
+    ) -> Result<()> {
+        self.check_attributes()?;
+        while !self.closed {
+            match self.reader.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let child = Element::new(self.reader, name, attributes);
+                    match self.extensions.as_mut() {
+                        Some(extensions) if is_extension(&child.name) => {
+                            extensions.push(child.extension()?)
+                        }
+                        _ => child.visit(&self.name.local_name, &mut read)?,
+                    }
+                }
+                XmlEvent::EndElement { .. } => self.closed = true,
+                XmlEvent::Characters(characters) => match text.as_mut() {
+                    Some(text) => text.push_str(&characters),
+                    None => {
+                        let warning = Diagnostic::warning(format!(
+                            "unexpected text in `{}`",
+                            self.name.local_name
+                        ));
+                        let warning = self.at(warning);
+                        self.reader.report(false, warning)?;
+                    }
+                },
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// read a child element of `parent`, including the element it refers to if it is an
+    /// xi:include
+    fn visit(mut self, parent: &str, read: &mut Read<'_>) -> Result<()> {
+        if self.name.namespace.as_deref() == Some(XINCLUDE_NAMESPACE)
+            && self.name.local_name == "include"
+        {
+            self.include(parent, read)?;
+        } else if !self.is_seds() {
+            let warning = Diagnostic::warning(format!(
+                "skipped extension element `{}`",
+                self.qualified_name()
+            ));
+            let warning = self.at(warning);
+            self.reader.report(true, warning)?;
+            self.skip()?;
+        } else if !read(&mut self)? {
+            let warning =
+                Diagnostic::warning(format!("unknown element `{}` in `{}`", self.name(), parent));
+            let warning = self.at(warning);
+            self.reader.report(false, warning)?;
+            self.skip()?;
+        }
+        self.finish()
+    }
+
+    /// read the element an xi:include refers to in place of it
+    fn include(&mut self, parent: &str, read: &mut Read<'_>) -> Result<()> {
+        let note = format!(
+            "included from {}:{}:{}",
+            self.reader.file,
+            self.position.row + 1,
+            self.position.column + 1
+        );
+        let href = self.required("href")?;
+        let sequence = match self.attribute("xpointer") {
+            None => vec![1],
+            Some(xpointer) => child_sequence(&xpointer)
+                .ok_or_else(|| self.error(format!("unsupported XPointer `{}`", xpointer)))?,
+        };
+        if let Some(parse) = self.attribute("parse").filter(|parse| parse != "xml") {
+            return Err(self.error(format!("unsupported include of {} `{}`", parse, href)));
+        }
+        // a fallback is not needed, a missing file is an error
+        self.skip()?;
+
+        let (canonical, path) = self
+            .reader
+            .find_include(&href)
+            .ok_or_else(|| self.error(format!("included file `{}` not found", href)))?;
+        let file = path.display().to_string();
+        if let Some(start) = self.reader.chain.iter().position(|(c, _)| *c == canonical) {
+            let cycle: Vec<_> = self.reader.chain[start..]
+                .iter()
+                .map(|(_, file)| file.as_str())
+                .chain([file.as_str()])
+                .collect();
+            return Err(self.error(format!("include cycle {}", cycle.join(" -> "))));
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|error| self.error(format!("can not read `{}`: {}", file, error)))?;
+
+        let mut chain = self.reader.chain.clone();
+        chain.push((canonical, file.clone()));
+        let mut included = Reader::new(
+            &file,
+            &contents,
+            self.reader.strictness,
+            self.reader.include_path,
+            chain,
+        );
+        let result = included.included(&sequence, parent, read);
+        let source = SourceMap::from_index(&file, &contents, included.index);
+        for warning in included.warnings {
+            self.reader
+                .warnings
+                .push(locate(&source, warning).with_note(note.clone()));
+        }
+        result.map_err(|error| Box::new(locate(&source, *error).with_note(note)))
+    }
+
+    /// read an extension element with all its content
+    fn extension(mut self) -> Result<ExtensionElement> {
+        self.attributes_checked = true;
+        let mut element = ExtensionElement {
+            namespace: self.name.namespace.clone().unwrap_or_default(),
+            name: self.name.local_name.clone(),
+            attributes: std::mem::take(&mut self.attributes)
+                .into_iter()
+                .filter(|attribute| {
+                    !DOCUMENT_NAMESPACES
+                        .contains(&attribute.name.namespace.as_deref().unwrap_or_default())
+                })
+                .map(extension_attribute)
+                .collect(),
+            ..ExtensionElement::default()
+        };
+        while !self.closed {
+            match self.reader.next()? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let child = Element::new(self.reader, name, attributes);
+                    element.children.push(child.extension()?);
+                }
+                XmlEvent::EndElement { .. } => self.closed = true,
+                XmlEvent::Characters(characters) => element.text.push_str(&characters),
+                _ => (),
+            }
+        }
+        Ok(element)
+    }
+
+    /// skip the rest of the element, including its attributes
+    fn skip(&mut self) -> Result<()> {
+        self.attributes_checked = true;
+        let mut depth = 0;
+        while !self.closed {
+            match self.reader.next()? {
+                XmlEvent::StartElement { .. } => depth += 1,
+                XmlEvent::EndElement { .. } if depth == 0 => self.closed = true,
+                XmlEvent::EndElement { .. } => depth -= 1,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// report the attributes that were not read
+    fn check_attributes(&mut self) -> Result<()> {
+        if self.attributes_checked {
+            return Ok(());
+        }
+        self.attributes_checked = true;
+        for attribute in std::mem::take(&mut self.attributes) {
+            let name = &attribute.name;
+            let path = format!("{}@{}", self.path, name.local_name);
+            let warning = match name.namespace.as_deref() {
+                None | Some(SEDS_NAMESPACE) => Diagnostic::warning(format!(
+                    "unknown attribute `{}` in `{}`",
+                    name.local_name, self.name.local_name
+                )),
+                Some(namespace) if DOCUMENT_NAMESPACES.contains(&namespace) => continue,
+                Some(_) => Diagnostic::warning(format!(
+                    "skipped extension attribute `{}:{}`",
+                    name.prefix.as_deref().unwrap_or_default(),
+                    name.local_name
+                )),
+            };
+            let extension = !matches!(name.namespace.as_deref(), None | Some(SEDS_NAMESPACE));
+            self.reader.report(extension, warning.at(path))?;
+        }
+        Ok(())
+    }
+
+    /// read up to the end of the element, whatever the model did not read is unknown
+    fn finish(mut self) -> Result<()> {
+        self.walk(|_| Ok(false), None)
+    }
+}
+
+/// true if a name is in the namespace of a user DoT, rather than SEDS, XInclude or the
+/// namespaces about the document
+fn is_extension(name: &OwnedName) -> bool {
+    match name.namespace.as_deref() {
+        None | Some(SEDS_NAMESPACE) | Some(XINCLUDE_NAMESPACE) => false,
+        Some(namespace) => !DOCUMENT_NAMESPACES.contains(&namespace),
+    }
+}
+
+fn extension_attribute(attribute: OwnedAttribute) -> ExtensionAttribute {
+    ExtensionAttribute {
+        namespace: attribute.name.namespace.unwrap_or_default(),
+        name: attribute.name.local_name,
+        value: attribute.value,
+    }
+}
+
+/// the child sequence of an XPointer of the `element()` scheme, e.g. `element(/1/1)`
+pub(crate) fn child_sequence(xpointer: &str) -> Option<Vec<usize>> {
+    let sequence = xpointer
+        .trim()
+        .strip_prefix("element(/")?
+        .strip_suffix(')')?;
+    sequence
+        .split('/')
+        .map(|step| step.parse().ok().filter(|step| *step > 0))
+        .collect()
+}
@@ -0,0 +1,672 @@
+//! Activity Evaluator - Req 3.15
+//!
+//! Executes the statements of activity bodies, evaluates `MathOperation` stack calculations
+//! and `Conditional` boolean expressions, and calculates metadata values (Req 3.4.9).
+//!
+//! Variable references are bound to a [`VariableScope`] (usually the component variables,
+//! layered with the activity arguments) and fall back to the mission parameters of an
+//! [`ExpressionContext`], addressed by their path (e.g. `CFE_MISSION/MAX_PATH_LEN`).
+//!
+//! Integer operands stay integers as long as the result is exact; everything else is
+//! calculated in double precision. Trigonometric operators use degrees.
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::eds::ast::{
+    Activity, Body, BooleanExpression, Comparison, ComparisonOperator, Component, IterationRange,
+    MathElement, MathOperation, MathOperator, MetadataValue, NumericValue, Operand,
+    ParameterOperation, QualifiedName, Statement,
+};
+use crate::eds::value::Value;
+use crate::expr::ExpressionContext;
+
+/// maximum depth of nested activity calls
+const MAX_CALL_DEPTH: usize = 64;
+
+/// maximum number of times a numeric iteration runs its body
+const MAX_ITERATIONS: usize = 1 << 20;
+
+/// Errors that can occur while evaluating activities and expressions
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// a variable is neither set nor a mission parameter
+    UnboundVariable(String),
+    /// an operator or comparison was applied to values of the wrong type
+    TypeMismatch(String),
+    /// integer division or modulus by zero
+    DivisionByZero,
+    /// an operator found too few values on the stack
+    StackUnderflow(MathOperator),
+    /// a calculation did not produce a (finite) value
+    InvalidResult(String),
+    /// a called activity is not part of the component
+    UnknownActivity(String),
+    /// activities call each other too deeply
+    CallDepthExceeded(String),
+    /// a numeric iteration runs more than [`MAX_ITERATIONS`] times
+    IterationLimitExceeded(String),
+    /// the statement can not be evaluated (e.g. an operator without a definition)
+    Unsupported(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnboundVariable(v) => write!(f, "variable {} has no value", v),
+            EvalError::TypeMismatch(msg) => write!(f, "type mismatch: {}", msg),
+            EvalError::DivisionByZero => f.write_str("division by zero"),
+            EvalError::StackUnderflow(op) => write!(f, "too few operands for {:?}", op),
+            EvalError::InvalidResult(msg) => write!(f, "invalid result: {}", msg),
+            EvalError::UnknownActivity(a) => write!(f, "activity {} is not defined", a),
+            EvalError::CallDepthExceeded(a) => write!(f, "activity calls nest too deeply in {}", a),
+            EvalError::IterationLimitExceeded(i) => write!(
+                f,
+                "iteration over {} runs more than {} times",
+                i, MAX_ITERATIONS
+            ),
+            EvalError::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Storage of variable values that expressions are evaluated against
+pub trait VariableScope {
+    /// current value of a variable
+    fn value(&self, name: &str) -> Option<Value>;
+
+    /// assign a value to a variable
+    fn assign(&mut self, name: &str, value: Value);
+}
+
+impl VariableScope for HashMap<String, Value> {
+    fn value(&self, name: &str) -> Option<Value> {
+        self.get(name).cloned()
+    }
+
+    fn assign(&mut self, name: &str, value: Value) {
+        let _ = self.insert(name.to_string(), value);
+    }
+}
+
+/// variables stored as literals (as the state machine interpreter does)
+impl VariableScope for HashMap<String, String> {
+    fn value(&self, name: &str) -> Option<Value> {
+        self.get(name).map(|literal| Value::parse(literal))
+    }
+
+    fn assign(&mut self, name: &str, value: Value) {
+        let _ = self.insert(name.to_string(), value.to_string());
+    }
+}
+
+/// activity arguments layered over the variables of the component
+struct LocalScope<'s> {
+    locals: HashMap<String, Value>,
+    outer: &'s mut dyn VariableScope,
+}
+
+impl VariableScope for LocalScope<'_> {
+    fn value(&self, name: &str) -> Option<Value> {
+        self.locals
+            .get(name)
+            .cloned()
+            .or_else(|| self.outer.value(name))
+    }
+
+    fn assign(&mut self, name: &str, value: Value) {
+        match self.locals.get_mut(name) {
+            Some(local) => *local = value,
+            None => self.outer.assign(name, value),
+        }
+    }
+}
+
+/// A primitive sent on an interface by an activity
+#[derive(Debug, Clone, PartialEq)]
+pub enum SentPrimitive {
+    /// SendCommandPrimitive
+    Command {
+        /// interface name
+        interface: String,
+        /// command name
+        command: String,
+        /// argument names and values
+        arguments: Vec<(String, Value)>,
+    },
+    /// SendParameterPrimitive
+    Parameter {
+        /// interface name
+        interface: String,
+        /// parameter name
+        parameter: String,
+        /// get or set
+        operation: ParameterOperation,
+        /// value written by a set
+        value: Option<Value>,
+    },
+}
+
+/// Evaluator for the activities and expressions of a component
+#[derive(Debug, Clone, Default)]
+pub struct Evaluator<'a> {
+    activities: &'a [Activity],
+    variable_types: HashMap<&'a str, &'a QualifiedName>,
+    parameters: Option<&'a ExpressionContext>,
+    sent: Vec<SentPrimitive>,
+    depth: usize,
+}
+
+impl<'a> Evaluator<'a> {
+    /// create an evaluator without activities or mission parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// create an evaluator for the activities and variables of a component
+    pub fn for_component(component: &'a Component) -> Self {
+        let implementation = &component.implementation;
+        Evaluator {
+            activities: &implementation.activity_set.activities,
+            variable_types: implementation
+                .variable_set
+                .variables
+                .iter()
+                .map(|v| (v.name.0.as_str(), &v.type_))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// bind variable references that are not set in the scope to mission parameters
+    pub fn with_parameters(mut self, parameters: &'a ExpressionContext) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// initial values of the variables of a component
+    pub fn initial_values(component: &Component) -> HashMap<String, Value> {
+        component
+            .implementation
+            .variable_set
+            .variables
+            .iter()
+            .filter_map(|v| {
+                v.initial_value
+                    .as_ref()
+                    .map(|iv| (v.name.0.clone(), Value::parse(&iv.0)))
+            })
+            .collect()
+    }
+
+    /// primitives sent since the last call, in order
+    pub fn take_sent(&mut self) -> Vec<SentPrimitive> {
+        std::mem::take(&mut self.sent)
+    }
+
+    /// value of a variable, or of the mission parameter with that path
+    pub fn variable(
+        &self,
+        scope: &dyn VariableScope,
+        name: &QualifiedName,
+    ) -> Result<Value, EvalError> {
+        if let Some(value) = scope.value(&name.0) {
+            return Ok(value);
+        }
+        self.parameters
+            .and_then(|p| p.value(&name.0.split('/').collect::<Vec<_>>()).ok())
+            .ok_or(EvalError::UnboundVariable(name.0.clone()))
+    }
+
+    /// value of an operand
+    pub fn operand(
+        &self,
+        scope: &dyn VariableScope,
+        operand: &Operand,
+    ) -> Result<Value, EvalError> {
+        match operand {
+            Operand::Value(literal) => Ok(Value::parse(&literal.0)),
+            Operand::VariableRef(name) => self.variable(scope, name),
+        }
+    }
+
+    /// calculate the result of a math operation without assigning it - Req 3.15.32
+    pub fn calculate(
+        &self,
+        scope: &dyn VariableScope,
+        operation: &MathOperation,
+    ) -> Result<Value, EvalError> {
+        let mut stack: Vec<Value> = Vec::new();
+        for element in operation.elements.iter() {
+            match element {
+                MathElement::Operand(operand) => stack.push(self.operand(scope, operand)?),
+                MathElement::Operator(op) => {
+                    if stack.len() < op.arity() {
+                        return Err(EvalError::StackUnderflow(*op));
+                    }
+                    let b = stack.pop().unwrap_or(Value::Integer(0));
+                    if *op == MathOperator::Swap {
+                        let a = stack.pop().unwrap_or(Value::Integer(0));
+                        stack.push(b);
+                        stack.push(a);
+                    } else if op.arity() == 2 {
+                        let a = stack.pop().unwrap_or(Value::Integer(0));
+                        stack.push(apply_binary(*op, &a, &b)?);
+                    } else {
+                        stack.push(apply_unary(*op, &b)?);
+                    }
+                }
+            }
+        }
+        match (stack.pop(), stack.is_empty()) {
+            (Some(result), true) => Ok(result),
+            _ => Err(EvalError::InvalidResult(format!(
+                "{} does not leave a single value",
+                operation.output_variable_ref.0
+            ))),
+        }
+    }
+
+    /// calculate a math operation and assign the result to its output variable
+    pub fn math_operation(
+        &self,
+        scope: &mut dyn VariableScope,
+        operation: &MathOperation,
+    ) -> Result<Value, EvalError> {
+        let result = self.calculate(scope, operation)?;
+        scope.assign(&operation.output_variable_ref.0, result.clone());
+        Ok(result)
+    }
+
+    /// evaluate a boolean expression - Req 3.15.37
+    pub fn condition(
+        &self,
+        scope: &dyn VariableScope,
+        expression: &BooleanExpression,
+    ) -> Result<bool, EvalError> {
+        match expression {
+            BooleanExpression::Condition(comparison) => self.comparison(scope, comparison),
+            BooleanExpression::ANDedConditions(list) => {
+                for e in list.iter() {
+                    if !self.condition(scope, e)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            BooleanExpression::ORedConditions(list) => {
+                for e in list.iter() {
+                    if self.condition(scope, e)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            BooleanExpression::TypeCondition(check) => {
+                // the variable must be set, its declared type decides compatibility
+                let _ = self.variable(scope, &check.first_operand)?;
+                Ok(self
+                    .variable_types
+                    .get(check.first_operand.0.as_str())
+                    .is_some_and(|t| same_type(t, &check.type_operand)))
+            }
+        }
+    }
+
+    fn comparison(
+        &self,
+        scope: &dyn VariableScope,
+        comparison: &Comparison,
+    ) -> Result<bool, EvalError> {
+        use std::cmp::Ordering::*;
+        let a = self.variable(scope, &comparison.first_operand)?;
+        let b = self.operand(scope, &comparison.second_operand)?;
+        let ordering = a.compare(&b);
+        let result = match (comparison.comparison_operator, ordering) {
+            (ComparisonOperator::Equals, o) => o == Some(Equal),
+            (ComparisonOperator::NotEquals, o) => o != Some(Equal),
+            (_, None) => {
+                return Err(EvalError::TypeMismatch(format!(
+                    "cannot order {} and {}",
+                    a.type_name(),
+                    b.type_name()
+                )))
+            }
+            (ComparisonOperator::LessThan, Some(o)) => o == Less,
+            (ComparisonOperator::LessThanEquals, Some(o)) => o != Greater,
+            (ComparisonOperator::GreaterThan, Some(o)) => o == Greater,
+            (ComparisonOperator::GreaterThanEquals, Some(o)) => o != Less,
+        };
+        Ok(result)
+    }
+
+    /// execute the statements of a body, returning the last value that was calculated or assigned
+    pub fn execute(
+        &mut self,
+        scope: &mut dyn VariableScope,
+        body: &Body,
+    ) -> Result<Option<Value>, EvalError> {
+        let mut last = None;
+        for statement in body.statements.iter() {
+            let value = self.statement(scope, statement)?;
+            if value.is_some() {
+                last = value;
+            }
+        }
+        Ok(last)
+    }
+
+    fn statement(
+        &mut self,
+        scope: &mut dyn VariableScope,
+        statement: &Statement,
+    ) -> Result<Option<Value>, EvalError> {
+        match statement {
+            Statement::Assignment(a) => {
+                let value = self.operand(scope, &a.value)?;
+                scope.assign(&a.output_variable_ref.0, value.clone());
+                Ok(Some(value))
+            }
+            Statement::Conditional(c) => {
+                if self.condition(scope, &c.condition)? {
+                    self.execute(scope, &c.on_condition_true)
+                } else {
+                    self.execute(scope, &c.on_condition_false)
+                }
+            }
+            Statement::Iteration(i) => {
+                let (start_at, step, end_at) = match &i.range {
+                    IterationRange::Numeric {
+                        start_at,
+                        step,
+                        end_at,
+                    } => (
+                        self.operand(scope, start_at)?,
+                        self.operand(scope, step)?,
+                        self.operand(scope, end_at)?,
+                    ),
+                    IterationRange::OverArray(array) => {
+                        let elements = match self.variable(scope, array)? {
+                            Value::Array(elements) => elements,
+                            value => {
+                                return Err(EvalError::TypeMismatch(format!(
+                                    "cannot iterate over {} {}",
+                                    value.type_name(),
+                                    array.0
+                                )))
+                            }
+                        };
+                        let mut last = None;
+                        for element in elements {
+                            scope.assign(&i.iterator_variable_ref.0, element);
+                            if let Some(value) = self.execute(scope, &i.do_)? {
+                                last = Some(value);
+                            }
+                        }
+                        return Ok(last);
+                    }
+                };
+                let step_f = numeric(&step)?;
+                if step_f == 0.0 {
+                    return Err(EvalError::InvalidResult(format!(
+                        "iteration over {} does not advance",
+                        i.iterator_variable_ref.0
+                    )));
+                }
+                let end_f = numeric(&end_at)?;
+                let mut last = None;
+                let mut current = start_at;
+                for count in 0.. {
+                    let current_f = numeric(&current)?;
+                    if (step_f > 0.0 && current_f > end_f) || (step_f < 0.0 && current_f < end_f) {
+                        break;
+                    }
+                    if count == MAX_ITERATIONS {
+                        return Err(EvalError::IterationLimitExceeded(
+                            i.iterator_variable_ref.0.clone(),
+                        ));
+                    }
+                    scope.assign(&i.iterator_variable_ref.0, current.clone());
+                    if let Some(value) = self.execute(scope, &i.do_)? {
+                        last = Some(value);
+                    }
+                    current = apply_binary(MathOperator::Add, &current, &step)?;
+                }
+                Ok(last)
+            }
+            Statement::Calibration(c) => {
+                let input = numeric(&self.variable(scope, &c.input_variable_ref)?)?;
+                let mut output = 0.0;
+                for term in c.calibrator.term.iter() {
+                    let coefficient = numeric(&Value::parse(&term.coefficient.0))?;
+                    let exponent = numeric(&Value::parse(&term.exponent.0))?;
+                    output += coefficient * input.powf(exponent);
+                }
+                let value = finite(output)?;
+                scope.assign(&c.output_variable_ref.0, value.clone());
+                Ok(Some(value))
+            }
+            Statement::SendParameterPrimitive(p) => {
+                let value = match &p.argument_value {
+                    Some(operand) => Some(self.operand(scope, operand)?),
+                    None => None,
+                };
+                self.sent.push(SentPrimitive::Parameter {
+                    interface: p.interface.0.clone(),
+                    parameter: p.parameter.0.clone(),
+                    operation: p.operation,
+                    value,
+                });
+                Ok(None)
+            }
+            Statement::SendCommandPrimitive(c) => {
+                let arguments = c
+                    .argument_values
+                    .iter()
+                    .map(|av| Ok((av.name.0.clone(), self.operand(scope, &av.value)?)))
+                    .collect::<Result<Vec<_>, EvalError>>()?;
+                self.sent.push(SentPrimitive::Command {
+                    interface: c.interface.0.clone(),
+                    command: c.command.0.clone(),
+                    arguments,
+                });
+                Ok(None)
+            }
+            Statement::MathOperation(m) => self.math_operation(scope, m).map(Some),
+            Statement::Call(call) => {
+                let arguments = call
+                    .argument_values
+                    .iter()
+                    .map(|av| Ok((av.name.0.clone(), self.operand(scope, &av.value)?)))
+                    .collect::<Result<Vec<_>, EvalError>>()?;
+                self.invoke(scope, &call.activity.0, arguments)
+            }
+        }
+    }
+
+    /// invoke an activity of the component with its arguments bound to values
+    pub fn invoke(
+        &mut self,
+        scope: &mut dyn VariableScope,
+        activity: &str,
+        arguments: Vec<(String, Value)>,
+    ) -> Result<Option<Value>, EvalError> {
+        let activities = self.activities;
+        let definition = activities
+            .iter()
+            .find(|a| a.name_entity_type.name.0 == activity)
+            .ok_or(EvalError::UnknownActivity(activity.to_string()))?;
+        if self.depth >= MAX_CALL_DEPTH {
+            return Err(EvalError::CallDepthExceeded(activity.to_string()));
+        }
+
+        let mut local = LocalScope {
+            locals: arguments.into_iter().collect(),
+            outer: scope,
+        };
+        self.depth += 1;
+        let result = self.execute(&mut local, &definition.body);
+        self.depth -= 1;
+        result
+    }
+
+    /// true if the component defines the activity
+    pub fn has_activity(&self, activity: &str) -> bool {
+        self.activities
+            .iter()
+            .any(|a| a.name_entity_type.name.0 == activity)
+    }
+
+    /// value of a metadata value, calculating it if necessary - Req 3.4.9
+    pub fn metadata_value(&mut self, value: &MetadataValue) -> Result<Value, EvalError> {
+        let (numeric_value, integer) = match value {
+            MetadataValue::DateValue(v) | MetadataValue::StringValue(v) => {
+                return Ok(Value::String(v.value.0.clone()))
+            }
+            MetadataValue::FloatValue(v) => (&v.value, false),
+            MetadataValue::IntegerValue(v) => (&v.value, true),
+        };
+        let name = &value.name_entity_type().name.0;
+        let mut scratch: HashMap<String, Value> = HashMap::new();
+        let result = match numeric_value {
+            NumericValue::Literal(literal) => Value::parse(&literal.0),
+            NumericValue::MathOperation(m) => self.calculate(&scratch, m)?,
+            NumericValue::Conditional(c) => {
+                let branch = if self.condition(&scratch, &c.condition)? {
+                    &c.on_condition_true
+                } else {
+                    &c.on_condition_false
+                };
+                self.execute(&mut scratch, branch)?
+                    .ok_or(EvalError::InvalidResult(format!("{} has no value", name)))?
+            }
+        };
+        match (integer, &result) {
+            (true, _) => result
+                .as_i64()
+                .map(Value::Integer)
+                .ok_or(EvalError::TypeMismatch(format!(
+                    "{} is not an integer: {}",
+                    name, result
+                ))),
+            (false, Value::Integer(i)) => Ok(Value::Float(*i as f64)),
+            (false, Value::Float(_)) => Ok(result),
+            (false, _) => Err(EvalError::TypeMismatch(format!(
+                "{} is not a number: {}",
+                name, result
+            ))),
+        }
+    }
+}
+
+/// type references match if they are equal, or if one is the other qualified by a package
+fn same_type(a: &QualifiedName, b: &QualifiedName) -> bool {
+    let local = |name: &str| name.rsplit('/').next().unwrap_or("").to_string();
+    a == b || (!(a.0.contains('/') && b.0.contains('/')) && local(&a.0) == local(&b.0))
+}
+
+fn numeric(value: &Value) -> Result<f64, EvalError> {
+    value.as_f64().ok_or(EvalError::TypeMismatch(format!(
+        "{} is not a number",
+        value.type_name()
+    )))
+}
+
+fn finite(value: f64) -> Result<Value, EvalError> {
+    if value.is_finite() {
+        Ok(Value::Float(value))
+    } else {
+        Err(EvalError::InvalidResult(value.to_string()))
+    }
+}
+
+fn apply_binary(op: MathOperator, a: &Value, b: &Value) -> Result<Value, EvalError> {
+    use MathOperator::*;
+    // exact integer arithmetic while possible
+    if let (Value::Integer(x), Value::Integer(y)) = (a, b) {
+        let (x, y) = (*x, *y);
+        let exact = match op {
+            Add => x.checked_add(y),
+            Subtract => x.checked_sub(y),
+            Multiply => x.checked_mul(y),
+            Divide if y == 0 => return Err(EvalError::DivisionByZero),
+            Divide if x % y == 0 => x.checked_div(y),
+            Modulus if y == 0 => return Err(EvalError::DivisionByZero),
+            Modulus => x.checked_rem(y),
+            Pow if (0..=u32::MAX as i64).contains(&y) => x.checked_pow(y as u32),
+            Min => Some(x.min(y)),
+            Max => Some(x.max(y)),
+            _ => None,
+        };
+        if let Some(result) = exact {
+            return Ok(Value::Integer(result));
+        }
+    }
+
+    let (x, y) = (numeric(a)?, numeric(b)?);
+    let result = match op {
+        Add => x + y,
+        Subtract => x - y,
+        Multiply => x * y,
+        Divide => x / y,
+        Modulus => x % y,
+        Pow => x.powf(y),
+        Atan2 => x.atan2(y).to_degrees(),
+        Min => x.min(y),
+        Max => x.max(y),
+        _ => return Err(EvalError::Unsupported(format!("{:?}", op))),
+    };
+    finite(result)
+}
+
+fn apply_unary(op: MathOperator, a: &Value) -> Result<Value, EvalError> {
+    use MathOperator::*;
+    if let Value::Integer(x) = a {
+        let exact = match op {
+            Abs => x.checked_abs(),
+            Ceil | Floor | Round => Some(*x),
+            _ => None,
+        };
+        if let Some(result) = exact {
+            return Ok(Value::Integer(result));
+        }
+    }
+
+    let x = numeric(a)?;
+    let result = match op {
+        Ln => x.ln(),
+        Log => x.log10(),
+        Exp => x.exp(),
+        Inverse => 1.0 / x,
+        Tan => x.to_radians().tan(),
+        Cos => x.to_radians().cos(),
+        Sin => x.to_radians().sin(),
+        Atan => x.atan().to_degrees(),
+        Acos => x.acos().to_degrees(),
+        Asin => x.asin().to_degrees(),
+        Tanh => x.tanh(),
+        Cosh => x.cosh(),
+        Sinh => x.sinh(),
+        Atanh => x.atanh(),
+        Acosh => x.acosh(),
+        Asinh => x.asinh(),
+        Abs => x.abs(),
+        Sqrt => x.sqrt(),
+        // rounding yields an integer whenever it fits
+        Ceil | Floor | Round => {
+            let rounded = match op {
+                Ceil => x.ceil(),
+                Floor => x.floor(),
+                _ => x.round(),
+            };
+            return Value::Float(rounded)
+                .as_i64()
+                .map(Value::Integer)
+                .ok_or(EvalError::InvalidResult(rounded.to_string()));
+        }
+        _ => return Err(EvalError::Unsupported(format!("{:?}", op))),
+    };
+    finite(result)
+}
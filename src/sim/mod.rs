@@ -0,0 +1,7 @@
+//! Simulation of Component Behavior described in EDS
+
+/// Evaluator for activities, math operations and conditionals
+pub mod activity;
+
+/// Interpreter for component state machines
+pub mod statemachine;
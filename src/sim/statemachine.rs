@@ -0,0 +1,433 @@
+//! State Machine Interpreter - Req 3.16
+//!
+//! Executes a resolved [`StateMachine`] by feeding it interface events (command and
+//! parameter primitives) and elapsed time (timer triggers). Every fired transition is
+//! reported as a [`StateChange`] listing the activities that the datasheet schedules:
+//! the `OnExit` of the source state, the `Do` of the transition and the `OnEntry` of the
+//! target state, in that order.
+//!
+//! The first matching transition whose guard holds fires. Guards see the component variables
+//! as they would be after storing the values carried by the event. Activities defined in the
+//! component's ActivitySet are executed by the [`Evaluator`] when they are invoked.
+use std::collections::HashMap;
+use std::fmt;
+
+use super::activity::{EvalError, Evaluator, SentPrimitive};
+use crate::eds::ast::{
+    ActivityInvocation, Component, Identifier, Operand, ParameterOperation, State, StateMachine,
+    StateMachineElement, Transition, TransitionTrigger,
+};
+use crate::eds::value::Value;
+use crate::expr::ExpressionContext;
+
+/// Errors that can occur while interpreting a state machine
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpreterError {
+    /// the state machine has no EntryState to start in
+    NoEntryState(String),
+    /// an activity argument refers to a variable that has no value
+    UnboundVariable(String),
+    /// timer transitions keep firing without time passing
+    TimerLoop(String),
+    /// a guard or an activity failed to evaluate
+    Evaluation(EvalError),
+}
+
+impl fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpreterError::NoEntryState(sm) => {
+                write!(f, "state machine {} has no EntryState", sm)
+            }
+            InterpreterError::UnboundVariable(v) => write!(f, "variable {} has no value", v),
+            InterpreterError::TimerLoop(s) => {
+                write!(
+                    f,
+                    "timer transitions loop without elapsed time in state {}",
+                    s
+                )
+            }
+            InterpreterError::Evaluation(e) => write!(f, "evaluation failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InterpreterError {}
+
+/// An interface event that may trigger a transition
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// a command arrived on an interface (OnCommandPrimitive)
+    Command {
+        /// interface name
+        interface: String,
+        /// command name
+        command: String,
+        /// command argument values by argument name
+        arguments: HashMap<String, String>,
+    },
+    /// a parameter was read or written on an interface (OnParameterPrimitive)
+    Parameter {
+        /// interface name
+        interface: String,
+        /// parameter name
+        parameter: String,
+        /// get or set
+        operation: ParameterOperation,
+        /// parameter value, stored in the transition's VariableRef (if any)
+        value: Option<String>,
+    },
+}
+
+impl Event {
+    /// a command event without arguments
+    pub fn command(interface: &str, command: &str) -> Self {
+        Event::Command {
+            interface: interface.to_string(),
+            command: command.to_string(),
+            arguments: HashMap::new(),
+        }
+    }
+
+    /// a parameter event without value
+    pub fn parameter(interface: &str, parameter: &str, operation: ParameterOperation) -> Self {
+        Event::Parameter {
+            interface: interface.to_string(),
+            parameter: parameter.to_string(),
+            operation,
+            value: None,
+        }
+    }
+}
+
+/// When an activity is invoked during a transition - Req 3.16.6
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityPhase {
+    /// immediately before exit from the source state
+    OnExit,
+    /// when performing the transition
+    Do,
+    /// on entry to the target state
+    OnEntry,
+}
+
+/// An activity invoked by the state machine, with its arguments bound to values
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvokedActivity {
+    /// name of the activity
+    pub activity: String,
+    /// phase of the transition that invoked the activity
+    pub phase: ActivityPhase,
+    /// argument names and values, in the order of the datasheet
+    pub arguments: Vec<(String, String)>,
+    /// primitives sent by the activity, if the component defines it
+    pub sent: Vec<SentPrimitive>,
+}
+
+/// A fired transition
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    /// name of the transition
+    pub transition: String,
+    /// state before the transition
+    pub from_state: String,
+    /// state after the transition
+    pub to_state: String,
+    /// activities invoked by the transition, in invocation order
+    pub activities: Vec<InvokedActivity>,
+}
+
+/// Interpreter that executes a state machine
+#[derive(Debug, Clone)]
+pub struct StateMachineInterpreter<'a> {
+    machine: &'a StateMachine,
+    current: &'a Identifier,
+    time_in_state_ns: u64,
+    variables: HashMap<String, String>,
+    evaluator: Evaluator<'a>,
+}
+
+impl<'a> StateMachineInterpreter<'a> {
+    /// create an interpreter that starts in the (first) EntryState of the state machine
+    pub fn new(machine: &'a StateMachine) -> Result<Self, InterpreterError> {
+        let current = machine
+            .elements
+            .iter()
+            .find_map(|e| match e {
+                StateMachineElement::EntryState(es) => Some(&es.name_entity_type.name),
+                _ => None,
+            })
+            .ok_or(InterpreterError::NoEntryState(
+                machine.name_entity_type.name.0.clone(),
+            ))?;
+        Ok(StateMachineInterpreter {
+            machine,
+            current,
+            time_in_state_ns: 0,
+            variables: HashMap::new(),
+            evaluator: Evaluator::new(),
+        })
+    }
+
+    /// create an interpreter with the variables of the component set to their initial values
+    pub fn for_component(
+        component: &'a Component,
+        machine: &'a StateMachine,
+    ) -> Result<Self, InterpreterError> {
+        let mut interpreter = Self::new(machine)?;
+        interpreter.evaluator = Evaluator::for_component(component);
+        for variable in component.implementation.variable_set.variables.iter() {
+            if let Some(initial_value) = &variable.initial_value {
+                interpreter.set_variable(&variable.name.0, &initial_value.0);
+            }
+        }
+        Ok(interpreter)
+    }
+
+    /// bind variable references that are not component variables to mission parameters
+    pub fn with_parameters(mut self, parameters: &'a ExpressionContext) -> Self {
+        self.evaluator = self.evaluator.with_parameters(parameters);
+        self
+    }
+
+    /// name of the current state
+    pub fn current_state(&self) -> &str {
+        &self.current.0
+    }
+
+    /// true once the state machine has reached an ExitState
+    pub fn is_finished(&self) -> bool {
+        self.machine.elements.iter().any(|e| match e {
+            StateMachineElement::ExitState(es) => es.name_entity_type.name == *self.current,
+            _ => false,
+        })
+    }
+
+    /// value of a component variable
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(|v| v.as_str())
+    }
+
+    /// set a component variable
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        let _ = self.variables.insert(name.to_string(), value.to_string());
+    }
+
+    /// handle an interface event, firing the first matching transition from the current state
+    pub fn handle_event(&mut self, event: &Event) -> Result<Option<StateChange>, InterpreterError> {
+        if self.is_finished() {
+            return Ok(None);
+        }
+
+        let machine = self.machine;
+        let candidates = self
+            .transitions_from_current(machine)
+            .filter(|t| match (&t.trigger, event) {
+                (
+                    TransitionTrigger::OnCommandPrimitive(cmd),
+                    Event::Command {
+                        interface, command, ..
+                    },
+                ) => cmd.interface.0 == *interface && cmd.command.0 == *command,
+                (
+                    TransitionTrigger::OnParameterPrimitive(param),
+                    Event::Parameter {
+                        interface,
+                        parameter,
+                        operation,
+                        ..
+                    },
+                ) => {
+                    param.interface.0 == *interface
+                        && param.parameter.0 == *parameter
+                        && param.operation == *operation
+                }
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+
+        for transition in candidates {
+            // store the incoming values in (a copy of) the component variables
+            let mut variables = self.variables.clone();
+            match (&transition.trigger, event) {
+                (TransitionTrigger::OnCommandPrimitive(cmd), Event::Command { arguments, .. }) => {
+                    for sink in cmd.argument_values.iter() {
+                        if let Some(value) = arguments.get(&sink.name.0) {
+                            let _ =
+                                variables.insert(sink.output_variable_ref.0.clone(), value.clone());
+                        }
+                    }
+                }
+                (
+                    TransitionTrigger::OnParameterPrimitive(param),
+                    Event::Parameter {
+                        value: Some(value), ..
+                    },
+                ) => {
+                    if let Some(variable_ref) = &param.variable_ref {
+                        let _ = variables.insert(variable_ref.0.clone(), value.clone());
+                    }
+                }
+                _ => (),
+            }
+
+            if self.guard_holds(&variables, transition)? {
+                self.variables = variables;
+                return self.fire(transition).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// let time pass, firing timer transitions that become due (possibly several in a row)
+    pub fn advance_time(&mut self, nanoseconds: u64) -> Result<Vec<StateChange>, InterpreterError> {
+        let machine = self.machine;
+        let mut changes = Vec::new();
+        // transitions fired in a row without time passing
+        let mut instant = 0;
+        self.time_in_state_ns = self.time_in_state_ns.saturating_add(nanoseconds);
+
+        while !self.is_finished() {
+            // the earliest due timer of the current state fires first
+            let due = self
+                .transitions_from_current(machine)
+                .filter_map(|t| match &t.trigger {
+                    TransitionTrigger::OnTimer(timer)
+                        if timer.nanoseconds_after_entry <= self.time_in_state_ns =>
+                    {
+                        Some((timer.nanoseconds_after_entry, t))
+                    }
+                    _ => None,
+                })
+                .map(|(ns, t)| Ok((ns, t, self.guard_holds(&self.variables, t)?)))
+                .collect::<Result<Vec<_>, InterpreterError>>()?
+                .into_iter()
+                .filter(|(_, _, holds)| *holds)
+                .map(|(ns, t, _)| (ns, t))
+                .min_by_key(|(ns, _)| *ns);
+
+            let (ns, transition) = match due {
+                Some(due) => due,
+                None => break,
+            };
+
+            // zero-delay timers could cycle forever, periodic timers consume time
+            instant = if ns == 0 { instant + 1 } else { 0 };
+            if instant > machine.elements.len() {
+                return Err(InterpreterError::TimerLoop(self.current.0.clone()));
+            }
+
+            let remaining = self.time_in_state_ns - ns;
+            changes.push(self.fire(transition)?);
+            self.time_in_state_ns = remaining;
+        }
+
+        Ok(changes)
+    }
+
+    fn transitions_from_current(
+        &self,
+        machine: &'a StateMachine,
+    ) -> impl Iterator<Item = &'a Transition> + '_ {
+        machine.elements.iter().filter_map(move |e| match e {
+            StateMachineElement::Transition(t) if t.from_state == *self.current => Some(t),
+            _ => None,
+        })
+    }
+
+    fn guard_holds(
+        &self,
+        variables: &HashMap<String, String>,
+        transition: &Transition,
+    ) -> Result<bool, InterpreterError> {
+        match &transition.guard {
+            Some(guard) => self
+                .evaluator
+                .condition(variables, guard)
+                .map_err(InterpreterError::Evaluation),
+            None => Ok(true),
+        }
+    }
+
+    fn fire(&mut self, transition: &'a Transition) -> Result<StateChange, InterpreterError> {
+        let mut activities = Vec::new();
+
+        if let Some(on_exit) = self.state_activity(&transition.from_state, |s| &s.on_exit) {
+            activities.push(self.invoke(on_exit, ActivityPhase::OnExit)?);
+        }
+        if let Some(do_) = &transition.do_ {
+            activities.push(self.invoke(do_, ActivityPhase::Do)?);
+        }
+        if let Some(on_entry) = self.state_activity(&transition.to_state, |s| &s.on_entry) {
+            activities.push(self.invoke(on_entry, ActivityPhase::OnEntry)?);
+        }
+
+        self.current = &transition.to_state;
+        self.time_in_state_ns = 0;
+
+        Ok(StateChange {
+            transition: transition.name_entity_type.name.0.clone(),
+            from_state: transition.from_state.0.clone(),
+            to_state: transition.to_state.0.clone(),
+            activities,
+        })
+    }
+
+    fn state_activity(
+        &self,
+        state: &Identifier,
+        select: fn(&'a State) -> &'a Option<ActivityInvocation>,
+    ) -> Option<&'a ActivityInvocation> {
+        self.machine.elements.iter().find_map(|e| match e {
+            StateMachineElement::State(s) if s.name_entity_type.name == *state => {
+                select(s).as_ref()
+            }
+            _ => None,
+        })
+    }
+
+    fn invoke(
+        &mut self,
+        invocation: &ActivityInvocation,
+        phase: ActivityPhase,
+    ) -> Result<InvokedActivity, InterpreterError> {
+        let arguments = invocation
+            .argument_values
+            .iter()
+            .map(|av| {
+                let value = match &av.value {
+                    Operand::Value(literal) => literal.0.clone(),
+                    Operand::VariableRef(variable) => self
+                        .variables
+                        .get(&variable.0)
+                        .cloned()
+                        .ok_or(InterpreterError::UnboundVariable(variable.0.clone()))?,
+                };
+                Ok((av.name.0.clone(), value))
+            })
+            .collect::<Result<Vec<_>, InterpreterError>>()?;
+
+        let activity = &invocation.activity.0;
+        let mut sent = Vec::new();
+        if self.evaluator.has_activity(activity) {
+            let values = arguments
+                .iter()
+                .map(|(name, value)| (name.clone(), Value::parse(value)))
+                .collect();
+            let _ = self
+                .evaluator
+                .invoke(&mut self.variables, activity, values)
+                .map_err(InterpreterError::Evaluation)?;
+            sent = self.evaluator.take_sent();
+        }
+
+        Ok(InvokedActivity {
+            activity: activity.clone(),
+            phase,
+            arguments,
+            sent,
+        })
+    }
+}
@@ -0,0 +1,271 @@
+//! Element trees of XML files
+//!
+//! Schemas and datasheets are validated as trees of elements, which keep the path of each
+//! element (see [`crate::diagnostic`]) and the file it comes from, so that violations can be
+//! located in the datasheet or in the package file it includes.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use xml::attribute::OwnedAttribute;
+use xml::common::{Position, TextPosition};
+use xml::name::OwnedName;
+use xml::namespace::Namespace;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+use crate::diagnostic::{parse_error, Diagnostic, Index, SourceMap, Span};
+use crate::parse::reader::{child_sequence, XINCLUDE_NAMESPACE};
+
+/// An element of a file
+#[derive(Debug, Clone)]
+pub(crate) struct Node {
+    pub name: OwnedName,
+    pub attributes: Vec<OwnedAttribute>,
+    /// namespaces in scope, to resolve qualified names in attribute values
+    pub namespace: Namespace,
+    pub children: Vec<Node>,
+    pub text: String,
+    /// path of the element within its file
+    pub path: String,
+    position: TextPosition,
+    /// index of the file of the element in [`Document::sources`]
+    source: usize,
+}
+
+impl Node {
+    /// value of an unqualified attribute
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| {
+                attribute.name.namespace.is_none() && attribute.name.local_name == name
+            })
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    /// true if the element has this namespace and local name
+    pub fn is(&self, namespace: &str, local_name: &str) -> bool {
+        self.name.namespace.as_deref() == Some(namespace) && self.name.local_name == local_name
+    }
+}
+
+/// A file an element comes from
+#[derive(Debug, Clone)]
+struct Source {
+    map: SourceMap,
+    /// where the file is included from, for included files
+    note: Option<String>,
+}
+
+/// A tree of elements, with the files they come from
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    pub root: Node,
+    sources: Vec<Source>,
+}
+
+impl Document {
+    /// read a file, replacing `xi:include` elements by the elements they include; included
+    /// files are searched next to the including file, then in the directories of `include_path`
+    pub fn read(file: &str, text: &str, include_path: &[PathBuf]) -> Result<Self, Box<Diagnostic>> {
+        let mut reader = TreeReader {
+            include_path,
+            sources: Vec::new(),
+        };
+        let chain = fs::canonicalize(file)
+            .map(|canonical| (canonical, file.to_string()))
+            .into_iter()
+            .collect();
+        let root = reader.read(file, text, None, chain)?;
+        Ok(Document {
+            root,
+            sources: reader.sources,
+        })
+    }
+
+    /// locate a diagnostic at an element, it has the path of the diagnostic; the root element
+    /// has no path, so it is located by its position
+    pub fn locate(&self, node: &Node, diagnostic: Diagnostic) -> Diagnostic {
+        let source = &self.sources[node.source];
+        let diagnostic = match (
+            &diagnostic.path,
+            source.map.span_at(
+                node.position.row as usize + 1,
+                node.position.column as usize + 2,
+            ),
+        ) {
+            (None, Some(span)) => diagnostic.with_span(Span {
+                length: node.name.local_name.chars().count(),
+                ..span
+            }),
+            _ => diagnostic,
+        };
+        let diagnostic = source.map.locate(diagnostic);
+        match &source.note {
+            Some(note) => diagnostic.with_note(note.clone()),
+            None => diagnostic,
+        }
+    }
+}
+
+/// Reader of the files of a document
+struct TreeReader<'a> {
+    include_path: &'a [PathBuf],
+    sources: Vec<Source>,
+}
+
+impl TreeReader<'_> {
+    /// read the root element of a file; `chain` has the canonical and displayed paths of the
+    /// files including this one, and of this one
+    fn read(
+        &mut self,
+        file: &str,
+        text: &str,
+        note: Option<String>,
+        chain: Vec<(PathBuf, String)>,
+    ) -> Result<Node, Box<Diagnostic>> {
+        let config = ParserConfig::new()
+            .trim_whitespace(true)
+            .whitespace_to_characters(true)
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .coalesce_characters(true);
+        let mut events = EventReader::new_with_config(text.as_bytes(), config);
+        let mut index = Index::new(text);
+        let source = self.sources.len();
+        let mut stack: Vec<Node> = Vec::new();
+        let mut root = None;
+        loop {
+            let event = events.next().map_err(|error| {
+                let map = SourceMap::new(file, text);
+                Box::new(parse_error(&map, &error))
+            })?;
+            match event {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } => stack.push(Node {
+                    path: index.start(text, events.position(), &name, &attributes),
+                    name,
+                    attributes,
+                    namespace,
+                    children: Vec::new(),
+                    text: String::new(),
+                    position: events.position(),
+                    source,
+                }),
+                XmlEvent::EndElement { .. } => {
+                    index.end();
+                    let Some(node) = stack.pop() else {
+                        return Err(Box::new(
+                            Diagnostic::error("unexpected end of element").in_file(file),
+                        ));
+                    };
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(node),
+                        None => root = Some(node),
+                    }
+                }
+                XmlEvent::Characters(characters) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text.push_str(&characters);
+                    }
+                }
+                XmlEvent::EndDocument => break,
+                _ => (),
+            }
+        }
+        self.sources.push(Source {
+            map: SourceMap::from_index(file, text, index),
+            note,
+        });
+        let mut root =
+            root.ok_or_else(|| Box::new(Diagnostic::error("no root element").in_file(file)))?;
+        self.include(&mut root, file, &chain)?;
+        Ok(root)
+    }
+
+    /// replace the `xi:include` elements among the descendants of a node
+    fn include(
+        &mut self,
+        node: &mut Node,
+        file: &str,
+        chain: &[(PathBuf, String)],
+    ) -> Result<(), Box<Diagnostic>> {
+        for child in node.children.iter_mut() {
+            if child.is(XINCLUDE_NAMESPACE, "include") {
+                *child = self.included(child, file, chain)?;
+            } else {
+                self.include(child, file, chain)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the element an `xi:include` refers to
+    fn included(
+        &mut self,
+        include: &Node,
+        file: &str,
+        chain: &[(PathBuf, String)],
+    ) -> Result<Node, Box<Diagnostic>> {
+        let source = self.sources[include.source].map.clone();
+        let error = |message: String| {
+            Box::new(source.locate(Diagnostic::error(message).at(include.path.clone())))
+        };
+        let href = include
+            .attribute("href")
+            .ok_or_else(|| error("missing attribute `href` in `include`".to_string()))?;
+        let sequence = match include.attribute("xpointer") {
+            None => vec![1],
+            Some(xpointer) => child_sequence(xpointer)
+                .ok_or_else(|| error(format!("unsupported XPointer `{}`", xpointer)))?,
+        };
+        if let Some(parse) = include.attribute("parse").filter(|parse| *parse != "xml") {
+            return Err(error(format!(
+                "unsupported include of {} `{}`",
+                parse, href
+            )));
+        }
+
+        let path = Path::new(file)
+            .parent()
+            .into_iter()
+            .chain(self.include_path.iter().map(PathBuf::as_path))
+            .map(|directory| directory.join(href))
+            .find(|path| path.is_file())
+            .ok_or_else(|| error(format!("included file `{}` not found", href)))?;
+        let canonical = fs::canonicalize(&path)
+            .map_err(|e| error(format!("can not read `{}`: {}", path.display(), e)))?;
+        let included = path.display().to_string();
+        if let Some(start) = chain.iter().position(|(c, _)| *c == canonical) {
+            let cycle: Vec<_> = chain[start..]
+                .iter()
+                .map(|(_, file)| file.as_str())
+                .chain([included.as_str()])
+                .collect();
+            return Err(error(format!("include cycle {}", cycle.join(" -> "))));
+        }
+        let text = fs::read_to_string(&path)
+            .map_err(|e| error(format!("can not read `{}`: {}", included, e)))?;
+
+        let note = format!(
+            "included from {}:{}:{}",
+            file,
+            include.position.row + 1,
+            include.position.column + 1
+        );
+        let mut chain = chain.to_vec();
+        chain.push((canonical, included.clone()));
+        let root = self.read(&included, &text, Some(note), chain)?;
+
+        // the root element is `[1]`
+        let mut selected = Some(&root).filter(|_| sequence[0] == 1);
+        for step in sequence[1..].iter() {
+            selected = selected.and_then(|node| node.children.get(step - 1));
+        }
+        selected
+            .cloned()
+            .ok_or_else(|| error("the XPointer selects no element".to_string()))
+    }
+}
@@ -0,0 +1,28 @@
+//! Schema validation for SEDS-rs
+//!
+//! Datasheets and package files are checked against the SEDS schema, or against a user-defined
+//! DoT extending it (Req 3.2.6), before they are parsed and resolved. Violations are returned as
+//! diagnostics located in the file they are in, like the errors of the parser.
+//!
+//! The rules the schema can not express are checked on the resolved models by the
+//! [`SemanticValidator`].
+/// element trees of XML files
+mod document;
+/// XSD schemas
+pub mod schema;
+/// semantic checks of resolved package files
+pub mod semantic;
+/// the validator
+pub mod validator;
+
+pub use schema::Schema;
+pub use semantic::SemanticValidator;
+pub use validator::Validator;
+
+use crate::diagnostic::Diagnostic;
+
+/// validate the contents of a file against the built-in SEDS schema
+pub fn validate(file: &str, contents: &str) -> Result<(), Vec<Diagnostic>> {
+    let schema = Schema::seds().map_err(|d| vec![*d])?;
+    Validator::new(&schema, file).validate(contents)
+}
@@ -0,0 +1,787 @@
+//! XSD schemas
+//!
+//! A [`Schema`] holds the declarations of a set of XSD files, read from the subset of XSD the
+//! SEDS schema is written in: global and local element declarations, complex types with
+//! sequences, choices and complex content extensions, attributes, attribute wildcards and
+//! attribute groups, and simple types restricted by enumerations, patterns and inclusive bounds, or unions of them.
+//!
+//! The SEDS schema of `eds/schema` is built in. A user-defined DoT (Req 3.2.6) is an extension
+//! of it: a schema that may include the SEDS schema files and (re)defines types, typically the
+//! `Ext*` types and the `ExtensionSemanticsAttributeGroup` of `seds-extension-semantics.xsd`.
+//! Definitions read later replace the definitions of the same name read before.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::diagnostic::Diagnostic;
+
+use super::document::{Document, Node};
+
+/// namespace of XSD
+pub const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// namespace of the `xml:` attributes, whose schema is not read
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// the files of the SEDS schema
+const SEDS_SCHEMA: [(&str, &str); 3] = [
+    ("seds.xsd", include_str!("../../eds/schema/seds.xsd")),
+    (
+        "seds-core-semantics.xsd",
+        include_str!("../../eds/schema/seds-core-semantics.xsd"),
+    ),
+    (
+        "seds-extension-semantics.xsd",
+        include_str!("../../eds/schema/seds-extension-semantics.xsd"),
+    ),
+];
+
+/// An expanded name, the namespace is empty for unqualified names
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct QName {
+    pub namespace: String,
+    pub local: String,
+}
+
+impl QName {
+    pub fn new(namespace: &str, local: &str) -> Self {
+        QName {
+            namespace: namespace.to_string(),
+            local: local.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for QName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.namespace == XSD_NAMESPACE {
+            write!(f, "xs:{}", self.local)
+        } else {
+            f.write_str(&self.local)
+        }
+    }
+}
+
+/// An element declaration
+#[derive(Debug, Clone)]
+pub(crate) struct ElementDecl {
+    pub name: QName,
+    pub type_: TypeRef,
+}
+
+/// The type of an element
+#[derive(Debug, Clone)]
+pub(crate) enum TypeRef {
+    Named(QName),
+    Complex(Box<ComplexType>),
+    Simple(Box<SimpleType>),
+    /// an element without a type accepts any content
+    Any,
+}
+
+/// A complex type, with the content and attributes it adds to its base type
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ComplexType {
+    pub base: Option<QName>,
+    pub is_abstract: bool,
+    pub content: Option<Particle>,
+    pub attributes: Vec<AttributeItem>,
+}
+
+/// A term of a content model, with its number of occurrences
+#[derive(Debug, Clone)]
+pub(crate) struct Particle {
+    pub term: Term,
+    pub min: usize,
+    /// unbounded if missing
+    pub max: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Term {
+    Element(ElementDecl),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum AttributeItem {
+    Attribute(AttributeDecl),
+    Group(QName),
+    Any(Wildcard),
+}
+
+/// An `xs:anyAttribute`, which allows the attributes of some namespaces that are not declared
+#[derive(Debug, Clone)]
+pub(crate) struct Wildcard {
+    pub namespaces: Namespaces,
+    pub process: Process,
+}
+
+impl Wildcard {
+    /// true if the wildcard allows the attributes of a namespace, empty for unqualified ones
+    pub fn allows(&self, namespace: &str) -> bool {
+        match &self.namespaces {
+            Namespaces::Any => true,
+            Namespaces::Other(target) => !namespace.is_empty() && namespace != target,
+            Namespaces::List(namespaces) => namespaces.iter().any(|n| n == namespace),
+        }
+    }
+}
+
+/// The namespaces a wildcard allows
+#[derive(Debug, Clone)]
+pub(crate) enum Namespaces {
+    /// `##any`
+    Any,
+    /// `##other`, any namespace but the target namespace of the schema and no namespace
+    Other(String),
+    /// a list of namespaces, empty for `##local`
+    List(Vec<String>),
+}
+
+/// How the attributes a wildcard allows are validated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Process {
+    /// against their global declaration, which must exist
+    Strict,
+    /// against their global declaration if there is one
+    Lax,
+    /// not at all
+    Skip,
+}
+
+/// An attribute declaration
+#[derive(Debug, Clone)]
+pub(crate) struct AttributeDecl {
+    pub name: QName,
+    /// any value is valid if missing
+    pub type_: Option<SimpleRef>,
+    pub required: bool,
+}
+
+/// A simple type, by name or inline
+#[derive(Debug, Clone)]
+pub(crate) enum SimpleRef {
+    Named(QName),
+    Inline(Box<SimpleType>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SimpleType {
+    Restriction {
+        base: SimpleRef,
+        enumeration: Vec<String>,
+        patterns: Vec<(String, Regex)>,
+        min_inclusive: Option<f64>,
+        max_inclusive: Option<f64>,
+    },
+    Union(Vec<SimpleRef>),
+}
+
+/// The declarations of a set of XSD files
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    elements: HashMap<QName, ElementDecl>,
+    complex_types: HashMap<QName, ComplexType>,
+    simple_types: HashMap<QName, SimpleType>,
+    attribute_groups: HashMap<QName, Vec<AttributeItem>>,
+    attributes: HashMap<QName, AttributeDecl>,
+    /// the files read so far, to read each of them once
+    files: HashSet<PathBuf>,
+}
+
+impl Schema {
+    /// the built-in SEDS schema
+    pub fn seds() -> Result<Self, Box<Diagnostic>> {
+        let mut schema = Schema::default();
+        schema.read(Path::new(SEDS_SCHEMA[0].0), SEDS_SCHEMA[0].1, None, true)?;
+        Ok(schema)
+    }
+
+    /// read a schema file and the files it includes or imports
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<Diagnostic>> {
+        Schema::default().extend(path)
+    }
+
+    /// add the declarations of a schema file to this schema, like a user-defined DoT; they
+    /// replace the declarations of the same name. The files of the SEDS schema it includes are
+    /// read from next to it if they exist there, they are built in otherwise.
+    pub fn extend(mut self, path: impl AsRef<Path>) -> Result<Self, Box<Diagnostic>> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| {
+            Box::new(Diagnostic::error(format!(
+                "can not read `{}`: {}",
+                path.display(),
+                e
+            )))
+        })?;
+        self.read(path, &text, None, false)?;
+        Ok(self)
+    }
+
+    /// the global declaration of an element
+    pub(crate) fn element(&self, name: &QName) -> Option<&ElementDecl> {
+        self.elements.get(name)
+    }
+
+    pub(crate) fn complex_type(&self, name: &QName) -> Option<&ComplexType> {
+        self.complex_types.get(name)
+    }
+
+    /// true if a simple type of this name is declared or built in
+    pub(crate) fn is_simple(&self, name: &QName) -> bool {
+        name.namespace == XSD_NAMESPACE || self.simple_types.contains_key(name)
+    }
+
+    /// the global declaration of an attribute
+    pub(crate) fn attribute(&self, name: &QName) -> Option<&AttributeDecl> {
+        self.attributes.get(name)
+    }
+
+    /// the attributes and attribute group references of an attribute group
+    pub(crate) fn attribute_group(&self, name: &QName) -> &[AttributeItem] {
+        self.attribute_groups
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// read a schema file; `target` is the target namespace of the including file, the files
+    /// a built-in file includes are built in too
+    fn read(
+        &mut self,
+        path: &Path,
+        text: &str,
+        target: Option<&str>,
+        built_in: bool,
+    ) -> Result<(), Box<Diagnostic>> {
+        let file = path.display().to_string();
+        let key = match built_in {
+            true => Path::new("<built-in>").join(path),
+            false => fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()),
+        };
+        if !self.files.insert(key) {
+            return Ok(());
+        }
+        // the byte order mark of the built-in files
+        let text = text.trim_start_matches('\u{feff}');
+        let document = Document::read(&file, text, &[])?;
+        let root = &document.root;
+        if !root.is(XSD_NAMESPACE, "schema") {
+            return Err(Box::new(document.locate(
+                root,
+                Diagnostic::error(format!("`{}` is not an XSD schema", file)),
+            )));
+        }
+        let reader = SchemaReader {
+            document: &document,
+            target: root
+                .attribute("targetNamespace")
+                .or(target)
+                .unwrap_or_default()
+                .to_string(),
+            qualified_elements: root.attribute("elementFormDefault") == Some("qualified"),
+            qualified_attributes: root.attribute("attributeFormDefault") == Some("qualified"),
+        };
+
+        for node in root.children.iter() {
+            match node.name.local_name.as_str() {
+                "include" | "import" => {
+                    // the schema of the xml: attributes is not read, they are accepted as they are
+                    if node.attribute("namespace") == Some(XML_NAMESPACE) {
+                        continue;
+                    }
+                    let Some(location) = node.attribute("schemaLocation") else {
+                        continue;
+                    };
+                    let target = match node.name.local_name.as_str() {
+                        "include" => Some(reader.target.clone()),
+                        _ => None,
+                    };
+                    let included = path.parent().unwrap_or(Path::new("")).join(location);
+                    let seds = SEDS_SCHEMA.iter().find(|(name, _)| *name == location);
+                    let text = match built_in {
+                        true => Err(std::io::ErrorKind::NotFound.into()),
+                        false => fs::read_to_string(&included),
+                    };
+                    match (text, seds) {
+                        (Ok(text), _) => self.read(&included, &text, target.as_deref(), false)?,
+                        (Err(_), Some((name, text))) => {
+                            self.read(Path::new(name), text, target.as_deref(), true)?
+                        }
+                        (Err(e), None) => {
+                            return Err(reader.error(
+                                node,
+                                format!("can not read `{}`: {}", included.display(), e),
+                            ))
+                        }
+                    }
+                }
+                "element" => {
+                    let element = reader.element(node, true)?;
+                    let _ = self.elements.insert(element.name.clone(), element);
+                }
+                "complexType" => {
+                    let name = reader.name(node, true)?;
+                    let complex_type = reader.complex_type(node)?;
+                    let _ = self.complex_types.insert(name, complex_type);
+                }
+                "simpleType" => {
+                    let name = reader.name(node, true)?;
+                    let simple_type = reader.simple_type(node)?;
+                    let _ = self.simple_types.insert(name, simple_type);
+                }
+                "attribute" => {
+                    let attribute = reader.attribute(node, true)?;
+                    let _ = self.attributes.insert(attribute.name.clone(), attribute);
+                }
+                "attributeGroup" => {
+                    let name = reader.name(node, true)?;
+                    let attributes = reader.attributes(node)?;
+                    let _ = self.attribute_groups.insert(name, attributes);
+                }
+                "annotation" => (),
+                other => {
+                    return Err(reader.error(node, format!("unsupported XSD element `{}`", other)))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reader of the declarations of a schema file
+struct SchemaReader<'d> {
+    document: &'d Document,
+    target: String,
+    qualified_elements: bool,
+    qualified_attributes: bool,
+}
+
+impl SchemaReader<'_> {
+    fn error(&self, node: &Node, message: String) -> Box<Diagnostic> {
+        Box::new(
+            self.document
+                .locate(node, Diagnostic::error(message).at(node.path.clone())),
+        )
+    }
+
+    /// the XSD children of a node, without annotations
+    fn children<'n>(&self, node: &'n Node) -> impl Iterator<Item = &'n Node> {
+        node.children.iter().filter(|child| {
+            child.name.namespace.as_deref() == Some(XSD_NAMESPACE)
+                && child.name.local_name != "annotation"
+        })
+    }
+
+    /// the name declared by a node, in the target namespace if it is global or qualified
+    fn name(&self, node: &Node, qualified: bool) -> Result<QName, Box<Diagnostic>> {
+        let local = node.attribute("name").ok_or_else(|| {
+            self.error(
+                node,
+                format!("missing attribute `name` in `{}`", node.name.local_name),
+            )
+        })?;
+        let namespace = if qualified { self.target.as_str() } else { "" };
+        Ok(QName::new(namespace, local))
+    }
+
+    /// a qualified name in an attribute value, like `seds:NameType`
+    fn reference(&self, node: &Node, value: &str) -> Result<QName, Box<Diagnostic>> {
+        let (prefix, local) = value.split_once(':').unwrap_or(("", value));
+        match node.namespace.get(prefix) {
+            Some(namespace) => Ok(QName::new(namespace, local)),
+            None if prefix.is_empty() => Ok(QName::new("", local)),
+            None => Err(self.error(node, format!("unknown namespace prefix `{}`", prefix))),
+        }
+    }
+
+    fn element(&self, node: &Node, global: bool) -> Result<ElementDecl, Box<Diagnostic>> {
+        let name = self.name(node, global || self.qualified_elements)?;
+        let inline = self
+            .children(node)
+            .find(|child| matches!(child.name.local_name.as_str(), "complexType" | "simpleType"));
+        let type_ = match (node.attribute("type"), inline) {
+            (Some(type_), _) => TypeRef::Named(self.reference(node, type_)?),
+            (None, Some(inline)) if inline.name.local_name == "complexType" => {
+                TypeRef::Complex(Box::new(self.complex_type(inline)?))
+            }
+            (None, Some(inline)) => TypeRef::Simple(Box::new(self.simple_type(inline)?)),
+            (None, None) => TypeRef::Any,
+        };
+        Ok(ElementDecl { name, type_ })
+    }
+
+    fn complex_type(&self, node: &Node) -> Result<ComplexType, Box<Diagnostic>> {
+        let mut complex_type = ComplexType {
+            is_abstract: node.attribute("abstract") == Some("true"),
+            ..ComplexType::default()
+        };
+        let mut definition = node;
+        if let Some(content) = self
+            .children(node)
+            .find(|child| child.name.local_name == "complexContent")
+        {
+            let Some(extension) = self
+                .children(content)
+                .find(|child| child.name.local_name == "extension")
+            else {
+                return Err(self.error(
+                    content,
+                    "unsupported complex content, only extensions are".to_string(),
+                ));
+            };
+            let base = extension.attribute("base").ok_or_else(|| {
+                self.error(
+                    extension,
+                    "missing attribute `base` in `extension`".to_string(),
+                )
+            })?;
+            complex_type.base = Some(self.reference(extension, base)?);
+            definition = extension;
+        }
+        for child in self.children(definition) {
+            match child.name.local_name.as_str() {
+                "sequence" | "choice" => complex_type.content = Some(self.particle(child)?),
+                "attribute" | "attributeGroup" | "complexContent" | "anyAttribute" => (),
+                other => {
+                    return Err(self.error(child, format!("unsupported XSD element `{}`", other)))
+                }
+            }
+        }
+        complex_type.attributes = self.attributes(definition)?;
+        Ok(complex_type)
+    }
+
+    fn particle(&self, node: &Node) -> Result<Particle, Box<Diagnostic>> {
+        let occurs = |attribute: &str, default: usize| match node.attribute(attribute) {
+            None => Ok(Some(default)),
+            Some("unbounded") => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| self.error(node, format!("invalid `{}` `{}`", attribute, value))),
+        };
+        let min = occurs("minOccurs", 1)?.unwrap_or_default();
+        let max = occurs("maxOccurs", 1)?;
+        let term = match node.name.local_name.as_str() {
+            "element" => Term::Element(self.element(node, false)?),
+            "sequence" | "choice" => {
+                let particles = self
+                    .children(node)
+                    .map(|child| self.particle(child))
+                    .collect::<Result<_, _>>()?;
+                match node.name.local_name.as_str() {
+                    "sequence" => Term::Sequence(particles),
+                    _ => Term::Choice(particles),
+                }
+            }
+            other => return Err(self.error(node, format!("unsupported XSD element `{}`", other))),
+        };
+        Ok(Particle { term, min, max })
+    }
+
+    /// the attribute declarations and attribute group references of a node
+    fn attributes(&self, node: &Node) -> Result<Vec<AttributeItem>, Box<Diagnostic>> {
+        let mut attributes = Vec::new();
+        for child in self.children(node) {
+            match child.name.local_name.as_str() {
+                "attribute" => {
+                    attributes.push(AttributeItem::Attribute(self.attribute(child, false)?))
+                }
+                "attributeGroup" => {
+                    let group = child.attribute("ref").ok_or_else(|| {
+                        self.error(
+                            child,
+                            "missing attribute `ref` in `attributeGroup`".to_string(),
+                        )
+                    })?;
+                    attributes.push(AttributeItem::Group(self.reference(child, group)?));
+                }
+                "anyAttribute" => attributes.push(AttributeItem::Any(self.wildcard(child)?)),
+                _ => (),
+            }
+        }
+        Ok(attributes)
+    }
+
+    fn attribute(&self, node: &Node, global: bool) -> Result<AttributeDecl, Box<Diagnostic>> {
+        let required = node.attribute("use") == Some("required");
+        if let Some(reference) = node.attribute("ref") {
+            // a reference to a global attribute, like xml:base
+            return Ok(AttributeDecl {
+                name: self.reference(node, reference)?,
+                type_: None,
+                required,
+            });
+        }
+        let inline = self
+            .children(node)
+            .find(|child| child.name.local_name == "simpleType");
+        let type_ = match (node.attribute("type"), inline) {
+            (Some(type_), _) => Some(SimpleRef::Named(self.reference(node, type_)?)),
+            (None, Some(inline)) => Some(SimpleRef::Inline(Box::new(self.simple_type(inline)?))),
+            (None, None) => None,
+        };
+        Ok(AttributeDecl {
+            name: self.name(node, global || self.qualified_attributes)?,
+            type_,
+            required,
+        })
+    }
+
+    fn wildcard(&self, node: &Node) -> Result<Wildcard, Box<Diagnostic>> {
+        let namespaces = match node.attribute("namespace").unwrap_or("##any").trim() {
+            "##any" => Namespaces::Any,
+            "##other" => Namespaces::Other(self.target.clone()),
+            list => Namespaces::List(
+                list.split_whitespace()
+                    .map(|namespace| match namespace {
+                        "##local" => String::new(),
+                        "##targetNamespace" => self.target.clone(),
+                        namespace => namespace.to_string(),
+                    })
+                    .collect(),
+            ),
+        };
+        let process = match node.attribute("processContents").unwrap_or("strict") {
+            "strict" => Process::Strict,
+            "lax" => Process::Lax,
+            "skip" => Process::Skip,
+            other => return Err(self.error(node, format!("invalid `processContents` `{}`", other))),
+        };
+        Ok(Wildcard {
+            namespaces,
+            process,
+        })
+    }
+
+    fn simple_type(&self, node: &Node) -> Result<SimpleType, Box<Diagnostic>> {
+        let Some(definition) = self.children(node).next() else {
+            return Err(self.error(node, "empty simple type".to_string()));
+        };
+        match definition.name.local_name.as_str() {
+            "restriction" => self.restriction(definition),
+            "union" => {
+                let mut members = Vec::new();
+                for member in definition
+                    .attribute("memberTypes")
+                    .unwrap_or_default()
+                    .split_whitespace()
+                {
+                    members.push(SimpleRef::Named(self.reference(definition, member)?));
+                }
+                for inline in self.children(definition) {
+                    members.push(SimpleRef::Inline(Box::new(self.simple_type(inline)?)));
+                }
+                Ok(SimpleType::Union(members))
+            }
+            other => Err(self.error(definition, format!("unsupported XSD element `{}`", other))),
+        }
+    }
+
+    fn restriction(&self, node: &Node) -> Result<SimpleType, Box<Diagnostic>> {
+        let base = match node.attribute("base") {
+            Some(base) => SimpleRef::Named(self.reference(node, base)?),
+            None => match self
+                .children(node)
+                .find(|child| child.name.local_name == "simpleType")
+            {
+                Some(inline) => SimpleRef::Inline(Box::new(self.simple_type(inline)?)),
+                None => {
+                    return Err(self.error(
+                        node,
+                        "missing attribute `base` in `restriction`".to_string(),
+                    ))
+                }
+            },
+        };
+        let mut enumeration = Vec::new();
+        let mut patterns = Vec::new();
+        let mut min_inclusive = None;
+        let mut max_inclusive = None;
+        for facet in self.children(node) {
+            let value = facet.attribute("value").unwrap_or_default();
+            let bound = || {
+                value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| self.error(facet, format!("invalid bound `{}`", value)))
+            };
+            match facet.name.local_name.as_str() {
+                "enumeration" => enumeration.push(value.to_string()),
+                "pattern" => {
+                    // patterns match whole values
+                    let regex = Regex::new(&format!("^(?:{})$", value))
+                        .map_err(|e| self.error(facet, format!("unsupported pattern: {}", e)))?;
+                    patterns.push((value.to_string(), regex));
+                }
+                "minInclusive" => min_inclusive = Some(bound()?),
+                "maxInclusive" => max_inclusive = Some(bound()?),
+                "simpleType" => (),
+                other => return Err(self.error(facet, format!("unsupported facet `{}`", other))),
+            }
+        }
+        Ok(SimpleType::Restriction {
+            base,
+            enumeration,
+            patterns,
+            min_inclusive,
+            max_inclusive,
+        })
+    }
+}
+
+impl Schema {
+    /// check a value against a simple type, the error tells why it is not valid
+    pub(crate) fn check(&self, type_: &SimpleRef, value: &str) -> Result<(), String> {
+        match type_ {
+            SimpleRef::Inline(simple_type) => self.check_simple(simple_type, value, None),
+            SimpleRef::Named(name) if name.namespace == XSD_NAMESPACE => {
+                built_in(&name.local, value)
+            }
+            SimpleRef::Named(name) => match self.simple_types.get(name) {
+                Some(simple_type) => self.check_simple(simple_type, value, Some(name)),
+                None => Err(format!("unknown type `{}`", name)),
+            },
+        }
+    }
+
+    fn check_simple(
+        &self,
+        simple_type: &SimpleType,
+        value: &str,
+        name: Option<&QName>,
+    ) -> Result<(), String> {
+        let of = name
+            .map(|name| format!(" of `{}`", name))
+            .unwrap_or_default();
+        match simple_type {
+            SimpleType::Restriction {
+                base,
+                enumeration,
+                patterns,
+                min_inclusive,
+                max_inclusive,
+            } => {
+                self.check(base, value)?;
+                if !enumeration.is_empty() && !enumeration.iter().any(|e| e == value) {
+                    return Err(match enumeration.len() {
+                        n if n <= 8 => {
+                            format!("`{}` is not one of {}", value, enumeration.join(", "))
+                        }
+                        _ => format!("`{}` is not a value{}", value, of),
+                    });
+                }
+                if let Some((pattern, _)) =
+                    patterns.iter().find(|(_, regex)| !regex.is_match(value))
+                {
+                    return Err(format!(
+                        "`{}` does not match the pattern `{}`{}",
+                        value, pattern, of
+                    ));
+                }
+                let number = || value.trim().parse::<f64>().unwrap_or(f64::NAN);
+                match (min_inclusive, max_inclusive) {
+                    (Some(min), _) if number() < *min => Err(format!(
+                        "`{}` is less than the minimum {}{}",
+                        value, min, of
+                    )),
+                    (_, Some(max)) if number() > *max => Err(format!(
+                        "`{}` is more than the maximum {}{}",
+                        value, max, of
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            SimpleType::Union(members) => {
+                if members
+                    .iter()
+                    .any(|member| self.check(member, value).is_ok())
+                {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "`{}` is not a valid `{}`",
+                        value,
+                        name.map(|n| n.local.as_str()).unwrap_or("union")
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// check a value against a built-in XSD type; the types SEDS does not use accept any value
+fn built_in(type_: &str, value: &str) -> Result<(), String> {
+    let collapsed = value.trim();
+    let integer = |min: i128, max: i128| {
+        collapsed
+            .strip_prefix('+')
+            .unwrap_or(collapsed)
+            .parse::<i128>()
+            .ok()
+            .filter(|n| (min..=max).contains(n))
+            .is_some()
+    };
+    let valid = match type_ {
+        "boolean" => matches!(collapsed, "true" | "false" | "1" | "0"),
+        "integer" => integer(i128::MIN, i128::MAX),
+        "long" => integer(i64::MIN.into(), i64::MAX.into()),
+        "int" => integer(i32::MIN.into(), i32::MAX.into()),
+        "short" => integer(i16::MIN.into(), i16::MAX.into()),
+        "byte" => integer(i8::MIN.into(), i8::MAX.into()),
+        "nonNegativeInteger" => integer(0, i128::MAX),
+        "positiveInteger" => integer(1, i128::MAX),
+        "unsignedLong" => integer(0, u64::MAX.into()),
+        "unsignedInt" => integer(0, u32::MAX.into()),
+        "unsignedShort" => integer(0, u16::MAX.into()),
+        "unsignedByte" => integer(0, u8::MAX.into()),
+        "double" | "float" => {
+            matches!(collapsed, "INF" | "-INF" | "NaN")
+                || (collapsed.parse::<f64>().is_ok() && !collapsed.contains(['i', 'I', 'n', 'N']))
+        }
+        "decimal" => {
+            collapsed.parse::<f64>().is_ok()
+                && collapsed
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || "+-.".contains(c))
+        }
+        "date" => date(collapsed),
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("`{}` is not a valid xs:{}", value, type_))
+    }
+}
+
+/// an xs:date, like `2023-07-31` with an optional timezone
+fn date(value: &str) -> bool {
+    let digits =
+        |part: &str, len: usize| part.len() >= len && part.chars().all(|c| c.is_ascii_digit());
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let (date, zone) = match value.find(['Z', '+']).or_else(|| {
+        value
+            .get(8..)
+            .and_then(|rest| rest.find('-').map(|i| i + 8))
+    }) {
+        Some(i) => value.split_at(i),
+        None => (value, ""),
+    };
+    let zone_valid = match zone.split_at(zone.len().min(1)) {
+        ("", _) | ("Z", "") => true,
+        ("+" | "-", offset) => {
+            matches!(offset.split_once(':'), Some((h, m)) if h.len() == 2 && m.len() == 2 && digits(h, 2) && digits(m, 2))
+        }
+        _ => false,
+    };
+    let parts: Vec<&str> = date.rsplitn(3, '-').collect();
+    zone_valid
+        && matches!(parts.as_slice(), [day, month, year] if digits(year, 4) && month.len() == 2 && digits(month, 2) && day.len() == 2 && digits(day, 2))
+}
@@ -0,0 +1,683 @@
+//! Semantic Checks of Resolved Package Files
+//!
+//! The schema can not express the rules that relate containers to their base containers and
+//! to the types of their entries. They are checked on the resolved models:
+//!
+//! - the names of the entries of a container are unique within the container and its base
+//!   containers (Req 3.10.16)
+//! - each constraint names an entry of a base container, like `Hdr` or `Hdr.SecHdrFlags` for
+//!   an entry of a container entry (Req 3.10.7)
+//! - the type of a `TypeConstraint` is numeric, with a range included in the range of the
+//!   type of the entry (Req 3.10.9)
+//! - the value of a `ValueConstraint` is a literal of the type of the entry (Req 3.10.10),
+//!   within its range and the values its encoding can hold
+//!
+//! Types and base containers are looked up across all the package files given to the
+//! [`SemanticValidator`] with [`Layouts`], like for lints and compatibility reports, and
+//! violations are located by the path of the element or attribute they are in:
+//!
+//! ```text
+//! error: value `5` of the constraint on `Hdr.SecHdrFlags` in `CommandPacket` is not a valid `SecHdrFlags`: 5 is outside [0, 3]
+//!    = in Package[CCSDS]/DataTypeSet/ContainerDataType[CommandPacket]/ConstraintSet/ValueConstraint@value
+//! ```
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::diagnostic::Diagnostic;
+use crate::eds::ast::{
+    ComponentSet, Constraint, ContainerDataType, DataType, EntryElement, EntryList,
+    EnumeratedDataType, FloatDataEncoding, FloatEncodingAndPrecision, IntegerDataEncoding,
+    IntegerEncoding, MetaData, MinMaxRange, MinMaxRangeType, Package, PackageFile, Range,
+};
+use crate::eds::layout::{LayoutError, Layouts, MAX_DEPTH};
+use crate::eds::value::{parse_integer, Value};
+use crate::eds::visit::{walk_package, Path, Visitor};
+
+/// Validator of the semantic rules of resolved package files
+#[derive(Debug, Clone)]
+pub struct SemanticValidator<'a> {
+    layouts: Layouts<'a>,
+}
+
+impl<'a> SemanticValidator<'a> {
+    /// a validator that looks up types in the packages of the given files
+    pub fn new(package_files: &[&'a PackageFile]) -> Self {
+        SemanticValidator {
+            layouts: Layouts::new(package_files),
+        }
+    }
+
+    /// check the containers of a package file; the violations are returned in document order,
+    /// with paths within the file
+    pub fn validate(&self, package_file: &'a PackageFile) -> Result<(), Vec<Diagnostic>> {
+        let mut containers = Containers {
+            validator: self,
+            package: None,
+            diagnostics: Vec::new(),
+        };
+        containers.visit_package_file(&Path::default(), package_file);
+        match containers.diagnostics.is_empty() {
+            true => Ok(()),
+            false => Err(containers.diagnostics),
+        }
+    }
+
+    /// a data type by its qualified name, relative to the package it is referenced from
+    fn lookup(&self, package: &'a Package, name: &str) -> Option<(&'a Package, &'a DataType)> {
+        self.layouts.lookup(package, name)
+    }
+
+    /// the base containers of a container, nearest first, with the packages they are in
+    fn bases(
+        &self,
+        package: &'a Package,
+        container: &'a ContainerDataType,
+    ) -> Result<Vec<Scope<'a>>, String> {
+        match self.layouts.bases(package, container) {
+            Ok(bases) => Ok(bases
+                .into_iter()
+                .map(|(package, container)| Scope { package, container })
+                .collect()),
+            Err(LayoutError::UnknownType(base_type)) => Err(format!(
+                "unknown base type `{}` of `{}`",
+                base_type,
+                name(container)
+            )),
+            Err(LayoutError::NotAContainer(base_type)) => Err(format!(
+                "base type `{}` of `{}` is not a container",
+                base_type,
+                name(container)
+            )),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// the kind of values of a data type
+    fn kind(&self, package: &'a Package, data_type: &'a DataType, depth: usize) -> Kind<'a> {
+        match data_type {
+            DataType::IntegerDataType(dt) => {
+                Kind::Integer(Interval::of(&dt.range).within(&Interval::integer(&dt.encoding)))
+            }
+            DataType::FloatDataType(dt) => {
+                let encoding = Interval::float(&dt.encoding);
+                match &dt.range {
+                    Some(range) => Kind::Float(Interval::of(range).within(&encoding)),
+                    None => Kind::Float(encoding),
+                }
+            }
+            DataType::BooleanDataType(_) => Kind::Boolean,
+            DataType::EnumeratedDataType(dt) => Kind::Enumerated(dt),
+            DataType::StringDataType(_) => Kind::String,
+            DataType::SubRangeDataType(dt) if depth < MAX_DEPTH => {
+                match self.lookup(package, &dt.base_type.0) {
+                    Some((p, base)) => match self.kind(p, base, depth + 1) {
+                        Kind::Integer(base) => Kind::Integer(Interval::of(&dt.range).within(&base)),
+                        Kind::Float(base) => Kind::Float(Interval::of(&dt.range).within(&base)),
+                        kind => kind,
+                    },
+                    None => Kind::Unknown,
+                }
+            }
+            _ => Kind::Other(data_type.element()),
+        }
+    }
+}
+
+/// Checks the containers of the package files it visits
+struct Containers<'v, 'a> {
+    validator: &'v SemanticValidator<'a>,
+    package: Option<&'a Package>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Visitor<'a> for Containers<'_, 'a> {
+    fn visit_metadata(&mut self, _path: &Path, _metadata: &'a MetaData) {}
+
+    fn visit_package(&mut self, path: &Path, package: &'a Package) {
+        self.package = Some(package);
+        walk_package(self, path, package);
+    }
+
+    fn visit_container_data_type(&mut self, _path: &Path, container: &'a ContainerDataType) {
+        let Some(package) = self.package else {
+            return;
+        };
+        let check = Check {
+            validator: self.validator,
+            package,
+            container,
+            path: format!(
+                "Package[{}]/DataTypeSet/ContainerDataType[{}]",
+                package.name_entity_type.name.0,
+                name(container)
+            ),
+        };
+        check.container(&mut self.diagnostics);
+    }
+
+    fn visit_component_set(&mut self, _path: &Path, _component_set: &'a ComponentSet) {}
+}
+
+/// name of a container
+fn name(container: &ContainerDataType) -> &str {
+    &container.name_entity_type.name.0
+}
+
+/// the entries of a container, then those of its trailer
+fn entries<'a>(
+    container: &'a ContainerDataType,
+) -> impl Iterator<Item = (&'static str, &'a EntryElement)> {
+    let lists: [(&'static str, &'a Option<EntryList>); 2] = [
+        ("EntryList", &container.entry_list),
+        ("TrailerEntryList", &container.trailer_entry_list),
+    ];
+    lists.into_iter().flat_map(|(list, entries)| {
+        entries
+            .iter()
+            .flat_map(|entries| entries.entries.iter())
+            .map(move |entry| (list, entry))
+    })
+}
+
+/// A container, with the package its references are relative to
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    package: &'a Package,
+    container: &'a ContainerDataType,
+}
+
+impl<'a> Scope<'a> {
+    /// the named entry of the container
+    fn entry(&self, entry: &str) -> Option<&'a EntryElement> {
+        entries(self.container)
+            .map(|(_, e)| e)
+            .find(|e| !e.name().is_empty() && e.name() == entry)
+    }
+}
+
+/// The checks of a container
+struct Check<'v, 'a> {
+    validator: &'v SemanticValidator<'a>,
+    package: &'a Package,
+    container: &'a ContainerDataType,
+    path: String,
+}
+
+impl<'a> Check<'_, 'a> {
+    fn container(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let bases = match self.validator.bases(self.package, self.container) {
+            Ok(bases) => bases,
+            Err(message) => {
+                let path = format!("{}@baseType", self.path);
+                diagnostics.push(Diagnostic::error(message).at(path));
+                return;
+            }
+        };
+        self.unique_names(&bases, diagnostics);
+        if let Some(constraint_set) = &self.container.constraint_set {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for constraint in constraint_set.constraints.iter() {
+                let element = match constraint {
+                    Constraint::RangeConstraint(_) => "RangeConstraint",
+                    Constraint::TypeConstraint(_) => "TypeConstraint",
+                    Constraint::ValueConstraint(_) => "ValueConstraint",
+                };
+                // constraints are unnamed, they are numbered among their siblings
+                let count = counts.entry(element).or_insert(0);
+                let path = match *count {
+                    0 => format!("{}/ConstraintSet/{}", self.path, element),
+                    index => format!("{}/ConstraintSet/{}[{}]", self.path, element, index),
+                };
+                *count += 1;
+                if let Err(diagnostic) = self.constraint(&bases, constraint, &path) {
+                    diagnostics.push(*diagnostic);
+                }
+            }
+        }
+    }
+
+    /// Req 3.10.16
+    fn unique_names(&self, bases: &[Scope<'a>], diagnostics: &mut Vec<Diagnostic>) {
+        let mut owners: HashMap<&str, &str> = HashMap::new();
+        for base in bases.iter().rev() {
+            for (_, entry) in entries(base.container) {
+                let _ = owners.entry(entry.name()).or_insert(name(base.container));
+            }
+        }
+        let mut own = HashSet::new();
+        for (list, entry) in entries(self.container) {
+            if entry.name().is_empty() {
+                continue;
+            }
+            let message = if !own.insert(entry.name()) {
+                format!(
+                    "duplicate entry `{}` in `{}`",
+                    entry.name(),
+                    name(self.container)
+                )
+            } else if let Some(owner) = owners.get(entry.name()) {
+                format!(
+                    "entry `{}` of `{}` is already defined in its base container `{}`",
+                    entry.name(),
+                    name(self.container),
+                    owner
+                )
+            } else {
+                continue;
+            };
+            let path = format!(
+                "{}/{}/{}[{}]",
+                self.path,
+                list,
+                entry.element(),
+                entry.name()
+            );
+            diagnostics.push(Diagnostic::error(message).at(path));
+        }
+    }
+
+    fn constraint(
+        &self,
+        bases: &[Scope<'a>],
+        constraint: &'a Constraint,
+        path: &str,
+    ) -> Result<(), Box<Diagnostic>> {
+        let entry = match constraint {
+            Constraint::RangeConstraint(c) => &c.entry.0,
+            Constraint::TypeConstraint(c) => &c.entry.0,
+            Constraint::ValueConstraint(c) => &c.entry.0,
+        };
+        let (scope, target) = self
+            .target(bases, entry)
+            .map_err(|message| Diagnostic::error(message).at(format!("{}@entry", path)))?;
+        let entry_type = match target.type_() {
+            Some(type_) => type_,
+            None => return Ok(()),
+        };
+        let entry_kind = match self.validator.lookup(scope.package, &entry_type.0) {
+            Some((package, data_type)) => self.validator.kind(package, data_type, 0),
+            // unknown types are reported by the code generator
+            None => return Ok(()),
+        };
+        match constraint {
+            // Req 3.10.9
+            Constraint::TypeConstraint(c) => {
+                let error = |message: String| -> Box<Diagnostic> {
+                    Box::new(Diagnostic::error(message).at(format!("{}@type", path)))
+                };
+                let kind = match self.validator.lookup(self.package, &c.type_.0) {
+                    Some((package, data_type)) => self.validator.kind(package, data_type, 0),
+                    None => return Err(error(format!("unknown type `{}`", c.type_.0))),
+                };
+                let range = kind.interval().ok_or_else(|| {
+                    error(format!(
+                        "type `{}` of the constraint on `{}` in `{}` is not numeric",
+                        c.type_.0,
+                        entry,
+                        name(self.container)
+                    ))
+                })?;
+                let entry_range = entry_kind.interval().ok_or_else(|| {
+                    error(format!(
+                        "entry `{}` of a type constraint in `{}` has the non-numeric type `{}`",
+                        entry,
+                        name(self.container),
+                        entry_type.0
+                    ))
+                })?;
+                if !entry_range.includes(range) {
+                    return Err(error(format!(
+                        "range {} of `{}` is not included in the range {} of `{}`, the type of `{}`",
+                        range, c.type_.0, entry_range, entry_type.0, entry
+                    )));
+                }
+            }
+            // Req 3.10.10
+            Constraint::ValueConstraint(c) => {
+                if let Err(reason) = entry_kind.accepts(&c.value.0) {
+                    let message = format!(
+                        "value `{}` of the constraint on `{}` in `{}` is not a valid `{}`: {}",
+                        c.value.0,
+                        entry,
+                        name(self.container),
+                        entry_type.0,
+                        reason
+                    );
+                    return Err(Box::new(
+                        Diagnostic::error(message).at(format!("{}@value", path)),
+                    ));
+                }
+            }
+            Constraint::RangeConstraint(_) => (),
+        }
+        Ok(())
+    }
+
+    /// the entry a constraint applies to, with the container it is in (Req 3.10.7); the
+    /// segments after the first one name the entries of container entries
+    fn target(
+        &self,
+        bases: &[Scope<'a>],
+        entry: &str,
+    ) -> Result<(Scope<'a>, &'a EntryElement), String> {
+        if bases.is_empty() {
+            return Err(format!(
+                "constraint on `{}` in `{}`, which has no base container",
+                entry,
+                name(self.container)
+            ));
+        }
+        let mut segments = entry.split('.');
+        let first = segments.next().unwrap_or_default();
+        let (mut scope, mut target) = bases
+            .iter()
+            .find_map(|base| base.entry(first).map(|target| (*base, target)))
+            .ok_or_else(|| {
+                format!(
+                    "no entry `{}` in the base containers of `{}`",
+                    first,
+                    name(self.container)
+                )
+            })?;
+        let mut prefix = first.to_string();
+        for segment in segments {
+            let container = target
+                .type_()
+                .and_then(|type_| self.validator.lookup(scope.package, &type_.0));
+            let (package, container) = match container {
+                Some((package, DataType::ContainerDataType(container))) => (package, container),
+                _ => return Err(format!("entry `{}` is not a container", prefix)),
+            };
+            let mut scopes = vec![Scope { package, container }];
+            scopes.extend(self.validator.bases(package, container)?);
+            (scope, target) = scopes
+                .iter()
+                .find_map(|s| s.entry(segment).map(|target| (*s, target)))
+                .ok_or_else(|| {
+                    format!(
+                        "no entry `{}` in `{}`, the type of `{}`",
+                        segment,
+                        name(container),
+                        prefix
+                    )
+                })?;
+            prefix = format!("{}.{}", prefix, segment);
+        }
+        Ok((scope, target))
+    }
+}
+
+/// The values of a data type
+enum Kind<'a> {
+    Integer(Interval),
+    Float(Interval),
+    Boolean,
+    Enumerated(&'a EnumeratedDataType),
+    String,
+    /// a type without literals, like a container
+    Other(&'static str),
+    /// the base type of a sub range is unknown
+    Unknown,
+}
+
+impl Kind<'_> {
+    /// the range of a numeric type
+    fn interval(&self) -> Option<&Interval> {
+        match self {
+            Kind::Integer(interval) | Kind::Float(interval) => Some(interval),
+            _ => None,
+        }
+    }
+
+    /// check that a literal is a value of the type, table 3-1
+    fn accepts(&self, literal: &str) -> Result<(), String> {
+        let value = Value::parse(literal);
+        let number = match (self, &value) {
+            (Kind::Integer(interval), value) => match Number::parse(literal) {
+                Some(x @ Number::Integer(_)) => Some((interval, x)),
+                _ => return Err(format!("expected an integer, not a {}", value.type_name())),
+            },
+            (Kind::Float(interval), value) => match Number::parse(literal) {
+                Some(x) => Some((interval, x)),
+                None => return Err(format!("expected a number, not a {}", value.type_name())),
+            },
+            (Kind::Boolean, Value::Boolean(_)) => None,
+            (Kind::Boolean, value) => {
+                return Err(format!(
+                    "expected true or false, not a {}",
+                    value.type_name()
+                ))
+            }
+            (Kind::Enumerated(dt), value) => {
+                // labels, or the values of the labels as in the CCSDS headers
+                let labels = &dt.enumeration_list.enumeration;
+                let valid = labels.iter().any(|e| {
+                    e.label.0 == literal.trim()
+                        || Value::parse(&e.value.0).compare(value) == Some(Ordering::Equal)
+                });
+                if !valid {
+                    let labels: Vec<_> = labels.iter().map(|e| e.label.0.as_str()).collect();
+                    return Err(format!("expected one of {}", labels.join(", ")));
+                }
+                None
+            }
+            (Kind::String, _) | (Kind::Unknown, _) => None,
+            (Kind::Other(element), _) => {
+                return Err(format!("a `{}` has no literal values", element))
+            }
+        };
+        match number {
+            Some((interval, x)) if !interval.contains(x) => {
+                Err(format!("{} is outside {}", x, interval))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A number of a literal, integers are exact over the values of 64-bit signed and unsigned
+/// integers
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(literal: &str) -> Option<Number> {
+        match parse_integer(literal.trim()) {
+            Some(i) => Some(Number::Integer(i)),
+            None => Value::parse(literal).as_f64().map(Number::Float),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    /// order two numbers, integers are compared exactly
+    fn compare(self, other: Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(a.cmp(&b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(i) => write!(f, "{}", i),
+            Number::Float(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// A bound of a range
+#[derive(Debug, Clone, Copy)]
+struct Bound {
+    value: Number,
+    inclusive: bool,
+}
+
+impl Bound {
+    fn inclusive(value: Number) -> Option<Bound> {
+        Some(Bound {
+            value,
+            inclusive: true,
+        })
+    }
+
+    /// true if the bound lets fewer values in than the other, `max` for an upper bound
+    fn tighter(&self, other: &Bound, max: bool) -> bool {
+        match self.value.compare(other.value) {
+            Some(Ordering::Less) => max,
+            Some(Ordering::Greater) => !max,
+            Some(Ordering::Equal) => !self.inclusive && other.inclusive,
+            None => false,
+        }
+    }
+}
+
+/// The values of a range, unbounded where the range has no minimum or maximum
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    min: Option<Bound>,
+    max: Option<Bound>,
+}
+
+impl Interval {
+    /// the values of a range, table 3-2; a bound that is missing or is not a number is
+    /// unbounded
+    fn of(range: &Range) -> Interval {
+        let MinMaxRange {
+            min,
+            max,
+            range_type,
+        } = &range.min_max_range;
+        let bound = |literal: &str, inclusive: bool| {
+            Number::parse(literal).map(|value| Bound { value, inclusive })
+        };
+        let (min, max) = match range_type {
+            MinMaxRangeType::ExclusiveMinExclusiveMax => {
+                (bound(&min.0, false), bound(&max.0, false))
+            }
+            MinMaxRangeType::InclusiveMinInclusiveMax => (bound(&min.0, true), bound(&max.0, true)),
+            MinMaxRangeType::InclusiveMinExclusiveMax => {
+                (bound(&min.0, true), bound(&max.0, false))
+            }
+            MinMaxRangeType::ExclusiveMinInclusiveMax => {
+                (bound(&min.0, false), bound(&max.0, true))
+            }
+            MinMaxRangeType::GreaterThan => (bound(&min.0, false), None),
+            MinMaxRangeType::AtLeast => (bound(&min.0, true), None),
+            MinMaxRangeType::LessThan => (None, bound(&max.0, false)),
+            MinMaxRangeType::AtMost => (None, bound(&max.0, true)),
+        };
+        Interval { min, max }
+    }
+
+    /// the values an integer encoding can hold, unbounded beyond 64 bits
+    fn integer(encoding: &IntegerDataEncoding) -> Interval {
+        let bits = encoding.size_in_bits as u32;
+        if bits == 0 || bits > 64 {
+            return Interval::default();
+        }
+        let (min, max) = match encoding.encoding {
+            IntegerEncoding::Unsigned => (0, (1i128 << bits) - 1),
+            IntegerEncoding::TwosComplement => (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1),
+            IntegerEncoding::SignMagnitude | IntegerEncoding::OnesComplement => {
+                (-(1i128 << (bits - 1)) + 1, (1i128 << (bits - 1)) - 1)
+            }
+            // four bits a digit
+            IntegerEncoding::BinaryCodedDecimal => (0, 10i128.pow(bits / 4) - 1),
+        };
+        Interval {
+            min: Bound::inclusive(Number::Integer(min)),
+            max: Bound::inclusive(Number::Integer(max)),
+        }
+    }
+
+    /// the finite values a float encoding can hold, unbounded for the encodings wider than a
+    /// double
+    fn float(encoding: &FloatDataEncoding) -> Interval {
+        let max = match encoding.encoding_and_precision {
+            FloatEncodingAndPrecision::IEEE7542008Single => f32::MAX as f64,
+            FloatEncodingAndPrecision::IEEE7542008Double => f64::MAX,
+            _ => return Interval::default(),
+        };
+        Interval {
+            min: Bound::inclusive(Number::Float(-max)),
+            max: Bound::inclusive(Number::Float(max)),
+        }
+    }
+
+    /// the values of both ranges, like a range within the values of its encoding
+    fn within(&self, other: &Interval) -> Interval {
+        let pick = |a: Option<Bound>, b: Option<Bound>, max: bool| match (a, b) {
+            (Some(a), Some(b)) if b.tighter(&a, max) => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        };
+        Interval {
+            min: pick(self.min, other.min, false),
+            max: pick(self.max, other.max, true),
+        }
+    }
+
+    fn contains(&self, x: Number) -> bool {
+        let above = match self.min {
+            Some(min) => match x.compare(min.value) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => min.inclusive,
+                _ => false,
+            },
+            None => true,
+        };
+        let below = match self.max {
+            Some(max) => match x.compare(max.value) {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Equal) => max.inclusive,
+                _ => false,
+            },
+            None => true,
+        };
+        above && below
+    }
+
+    /// true if every value of the other range is in this one
+    fn includes(&self, other: &Interval) -> bool {
+        let min = match (self.min, other.min) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(a), Some(b)) => !a.tighter(&b, false),
+        };
+        let max = match (self.max, other.max) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(a), Some(b)) => !a.tighter(&b, true),
+        };
+        min && max
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.min {
+            Some(Bound { value, inclusive }) => {
+                write!(f, "{}{}", if inclusive { "[" } else { "(" }, value)?
+            }
+            None => f.write_str("(-inf")?,
+        }
+        match self.max {
+            Some(Bound { value, inclusive }) => {
+                write!(f, ", {}{}", value, if inclusive { "]" } else { ")" })
+            }
+            None => f.write_str(", inf)"),
+        }
+    }
+}
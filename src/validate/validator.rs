@@ -0,0 +1,407 @@
+//! Validator of datasheets
+//!
+//! The children of an element are matched against the content model of its type, the
+//! particles of its base types first. All the ways the children can be matched are followed at
+//! once, so a violation is reported at the first child that no way gets past, with the
+//! elements that were expected there:
+//!
+//! ```text
+//! error: unexpected element `ConstraintSet` in `ContainerDataType`, expected TrailerEntryList
+//! ```
+//!
+//! Attributes that are not declared are allowed by the `xs:anyAttribute` wildcards of the type,
+//! and checked against their global declaration unless the wildcard skips them.
+//!
+//! Identity constraints (`xs:key` and `xs:keyref`) are not checked, unknown names are reported
+//! by the resolver.
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::diagnostic::Diagnostic;
+use crate::eds::layout::MAX_DEPTH;
+
+use super::document::{Document, Node};
+use super::schema::{
+    AttributeDecl, AttributeItem, ComplexType, ElementDecl, Particle, Process, QName, Schema,
+    SimpleRef, Term, TypeRef, Wildcard,
+};
+
+/// namespace of the attributes about the document, like xsi:schemaLocation
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Validator of datasheets and package files against a [`Schema`]
+#[derive(Debug, Clone)]
+pub struct Validator<'s> {
+    schema: &'s Schema,
+    file: String,
+    include_path: Vec<PathBuf>,
+}
+
+impl<'s> Validator<'s> {
+    /// a validator of the given file, the name is used to locate diagnostics and included files
+    pub fn new(schema: &'s Schema, file: &str) -> Self {
+        Validator {
+            schema,
+            file: file.to_string(),
+            include_path: Vec::new(),
+        }
+    }
+
+    /// add a directory to search for included files that are not next to the including file
+    pub fn include_path(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.include_path.push(directory.into());
+        self
+    }
+
+    /// validate the contents of the file, with its includes; the violations are returned in
+    /// document order
+    pub fn validate(&self, contents: &str) -> Result<(), Vec<Diagnostic>> {
+        let document =
+            Document::read(&self.file, contents, &self.include_path).map_err(|d| vec![*d])?;
+        let mut check = Check {
+            schema: self.schema,
+            document: &document,
+            diagnostics: Vec::new(),
+        };
+        let root = &document.root;
+        match self.schema.element(&name_of(root)) {
+            Some(declaration) => check.element(root, &declaration.type_),
+            None => check.error(
+                root,
+                &root.path,
+                format!(
+                    "no declaration of the root element `{}`",
+                    root.name.local_name
+                ),
+            ),
+        }
+        match check.diagnostics.is_empty() {
+            true => Ok(()),
+            false => Err(check.diagnostics),
+        }
+    }
+}
+
+/// the expanded name of an element
+fn name_of(node: &Node) -> QName {
+    QName::new(
+        node.name.namespace.as_deref().unwrap_or_default(),
+        &node.name.local_name,
+    )
+}
+
+/// The state of a validation
+struct Check<'a> {
+    schema: &'a Schema,
+    document: &'a Document,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Check<'a> {
+    fn error(&mut self, node: &Node, path: &str, message: String) {
+        let diagnostic = self
+            .document
+            .locate(node, Diagnostic::error(message).at(path));
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn element(&mut self, node: &Node, type_: &'a TypeRef) {
+        match type_ {
+            TypeRef::Any => (),
+            TypeRef::Simple(simple_type) => {
+                self.simple_content(node, &SimpleRef::Inline(simple_type.clone()))
+            }
+            TypeRef::Complex(complex_type) => self.complex(node, complex_type, None),
+            TypeRef::Named(name) => match self.schema.complex_type(name) {
+                Some(complex_type) => self.complex(node, complex_type, Some(name)),
+                None if self.schema.is_simple(name) => {
+                    self.simple_content(node, &SimpleRef::Named(name.clone()))
+                }
+                None => self.error(node, &node.path, format!("unknown type `{}`", name)),
+            },
+        }
+    }
+
+    /// an element whose content is a value
+    fn simple_content(&mut self, node: &Node, type_: &SimpleRef) {
+        self.attributes(node, &Attributes::default());
+        for child in node.children.iter() {
+            self.unexpected(child, node, &BTreeSet::new());
+        }
+        if let Err(reason) = self.schema.check(type_, &node.text) {
+            let message = format!("invalid content of `{}`: {}", node.name.local_name, reason);
+            self.error(node, &node.path, message);
+        }
+    }
+
+    fn complex(&mut self, node: &Node, complex_type: &'a ComplexType, name: Option<&QName>) {
+        if complex_type.is_abstract {
+            let message = format!(
+                "`{}` has the abstract type `{}`",
+                node.name.local_name,
+                name.map(|name| name.local.as_str()).unwrap_or_default()
+            );
+            self.error(node, &node.path, message);
+            return;
+        }
+        // the base types come first
+        let mut chain = vec![complex_type];
+        while let Some(base) = chain.last().and_then(|t| t.base.as_ref()) {
+            match self.schema.complex_type(base) {
+                Some(base) if chain.len() < MAX_DEPTH => chain.push(base),
+                _ => {
+                    let message = format!("unknown or cyclic base type `{}`", base);
+                    self.error(node, &node.path, message);
+                    return;
+                }
+            }
+        }
+        chain.reverse();
+
+        let mut attributes = Attributes::default();
+        for complex_type in chain.iter() {
+            self.collect(&complex_type.attributes, &mut attributes, 0);
+        }
+        self.attributes(node, &attributes);
+
+        if !node.text.trim().is_empty() {
+            let message = format!("unexpected text in `{}`", node.name.local_name);
+            self.error(node, &node.path, message);
+        }
+
+        let particles: Vec<&Particle> = chain.iter().filter_map(|t| t.content.as_ref()).collect();
+        let mut matcher = Matcher {
+            children: &node.children,
+            reached: 0,
+            expected: BTreeMap::new(),
+        };
+        let ends = matcher.sequence(&particles, 0);
+        if !ends.contains(&node.children.len()) {
+            let reached = matcher.reached;
+            let expected = matcher.expected.remove(&reached).unwrap_or_default();
+            match node.children.get(reached) {
+                Some(child) => self.unexpected(child, node, &expected),
+                None => {
+                    let message = format!(
+                        "missing element in `{}`, expected {}",
+                        node.name.local_name,
+                        alternatives(&expected)
+                    );
+                    self.error(node, &node.path, message);
+                }
+            }
+        }
+
+        for child in node.children.iter() {
+            let name = name_of(child);
+            if let Some(declaration) = particles.iter().find_map(|p| declaration(p, &name)) {
+                self.element(child, &declaration.type_);
+            }
+        }
+    }
+
+    fn unexpected(&mut self, child: &Node, parent: &Node, expected: &BTreeSet<&str>) {
+        let local = child.name.local_name.as_str();
+        let message = if expected.contains(local) {
+            // the element is in the wrong namespace, like a SEDS element without one
+            format!("element `{}` is not in the namespace of the schema", local)
+        } else if expected.is_empty() {
+            format!(
+                "unexpected element `{}` in `{}`",
+                local, parent.name.local_name
+            )
+        } else {
+            format!(
+                "unexpected element `{}` in `{}`, expected {}",
+                local,
+                parent.name.local_name,
+                alternatives(expected)
+            )
+        };
+        self.error(child, &child.path, message);
+    }
+
+    /// the attribute declarations and wildcards of a list of items, with those of the groups
+    /// it refers to
+    fn collect(&self, items: &'a [AttributeItem], attributes: &mut Attributes<'a>, depth: usize) {
+        for item in items.iter() {
+            match item {
+                AttributeItem::Attribute(attribute) => attributes.declarations.push(attribute),
+                AttributeItem::Any(wildcard) => attributes.wildcards.push(wildcard),
+                AttributeItem::Group(group) if depth < MAX_DEPTH => {
+                    self.collect(self.schema.attribute_group(group), attributes, depth + 1)
+                }
+                AttributeItem::Group(_) => (),
+            }
+        }
+    }
+
+    fn attributes(&mut self, node: &Node, attributes: &Attributes) {
+        let element = node.name.local_name.as_str();
+        for attribute in node.attributes.iter() {
+            let namespace = attribute.name.namespace.as_deref().unwrap_or_default();
+            if namespace == XSI_NAMESPACE {
+                continue;
+            }
+            let local = attribute.name.local_name.as_str();
+            let path = format!("{}@{}", node.path, local);
+            let name = match &attribute.name.prefix {
+                Some(prefix) => format!("{}:{}", prefix, local),
+                None => local.to_string(),
+            };
+            let mut declaration = attributes
+                .declarations
+                .iter()
+                .find(|d| d.name.namespace == namespace && d.name.local == local)
+                .copied();
+            if declaration.is_none() {
+                let Some(wildcard) = attributes.wildcards.iter().find(|w| w.allows(namespace))
+                else {
+                    let message = format!("attribute `{}` is not allowed in `{}`", name, element);
+                    self.error(node, &path, message);
+                    continue;
+                };
+                declaration = self.schema.attribute(&QName::new(namespace, local));
+                match wildcard.process {
+                    Process::Skip => continue,
+                    Process::Lax => (),
+                    Process::Strict if declaration.is_some() => (),
+                    Process::Strict => {
+                        let message = format!(
+                            "attribute `{}` in `{}` has no global declaration",
+                            name, element
+                        );
+                        self.error(node, &path, message);
+                        continue;
+                    }
+                }
+            }
+            if let Some(type_) = declaration.and_then(|d| d.type_.as_ref()) {
+                if let Err(reason) = self.schema.check(type_, &attribute.value) {
+                    let message =
+                        format!("invalid value of `{}` in `{}`: {}", local, element, reason);
+                    self.error(node, &path, message);
+                }
+            }
+        }
+        for declaration in attributes.declarations.iter().filter(|d| d.required) {
+            let present = node.attributes.iter().any(|attribute| {
+                attribute.name.namespace.as_deref().unwrap_or_default()
+                    == declaration.name.namespace
+                    && attribute.name.local_name == declaration.name.local
+            });
+            if !present {
+                let message = format!(
+                    "missing attribute `{}` in `{}`",
+                    declaration.name.local, element
+                );
+                self.error(node, &node.path, message);
+            }
+        }
+    }
+}
+
+/// The attributes a type allows
+#[derive(Default)]
+struct Attributes<'a> {
+    declarations: Vec<&'a AttributeDecl>,
+    wildcards: Vec<&'a Wildcard>,
+}
+
+/// the declaration of the elements of a name in a content model
+fn declaration<'p>(particle: &'p Particle, name: &QName) -> Option<&'p ElementDecl> {
+    match &particle.term {
+        Term::Element(element) if element.name == *name => Some(element),
+        Term::Element(_) => None,
+        Term::Sequence(particles) | Term::Choice(particles) => {
+            particles.iter().find_map(|p| declaration(p, name))
+        }
+    }
+}
+
+/// a list of element names, like `A, B or C`
+fn alternatives(names: &BTreeSet<&str>) -> String {
+    let names: Vec<_> = names.iter().map(|name| name.to_string()).collect();
+    match names.split_last() {
+        None => "more elements".to_string(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+/// Matcher of the children of an element against a content model
+struct Matcher<'n, 'p> {
+    children: &'n [Node],
+    /// the furthest child any way of matching got to
+    reached: usize,
+    /// the elements tried at each position
+    expected: BTreeMap<usize, BTreeSet<&'p str>>,
+}
+
+impl<'p> Matcher<'_, 'p> {
+    /// the positions after the particles, matched in order from `start`
+    fn sequence(&mut self, particles: &[&'p Particle], start: usize) -> BTreeSet<usize> {
+        let mut positions = BTreeSet::from([start]);
+        for particle in particles.iter() {
+            positions = positions
+                .into_iter()
+                .flat_map(|position| self.particle(particle, position))
+                .collect();
+        }
+        positions
+    }
+
+    /// the positions after the occurrences of a particle from `start`
+    fn particle(&mut self, particle: &'p Particle, start: usize) -> BTreeSet<usize> {
+        let mut ends = BTreeSet::new();
+        if particle.min == 0 {
+            let _ = ends.insert(start);
+        }
+        let mut current = BTreeSet::from([start]);
+        let mut count = 0;
+        while !current.is_empty() && particle.max.is_none_or(|max| count < max) {
+            count += 1;
+            let mut next: BTreeSet<usize> = current
+                .iter()
+                .flat_map(|position| self.term(&particle.term, *position))
+                .collect();
+            if count >= particle.min {
+                // positions that were already reached lead nowhere new
+                next.retain(|position| ends.insert(*position));
+            }
+            // a term that matches no element can only repeat up to its minimum
+            if count > particle.min + self.children.len() {
+                break;
+            }
+            current = next;
+        }
+        ends
+    }
+
+    fn term(&mut self, term: &'p Term, start: usize) -> BTreeSet<usize> {
+        match term {
+            Term::Element(element) => match self.children.get(start) {
+                Some(child) if name_of(child) == element.name => {
+                    self.reached = self.reached.max(start + 1);
+                    BTreeSet::from([start + 1])
+                }
+                _ => {
+                    let _ = self
+                        .expected
+                        .entry(start)
+                        .or_default()
+                        .insert(element.name.local.as_str());
+                    BTreeSet::new()
+                }
+            },
+            Term::Sequence(particles) => {
+                let particles: Vec<&Particle> = particles.iter().collect();
+                self.sequence(&particles, start)
+            }
+            Term::Choice(particles) => particles
+                .iter()
+                .flat_map(|particle| self.particle(particle, start))
+                .collect(),
+        }
+    }
+}
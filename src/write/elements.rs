@@ -0,0 +1,889 @@
+//! [`ToXml`] implementations of the raw EDS models
+//!
+//! Attributes are written first, then the child elements in the order of the schema. Optional
+//! attributes are left out when the model has no value for them, as are the sets that the models
+//! keep empty when the element is missing.
+use crate::eds::raw::{
+    Activity, ActivityArgument, ActivityInvocation, ActivitySet, ArgumentValue, ArrayDataType,
+    Assignment, Body, BooleanDataEncoding, BooleanDataType, BooleanExpression, Calibration,
+    Comparison, Component, ComponentSet, Conditional, Constraint, ConstraintSet, ContainerDataType,
+    DataSheet, DataType, DataTypeSet, Device, Dimension, DimensionList, Entry, EntryElement,
+    EntryList, EntryState, EnumeratedDataType, Enumeration, EnumerationList, ErrorControlEntry,
+    ExitState, FixedValueEntry, FloatDataEncoding, FloatDataType, GenericTypeMap,
+    GenericTypeMapSet, Implementation, IntegerDataEncoding, IntegerDataType, Interface, Iteration,
+    LengthEntry, ListEntry, LongDescription, MathElement, MathOperation, MathOperator, MetaData,
+    MetadataCategory, MetadataValue, MetadataValueSet, MinMaxRange, NamedEntityType,
+    NumericMetadataValue, OnCommandPrimitive, OnParameterPrimitive, OnTimer, Operand, Package,
+    PackageFile, PaddingEntry, ParameterMap, ParameterMapSet, PolynomialCalibrator, Range,
+    RangeConstraint, RequiredInterfaceSet, Semantics, SendCommandPrimitive, SendParameterPrimitive,
+    SinkArgumentValue, SplineCalibrator, SplinePoint, State, StateMachine, StateMachineElement,
+    StateMachineSet, Statement, StringDataEncoding, StringDataType, StringMetadataValue,
+    SubRangeDataType, Term, Transition, TypeCheck, TypeConstraint, ValueConstraint, ValueOperand,
+    Variable, VariableRefOperand, VariableSet,
+};
+
+use super::writer::{Node, ToXml};
+
+/// A model that is one of several elements, like the data types of a DataTypeSet
+trait Choice {
+    /// the element of the variant, if it has one
+    fn node(&self) -> Option<Node>;
+}
+
+/// add the elements of a list of choices
+fn choices<T: Choice>(element: &mut Node, values: &[T]) {
+    for node in values.iter().filter_map(Choice::node) {
+        let _ = element.push(node);
+    }
+}
+
+/// add an attribute the parser defaults to an empty string when it is missing
+fn defaulted(element: &mut Node, name: &str, value: &str) {
+    if !value.is_empty() {
+        let _ = element.attribute(name, value);
+    }
+}
+
+/// the attributes and LongDescription of a NamedEntityType, which come before the other
+/// attributes and children of an element
+fn named_entity(element: &mut Node, named: &NamedEntityType) {
+    let _ = element
+        .attribute("name", named.name.as_str())
+        .optional("shortDescription", &named.short_description);
+    semantics(element, &named.semantics);
+    let _ = element
+        .optional_child("LongDescription", &named.long_description)
+        .extensions(&named.extensions);
+}
+
+fn semantics(element: &mut Node, semantics: &Semantics) {
+    let _ = element
+        .optional("quantityKind", &semantics.quantity_kind)
+        .optional("unit", &semantics.unit)
+        .optional("chirality", &semantics.chirality)
+        .optional("coordinateType", &semantics.coordinate_type)
+        .optional("toCoordinateType", &semantics.to_coordinate_type)
+        .optional("differenceConvention", &semantics.difference_convention)
+        .optional("interpretation", &semantics.interpretation)
+        .optional("purpose", &semantics.purpose)
+        .optional("referenceFrame", &semantics.reference_frame)
+        .optional("toFrame", &semantics.to_frame)
+        .optional("relationToTimestamp", &semantics.relation_to_timestamp)
+        .optional("statusConvention", &semantics.status_convention)
+        .optional("transformationType", &semantics.transformation_type);
+}
+
+/// a choice of a VariableRef and a Value element
+fn operand(
+    element: &mut Node,
+    variable_ref: &Option<VariableRefOperand>,
+    value: &Option<ValueOperand>,
+) {
+    let _ = element
+        .optional_child("VariableRef", variable_ref)
+        .optional_child("Value", value);
+}
+
+impl ToXml for String {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.set_text(self.as_str());
+    }
+}
+
+impl ToXml for LongDescription {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.set_text(self.text.as_str());
+    }
+}
+
+impl ToXml for DataSheet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .repeated("Device", &self.devices)
+            .repeated("Package", &self.packages);
+    }
+}
+
+impl ToXml for Device {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element.optional_child("Metadata", &self.metadata);
+    }
+}
+
+impl ToXml for MetaData {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .optional_child("CreationDate", &self.creation_date)
+            .optional_child("Creator", &self.creator)
+            .optional_child("MetadataValueSet", &self.value_set)
+            .repeated("Category", &self.categories);
+    }
+}
+
+impl ToXml for MetadataCategory {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("MetadataValueSet", &self.value_set)
+            .repeated("Category", &self.categories);
+    }
+}
+
+impl ToXml for MetadataValueSet {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, &self.values);
+    }
+}
+
+impl Choice for MetadataValue {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            MetadataValue::DateValue(v) => Node::of("DateValue", v),
+            MetadataValue::FloatValue(v) => Node::of("FloatValue", v),
+            MetadataValue::IntegerValue(v) => Node::of("IntegerValue", v),
+            MetadataValue::StringValue(v) => Node::of("StringValue", v),
+        })
+    }
+}
+
+impl ToXml for StringMetadataValue {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element.attribute("value", self.value.as_str());
+    }
+}
+
+impl ToXml for NumericMetadataValue {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional("value", &self.value)
+            .optional_child("MathOperation", &self.math_operation)
+            .optional_child("Conditional", &self.conditional);
+    }
+}
+
+impl ToXml for PackageFile {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .repeated("Package", &self.package)
+            .optional_child("Metadata", &self.metadata);
+    }
+}
+
+impl ToXml for Package {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("DataTypeSet", &self.data_type_set)
+            .optional_child("ComponentSet", &self.component_set);
+    }
+}
+
+impl ToXml for DataTypeSet {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, &self.data_types);
+    }
+}
+
+impl Choice for DataType {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            DataType::NoneDataType => return None,
+            DataType::BooleanDataType(dt) => Node::of("BooleanDataType", dt),
+            DataType::IntegerDataType(dt) => Node::of("IntegerDataType", dt),
+            DataType::ArrayDataType(dt) => Node::of("ArrayDataType", dt),
+            DataType::EnumeratedDataType(dt) => Node::of("EnumeratedDataType", dt),
+            DataType::ContainerDataType(dt) => Node::of("ContainerDataType", dt),
+            DataType::FloatDataType(dt) => Node::of("FloatDataType", dt),
+            DataType::StringDataType(dt) => Node::of("StringDataType", dt),
+            DataType::SubRangeDataType(dt) => Node::of("SubRangeDataType", dt),
+        })
+    }
+}
+
+impl ToXml for EnumeratedDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("IntegerDataEncoding", &self.encoding)
+            .child("EnumerationList", &self.enumeration_list);
+    }
+}
+
+impl ToXml for EnumerationList {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Enumeration", &self.enumeration);
+    }
+}
+
+impl ToXml for Enumeration {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(element, "label", &self.label);
+        defaulted(element, "value", &self.value);
+        let _ = element.optional("shortDescription", &self.short_description);
+        semantics(element, &self.semantics);
+        let _ = element.extensions(&self.extensions);
+    }
+}
+
+impl ToXml for ContainerDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional("abstract", &self._abstract)
+            .optional("baseType", &self.base_type)
+            .optional_child("ConstraintSet", &self.constraint_set)
+            .optional_child("EntryList", &self.entry_list)
+            .optional_child("TrailerEntryList", &self.trailer_entry_list);
+    }
+}
+
+impl ToXml for ConstraintSet {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, &self.constraints);
+    }
+}
+
+impl Choice for Constraint {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            Constraint::RangeConstraint(c) => Node::of("RangeConstraint", c),
+            Constraint::TypeConstraint(c) => Node::of("TypeConstraint", c),
+            Constraint::ValueConstraint(c) => Node::of("ValueConstraint", c),
+        })
+    }
+}
+
+impl ToXml for RangeConstraint {
+    fn to_xml(&self, element: &mut Node) {
+        // the range of a constraint is not wrapped in a Range element
+        let _ = element
+            .attribute("entry", self.entry.as_str())
+            .child("MinMaxRange", &self.range.min_max_range);
+    }
+}
+
+impl ToXml for TypeConstraint {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("entry", self.entry.as_str())
+            .attribute("type", self.type_.as_str());
+    }
+}
+
+impl ToXml for ValueConstraint {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("entry", self.entry.as_str())
+            .attribute("value", self.value.as_str());
+    }
+}
+
+impl ToXml for EntryList {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, &self.entries);
+    }
+}
+
+impl Choice for EntryElement {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            EntryElement::Entry(e) => Node::of("Entry", e),
+            EntryElement::FixedValueEntry(e) => Node::of("FixedValueEntry", e),
+            EntryElement::PaddingEntry(e) => Node::of("PaddingEntry", e),
+            EntryElement::ListEntry(e) => Node::of("ListEntry", e),
+            EntryElement::LengthEntry(e) => Node::of("LengthEntry", e),
+            EntryElement::ErrorControlEntry(e) => Node::of("ErrorControlEntry", e),
+        })
+    }
+}
+
+impl ToXml for Entry {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element.attribute("type", self.type_.as_str());
+    }
+}
+
+impl ToXml for PaddingEntry {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("sizeInBits", self.size_in_bits.as_str())
+            .optional("shortDescription", &self.short_description);
+    }
+}
+
+impl ToXml for LengthEntry {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("type", self.type_.as_str())
+            .optional_child("PolynomialCalibrator", &self.calibration);
+    }
+}
+
+impl ToXml for ErrorControlEntry {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("type", self.type_.as_str())
+            .attribute("errorControlType", self.error_control_type.as_str());
+    }
+}
+
+impl ToXml for FixedValueEntry {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("type", self.type_.as_str())
+            .attribute("fixedValue", self.fixed_value.as_str());
+    }
+}
+
+impl ToXml for ListEntry {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        defaulted(element, "type", &self.type_);
+        let _ = element.attribute("listLengthField", self.list_length_field.as_str());
+    }
+}
+
+impl ToXml for ArrayDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        defaulted(element, "dataTypeRef", &self.data_type_ref);
+        let _ = element.child("DimensionList", &self.dimension_list);
+    }
+}
+
+impl ToXml for DimensionList {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Dimension", &self.dimension);
+    }
+}
+
+impl ToXml for Dimension {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(element, "size", &self.size);
+    }
+}
+
+impl ToXml for BooleanDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element.optional_child("BooleanDataEncoding", &self.encoding);
+    }
+}
+
+impl ToXml for BooleanDataEncoding {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(element, "sizeInBits", &self.size_in_bits);
+        let _ = element.optional("falseValue", &self.false_value);
+    }
+}
+
+impl ToXml for IntegerDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("PolynomialCalibrator", &self.polynomial_calibrator)
+            .optional_child("SplineCalibrator", &self.spline_calibrator)
+            .optional_child("IntegerDataEncoding", &self.encoding)
+            .nonempty_child("Range", &self.range);
+    }
+}
+
+impl ToXml for IntegerDataEncoding {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(element, "sizeInBits", &self.size_in_bits);
+        defaulted(element, "encoding", &self.encoding);
+        let _ = element.optional("byteOrder", &self.byte_order);
+    }
+}
+
+impl ToXml for Range {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = match &self.precision_range {
+            Some(precision) => element.child("PrecisionRange", precision),
+            None => element.nonempty_child("MinMaxRange", &self.min_max_range),
+        };
+    }
+}
+
+impl ToXml for MinMaxRange {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(element, "min", &self.min);
+        defaulted(element, "max", &self.max);
+        defaulted(element, "rangeType", &self.range_type);
+    }
+}
+
+impl ToXml for FloatDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("PolynomialCalibrator", &self.polynomial_calibrator)
+            .optional_child("SplineCalibrator", &self.spline_calibrator)
+            .optional_child("FloatDataEncoding", &self.encoding)
+            .optional_child("Range", &self.range);
+    }
+}
+
+impl ToXml for FloatDataEncoding {
+    fn to_xml(&self, element: &mut Node) {
+        defaulted(
+            element,
+            "encodingAndPrecision",
+            &self.encoding_and_precision,
+        );
+        defaulted(element, "sizeInBits", &self.size_in_bits);
+        let _ = element.optional("byteOrder", &self.byte_order);
+    }
+}
+
+impl ToXml for StringDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("length", self.length.as_str())
+            .optional("fixedLength", &self.fixed_length)
+            .optional_child("StringDataEncoding", &self.encoding);
+    }
+}
+
+impl ToXml for StringDataEncoding {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .optional("encoding", &self.encoding)
+            .optional("terminationCharacter", &self.termination_character);
+    }
+}
+
+impl ToXml for SubRangeDataType {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("baseType", self.base_type.as_str())
+            .optional("encoding", &self.encoding)
+            .nonempty_child("Range", &self.range);
+    }
+}
+
+impl ToXml for ComponentSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Component", &self.components);
+    }
+}
+
+impl ToXml for Component {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("name", self.name.as_str())
+            .nonempty_child("RequiredInterfaceSet", &self.required_interface_set)
+            .nonempty_child("Implementation", &self.implementation);
+    }
+}
+
+impl ToXml for RequiredInterfaceSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Interface", &self.interfaces);
+    }
+}
+
+impl ToXml for Interface {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("name", self.name.as_str())
+            .optional("shortDescription", &self.short_description)
+            .attribute("type", self.type_.as_str())
+            .nonempty_child("GenericTypeMapSet", &self.generic_type_map_set);
+    }
+}
+
+impl ToXml for GenericTypeMapSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("GenericTypeMap", &self.generic_type_maps);
+    }
+}
+
+impl ToXml for GenericTypeMap {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("name", self.name.as_str())
+            .attribute("type", self.type_.as_str());
+    }
+}
+
+impl ToXml for Implementation {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .nonempty_child("VariableSet", &self.variable_set)
+            .nonempty_child("ParameterMapSet", &self.parameter_map_set)
+            .nonempty_child("ActivitySet", &self.activity_set)
+            .nonempty_child("StateMachineSet", &self.state_machine_set);
+    }
+}
+
+impl ToXml for VariableSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Variable", &self.variables);
+    }
+}
+
+impl ToXml for Variable {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("name", self.name.as_str())
+            .attribute("type", self.type_.as_str())
+            .optional("initialValue", &self.initial_value)
+            .flag("readOnly", self.read_only);
+    }
+}
+
+impl ToXml for ParameterMapSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("ParameterMap", &self.parameter_maps);
+    }
+}
+
+impl ToXml for ParameterMap {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("interface", self.interface.as_str())
+            .attribute("parameter", self.parameter.as_str())
+            .attribute("variableRef", self.variable_ref.as_str());
+    }
+}
+
+impl ToXml for StateMachineSet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("StateMachine", &self.state_machines);
+    }
+}
+
+impl ToXml for StateMachine {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        choices(element, &self.elements);
+    }
+}
+
+impl Choice for StateMachineElement {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            StateMachineElement::EntryState(s) => Node::of("EntryState", s),
+            StateMachineElement::ExitState(s) => Node::of("ExitState", s),
+            StateMachineElement::State(s) => Node::of("State", s),
+            StateMachineElement::Transition(t) => Node::of("Transition", t),
+        })
+    }
+}
+
+impl ToXml for EntryState {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+    }
+}
+
+impl ToXml for ExitState {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+    }
+}
+
+impl ToXml for State {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .optional_child("OnEntry", &self.on_entry)
+            .optional_child("OnExit", &self.on_exit);
+    }
+}
+
+impl ToXml for Transition {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .attribute("fromState", self.from_state.as_str())
+            .attribute("toState", self.to_state.as_str())
+            .optional_child("OnCommandPrimitive", &self.on_command_primitive)
+            .optional_child("OnParameterPrimitive", &self.on_parameter_primitive)
+            .optional_child("OnTimer", &self.on_timer)
+            .optional_child("Guard", &self.guard)
+            .optional_child("Do", &self.do_);
+    }
+}
+
+impl ToXml for ActivityInvocation {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("activity", self.activity.as_str())
+            .repeated("ArgumentValue", &self.argument_values);
+    }
+}
+
+impl ToXml for ArgumentValue {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("name", self.name.as_str());
+        operand(element, &self.variable_ref, &self.value);
+    }
+}
+
+impl ToXml for VariableRefOperand {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("variableRef", self.variable_ref.as_str());
+    }
+}
+
+impl ToXml for ValueOperand {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("value", self.value.as_str());
+    }
+}
+
+impl ToXml for OnCommandPrimitive {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("interface", self.interface.as_str())
+            .attribute("command", self.command.as_str())
+            .optional("transaction", &self.transaction)
+            .repeated("ArgumentValue", &self.argument_values);
+    }
+}
+
+impl ToXml for SinkArgumentValue {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("name", self.name.as_str())
+            .attribute("outputVariableRef", self.output_variable_ref.as_str());
+    }
+}
+
+impl ToXml for OnParameterPrimitive {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("interface", self.interface.as_str())
+            .attribute("parameter", self.parameter.as_str())
+            .attribute("operation", self.operation.as_str())
+            .optional("transaction", &self.transaction);
+        if let Some(variable_ref) = &self.variable_ref {
+            let mut node = Node::new("VariableRef");
+            let _ = node.attribute("variableRef", variable_ref.as_str());
+            let _ = element.push(node);
+        }
+    }
+}
+
+impl ToXml for OnTimer {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.optional("nanosecondsAfterEntry", &self.nanoseconds_after_entry);
+    }
+}
+
+impl ToXml for ActivitySet {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Activity", &self.activities);
+    }
+}
+
+impl ToXml for Activity {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element
+            .repeated("Argument", &self.arguments)
+            .child("Body", &self.body);
+    }
+}
+
+impl ToXml for ActivityArgument {
+    fn to_xml(&self, element: &mut Node) {
+        named_entity(element, &self.name_entity_type);
+        let _ = element.attribute("type", self.type_.as_str());
+    }
+}
+
+impl ToXml for Body {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, &self.statements);
+    }
+}
+
+impl Choice for Statement {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            Statement::Assignment(s) => Node::of("Assignment", s),
+            Statement::Conditional(s) => Node::of("Conditional", s),
+            Statement::Iteration(s) => Node::of("Iteration", s),
+            Statement::Calibration(s) => Node::of("Calibration", s),
+            Statement::SendParameterPrimitive(s) => Node::of("SendParameterPrimitive", s),
+            Statement::SendCommandPrimitive(s) => Node::of("SendCommandPrimitive", s),
+            Statement::MathOperation(s) => Node::of("MathOperation", s),
+            Statement::Call(s) => Node::of("Call", s),
+        })
+    }
+}
+
+impl ToXml for Operand {
+    fn to_xml(&self, element: &mut Node) {
+        operand(element, &self.variable_ref, &self.value);
+    }
+}
+
+impl ToXml for Assignment {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("outputVariableRef", self.output_variable_ref.as_str());
+        operand(element, &self.variable_ref, &self.value);
+    }
+}
+
+impl ToXml for Conditional {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .child("Condition", &self.condition)
+            .optional_child("OnConditionTrue", &self.on_condition_true)
+            .optional_child("OnConditionFalse", &self.on_condition_false);
+    }
+}
+
+/// a boolean expression is the element of its single condition
+impl ToXml for BooleanExpression {
+    fn to_xml(&self, element: &mut Node) {
+        choices(element, std::slice::from_ref(self));
+    }
+}
+
+impl Choice for BooleanExpression {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            BooleanExpression::Condition(c) => Node::of("Condition", c),
+            BooleanExpression::ANDedConditions(conditions) => {
+                let mut node = Node::new("ANDedConditions");
+                choices(&mut node, conditions);
+                node
+            }
+            BooleanExpression::ORedConditions(conditions) => {
+                let mut node = Node::new("ORedConditions");
+                choices(&mut node, conditions);
+                node
+            }
+            BooleanExpression::TypeCondition(c) => Node::of("TypeCondition", c),
+        })
+    }
+}
+
+impl ToXml for Comparison {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .child("FirstOperand", &self.first_operand)
+            .child("ComparisonOperator", &self.comparison_operator)
+            .optional_child("SecondOperand", &self.second_operand)
+            .optional_child("Value", &self.value);
+    }
+}
+
+impl ToXml for TypeCheck {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .child("FirstOperand", &self.first_operand)
+            .child("TypeOperand", &self.type_operand);
+    }
+}
+
+impl ToXml for Iteration {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("iteratorVariableRef", self.iterator_variable_ref.as_str())
+            .optional_child("OverArray", &self.over_array)
+            .optional_child("StartAt", &self.start_at)
+            .optional_child("Step", &self.step)
+            .optional_child("EndAt", &self.end_at)
+            .child("Do", &self.do_);
+    }
+}
+
+impl ToXml for Calibration {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("outputVariableRef", self.output_variable_ref.as_str())
+            .attribute("inputVariableRef", self.input_variable_ref.as_str())
+            .optional_child("SplineCalibrator", &self.spline_calibrator)
+            .optional_child("PolynomialCalibrator", &self.polynomial_calibrator);
+    }
+}
+
+impl ToXml for SendCommandPrimitive {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("interface", self.interface.as_str())
+            .attribute("command", self.command.as_str())
+            .optional("transaction", &self.transaction)
+            .repeated("ArgumentValue", &self.argument_values);
+    }
+}
+
+impl ToXml for SendParameterPrimitive {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("interface", self.interface.as_str())
+            .attribute("parameter", self.parameter.as_str())
+            .attribute("operation", self.operation.as_str())
+            .optional("transaction", &self.transaction)
+            .optional_child("ArgumentValue", &self.argument_value);
+    }
+}
+
+impl ToXml for MathOperation {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("outputVariableRef", self.output_variable_ref.as_str());
+        choices(element, &self.elements);
+    }
+}
+
+impl Choice for MathElement {
+    fn node(&self) -> Option<Node> {
+        Some(match self {
+            MathElement::Value(v) => Node::of("Value", v),
+            MathElement::VariableRef(v) => Node::of("VariableRef", v),
+            MathElement::Operator(o) => Node::of("Operator", o),
+        })
+    }
+}
+
+impl ToXml for MathOperator {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.attribute("operator", self.operator.as_str());
+    }
+}
+
+impl ToXml for PolynomialCalibrator {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element.repeated("Term", &self.term);
+    }
+}
+
+impl ToXml for SplineCalibrator {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .optional("extrapolate", &self.extrapolate)
+            .repeated("SplinePoint", &self.spline_point);
+    }
+}
+
+impl ToXml for SplinePoint {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .optional("order", &self.order)
+            .attribute("raw", self.raw.as_str())
+            .attribute("calibrated", self.calibrated.as_str());
+    }
+}
+
+impl ToXml for Term {
+    fn to_xml(&self, element: &mut Node) {
+        let _ = element
+            .attribute("coefficient", self.coefficient.as_str())
+            .attribute("exponent", self.exponent.as_str());
+    }
+}
@@ -0,0 +1,15 @@
+//! XML writing for SEDS-rs
+//!
+//! The raw models are written back to datasheets by a [`Writer`], either as they are or
+//! flattened, with their parameters substituted.
+/// [`ToXml`] implementations of the raw models
+pub mod elements;
+/// the writer
+pub mod writer;
+
+pub use writer::{Document, Node, ToXml, WriteError, Writer};
+
+/// write a package file or a datasheet as indented XML
+pub fn to_xml<T: Document>(document: &T) -> writer::Result<String> {
+    Writer::new().write_string(document)
+}
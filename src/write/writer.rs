@@ -0,0 +1,444 @@
+//! Writer of datasheets
+//!
+//! Each model implements [`ToXml`]: it adds its attributes and child elements to the [`Node`]
+//! of its element, in the order of the schema. The tree of nodes is then emitted by a
+//! [`Writer`], with the SEDS namespace as the default namespace of the root element.
+//!
+//! Extensions of user DoTs are written with the names of their namespace in braces, like
+//! `{urn:example:tlm}priority`, and the namespaces are declared on the root element with the
+//! prefixes `ext1`, `ext2`, ... in the order they are first used.
+//!
+//! A flattening writer substitutes the `${...}` parameters of every attribute with their values,
+//! so that the output is fully resolved and can be checked against the schema. Parameters that
+//! cannot be substituted are reported like errors of the resolver, with the path of the
+//! attribute they are in (see [`crate::diagnostic`]).
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io;
+
+use xml::writer::{EmitterConfig, XmlEvent};
+
+use crate::eds::extension::{ExtensionAttribute, ExtensionElement, Extensions};
+use crate::eds::raw::{DataSheet, PackageFile};
+use crate::eds::resolve::ResolveError;
+use crate::expr::ExpressionContext;
+use crate::parse::SEDS_NAMESPACE;
+
+/// Errors of writing a document
+#[allow(missing_docs)]
+#[derive(Debug)]
+pub enum WriteError {
+    Xml(xml::writer::Error),
+    /// attributes whose parameters could not be substituted, in document order
+    Unresolved(Vec<ResolveError>),
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::Xml(err) => err.fmt(f),
+            WriteError::Unresolved(errors) => {
+                for (i, err) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n")?;
+                    }
+                    write!(f, "{} in {}", err.cause(), err.path())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl From<xml::writer::Error> for WriteError {
+    fn from(err: xml::writer::Error) -> Self {
+        WriteError::Xml(err)
+    }
+}
+
+/// Result of writing a document
+pub type Result<T> = std::result::Result<T, WriteError>;
+
+/// A model that can be written as an XML element
+pub trait ToXml {
+    /// add the attributes and child elements of the model to its element
+    fn to_xml(&self, element: &mut Node);
+}
+
+/// A model that can be the root element of a document
+pub trait Document: ToXml {
+    /// name of the root element
+    const ROOT: &'static str;
+}
+
+impl Document for PackageFile {
+    const ROOT: &'static str = "PackageFile";
+}
+
+impl Document for DataSheet {
+    const ROOT: &'static str = "DataSheet";
+}
+
+impl<T: ToXml> ToXml for Box<T> {
+    fn to_xml(&self, element: &mut Node) {
+        self.as_ref().to_xml(element)
+    }
+}
+
+/// An element to be written, with its attributes and children in document order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Node {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+    text: Option<String>,
+}
+
+impl Node {
+    /// an element without attributes or children
+    pub fn new(name: impl Into<String>) -> Self {
+        Node {
+            name: name.into(),
+            ..Node::default()
+        }
+    }
+
+    /// the element of a model
+    pub fn of<T: ToXml + ?Sized>(name: impl Into<String>, value: &T) -> Self {
+        let mut node = Node::new(name);
+        value.to_xml(&mut node);
+        node
+    }
+
+    /// name of the element
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// value of an attribute
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// the child elements
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// the text content
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// add an attribute
+    pub fn attribute(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.attributes.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// add an attribute if it has a value
+    pub fn optional(&mut self, name: &str, value: &Option<String>) -> &mut Self {
+        if let Some(value) = value {
+            let _ = self.attribute(name, value.clone());
+        }
+        self
+    }
+
+    /// add a boolean attribute if it is set, false being its default
+    pub fn flag(&mut self, name: &str, value: bool) -> &mut Self {
+        if value {
+            let _ = self.attribute(name, "true");
+        }
+        self
+    }
+
+    /// set the text content
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// add a child element
+    pub fn push(&mut self, child: Node) -> &mut Self {
+        self.children.push(child);
+        self
+    }
+
+    /// add the element of a model as a child
+    pub fn child<T: ToXml + ?Sized>(&mut self, name: &str, value: &T) -> &mut Self {
+        self.push(Node::of(name, value))
+    }
+
+    /// add the element of a model that may be missing as a child
+    pub fn optional_child<T: ToXml>(&mut self, name: &str, value: &Option<T>) -> &mut Self {
+        if let Some(value) = value {
+            let _ = self.child(name, value);
+        }
+        self
+    }
+
+    /// add an element for each of a list of models
+    pub fn repeated<T: ToXml>(&mut self, name: &str, values: &[T]) -> &mut Self {
+        for value in values {
+            let _ = self.child(name, value);
+        }
+        self
+    }
+
+    /// add the element of a model, unless it has neither attributes nor children, like an
+    /// empty set
+    pub fn nonempty_child<T: ToXml>(&mut self, name: &str, value: &T) -> &mut Self {
+        let node = Node::of(name, value);
+        if !node.attributes.is_empty() || !node.children.is_empty() || node.text.is_some() {
+            let _ = self.push(node);
+        }
+        self
+    }
+
+    /// add the attributes and elements of user DoTs
+    pub fn extensions(&mut self, extensions: &Extensions) -> &mut Self {
+        for attribute in extensions.attributes.iter() {
+            self.extension_attribute(attribute);
+        }
+        for element in extensions.elements.iter() {
+            let _ = self.push(Node::extension(element));
+        }
+        self
+    }
+
+    fn extension_attribute(&mut self, attribute: &ExtensionAttribute) {
+        let name = expanded(&attribute.namespace, &attribute.name);
+        self.attributes.push((name, attribute.value.clone()));
+    }
+
+    fn extension(element: &ExtensionElement) -> Self {
+        let mut node = Node::new(expanded(&element.namespace, &element.name));
+        for attribute in element.attributes.iter() {
+            node.extension_attribute(attribute);
+        }
+        if !element.text.is_empty() {
+            node.text = Some(element.text.clone());
+        }
+        node.children = element.children.iter().map(Node::extension).collect();
+        node
+    }
+
+    /// the namespaces of the extensions of this element and its children, in document order
+    fn namespaces(&self, namespaces: &mut Vec<String>) {
+        let names = std::iter::once(&self.name).chain(self.attributes.iter().map(|(n, _)| n));
+        for name in names {
+            if let Some((namespace, _)) = split(name) {
+                if !namespaces.iter().any(|n| n == namespace) {
+                    namespaces.push(namespace.to_string());
+                }
+            }
+        }
+        for child in self.children.iter() {
+            child.namespaces(namespaces);
+        }
+    }
+
+    /// substitute the parameters of the attributes of this element and its children,
+    /// `path` is the path of this element
+    fn substitute(&mut self, path: &str, ectx: &ExpressionContext, errors: &mut Vec<ResolveError>) {
+        for (name, value) in self.attributes.iter_mut() {
+            if !value.contains("${") {
+                continue;
+            }
+            match ectx.eval_expression(value) {
+                Ok(resolved) => *value = resolved.to_string(),
+                Err(error) => errors.push(ResolveError::InElement {
+                    path: format!("{}@{}", path, local(name)),
+                    error: Box::new(ResolveError::ExpressionContextError(error)),
+                }),
+            }
+        }
+        let paths = self.child_paths(path);
+        for (child, path) in self.children.iter_mut().zip(paths) {
+            child.substitute(&path, ectx, errors);
+        }
+    }
+
+    /// call `found` with the location and the value of every attribute and text of this
+    /// element and its children, in document order, `path` is the path of this element
+    pub(crate) fn values(&self, path: &str, found: &mut impl FnMut(String, &str)) {
+        for (name, value) in self.attributes.iter() {
+            found(format!("{}@{}", path, local(name)), value);
+        }
+        if let Some(text) = &self.text {
+            found(path.to_string(), text);
+        }
+        for (child, path) in self.children.iter().zip(self.child_paths(path)) {
+            child.values(&path, found);
+        }
+    }
+
+    /// the paths of the children of this element, named like the source map of the parser
+    /// does, `path` is the path of this element
+    fn child_paths(&self, path: &str) -> Vec<String> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+        self.children
+            .iter()
+            .map(|child| {
+                let name = local(&child.name);
+                let count = counts.entry(name).or_insert(0);
+                let index = *count;
+                *count += 1;
+                let segment = match child.get("name") {
+                    Some(value) => format!("{}[{}]", name, value),
+                    None if index > 0 => format!("{}[{}]", name, index),
+                    None => name.to_string(),
+                };
+                format!("{}{}", prefix, segment)
+            })
+            .collect()
+    }
+
+    /// emit the element and its children, the root declares the `prefixes` of the namespaces
+    /// of the extensions
+    fn emit<W: io::Write>(
+        &self,
+        writer: &mut xml::EventWriter<W>,
+        prefixes: &BTreeMap<String, String>,
+        root: bool,
+    ) -> xml::writer::Result<()> {
+        let name = qualified(&self.name, prefixes);
+        let mut start = XmlEvent::start_element(name.as_str());
+        if root {
+            start = start.default_ns(SEDS_NAMESPACE);
+            for (namespace, prefix) in prefixes.iter() {
+                start = start.ns(prefix.as_str(), namespace.as_str());
+            }
+        }
+        let attributes: Vec<_> = self
+            .attributes
+            .iter()
+            .map(|(name, value)| (qualified(name, prefixes), value))
+            .collect();
+        for (name, value) in attributes.iter() {
+            start = start.attr(name.as_str(), value);
+        }
+        writer.write(start)?;
+        if let Some(text) = &self.text {
+            writer.write(XmlEvent::characters(text))?;
+        }
+        for child in self.children.iter() {
+            child.emit(writer, prefixes, false)?;
+        }
+        writer.write(XmlEvent::end_element())
+    }
+}
+
+/// the name of an extension with its namespace in braces, like `{urn:example:tlm}priority`
+fn expanded(namespace: &str, name: &str) -> String {
+    match namespace {
+        "" => name.to_string(),
+        namespace => format!("{{{}}}{}", namespace, name),
+    }
+}
+
+/// the namespace and local name of an expanded name
+fn split(name: &str) -> Option<(&str, &str)> {
+    name.strip_prefix('{')?.split_once('}')
+}
+
+/// the local name of an expanded name
+fn local(name: &str) -> &str {
+    split(name).map_or(name, |(_, local)| local)
+}
+
+/// an expanded name with the prefix of its namespace
+fn qualified(name: &str, prefixes: &BTreeMap<String, String>) -> String {
+    match split(name) {
+        Some((namespace, local)) => format!("{}:{}", prefixes[namespace], local),
+        None => name.to_string(),
+    }
+}
+
+/// Writer of datasheets and package files
+#[derive(Debug, Clone)]
+pub struct Writer<'a> {
+    indent: bool,
+    parameters: Option<&'a ExpressionContext>,
+}
+
+impl Default for Writer<'_> {
+    fn default() -> Self {
+        Writer {
+            indent: true,
+            parameters: None,
+        }
+    }
+}
+
+impl<'a> Writer<'a> {
+    /// a writer of indented XML that keeps the parameters
+    pub fn new() -> Self {
+        Writer::default()
+    }
+
+    /// whether to indent the elements, they are by default
+    pub fn indent(mut self, indent: bool) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// substitute the parameters of the attributes with their values in a context
+    pub fn flatten(mut self, parameters: &'a ExpressionContext) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// the tree of elements of a document, flattened if the writer flattens
+    pub fn node<T: Document>(&self, document: &T) -> Result<Node> {
+        let mut node = Node::of(T::ROOT, document);
+        if let Some(ectx) = self.parameters {
+            let mut errors = Vec::new();
+            node.substitute("", ectx, &mut errors);
+            if !errors.is_empty() {
+                return Err(WriteError::Unresolved(errors));
+            }
+        }
+        Ok(node)
+    }
+
+    /// write a document to a sink
+    pub fn write<T: Document, W: io::Write>(&self, document: &T, sink: W) -> Result<()> {
+        let node = self.node(document)?;
+        let mut writer = EmitterConfig::new()
+            .perform_indent(self.indent)
+            .create_writer(sink);
+        writer.write(XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: Some("UTF-8"),
+            standalone: None,
+        })?;
+        let mut namespaces = Vec::new();
+        node.namespaces(&mut namespaces);
+        let prefixes = namespaces
+            .into_iter()
+            .enumerate()
+            .map(|(i, namespace)| (namespace, format!("ext{}", i + 1)))
+            .collect();
+        node.emit(&mut writer, &prefixes, true)?;
+        Ok(())
+    }
+
+    /// write a document to a string
+    pub fn write_string<T: Document>(&self, document: &T) -> Result<String> {
+        let mut buffer = Vec::new();
+        self.write(document, &mut buffer)?;
+        // the emitter writes UTF-8 only
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
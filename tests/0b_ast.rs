@@ -1,6 +1,6 @@
 //! Parsing tests for external EDS files
 use seds_rs::eds::{raw::PackageFile, resolve::Resolve};
-use serde_xml_rs::from_str;
+use seds_rs::parse::Parser;
 use std::fs;
 use walkdir::{DirEntry, WalkDir};
 
@@ -12,14 +12,6 @@ fn is_xml(entry: &DirEntry) -> bool {
     entry.file_name().to_string_lossy().ends_with(".xml")
 }
 
-/// Parse an XML string into a `PackageFile` struct
-fn parse_xml<DeError: std::convert::From<serde_xml_rs::Error>>(
-    xml: &str,
-) -> Result<PackageFile, DeError> {
-    let package_file: PackageFile = from_str(xml)?;
-    Ok(package_file)
-}
-
 /// attempt to parse all the EDS XML files in a directory
 fn test_directory(directory_name: &str) {
     // assert the directory exists
@@ -32,14 +24,15 @@ fn test_directory(directory_name: &str) {
     for entry in walker.filter_map(Result::ok).filter(is_xml) {
         println!("Parsing: {}", entry.path().display());
         let xml_string = fs::read_to_string(entry.path()).expect("Could not read the XML file");
-        let res = parse_xml::<serde_xml_rs::Error>(&xml_string);
+        let path = entry.path().display().to_string();
+        let res = Parser::new(&path).parse::<PackageFile>(&xml_string);
         assert!(
             res.is_ok(),
             "Failed to parse file: {}, Error: {:?}",
             entry.path().display(),
             res
         );
-        let package = res.unwrap();
+        let package = res.unwrap().value;
 
         let ectx = get_mission_params();
 
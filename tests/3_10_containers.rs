@@ -1,15 +1,16 @@
 //! 3.10 CONTAINERS
+#![allow(clippy::assertions_on_constants)]
 use std::collections::HashSet;
 
 use seds_rs::eds::raw::{Constraint, DataType, DataTypeSet, EntryElement, PackageFile};
 
 mod common;
 
-use common::open_file;
+use common::{open_file, parse};
 
 fn get_test_data_type_set() -> DataTypeSet {
     let contents = open_file("eds/test/test_container.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     package.package[0].clone().data_type_set.unwrap()
 }
 
@@ -0,0 +1,395 @@
+//! 3.15 ACTIVITIES
+use std::collections::HashMap;
+
+use seds_rs::eds::ast::{
+    Body, BooleanExpression, ComparisonOperator, Component, Iteration, IterationRange, Literal,
+    MathElement, MathOperation, MathOperator, Operand, PackageFile, ParameterOperation,
+    QualifiedName, StateMachineElement, Statement,
+};
+use seds_rs::eds::raw;
+use seds_rs::eds::resolve::{Resolve, ResolveError};
+use seds_rs::eds::value::Value;
+use seds_rs::sim::activity::{EvalError, Evaluator, SentPrimitive};
+use seds_rs::sim::statemachine::{Event, StateMachineInterpreter};
+
+mod common;
+
+use common::{get_mission_params, open_file, parse};
+
+fn get_raw_package_file() -> raw::PackageFile {
+    let contents = open_file("eds/test/test_activities.xml");
+    parse(&contents)
+}
+
+fn get_component() -> Component {
+    let pf: PackageFile = get_raw_package_file()
+        .resolve(&get_mission_params())
+        .unwrap();
+    pf.package[0].component_set.components[0].clone()
+}
+
+/// resolve the test file after modifying the raw body of its "Regulate" activity
+fn resolve_modified(modify: fn(&mut Vec<raw::Statement>)) -> Result<PackageFile, ResolveError> {
+    let mut pf = get_raw_package_file();
+    let component_set = pf.package[0].component_set.as_mut().unwrap();
+    modify(
+        &mut component_set.components[0]
+            .implementation
+            .activity_set
+            .activities[0]
+            .body
+            .statements,
+    );
+    pf.resolve(&get_mission_params())
+        .map_err(ResolveError::into_cause)
+}
+
+fn raw_math_operation(statements: &mut [raw::Statement]) -> &mut raw::MathOperation {
+    match &mut statements[0] {
+        raw::Statement::MathOperation(m) => m,
+        s => panic!("unexpected statement {:?}", s),
+    }
+}
+
+fn calculation(elements: Vec<MathElement>) -> MathOperation {
+    MathOperation {
+        output_variable_ref: QualifiedName("Result".to_string()),
+        elements,
+    }
+}
+
+/// An ActivitySet holds named activities with arguments and a body of statements
+#[test]
+fn test_activity_set() {
+    let component = get_component();
+    let activities = &component.implementation.activity_set.activities;
+    assert_eq!(activities.len(), 2);
+
+    let regulate = &activities[0];
+    assert_eq!(regulate.name_entity_type.name.0, "Regulate");
+    assert_eq!(regulate.arguments.len(), 1);
+    assert_eq!(regulate.arguments[0].name_entity_type.name.0, "Gain");
+    assert_eq!(regulate.arguments[0].type_.0, "Power");
+    assert!(matches!(
+        regulate.body.statements.as_slice(),
+        [
+            Statement::MathOperation(_),
+            Statement::Conditional(_),
+            Statement::SendParameterPrimitive(_)
+        ]
+    ));
+
+    let ramp = &activities[1];
+    match &ramp.body.statements[0] {
+        Statement::Iteration(i) => {
+            assert_eq!(i.iterator_variable_ref.0, "Setpoint");
+            assert!(matches!(&i.range, IterationRange::Numeric { .. }));
+            assert!(
+                matches!(&i.do_.statements[0], Statement::Call(c) if c.activity.0 == "Regulate")
+            );
+        }
+        s => panic!("unexpected statement {:?}", s),
+    }
+}
+
+/// MathOperation elements are evaluated in reverse Polish notation
+#[test]
+fn test_math_operation() {
+    let component = get_component();
+    let regulate = &component.implementation.activity_set.activities[0];
+    let math = match &regulate.body.statements[0] {
+        Statement::MathOperation(m) => m,
+        s => panic!("unexpected statement {:?}", s),
+    };
+    assert_eq!(math.output_variable_ref.0, "Error");
+    assert_eq!(
+        math.elements[2],
+        MathElement::Operator(MathOperator::Subtract)
+    );
+
+    let evaluator = Evaluator::for_component(&component);
+    let mut variables = Evaluator::initial_values(&component);
+    assert_eq!(
+        evaluator.math_operation(&mut variables, math).unwrap(),
+        Value::Integer(5)
+    );
+    assert_eq!(variables["Error"], Value::Integer(5));
+
+    // the operand stack
+    let scope: HashMap<String, Value> = HashMap::new();
+    let operation = calculation(vec![
+        MathElement::Operand(Operand::Value(Literal("7".to_string()))),
+        MathElement::Operand(Operand::Value(Literal("2".to_string()))),
+        MathElement::Operator(MathOperator::Divide),
+    ]);
+    assert_eq!(
+        evaluator.calculate(&scope, &operation).unwrap(),
+        Value::Float(3.5)
+    );
+    let operation = calculation(vec![
+        MathElement::Operand(Operand::Value(Literal("7".to_string()))),
+        MathElement::Operand(Operand::Value(Literal("0".to_string()))),
+        MathElement::Operator(MathOperator::Modulus),
+    ]);
+    assert!(matches!(
+        evaluator.calculate(&scope, &operation),
+        Err(EvalError::DivisionByZero)
+    ));
+    let operation = calculation(vec![
+        MathElement::Operand(Operand::VariableRef(QualifiedName("Missing".to_string()))),
+        MathElement::Operator(MathOperator::Sqrt),
+    ]);
+    assert!(matches!(
+        evaluator.calculate(&scope, &operation),
+        Err(EvalError::UnboundVariable(name)) if name == "Missing"
+    ));
+}
+
+/// A Conditional executes one of two bodies depending on its condition
+#[test]
+fn test_conditional() {
+    let component = get_component();
+    let regulate = &component.implementation.activity_set.activities[0];
+    let conditional = match &regulate.body.statements[1] {
+        Statement::Conditional(c) => c,
+        s => panic!("unexpected statement {:?}", s),
+    };
+    match &conditional.condition {
+        BooleanExpression::Condition(c) => {
+            assert_eq!(c.first_operand.0, "Error");
+            assert_eq!(c.comparison_operator, ComparisonOperator::GreaterThan);
+            assert_eq!(c.second_operand, Operand::Value(Literal("0".to_string())));
+        }
+        c => panic!("unexpected condition {:?}", c),
+    }
+
+    let mut evaluator = Evaluator::for_component(&component);
+    let mut variables = Evaluator::initial_values(&component);
+    let _ = variables.insert("Gain".to_string(), Value::Float(0.5));
+
+    let _ = evaluator.execute(&mut variables, &regulate.body).unwrap();
+    // Error = 20 - 15 is positive, so Duty = min(Error * Gain, 100)
+    assert_eq!(variables["Duty"], Value::Float(2.5));
+
+    // Error = 20 - 25 is negative, so Duty = 0
+    let _ = variables.insert("Measured".to_string(), Value::Integer(25));
+    let _ = evaluator.execute(&mut variables, &regulate.body).unwrap();
+    assert_eq!(variables["Error"], Value::Integer(-5));
+    assert_eq!(variables["Duty"], Value::Integer(0));
+
+    assert_eq!(
+        evaluator.take_sent(),
+        vec![
+            SentPrimitive::Parameter {
+                interface: "Cmd".to_string(),
+                parameter: "Duty".to_string(),
+                operation: ParameterOperation::Set,
+                value: Some(Value::Float(2.5)),
+            },
+            SentPrimitive::Parameter {
+                interface: "Cmd".to_string(),
+                parameter: "Duty".to_string(),
+                operation: ParameterOperation::Set,
+                value: Some(Value::Integer(0)),
+            },
+        ]
+    );
+}
+
+/// An Iteration runs its body for each value of the iterator, and Call invokes another activity
+#[test]
+fn test_iteration() {
+    let component = get_component();
+    let mut evaluator = Evaluator::for_component(&component);
+    let mut variables = Evaluator::initial_values(&component);
+
+    let result = evaluator.invoke(&mut variables, "Ramp", vec![]).unwrap();
+    assert_eq!(result, Some(Value::Float(37.5)));
+    assert_eq!(variables["Setpoint"], Value::Integer(30));
+    // arguments are local to the activity
+    assert!(!variables.contains_key("Gain"));
+
+    let duties: Vec<_> = evaluator
+        .take_sent()
+        .into_iter()
+        .map(|sent| match sent {
+            SentPrimitive::Parameter { value, .. } => value.unwrap(),
+            s => panic!("unexpected primitive {:?}", s),
+        })
+        .collect();
+    assert_eq!(
+        duties,
+        vec![Value::Float(12.5), Value::Float(25.0), Value::Float(37.5)]
+    );
+
+    assert!(matches!(
+        evaluator.invoke(&mut variables, "Missing", vec![]),
+        Err(EvalError::UnknownActivity(name)) if name == "Missing"
+    ));
+}
+
+/// an Iteration that adds each value of the iterator to `Sum`
+fn summation(range: IterationRange) -> Statement {
+    let variable =
+        |name: &str| MathElement::Operand(Operand::VariableRef(QualifiedName(name.to_string())));
+    Statement::Iteration(Iteration {
+        iterator_variable_ref: QualifiedName("X".to_string()),
+        range,
+        do_: Body {
+            statements: vec![Statement::MathOperation(MathOperation {
+                output_variable_ref: QualifiedName("Sum".to_string()),
+                elements: vec![
+                    variable("Sum"),
+                    variable("X"),
+                    MathElement::Operator(MathOperator::Add),
+                ],
+            })],
+        },
+    })
+}
+
+/// An Iteration over an array runs its body for each element, a numeric Iteration is capped
+#[test]
+fn test_iteration_range() {
+    let mut evaluator = Evaluator::new();
+    let mut variables = HashMap::from([
+        ("Sum".to_string(), Value::Integer(0)),
+        (
+            "Samples".to_string(),
+            Value::Array(vec![
+                Value::Integer(3),
+                Value::Integer(4),
+                Value::Integer(5),
+            ]),
+        ),
+    ]);
+    let body = Body {
+        statements: vec![summation(IterationRange::OverArray(QualifiedName(
+            "Samples".to_string(),
+        )))],
+    };
+    assert_eq!(
+        evaluator.execute(&mut variables, &body).unwrap(),
+        Some(Value::Integer(12))
+    );
+    assert_eq!(variables["X"], Value::Integer(5));
+
+    let body = Body {
+        statements: vec![summation(IterationRange::OverArray(QualifiedName(
+            "Sum".to_string(),
+        )))],
+    };
+    assert!(matches!(
+        evaluator.execute(&mut variables, &body),
+        Err(EvalError::TypeMismatch(_))
+    ));
+
+    let literal = |value: &str| Operand::Value(Literal(value.to_string()));
+    let body = Body {
+        statements: vec![summation(IterationRange::Numeric {
+            start_at: literal("0"),
+            step: literal("1"),
+            end_at: literal("1e12"),
+        })],
+    };
+    assert_eq!(
+        evaluator.execute(&mut variables, &body),
+        Err(EvalError::IterationLimitExceeded("X".to_string()))
+    );
+}
+
+/// A transition Guard must hold for the transition to fire
+#[test]
+fn test_guard() {
+    let component = get_component();
+    let machine = &component.implementation.state_machine_set.state_machines[0];
+    let guard = machine
+        .elements
+        .iter()
+        .find_map(|e| match e {
+            StateMachineElement::Transition(t) => t.guard.as_ref(),
+            _ => None,
+        })
+        .unwrap();
+    assert!(matches!(guard, BooleanExpression::ANDedConditions(c) if c.len() == 2));
+
+    let mut interpreter = StateMachineInterpreter::for_component(&component, machine).unwrap();
+    let measure = |temperature: &str| Event::Command {
+        interface: "Cmd".to_string(),
+        command: "Measure".to_string(),
+        arguments: HashMap::from([("Temperature".to_string(), temperature.to_string())]),
+    };
+
+    // too warm, the guard does not hold and nothing is stored
+    assert_eq!(interpreter.handle_event(&measure("25")).unwrap(), None);
+    assert_eq!(interpreter.current_state(), "Idle");
+    assert_eq!(interpreter.variable("Measured"), Some("15"));
+
+    let change = interpreter.handle_event(&measure("18")).unwrap().unwrap();
+    assert_eq!(change.to_state, "Regulating");
+    assert_eq!(interpreter.variable("Measured"), Some("18"));
+    assert_eq!(interpreter.variable("Error"), Some("2"));
+    assert_eq!(interpreter.variable("Duty"), Some("20"));
+    assert_eq!(change.activities[0].activity, "Regulate");
+    assert_eq!(change.activities[0].sent.len(), 1);
+}
+
+/// Operands must refer to declared variables, arguments or mission parameters
+#[test]
+fn test_resolve_operands() {
+    let res = resolve_modified(|statements| {
+        raw_math_operation(statements).elements[0] =
+            raw::MathElement::VariableRef(raw::VariableRefOperand {
+                variable_ref: "Undeclared".to_string(),
+            });
+    });
+    assert!(
+        matches!(res, Err(ResolveError::UnknownVariable(name)) if name == "Regulator/Regulate/Undeclared")
+    );
+
+    // calculations need numbers
+    let res = resolve_modified(|statements| {
+        raw_math_operation(statements).elements[0] =
+            raw::MathElement::VariableRef(raw::VariableRefOperand {
+                variable_ref: "Name".to_string(),
+            });
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidOperand(msg)) if msg.ends_with("Name is not a number"))
+    );
+
+    let res = resolve_modified(|statements| {
+        raw_math_operation(statements).elements[0] = raw::MathElement::Value(raw::ValueOperand {
+            value: "warm".to_string(),
+        });
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidOperand(msg)) if msg == "Error: warm is not a number")
+    );
+}
+
+/// Operators must be known and find their operands on the stack
+#[test]
+fn test_resolve_math_operation() {
+    let res = resolve_modified(|statements| {
+        raw_math_operation(statements).elements[2] =
+            raw::MathElement::Operator(raw::MathOperator {
+                operator: "cube".to_string(),
+            });
+    });
+    assert!(matches!(res, Err(ResolveError::InvalidMathOperator(op)) if op == "cube"));
+
+    let res = resolve_modified(|statements| {
+        let _ = raw_math_operation(statements).elements.remove(1);
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidMathOperation(msg)) if msg == "Error: Subtract needs 2 operands")
+    );
+
+    let res = resolve_modified(|statements| {
+        let _ = raw_math_operation(statements).elements.pop();
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidMathOperation(msg)) if msg == "Error: leaves 2 values on the stack")
+    );
+}
@@ -0,0 +1,296 @@
+//! 3.16 STATE MACHINES
+use std::collections::{HashMap, HashSet};
+
+use seds_rs::eds::ast::{
+    Component, Operand, PackageFile, ParameterOperation, StateMachine, StateMachineElement,
+    TransitionTrigger,
+};
+use seds_rs::eds::raw;
+use seds_rs::eds::resolve::{Resolve, ResolveError};
+use seds_rs::sim::statemachine::{ActivityPhase, Event, InterpreterError, StateMachineInterpreter};
+
+mod common;
+
+use common::{get_mission_params, open_file, parse};
+
+fn get_raw_package_file() -> raw::PackageFile {
+    let contents = open_file("eds/test/test_statemachine.xml");
+    parse(&contents)
+}
+
+fn get_component() -> Component {
+    let pf: PackageFile = get_raw_package_file()
+        .resolve(&get_mission_params())
+        .unwrap();
+    pf.package[0].component_set.components[0].clone()
+}
+
+fn get_state_machine(component: &Component) -> &StateMachine {
+    &component.implementation.state_machine_set.state_machines[0]
+}
+
+/// resolve the test file after modifying its (only) raw state machine
+fn resolve_modified(modify: fn(&mut raw::StateMachine)) -> Result<PackageFile, ResolveError> {
+    let mut pf = get_raw_package_file();
+    let component_set = pf.package[0].component_set.as_mut().unwrap();
+    modify(
+        &mut component_set.components[0]
+            .implementation
+            .state_machine_set
+            .state_machines[0],
+    );
+    pf.resolve(&get_mission_params())
+        .map_err(ResolveError::into_cause)
+}
+
+/// 3.16.2 Each StateMachine element shall include one or more of the following elements: `EntryState`, `ExitState`, `State`, and `Transition`.
+#[test]
+fn test_3_16_2() {
+    let component = get_component();
+    let sm = get_state_machine(&component);
+    assert!(!sm.elements.is_empty());
+
+    let res = resolve_modified(|sm| sm.elements.clear());
+    assert!(matches!(res, Err(ResolveError::EmptyStateMachine(name)) if name == "Control"));
+}
+
+/// 3.16.3 Each child element of a StateMachine element shall carry a name attribute identifying the name of that element.
+#[test]
+fn test_3_16_3() {
+    let component = get_component();
+    for element in get_state_machine(&component).elements.iter() {
+        assert!(!element.name_entity_type().name.0.is_empty());
+    }
+}
+
+/// 3.16.4 The name of each child element of a StateMachine element shall be unique within the state machine.
+#[test]
+fn test_3_16_4() {
+    let component = get_component();
+    let mut names = HashSet::new();
+    for element in get_state_machine(&component).elements.iter() {
+        assert!(names.insert(element.name_entity_type().name.0.clone()));
+    }
+
+    // a transition named like a state is rejected
+    let res = resolve_modified(|sm| {
+        if let raw::StateMachineElement::Transition(t) = &mut sm.elements[6] {
+            t.name_entity_type.name = "On".to_string();
+        }
+    });
+    assert!(matches!(res, Err(ResolveError::DuplicateName(name)) if name == "Control/On"));
+}
+
+/// 3.16.5 Each State element shall include zero or one of the following elements: `OnEntry`, `OnExit`.
+#[test]
+fn test_3_16_5() {
+    let component = get_component();
+    let states: Vec<_> = get_state_machine(&component)
+        .elements
+        .iter()
+        .filter_map(|e| match e {
+            StateMachineElement::State(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(states.len(), 2);
+    assert!(states[0].on_entry.is_some() && states[0].on_exit.is_none());
+    assert!(states[1].on_entry.is_some() && states[1].on_exit.is_some());
+}
+
+/// 3.16.6 The `OnEntry`, `OnExit`, and `Do` elements shall each specify the name of an activity, using the activity attribute, to be invoked on entry to the state, immediately before exit from the state, and when performing a transition between states, respectively.
+#[test]
+fn test_3_16_6() {
+    let component = get_component();
+    let sm = get_state_machine(&component);
+    let transition = sm
+        .elements
+        .iter()
+        .find_map(|e| match e {
+            StateMachineElement::Transition(t) if t.name_entity_type.name.0 == "Enable" => Some(t),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(transition.do_.as_ref().unwrap().activity.0, "LogTransition");
+    assert!(matches!(
+        &transition.trigger,
+        TransitionTrigger::OnCommandPrimitive(cmd) if cmd.command.0 == "Enable"
+    ));
+}
+
+/// 3.16.7 The `OnEntry`, `OnExit`, and `Do` elements shall each include zero or more ArgumentValue elements, each of which, in turn, carries a name attribute, identifying the name of an activity argument, and includes either a Value element, specifying a literal value to be associated with the named activity argument, or a VariableRef element, specifying a component variable to associate with the named activity argument
+#[test]
+fn test_3_16_7() {
+    let component = get_component();
+    let sm = get_state_machine(&component);
+    match &sm.elements[2] {
+        StateMachineElement::State(s) => {
+            let args = &s.on_entry.as_ref().unwrap().argument_values;
+            assert_eq!(args[0].name.0, "Enable");
+            assert!(matches!(&args[0].value, Operand::Value(v) if v.0 == "1"));
+            assert!(matches!(&args[1].value, Operand::VariableRef(v) if v.0 == "Target"));
+        }
+        _ => panic!("expected State"),
+    }
+
+    // both a Value and a VariableRef is not allowed
+    let res = resolve_modified(|sm| {
+        if let raw::StateMachineElement::State(s) = &mut sm.elements[1] {
+            s.on_entry.as_mut().unwrap().argument_values[0].variable_ref =
+                Some(raw::VariableRefOperand {
+                    variable_ref: "Target".to_string(),
+                });
+        }
+    });
+    assert!(matches!(res, Err(ResolveError::InvalidOperand(name)) if name == "Enable"));
+}
+
+#[test]
+fn test_invalid_state_reference() {
+    let res = resolve_modified(|sm| {
+        if let raw::StateMachineElement::Transition(t) = &mut sm.elements[6] {
+            t.to_state = "Standby".to_string();
+        }
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidStateReference(name)) if name == "Control/Standby")
+    );
+}
+
+#[test]
+fn test_interpreter_commands() {
+    let component = get_component();
+    let mut sim =
+        StateMachineInterpreter::for_component(&component, get_state_machine(&component)).unwrap();
+    assert_eq!(sim.current_state(), "PowerOn");
+
+    // leave the entry state through the zero delay timer
+    let changes = sim.advance_time(0).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].to_state, "Off");
+    assert_eq!(changes[0].activities[0].activity, "SwitchHeater");
+    assert_eq!(changes[0].activities[0].phase, ActivityPhase::OnEntry);
+
+    // unknown commands are ignored
+    assert_eq!(
+        sim.handle_event(&Event::command("Cmd", "Reboot")).unwrap(),
+        None
+    );
+
+    // command arguments are stored in the component variables and passed on to activities
+    let event = Event::Command {
+        interface: "Cmd".to_string(),
+        command: "Enable".to_string(),
+        arguments: HashMap::from([("Setpoint".to_string(), "42".to_string())]),
+    };
+    let change = sim.handle_event(&event).unwrap().unwrap();
+    assert_eq!(change.transition, "Enable");
+    assert_eq!(sim.current_state(), "On");
+    assert_eq!(sim.variable("Target"), Some("42"));
+    let activities: Vec<_> = change
+        .activities
+        .iter()
+        .map(|a| (a.activity.as_str(), a.phase))
+        .collect();
+    assert_eq!(
+        activities,
+        [
+            ("LogTransition", ActivityPhase::Do),
+            ("SwitchHeater", ActivityPhase::OnEntry)
+        ]
+    );
+    assert_eq!(
+        change.activities[1].arguments,
+        [
+            ("Enable".to_string(), "1".to_string()),
+            ("Level".to_string(), "42".to_string())
+        ]
+    );
+
+    // leaving On invokes its OnExit activity first
+    let change = sim
+        .handle_event(&Event::command("Cmd", "Disable"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.activities[0].activity, "ReportOff");
+    assert_eq!(change.activities[0].phase, ActivityPhase::OnExit);
+    assert_eq!(sim.current_state(), "Off");
+}
+
+#[test]
+fn test_interpreter_timer_and_exit() {
+    let component = get_component();
+    let mut sim =
+        StateMachineInterpreter::for_component(&component, get_state_machine(&component)).unwrap();
+    let _ = sim.advance_time(0).unwrap();
+    let _ = sim.handle_event(&Event::command("Cmd", "Enable")).unwrap();
+    // the initial value of Target is used when the command carries no argument
+    assert_eq!(sim.variable("Target"), Some("20"));
+
+    // the watchdog fires 5 s after entering On
+    assert!(sim.advance_time(4_000_000_000).unwrap().is_empty());
+    let changes = sim.advance_time(1_000_000_000).unwrap();
+    assert_eq!(changes[0].transition, "Watchdog");
+    assert_eq!(sim.current_state(), "Off");
+
+    // a parameter set moves to the exit state, which ends the simulation
+    let _ = sim.handle_event(&Event::command("Cmd", "Enable")).unwrap();
+    let change = sim
+        .handle_event(&Event::parameter(
+            "Cmd",
+            "Overtemp",
+            ParameterOperation::Set,
+        ))
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.to_state, "Fault");
+    assert!(sim.is_finished());
+    assert_eq!(
+        sim.handle_event(&Event::command("Cmd", "Disable")).unwrap(),
+        None
+    );
+}
+
+/// the interpreter of the test file, with its watchdog timer changed
+fn with_watchdog(to_state: &str, nanoseconds: &str) -> Component {
+    let contents = open_file("eds/test/test_statemachine.xml").replace(
+        r#"<Transition name="Watchdog" fromState="On" toState="Off">
+								<OnTimer nanosecondsAfterEntry="5000000000" />"#,
+        &format!(
+            r#"<Transition name="Watchdog" fromState="On" toState="{}">
+								<OnTimer nanosecondsAfterEntry="{}" />"#,
+            to_state, nanoseconds
+        ),
+    );
+    let rpf: raw::PackageFile = parse(&contents);
+    let pf = rpf.resolve(&get_mission_params()).unwrap();
+    pf.package[0].component_set.components[0].clone()
+}
+
+#[test]
+fn test_interpreter_periodic_timer() {
+    // a 1 ms timer that re-enters its state fires once per period
+    let component = with_watchdog("On", "1000000");
+    let mut sim =
+        StateMachineInterpreter::for_component(&component, get_state_machine(&component)).unwrap();
+    let _ = sim.advance_time(0).unwrap();
+    let _ = sim.handle_event(&Event::command("Cmd", "Enable")).unwrap();
+
+    let changes = sim.advance_time(1_000_000_000).unwrap();
+    assert_eq!(changes.len(), 1000);
+    assert!(changes.iter().all(|c| c.transition == "Watchdog"));
+    assert_eq!(sim.current_state(), "On");
+}
+
+#[test]
+fn test_interpreter_timer_loop() {
+    // a zero delay timer that re-enters its state never lets time pass
+    let component = with_watchdog("On", "0");
+    let mut sim =
+        StateMachineInterpreter::for_component(&component, get_state_machine(&component)).unwrap();
+    let _ = sim.advance_time(0).unwrap();
+    let _ = sim.handle_event(&Event::command("Cmd", "Enable")).unwrap();
+
+    let err = sim.advance_time(0).unwrap_err();
+    assert!(matches!(err, InterpreterError::TimerLoop(state) if state == "On"));
+}
@@ -1,9 +1,11 @@
 //! 3.2 ELECTRONIC DATA SHEETS AND THE ASSOCIATED SCHEMA
-use seds_rs::eds::raw::PackageFile;
+#![allow(clippy::assertions_on_constants)]
+use seds_rs::eds::raw::{DataSheet, PackageFile};
+use seds_rs::parse::{Parser, Strictness};
 use std::fs;
 use std::path::Path;
 
-use crate::common::open_file;
+use crate::common::{open_file, parse};
 
 mod common;
 
@@ -11,7 +13,7 @@ mod common;
 #[test]
 fn test_3_2_1() {
     let contents = open_file("eds/test/test_eds.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     assert_eq!(package.package[0].name_entity_type.name, "TestPackage");
 }
 
@@ -20,7 +22,7 @@ fn test_3_2_1() {
 fn test_3_2_2() {
     // Testing that a package file has a single top-level XML file would require that we count
     // the number of XML root elements in a file, but XML files by definition have only one root
-    // element. The XML parsing library (serde_xml_rs) would error out if there were more.
+    // element. The XML parser would error out if there were more.
     let path = Path::new("eds/test/test_eds.xml");
     assert!(fs::metadata(path).is_ok());
 }
@@ -31,17 +33,35 @@ fn test_3_2_3() {
     // The referenced file will need to be manually inspected and confirmed that it is a valid
     // SEDS package file. For this test, we'll simply load the file and parse it.
     let contents = open_file("eds/test/test_eds.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     assert_eq!(package.package[0].name_entity_type.name, "TestPackage");
 }
 
 /// **3.2.4** When a package file is used by a datasheet, XInclude (reference [5]) may be used to incorporate the Package element of that file into a single logical document compliant to the DataSheet element of the SEDS schema.
 #[test]
 fn test_3_2_4() {
-    // Given that this requirement states "may be used", this is an optional feature.
-    // This test can only be performed if we have an example of a package file using XInclude.
-    // Otherwise, this test will remain as a placeholder.
-    assert!(true);
+    let contents = open_file("eds/test/include/datasheet.xml");
+    let parsed = Parser::new("eds/test/include/datasheet.xml")
+        .strictness(Strictness::Strict)
+        .include_path("eds/test/include/lib")
+        .parse::<DataSheet>(&contents)
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+    let datasheet = parsed.value;
+    assert_eq!(datasheet.devices[0].name_entity_type.name, "Heater");
+    let names: Vec<_> = datasheet
+        .packages
+        .iter()
+        .map(|p| p.name_entity_type.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Heater", "Units"]);
+
+    // the included package is the one of the package file
+    let package_file: PackageFile = Parser::new("eds/test/include/heater.xml")
+        .parse(&open_file("eds/test/include/heater.xml"))
+        .unwrap()
+        .value;
+    assert_eq!(datasheet.packages[0], package_file.package[0]);
 }
 
 /// **3.2.5** A package file shall be a single standalone XML file without any use of XInclude.
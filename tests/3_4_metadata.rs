@@ -0,0 +1,151 @@
+//! 3.4 METADATA
+use seds_rs::eds::ast::{MetadataValue, NumericValue, PackageFile};
+use seds_rs::eds::raw;
+use seds_rs::eds::resolve::{Resolve, ResolveError};
+use seds_rs::eds::value::Value;
+use seds_rs::sim::activity::Evaluator;
+
+mod common;
+
+use common::{get_mission_params, open_file, parse};
+
+/// metadata values given as literals
+const LITERALS: &str = "eds/test/test_activities.xml";
+
+/// metadata values calculated by math operations and conditionals
+const CALCULATED: &str = "eds/test/test_calculated_metadata.xml";
+
+fn get_raw_package_file(path: &str) -> raw::PackageFile {
+    let contents = open_file(path);
+    parse(&contents)
+}
+
+fn get_package_file(path: &str) -> PackageFile {
+    get_raw_package_file(path)
+        .resolve(&get_mission_params())
+        .unwrap()
+}
+
+/// resolve a test file after modifying its raw metadata values
+fn resolve_modified(
+    path: &str,
+    modify: fn(&mut Vec<raw::MetadataValue>),
+) -> Result<PackageFile, ResolveError> {
+    let mut pf = get_raw_package_file(path);
+    modify(
+        &mut pf
+            .metadata
+            .as_mut()
+            .unwrap()
+            .value_set
+            .as_mut()
+            .unwrap()
+            .values,
+    );
+    pf.resolve(&get_mission_params())
+        .map_err(ResolveError::into_cause)
+}
+
+/// 3.4.1 A Metadata element shall specify a hierarchical set of categories of constant data values, each of which can be associated with machine-understandable semantics.
+/// 3.4.2 A Category element shall specify a categorization or grouping of metadata.
+/// 3.4.3 The Category element is based on NamedEntityType (see 3.3.6).
+#[test]
+fn test_3_4_1() {
+    let pf = get_package_file(LITERALS);
+    let metadata = pf.metadata.unwrap();
+    assert_eq!(metadata.values.len(), 3);
+    assert_eq!(metadata.categories.len(), 1);
+
+    let thermal = &metadata.categories[0];
+    assert_eq!(thermal.name_entity_type.name.0, "Thermal");
+    assert_eq!(thermal.values.len(), 1);
+    assert_eq!(
+        thermal.values[0].name_entity_type().name.0,
+        "MaxTemperature"
+    );
+}
+
+/// 3.4.5 A MetadataValueSet element shall contain one or more child elements, each of which is either a DateValue element, a FloatValue element, an IntegerValue element, or a StringValue element.
+/// 3.4.7 DateValue and StringValue elements shall contain a value attribute specifying the value of the metadata as a literal, per table 3-1.
+#[test]
+fn test_3_4_5() {
+    let values = get_package_file(LITERALS).metadata.unwrap().values;
+    assert!(matches!(&values[0], MetadataValue::StringValue(v) if v.value.0 == "ACME"));
+    assert!(matches!(&values[1], MetadataValue::DateValue(v) if v.value.0 == "2024-05-01"));
+    assert!(matches!(&values[2], MetadataValue::IntegerValue(_)));
+    let values = get_package_file(CALCULATED).metadata.unwrap().values;
+    assert!(matches!(&values[0], MetadataValue::FloatValue(_)));
+}
+
+/// 3.4.8 FloatValue and IntegerValue elements may contain a value attribute specifying the value of the metadata as a literal, per table 3-1.
+#[test]
+fn test_3_4_8() {
+    let values = get_package_file(LITERALS).metadata.unwrap().values;
+    match &values[2] {
+        MetadataValue::IntegerValue(v) => {
+            // mission parameters are substituted
+            assert!(matches!(&v.value, NumericValue::Literal(l) if l.0 == "2"));
+        }
+        v => panic!("unexpected value {:?}", v),
+    }
+
+    // an IntegerValue literal must be an integer
+    let res = resolve_modified(LITERALS, |values| {
+        if let raw::MetadataValue::IntegerValue(v) = &mut values[2] {
+            v.value = Some("2.5".to_string());
+        }
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidMetadataValue(msg)) if msg == "MaxApplications: 2.5")
+    );
+}
+
+/// 3.4.9 If a FloatValue or IntegerValue element does not contain a value attribute, the body of the element shall specify a MathOperation element, as described in 3.15.32 below, or a Conditional element, as described in 3.15.37 below, to describe how the value should be calculated.
+#[test]
+fn test_3_4_9() {
+    let params = get_mission_params();
+    let metadata = get_package_file(LITERALS).metadata.unwrap();
+    let mut evaluator = Evaluator::new().with_parameters(&params);
+
+    let expected = [
+        Value::from("ACME"),
+        Value::from("2024-05-01"),
+        Value::Integer(2),
+    ];
+    for (value, expected) in metadata.values.iter().zip(expected) {
+        assert_eq!(evaluator.metadata_value(value).unwrap(), expected);
+    }
+    assert_eq!(
+        evaluator
+            .metadata_value(&metadata.categories[0].values[0])
+            .unwrap(),
+        Value::Float(85.5)
+    );
+
+    let calculated = get_package_file(CALCULATED).metadata.unwrap();
+    let expected = [Value::Float(0.75), Value::Integer(16)];
+    assert_eq!(calculated.values.len(), expected.len());
+    for (value, expected) in calculated.values.iter().zip(expected) {
+        assert_eq!(evaluator.metadata_value(value).unwrap(), expected);
+    }
+
+    // neither a value nor a calculation
+    let res = resolve_modified(CALCULATED, |values| {
+        if let raw::MetadataValue::FloatValue(v) = &mut values[0] {
+            v.math_operation = None;
+        }
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidMetadataValue(msg)) if msg.starts_with("Scale"))
+    );
+
+    // both a value and a calculation
+    let res = resolve_modified(CALCULATED, |values| {
+        if let raw::MetadataValue::FloatValue(v) = &mut values[0] {
+            v.value = Some("1.0".to_string());
+        }
+    });
+    assert!(
+        matches!(res, Err(ResolveError::InvalidMetadataValue(msg)) if msg.starts_with("Scale"))
+    );
+}
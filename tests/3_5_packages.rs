@@ -1,20 +1,20 @@
 //! 3.5 PACKAGES
 use std::collections::HashSet;
 
-use common::open_file;
+use common::{open_file, parse};
 use seds_rs::eds::raw::{DataSheet, Package, PackageFile};
 
 mod common;
 
 fn get_test_data_sheet() -> DataSheet {
     let contents = open_file("eds/test/test_datasheet.xml");
-    let datasheet: DataSheet = serde_xml_rs::from_str(&contents).unwrap();
+    let datasheet: DataSheet = parse(&contents);
     datasheet
 }
 
 fn get_test_package() -> Package {
     let contents = open_file("eds/test/test_package.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     package.package[0].clone()
 }
 
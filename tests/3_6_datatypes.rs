@@ -5,17 +5,17 @@ use seds_rs::eds::raw::{DataType, DataTypeSet, Package, PackageFile};
 
 mod common;
 
-use common::open_file;
+use common::{open_file, parse};
 
 fn get_test_package() -> Package {
     let contents = open_file("eds/test/test_datatypes.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     package.package[0].clone()
 }
 
 fn get_test_data_type_set() -> DataTypeSet {
     let contents = open_file("eds/test/test_datatypes.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     package.package[0].clone().data_type_set.unwrap()
 }
 
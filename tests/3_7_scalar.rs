@@ -3,11 +3,11 @@ use seds_rs::eds::raw::{DataType, DataTypeSet, PackageFile};
 
 mod common;
 
-use common::open_file;
+use common::{open_file, parse};
 
 fn get_test_data_type_set() -> DataTypeSet {
     let contents = open_file("eds/test/test_datatypes.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     package.package[0].clone().data_type_set.unwrap()
 }
 
@@ -44,10 +44,7 @@ fn test_3_7_2() {
         match data_type {
             DataType::FloatDataType(data) => {
                 let data_string = data.encoding.unwrap().byte_order.unwrap();
-                assert!(
-                    data_string == *"bigEndian"
-                        || data_string == *"littleEndian"
-                )
+                assert!(data_string == *"bigEndian" || data_string == *"littleEndian")
             }
             DataType::IntegerDataType(data) => {
                 let data_string = data.encoding.unwrap().byte_order;
@@ -210,15 +207,13 @@ fn test_3_7_12() {
         if let DataType::StringDataType(data) = data_type {
             if let Some(encoding) = data.encoding {
                 let encoding_string = encoding.encoding.unwrap();
-                assert!(
-                    encoding_string == *"UTF8" || encoding_string == *"ASCII"
-                );
+                assert!(encoding_string == *"UTF8" || encoding_string == *"ASCII");
             }
         }
     }
 }
 
-/// 3.7.13 The optional terminationCharacter attribute of a StringDataEncoding element shall specify the termination character for the string.
+// 3.7.13 The optional terminationCharacter attribute of a StringDataEncoding element shall specify the termination character for the string.
 // #[test]
 // fn test_3_7_13() {
 //     let data_type_set = get_test_data_type_set();
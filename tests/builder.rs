@@ -0,0 +1,226 @@
+//! Building EDS models in Rust
+mod common;
+
+use common::get_mission_params;
+use seds_rs::eds::ast::{ByteOrder, DataType, ErrorControlType, IntegerEncoding, StringEncoding};
+use seds_rs::eds::builder::{DataTypeBuilder, EntryBuilder, PackageBuilder, PackageFileBuilder};
+use seds_rs::eds::layout::Layouts;
+use seds_rs::eds::raw::PackageFile;
+use seds_rs::eds::resolve::resolve_all;
+use seds_rs::parse::Parser;
+use seds_rs::validate::{Schema, Validator};
+use seds_rs::write::to_xml;
+
+fn pump() -> PackageBuilder {
+    PackageBuilder::new("Pump")
+        .short_description("pump protocol")
+        .data_type(
+            DataTypeBuilder::integer("Speed", 16)
+                .range(0, 1000)
+                .unit("hertz"),
+        )
+        .data_type(DataTypeBuilder::integer("Checksum", 8))
+        .data_type(
+            DataTypeBuilder::enumerated("Mode", 4)
+                .label("IDLE", 0)
+                .label("PUMPING", 1),
+        )
+        .data_type(
+            DataTypeBuilder::container("Packet")
+                .abstract_()
+                .entry(EntryBuilder::entry("Mode", "Mode"))
+                .entry(EntryBuilder::padding(4))
+                .trailer_entry(EntryBuilder::error_control(
+                    "Crc",
+                    "Checksum",
+                    ErrorControlType::CRC8,
+                )),
+        )
+        .data_type(
+            DataTypeBuilder::container("Status")
+                .base_type("Packet")
+                .value_constraint("Mode", "PUMPING")
+                .entry(
+                    EntryBuilder::entry("Speed", "Pump/Speed").short_description("current speed"),
+                ),
+        )
+}
+
+#[test]
+fn test_build() {
+    let builder = PackageFileBuilder::new().package(pump());
+    let package_file = builder.resolve(&get_mission_params()).unwrap();
+    let package = &package_file.package[0];
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    let layout = Layouts::new(&[&package_file])
+        .container(package, status)
+        .unwrap();
+    let fields: Vec<_> = layout
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.offset_in_bits, f.size_in_bits))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("Mode", Some(0), Some(4)),
+            ("", Some(4), Some(4)),
+            ("Speed", Some(8), Some(16)),
+            ("Crc", Some(24), Some(8)),
+        ]
+    );
+
+    // the datasheet written from the raw model reads back to the same model
+    let raw = builder.build().unwrap();
+    let xml = to_xml(&raw).unwrap();
+    let parsed = Parser::new("pump.xml").parse::<PackageFile>(&xml).unwrap();
+    assert_eq!(parsed.value, raw);
+    assert_eq!(
+        resolve_all(&parsed.value, &get_mission_params()).unwrap(),
+        package_file
+    );
+}
+
+#[test]
+fn test_default_ranges() {
+    let builder = PackageFileBuilder::new().package(
+        PackageBuilder::new("Ranges")
+            .data_type(DataTypeBuilder::integer("U8", 8))
+            .data_type(
+                DataTypeBuilder::integer("I16", 16).encoding(IntegerEncoding::TwosComplement),
+            )
+            .data_type(
+                DataTypeBuilder::integer("M8", 8)
+                    .range("${MIN}", 10)
+                    .encoding(IntegerEncoding::SignMagnitude)
+                    .byte_order(ByteOrder::LittleEndian),
+            ),
+    );
+    let raw = builder.build().unwrap();
+    let ranges: Vec<_> = raw.package[0]
+        .data_type_set
+        .as_ref()
+        .unwrap()
+        .data_types
+        .iter()
+        .map(|dt| match dt {
+            seds_rs::eds::raw::DataType::IntegerDataType(dt) => (
+                dt.range.min_max_range.min.as_str(),
+                dt.range.min_max_range.max.as_str(),
+            ),
+            _ => unreachable!(),
+        })
+        .collect();
+    // an explicit range is kept, with its parameters
+    assert_eq!(
+        ranges,
+        [("0", "255"), ("-32768", "32767"), ("${MIN}", "10")]
+    );
+}
+
+#[test]
+fn test_invariants() {
+    let builder = PackageFileBuilder::new()
+        .package(
+            pump()
+                .data_type(DataTypeBuilder::integer("Speed", 0))
+                .data_type(DataTypeBuilder::enumerated("Empty", 8).range(0, 1))
+                .data_type(DataTypeBuilder::array("Samples", "Sample"))
+                .data_type(DataTypeBuilder::sub_range("Slow", "Speed"))
+                .data_type(
+                    DataTypeBuilder::container("Command")
+                        .type_constraint("Mode", "Mode")
+                        .entry(EntryBuilder::entry("Id", "Other/Id"))
+                        .entry(EntryBuilder::padding(0).unit("meter"))
+                        .trailer_entry(EntryBuilder::entry("Id", "Speed"))
+                        .trailer_entry(EntryBuilder::length("", "Speed")),
+                ),
+        )
+        .package(PackageBuilder::new("Other"));
+    let errors: Vec<_> = builder
+        .build()
+        .unwrap_err()
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    let pump = "Package[Pump]/DataTypeSet";
+    assert_eq!(
+        errors,
+        [
+            format!("{pump}/IntegerDataType[Speed]: duplicate name `Speed`"),
+            format!("{pump}/IntegerDataType[Speed]: sizeInBits of 0"),
+            format!("{pump}/EnumeratedDataType[Empty]: `range` does not apply to the EnumeratedDataType"),
+            format!("{pump}/EnumeratedDataType[Empty]: no Enumeration"),
+            format!("{pump}/ArrayDataType[Samples]: no Dimension"),
+            format!("{pump}/ArrayDataType[Samples]: unknown type `Sample`"),
+            format!("{pump}/SubRangeDataType[Slow]: no Range"),
+            format!("{pump}/ContainerDataType[Command]: constraints without a base type"),
+            format!("{pump}/ContainerDataType[Command]/EntryList/Entry[Id]: unknown type `Other/Id`"),
+            format!("{pump}/ContainerDataType[Command]/EntryList/PaddingEntry[0]: sizeInBits of 0"),
+            format!("{pump}/ContainerDataType[Command]/EntryList/PaddingEntry[0]: `unit` does not apply to the PaddingEntry"),
+            format!("{pump}/ContainerDataType[Command]/TrailerEntryList/Entry[Id]: duplicate name `Id`"),
+            format!("{pump}/ContainerDataType[Command]/TrailerEntryList/LengthEntry[]: empty name"),
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_errors() {
+    // the builder leaves the terms of the semantics to the resolver
+    let builder = PackageFileBuilder::new().package(
+        PackageBuilder::new("Pump")
+            .data_type(DataTypeBuilder::integer("Speed", 16).unit("furlong")),
+    );
+    let errors = builder.resolve(&get_mission_params()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Package[Pump]/DataTypeSet/IntegerDataType[Speed]@unit: unknown semantic term `furlong`"
+    );
+}
+
+#[test]
+fn test_valid_datasheets() {
+    // every kind of data type, with its settings, is written as a datasheet valid for the schema
+    let kinds = [
+        DataTypeBuilder::integer("Count", 32)
+            .encoding(IntegerEncoding::TwosComplement)
+            .byte_order(ByteOrder::LittleEndian)
+            .range(-10, 10)
+            .unit("second"),
+        DataTypeBuilder::enumerated("Valve", 2)
+            .label("CLOSED", 0)
+            .label("OPEN", 3),
+        DataTypeBuilder::float("Pressure", 64)
+            .range(-1.5, 1.5)
+            .long_description("pressure at the outlet"),
+        DataTypeBuilder::boolean("Running", 1),
+        DataTypeBuilder::string("Label", 16)
+            .string_encoding(StringEncoding::UTF8)
+            .fixed_length(false),
+        DataTypeBuilder::array("Samples", "Speed")
+            .dimension(4)
+            .dimension(2),
+        DataTypeBuilder::sub_range("Slow", "Speed").range(0, 100),
+        DataTypeBuilder::container("Reading")
+            .base_type("Packet")
+            .range_constraint("Mode", 0, 1)
+            .type_constraint("Mode", "Mode")
+            .entry(EntryBuilder::fixed_value("Id", "Checksum", 7))
+            .entry(EntryBuilder::length("Length", "Checksum"))
+            .entry(EntryBuilder::list("Values", "Speed", "Length"))
+            .trailer_entry(EntryBuilder::padding(8)),
+    ];
+    let schema = Schema::seds().unwrap();
+    for kind in kinds {
+        let raw = PackageFileBuilder::new()
+            .package(pump().data_type(kind))
+            .build()
+            .unwrap();
+        let xml = to_xml(&raw).unwrap();
+        let result = Validator::new(&schema, "pump.xml").validate(&xml);
+        assert!(result.is_ok(), "{:?}\n{}", result, xml);
+    }
+}
@@ -1,5 +1,8 @@
-use common::{get_mission_params, open_file};
+use common::{get_mission_params, open_file, parse};
 use seds_rs::codegen::codegen_packagefiles;
+use seds_rs::codegen::context::{CodegenContext, Namespace};
+use seds_rs::codegen::diagram::get_datatype_packet_svg;
+use seds_rs::codegen::RustCodegenError;
 use seds_rs::eds::ast::PackageFile;
 use seds_rs::eds::raw;
 use seds_rs::{codegen::rustfmt, eds::resolve::Resolve};
@@ -41,16 +44,15 @@ fn test_example_codegen() {
         "eds/test/simplified_spacepacket.xml",
         // "eds/test/test_container.xml", // we don't support List's yet
         "eds/test/test_datasheet.xml",
+        "eds/test/test_semantics.xml",
     ];
 
     test_paths_codegen(&paths);
 }
 
 fn test_paths_codegen(paths: &[&str]) {
-    let rpackagefiles: Vec<raw::PackageFile> = paths
-        .iter()
-        .map(|fp| serde_xml_rs::from_str(&open_file(fp)).unwrap())
-        .collect();
+    let rpackagefiles: Vec<raw::PackageFile> =
+        paths.iter().map(|fp| parse(&open_file(fp))).collect();
 
     let ectx = get_mission_params();
     let packagefiles: Vec<PackageFile> = rpackagefiles
@@ -70,3 +72,68 @@ fn test_paths_codegen(paths: &[&str]) {
     let mut file = File::create("test_output/cfe.rs").unwrap();
     file.write_all(code.as_bytes()).unwrap();
 }
+
+/// unsupported datasheets are errors, not panics
+#[test]
+fn test_codegen_errors() {
+    let ectx = get_mission_params();
+    let codegen = |contents: &str| {
+        let rpf: raw::PackageFile = parse(contents);
+        let pf = rpf.resolve(&ectx).unwrap();
+        codegen_packagefiles(&[&pf]).unwrap_err()
+    };
+
+    // nested package names are not identifiers
+    let err = codegen(&open_file("eds/test/test_package.xml"));
+    assert!(matches!(
+        err.cause(),
+        RustCodegenError::InvalidIdentifier(_)
+    ));
+
+    let err = codegen(&open_file("eds/test/test_datatypes.xml").replace(
+        r#"<Enumeration label="Stop" value="1" />"#,
+        r#"<Enumeration label="Stop" value="1.5" />"#,
+    ));
+    assert!(matches!(err.cause(), RustCodegenError::InvalidEnumerationValue(v) if v == "1.5"));
+    assert!(err
+        .path()
+        .ends_with("EnumeratedDataType[StartStop]/EnumerationList/Enumeration[1]"));
+
+    // values with a unit whose encoding can not be converted to SI values
+    let err = codegen(&open_file("eds/test/test_semantics.xml").replace(
+        r#"encodingAndPrecision="IEEE754_2008_single" byteOrder="bigEndian" sizeInBits="32""#,
+        r#"encodingAndPrecision="IEEE754_2008_quadruple" byteOrder="bigEndian" sizeInBits="128""#,
+    ));
+    assert!(
+        matches!(err.cause(), RustCodegenError::UnsupportedEncoding(e) if e == "IEEE754_2008_quadruple")
+    );
+    assert!(err.path().ends_with("FloatDataType[Angle]"), "{}", err);
+
+    // diagrams of containers with unsupported entries
+    let rpf: raw::PackageFile = parse(
+        r#"<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Lists">
+    <DataTypeSet>
+      <IntegerDataType name="Count">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" />
+        <Range><MinMaxRange min="0" max="255" rangeType="inclusiveMinInclusiveMax" /></Range>
+      </IntegerDataType>
+      <ContainerDataType name="Samples">
+        <EntryList>
+          <Entry name="Length" type="Count" />
+          <ListEntry name="Values" type="Count" listLengthField="Length" />
+        </EntryList>
+      </ContainerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#,
+    );
+    let pf = rpf.resolve(&ectx).unwrap();
+    let pkg = &pf.package[0];
+    let namespace = Namespace::try_from(vec![&pf]).unwrap();
+    let locals = Namespace::try_from(pkg).unwrap();
+    let ctx = CodegenContext::new(&locals, &namespace);
+    let container = &pkg.data_type_set.data_types[1];
+    let err = get_datatype_packet_svg(container, &ctx).unwrap_err();
+    assert!(matches!(err, RustCodegenError::UnsupportedEntryElement(e) if e.name() == "Values"));
+}
@@ -1,6 +1,8 @@
 use std::{fs, io::Read, path::Path};
 
+use seds_rs::eds::{ast, raw, resolve::resolve_all};
 use seds_rs::expr::ExpressionContext;
+use seds_rs::parse::{FromXml, Parser};
 
 /// unsafe way to load file into string
 /// (unsafe because it assumes the file exists and it will fail the test if not)
@@ -36,6 +38,11 @@ pub fn get_mission_params() -> ExpressionContext {
             "ES_PERF_MAX_IDS": "2",
             "ES_POOL_MAX_BUCKETS": "2",
             "TBL_MAX_FULL_NAME_LEN": "2",
+            "ES_CMD_TOPICID": "6",
+            "ES_SEND_HK_TOPICID": "8",
+            "ES_HK_TLM_TOPICID": "0",
+            "ES_APP_TLM_TOPICID": "11",
+            "ES_MEMSTATS_TLM_TOPICID": "16",
         },
         "CFE_SB": {
             "MSGID_BIT_SIZE": "2",
@@ -47,3 +54,47 @@ pub fn get_mission_params() -> ExpressionContext {
     });
     ExpressionContext::from_json(&json).unwrap()
 }
+
+/// parse a test datasheet or package file; content the models do not know is skipped, see
+/// the tests of the parser for its warnings
+#[allow(dead_code)]
+pub fn parse<T: FromXml>(contents: &str) -> T {
+    Parser::new("test.xml").parse::<T>(contents).unwrap().value
+}
+
+/// parse and resolve a test package file with the default mission parameters; it must parse
+/// without warnings
+#[allow(dead_code)]
+pub fn resolved(contents: &str) -> ast::PackageFile {
+    resolved_with(contents, &get_mission_params())
+}
+
+/// parse and resolve a test package file, named `pump.xml`, with the given parameters
+#[allow(dead_code)]
+pub fn resolved_with(contents: &str, parameters: &ExpressionContext) -> ast::PackageFile {
+    let parsed = Parser::new("pump.xml")
+        .parse::<raw::PackageFile>(contents)
+        .unwrap();
+    assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+    resolve_all(&parsed.value, parameters).unwrap()
+}
+
+/// a package file of the `Pump` package with the data types of a test, written from the line
+/// after `<DataTypeSet>` like in the file, so that diagnostics keep their lines
+#[allow(unused_macros)]
+macro_rules! pump {
+    ($data_types:literal) => {
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Pump">
+    <DataTypeSet>"#,
+            $data_types,
+            r#"    </DataTypeSet>
+  </Package>
+</PackageFile>"#
+        )
+    };
+}
+#[allow(unused_imports)]
+pub(crate) use pump;
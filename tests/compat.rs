@@ -0,0 +1,267 @@
+//! Wire compatibility of two versions of a datasheet
+mod common;
+
+use common::{pump, resolved};
+use seds_rs::compat::{diff, Compatibility, Report};
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed" shortDescription="pump speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Flow">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <EnumeratedDataType name="Mode">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+          <Enumeration label="FAULT" value="2"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <ContainerDataType name="Status">
+        <EntryList>
+          <FixedValueEntry name="Id" type="Mode" fixedValue="1"/>
+          <Entry name="Mode" type="Mode"/>
+          <Entry name="Speed" type="Speed" shortDescription="current speed"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+/// the changes from the package to a new version of it
+fn report(new: &str) -> Report {
+    diff(&[&resolved(PACKAGE)], &[&resolved(new)])
+}
+
+/// the changes as they are printed, without the summary
+fn changes(new: &str) -> Vec<String> {
+    report(new)
+        .changes
+        .iter()
+        .map(|c| format!("{}: {}", c.data_type, c))
+        .collect()
+}
+
+#[test]
+fn test_unchanged() {
+    let report = report(PACKAGE);
+    assert!(report.changes.is_empty());
+    assert_eq!(report.compatibility(), None);
+    assert_eq!(report.to_string(), "no changes\n");
+}
+
+#[test]
+fn test_breaking_changes() {
+    let new = PACKAGE
+        .replace(r#"sizeInBits="8""#, r#"sizeInBits="16""#)
+        .replace(r#"fixedValue="1""#, r#"fixedValue="2""#);
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Mode: wire-breaking: resized from 8 bits to 16 bits",
+            "Pump/Status: wire-breaking: field `Id` resized from 8 bits to 16 bits",
+            "Pump/Status: wire-breaking: field `Id` fixed value changed from `1` to `2`",
+            "Pump/Status: wire-breaking: field `Mode` moved from bit 8 to bit 16",
+            "Pump/Status: wire-breaking: field `Mode` resized from 8 bits to 16 bits",
+            "Pump/Status: wire-breaking: field `Speed` moved from bit 16 to bit 32",
+        ]
+    );
+    assert!(report(&new).is_breaking());
+
+    let new = PACKAGE.replace(
+        r#"encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Flow">"#,
+        r#"encoding="unsigned" byteOrder="littleEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Flow">"#,
+    );
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Speed: wire-breaking: encoding changed from unsigned bigEndian integer to unsigned littleEndian integer",
+            "Pump/Status: wire-breaking: field `Speed` encoding changed from unsigned bigEndian integer to unsigned littleEndian integer",
+        ]
+    );
+}
+
+#[test]
+fn test_fields() {
+    // fields added at the end decode from the packets of the old version
+    let new = PACKAGE.replace(
+        r#"<Entry name="Speed" type="Speed" shortDescription="current speed"/>"#,
+        r#"<Entry name="Speed" type="Speed" shortDescription="current speed"/>
+          <Entry name="Flow" type="Flow"/>"#,
+    );
+    assert_eq!(
+        changes(&new),
+        ["Pump/Status: wire-compatible: field `Flow` added at the end"]
+    );
+    assert_eq!(
+        report(&new).compatibility(),
+        Some(Compatibility::Compatible)
+    );
+
+    let new = PACKAGE.replace(
+        r#"<Entry name="Mode" type="Mode"/>"#,
+        r#"<Entry name="Flow" type="Flow"/>"#,
+    );
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Status: wire-breaking: field `Mode` removed",
+            "Pump/Status: wire-breaking: field `Speed` moved from bit 16 to bit 24",
+            "Pump/Status: wire-breaking: field `Flow` inserted at bit 8",
+        ]
+    );
+
+    // renaming a field, or the type of a field, leaves the packets as they are
+    let new = PACKAGE
+        .replace(r#"name="Mode" type="Mode""#, r#"name="State" type="Mode""#)
+        .replace(
+            r#"name="Speed" type="Speed""#,
+            r#"name="Speed" type="Flow""#,
+        );
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Status: wire-compatible: field `Mode` renamed to `State`",
+            "Pump/Status: cosmetic: field `Speed` type changed from `Pump/Speed` to `Pump/Flow`, with the same encoding",
+        ]
+    );
+}
+
+#[test]
+fn test_enumerations() {
+    let new = PACKAGE
+        .replace(r#"label="IDLE" value="0""#, r#"label="OFF" value="0""#)
+        .replace(
+            r#"label="PUMPING" value="1""#,
+            r#"label="PUMPING" value="0x3""#,
+        )
+        .replace(
+            r#"<Enumeration label="FAULT" value="2"/>"#,
+            r#"<Enumeration label="FLUSHING" value="4"/>"#,
+        );
+    let report = report(&new);
+    let mode: Vec<_> = report
+        .changes
+        .iter()
+        .filter(|c| c.data_type == "Pump/Mode")
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(
+        mode,
+        [
+            "wire-compatible: label `IDLE` renamed to `OFF`",
+            "wire-breaking: value of label `PUMPING` changed from 1 to 3",
+            "wire-breaking: label `FAULT` removed",
+            "wire-compatible: label `FLUSHING` added",
+        ]
+    );
+
+    // values are compared as numbers
+    let new = PACKAGE.replace(r#"label="FAULT" value="2""#, r#"label="FAULT" value="0x2""#);
+    assert!(changes(&new).is_empty());
+}
+
+#[test]
+fn test_descriptions_and_order() {
+    let new = PACKAGE
+        .replace(r#""pump speed""#, r#""speed of the pump""#)
+        .replace(r#""current speed""#, r#""current  speed""#);
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Speed: wire-compatible: shortDescription changed",
+            "Pump/Status: cosmetic: field `Speed` shortDescription changed only in whitespace",
+        ]
+    );
+
+    let flow = r#"
+      <IntegerDataType name="Flow">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>"#;
+    let new = PACKAGE
+        .replace(flow, "")
+        .replace("<DataTypeSet>", &format!("<DataTypeSet>{}", flow));
+    assert_eq!(
+        changes(&new),
+        ["Pump: cosmetic: data types of the DataTypeSet reordered"]
+    );
+}
+
+#[test]
+fn test_data_types() {
+    let new = PACKAGE.replace(r#"name="Flow""#, r#"name="Pressure""#);
+    assert_eq!(
+        changes(&new),
+        [
+            "Pump/Flow: wire-breaking: removed",
+            "Pump/Pressure: wire-compatible: added",
+        ]
+    );
+}
+
+#[test]
+fn test_reports() {
+    let new = PACKAGE
+        .replace(r#"sizeInBits="16""#, r#"sizeInBits="32""#)
+        .replace(r#""pump speed""#, r#""speed of the pump""#);
+    let report = report(&new);
+    assert_eq!(
+        report.to_string(),
+        "Pump/Speed
+    wire-compatible: shortDescription changed
+    wire-breaking: resized from 16 bits to 32 bits
+Pump/Flow
+    wire-breaking: resized from 16 bits to 32 bits
+Pump/Status
+    wire-breaking: field `Speed` resized from 16 bits to 32 bits
+4 changes: 3 wire-breaking, 1 wire-compatible, 0 cosmetic
+"
+    );
+    assert_eq!(
+        report.to_json()["changes"][3],
+        serde_json::json!({
+            "compatibility": "wire-breaking",
+            "dataType": "Pump/Status",
+            "field": "Speed",
+            "message": "resized from 16 bits to 32 bits",
+        })
+    );
+    assert_eq!(report.to_json()["compatibility"], "wire-breaking");
+}
+
+#[test]
+fn test_unknown_layouts() {
+    // a container whose old layout cannot be computed is reported, not skipped
+    let old = PACKAGE.replace(r#"type="Speed" "#, r#"type="Pump/Velocity" "#);
+    let report = diff(&[&resolved(&old)], &[&resolved(PACKAGE)]);
+    let changes: Vec<_> = report
+        .changes
+        .iter()
+        .map(|c| format!("{}: {}", c.data_type, c))
+        .collect();
+    assert_eq!(
+        changes,
+        ["Pump/Status: wire-breaking: old layout unknown: unknown type `Pump/Velocity`"]
+    );
+}
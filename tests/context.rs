@@ -1,4 +1,4 @@
-use common::{get_mission_params, open_file};
+use common::{get_mission_params, open_file, parse};
 use seds_rs::codegen::context::{CodegenContext, Namespace};
 use seds_rs::eds::ast::PackageFile;
 use seds_rs::eds::raw;
@@ -12,15 +12,13 @@ mod common;
 /// test the namespace on multiple eds files
 #[test]
 fn test_cfe_namespace() {
-    let paths = vec![
+    let paths = [
         "eds/cFE/modules/core_api/eds/cfe_fs.xml",
         "eds/cFE/modules/core_api/eds/ccsds_spacepacket.xml",
         "eds/cFE/modules/core_api/eds/base_types.xml",
     ];
-    let rpackagefiles: Vec<raw::PackageFile> = paths
-        .iter()
-        .map(|fp| serde_xml_rs::from_str(&open_file(fp)).unwrap())
-        .collect();
+    let rpackagefiles: Vec<raw::PackageFile> =
+        paths.iter().map(|fp| parse(&open_file(fp))).collect();
 
     let ectx = get_mission_params();
     let packagefiles: Vec<PackageFile> = rpackagefiles
@@ -34,21 +32,14 @@ fn test_cfe_namespace() {
     let namespace = Namespace::try_from(pfs).unwrap();
     let locals = Namespace::try_from(&pkg).unwrap();
 
-    let ctx = CodegenContext {
-        name: None,
-        locals: &locals,
-        namespace: &namespace,
-    };
+    let ctx = CodegenContext::new(&locals, &namespace);
     //let code = rustfmt(pf.to_rust_mod(&ctx).unwrap()).unwrap();
 
     let mut spacepacket = pf.to_rust_mod(&ctx).unwrap();
 
     let npkg = packagefiles[0].package[0].clone();
-    let nctx = CodegenContext {
-        name: None,
-        locals: &Namespace::try_from(&npkg).unwrap(),
-        namespace: &namespace,
-    };
+    let nlocals = Namespace::try_from(&npkg).unwrap();
+    let nctx = CodegenContext::new(&nlocals, &namespace);
     spacepacket.extend(packagefiles[0].to_rust_mod(&nctx).unwrap());
     let code = rustfmt(spacepacket).unwrap();
 
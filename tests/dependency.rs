@@ -1,9 +1,10 @@
 //! manage and codegen depedencies during codegen
-use common::{get_mission_params, open_file};
+use common::{get_mission_params, open_file, parse, pump, resolved};
 use quote::format_ident;
 use seds_rs::{
     codegen::{
-        dependency::{AstNode, QualifiedNameIter},
+        codegen_packagefiles,
+        dependency::{AstNode, DependencyError, DependencyGraph, QualifiedNameIter},
         format::{format_pascal_case, format_snake_case},
     },
     eds::{ast::QualifiedName, raw, resolve::Resolve},
@@ -11,10 +12,62 @@ use seds_rs::{
 
 mod common;
 
+const BASE_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="BASE_TYPES">
+    <DataTypeSet>
+      <IntegerDataType name="uint16">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="65535" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+
+const PUMP: &str = pump!(
+    r#"
+      <ContainerDataType name="Status" baseType="CCSDS/TelemetryPacket">
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+          <Entry name="Spare" type="BASE_TYPES/uint16"/>
+        </EntryList>
+      </ContainerDataType>
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+"#
+);
+
+/// two containers that hold each other, and a third that holds itself
+const CYCLIC: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Loop">
+    <DataTypeSet>
+      <ContainerDataType name="Ping">
+        <EntryList>
+          <Entry name="Pong" type="Pong"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Pong">
+        <EntryList>
+          <Entry name="Ping" type="Ping"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Echo" baseType="Echo"/>
+      <ContainerDataType name="Quiet"/>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+
 #[test]
 fn test_spacepacket() {
     let contents = open_file("eds/cFE/modules/core_api/eds/ccsds_spacepacket.xml");
-    let rpf: raw::PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let rpf: raw::PackageFile = parse(&contents);
 
     let ectx = get_mission_params();
     let pf = rpf.resolve(&ectx).unwrap();
@@ -34,13 +87,181 @@ fn test_spacepacket() {
                 let snake_module = format_snake_case(&module_ident).unwrap();
                 let ident = format_ident!("{}", segments[1]);
                 let pascal_ident = format_pascal_case(&ident).unwrap();
-                println!(
-                    "use {}::{};",
-                    snake_module,
-                    pascal_ident
-                );
+                println!("use {}::{};", snake_module, pascal_ident);
             }
             _ => (),
         }
     }
 }
+
+/// a status whose trailer and constraints refer to other packages
+const CHECKED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Checked">
+    <DataTypeSet>
+      <ContainerDataType name="Status" baseType="CCSDS/TelemetryPacket">
+        <ConstraintSet>
+          <TypeConstraint entry="Sec" type="CCSDS/TlmSecHdr"/>
+        </ConstraintSet>
+        <EntryList>
+          <Entry name="Speed" type="Pump/Speed"/>
+        </EntryList>
+        <TrailerEntryList>
+          <ErrorControlEntry name="Crc" type="BASE_TYPES/uint16" errorControlType="CRC16_CCITT"/>
+        </TrailerEntryList>
+      </ContainerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+
+#[test]
+fn test_qualified_names() {
+    let package_file = resolved(CHECKED);
+    let names: Vec<_> = QualifiedNameIter::new(AstNode::PackageFile(&package_file))
+        .map(|name| name.0.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "CCSDS/TelemetryPacket",
+            "CCSDS/TlmSecHdr",
+            "Pump/Speed",
+            "BASE_TYPES/uint16"
+        ]
+    );
+}
+
+#[test]
+fn test_data_types() {
+    let package_files = [resolved(PUMP), resolved(BASE_TYPES)];
+    let pfs: Vec<_> = package_files.iter().collect();
+    let graph = DependencyGraph::data_types(&pfs);
+
+    // the base type of a package that is not loaded is a node of its own
+    assert_eq!(
+        graph.nodes().collect::<Vec<_>>(),
+        [
+            "Pump/Status",
+            "Pump/Speed",
+            "BASE_TYPES/uint16",
+            "CCSDS/TelemetryPacket"
+        ]
+    );
+    assert_eq!(
+        graph.dependencies("Pump/Status").collect::<Vec<_>>(),
+        ["Pump/Speed", "BASE_TYPES/uint16", "CCSDS/TelemetryPacket"]
+    );
+    assert_eq!(
+        graph.dependents("Pump/Speed").collect::<Vec<_>>(),
+        ["Pump/Status"]
+    );
+    assert!(graph.cycles().is_empty());
+    assert_eq!(
+        graph.topological_order().unwrap(),
+        [
+            "Pump/Speed",
+            "BASE_TYPES/uint16",
+            "CCSDS/TelemetryPacket",
+            "Pump/Status"
+        ]
+    );
+}
+
+#[test]
+fn test_packages() {
+    let package_files = [
+        resolved(PUMP),
+        resolved(BASE_TYPES),
+        resolved(&open_file("eds/test/simplified_spacepacket.xml")),
+    ];
+    let pfs: Vec<_> = package_files.iter().collect();
+    let graph = DependencyGraph::packages(&pfs);
+    assert_eq!(
+        graph.nodes().collect::<Vec<_>>(),
+        ["Pump", "BASE_TYPES", "CCSDS"]
+    );
+    assert_eq!(
+        graph.dependencies("Pump").collect::<Vec<_>>(),
+        ["BASE_TYPES", "CCSDS"]
+    );
+    assert_eq!(
+        graph.topological_order().unwrap(),
+        ["BASE_TYPES", "CCSDS", "Pump"]
+    );
+}
+
+#[test]
+fn test_codegen_order() {
+    let package_files = [
+        resolved(PUMP),
+        resolved(BASE_TYPES),
+        resolved(&open_file("eds/test/simplified_spacepacket.xml")),
+    ];
+    let pfs: Vec<_> = package_files.iter().collect();
+    let code = codegen_packagefiles(&pfs).unwrap().to_string();
+    let position = |module: &str| code.find(&format!("pub mod {} ", module)).unwrap();
+    assert!(position("base_types") < position("ccsds"));
+    assert!(position("ccsds") < position("pump"));
+}
+
+#[test]
+fn test_cycles() {
+    let package_file = resolved(CYCLIC);
+    let graph = DependencyGraph::data_types(&[&package_file]);
+    assert_eq!(
+        graph.cycles(),
+        [vec!["Loop/Ping", "Loop/Pong"], vec!["Loop/Echo"]]
+    );
+    let error = graph.topological_order().unwrap_err();
+    assert_eq!(
+        error,
+        DependencyError::Cycle(vec!["Loop/Ping".to_string(), "Loop/Pong".to_string()])
+    );
+    assert_eq!(
+        error.to_string(),
+        "cyclic dependencies between `Loop/Ping`, `Loop/Pong`"
+    );
+}
+
+#[test]
+fn test_exports() {
+    let package_files = [resolved(PUMP), resolved(BASE_TYPES)];
+    let pfs: Vec<_> = package_files.iter().collect();
+
+    let graph = DependencyGraph::data_types(&pfs);
+    assert_eq!(
+        graph.to_dot(),
+        r#"digraph dependencies {
+    rankdir=LR;
+    subgraph "cluster_Pump" {
+        label="Pump";
+        "Pump/Status" [label="Status"];
+        "Pump/Speed" [label="Speed"];
+    }
+    subgraph "cluster_BASE_TYPES" {
+        label="BASE_TYPES";
+        "BASE_TYPES/uint16" [label="uint16"];
+    }
+    subgraph "cluster_CCSDS" {
+        label="CCSDS";
+        "CCSDS/TelemetryPacket" [label="TelemetryPacket"];
+    }
+    "Pump/Status" -> "Pump/Speed";
+    "Pump/Status" -> "BASE_TYPES/uint16";
+    "Pump/Status" -> "CCSDS/TelemetryPacket";
+}
+"#
+    );
+
+    let graph = DependencyGraph::packages(&pfs);
+    assert_eq!(
+        graph.to_mermaid(),
+        r#"graph LR
+    n0["Pump"]
+    n1["BASE_TYPES"]
+    n2["CCSDS"]
+    n0 --> n1
+    n0 --> n2
+"#
+    );
+}
@@ -0,0 +1,226 @@
+//! Source locations and diagnostics
+use seds_rs::codegen::codegen_packagefiles;
+use seds_rs::diagnostic::{Diagnostic, SourceMap};
+use seds_rs::eds::raw;
+use seds_rs::eds::resolve::{resolve_all, Resolve, ResolveError};
+use seds_rs::parse::Parser;
+
+mod common;
+
+use common::{get_mission_params, open_file};
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- <Package name="Commented"> -->
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds" xmlns:xi="http://www.w3.org/2001/XInclude">
+  <Package name="Heater">
+    <DataTypeSet>
+      <IntegerDataType name="Setpoint">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="middleEndian" />
+        <Range>
+          <MinMaxRange min="0" max="100" rangeType="inclusiveMinInclusiveMax" />
+        </Range>
+      </IntegerDataType>
+      <ArrayDataType name="Table" dataTypeRef="Setpoint">
+        <DimensionList>
+          <Dimension size="4" />
+          <Dimension size='${MISSING}' />
+        </DimensionList>
+      </ArrayDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>
+"#;
+
+#[test]
+fn test_source_map() {
+    let source = SourceMap::new("heater.xml", PACKAGE);
+
+    let span = source.span("Package[Heater]").unwrap();
+    assert_eq!((span.line, span.column, span.length), (4, 4, 7));
+    assert_eq!(span.text, r#"  <Package name="Heater">"#);
+    assert!(!source.contains("Package[Commented]"));
+
+    // attributes
+    let span = source
+        .span("Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@byteOrder")
+        .unwrap();
+    assert_eq!((span.line, span.column, span.length), (7, 66, 24));
+
+    // unnamed elements by position, with either quote
+    let span = source
+        .span("Package[Heater]/DataTypeSet/ArrayDataType[Table]/DimensionList/Dimension[1]@size")
+        .unwrap();
+    assert_eq!((span.line, span.column, span.length), (15, 22, 17));
+    let span = source
+        .span("Package[Heater]/DataTypeSet/ArrayDataType[Table]/DimensionList/Dimension")
+        .unwrap();
+    assert_eq!(span.line, 14);
+
+    // unknown paths fall back to the closest element
+    let span = source
+        .span("Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/Range/MinMaxRange@unknown")
+        .unwrap();
+    assert_eq!((span.line, span.column), (9, 12));
+    assert!(source.span("Package[Other]").is_none());
+}
+
+#[test]
+fn test_resolve_diagnostic() {
+    let rpf: raw::PackageFile = Parser::new("heater.xml").parse(PACKAGE).unwrap().value;
+    let err = rpf.resolve(&get_mission_params()).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@byteOrder"
+    );
+    assert!(matches!(err.cause(), ResolveError::InvalidByteOrder(bo) if bo == "middleEndian"));
+
+    let diagnostic = SourceMap::new("heater.xml", PACKAGE).locate(Diagnostic::from(&err));
+    assert_eq!(
+        diagnostic.to_string(),
+        r#"error: invalid byte order `middleEndian`, expected littleEndian or bigEndian
+ --> heater.xml:7:66
+  |
+7 |         <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="middleEndian" />
+  |                                                                  ^^^^^^^^^^^^^^^^^^^^^^^^
+  = in Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@byteOrder
+"#
+    );
+
+    // the next error is in an unnamed element
+    let fixed = PACKAGE.replace("middleEndian", "bigEndian");
+    let rpf: raw::PackageFile = Parser::new("heater.xml").parse(&fixed).unwrap().value;
+    let err = rpf.resolve(&get_mission_params()).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Heater]/DataTypeSet/ArrayDataType[Table]/DimensionList/Dimension[1]@size"
+    );
+    let diagnostic = SourceMap::new("heater.xml", &fixed).locate(Diagnostic::from(&err));
+    assert_eq!(
+        diagnostic.message,
+        "unknown parameter `MISSING` at offset 0 in `${MISSING}`"
+    );
+    assert_eq!(diagnostic.span.unwrap().line, 15);
+}
+
+#[test]
+fn test_resolve_all() {
+    let contents = PACKAGE
+        .replace(r#"encoding="unsigned""#, r#"encoding="signed""#)
+        .replace(
+            r#"<IntegerDataType name="Setpoint">"#,
+            r#"<IntegerDataType name="Setpoint" unit="furlong">"#,
+        )
+        .replace(
+            r#"rangeType="inclusiveMinInclusiveMax""#,
+            r#"rangeType="closed""#,
+        );
+    let rpf: raw::PackageFile = Parser::new("heater.xml").parse(&contents).unwrap().value;
+    let errors = resolve_all(&rpf, &get_mission_params()).unwrap_err();
+    let paths: Vec<_> = errors.iter().map(ResolveError::path).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]@unit",
+            "Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@encoding",
+            "Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/IntegerDataEncoding@byteOrder",
+            "Package[Heater]/DataTypeSet/IntegerDataType[Setpoint]/Range/MinMaxRange@rangeType",
+            "Package[Heater]/DataTypeSet/ArrayDataType[Table]/DimensionList/Dimension[1]@size",
+        ]
+    );
+    assert!(matches!(errors[1].cause(), ResolveError::InvalidEncoding(e) if e == "signed"));
+
+    // resolve returns them as one error, located at the first
+    let err = rpf.resolve(&get_mission_params()).unwrap_err();
+    assert_eq!(err.path(), paths[0]);
+    assert!(matches!(err, ResolveError::InElement { .. }));
+    assert_eq!(err.into_errors().len(), 5);
+
+    // without mistakes, both resolve the same
+    let rpf: raw::PackageFile = Parser::new("test_container.xml")
+        .parse(&open_file("eds/test/test_container.xml"))
+        .unwrap()
+        .value;
+    assert_eq!(
+        resolve_all(&rpf, &get_mission_params()).unwrap(),
+        rpf.resolve(&get_mission_params()).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_diagnostic() {
+    let broken = PACKAGE.replace("</DimensionList>", "</Dimensions>");
+    let diagnostic = Parser::new("heater.xml")
+        .parse::<raw::PackageFile>(&broken)
+        .unwrap_err();
+    assert_eq!(diagnostic.file.as_deref(), Some("heater.xml"));
+    assert_eq!(diagnostic.span.as_ref().unwrap().line, 16);
+    assert!(diagnostic.to_string().starts_with("error: "));
+}
+
+#[test]
+fn test_codegen_diagnostic() {
+    let contents = open_file("eds/test/test_container.xml");
+    let rpf: raw::PackageFile = Parser::new("test_container.xml")
+        .parse(&contents)
+        .unwrap()
+        .value;
+    let pf = rpf.resolve(&get_mission_params()).unwrap();
+    // the base type is in a package of the cFE, so the layout of the command is unknown
+    let err = codegen_packagefiles(&[&pf]).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[SimpleDemo]/DataTypeSet/ContainerDataType[WritePipeInfoCmd]"
+    );
+
+    let diagnostic = SourceMap::new("test_container.xml", &contents).locate(Diagnostic::from(&err));
+    assert_eq!(
+        diagnostic.message,
+        "layout unknown: unknown type `CommandBase`"
+    );
+    let span = diagnostic.span.unwrap();
+    assert_eq!(span.line, 14);
+    assert!(span
+        .text
+        .contains(r#"<ContainerDataType name="WritePipeInfoCmd""#));
+}
+
+#[test]
+fn test_diagnostic_notes() {
+    let diagnostic = Diagnostic::warning("unused parameter `A/B`")
+        .in_file("mission.toml")
+        .with_note("defined by the mission layer");
+    assert_eq!(
+        diagnostic.to_string(),
+        "warning: unused parameter `A/B`\n --> mission.toml\n  = note: defined by the mission layer\n"
+    );
+}
+
+#[test]
+fn test_unnamed_siblings() {
+    let contents = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Heater">
+    <DataTypeSet>
+      <ContainerDataType name="Status">
+        <EntryList>
+          <PaddingEntry sizeInBits="4" />
+          <PaddingEntry sizeInBits="${MISSING}" />
+        </EntryList>
+      </ContainerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>
+"#;
+    let rpf: raw::PackageFile = Parser::new("heater.xml").parse(contents).unwrap().value;
+    let errors = resolve_all(&rpf, &get_mission_params()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].path(),
+        "Package[Heater]/DataTypeSet/ContainerDataType[Status]/EntryList/PaddingEntry[1]@sizeInBits"
+    );
+
+    // the error points at the second padding entry, not the first
+    let diagnostic = SourceMap::new("heater.xml", contents).locate(Diagnostic::from(&errors[0]));
+    let span = diagnostic.span.unwrap();
+    assert_eq!((span.line, span.column), (8, 25));
+}
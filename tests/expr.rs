@@ -1,4 +1,6 @@
-use seds_rs::expr::ExpressionContext;
+use seds_rs::eds::value::Value;
+use seds_rs::expr::parse::parse;
+use seds_rs::expr::{ExpressionContext, ExpressionErrorKind, NamespaceError};
 
 #[test]
 fn test_namespace_get() {
@@ -25,19 +27,19 @@ fn test_namespace_eval_expression() {
 
     assert_eq!(
         namespace.eval_expression("${a} * ${b}").unwrap(),
-        evalexpr::Value::from(2)
+        Value::Integer(2)
     );
     assert_eq!(
         namespace.eval_expression("no_vars").unwrap(),
-        evalexpr::Value::from("no_vars")
+        Value::from("no_vars")
     );
     assert_eq!(
         namespace.eval_expression("${a}").unwrap(),
-        evalexpr::Value::from(1)
+        Value::Integer(1)
     );
     assert_eq!(
         namespace.eval_expression("${c}").unwrap(),
-        evalexpr::Value::from("test")
+        Value::from("test")
     );
 }
 
@@ -64,13 +66,13 @@ fn test_namespace_eval_nested_expression() {
 
     assert_eq!(
         namespace.eval_expression("${CFE_MISSION/V_1}").unwrap(),
-        evalexpr::Value::from(2.0)
+        Value::Float(2.0)
     );
     assert_eq!(
         namespace
             .eval_expression("(${TEST_MISSION} / ${CFE_MISSION/V_1}) ^ ${CFE_MISSION/SUBSYS/c}")
             .unwrap(),
-        evalexpr::Value::from(1.0 / 8.0)
+        Value::Float(1.0 / 8.0)
     );
 }
 
@@ -80,3 +82,280 @@ fn test_empty_namespace() {
     let namespace = ExpressionContext::from_json(&json).unwrap();
     assert!(namespace.eval_expression("${a} * ${b}").is_err());
 }
+
+/// the offset and kind of the error evaluating an expression
+fn eval_error(expression: &str) -> (usize, ExpressionErrorKind) {
+    let json = serde_json::json!({"a": "1", "name": "heater", "big": "0x7fffffffffffffff"});
+    let namespace = ExpressionContext::from_json(&json).unwrap();
+    match namespace.eval_expression(expression) {
+        Err(NamespaceError::ExpressionEvaluationError(err)) => (err.offset, err.kind),
+        res => panic!("{} evaluated to {:?}", expression, res),
+    }
+}
+
+#[test]
+fn test_eval_typed_values() {
+    let json = serde_json::json!({"a": "3", "b": "0.5", "t": "true"});
+    let namespace = ExpressionContext::from_json(&json).unwrap();
+
+    let cases = [
+        ("0x10 + 0b11 - 0o7", Value::Integer(12)),
+        ("-2 ^ 2", Value::Integer(-4)),
+        ("2 ^ 3 ^ 2", Value::Integer(512)),
+        ("(1 + 2) * 3 % 4", Value::Integer(1)),
+        ("${a} / 3", Value::Integer(1)),
+        ("${a} / 2", Value::Float(1.5)),
+        ("2 ^ -1", Value::Float(0.5)),
+        ("1e3 * ${b}", Value::Float(500.0)),
+        ("${a} >= 3 && !(${b} > 1)", Value::Boolean(true)),
+        ("${t} || false", Value::Boolean(true)),
+        ("\"label\" == \"label\"", Value::Boolean(true)),
+        ("true", Value::Boolean(true)),
+    ];
+    for (expression, expected) in cases {
+        assert_eq!(
+            namespace.eval_expression(expression).unwrap(),
+            expected,
+            "{}",
+            expression
+        );
+    }
+
+    // names, keywords and type references are strings, with parameters substituted
+    assert_eq!(
+        namespace.eval_expression("BASE_TYPES/uint${a}2").unwrap(),
+        Value::from("BASE_TYPES/uint32")
+    );
+    assert_eq!(
+        namespace.eval_expression("up to ${a} entries").unwrap(),
+        Value::from("up to 3 entries")
+    );
+    assert_eq!(namespace.eval_expression("").unwrap(), Value::from(""));
+}
+
+#[test]
+fn test_eval_errors() {
+    use ExpressionErrorKind::*;
+
+    // typos are errors, not strings
+    assert_eq!(eval_error("16O"), (0, InvalidNumber("16O".to_string())));
+    assert_eq!(eval_error("1 6"), (2, UnexpectedToken("6".to_string())));
+    assert_eq!(eval_error("${a} *"), (6, UnexpectedEnd));
+    assert_eq!(eval_error("(${a} + 1"), (9, UnexpectedEnd));
+    assert_eq!(
+        eval_error("${a} + 1)"),
+        (8, UnexpectedToken(")".to_string()))
+    );
+    assert_eq!(
+        eval_error("${a} = 1"),
+        (5, UnexpectedToken("=".to_string()))
+    );
+    assert_eq!(
+        eval_error("2 * size"),
+        (4, UnexpectedToken("size".to_string()))
+    );
+    assert_eq!(
+        eval_error("1 < 2 < 3"),
+        (6, UnexpectedToken("<".to_string()))
+    );
+    assert_eq!(eval_error("${a"), (0, UnterminatedParameter));
+    assert_eq!(
+        eval_error("${a b}"),
+        (0, InvalidParameter("a b".to_string()))
+    );
+    assert_eq!(eval_error("\"open"), (0, UnterminatedString));
+    assert_eq!(eval_error("1 # 2"), (2, UnexpectedCharacter('#')));
+
+    // evaluation errors point at the operator or parameter
+    assert_eq!(
+        eval_error("1 + ${c}"),
+        (4, UnknownParameter("c".to_string()))
+    );
+    assert_eq!(eval_error("${a} / (1 - 1)"), (5, DivisionByZero));
+    assert_eq!(eval_error("${big} + 1"), (7, Overflow));
+    assert_eq!(eval_error("2 ^ 8192"), (2, Overflow));
+    assert_eq!(eval_error("2.0 ^ 8192"), (4, NonFiniteResult));
+    assert!(matches!(eval_error("${name} * 2"), (8, TypeMismatch(_))));
+    assert!(matches!(eval_error("1 && true"), (2, TypeMismatch(_))));
+
+    // names must still be names once their parameters are substituted
+    assert_eq!(
+        eval_error("BASE_TYPES/${a}"),
+        (11, InvalidName("BASE_TYPES/1".to_string()))
+    );
+    assert_eq!(
+        eval_error("Pump/${name}/${a}"),
+        (5, InvalidName("Pump/heater/1".to_string()))
+    );
+
+    let json = serde_json::json!({});
+    let err = ExpressionContext::from_json(&json)
+        .unwrap()
+        .eval_expression("8 * ${CFE_MISSION/MAX_SIZE}")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown parameter `CFE_MISSION/MAX_SIZE` at offset 4 in `8 * ${CFE_MISSION/MAX_SIZE}`"
+    );
+}
+
+#[test]
+fn test_eval_integer() {
+    let json = serde_json::json!({"a": "3"});
+    let namespace = ExpressionContext::from_json(&json).unwrap();
+    assert_eq!(namespace.eval_integer("${a} * 2").unwrap(), 6);
+
+    // values of other types are located at the operator, literal or string they come from
+    let error = |expression: &str| match namespace.eval_integer(expression) {
+        Err(NamespaceError::ExpressionEvaluationError(err)) => (err.offset, err.kind),
+        res => panic!("{} evaluated to {:?}", expression, res),
+    };
+    for (expression, offset) in [("${a} / 2", 5), (" 1.5", 1), ("  Pump${a}", 2)] {
+        assert!(
+            matches!(error(expression), (o, ExpressionErrorKind::TypeMismatch(_)) if o == offset),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_parse_parameters() {
+    let expr = parse("2 ^ (8 * ${CFE_MISSION/MAX_CPU_ADDRESS_SIZE}) - ${A}").unwrap();
+    assert_eq!(
+        expr.parameters(),
+        vec!["CFE_MISSION/MAX_CPU_ADDRESS_SIZE", "A"]
+    );
+}
+
+#[test]
+fn test_typed_parameters() {
+    let json = serde_json::json!({
+        "CFE_MISSION": {
+            "MAX_PATH_LEN": 64,
+            "SCALE": 0.5,
+            "ENABLED": true,
+            "QUOTED": "16",
+            "PIPE_DEPTHS": [4, 8, "0x10"],
+            "MATRIX": [[1, 2], [3, 4]],
+            "PIPES": [{"DEPTH": 4}, {"DEPTH": "0x10", "NAME": "evs"}],
+        },
+    });
+    let namespace = ExpressionContext::from_json(&json).unwrap();
+
+    assert_eq!(
+        namespace.value(&["CFE_MISSION", "MAX_PATH_LEN"]).unwrap(),
+        Value::Integer(64)
+    );
+    assert_eq!(
+        namespace.value(&["CFE_MISSION", "QUOTED"]).unwrap(),
+        Value::Integer(16)
+    );
+    assert_eq!(
+        namespace.get(&["CFE_MISSION", "PIPE_DEPTHS"]).unwrap(),
+        "[4, 8, 0x10]"
+    );
+
+    let cases = [
+        (
+            "${CFE_MISSION/MAX_PATH_LEN} * ${CFE_MISSION/SCALE}",
+            Value::Float(32.0),
+        ),
+        ("${CFE_MISSION/ENABLED} && true", Value::Boolean(true)),
+        ("${CFE_MISSION/QUOTED} + 1", Value::Integer(17)),
+        ("${CFE_MISSION/PIPE_DEPTHS}[2] * 2", Value::Integer(32)),
+        (
+            "${CFE_MISSION/PIPE_DEPTHS}[${CFE_MISSION/PIPE_DEPTHS}[0] - 3]",
+            Value::Integer(8),
+        ),
+        ("${CFE_MISSION/MATRIX}[1][0]", Value::Integer(3)),
+        ("${CFE_MISSION/PIPES/1/DEPTH} / 2", Value::Integer(8)),
+        (
+            "${CFE_MISSION/PIPE_DEPTHS}",
+            Value::Array(vec![
+                Value::Integer(4),
+                Value::Integer(8),
+                Value::Integer(16),
+            ]),
+        ),
+    ];
+    for (expression, expected) in cases {
+        assert_eq!(
+            namespace.eval_expression(expression).unwrap(),
+            expected,
+            "{}",
+            expression
+        );
+    }
+
+    // the objects of an array are namespaces named by their index
+    let pipes: Vec<_> = namespace
+        .parameters()
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| path.starts_with("CFE_MISSION/PIPES/"))
+        .collect();
+    assert_eq!(
+        pipes,
+        [
+            "CFE_MISSION/PIPES/0/DEPTH",
+            "CFE_MISSION/PIPES/1/DEPTH",
+            "CFE_MISSION/PIPES/1/NAME",
+        ]
+    );
+
+    let error = |expression: &str| match namespace.eval_expression(expression) {
+        Err(NamespaceError::ExpressionEvaluationError(err)) => (err.offset, err.kind),
+        res => panic!("{} evaluated to {:?}", expression, res),
+    };
+    assert_eq!(
+        error("${CFE_MISSION/PIPES}[0]"),
+        (
+            0,
+            ExpressionErrorKind::UnknownParameter("CFE_MISSION/PIPES".to_string())
+        )
+    );
+    assert_eq!(
+        error("${CFE_MISSION/PIPE_DEPTHS}[3]"),
+        (26, ExpressionErrorKind::IndexOutOfBounds(3, 3))
+    );
+    assert!(matches!(
+        error("${CFE_MISSION/PIPE_DEPTHS}[0.5]"),
+        (26, ExpressionErrorKind::TypeMismatch(_))
+    ));
+    assert!(matches!(
+        error("${CFE_MISSION/MAX_PATH_LEN}[0]"),
+        (27, ExpressionErrorKind::TypeMismatch(_))
+    ));
+    assert!(matches!(
+        error("${CFE_MISSION/PIPE_DEPTHS}[0"),
+        (28, ExpressionErrorKind::UnexpectedEnd)
+    ));
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let invalid_key = |json: serde_json::Value| match ExpressionContext::from_json(&json) {
+        Err(NamespaceError::InvalidParameterValue { key, .. }) => key,
+        res => panic!("{:?} loaded as {:?}", json, res),
+    };
+    assert_eq!(
+        invalid_key(serde_json::json!({"CFE_MISSION": {"MAX_PATH_LEN": null}})),
+        "CFE_MISSION/MAX_PATH_LEN"
+    );
+    assert_eq!(
+        invalid_key(serde_json::json!({"A": {"B": [[1, {"C": 2}]]}})),
+        "A/B[0][1]"
+    );
+    assert_eq!(
+        invalid_key(serde_json::json!({"BIG": 18446744073709551615u64})),
+        "BIG"
+    );
+    assert_eq!(invalid_key(serde_json::json!("not an object")), "");
+
+    let err = ExpressionContext::from_json(&serde_json::json!({"A": [null]})).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value for parameter `A[0]`: null is not a value"
+    );
+}
@@ -0,0 +1,224 @@
+//! Extensions of user-defined DoTs
+mod common;
+
+use common::resolved_with;
+use proc_macro2::TokenStream;
+use quote::quote;
+use seds_rs::codegen::{codegen_packagefiles, codegen_packagefiles_with_extensions, rustfmt};
+use seds_rs::eds::ast::{self, DataType};
+use seds_rs::eds::extension::{ExtensionElement, ExtensionRegistry, ExtensionValue};
+use seds_rs::eds::raw::PackageFile;
+use seds_rs::eds::resolve::Resolve;
+use seds_rs::expr::ExpressionContext;
+use seds_rs::parse::Parser;
+use seds_rs::write::to_xml;
+
+const TLM: &str = "urn:example:tlm";
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds" xmlns:tlm="urn:example:tlm">
+  <Package name="Thermal">
+    <DataTypeSet>
+      <IntegerDataType name="Temperature" tlm:priority="2" tlm:downlinkRate="${THERMAL/RATE}">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian" />
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax" />
+        </Range>
+        <tlm:Downlink channel="housekeeping">
+          <tlm:Window start="10" end="20" />
+        </tlm:Downlink>
+      </IntegerDataType>
+      <EnumeratedDataType name="Heater">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian" />
+        <EnumerationList>
+          <Enumeration label="OFF" value="0" />
+          <Enumeration label="ON" value="1" unit="watt" tlm:priority="1" />
+        </EnumerationList>
+      </EnumeratedDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+
+#[derive(Debug, PartialEq)]
+struct Priority(u8);
+
+impl ExtensionValue for Priority {
+    fn to_docstring(&self) -> String {
+        format!("- Telemetry Priority: {}", self.0)
+    }
+
+    fn to_rust_const(&self) -> Option<(TokenStream, TokenStream)> {
+        let value = self.0;
+        Some((quote!(u8), quote!(#value)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct DownlinkRate(f64);
+
+impl ExtensionValue for DownlinkRate {
+    fn to_docstring(&self) -> String {
+        format!("- Downlink Rate: {} Hz", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Downlink {
+    channel: String,
+}
+
+impl ExtensionValue for Downlink {
+    fn to_docstring(&self) -> String {
+        format!("- Downlink Channel: `{}`", self.channel)
+    }
+}
+
+fn registry() -> ExtensionRegistry {
+    ExtensionRegistry::new()
+        .attribute(TLM, "priority", |value| {
+            value.parse().map(Priority).map_err(|e| e.to_string())
+        })
+        .attribute(TLM, "downlinkRate", |value| {
+            value.parse().map(DownlinkRate).map_err(|e| e.to_string())
+        })
+        .element(TLM, "Downlink", |element: &ExtensionElement| {
+            let channel = element.attribute("channel").ok_or("missing channel")?;
+            Ok(Downlink {
+                channel: channel.to_string(),
+            })
+        })
+}
+
+fn parameters() -> ExpressionContext {
+    ExpressionContext::from_json(&serde_json::json!({ "THERMAL": { "RATE": "0.5" } })).unwrap()
+}
+
+fn resolved(contents: &str) -> ast::PackageFile {
+    resolved_with(contents, &parameters())
+}
+
+fn temperature(package_file: &ast::PackageFile) -> &ast::IntegerDataType {
+    match &package_file.package[0].data_type_set.data_types[0] {
+        DataType::IntegerDataType(t) => t,
+        _ => unreachable!(),
+    }
+}
+
+fn heater(package_file: &ast::PackageFile) -> &ast::EnumeratedDataType {
+    match &package_file.package[0].data_type_set.data_types[1] {
+        DataType::EnumeratedDataType(t) => t,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_parse_extensions() {
+    let parsed = Parser::new("thermal.xml")
+        .parse::<PackageFile>(PACKAGE)
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+    let package_file = parsed.value.resolve(&parameters()).unwrap();
+
+    let extensions = &temperature(&package_file).name_entity_type.extensions;
+    assert_eq!(extensions.attribute(TLM, "priority"), Some("2"));
+    // the parameters of extensions are substituted
+    assert_eq!(extensions.attribute(TLM, "downlinkRate"), Some("0.5"));
+    let downlink = extensions.element(TLM, "Downlink").unwrap();
+    assert_eq!(downlink.attribute("channel"), Some("housekeeping"));
+    assert_eq!(downlink.children[0].name, "Window");
+    assert_eq!(downlink.children[0].attribute("end"), Some("20"));
+
+    // the labels of an enumeration have semantics and extensions (Req 3.7.16)
+    let on = &heater(&package_file).enumeration_list.enumeration[1];
+    assert_eq!(on.semantics.unit, Some(ast::Unit::Watt));
+    assert_eq!(on.extensions.attribute(TLM, "priority"), Some("1"));
+}
+
+#[test]
+fn test_typed_values() {
+    let package_file = resolved(PACKAGE);
+    let extensions = &temperature(&package_file).name_entity_type.extensions;
+    let registry = registry();
+    assert_eq!(
+        registry.attribute_value::<Priority>(extensions, TLM, "priority"),
+        Some(Ok(Priority(2)))
+    );
+    assert_eq!(
+        registry.attribute_value::<DownlinkRate>(extensions, TLM, "downlinkRate"),
+        Some(Ok(DownlinkRate(0.5)))
+    );
+    // the handler reads another type
+    assert_eq!(
+        registry.attribute_value::<DownlinkRate>(extensions, TLM, "priority"),
+        None
+    );
+    assert_eq!(
+        registry.element_value::<Downlink>(extensions, TLM, "Downlink"),
+        Some(Ok(Downlink {
+            channel: "housekeeping".to_string()
+        }))
+    );
+
+    let names: Vec<_> = registry
+        .values(extensions)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.path)
+        .collect();
+    assert_eq!(names, ["@priority", "@downlinkRate", "Downlink"]);
+}
+
+#[test]
+fn test_codegen_extensions() {
+    let package_file = resolved(PACKAGE);
+    let code =
+        rustfmt(codegen_packagefiles_with_extensions(&[&package_file], &registry()).unwrap())
+            .unwrap();
+    assert!(code.contains("pub const PRIORITY: u8 = 2"), "{}", code);
+    assert!(code.contains("pub const ON_PRIORITY: u8 = 1"), "{}", code);
+    assert!(
+        code.contains("pub const ON_UNIT: &'static str = \"watt\""),
+        "{}",
+        code
+    );
+    assert!(code.contains("- Telemetry Priority: 2"));
+    assert!(code.contains("- Downlink Rate: 0.5 Hz"));
+    assert!(code.contains("- Downlink Channel: `housekeeping`"));
+
+    // extensions without a handler are not part of the generated code
+    let code = rustfmt(codegen_packagefiles(&[&package_file]).unwrap()).unwrap();
+    assert!(!code.contains("PRIORITY"));
+    assert!(!code.contains("Telemetry"));
+}
+
+#[test]
+fn test_invalid_extension() {
+    let package_file = resolved(&PACKAGE.replace(r#"tlm:priority="2""#, r#"tlm:priority="high""#));
+    let error = codegen_packagefiles_with_extensions(&[&package_file], &registry()).unwrap_err();
+    assert_eq!(
+        error.path(),
+        "Package[Thermal]/DataTypeSet/IntegerDataType[Temperature]@priority"
+    );
+    assert_eq!(
+        error.cause().to_string(),
+        "invalid extension: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_write_extensions() {
+    let parsed = Parser::new("thermal.xml")
+        .parse::<PackageFile>(PACKAGE)
+        .unwrap();
+    let written = to_xml(&parsed.value).unwrap();
+    assert!(
+        written.contains(r#"xmlns:ext1="urn:example:tlm""#),
+        "{}",
+        written
+    );
+    assert!(written.contains(r#"ext1:priority="2""#), "{}", written);
+    let reparsed = Parser::new("thermal.xml")
+        .parse::<PackageFile>(&written)
+        .unwrap();
+    assert_eq!(reparsed.value, parsed.value);
+}
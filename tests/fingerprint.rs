@@ -0,0 +1,150 @@
+//! Structural fingerprints of data types, in the library and in generated code
+// the deku derives in the generated code trip this lint
+#![allow(clippy::manual_div_ceil)]
+mod common;
+
+use common::{open_file, pump, resolved};
+use seds_macro::seds;
+use seds_rs::codegen::codegen_packagefiles;
+use seds_rs::eds::fingerprint::Fingerprints;
+use seds_rs::eds::layout::LayoutError;
+
+#[seds("eds/test/test_semantics.xml")]
+struct Dummy; // replaced by the generated module `thermal`
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed" shortDescription="pump speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <EnumeratedDataType name="Mode">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <ContainerDataType name="Header">
+        <EntryList>
+          <Entry name="Mode" type="Mode"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Status" shortDescription="pump status">
+        <EntryList>
+          <Entry name="Hdr" type="Header"/>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+fn fingerprints(contents: &str) -> Fingerprints {
+    Fingerprints::new(&[&resolved(contents)])
+}
+
+#[test]
+fn test_stable_fingerprints() {
+    let fingerprints = fingerprints(PACKAGE);
+    let names: Vec<_> = fingerprints.iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        names,
+        ["Pump/Header", "Pump/Mode", "Pump/Speed", "Pump/Status"]
+    );
+    // the hash of the encoding is part of the interface of archives
+    assert_eq!(fingerprints.get("Pump/Speed"), Some(0xd60e1b609a27eb90));
+    assert_eq!(fingerprints.get("Pump/Status"), Some(0xda3658d6b334d253));
+    assert_eq!(fingerprints.get("Pump/Flow"), None);
+}
+
+#[test]
+fn test_ignored_changes() {
+    let expected = fingerprints(PACKAGE);
+    let contents = PACKAGE
+        .replace(r#" shortDescription="pump status""#, "")
+        .replace(r#"max="1000""#, r#"max="2000""#)
+        .replace(r#"name="Header""#, r#"name="PumpHeader""#)
+        .replace(r#"type="Header""#, r#"type="PumpHeader""#);
+    let fingerprints = fingerprints(&contents);
+    for name in ["Pump/Mode", "Pump/Speed", "Pump/Status"] {
+        assert_eq!(fingerprints.get(name), expected.get(name), "{}", name);
+    }
+    assert_eq!(
+        fingerprints.get("Pump/PumpHeader"),
+        expected.get("Pump/Header")
+    );
+}
+
+#[test]
+fn test_transitive_changes() {
+    let expected = fingerprints(PACKAGE);
+
+    // a label of the type of an entry of an entry
+    let fingerprints = fingerprints(&PACKAGE.replace(r#"label="PUMPING""#, r#"label="RUNNING""#));
+    for name in ["Pump/Mode", "Pump/Header", "Pump/Status"] {
+        assert_ne!(fingerprints.get(name), expected.get(name), "{}", name);
+    }
+    assert_eq!(fingerprints.get("Pump/Speed"), expected.get("Pump/Speed"));
+
+    // the byte order and the name of an entry
+    let contents = PACKAGE.replace(
+        r#"byteOrder="bigEndian"/>
+        <Range>"#,
+        r#"byteOrder="littleEndian"/>
+        <Range>"#,
+    );
+    assert_ne!(
+        Fingerprints::new(&[&resolved(&contents)]).get("Pump/Status"),
+        expected.get("Pump/Status")
+    );
+    let contents = PACKAGE.replace(
+        r#"name="Speed" type="Speed""#,
+        r#"name="Velocity" type="Speed""#,
+    );
+    assert_ne!(
+        Fingerprints::new(&[&resolved(&contents)]).get("Pump/Status"),
+        expected.get("Pump/Status")
+    );
+}
+
+#[test]
+fn test_generated_fingerprints() {
+    let package_file = resolved(&open_file("eds/test/test_semantics.xml"));
+    let fingerprints = Fingerprints::new(&[&package_file]);
+    assert_eq!(
+        Some(thermal::TemperatureRaw::FINGERPRINT),
+        fingerprints.get("Thermal/TemperatureRaw")
+    );
+    assert_eq!(
+        Some(thermal::HeaterTlm::FINGERPRINT),
+        fingerprints.get("Thermal/HeaterTlm")
+    );
+    assert_ne!(
+        thermal::TemperatureRaw::FINGERPRINT,
+        thermal::Current::FINGERPRINT
+    );
+}
+
+#[test]
+fn test_unknown_fingerprints() {
+    let contents = PACKAGE.replace(r#"type="Speed""#, r#"type="Flow""#);
+    let package_file = resolved(&contents);
+    assert_eq!(Fingerprints::new(&[&package_file]).get("Pump/Status"), None);
+
+    let error = Fingerprints::try_new(&[&package_file]).unwrap_err();
+    assert_eq!(error.name, "Status");
+    assert_eq!(error.error, LayoutError::UnknownType("Flow".to_string()));
+    assert_eq!(
+        error.to_string(),
+        "no fingerprint of `Pump/Status`: unknown type `Flow`"
+    );
+
+    // generated code does not leave out the fingerprint of the type
+    let error = codegen_packagefiles(&[&package_file]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Package[Pump]/DataTypeSet/ContainerDataType[Status]: layout unknown: unknown type `Flow`"
+    );
+}
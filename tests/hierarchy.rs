@@ -0,0 +1,171 @@
+//! Inheritance diagrams of containers
+use seds_rs::codegen::diagram::{ContainerHierarchy, HierarchyEdge, HierarchyNode};
+
+mod common;
+
+use common::{open_file, pump, resolved};
+
+/// the commands of a pump, derived from the command packets of the `CCSDS` package
+const PUMP: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <ContainerDataType name="Command" abstract="true" baseType="CCSDS/CommandPacket">
+        <ConstraintSet>
+          <RangeConstraint entry="Sec.Command">
+            <MinMaxRange min="0" max="16" rangeType="inclusiveMinExclusiveMax"/>
+          </RangeConstraint>
+        </ConstraintSet>
+        <EntryList>
+          <FixedValueEntry name="Version" type="Speed" fixedValue="2"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Stop" baseType="Command">
+        <ConstraintSet>
+          <ValueConstraint entry="Sec.Command" value="3"/>
+        </ConstraintSet>
+      </ContainerDataType>
+      <ContainerDataType name="SetSpeed" baseType="Command">
+        <ConstraintSet>
+          <ValueConstraint entry="Sec.Command" value="4"/>
+          <TypeConstraint entry="Sec" type="CCSDS/CmdSecHdr"/>
+        </ConstraintSet>
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Unrelated">
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+fn node(name: &str) -> HierarchyNode {
+    HierarchyNode {
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_hierarchy() {
+    // without the CCSDS package, its command packet is a node without details
+    let package_file = resolved(PUMP);
+    let hierarchy = ContainerHierarchy::new(&[&package_file]);
+    assert_eq!(
+        hierarchy.nodes,
+        [
+            HierarchyNode {
+                name: "Pump/Command".to_string(),
+                is_abstract: true,
+                fixed_values: vec!["Version = 2".to_string()],
+            },
+            node("CCSDS/CommandPacket"),
+            node("Pump/Stop"),
+            node("Pump/SetSpeed"),
+        ]
+    );
+    assert_eq!(
+        hierarchy.edges,
+        [
+            HierarchyEdge {
+                base: 1,
+                derived: 0,
+                constraints: vec!["0 <= Sec.Command < 16".to_string()],
+            },
+            HierarchyEdge {
+                base: 0,
+                derived: 2,
+                constraints: vec!["Sec.Command = 3".to_string()],
+            },
+            HierarchyEdge {
+                base: 0,
+                derived: 3,
+                constraints: vec![
+                    "Sec.Command = 4".to_string(),
+                    "Sec : CCSDS/CmdSecHdr".to_string()
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_rooted() {
+    let package_files = [
+        resolved(&open_file("eds/test/simplified_spacepacket.xml")),
+        resolved(PUMP),
+    ];
+    let pfs: Vec<_> = package_files.iter().collect();
+
+    let hierarchy = ContainerHierarchy::rooted(&pfs, "CCSDS/SpacePacket").unwrap();
+    let names: Vec<_> = hierarchy.nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "CCSDS/SpacePacket",
+            "CCSDS/CommandPacket",
+            "CCSDS/TelemetryPacket",
+            "Pump/Command",
+            "Pump/Stop",
+            "Pump/SetSpeed",
+        ]
+    );
+    assert_eq!(hierarchy.edges.len(), 5);
+    assert_eq!(
+        hierarchy.edges[0].constraints,
+        ["Hdr.SecHdrFlags = 3".to_string()]
+    );
+
+    assert!(ContainerHierarchy::rooted(&pfs, "CCSDS/Missing").is_none());
+    assert!(ContainerHierarchy::rooted(&pfs, "Pump/Speed").is_none());
+}
+
+#[test]
+fn test_dot() {
+    let package_files = [
+        resolved(&open_file("eds/test/simplified_spacepacket.xml")),
+        resolved(PUMP),
+    ];
+    let pfs: Vec<_> = package_files.iter().collect();
+    let hierarchy = ContainerHierarchy::rooted(&pfs, "Pump/Command").unwrap();
+    assert_eq!(
+        hierarchy.to_dot(),
+        r#"digraph containers {
+    rankdir=BT;
+    node [shape=box];
+    "Pump/Command" [label="Pump/Command (abstract)\nVersion = 2", style=dashed];
+    "Pump/Stop" [label="Pump/Stop"];
+    "Pump/SetSpeed" [label="Pump/SetSpeed"];
+    "Pump/Stop" -> "Pump/Command" [arrowhead=empty, label="Sec.Command = 3"];
+    "Pump/SetSpeed" -> "Pump/Command" [arrowhead=empty, label="Sec.Command = 4\nSec : CCSDS/CmdSecHdr"];
+}
+"#
+    );
+}
+
+#[test]
+fn test_svg() {
+    let package_file = resolved(PUMP);
+    let svg = ContainerHierarchy::new(&[&package_file]).to_svg();
+    assert!(svg.starts_with("<svg viewBox="));
+    for text in [
+        "Pump/Command (abstract)",
+        "Version = 2",
+        "CCSDS/CommandPacket",
+        "Sec.Command = 3",
+        "Sec.Command = 4, Sec : CCSDS/CmdSecHdr",
+    ] {
+        assert!(svg.contains(text), "{}", text);
+    }
+    // a box for each container, its fixed value and the constraints of each base, and an
+    // arrow with its head to each base
+    assert_eq!(svg.matches("<rect").count(), 4 + 1 + 3);
+    assert_eq!(svg.matches("<line").count(), 3 * 3);
+}
@@ -0,0 +1,204 @@
+//! Bit layout of containers
+mod common;
+
+use common::{pump, resolved};
+use seds_rs::eds::ast::DataType;
+use seds_rs::eds::layout::{Encoding, LayoutError, Layouts};
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Checksum">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="255" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <EnumeratedDataType name="Mode">
+        <IntegerDataEncoding sizeInBits="4" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <EnumeratedDataType name="WideMode">
+        <IntegerDataEncoding sizeInBits="12" encoding="unsigned" byteOrder="littleEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <StringDataType name="Name" length="8" fixedLength="false">
+        <StringDataEncoding encoding="ASCII"/>
+      </StringDataType>
+      <ContainerDataType name="Header">
+        <EntryList>
+          <Entry name="Mode" type="Mode"/>
+          <PaddingEntry sizeInBits="4"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Packet" abstract="true">
+        <EntryList>
+          <Entry name="Hdr" type="Header"/>
+        </EntryList>
+        <TrailerEntryList>
+          <ErrorControlEntry name="Crc" type="Checksum" errorControlType="CRC8"/>
+        </TrailerEntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Status" baseType="Packet">
+        <ConstraintSet>
+          <ValueConstraint entry="Hdr.Mode" value="PUMPING"/>
+        </ConstraintSet>
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+/// the name, offset and size of the fields of a container
+fn fields(contents: &str, container: &str) -> Vec<(String, Option<usize>, Option<usize>)> {
+    let package_file = resolved(contents);
+    let package = &package_file.package[0];
+    let Some(DataType::ContainerDataType(container)) = package.data_type(container) else {
+        panic!("no container {}", container);
+    };
+    Layouts::new(&[&package_file])
+        .container(package, container)
+        .unwrap()
+        .fields
+        .into_iter()
+        .map(|f| (f.name, f.offset_in_bits, f.size_in_bits))
+        .collect()
+}
+
+#[test]
+fn test_container_layout() {
+    let package_file = resolved(PACKAGE);
+    let package = &package_file.package[0];
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    let layout = Layouts::new(&[&package_file])
+        .container(package, status)
+        .unwrap();
+    let fields: Vec<_> = layout
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.offset_in_bits, f.size_in_bits))
+        .collect();
+    // the entries of the base container, the entries, then the trailer of the base container
+    assert_eq!(
+        fields,
+        [
+            ("Hdr.Mode", Some(0), Some(4)),
+            ("", Some(4), Some(4)),
+            ("Speed", Some(8), Some(16)),
+            ("Crc", Some(24), Some(8)),
+        ]
+    );
+    assert_eq!(layout.size_in_bits, Some(32));
+
+    let mode = layout.field("Hdr.Mode").unwrap();
+    assert_eq!(mode.type_.as_deref(), Some("Pump/Mode"));
+    assert_eq!(mode.fixed_value.as_deref(), Some("PUMPING"));
+    assert_eq!(mode.encoding.to_string(), "unsigned bigEndian enumeration");
+    assert_eq!(layout.fields[1].encoding, Encoding::Padding);
+}
+
+#[test]
+fn test_type_constraint() {
+    // the constraint changes the size of the entry, and the offsets after it
+    let contents = PACKAGE.replace(
+        r#"<ValueConstraint entry="Hdr.Mode" value="PUMPING"/>"#,
+        r#"<TypeConstraint entry="Hdr.Mode" type="WideMode"/>"#,
+    );
+    assert_eq!(
+        fields(&contents, "Status"),
+        [
+            ("Hdr.Mode".to_string(), Some(0), Some(12)),
+            (String::new(), Some(12), Some(4)),
+            ("Speed".to_string(), Some(16), Some(16)),
+            ("Crc".to_string(), Some(32), Some(8)),
+        ]
+    );
+}
+
+#[test]
+fn test_variable_size() {
+    let contents = PACKAGE.replace(
+        r#"<Entry name="Speed" type="Speed"/>"#,
+        r#"<Entry name="Name" type="Name"/>
+          <Entry name="Speed" type="Speed"/>"#,
+    );
+    assert_eq!(
+        fields(&contents, "Status")[2..],
+        [
+            ("Name".to_string(), Some(8), None),
+            ("Speed".to_string(), None, Some(16)),
+            ("Crc".to_string(), None, Some(8)),
+        ]
+    );
+}
+
+#[test]
+fn test_layout_errors() {
+    let contents = PACKAGE.replace(r#"type="Speed"/>"#, r#"type="Pump/Velocity"/>"#);
+    let package_file = resolved(&contents);
+    let package = &package_file.package[0];
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    let layouts = Layouts::new(&[&package_file]);
+    assert_eq!(
+        layouts.container(package, status),
+        Err(LayoutError::UnknownType("Pump/Velocity".to_string()))
+    );
+
+    let contents = PACKAGE.replace(r#"baseType="Packet""#, r#"baseType="Speed""#);
+    let package_file = resolved(&contents);
+    let package = &package_file.package[0];
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    let error = Layouts::new(&[&package_file])
+        .container(package, status)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "base type `Speed` is not a container");
+}
+
+#[test]
+fn test_overflow() {
+    // the size of an array of 2^32 by 2^32 elements does not fit in 64 bits
+    let contents = PACKAGE.replace(
+        r#"<ContainerDataType name="Header">"#,
+        r#"<ArrayDataType name="Huge" dataTypeRef="Checksum">
+        <DimensionList>
+          <Dimension size="4294967296"/>
+          <Dimension size="4294967296"/>
+        </DimensionList>
+      </ArrayDataType>
+      <ContainerDataType name="Header">"#,
+    );
+    let contents = contents.replace(
+        r#"<Entry name="Speed" type="Speed"/>"#,
+        r#"<Entry name="Samples" type="Huge"/>"#,
+    );
+    let package_file = resolved(&contents);
+    let package = &package_file.package[0];
+    let layouts = Layouts::new(&[&package_file]);
+    let huge = package.data_type("Huge").unwrap();
+    assert_eq!(
+        layouts.encoding(package, huge),
+        Err(LayoutError::Overflow("Huge".to_string()))
+    );
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    let error = layouts.container(package, status).unwrap_err();
+    assert_eq!(error.to_string(), "size of `Huge` overflows");
+}
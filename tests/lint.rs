@@ -0,0 +1,270 @@
+//! Lints of datasheet quality issues
+mod common;
+
+use common::{pump, resolved};
+use seds_rs::diagnostic::{Diagnostic, Severity};
+use seds_rs::eds::ast::{self, DataType};
+use seds_rs::eds::raw::PackageFile;
+use seds_rs::eds::resolve::resolve_all;
+use seds_rs::expr::ExpressionContext;
+use seds_rs::lint::{Finding, Level, Lint, LintContext, LintError, Linter};
+use seds_rs::parse::Parser;
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed" shortDescription="pump speed">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="300" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Offset" shortDescription="speed offset">
+        <IntegerDataEncoding sizeInBits="8" encoding="twosComplement" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="-129" max="128" rangeType="inclusiveMinExclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <EnumeratedDataType name="Mode" shortDescription="operating mode">
+        <IntegerDataEncoding sizeInBits="4" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+          <Enumeration label="FLUSHING" value="4"/>
+          <Enumeration label="RINSING" value="4"/>
+          <Enumeration label="FAULT" value="7"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <ContainerDataType name="Status">
+        <EntryList>
+          <Entry name="Mode" type="Mode" shortDescription="current mode"/>
+          <Entry name="Speed" type="Speed"/>
+          <PaddingEntry sizeInBits="2"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+/// the findings of a single lint
+fn findings(id: &str, contents: &str) -> Vec<Finding> {
+    let mut linter = Linter::new();
+    linter.set_level("all", Level::Allow).unwrap();
+    linter.set_level(id, Level::Warn).unwrap();
+    linter.check(&[&resolved(contents)])
+}
+
+/// the messages and paths of a single lint
+fn messages(id: &str, contents: &str) -> Vec<(String, String)> {
+    findings(id, contents)
+        .into_iter()
+        .map(|f| {
+            assert_eq!(f.lint, id);
+            (f.diagnostic.message, f.diagnostic.path.unwrap_or_default())
+        })
+        .collect()
+}
+
+#[test]
+fn test_missing_short_description() {
+    assert_eq!(
+        messages("missing-short-description", PACKAGE),
+        [
+            (
+                "data type `Status` has no shortDescription".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]".to_string()
+            ),
+            (
+                "entry `Speed` of `Status` has no shortDescription".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/EntryList/Entry[Speed]"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_misaligned_container() {
+    assert_eq!(
+        messages("misaligned-container", PACKAGE),
+        [(
+            "container `Status` is 14 bits long, 2 bits short of a whole byte".to_string(),
+            "Package[Pump]/DataTypeSet/ContainerDataType[Status]".to_string()
+        )]
+    );
+    let aligned = PACKAGE.replace(r#"sizeInBits="2""#, r#"sizeInBits="4""#);
+    assert!(findings("misaligned-container", &aligned).is_empty());
+}
+
+#[test]
+fn test_enumerations() {
+    assert_eq!(
+        messages("duplicate-enumeration-value", PACKAGE),
+        [(
+            "labels `FLUSHING` and `RINSING` of `Mode` have the same value 4".to_string(),
+            "Package[Pump]/DataTypeSet/EnumeratedDataType[Mode]/EnumerationList/Enumeration[3]@value"
+                .to_string()
+        )]
+    );
+    assert_eq!(
+        messages("non-contiguous-enumeration", PACKAGE),
+        [(
+            "values of `Mode` are not contiguous, 2 to 3, 5 to 6 missing".to_string(),
+            "Package[Pump]/DataTypeSet/EnumeratedDataType[Mode]".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_range_exceeds_encoding() {
+    assert_eq!(
+        messages("range-exceeds-encoding", PACKAGE),
+        [
+            (
+                "max 300 of `Speed` does not fit 8 unsigned bits, which hold 0 to 255".to_string(),
+                "Package[Pump]/DataTypeSet/IntegerDataType[Speed]/Range/MinMaxRange@max"
+                    .to_string()
+            ),
+            // the exclusive maximum is in range
+            (
+                "min -129 of `Offset` does not fit 8 twosComplement bits, which hold -128 to 127"
+                    .to_string(),
+                "Package[Pump]/DataTypeSet/IntegerDataType[Offset]/Range/MinMaxRange@min"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_unused_data_type() {
+    let messages: Vec<_> = messages("unused-data-type", PACKAGE)
+        .into_iter()
+        .map(|(message, _)| message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "data type `Offset` is never used",
+            "data type `Status` is never used"
+        ]
+    );
+}
+
+#[test]
+fn test_levels() {
+    let mut linter = Linter::new();
+    assert_eq!(linter.level("missing-short-description"), Some(Level::Warn));
+    assert_eq!(linter.level("unused-data-type"), Some(Level::Warn));
+    assert_eq!(
+        linter.level("non-contiguous-enumeration"),
+        Some(Level::Warn)
+    );
+    assert_eq!(
+        linter.set_level("missing-docs", Level::Deny),
+        Err(LintError::UnknownLint("missing-docs".to_string()))
+    );
+    assert_eq!(
+        "forbid".parse::<Level>(),
+        Err(LintError::InvalidLevel("forbid".to_string()))
+    );
+
+    // the default levels
+    let package_file = resolved(PACKAGE);
+    let lints: Vec<_> = linter
+        .check(&[&package_file])
+        .iter()
+        .map(|f| f.lint)
+        .collect();
+    assert!(lints.contains(&"misaligned-container"));
+    assert!(lints.contains(&"unused-data-type"));
+    assert!(lints.contains(&"non-contiguous-enumeration"));
+
+    linter.set_level("all", Level::Allow).unwrap();
+    linter
+        .set_level("misaligned-container", Level::Deny)
+        .unwrap();
+    let findings = linter.check(&[&package_file]);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].level, Level::Deny);
+    assert_eq!(findings[0].diagnostic.severity, Severity::Error);
+    assert_eq!(
+        findings[0].diagnostic.notes,
+        [
+            "add a PaddingEntry to align the container",
+            "lint `misaligned-container` is set to deny"
+        ]
+    );
+}
+
+#[test]
+fn test_json() {
+    let parsed = Parser::new("pump.xml")
+        .parse::<PackageFile>(PACKAGE)
+        .unwrap();
+    let package_file = resolve_all(&parsed.value, &ExpressionContext::default()).unwrap();
+    let mut finding = Linter::new()
+        .check(&[&package_file])
+        .into_iter()
+        .find(|f| f.lint == "misaligned-container")
+        .unwrap();
+    finding.diagnostic = parsed.source.locate(finding.diagnostic);
+    assert_eq!(
+        finding.to_json(),
+        serde_json::json!({
+            "lint": "misaligned-container",
+            "level": "warn",
+            "message": "container `Status` is 14 bits long, 2 bits short of a whole byte",
+            "file": "pump.xml",
+            "line": 27,
+            "column": 8,
+            "path": "Package[Pump]/DataTypeSet/ContainerDataType[Status]",
+        })
+    );
+}
+
+/// A lint of the mission: data type names start with a capital letter
+struct CapitalizedNames;
+
+impl Lint for CapitalizedNames {
+    fn id(&self) -> &'static str {
+        "capitalized-names"
+    }
+
+    fn description(&self) -> &'static str {
+        "data type names that do not start with a capital letter"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Deny
+    }
+
+    fn check<'a>(
+        &self,
+        _cx: &LintContext<'a>,
+        package_file: &'a ast::PackageFile,
+    ) -> Vec<Diagnostic> {
+        package_file.package[0]
+            .data_type_set
+            .data_types
+            .iter()
+            .filter(|dt| !dt.name().starts_with(|c: char| c.is_uppercase()))
+            .map(|dt: &DataType| Diagnostic::error(format!("`{}` is not capitalized", dt.name())))
+            .collect()
+    }
+}
+
+#[test]
+fn test_custom_lint() {
+    let linter = Linter::new().with_lint(CapitalizedNames);
+    assert_eq!(linter.level("capitalized-names"), Some(Level::Deny));
+    let package_file = resolved(&PACKAGE.replace(r#"name="Offset""#, r#"name="offset""#));
+    let findings: Vec<_> = linter
+        .check(&[&package_file])
+        .into_iter()
+        .filter(|f| f.lint == "capitalized-names")
+        .collect();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(
+        findings[0].diagnostic.message,
+        "`offset` is not capitalized"
+    );
+}
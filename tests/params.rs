@@ -0,0 +1,310 @@
+//! Layered mission parameter sources and parameter reports
+use std::path::Path;
+
+use seds_rs::eds::raw;
+use seds_rs::eds::value::Value;
+use seds_rs::expr::report::{references, ParameterReference, ParameterReport};
+use seds_rs::expr::sources::{Format, Layer, ParameterError, ParameterLoader};
+use seds_rs::expr::ExpressionContext;
+
+mod common;
+
+use common::{get_mission_params, open_file, parse};
+
+/// value of a parameter path like `A/B`
+fn value(ctx: &ExpressionContext, path: &str) -> Value {
+    ctx.value(&path.split('/').collect::<Vec<_>>()).unwrap()
+}
+
+fn loader() -> ParameterLoader {
+    ParameterLoader::new()
+        .file(
+            Layer::Mission,
+            Path::new("eds/test/mission_parameters.json"),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_layer_precedence() {
+    // added out of order, merged by layer
+    let ctx = loader()
+        .set("CFE_MISSION/ES_MAX_APPLICATIONS=48")
+        .unwrap()
+        .variables(
+            "SEDS_PARAM_",
+            [
+                (
+                    "SEDS_PARAM_CFE_MISSION__MAX_PATH_LEN".to_string(),
+                    "128".to_string(),
+                ),
+                (
+                    "SEDS_PARAM_CFE_MISSION__ES_MAX_APPLICATIONS".to_string(),
+                    "40".to_string(),
+                ),
+                ("OTHER_VARIABLE".to_string(), "1".to_string()),
+            ],
+        )
+        .unwrap()
+        .file(Layer::Target, Path::new("eds/test/target_flatsat.toml"))
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        value(&ctx, "CFE_MISSION/ES_MAX_APPLICATIONS"),
+        Value::Integer(48)
+    );
+    assert_eq!(value(&ctx, "CFE_MISSION/MAX_PATH_LEN"), Value::Integer(128));
+    assert_eq!(
+        value(&ctx, "CFE_MISSION/DATA_BYTE_ORDER"),
+        Value::from("littleEndian")
+    );
+    // untouched mission values remain
+    assert_eq!(
+        value(&ctx, "CFE_MISSION/MAX_CPU_ADDRESS_SIZE"),
+        Value::Integer(1024)
+    );
+    assert!(ctx.get(&["OTHER_VARIABLE"]).is_err());
+}
+
+#[test]
+fn test_sources() {
+    let ctx = loader()
+        .file(Layer::Target, Path::new("eds/test/target_simulator.yaml"))
+        .unwrap()
+        .set("CFE_MISSION/MAX_PATH_LEN=64")
+        .unwrap()
+        .load()
+        .unwrap();
+
+    let source = ctx.source("CFE_MISSION/MAX_CPU_ADDRESS_SIZE").unwrap();
+    assert_eq!(source.layer, Layer::Mission);
+    assert_eq!(source.origin, "eds/test/mission_parameters.json");
+
+    let source = ctx.source("CFE_MISSION/ES_MAX_APPLICATIONS").unwrap();
+    assert_eq!(source.layer, Layer::Target);
+    assert_eq!(
+        source.to_string(),
+        "target (eds/test/target_simulator.yaml)"
+    );
+
+    let source = ctx.source("CFE_MISSION/MAX_PATH_LEN").unwrap();
+    assert_eq!(source.layer, Layer::CommandLine);
+    assert_eq!(source.origin, "CFE_MISSION/MAX_PATH_LEN=64");
+
+    // namespaces have no source
+    assert!(ctx.source("CFE_MISSION").is_none());
+    assert!(ctx.source("UNKNOWN").is_none());
+
+    let parameters = ctx.parameters();
+    assert!(parameters.contains(&("CFE_MISSION/SIMULATED".to_string(), Value::Boolean(true))));
+    assert!(parameters.windows(2).all(|p| p[0].0 < p[1].0));
+}
+
+#[test]
+fn test_document_formats() {
+    let ctx = ParameterLoader::new()
+        .document(
+            Layer::Mission,
+            "mission",
+            Format::Json,
+            r#"{"A": {"X": 1, "Y": [1, 2]}}"#,
+        )
+        .unwrap()
+        .document(Layer::Target, "target.toml", Format::Toml, "[A]\nX = 2.5\n")
+        .unwrap()
+        .document(
+            Layer::Target,
+            "target.yaml",
+            Format::Yaml,
+            "A:\n  Y: [3]\n  Z: text\n  P:\n    - D: 4\n",
+        )
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(value(&ctx, "A/X"), Value::Float(2.5));
+    // arrays are replaced, not merged
+    assert_eq!(value(&ctx, "A/Y"), Value::Array(vec![Value::Integer(3)]));
+    assert_eq!(value(&ctx, "A/Z"), Value::from("text"));
+    assert_eq!(ctx.source("A/X").unwrap().origin, "target.toml");
+    assert_eq!(ctx.source("A/Y").unwrap().origin, "target.yaml");
+    // the objects of an array come from the document of the array
+    assert_eq!(value(&ctx, "A/P/0/D"), Value::Integer(4));
+    assert_eq!(ctx.source("A/P/0/D").unwrap().origin, "target.yaml");
+
+    assert_eq!(
+        Format::from_path(Path::new("params.yml")),
+        Some(Format::Yaml)
+    );
+    assert_eq!(Format::from_path(Path::new("params.ini")), None);
+}
+
+#[test]
+fn test_set_values() {
+    let ctx = ParameterLoader::new()
+        .set("A/LIST=[1, 2, 3]")
+        .unwrap()
+        .set("A/TEXT=hello")
+        .unwrap()
+        .set("A/FLAG=true")
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        value(&ctx, "A/LIST"),
+        Value::Array(vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(3)
+        ])
+    );
+    assert_eq!(value(&ctx, "A/TEXT"), Value::from("hello"));
+    assert_eq!(value(&ctx, "A/FLAG"), Value::Boolean(true));
+    assert_eq!(
+        ctx.eval_expression("${A/LIST}[1] + 1").unwrap(),
+        Value::Integer(3)
+    );
+}
+
+#[test]
+fn test_parameter_errors() {
+    for assignment in ["NO_VALUE", "=1", "A//B=1", "A-B=1"] {
+        assert!(
+            matches!(ParameterLoader::new().set(assignment), Err(ParameterError::InvalidAssignment(a)) if a == assignment),
+            "{}",
+            assignment
+        );
+    }
+
+    // a value starting with `[` is a JSON array, not text
+    let res = ParameterLoader::new().set("A/LIST=[1, 2");
+    assert!(
+        matches!(&res, Err(ParameterError::Syntax { origin, .. }) if origin == "A/LIST=[1, 2"),
+        "{:?}",
+        res
+    );
+    let res = ParameterLoader::new().variables(
+        "SEDS_PARAM_",
+        [("SEDS_PARAM_A__LIST".to_string(), "[x]".to_string())],
+    );
+    assert!(
+        matches!(&res, Err(ParameterError::Syntax { origin, .. }) if origin == "SEDS_PARAM_A__LIST")
+    );
+
+    let res = ParameterLoader::new().file(Layer::Target, Path::new("target.ini"));
+    assert!(matches!(res, Err(ParameterError::UnknownFormat(p)) if p == "target.ini"));
+
+    let res = ParameterLoader::new().file(Layer::Target, Path::new("eds/test/missing.toml"));
+    assert!(matches!(res, Err(ParameterError::Io(p, _)) if p == "eds/test/missing.toml"));
+
+    let res = ParameterLoader::new().document(Layer::Target, "bad.toml", Format::Toml, "A = ");
+    assert!(matches!(res, Err(ParameterError::Syntax { origin, .. }) if origin == "bad.toml"));
+
+    let res = ParameterLoader::new().document(Layer::Target, "list.yaml", Format::Yaml, "- 1\n");
+    assert!(matches!(res, Err(ParameterError::Syntax { origin, .. }) if origin == "list.yaml"));
+
+    // a value can not replace a namespace
+    let res = loader().set("CFE_MISSION=1").unwrap().load();
+    match res {
+        Err(ParameterError::Conflict { key, source }) => {
+            assert_eq!(key, "CFE_MISSION");
+            assert_eq!(source.layer, Layer::CommandLine);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    // nor a namespace a value
+    let res = loader()
+        .set("CFE_MISSION/MAX_PATH_LEN/INNER=1")
+        .unwrap()
+        .load();
+    assert!(
+        matches!(res, Err(ParameterError::Conflict { key, .. }) if key == "CFE_MISSION/MAX_PATH_LEN")
+    );
+
+    // values are still checked
+    let res = ParameterLoader::new()
+        .document(Layer::Mission, "null.json", Format::Json, r#"{"A": null}"#)
+        .unwrap()
+        .load();
+    assert!(matches!(res, Err(ParameterError::Value(_))));
+}
+
+#[test]
+fn test_parameter_report() {
+    let datasheet: raw::DataSheet = parse(&open_file("eds/test/test_container.xml"));
+    let package_file: raw::PackageFile = parse(&open_file("eds/test/test_activities.xml"));
+
+    let mut refs = references("test_container.xml", &datasheet).unwrap();
+    refs.extend(references("test_activities.xml", &package_file).unwrap());
+    assert_eq!(
+        refs,
+        vec![
+            ParameterReference {
+                parameter: "CFE_MISSION/SPACECRAFT_ID".to_string(),
+                file: "test_container.xml".to_string(),
+                location: "Package[SimpleDemo]/DataTypeSet/ContainerDataType[WriteMapInfoCmd]/EntryList/FixedValueEntry[SpacecraftID]@fixedValue".to_string(),
+            },
+            ParameterReference {
+                parameter: "CFE_MISSION/ES_MAX_APPLICATIONS".to_string(),
+                file: "test_activities.xml".to_string(),
+                location: "Metadata/MetadataValueSet/IntegerValue[MaxApplications]@value"
+                    .to_string(),
+            },
+        ]
+    );
+
+    let report = ParameterReport::new(refs, &get_mission_params());
+    assert!(!report.is_complete());
+    assert_eq!(report.missing, vec!["CFE_MISSION/SPACECRAFT_ID"]);
+    assert!(!report
+        .unused
+        .contains(&"CFE_MISSION/ES_MAX_APPLICATIONS".to_string()));
+    assert!(report.unused.contains(&"CFE_SB/MSGID_BIT_SIZE".to_string()));
+    assert_eq!(report.references_to("CFE_MISSION/SPACECRAFT_ID").count(), 1);
+
+    // defining the parameter completes the report
+    let ctx = loader()
+        .set("CFE_MISSION/SPACECRAFT_ID=66")
+        .unwrap()
+        .load()
+        .unwrap();
+    let report = ParameterReport::new(report.references, &ctx);
+    assert!(report.is_complete());
+}
+
+#[test]
+fn test_parameter_report_locations() {
+    let package_file: raw::PackageFile = parse(
+        r#"<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+            <Package name="P">
+                <LongDescription>up to ${N} entries, ${unterminated</LongDescription>
+                <DataTypeSet>
+                    <ArrayDataType name="A" dataTypeRef="T">
+                        <DimensionList>
+                            <Dimension size="${N}"/>
+                            <Dimension size="${M} * ${N}"/>
+                        </DimensionList>
+                    </ArrayDataType>
+                </DataTypeSet>
+            </Package>
+        </PackageFile>"#,
+    );
+    let locations: Vec<_> = references("f.xml", &package_file)
+        .unwrap()
+        .into_iter()
+        .map(|r| format!("{} {}", r.parameter, r.location))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            "N Package[P]/LongDescription",
+            "N Package[P]/DataTypeSet/ArrayDataType[A]/DimensionList/Dimension@size",
+            "M Package[P]/DataTypeSet/ArrayDataType[A]/DimensionList/Dimension[1]@size",
+            "N Package[P]/DataTypeSet/ArrayDataType[A]/DimensionList/Dimension[1]@size",
+        ]
+    );
+}
@@ -0,0 +1,282 @@
+//! Streaming, namespace-aware parser
+use seds_rs::diagnostic::Severity;
+use seds_rs::eds::raw::{self, DataType, PackageFile};
+use seds_rs::parse::{Parser, Strictness};
+use std::fs;
+use walkdir::{DirEntry, WalkDir};
+
+/// filter for determining eds
+fn is_xml(entry: &DirEntry) -> bool {
+    entry.file_name().to_string_lossy().ends_with(".xml")
+}
+
+/// test files that include each other, to test the detection of include cycles
+const INCLUDE_CYCLES: [&str; 2] = [
+    "eds/test/include/cycle.xml",
+    "eds/test/include/cycle_package.xml",
+];
+
+#[test]
+fn test_parse_test_files() {
+    let walker = WalkDir::new("eds/test").into_iter();
+    for entry in walker.filter_map(Result::ok).filter(is_xml) {
+        let path = entry.path().display().to_string();
+        if INCLUDE_CYCLES.contains(&path.as_str()) {
+            assert!(Parser::new(&path)
+                .parse::<PackageFile>(&fs::read_to_string(entry.path()).unwrap())
+                .is_err());
+            continue;
+        }
+        let xml_string = fs::read_to_string(entry.path()).unwrap();
+        let parsed = Parser::new(&path)
+            .include_path("eds/test/include/lib")
+            .parse::<PackageFile>(&xml_string);
+        assert!(parsed.is_ok(), "{}: {:?}", path, parsed);
+    }
+}
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds" xmlns:ext="urn:example:ext">
+  <Package name="Pump">
+    <DeclaredInterfaceSet>
+      <Interface name="Flow" />
+    </DeclaredInterfaceSet>
+    <DataTypeSet>
+      <IntegerDataType name="Rate" ext:calibrated="true">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" />
+        <ext:Annotation>litres per minute</ext:Annotation>
+      </IntegerDataType>
+      <BooleanDataType name="Running" />
+    </DataTypeSet>
+  </Package>
+</PackageFile>
+"#;
+
+fn data_type_names(package_file: &PackageFile) -> Vec<String> {
+    let data_types = &package_file.package[0]
+        .data_type_set
+        .as_ref()
+        .unwrap()
+        .data_types;
+    data_types
+        .iter()
+        .map(|data_type| match data_type {
+            DataType::IntegerDataType(t) => t.name_entity_type.name.clone(),
+            DataType::BooleanDataType(t) => t.name_entity_type.name.clone(),
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_strictness() {
+    // unknown elements and extensions the models do not keep are warnings by default
+    let contents = PACKAGE.replace(
+        r#"encoding="unsigned" />"#,
+        r#"encoding="unsigned" ext:packed="true" />"#,
+    );
+    let parsed = Parser::new("pump.xml")
+        .parse::<PackageFile>(&contents)
+        .unwrap();
+    assert_eq!(data_type_names(&parsed.value), vec!["Rate", "Running"]);
+    let messages: Vec<_> = parsed.warnings.iter().map(|w| w.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "unknown element `DeclaredInterfaceSet` in `Package`",
+            "skipped extension attribute `ext:packed`",
+        ]
+    );
+    let warning = &parsed.warnings[0];
+    assert_eq!(warning.severity, Severity::Warning);
+    assert_eq!(
+        warning.path.as_deref(),
+        Some("Package[Pump]/DeclaredInterfaceSet")
+    );
+    let span = warning.span.as_ref().unwrap();
+    assert_eq!((span.line, span.column), (4, 6));
+    assert_eq!(
+        parsed.warnings[1].path.as_deref(),
+        Some("Package[Pump]/DataTypeSet/IntegerDataType[Rate]/IntegerDataEncoding@packed")
+    );
+
+    // strict parsing fails on unknown SEDS elements only
+    let error = Parser::new("pump.xml")
+        .strictness(Strictness::Strict)
+        .parse::<PackageFile>(PACKAGE)
+        .unwrap_err();
+    assert_eq!(error.severity, Severity::Error);
+    assert_eq!(error.span.as_ref().unwrap().line, 4);
+    let known = PACKAGE.replace(
+        "<DeclaredInterfaceSet>\n      <Interface name=\"Flow\" />\n    </DeclaredInterfaceSet>",
+        "",
+    );
+    let parsed = Parser::new("pump.xml")
+        .strictness(Strictness::Strict)
+        .parse::<PackageFile>(&known)
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+
+    // lenient parsing is silent
+    let parsed = Parser::new("pump.xml")
+        .strictness(Strictness::Lenient)
+        .parse::<PackageFile>(PACKAGE)
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+    assert_eq!(data_type_names(&parsed.value), vec!["Rate", "Running"]);
+}
+
+#[test]
+fn test_namespace_prefix() {
+    let prefixed = r#"<?xml version="1.0" encoding="UTF-8"?>
+<seds:PackageFile xmlns:seds="http://www.ccsds.org/schema/sois/seds">
+  <seds:Package name="Pump">
+    <seds:DataTypeSet>
+      <seds:IntegerDataType name="Rate">
+        <seds:IntegerDataEncoding sizeInBits="8" encoding="unsigned" />
+      </seds:IntegerDataType>
+      <seds:BooleanDataType name="Running" />
+    </seds:DataTypeSet>
+  </seds:Package>
+</seds:PackageFile>
+"#;
+    let parsed = Parser::new("pump.xml")
+        .parse::<PackageFile>(prefixed)
+        .unwrap();
+    assert!(parsed.warnings.is_empty());
+    assert_eq!(data_type_names(&parsed.value), vec!["Rate", "Running"]);
+    assert!(parsed.source.contains(
+        "Package[Pump]/DataTypeSet/IntegerDataType[Rate]/IntegerDataEncoding@sizeInBits"
+    ));
+
+    // the same elements in another namespace are extensions
+    let foreign = prefixed.replace("http://www.ccsds.org/schema/sois/seds", "urn:example:other");
+    let parsed = Parser::new("pump.xml")
+        .strictness(Strictness::Strict)
+        .parse::<PackageFile>(&foreign)
+        .unwrap();
+    assert!(parsed.value.package.is_empty());
+    assert_eq!(parsed.warnings.len(), 1);
+}
+
+#[test]
+fn test_parse_errors() {
+    // missing required attributes are located at their element
+    let unnamed = PACKAGE.replace(
+        r#"<BooleanDataType name="Running" />"#,
+        "<BooleanDataType />",
+    );
+    let error = Parser::new("pump.xml")
+        .parse::<PackageFile>(&unnamed)
+        .unwrap_err();
+    assert_eq!(
+        error.message,
+        "missing attribute `name` in `BooleanDataType`"
+    );
+    assert_eq!(error.span.as_ref().unwrap().line, 12);
+
+    // syntax errors are located by the XML parser
+    let broken = PACKAGE.replace("</DataTypeSet>", "</DataTypes>");
+    let error = Parser::new("pump.xml")
+        .parse::<PackageFile>(&broken)
+        .unwrap_err();
+    assert_eq!(error.file.as_deref(), Some("pump.xml"));
+    assert_eq!(error.span.as_ref().unwrap().line, 13);
+
+    let invalid = r#"<PackageFile><Package name="P"><ComponentSet><Component name="C">
+        <Implementation><VariableSet><Variable name="V" type="T" readOnly="maybe" />
+        </VariableSet></Implementation></Component></ComponentSet></Package></PackageFile>"#;
+    let error = Parser::new("p.xml")
+        .parse::<PackageFile>(invalid)
+        .unwrap_err();
+    assert_eq!(error.message, "invalid boolean `maybe`");
+    assert_eq!(
+        error.path.as_deref(),
+        Some(
+            "Package[P]/ComponentSet/Component[C]/Implementation/VariableSet/Variable[V]@readOnly"
+        )
+    );
+}
+
+#[test]
+fn test_parse_datasheet() {
+    let contents = fs::read_to_string("eds/test/test_datasheet.xml").unwrap();
+    let parsed = Parser::new("test_datasheet.xml")
+        .strictness(Strictness::Strict)
+        .parse::<raw::DataSheet>(&contents)
+        .unwrap();
+    assert_eq!(parsed.value.devices.len(), 1);
+    assert!(!parsed.value.packages.is_empty());
+
+    // a datasheet can be read as a package file
+    let package_file: PackageFile = Parser::new("test_datasheet.xml")
+        .parse(&contents)
+        .unwrap()
+        .value;
+    assert_eq!(package_file.package, parsed.value.packages);
+}
+
+#[test]
+fn test_include_errors() {
+    // included files are searched next to the including file, then on the include path
+    let contents = fs::read_to_string("eds/test/include/datasheet.xml").unwrap();
+    let error = Parser::new("eds/test/include/datasheet.xml")
+        .parse::<raw::DataSheet>(&contents)
+        .unwrap_err();
+    assert_eq!(error.message, "included file `units.xml` not found");
+    assert_eq!(error.span.as_ref().unwrap().line, 9);
+
+    // cycles are reported with the chain of includes
+    let contents = fs::read_to_string("eds/test/include/cycle.xml").unwrap();
+    let error = Parser::new("eds/test/include/cycle.xml")
+        .parse::<raw::DataSheet>(&contents)
+        .unwrap_err();
+    assert_eq!(
+        error.message,
+        "include cycle eds/test/include/cycle.xml -> eds/test/include/cycle_package.xml -> eds/test/include/cycle.xml"
+    );
+    assert_eq!(
+        error.file.as_deref(),
+        Some("eds/test/include/cycle_package.xml")
+    );
+    assert_eq!(error.span.as_ref().unwrap().line, 4);
+    assert_eq!(
+        error.notes,
+        vec!["included from eds/test/include/cycle.xml:4:2"]
+    );
+
+    // warnings of included files are located in them
+    let heater = fs::read_to_string("eds/test/include/heater.xml").unwrap();
+    let datasheet = r#"<DataSheet xmlns="http://www.ccsds.org/schema/sois/seds"
+        xmlns:xi="http://www.w3.org/2001/XInclude">
+        <Device name="Heater" />
+        <xi:include href="heater.xml" />
+    </DataSheet>"#;
+    let parsed = Parser::new("eds/test/include/inline.xml")
+        .parse::<raw::DataSheet>(datasheet)
+        .unwrap();
+    assert!(parsed.value.packages.is_empty());
+    let warning = &parsed.warnings[0];
+    assert_eq!(
+        warning.message,
+        "unknown element `PackageFile` in `DataSheet`"
+    );
+    assert_eq!(warning.file.as_deref(), Some("eds/test/include/heater.xml"));
+    assert_eq!(
+        warning.span.as_ref().unwrap().text,
+        heater.lines().nth(1).unwrap()
+    );
+    assert_eq!(
+        warning.notes,
+        vec!["included from eds/test/include/inline.xml:4:9"]
+    );
+
+    let unsupported = datasheet.replace("/>", r#"xpointer="xpointer(/PackageFile/Package)" />"#);
+    let error = Parser::new("eds/test/include/inline.xml")
+        .parse::<raw::DataSheet>(&unsupported)
+        .unwrap_err();
+    assert_eq!(
+        error.message,
+        "unsupported XPointer `xpointer(/PackageFile/Package)`"
+    );
+}
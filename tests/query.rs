@@ -0,0 +1,191 @@
+//! Queries over resolved packages
+use seds_rs::eds::ast::{self, DataType, EntryElement, MetadataValue};
+use seds_rs::eds::query::Query;
+
+mod common;
+
+use common::{open_file, pump, resolved};
+
+/// a package that derives from the space packets of the `CCSDS` package
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <ContainerDataType name="SetSpeed" baseType="CCSDS/CommandPacket">
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+          <Entry name="Ramp" type="Pump/Speed"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="SetSpeedNow" baseType="SetSpeed">
+        <EntryList>
+          <Entry name="Immediate" type="CCSDS/SingleBitFlag"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Status" baseType="CCSDS/TelemetryPacket">
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+        <TrailerEntryList>
+          <Entry name="Check" type="Speed"/>
+        </TrailerEntryList>
+      </ContainerDataType>
+"#
+);
+
+fn package_files() -> Vec<ast::PackageFile> {
+    vec![
+        resolved(&open_file("eds/test/simplified_spacepacket.xml")),
+        resolved(PACKAGE),
+    ]
+}
+
+fn names(matches: &[seds_rs::eds::query::TypeMatch]) -> Vec<String> {
+    matches.iter().map(|t| t.name()).collect()
+}
+
+#[test]
+fn test_lookup() {
+    let package_files = package_files();
+    let pfs: Vec<_> = package_files.iter().collect();
+    let query = Query::new(&pfs);
+
+    let status = query.data_type("Pump/Status").unwrap();
+    assert_eq!(status.name(), "Pump/Status");
+    assert_eq!(status.element(), "ContainerDataType");
+    assert!(query.data_type("Pump/Flow").is_none());
+    assert!(query.data_type("Status").is_none());
+
+    let check = query.entry("Pump/Status/Check").unwrap();
+    assert!(check.trailer);
+    assert_eq!(check.type_name().as_deref(), Some("Pump/Speed"));
+    let length = query.entry("CCSDS/BaseHdr/Length").unwrap();
+    assert!(matches!(length.entry, EntryElement::LengthEntry(_)));
+    assert!(query.entry("Pump/Status/Length").is_none());
+
+    assert_eq!(
+        names(&query.bases("Pump/SetSpeedNow")),
+        ["Pump/SetSpeed", "CCSDS/CommandPacket", "CCSDS/SpacePacket"]
+    );
+    assert!(query.bases("Pump/Speed").is_empty());
+}
+
+#[test]
+fn test_references() {
+    let package_files = package_files();
+    let pfs: Vec<_> = package_files.iter().collect();
+    let query = Query::new(&pfs);
+
+    // local and qualified references are found by the qualified name
+    let references: Vec<_> = query
+        .references_to("Pump/Speed")
+        .iter()
+        .map(|r| (r.path.as_str(), r.element, r.data_type.as_deref()))
+        .collect();
+    assert_eq!(
+        references,
+        [
+            ("Pump/SetSpeed/Speed", "Entry", Some("Pump/SetSpeed")),
+            ("Pump/SetSpeed/Ramp", "Entry", Some("Pump/SetSpeed")),
+            ("Pump/Status/Speed", "Entry", Some("Pump/Status")),
+            ("Pump/Status/Check", "Entry", Some("Pump/Status")),
+        ]
+    );
+    assert_eq!(
+        query.dependents("Pump/Speed"),
+        ["Pump/SetSpeed", "Pump/Status", "Pump/SetSpeedNow"]
+    );
+
+    // across packages
+    let entries: Vec<_> = query
+        .entries_of_type("CCSDS/SingleBitFlag")
+        .iter()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            "CCSDS/APIDqualifiers/Endian",
+            "CCSDS/APIDqualifiers/Playback",
+            "Pump/SetSpeedNow/Immediate",
+        ]
+    );
+    assert!(query.references_to("Pump/Flow").is_empty());
+}
+
+#[test]
+fn test_derived() {
+    let package_files = package_files();
+    let pfs: Vec<_> = package_files.iter().collect();
+    let query = Query::new(&pfs);
+
+    assert_eq!(
+        names(&query.derived("CCSDS/CommandPacket")),
+        ["Pump/SetSpeed", "Pump/SetSpeedNow"]
+    );
+    assert_eq!(
+        names(&query.derived("CCSDS/SpacePacket")),
+        [
+            "CCSDS/CommandPacket",
+            "CCSDS/TelemetryPacket",
+            "Pump/SetSpeed",
+            "Pump/Status",
+            "Pump/SetSpeedNow",
+        ]
+    );
+    assert!(query.derived("Pump/SetSpeedNow").is_empty());
+}
+
+#[test]
+fn test_predicates() {
+    let package_files = package_files();
+    let pfs: Vec<_> = package_files.iter().collect();
+    let query = Query::new(&pfs);
+
+    let roots: Vec<_> = query
+        .data_types_where(|t| {
+            matches!(t.data_type, DataType::ContainerDataType(c) if c.base_type.is_none())
+                && !query.derived(&t.name()).is_empty()
+        })
+        .iter()
+        .map(|t| t.name())
+        .collect();
+    assert_eq!(roots, ["CCSDS/BaseHdr", "CCSDS/SpacePacket"]);
+
+    let trailers: Vec<_> = query
+        .entries_where(|e| e.trailer || e.entry.element() == "ErrorControlEntry")
+        .iter()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(trailers, ["CCSDS/CmdSecHdr/Checksum", "Pump/Status/Check"]);
+}
+
+#[test]
+fn test_metadata() {
+    let package_file = resolved(&open_file("eds/test/test_activities.xml"));
+    let query = Query::new(&[&package_file]);
+
+    let paths: Vec<_> = query.metadata().map(|(path, _)| path).collect();
+    assert_eq!(
+        paths,
+        [
+            "Vendor",
+            "Released",
+            "MaxApplications",
+            "Thermal/MaxTemperature",
+        ]
+    );
+    assert!(matches!(
+        query.metadata_value("Vendor"),
+        Some(MetadataValue::StringValue(v)) if v.value.0 == "ACME"
+    ));
+    assert!(matches!(
+        query.metadata_value("Thermal/MaxTemperature"),
+        Some(MetadataValue::FloatValue(_))
+    ));
+    assert!(query.metadata_value("MaxTemperature").is_none());
+}
@@ -1,6 +1,6 @@
 mod common;
 
-use common::open_file;
+use common::{open_file, parse};
 use seds_rs::eds::raw::PackageFile;
 use seds_rs::eds::resolve::Resolve;
 use seds_rs::expr::ExpressionContext;
@@ -8,7 +8,7 @@ use seds_rs::expr::ExpressionContext;
 #[test]
 fn test_resolve_spacepacket() {
     let contents = open_file("./eds/cFE/modules/core_api/eds/ccsds_spacepacket.xml");
-    let package: PackageFile = serde_xml_rs::from_str(&contents).unwrap();
+    let package: PackageFile = parse(&contents);
     let json = serde_json::json!({
         "CCSDS_SPACEPACKET": {
             "HEADER_TYPE": "<HEADER_TYPE>",
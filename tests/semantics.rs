@@ -0,0 +1,206 @@
+//! Semantic terms from the SEDS core semantics DoT (units, quantity kinds, frames, ...)
+use seds_rs::codegen::{codegen_packagefiles, rustfmt};
+use seds_rs::eds::ast::{self, DataType, EntryElement, PackageFile, QuantityKind, Unit};
+use seds_rs::eds::raw;
+use seds_rs::eds::resolve::{Resolve, ResolveError};
+use seds_rs::eds::semantics::SemanticTerm;
+
+mod common;
+
+use common::{get_mission_params, open_file, parse};
+
+fn get_raw_package_file() -> raw::PackageFile {
+    let contents = open_file("eds/test/test_semantics.xml");
+    parse(&contents)
+}
+
+fn get_package_file() -> PackageFile {
+    get_raw_package_file()
+        .resolve(&get_mission_params())
+        .unwrap()
+}
+
+fn find_data_type<'a>(pf: &'a PackageFile, name: &str) -> &'a DataType {
+    pf.package[0]
+        .data_type_set
+        .data_types
+        .iter()
+        .find(|dt| match dt {
+            DataType::IntegerDataType(dt) => dt.name_entity_type.name.0 == name,
+            DataType::FloatDataType(dt) => dt.name_entity_type.name.0 == name,
+            DataType::ContainerDataType(dt) => dt.name_entity_type.name.0 == name,
+            _ => false,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_term_round_trip() {
+    assert_eq!(Unit::from_term("ampere-hour"), Some(Unit::AmpereHour));
+    assert_eq!(Unit::AmpereHour.as_term(), "ampere-hour");
+    assert_eq!(Unit::from_term("localUnit"), Some(Unit::Local));
+    assert_eq!(
+        QuantityKind::from_term("angleQK"),
+        Some(QuantityKind::Angle)
+    );
+    assert_eq!(Unit::from_term("furlong"), None);
+    assert_eq!(format!("{}", Unit::MetrePerSecond), "metrePerSecond");
+}
+
+#[test]
+fn test_parse_semantics() {
+    let pf = get_raw_package_file();
+    let data_types = &pf.package[0].data_type_set.as_ref().unwrap().data_types;
+    match &data_types[0] {
+        raw::DataType::IntegerDataType(dt) => {
+            let semantics = &dt.name_entity_type.semantics;
+            assert_eq!(semantics.unit, Some("degreeCelsius".to_string()));
+            assert_eq!(
+                semantics.quantity_kind,
+                Some("celsiusTemperatureQK".to_string())
+            );
+            assert_eq!(semantics.purpose, Some("measurement".to_string()));
+        }
+        _ => panic!("expected IntegerDataType"),
+    }
+}
+
+#[test]
+fn test_resolve_semantics() {
+    let pf = get_package_file();
+
+    match find_data_type(&pf, "Angle") {
+        DataType::FloatDataType(dt) => {
+            let semantics = &dt.name_entity_type.semantics;
+            assert_eq!(semantics.unit, Some(Unit::DegreeAngle));
+            assert_eq!(semantics.quantity_kind, Some(QuantityKind::Angle));
+            assert_eq!(
+                semantics.reference_frame,
+                Some(ast::ReferenceFrame::Vehicle)
+            );
+            assert_eq!(semantics.chirality, Some(ast::Chirality::RightHanded));
+        }
+        _ => panic!("expected FloatDataType"),
+    }
+
+    match find_data_type(&pf, "Current") {
+        DataType::IntegerDataType(dt) => assert!(dt.name_entity_type.semantics.is_empty()),
+        _ => panic!("expected IntegerDataType"),
+    }
+
+    match find_data_type(&pf, "HeaterTlm") {
+        DataType::ContainerDataType(dt) => match &dt.entry_list.as_ref().unwrap().entries[1] {
+            EntryElement::Entry(entry) => {
+                assert_eq!(entry.name_entity_type.semantics.unit, Some(Unit::Ampere))
+            }
+            _ => panic!("expected Entry"),
+        },
+        _ => panic!("expected ContainerDataType"),
+    }
+}
+
+#[test]
+fn test_resolve_invalid_unit() {
+    let mut pf = get_raw_package_file();
+    match &mut pf.package[0].data_type_set.as_mut().unwrap().data_types[0] {
+        raw::DataType::IntegerDataType(dt) => {
+            dt.name_entity_type.semantics.unit = Some("furlong".to_string())
+        }
+        _ => panic!("expected IntegerDataType"),
+    }
+    let err = pf.resolve(&get_mission_params()).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Thermal]/DataTypeSet/IntegerDataType[TemperatureRaw]@unit"
+    );
+    match err.into_cause() {
+        ResolveError::InvalidSemanticTerm(term) => assert_eq!(term, "furlong"),
+        err => panic!("expected InvalidSemanticTerm, got {:?}", err),
+    }
+}
+
+#[test]
+fn test_codegen_semantics() {
+    let pf = get_package_file();
+    let code = rustfmt(codegen_packagefiles(&[&pf]).unwrap()).unwrap();
+
+    assert!(code.contains("pub const UNIT: &'static str = \"degreeCelsius\";"));
+    assert!(code.contains("pub const QUANTITY_KIND: &'static str = \"angleQK\";"));
+    assert!(code.contains("pub const HEATER_CURRENT_UNIT: &'static str = \"ampere\";"));
+    // semantic terms are listed in the rustdoc
+    assert!(code.contains("- Unit: `degreeAngle`"));
+    assert!(code.contains("- Reference Frame: `vehicle`"));
+}
+
+#[test]
+fn test_resolve_calibrators() {
+    let pf = get_package_file();
+    match find_data_type(&pf, "CurrentMilli") {
+        DataType::IntegerDataType(dt) => assert!(matches!(
+            &dt.calibrator,
+            Some(ast::Calibrator::PolynomialCalibrator(pc)) if pc.term.len() == 1
+        )),
+        _ => panic!("expected IntegerDataType"),
+    }
+    match find_data_type(&pf, "BusVoltage") {
+        DataType::IntegerDataType(dt) => match &dt.calibrator {
+            Some(ast::Calibrator::SplineCalibrator(sc)) => {
+                assert!(!sc.extrapolate);
+                assert_eq!(sc.spline_point.len(), 3);
+                assert_eq!(sc.spline_point[1].order, 1);
+                assert_eq!(sc.spline_point[1].calibrated.0, "5");
+            }
+            calibrator => panic!("expected SplineCalibrator, got {:?}", calibrator),
+        },
+        _ => panic!("expected IntegerDataType"),
+    }
+
+    // a spline point of an order above 3
+    let contents = open_file("eds/test/test_semantics.xml").replace(
+        r#"<SplinePoint raw="100" calibrated="5" />"#,
+        r#"<SplinePoint raw="100" calibrated="5" order="4" />"#,
+    );
+    let err = parse::<raw::PackageFile>(&contents)
+        .resolve(&get_mission_params())
+        .unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Thermal]/DataTypeSet/IntegerDataType[BusVoltage]/SplineCalibrator/SplinePoint[1]@order"
+    );
+    assert_eq!(
+        err.cause().to_string(),
+        "invalid calibrator: invalid order `4`"
+    );
+}
+
+#[test]
+fn test_codegen_unsupported_calibrators() {
+    // curves are not supported, rather than leaving the raw value uncalibrated
+    let contents = open_file("eds/test/test_semantics.xml").replace(
+        r#"<SplinePoint raw="100" calibrated="5" />"#,
+        r#"<SplinePoint raw="100" calibrated="5" order="2" />"#,
+    );
+    let pf = parse::<raw::PackageFile>(&contents)
+        .resolve(&get_mission_params())
+        .unwrap();
+    let err = codegen_packagefiles(&[&pf]).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Thermal]/DataTypeSet/IntegerDataType[BusVoltage]/SplineCalibrator"
+    );
+    assert_eq!(
+        err.cause().to_string(),
+        "invalid calibrator: splines of order 2 are not supported"
+    );
+
+    let contents = open_file("eds/test/test_semantics.xml")
+        .replace(r#"coefficient="0.001""#, r#"coefficient="milli""#);
+    let pf = parse::<raw::PackageFile>(&contents)
+        .resolve(&get_mission_params())
+        .unwrap();
+    let err = codegen_packagefiles(&[&pf]).unwrap_err();
+    assert_eq!(
+        err.path(),
+        "Package[Thermal]/DataTypeSet/IntegerDataType[CurrentMilli]/PolynomialCalibrator/Term[0]"
+    );
+}
@@ -0,0 +1,141 @@
+//! Unit-checked conversions using the units of the SEDS core semantics DoT
+// the deku derives in the generated code trip this lint
+#![allow(clippy::manual_div_ceil)]
+use deku::DekuContainerRead;
+use seds_macro::seds;
+use seds_rs::eds::semantics::{QuantityKind, SemanticTerm, Unit};
+use seds_rs::eds::units::{Quantity, UnitConversionError};
+
+#[seds("eds/test/test_semantics.xml", validate = true)]
+struct Dummy; // replaced by the generated module `thermal`
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+}
+
+#[test]
+fn test_convert_compatible_units() {
+    let angle = Quantity::new(180.0, Unit::DegreeAngle);
+    let rad = angle.convert_to(Unit::Radian).unwrap();
+    assert_eq!(rad.unit, Unit::Radian);
+    assert_close(rad.value, std::f64::consts::PI);
+
+    let arcsec = angle.convert_to(Unit::Arcsecond).unwrap();
+    assert_close(arcsec.value, 648_000.0);
+
+    let hours = Quantity::new(7200.0, Unit::Second)
+        .convert_to(Unit::Hour)
+        .unwrap();
+    assert_close(hours.value, 2.0);
+
+    let charge = Quantity::new(1.0, Unit::AmpereHour).to_si().unwrap();
+    assert_eq!(charge.unit, Unit::Coulomb);
+    assert_close(charge.value, 3600.0);
+}
+
+#[test]
+fn test_convert_temperature_offset() {
+    let t = Quantity::new(-273.15, Unit::DegreeCelsius);
+    assert_close(t.convert_to(Unit::Kelvin).unwrap().value, 0.0);
+    let k = Quantity::new(300.0, Unit::Kelvin);
+    assert_close(k.convert_to(Unit::DegreeCelsius).unwrap().value, 26.85);
+}
+
+#[test]
+fn test_convert_incompatible_units() {
+    let length = Quantity::new(1.0, Unit::Metre);
+    assert_eq!(
+        length.convert_to(Unit::Second),
+        Err(UnitConversionError::IncompatibleUnits(
+            Unit::Metre,
+            Unit::Second
+        ))
+    );
+    assert!(!Unit::DegreeAngle.is_compatible(&Unit::DegreeCelsius));
+    assert_eq!(
+        Quantity::new(1.0, Unit::Local).to_si(),
+        Err(UnitConversionError::UnknownConversion(Unit::Local))
+    );
+}
+
+#[test]
+fn test_unit_quantity_kind() {
+    assert_eq!(Unit::Arcsecond.quantity_kind(), Some(QuantityKind::Angle));
+    assert_eq!(
+        Unit::Kibibyte.quantity_kind(),
+        Some(QuantityKind::Information)
+    );
+    assert_eq!(Unit::Local.quantity_kind(), None);
+}
+
+#[test]
+fn test_generated_si_accessors() {
+    let raw = thermal::TemperatureRaw {
+        value: (-10i16) as u16,
+    };
+    assert_eq!(thermal::TemperatureRaw::SI_UNIT, "kelvin");
+    assert_close(raw.to_si(), 263.15);
+
+    let angle = thermal::Angle {
+        value: 90f32.to_bits(),
+    };
+    assert_eq!(thermal::Angle::UNIT, "degreeAngle");
+    assert_close(angle.to_si(), std::f64::consts::FRAC_PI_2);
+
+    // generated SI unit names are valid terms of the vocabulary
+    let si = Quantity::new(
+        angle.to_si(),
+        Unit::from_term(thermal::Angle::SI_UNIT).unwrap(),
+    );
+    assert_close(si.convert_to(Unit::DegreeAngle).unwrap().value, 90.0);
+}
+
+#[test]
+fn test_signed_si_accessors() {
+    // the sign of integers of any width and signed encoding is kept
+    let fine = |value| thermal::TemperatureFine { value }.to_si();
+    assert_close(fine(0xfff), 272.15);
+    assert_close(fine(0x800), 273.15 - 2048.0);
+    assert_close(fine(0x7ff), 273.15 + 2047.0);
+
+    let position = |value| thermal::Position { value }.to_si();
+    assert_eq!(thermal::Position::SI_UNIT, "metre");
+    assert_close(position(0x0005), 5.0);
+    assert_close(position(0x8005), -5.0);
+    assert_close(position(0x8000), 0.0);
+
+    let deflection = |value| thermal::Deflection { value }.to_si();
+    assert_close(deflection(0x5a), std::f64::consts::FRAC_PI_2);
+    assert_close(deflection(0xa5), -std::f64::consts::FRAC_PI_2);
+    assert_close(deflection(0xff), 0.0);
+}
+
+#[test]
+fn test_decoded_entry_si_accessor() {
+    // Temperature = 25 degC (big endian), HeaterCurrent = 2 A
+    let ((_rest, _), tlm) = thermal::HeaterTlm::from_bytes((&[0x00, 0x19, 0x00, 0x02], 0)).unwrap();
+    assert_close(tlm.temperature.to_si(), 298.15);
+    assert_eq!(thermal::HeaterTlm::HEATER_CURRENT_SI_UNIT, "ampere");
+    assert_close(tlm.heater_current_to_si(), 2.0);
+}
+
+#[test]
+fn test_calibrated_si_accessors() {
+    // the DoT has no milliampere, so a current in mA is an ampere type with a calibrator
+    let current = thermal::CurrentMilli { value: 1500 };
+    assert_eq!(thermal::CurrentMilli::SI_UNIT, "ampere");
+    assert_close(current.to_si(), 1.5);
+
+    // straight lines between the points of a spline, and no value outside of them
+    let voltage = |value| thermal::BusVoltage { value }.to_si();
+    assert_close(voltage(0), 0.0);
+    assert_close(voltage(50), 2.5);
+    assert_close(voltage(150), 8.5);
+    assert_close(voltage(200), 12.0);
+    assert!(voltage(201).is_nan());
+
+    // entries are calibrated by their types, 500 mA and 100 raw
+    let ((_rest, _), tlm) = thermal::PowerTlm::from_bytes((&[0x01, 0xf4, 0x64], 0)).unwrap();
+    assert_close(tlm.current_to_si(), 0.5);
+    assert_close(tlm.voltage_to_si(), 5.0);
+}
@@ -0,0 +1,266 @@
+//! Validation against the SEDS schema and user-defined DoTs
+mod common;
+
+use common::open_file;
+use seds_rs::diagnostic::Diagnostic;
+use seds_rs::validate::{validate, Schema, Validator};
+
+/// the violations of a document against the SEDS schema
+fn violations(contents: &str) -> Vec<Diagnostic> {
+    validate("test.xml", contents).err().unwrap_or_default()
+}
+
+#[test]
+fn test_valid_files() {
+    let schema = Schema::seds().unwrap();
+    for path in [
+        "eds/basic/enumerateddatatype.xml",
+        "eds/test/test_container.xml",
+        "eds/test/test_datasheet.xml",
+        "eds/test/test_datatypes.xml",
+        "eds/test/test_semantics.xml",
+        "eds/test/test_multi_datasheet.xml",
+        "eds/SEDSDoTForSANA/ccsds.sois.seds.xml",
+        "eds/SEDSDoTForSANA/ccsds.sois.subnetwork.xml",
+    ] {
+        let result = Validator::new(&schema, path).validate(&open_file(path));
+        assert!(result.is_ok(), "{}: {:?}", path, result);
+    }
+}
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Pump">
+    <DataTypeSet>
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="middleEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="10" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+
+#[test]
+fn test_invalid_attribute() {
+    let diagnostics = violations(PACKAGE);
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(
+        diagnostic.message,
+        "invalid value of `byteOrder` in `IntegerDataEncoding`: `middleEndian` is not one of bigEndian, littleEndian"
+    );
+    let span = diagnostic.span.as_ref().unwrap();
+    assert_eq!((span.line, span.column), (6, 66));
+    assert_eq!(diagnostic.file.as_deref(), Some("test.xml"));
+}
+
+#[test]
+fn test_unexpected_element() {
+    let contents = PACKAGE
+        .replace("middleEndian", "bigEndian")
+        .replace("<Range>", "<Range>\n          <Bogus/>");
+    let diagnostics = violations(&contents);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "unexpected element `Bogus` in `Range`, expected MinMaxRange"
+    );
+    assert_eq!(
+        diagnostics[0].path.as_deref(),
+        Some("Package[Pump]/DataTypeSet/IntegerDataType[Speed]/Range/Bogus")
+    );
+}
+
+#[test]
+fn test_element_order() {
+    let contents = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Pump">
+    <DataTypeSet>
+      <ContainerDataType name="Cmd">
+        <EntryList>
+          <Entry name="Id" type="Speed"/>
+        </EntryList>
+        <ConstraintSet>
+          <ValueConstraint entry="Id" value="1"/>
+        </ConstraintSet>
+      </ContainerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+    let diagnostics = violations(contents);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "unexpected element `ConstraintSet` in `ContainerDataType`, expected TrailerEntryList"
+    );
+    assert_eq!(diagnostics[0].span.as_ref().unwrap().line, 9);
+}
+
+#[test]
+fn test_missing_content() {
+    let contents = PACKAGE
+        .replace("middleEndian", "bigEndian")
+        .replace(r#"<IntegerDataType name="Speed">"#, "<IntegerDataType>");
+    let contents = contents.replace(
+        r#"<MinMaxRange min="0" max="10" rangeType="inclusiveMinInclusiveMax"/>"#,
+        "",
+    );
+    let messages: Vec<_> = violations(&contents)
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "missing attribute `name` in `IntegerDataType`",
+            "missing element in `Range`, expected MinMaxRange",
+        ]
+    );
+}
+
+#[test]
+fn test_unqualified_elements() {
+    let contents = PACKAGE.replace(r#" xmlns="http://www.ccsds.org/schema/sois/seds""#, "");
+    let diagnostics = violations(&contents);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "no declaration of the root element `PackageFile`"
+    );
+    assert_eq!(diagnostics[0].span.as_ref().unwrap().line, 2);
+}
+
+#[test]
+fn test_include() {
+    let path = "eds/test/include/datasheet.xml";
+    let schema = Schema::seds().unwrap();
+    let validator = Validator::new(&schema, path).include_path("eds/test/include/lib");
+    assert!(validator.validate(&open_file(path)).is_ok());
+
+    // violations in an included file are located there
+    let contents = open_file("eds/test/include/heater.xml").replace("bigEndian", "middleEndian");
+    let directory = std::env::temp_dir().join("seds-validate-include");
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("heater.xml"), contents).unwrap();
+    let datasheet = directory.join("datasheet.xml");
+    std::fs::write(&datasheet, open_file(path)).unwrap();
+    let datasheet = datasheet.display().to_string();
+    let diagnostics = Validator::new(&schema, &datasheet)
+        .include_path("eds/test/include/lib")
+        .validate(&open_file(&datasheet))
+        .unwrap_err();
+    assert!(!diagnostics.is_empty());
+    for diagnostic in diagnostics.iter() {
+        assert!(diagnostic.file.as_deref().unwrap().ends_with("heater.xml"));
+        assert!(diagnostic.notes[0].starts_with(&format!("included from {}:8:", datasheet)));
+    }
+}
+
+#[test]
+fn test_dot_extension() {
+    let path = "eds/test/dot/heater.xml";
+    let contents = open_file(path);
+
+    // the unit and the attribute are not part of the SEDS schema
+    let schema = Schema::seds().unwrap();
+    let messages: Vec<_> = Validator::new(&schema, path)
+        .validate(&contents)
+        .unwrap_err()
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "invalid value of `unit` in `IntegerDataType`: `furlong` is not a valid `Unit`",
+            "attribute `criticality` is not allowed in `IntegerDataType`",
+        ]
+    );
+
+    let schema = Schema::seds()
+        .unwrap()
+        .extend("eds/test/dot/heater_dot.xsd")
+        .unwrap();
+    assert!(Validator::new(&schema, path).validate(&contents).is_ok());
+    let invalid = contents.replace(r#"criticality="high""#, r#"criticality="extreme""#);
+    let diagnostics = Validator::new(&schema, path)
+        .validate(&invalid)
+        .unwrap_err();
+    assert_eq!(
+        diagnostics[0].message,
+        "invalid value of `criticality` in `IntegerDataType`: `extreme` is not one of low, high"
+    );
+
+    // the DoT includes the built-in SEDS schema
+    let schema = Schema::load("eds/test/dot/heater_dot.xsd").unwrap();
+    assert!(Validator::new(&schema, path).validate(&contents).is_ok());
+}
+
+#[test]
+fn test_any_attribute() {
+    let path = "eds/test/dot/heater.xml";
+    let contents = open_file(path)
+        .replace(r#"xmlns:xsi="#, r#"xmlns:tlm="urn:example:tlm" xmlns:xsi="#)
+        .replace(
+            r#"criticality="high""#,
+            r#"tlm:priority="3" tlm:owner="eps""#,
+        );
+    let messages = |schema: &Schema, contents: &str| -> Vec<_> {
+        Validator::new(schema, path)
+            .validate(contents)
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    };
+
+    // attributes of other namespaces are checked if they are declared
+    let schema = Schema::load("eds/test/dot/open_dot.xsd").unwrap();
+    let contents = contents.replace(r#"unit="furlong" "#, "");
+    assert!(messages(&schema, &contents).is_empty());
+    let invalid = contents
+        .replace(r#"tlm:priority="3""#, r#"tlm:priority="high""#)
+        .replace(r#"tlm:owner="eps""#, r#"tlm:owner="eps" owner="eps""#);
+    assert_eq!(
+        messages(&schema, &invalid),
+        [
+            "invalid value of `priority` in `IntegerDataType`: `high` is not a valid xs:int",
+            "attribute `owner` is not allowed in `IntegerDataType`",
+        ]
+    );
+
+    // skipped attributes are not checked, strict ones must be declared
+    let directory = std::env::temp_dir().join("seds-validate-any-attribute");
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("tlm.xsd"), open_file("eds/test/dot/tlm.xsd")).unwrap();
+    let load = |process: &str| {
+        let path = directory.join(format!("{}_dot.xsd", process));
+        let dot = open_file("eds/test/dot/open_dot.xsd").replace("lax", process);
+        std::fs::write(&path, dot).unwrap();
+        Schema::load(&path).unwrap()
+    };
+    let invalid = contents.replace(r#"tlm:priority="3""#, r#"tlm:priority="high""#);
+    assert!(messages(&load("skip"), &invalid).is_empty());
+    assert_eq!(
+        messages(&load("strict"), &invalid),
+        [
+            "invalid value of `priority` in `IntegerDataType`: `high` is not a valid xs:int",
+            "attribute `tlm:owner` in `IntegerDataType` has no global declaration",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_error() {
+    let error = Schema::seds()
+        .unwrap()
+        .extend("eds/test/dot/missing.xsd")
+        .unwrap_err();
+    assert!(error
+        .message
+        .starts_with("can not read `eds/test/dot/missing.xsd`"));
+}
@@ -0,0 +1,297 @@
+//! Semantic checks of resolved package files
+mod common;
+
+use common::{get_mission_params, open_file, pump, resolved};
+use seds_rs::diagnostic::Diagnostic;
+use seds_rs::eds::raw::PackageFile;
+use seds_rs::eds::resolve::resolve_all;
+use seds_rs::parse::Parser;
+use seds_rs::validate::SemanticValidator;
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="SlowSpeed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="100" rangeType="inclusiveMinExclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <FloatDataType name="Pressure">
+        <FloatDataEncoding sizeInBits="32" encodingAndPrecision="IEEE754_2008_single" byteOrder="bigEndian"/>
+      </FloatDataType>
+      <EnumeratedDataType name="Mode">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <ContainerDataType name="Header" abstract="true">
+        <EntryList>
+          <Entry name="Mode" type="Mode"/>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Packet" abstract="true">
+        <EntryList>
+          <Entry name="Hdr" type="Header"/>
+          <Entry name="Pressure" type="Pressure"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Status" baseType="Packet">
+        <ConstraintSet>
+          <ValueConstraint entry="Hdr.Mode" value="PUMPING"/>
+          <TypeConstraint entry="Hdr.Speed" type="SlowSpeed"/>
+          <ValueConstraint entry="Pressure" value="1.5e5"/>
+        </ConstraintSet>
+        <EntryList>
+          <Entry name="Flow" type="Speed"/>
+        </EntryList>
+      </ContainerDataType>
+"#
+);
+
+/// the violations of a package file, with the types of its own packages
+fn violations(contents: &str) -> Vec<Diagnostic> {
+    let package_file = resolved(contents);
+    SemanticValidator::new(&[&package_file])
+        .validate(&package_file)
+        .err()
+        .unwrap_or_default()
+}
+
+/// the messages and paths of the violations
+fn messages(contents: &str) -> Vec<(String, String)> {
+    violations(contents)
+        .into_iter()
+        .map(|d| (d.message, d.path.unwrap_or_default()))
+        .collect()
+}
+
+#[test]
+fn test_valid_package() {
+    assert!(violations(PACKAGE).is_empty());
+
+    // the constraints of the CCSDS headers name the entries of container entries
+    let package_file = resolved(&open_file("eds/test/simplified_spacepacket.xml"));
+    let result = SemanticValidator::new(&[&package_file]).validate(&package_file);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[test]
+fn test_unique_names() {
+    let contents = PACKAGE.replace(
+        r#"<Entry name="Flow" type="Speed"/>"#,
+        r#"<Entry name="Flow" type="Speed"/>
+          <Entry name="Pressure" type="Pressure"/>
+          <Entry name="Flow" type="Speed"/>"#,
+    );
+    assert_eq!(
+        messages(&contents),
+        [
+            (
+                "entry `Pressure` of `Status` is already defined in its base container `Packet`"
+                    .to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/EntryList/Entry[Pressure]"
+                    .to_string()
+            ),
+            (
+                "duplicate entry `Flow` in `Status`".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/EntryList/Entry[Flow]"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_constraint_targets() {
+    let contents = PACKAGE
+        .replace(r#"entry="Hdr.Mode""#, r#"entry="Flow""#)
+        .replace(r#"entry="Pressure""#, r#"entry="Pressure.Value""#);
+    assert_eq!(
+        messages(&contents),
+        [
+            (
+                "no entry `Flow` in the base containers of `Status`".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/ConstraintSet/ValueConstraint@entry"
+                    .to_string()
+            ),
+            (
+                "entry `Pressure` is not a container".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/ConstraintSet/ValueConstraint[1]@entry"
+                    .to_string()
+            ),
+        ]
+    );
+
+    // constraints apply to the entries of base containers
+    let contents = PACKAGE.replace(r#" baseType="Packet""#, "");
+    assert_eq!(
+        violations(&contents)[0].message,
+        "constraint on `Hdr.Mode` in `Status`, which has no base container"
+    );
+    let contents = PACKAGE.replace(r#"baseType="Packet""#, r#"baseType="Pump/Packets""#);
+    assert_eq!(
+        messages(&contents),
+        [(
+            "unknown base type `Pump/Packets` of `Status`".to_string(),
+            "Package[Pump]/DataTypeSet/ContainerDataType[Status]@baseType".to_string()
+        )]
+    );
+    // cyclic base containers are reported like their layouts
+    let contents = PACKAGE.replace(
+        r#"name="Packet" abstract="true""#,
+        r#"name="Packet" abstract="true" baseType="Status""#,
+    );
+    assert_eq!(
+        messages(&contents),
+        [
+            (
+                "types nested too deeply in `Packet`, they may be cyclic".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Packet]@baseType".to_string()
+            ),
+            (
+                "types nested too deeply in `Status`, they may be cyclic".to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]@baseType".to_string()
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_type_constraint() {
+    // the range of the constraint exceeds the range of the entry
+    let contents = PACKAGE.replace(r#"max="100""#, r#"max="1001""#);
+    assert_eq!(
+        messages(&contents),
+        [(
+            "range [0, 1001) of `SlowSpeed` is not included in the range [0, 1000] of `Speed`, the type of `Hdr.Speed`"
+                .to_string(),
+            "Package[Pump]/DataTypeSet/ContainerDataType[Status]/ConstraintSet/TypeConstraint@type"
+                .to_string()
+        )]
+    );
+
+    let contents = PACKAGE.replace(r#"type="SlowSpeed""#, r#"type="Mode""#);
+    assert_eq!(
+        violations(&contents)[0].message,
+        "type `Mode` of the constraint on `Hdr.Speed` in `Status` is not numeric"
+    );
+    let contents = PACKAGE.replace(r#"entry="Hdr.Speed""#, r#"entry="Hdr.Mode""#);
+    assert_eq!(
+        violations(&contents)[0].message,
+        "entry `Hdr.Mode` of a type constraint in `Status` has the non-numeric type `Mode`"
+    );
+}
+
+#[test]
+fn test_value_constraint() {
+    let contents = PACKAGE
+        .replace(r#"value="PUMPING""#, r#"value="SLEEPING""#)
+        .replace(r#"value="1.5e5""#, r#"value="high""#);
+    assert_eq!(
+        messages(&contents),
+        [
+            (
+                "value `SLEEPING` of the constraint on `Hdr.Mode` in `Status` is not a valid `Mode`: expected one of IDLE, PUMPING"
+                    .to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/ConstraintSet/ValueConstraint@value"
+                    .to_string()
+            ),
+            (
+                "value `high` of the constraint on `Pressure` in `Status` is not a valid `Pressure`: expected a number, not a string"
+                    .to_string(),
+                "Package[Pump]/DataTypeSet/ContainerDataType[Status]/ConstraintSet/ValueConstraint[1]@value"
+                    .to_string()
+            ),
+        ]
+    );
+
+    // enumerations take the values of their labels too
+    let contents = PACKAGE.replace(r#"value="PUMPING""#, r#"value="1""#);
+    assert!(violations(&contents).is_empty());
+
+    let contents = PACKAGE.replace(
+        r#"<ValueConstraint entry="Hdr.Mode" value="PUMPING"/>"#,
+        r#"<ValueConstraint entry="Hdr.Speed" value="1200"/>
+          <ValueConstraint entry="Hdr" value="0"/>"#,
+    );
+    let messages: Vec<_> = violations(&contents)
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "value `1200` of the constraint on `Hdr.Speed` in `Status` is not a valid `Speed`: 1200 is outside [0, 1000]",
+            "value `0` of the constraint on `Hdr` in `Status` is not a valid `Header`: a `ContainerDataType` has no literal values",
+        ]
+    );
+}
+
+#[test]
+fn test_encoding_range() {
+    // without a maximum, the values of an integer are bounded by its encoding
+    let contents = PACKAGE
+        .replace(
+            r#"<ContainerDataType name="Header" abstract="true">
+        <EntryList>"#,
+            r#"<IntegerDataType name="Count">
+        <IntegerDataEncoding sizeInBits="8" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" rangeType="atLeast"/>
+        </Range>
+      </IntegerDataType>
+      <IntegerDataType name="Id">
+        <IntegerDataEncoding sizeInBits="64" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="18446744073709551615" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <ContainerDataType name="Header" abstract="true">
+        <EntryList>
+          <Entry name="Count" type="Count"/>
+          <Entry name="Id" type="Id"/>"#,
+        )
+        .replace(
+            r#"<ValueConstraint entry="Hdr.Mode" value="PUMPING"/>"#,
+            r#"<ValueConstraint entry="Hdr.Count" value="255"/>
+          <ValueConstraint entry="Hdr.Count" value="300"/>
+          <ValueConstraint entry="Hdr.Id" value="18446744073709551615"/>
+          <ValueConstraint entry="Hdr.Id" value="18446744073709551616"/>"#,
+        );
+    let messages: Vec<_> = violations(&contents)
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    // 64-bit integers are compared exactly, as floats the last two are the same
+    assert_eq!(
+        messages,
+        [
+            "value `300` of the constraint on `Hdr.Count` in `Status` is not a valid `Count`: 300 is outside [0, 255]",
+            "value `18446744073709551616` of the constraint on `Hdr.Id` in `Status` is not a valid `Id`: 18446744073709551616 is outside [0, 18446744073709551615]",
+        ]
+    );
+}
+
+#[test]
+fn test_located_violations() {
+    let contents = PACKAGE.replace(r#"value="1.5e5""#, r#"value="high""#);
+    let parsed = Parser::new("pump.xml")
+        .parse::<PackageFile>(&contents)
+        .unwrap();
+    let package_file = resolve_all(&parsed.value, &get_mission_params()).unwrap();
+    let diagnostics = SemanticValidator::new(&[&package_file])
+        .validate(&package_file)
+        .unwrap_err();
+    let diagnostic = parsed.source.locate(diagnostics[0].clone());
+    assert_eq!(diagnostic.file.as_deref(), Some("pump.xml"));
+    assert_eq!(diagnostic.span.unwrap().line, 43);
+}
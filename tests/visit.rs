@@ -0,0 +1,270 @@
+//! Visitors of the ast
+use std::collections::BTreeMap;
+
+use seds_rs::eds::ast::{self, DataType, EntryElement, EntryList, NamedEntityType, QualifiedName};
+use seds_rs::eds::layout::Layouts;
+use seds_rs::eds::visit::{
+    walk_container_data_type, walk_entry_list_mut, Path, Visitor, VisitorMut,
+};
+
+mod common;
+
+use common::{open_file, pump, resolved};
+
+const PACKAGE: &str = pump!(
+    r#"
+      <IntegerDataType name="Speed">
+        <IntegerDataEncoding sizeInBits="16" encoding="unsigned" byteOrder="bigEndian"/>
+        <Range>
+          <MinMaxRange min="0" max="1000" rangeType="inclusiveMinInclusiveMax"/>
+        </Range>
+      </IntegerDataType>
+      <EnumeratedDataType name="Mode">
+        <IntegerDataEncoding sizeInBits="4" encoding="unsigned" byteOrder="bigEndian"/>
+        <EnumerationList>
+          <Enumeration label="IDLE" value="0"/>
+          <Enumeration label="PUMPING" value="1"/>
+        </EnumerationList>
+      </EnumeratedDataType>
+      <ContainerDataType name="Packet" abstract="true">
+        <EntryList>
+          <Entry name="Mode" type="Mode"/>
+          <PaddingEntry sizeInBits="4"/>
+        </EntryList>
+      </ContainerDataType>
+      <ContainerDataType name="Status" baseType="Packet">
+        <ConstraintSet>
+          <TypeConstraint entry="Mode" type="Mode"/>
+        </ConstraintSet>
+        <EntryList>
+          <Entry name="Speed" type="Speed"/>
+        </EntryList>
+        <TrailerEntryList>
+          <Entry name="Check" type="Pump/Speed"/>
+        </TrailerEntryList>
+      </ContainerDataType>
+"#
+);
+
+/// the number of data types and entries of each kind
+#[derive(Default)]
+struct Statistics {
+    elements: BTreeMap<&'static str, usize>,
+}
+
+impl<'ast> Visitor<'ast> for Statistics {
+    fn visit_named_entity_type(&mut self, path: &Path, _named: &'ast NamedEntityType) {
+        *self
+            .elements
+            .entry(path.parent().unwrap().element)
+            .or_default() += 1;
+    }
+
+    fn visit_padding_entry(&mut self, _path: &Path, _entry: &'ast ast::PaddingEntry) {
+        *self.elements.entry("PaddingEntry").or_default() += 1;
+    }
+}
+
+/// the type references with the path and the element that holds them
+#[derive(Default)]
+struct TypeRefs<'ast> {
+    type_refs: Vec<(String, &'static str, &'ast str)>,
+}
+
+impl<'ast> Visitor<'ast> for TypeRefs<'ast> {
+    fn visit_type_ref(&mut self, path: &Path, type_ref: &'ast QualifiedName) {
+        let parent = path.parent().unwrap();
+        self.type_refs
+            .push((path.to_string(), parent.element, type_ref.0.as_str()));
+    }
+}
+
+#[test]
+fn test_statistics() {
+    let package_file = resolved(PACKAGE);
+    let mut statistics = Statistics::default();
+    statistics.visit_package_file(&Path::default(), &package_file);
+    assert_eq!(
+        statistics.elements.into_iter().collect::<Vec<_>>(),
+        [
+            ("ContainerDataType", 2),
+            ("Entry", 3),
+            ("EnumeratedDataType", 1),
+            ("IntegerDataType", 1),
+            ("Package", 1),
+            ("PaddingEntry", 1),
+        ]
+    );
+}
+
+#[test]
+fn test_paths() {
+    let package_file = resolved(PACKAGE);
+    let mut type_refs = TypeRefs::default();
+    type_refs.visit_package_file(&Path::default(), &package_file);
+    assert_eq!(
+        type_refs.type_refs,
+        [
+            ("Pump/Packet/Mode".to_string(), "Entry", "Mode"),
+            ("Pump/Status".to_string(), "ContainerDataType", "Packet"),
+            ("Pump/Status/Mode".to_string(), "TypeConstraint", "Mode"),
+            ("Pump/Status/Speed".to_string(), "Entry", "Speed"),
+            ("Pump/Status/Check".to_string(), "Entry", "Pump/Speed"),
+        ]
+    );
+
+    /// the qualified names of the containers with trailers, without walking their entries
+    #[derive(Default)]
+    struct Trailers(Vec<String>);
+
+    impl<'ast> Visitor<'ast> for Trailers {
+        fn visit_entry_list(&mut self, _path: &Path, _list: &'ast EntryList) {}
+
+        fn visit_trailer_entry_list(&mut self, path: &Path, _list: &'ast EntryList) {
+            assert_eq!(path.package(), Some("Pump"));
+            self.0.push(path.data_type().unwrap());
+        }
+
+        fn visit_container_data_type(
+            &mut self,
+            path: &Path,
+            data_type: &'ast ast::ContainerDataType,
+        ) {
+            assert_eq!(path.to_string(), "Pump");
+            assert_eq!(path.parent().unwrap().element, "DataTypeSet");
+            walk_container_data_type(self, path, data_type)
+        }
+    }
+
+    let mut trailers = Trailers::default();
+    trailers.visit_package_file(&Path::default(), &package_file);
+    assert_eq!(trailers.0, ["Pump/Status"]);
+}
+
+#[test]
+fn test_components() {
+    let contents = open_file("eds/test/test_statemachine.xml");
+    let package_file = resolved(&contents);
+    let mut type_refs = TypeRefs::default();
+    type_refs.visit_package_file(&Path::default(), &package_file);
+    assert_eq!(
+        type_refs.type_refs,
+        [
+            (
+                "Heater/HeaterController/Cmd".to_string(),
+                "Interface",
+                "HeaterCommands"
+            ),
+            (
+                "Heater/HeaterController/Target".to_string(),
+                "Variable",
+                "Setpoint"
+            ),
+        ]
+    );
+
+    /// the paths of the named nodes of the state machines
+    #[derive(Default)]
+    struct Names(Vec<String>);
+
+    impl<'ast> Visitor<'ast> for Names {
+        fn visit_named_entity_type(&mut self, path: &Path, _named: &'ast NamedEntityType) {
+            if path.named("StateMachine").is_some() {
+                self.0.push(path.to_string());
+            }
+        }
+    }
+
+    let mut names = Names::default();
+    names.visit_package_file(&Path::default(), &package_file);
+    assert_eq!(names.0.len(), 10);
+    assert_eq!(names.0[0], "Heater/HeaterController/Control");
+    assert_eq!(names.0[1], "Heater/HeaterController/Control/PowerOn");
+    assert_eq!(names.0[9], "Heater/HeaterController/Control/Watchdog");
+}
+
+/// rename a data type of a package, and the references to it
+struct Rename {
+    package: &'static str,
+    from: &'static str,
+    to: &'static str,
+}
+
+impl VisitorMut for Rename {
+    fn visit_named_entity_type_mut(&mut self, path: &Path, named: &mut NamedEntityType) {
+        let parent = path.parent().unwrap();
+        if parent.element.ends_with("DataType")
+            && path.package() == Some(self.package)
+            && named.name.0 == self.from
+        {
+            named.name.0 = self.to.to_string();
+        }
+    }
+
+    fn visit_type_ref_mut(&mut self, path: &Path, type_ref: &mut QualifiedName) {
+        if type_ref.0 == self.from && path.package() == Some(self.package) {
+            type_ref.0 = self.to.to_string();
+        } else if type_ref.0 == format!("{}/{}", self.package, self.from) {
+            type_ref.0 = format!("{}/{}", self.package, self.to);
+        }
+    }
+}
+
+#[test]
+fn test_rename() {
+    let mut package_file = resolved(PACKAGE);
+    let mut rename = Rename {
+        package: "Pump",
+        from: "Speed",
+        to: "Velocity",
+    };
+    rename.visit_package_file_mut(&Path::default(), &mut package_file);
+
+    let package = &package_file.package[0];
+    assert!(package.data_type("Speed").is_none());
+    let Some(DataType::ContainerDataType(status)) = package.data_type("Status") else {
+        panic!("no Status");
+    };
+    // the entry named `Speed` keeps its name, its type is renamed
+    let layout = Layouts::new(&[&package_file])
+        .container(package, status)
+        .unwrap();
+    let fields: Vec<_> = layout
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.type_.as_deref()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("Mode", Some("Pump/Mode")),
+            ("", None),
+            ("Speed", Some("Pump/Velocity")),
+            ("Check", Some("Pump/Velocity")),
+        ]
+    );
+}
+
+#[test]
+fn test_prune() {
+    /// remove the padding entries of the containers
+    struct Unpad;
+
+    impl VisitorMut for Unpad {
+        fn visit_entry_list_mut(&mut self, path: &Path, list: &mut EntryList) {
+            list.entries
+                .retain(|e| !matches!(e, EntryElement::PaddingEntry(_)));
+            walk_entry_list_mut(self, path, list)
+        }
+    }
+
+    let mut package_file = resolved(PACKAGE);
+    Unpad.visit_package_file_mut(&Path::default(), &mut package_file);
+    let Some(DataType::ContainerDataType(packet)) = package_file.package[0].data_type("Packet")
+    else {
+        panic!("no Packet");
+    };
+    let entries = &packet.entry_list.as_ref().unwrap().entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name(), "Mode");
+}
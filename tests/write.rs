@@ -0,0 +1,174 @@
+//! Writing raw models back to SEDS XML
+mod common;
+
+use common::{get_mission_params, open_file};
+use seds_rs::eds::raw::{DataSheet, PackageFile};
+use seds_rs::parse::{FromXml, Parser};
+use seds_rs::validate::{Schema, Validator};
+use seds_rs::write::{to_xml, Document, WriteError, Writer};
+use walkdir::{DirEntry, WalkDir};
+
+/// directory of the files the test files include
+const INCLUDE_PATH: &str = "eds/test/include/lib";
+
+/// the files that include each other, they do not parse
+const INCLUDE_CYCLES: [&str; 2] = [
+    "eds/test/include/cycle.xml",
+    "eds/test/include/cycle_package.xml",
+];
+
+/// the files that are not valid against the SEDS schema before they are written: units of a
+/// user-defined DoT, and metadata values calculated as 3.4.9 allows
+const INVALID: [&str; 2] = [
+    "eds/test/dot/heater.xml",
+    "eds/test/test_calculated_metadata.xml",
+];
+
+/// the files that do not flatten: a parameter that is not in the mission parameters, and a
+/// range of `2 ^ 64` that overflows
+const UNFLATTENED: [&str; 2] = ["eds/test/test_container.xml", "eds/basic/datatypeset.xml"];
+
+/// filter for determining eds
+fn is_xml(entry: &DirEntry) -> bool {
+    entry.file_name().to_string_lossy().ends_with(".xml")
+}
+
+/// the document reads back to the same model once written, and the written and the flattened
+/// documents are valid against the SEDS schema
+fn round_trip<T>(schema: &Schema, path: &str, contents: &str)
+where
+    T: FromXml + Document + PartialEq + std::fmt::Debug,
+{
+    let parser = Parser::new(path).include_path(INCLUDE_PATH);
+    let parsed = match parser.parse::<T>(contents) {
+        Ok(parsed) => parsed,
+        Err(_) if INCLUDE_CYCLES.contains(&path) => return,
+        Err(e) => panic!("{}: {}", path, e),
+    };
+    assert!(!INCLUDE_CYCLES.contains(&path), "{} parsed", path);
+
+    let written = to_xml(&parsed.value).unwrap();
+    let reparsed = Parser::new(path).parse::<T>(&written).unwrap();
+    assert_eq!(reparsed.value, parsed.value, "{}", path);
+    // the schema types of numbers and enumerations do not allow parameters, like
+    // `${CFE_MISSION/ES_MAX_APPLICATIONS}`, until the document is flattened
+    let valid = !INVALID.contains(&path);
+    if valid && !written.contains("${") {
+        let result = Validator::new(schema, path).validate(&written);
+        assert!(result.is_ok(), "{}: {:?}", path, result);
+    }
+
+    let ectx = get_mission_params();
+    let flattened = match Writer::new().flatten(&ectx).write_string(&parsed.value) {
+        Ok(flattened) => flattened,
+        Err(_) if UNFLATTENED.contains(&path) => return,
+        Err(e) => panic!("{}: {}", path, e),
+    };
+    assert!(!UNFLATTENED.contains(&path), "{} flattened", path);
+    let _ = Parser::new(path).parse::<T>(&flattened).unwrap();
+    if valid {
+        let result = Validator::new(schema, path).validate(&flattened);
+        assert!(result.is_ok(), "{} flattened: {:?}", path, result);
+    }
+}
+
+/// every file of a directory reads back to the same model once written
+fn round_trip_directory(directory_name: &str) {
+    let schema = Schema::seds().unwrap();
+    let walker = WalkDir::new(directory_name).into_iter();
+    for entry in walker.filter_map(Result::ok).filter(is_xml) {
+        let path = entry.path().display().to_string();
+        let contents = open_file(&path);
+        if contents.contains("<DataSheet") {
+            round_trip::<DataSheet>(&schema, &path, &contents);
+        } else {
+            round_trip::<PackageFile>(&schema, &path, &contents);
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    round_trip_directory("eds/basic");
+    round_trip_directory("eds/test");
+    round_trip_directory("eds/SEDSDoTForSANA");
+}
+
+#[test]
+fn test_round_trip_datasheet() {
+    let path = "eds/test/test_datasheet.xml";
+    let parsed = Parser::new(path)
+        .parse::<DataSheet>(&open_file(path))
+        .unwrap();
+    let written = to_xml(&parsed.value).unwrap();
+    assert!(written.contains("<DataSheet xmlns=\"http://www.ccsds.org/schema/sois/seds\""));
+    assert!(written.contains("<Device name=\"SimpleDevice\""));
+    let reparsed = Parser::new(path).parse::<DataSheet>(&written).unwrap();
+    assert_eq!(reparsed.value, parsed.value);
+}
+
+#[test]
+fn test_canonical_order() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<PackageFile xmlns="http://www.ccsds.org/schema/sois/seds">
+  <Package name="Pump">
+    <DataTypeSet>
+      <ContainerDataType name="Cmd" baseType="Base">
+        <EntryList>
+          <Entry name="Speed" type="uint8"/>
+        </EntryList>
+        <ConstraintSet>
+          <ValueConstraint entry="Id" value="1"/>
+        </ConstraintSet>
+      </ContainerDataType>
+    </DataTypeSet>
+  </Package>
+</PackageFile>"#;
+    let parsed = Parser::new("order.xml").parse::<PackageFile>(xml).unwrap();
+    let written = to_xml(&parsed.value).unwrap();
+    let constraints = written.find("<ConstraintSet>").unwrap();
+    let entries = written.find("<EntryList>").unwrap();
+    assert!(constraints < entries, "{}", written);
+}
+
+#[test]
+fn test_flatten() {
+    let path = "eds/basic/full.xml";
+    let parsed = Parser::new(path)
+        .parse::<PackageFile>(&open_file(path))
+        .unwrap();
+    let ectx = get_mission_params();
+    let flattened = Writer::new()
+        .flatten(&ectx)
+        .write_string(&parsed.value)
+        .unwrap();
+    assert!(!flattened.contains("${"));
+    assert!(flattened.contains("initialValue=\"6\""));
+    // the flattened file is still a package file
+    let _ = Parser::new(path).parse::<PackageFile>(&flattened).unwrap();
+}
+
+#[test]
+fn test_flatten_unresolved() {
+    let path = "eds/test/test_container.xml";
+    let parsed = Parser::new(path)
+        .parse::<PackageFile>(&open_file(path))
+        .unwrap();
+    let ectx = get_mission_params();
+    let Err(WriteError::Unresolved(errors)) =
+        Writer::new().flatten(&ectx).write_string(&parsed.value)
+    else {
+        panic!("expected unresolved parameters");
+    };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].path(),
+        "Package[SimpleDemo]/DataTypeSet/ContainerDataType[WriteMapInfoCmd]/EntryList/FixedValueEntry[SpacecraftID]@fixedValue"
+    );
+    // the error is located in the source like the errors of the resolver
+    let diagnostic = parsed
+        .source
+        .locate(seds_rs::diagnostic::Diagnostic::from(&errors[0]));
+    assert!(diagnostic.to_string().contains("SPACECRAFT_ID"));
+    assert!(diagnostic.to_string().contains("test_container.xml:"));
+}
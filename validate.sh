@@ -1,20 +1,15 @@
 #! /usr/bin/env bash
-err=0
 
-# Note we are validating only a subset of available SED XMLs,
-# solely because a number of the cFE SEDS is not fully resolved,
-# and the validator requires fully resolved XMLs
-#
-#for f in `find . -name "*.xml"`
-for f in `find . -wholename "*eds/SEDS**.xml"` `find . -wholename "*eds/test**.xml"`
-do
-    xmllint --noout --schema eds/schema/seds.xsd $f
-    if (($? != 0)); then
-        err=$((err + 1))
-    fi
-done
+# The validator requires fully resolved XMLs, so the cFE SEDS are
+# flattened first, substituting their parameters with the test mission
+# parameters. The remaining files are validated as they are.
+cargo run -q --bin seds-codegen -- flatten \
+    -m eds/test/mission_parameters.json \
+    --out-dir target/flattened "eds/cFE/**/*.xml" || exit 1
 
-if ((${err} > 0)); then
+cargo run -q --bin seds-codegen -- validate \
+    "eds/SEDS*/**/*.xml" "eds/test/*.xml" "target/flattened/*.xml"
+if (($? != 0)); then
     echo "There were errors while parsing"
     exit 1
 fi